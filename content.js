@@ -629,6 +629,21 @@ async function submitThreatToBlockchain(domain, confidence, threatType, evidence
                 features = Array.from(window.wasm_bindgen.extract_features(url));
             }
 
+            // model.onnx was trained on the 56 features of feature version 1
+            // (the original features.py). extract_features has since
+            // redefined slots inside those 56, so the model gets the
+            // version-1 vector the extractor keeps for it; any other vector
+            // is skipped rather than cut down to fit.
+            const MODEL_FEATURE_VERSION = 1;
+            const MODEL_INPUTS = 56;
+            const extractor = window.wasmFeatureExtractor || {};
+            const modelFeatures = extractor.legacy_feature_version === MODEL_FEATURE_VERSION
+                ? extractor.extract_legacy_features(url) : null;
+            if (!modelFeatures || modelFeatures.length !== MODEL_INPUTS) {
+                console.warn(`[BV] model.onnx expects feature version ${MODEL_FEATURE_VERSION}, extractor gives ${extractor.legacy_feature_version}; skipping ML layer`);
+                return { mlProb, features };
+            }
+
             // Layer 2: ONNX ML inference
             // Disable threading + JSEP — only basic ort-wasm.wasm or ort-wasm-simd.wasm exist
             ort.env.wasm.numThreads = 1;     // stops ORT from loading *-threaded.jsep.mjs
@@ -662,7 +677,7 @@ async function submitThreatToBlockchain(domain, confidence, threatType, evidence
                     }
                 }
             });
-            const tensor = new ort.Tensor("float32", Float32Array.from(features), [1, 56]);
+            const tensor = new ort.Tensor("float32", Float32Array.from(modelFeatures), [1, MODEL_INPUTS]);
             const results = await session.run({ input: tensor });
 
             // Extract phishing probability from model outputs
@@ -1,9 +1,10 @@
 """
 features.py — Browser Vigilant ML Feature Extractor
 ====================================================
-Extracts exactly 56 float features from a URL string using pure math.
-No network calls. No blacklist lookups. No external data at runtime.
-Feature order MUST stay in sync with wasm-feature/src/lib.rs.
+Extracts a fixed-length float vector (FEATURE_NAMES) from a URL string using pure math.
+No network calls. No blacklist lookups. No external data at runtime beyond
+the public_suffix_list.dat snapshot shipped alongside.
+Feature order MUST stay in sync with wasm-feature/crates/vigilant-core/src/lib.rs.
 
 Math used:
   - Shannon entropy: H = -Σ p(c) · log₂(p(c))
@@ -12,9 +13,11 @@ Math used:
   - UPI VPA regex, n-gram character analysis
 """
 
+import ipaddress
 import math
+import os
 import re
-from urllib.parse import urlparse
+from urllib.parse import unquote, urlparse
 
 # ── Constants (used only for feature COMPUTATION, not runtime lookup) ──────────
 # These determine feature values — they are part of the algorithm,
@@ -36,6 +39,39 @@ SUSPICIOUS_TLDS = {
     "tech","store","shop","ru","cn","vip","win","loan","download",
 }
 
+# Public suffix rules, read from the public_suffix_list.dat snapshot next to
+# this file; gen_psl.py compiles the same file into psl_rules.rs.
+PSL_PATH = os.path.join(os.path.dirname(os.path.abspath(__file__)), "public_suffix_list.dat")
+
+
+def _psl_forms(rule: str) -> list:
+    """`rule` as listed and, when it has non-ASCII labels, punycoded."""
+    ascii_form = ".".join(l if l.isascii() else "xn--" + l.encode("punycode").decode("ascii") for l in rule.split("."))
+    return [rule] if ascii_form == rule else [rule, ascii_form]
+
+
+def load_suffix_rules(path: str = PSL_PATH) -> tuple:
+    """(multi-label suffixes, wildcard parents, exceptions) of a PSL file, ICANN
+    and private sections alike. Single-label rules are implicit: an unlisted
+    TLD is its own suffix. `*.ck` lists `ck` as a wildcard parent, `!www.ck`
+    lists `www.ck` as an exception."""
+    suffixes, wildcards, exceptions = set(), set(), set()
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            rule = line.split()[0].lower() if line.split() else ""
+            if not rule or rule.startswith("//"):
+                continue
+            if rule.startswith("!"):
+                exceptions.update(_psl_forms(rule[1:]))
+            elif rule.startswith("*."):
+                wildcards.update(_psl_forms(rule[2:]))
+            elif "." in rule:
+                suffixes.update(_psl_forms(rule))
+    return suffixes, wildcards, exceptions
+
+
+MULTI_LABEL_SUFFIXES, WILDCARD_SUFFIXES, SUFFIX_EXCEPTIONS = load_suffix_rules()
+
 LEGIT_UPI_HANDLES = {
     "okaxis","okicici","oksbi","okhdfcbank","ybl","ibl","axl","apl","fbl",
     "upi","paytm","waaxis","waxis","rajgovhdfcbank","barodampay","allbank",
@@ -51,10 +87,17 @@ SHORT_URL_SERVICES = {
     "bitly.com","shorte.st","t2m.io",
 }
 
-DANGEROUS_EXTENSIONS = {
-    "exe","scr","bat","cmd","ps1","vbs","wsf","hta","jar","msi","msp",
-    "reg","dll","pif","com","cpl","inf","apk","ipa","dmg","pkg","deb","rpm",
-}
+# Extension classes in one-hot order, as extensions.rs lists them; a path
+# with no listed extension is the sixth, none.
+EXTENSION_CLASSES = [
+    ("document",   {"pdf","doc","docx","docm","xls","xlsx","xlsm","ppt","pptx","odt","rtf","txt","csv"}),
+    ("media",      {"jpg","jpeg","png","gif","webp","svg","ico","mp3","mp4","webm","avi","mov","wav"}),
+    ("script",     {"js","mjs","vbs","vbe","wsf","ps1","bat","cmd","sh","hta","jse","py"}),
+    ("executable", {"exe","scr","msi","msp","dll","pif","com","cpl","jar","apk","ipa","dmg","pkg","deb","rpm","appimage"}),
+    ("archive",    {"zip","rar","7z","tar","gz","tgz","bz2","xz","iso","img","cab"}),
+]
+
+SERVER_SCRIPT_EXTS = {"php","php5","phtml","asp","aspx","jsp","cgi","pl"}
 
 # ── Math helpers ───────────────────────────────────────────────────────────────
 
@@ -69,25 +112,27 @@ def shannon_entropy(s: str) -> float:
     return -sum((f / n) * math.log2(f / n) for f in freq.values())
 
 
-def levenshtein(a: str, b: str) -> int:
-    """Wagner-Fischer algorithm — O(min(m,n)) space."""
-    if len(a) < len(b):
-        a, b = b, a
+def damerau(a: str, b: str) -> int:
+    """Optimal string alignment distance: a swap of two adjacent letters is one edit."""
     m, n = len(a), len(b)
-    prev = list(range(n + 1))
+    d = [[0] * (n + 1) for _ in range(m + 1)]
+    for i in range(m + 1):
+        d[i][0] = i
+    for j in range(n + 1):
+        d[0][j] = j
     for i in range(1, m + 1):
-        curr = [i] + [0] * n
         for j in range(1, n + 1):
             cost = 0 if a[i - 1] == b[j - 1] else 1
-            curr[j] = min(prev[j] + 1, curr[j - 1] + 1, prev[j - 1] + cost)
-        prev = curr
-    return prev[n]
+            d[i][j] = min(d[i - 1][j] + 1, d[i][j - 1] + 1, d[i - 1][j - 1] + cost)
+            if i > 1 and j > 1 and a[i - 1] == b[j - 2] and a[i - 2] == b[j - 1]:
+                d[i][j] = min(d[i][j], d[i - 2][j - 2] + 1)
+    return d[m][n]
 
 
 def min_brand_distance(domain: str) -> int:
-    """Minimum Levenshtein distance from domain core to any known brand."""
+    """Minimum Damerau distance from domain core to any known brand."""
     core = domain.split(".")[0].lower()
-    return min(levenshtein(core, b) for b in BRANDS)
+    return min(damerau(core, b) for b in BRANDS)
 
 
 def max_consecutive_consonants(s: str) -> int:
@@ -115,17 +160,207 @@ def char_ngram_entropy(s: str, n: int = 2) -> float:
     return -sum((c / total) * math.log2(c / total) for c in freq.values())
 
 
+def public_suffix(host: str) -> str:
+    """The prevailing PSL rule's suffix: an exception rule drops its first
+    label, otherwise the longest listed or wildcard match, otherwise the
+    last label — the same walk as psl.rs."""
+    labels = host.rstrip(".").split(".")
+    for take in range(len(labels), 1, -1):
+        cand = ".".join(labels[-take:])
+        if cand in SUFFIX_EXCEPTIONS:
+            return ".".join(labels[-take + 1:])
+    for take in range(len(labels), 1, -1):
+        cand = ".".join(labels[-take:])
+        if cand in MULTI_LABEL_SUFFIXES or ".".join(labels[-take + 1:]) in WILDCARD_SUFFIXES:
+            return cand
+    return labels[-1]
+
+
+def registrable_domain(host: str) -> str:
+    host = host.rstrip(".")
+    suffix = public_suffix(host)
+    if len(host) <= len(suffix):
+        return host
+    head = host[:-len(suffix) - 1]
+    return head.rsplit(".", 1)[-1] + "." + suffix
+
+
+def _ipv4_part(part: str):
+    if part[:2].lower() == "0x":
+        digits, base = part[2:], 16
+    elif len(part) > 1 and part[0] == "0":
+        digits, base = part[1:], 8
+    else:
+        digits, base = part, 10
+    if not part or not all(c in "0123456789abcdef"[:base] for c in digits.lower()):
+        return None
+    return int(digits, base) if digits else 0
+
+
+def is_ip_host(host: str) -> bool:
+    """Dotted quad, 1-3 part, octal and hex IPv4 as browsers read them, or bracketed IPv6."""
+    if host.startswith("[") and host.endswith("]"):
+        try:
+            ipaddress.IPv6Address(host[1:-1])
+            return True
+        except ValueError:
+            return False
+    parts = [_ipv4_part(p) for p in (host[:-1] if host.endswith(".") else host).split(".")]
+    if None in parts or len(parts) > 4:
+        return False
+    *head, last = parts
+    return all(n <= 255 for n in head) and last < 1 << (8 * (5 - len(parts)))
+
+
+SCHEME_CLASSES = ["https", "http", "ftp", "data", "javascript", "blob", "mailto", "intent", "upi"]
+
+
+def scheme_of(url: str) -> str:
+    """Lowercased scheme the text starts with ("blob" for blob:https://…),
+    or "" — read from the raw text like scheme.rs, since urlparse only
+    splits scheme://-style URLs reliably."""
+    head, sep, _ = url.lstrip().partition(":")
+    valid = sep and head[:1].isascii() and head[:1].isalpha() \
+        and all(c.isascii() and (c.isalnum() or c in "+-.") for c in head)
+    return head.lower() if valid else ""
+
+
+def scheme_class(url: str) -> list:
+    """[https, http, ftp, data, javascript, blob, mailto, intent, upi, other]
+    one-hot on the URL's scheme; anything unlisted, or none, is other."""
+    f = [0.0] * (len(SCHEME_CLASSES) + 1)
+    s = scheme_of(url)
+    f[SCHEME_CLASSES.index(s) if s in SCHEME_CLASSES else len(SCHEME_CLASSES)] = 1.0
+    return f
+
+
+# javascript: payload lists, as schemepayload.rs keeps them.
+OBFUSCATION_CALLS = ["eval(", "atob(", "unescape(", "fromcharcode(", "decodeuricomponent(", "settimeout(", "setinterval(", "newfunction("]
+PAGE_WRITES = ["document.write(", "document.writeln(", "innerhtml=", "outerhtml=", "document.open("]
+NAVIGATIONS = ["location=", "location.href=", "location.replace(", "location.assign(", "window.open(", ".submit()", "fetch(", "sendbeacon("]
+CREDENTIAL_WORDS = [
+    "password", "passwd", "passcode", "type=\"password\"", "type='password'", "enter otp", "otp code", "one-time password", "cvv", "card number", "cardnumber", "username",
+    "sign in", "signin", "login", "verify your", "account number", "upi pin", "atm pin", "aadhaar", "seed phrase", "recovery phrase",
+]
+MAX_ATOB_ROUNDS = 3
+_ATOB = re.compile(r"atob\(", re.I | re.A)
+_B64 = {c: i for i, c in enumerate(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")}
+_B64.update({ord("-"): 62, ord("_"): 63})
+
+
+def base64_decode(s: str):
+    """Standard or URL-safe base64, padding optional, ASCII whitespace
+    skipped; None on any other byte — datauri.rs's base64_decode."""
+    out, acc, bits = bytearray(), 0, 0
+    for b in s.encode():
+        if b in b" \t\n\x0c\r":
+            continue
+        if b == ord("="):
+            break
+        if b not in _B64:
+            return None
+        acc, bits = (acc << 6) | _B64[b], bits + 6
+        if bits >= 8:
+            bits -= 8
+            out.append((acc >> bits) & 0xFF)
+    return None if bits >= 6 else bytes(out)
+
+
+def atob_literals(code: str) -> list:
+    """Base64 string literals passed to atob in code, decoded."""
+    out = []
+    while (m := _ATOB.search(code)):
+        code = code[m.end():].lstrip()
+        if code[:1] not in ("'", '"', "`"):
+            continue
+        end = code.find(code[0], 1)
+        if end < 0:
+            break
+        raw = base64_decode(code[1:end])
+        if raw is not None:
+            out.append(raw.decode("utf-8", "replace"))
+        code = code[end:]
+    return out
+
+
+def scheme_risk(url: str) -> list:
+    """[js_obfuscation_calls, js_writes_page, js_credential_prompt,
+    js_navigates, blob_opaque_origin] — schemepayload.rs's
+    scheme_risk_features: a javascript: URL's script, percent-decoded with
+    its atob literals, and a blob: URL's origin. All 0 for other schemes."""
+    url = "".join(c for c in url.strip("".join(map(chr, range(33)))) if c not in "\t\n\r")
+    scheme = scheme_of(url)
+    body = url[len(scheme) + 1:]
+    f = [0.0] * 5
+    if scheme == "javascript":
+        code = decode_fully(body)[0]
+        texts, round_ = [code], atob_literals(code)
+        for _ in range(MAX_ATOB_ROUNDS):
+            if not round_:
+                break
+            texts += round_
+            round_ = [d for r in round_ for d in atob_literals(r)]
+        text = "\n".join(texts).lower()
+        squashed = text.replace(" ", "").replace("\t", "")
+        f[0] = float(sum(1 for c in OBFUSCATION_CALLS if c in squashed))
+        f[1] = 1.0 if any(w in squashed for w in PAGE_WRITES) else 0.0
+        f[2] = 1.0 if any(w in text for w in CREDENTIAL_WORDS) else 0.0
+        f[3] = 1.0 if any(n in squashed for n in NAVIGATIONS) else 0.0
+    elif scheme == "blob":
+        origin = body.rsplit("/", 1)[0].lower()
+        f[4] = 1.0 if origin in ("null", "") or origin.startswith("data:") else 0.0
+    return f
+
+
+def extension_class(p: dict) -> list:
+    """[ext_document, ext_media, ext_script, ext_executable, ext_archive,
+    ext_none, server_script, server_script_odd_host] — the last path
+    segment's extension class one-hot, and a server-side script extension,
+    also when served from an IP, a non-default port or a suspicious TLD."""
+    last = p["path"].lower().rsplit("/", 1)[-1]
+    ext = last.rsplit(".", 1)[1] if "." in last else ""
+    f = [0.0] * 8
+    f[next((i for i, (_, exts) in enumerate(EXTENSION_CLASSES) if ext in exts), len(EXTENSION_CLASSES))] = 1.0
+    server = ext in SERVER_SCRIPT_EXTS
+    default_port = {"http": 80, "https": 443}.get(p["scheme"])
+    odd_port = p["port"] is not None and p["port"] != default_port
+    odd_host = is_ip_host(p["host"]) or odd_port or p["tld"] in SUSPICIOUS_TLDS
+    f[6] = 1.0 if server else 0.0
+    f[7] = 1.0 if server and odd_host else 0.0
+    return f
+
+
+MAX_DECODE_DEPTH = 8
+
+
+def decode_fully(s: str) -> tuple:
+    """(s percent-decoded until a pass changes nothing, passes that changed
+    it), at most MAX_DECODE_DEPTH passes — encoding.rs's decode_fully.
+    Invalid escapes stay literal; invalid UTF-8 becomes U+FFFD."""
+    depth = 0
+    while depth < MAX_DECODE_DEPTH:
+        nxt = unquote(s, errors="replace")
+        if nxt == s:
+            break
+        s, depth = nxt, depth + 1
+    return s, depth
+
+
 def parse_url_parts(url: str) -> dict:
     try:
         p = urlparse(url)
         netloc    = p.netloc.lower()
         host_port = netloc.split("@")[-1]
-        host, _, port_str = host_port.partition(":")
+        if host_port.startswith("["):
+            end = host_port.find("]") + 1
+            host, port_str = host_port[:end], host_port[end + 1:]
+        else:
+            host, _, port_str = host_port.partition(":")
         port = int(port_str) if port_str.isdigit() else None
         labels = host.split(".")
         tld    = labels[-1] if labels else ""
-        reg    = ".".join(labels[-2:]) if len(labels) >= 2 else host
-        sub    = ".".join(labels[:-2]) if len(labels) > 2 else ""
+        reg    = host if is_ip_host(host) else registrable_domain(host)
+        sub    = host[:-len(reg)].rstrip(".") if host.endswith(reg) else ""
         return dict(scheme=p.scheme.lower(), host=host, path=p.path,
                     query=p.query, fragment=p.fragment, port=port,
                     tld=tld, registered_domain=reg, subdomain=sub,
@@ -136,13 +371,13 @@ def parse_url_parts(url: str) -> dict:
                     labels=[url])
 
 
-# ── Main extractor — 56 features ──────────────────────────────────────────────
+# ── Main extractor ──────────────────────────────────────────────────────────────
 
 def extract_features(url: str) -> list:
     """
-    Returns list[float] of exactly 56 features extracted purely from the URL
+    Returns list[float] of len(FEATURE_NAMES) features extracted purely from the URL
     string using mathematical operations. No network calls, no lookups.
-    Feature order must match wasm-feature/src/lib.rs.
+    Feature order must match wasm-feature/crates/vigilant-core/src/lib.rs.
     """
     p   = parse_url_parts(url)
     host   = p["host"]
@@ -152,7 +387,7 @@ def extract_features(url: str) -> list:
     domain = p["registered_domain"]
     sub    = p["subdomain"]
     low    = url.lower()
-    f = [0.0] * 56
+    f = [0.0] * len(FEATURE_NAMES)
 
     # ── GROUP A: Lexical Structure (F0–F15) ────────────────────────────────────
     f[0]  = float(len(url))
@@ -168,10 +403,11 @@ def extract_features(url: str) -> list:
     digits = sum(1 for c in url if c.isdigit())
     f[9]  = float(digits)
     f[10] = digits / max(len(url), 1)                                   # digit ratio
-    f[11] = 1.0 if p["scheme"] == "https" else 0.0                      # HTTPS flag
-    f[12] = 1.0 if re.search(r"\b(?:\d{1,3}\.){3}\d{1,3}\b", host) else 0.0  # IP-in-URL
+    scheme = scheme_class(url)
+    f[11] = scheme[0]                                                   # HTTPS scheme
+    f[12] = 1.0 if is_ip_host(host) else 0.0              # IP-in-URL, any encoding
     f[13] = 1.0 if "xn--" in host else 0.0                              # Punycode
-    f[14] = float(max(len(p["labels"]) - 2, 0))                         # subdomain depth
+    f[14] = float(len(sub.split("."))) if sub else 0.0                  # subdomain depth
     f[15] = 1.0 if (p["port"] is not None and
                     p["port"] not in (80, 443, 8080, 8443)) else 0.0    # port anomaly
 
@@ -196,13 +432,16 @@ def extract_features(url: str) -> list:
     pay_kw    = {"pay","payment","wallet","upi","gpay","paytm","bhim","razorpay","phonepay"}
     free_kw   = {"free","bonus","prize","winner","giveaway","reward","claim","gift","lucky","congratulations"}
     fraud_kw  = {"kyc","refund","tax","block","suspend","urgent","helpdesk","support","care","alert"}
-    f[24] = 1.0 if any(k in low for k in login_kw) else 0.0
-    f[25] = 1.0 if any(k in host for k in trust_kw) else 0.0
-    f[26] = 1.0 if any(k in low for k in pay_kw) else 0.0
-    f[27] = 1.0 if any(k in low for k in free_kw) else 0.0
-    f[28] = 1.0 if any(k in low for k in fraud_kw) else 0.0
+    # on the fully decoded text, so %256c%256f%2567%2569%256e reads as login
+    decoded      = decode_fully(url)[0].lower()
+    decoded_host = decode_fully(host)[0].lower()
+    f[24] = 1.0 if any(k in decoded for k in login_kw) else 0.0
+    f[25] = 1.0 if any(k in decoded_host for k in trust_kw) else 0.0
+    f[26] = 1.0 if any(k in decoded for k in pay_kw) else 0.0
+    f[27] = 1.0 if any(k in decoded for k in free_kw) else 0.0
+    f[28] = 1.0 if any(k in decoded for k in fraud_kw) else 0.0
     all_kw = login_kw | trust_kw | pay_kw | free_kw | fraud_kw
-    hits = sum(1 for k in all_kw if k in low)
+    hits = sum(1 for k in all_kw if k in decoded)
     f[29] = min(hits / 6.0, 1.0)                    # keyword density score
     f[30] = 1.0 if "-" in host else 0.0             # hyphen in domain flag
 
@@ -215,7 +454,7 @@ def extract_features(url: str) -> list:
     f[34] = float(len(query.split("&")) if query else 0)  # query param count
     f[35] = 1.0 if p["fragment"] else 0.0           # fragment presence
     f[36] = 1.0 if low.startswith("data:") else 0.0 # data: URI
-    f[37] = 1.0 if (".." in path or "%2e%2e" in low) else 0.0  # path traversal
+    f[37] = 1.0 if (".." in decode_fully(path)[0] or "%2e%2e" in low) else 0.0  # path traversal, decoded
 
     # ── GROUP F: Domain Quality (F38–F47) ──────────────────────────────────────
     f[38] = 1.0 if tld in SUSPICIOUS_TLDS else 0.0  # suspicious TLD
@@ -246,9 +485,8 @@ def extract_features(url: str) -> list:
     f[50] = 1.0 if re.search(r"upi://pay|pa=.*@|vpa=", low) else 0.0  # UPI collect request
 
     # ── GROUP H: File & Extension Risk (F51–F55) ───────────────────────────────
-    ext_m = re.search(r"\.([a-zA-Z0-9]{1,5})(?:[?#]|$)", path)
-    ext = ext_m.group(1).lower() if ext_m else ""
-    f[51] = 1.0 if ext in DANGEROUS_EXTENSIONS else 0.0   # dangerous extension
+    ext = extension_class(p)
+    f[51] = ext[3]                                   # executable extension
     f[52] = 1.0 if re.search(r"/(wp-admin|admin|phpmyadmin|cgi-bin)/", low) else 0.0  # admin path
     f[53] = 1.0 if re.search(r"(redirect|returnurl|continue|next|goto|url)=http", low, re.I) else 0.0  # open redirect
     # Repeated char ratio (e.g. "aaaa" in domain = anomalous)
@@ -256,6 +494,16 @@ def extract_features(url: str) -> list:
     f[54] = max_rep / max(len(host), 1)              # max char repeat ratio
     f[55] = 1.0 if re.search(r"[a-f0-9]{32,}", low) else 0.0  # MD5/hex token in URL
 
+    # ── GROUP I: Extension Class (F56–F62) ─────────────────────────────────────
+    f[56:59] = ext[:3]                               # document, media, script
+    f[59:63] = ext[4:]                               # archive, none, server script (odd host)
+
+    # ── GROUP J: Scheme Class (F63–F71) ────────────────────────────────────────
+    f[63:72] = scheme[1:]                            # http … upi, other
+
+    # ── GROUP K: Scheme Payload Risk (F72–F76) ─────────────────────────────────
+    f[72:77] = scheme_risk(url)                      # javascript: script, blob: origin
+
     return f
 
 
@@ -263,7 +511,7 @@ FEATURE_NAMES = [
     # Group A
     "url_length", "domain_length", "path_length", "query_length",
     "dot_count", "hyphen_count", "underscore_count", "slash_count",
-    "at_count", "digit_count", "digit_ratio", "is_https",
+    "at_count", "digit_count", "digit_ratio", "scheme_https",
     "ip_in_url", "is_punycode", "subdomain_depth", "port_anomaly",
     # Group B
     "url_entropy", "domain_entropy", "path_entropy",
@@ -283,8 +531,56 @@ FEATURE_NAMES = [
     # Group G
     "upi_vpa_present", "suspicious_upi_vpa", "upi_collect_request",
     # Group H
-    "dangerous_extension", "admin_path", "open_redirect",
+    "ext_executable", "admin_path", "open_redirect",
     "max_char_repeat_ratio", "hex_token_in_url",
+    # Group I
+    "ext_document", "ext_media", "ext_script", "ext_archive", "ext_none",
+    "server_script", "server_script_odd_host",
+    # Group J
+    "scheme_http", "scheme_ftp", "scheme_data", "scheme_javascript",
+    "scheme_blob", "scheme_mailto", "scheme_intent", "scheme_upi", "scheme_other",
+    # Group K
+    "js_obfuscation_calls", "js_writes_page", "js_credential_prompt", "js_navigates",
+    "blob_opaque_origin",
+]
+
+assert len(FEATURE_NAMES) == 77, f"Feature count mismatch: {len(FEATURE_NAMES)}"
+
+# Type of each feature, as vigilant-core's schema::feature_schema reports it:
+# flag (0/1), ratio (0–1), count, length or bits of entropy.
+FEATURE_TYPES = [
+    # Group A
+    "length", "count", "length", "length", "count", "count", "count", "count",
+    "count", "length", "ratio", "flag", "flag", "flag", "count", "flag",
+    # Group B
+    "bits", "bits", "bits", "bits", "bits",
+    # Group C
+    "flag", "ratio", "flag",
+    # Group D
+    "flag", "flag", "flag", "flag", "flag", "ratio", "flag",
+    # Group E
+    "flag", "ratio", "ratio", "count", "flag", "flag", "flag",
+    # Group F
+    "flag", "count", "flag", "flag", "ratio", "ratio", "count", "flag", "flag", "count",
+    # Group G
+    "flag", "flag", "flag",
+    # Group H
+    "flag", "flag", "flag", "ratio", "flag",
+    # Group I
+    "flag", "flag", "flag", "flag", "flag", "flag", "flag",
+    # Group J
+    "flag", "flag", "flag", "flag", "flag", "flag", "flag", "flag", "flag",
+    # Group K
+    "count", "flag", "flag", "flag", "flag",
 ]
 
-assert len(FEATURE_NAMES) == 56, f"Feature count mismatch: {len(FEATURE_NAMES)}"
+assert len(FEATURE_TYPES) == len(FEATURE_NAMES), f"Feature type count mismatch: {len(FEATURE_TYPES)}"
+
+
+def feature_schema_hash(feature_version: int) -> str:
+    """Same hash as vigilant-core's feature_schema_hash(): a model container
+    carrying another one is refused at load, so drift between this file and
+    the Rust extractor surfaces before any URL is scored."""
+    import hashlib
+    lines = [str(feature_version)] + [f"{n}:{t}" for n, t in zip(FEATURE_NAMES, FEATURE_TYPES)]
+    return hashlib.sha256("\n".join(lines).encode()).hexdigest()[:16]
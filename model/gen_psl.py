@@ -0,0 +1,97 @@
+"""
+gen_psl.py — public suffix tables for vigilant-core's registrable-domain split
+==============================================================================
+Compiles public_suffix_list.dat (ICANN and private sections, `*` wildcard
+and `!` exception rules included) into the table between the markers in
+wasm-feature/crates/vigilant-core/src/psl.rs. features.py reads the same
+file, so training and the extension split hosts alike.
+
+OFFLINE ONLY — replace public_suffix_list.dat with a fresh copy from
+https://publicsuffix.org/list/public_suffix_list.dat, run this, then update
+the suffix digests in selftest.rs; the failing `table:public_suffixes`,
+`table:suffix_wildcards` and `table:suffix_exceptions` self-test checks
+print the new ones.
+
+Usage:
+    python gen_psl.py                       # snapshot date from the list's VERSION line
+    python gen_psl.py --snapshot 2023-02-09 # for lists without one
+    python gen_psl.py --dry-run             # print the table only
+"""
+
+import argparse
+import hashlib
+import os
+import re
+import sys
+
+from features import PSL_PATH, load_suffix_rules
+
+PSL_RS = os.path.join(os.path.dirname(__file__), "..", "wasm-feature", "crates", "vigilant-core", "src", "psl.rs")
+BEGIN, END = "// BEGIN PSL TABLE", "// END PSL TABLE"
+WIDTH = 116
+
+
+def rust_list(name: str, doc: list, items: set) -> list:
+    lines = [f"/// {d}" for d in doc] + [f"pub(crate) const {name}: &[&str] = &["]
+    row = "   "
+    for item in sorted(items):
+        cell = f' "{item}",'
+        if len(row) + len(cell) > WIDTH:
+            lines.append(row)
+            row = "   "
+        row += cell
+    if row.strip():
+        lines.append(row)
+    return lines + ["];"]
+
+
+def render(rules: tuple, snapshot: str, digest: str) -> str:
+    suffixes, wildcards, exceptions = rules
+    lines = [BEGIN, f"// generated from public_suffix_list.dat (sha256 {digest[:16]}) by model/gen_psl.py", ""]
+    lines += rust_list("MULTI_LABEL_SUFFIXES", [
+        "Public suffixes with more than one label. Single-label TLDs are implicit —",
+        "anything not listed here falls back to \"last label is the suffix\".",
+    ], suffixes) + [""]
+    lines += rust_list("WILDCARD_SUFFIXES", [
+        "Names under which every label is itself a suffix (`*.ck` lists `ck`).",
+    ], wildcards) + [""]
+    lines += rust_list("SUFFIX_EXCEPTIONS", [
+        "Names a wildcard rule would make a suffix but that are registrable (`!www.ck`).",
+    ], exceptions) + [""]
+    lines += [
+        "/// Date of the publicsuffix.org list the tables were generated from.",
+        f'pub(crate) const PSL_SNAPSHOT: &str = "{snapshot}";',
+        END,
+    ]
+    return "\n".join(lines)
+
+
+def main():
+    ap = argparse.ArgumentParser(description=__doc__, formatter_class=argparse.RawDescriptionHelpFormatter)
+    ap.add_argument("--snapshot", help="date of the list, YYYY-MM-DD, when it has no VERSION line")
+    ap.add_argument("--dry-run", action="store_true", help="print instead of rewriting psl.rs")
+    args = ap.parse_args()
+
+    with open(PSL_PATH, "rb") as f:
+        raw = f.read()
+    version = re.search(rb"^// VERSION: (\d{4}-\d{2}-\d{2})", raw, re.M)
+    snapshot = args.snapshot or (version.group(1).decode() if version else None)
+    if not snapshot:
+        sys.exit("public_suffix_list.dat has no VERSION line; pass --snapshot")
+    rules = load_suffix_rules()
+    print(f"{len(rules[0])} suffixes, {len(rules[1])} wildcards, {len(rules[2])} exceptions", file=sys.stderr)
+
+    block = render(rules, snapshot, hashlib.sha256(raw).hexdigest())
+    if args.dry_run:
+        print(block)
+        return
+    with open(PSL_RS, encoding="utf-8") as f:
+        src = f.read()
+    start, end = src.index(BEGIN), src.index(END) + len(END)
+    with open(PSL_RS, "w", encoding="utf-8") as f:
+        f.write(src[:start] + block + src[end:])
+    print(f"wrote {PSL_RS}", file=sys.stderr)
+
+
+if __name__ == "__main__":
+    main()
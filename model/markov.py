@@ -0,0 +1,124 @@
+"""
+markov.py — character bigram table for vigilant-core's DGA features
+====================================================================
+Fits the first-order Markov chain `dga_features` scores registrable labels
+with, and rewrites the table between the markers in
+wasm-feature/crates/vigilant-core/src/dga.rs.
+
+OFFLINE ONLY — run on a developer machine, then update MARKOV_DIGEST in
+selftest.rs; the failing `table:dga_markov` self-test check prints the new one.
+
+Usage:
+    python markov.py                      # fit on the Tranco top 100k (downloads)
+    python markov.py --from domains.txt   # one domain or word per line; Tranco's
+                                          # "rank,domain" CSV lines also work
+    python markov.py --from words.txt --dry-run   # print the table and stats only
+"""
+
+import argparse
+import io
+import math
+import os
+import re
+import sys
+import zipfile
+
+# Must match vigilant-core's dga.rs: start, a–z, any digit, hyphen / a–z,
+# any digit, hyphen, end.
+FROM = "^abcdefghijklmnopqrstuvwxyz0-"
+TO = "abcdefghijklmnopqrstuvwxyz0-$"
+# Costs are -log2(p) in quarter bits, capped to fit a byte.
+COST_SCALE = 4
+MAX_COST = 255
+
+DGA_RS = os.path.join(os.path.dirname(__file__), "..", "wasm-feature", "crates", "vigilant-core", "src", "dga.rs")
+BEGIN, END = "// BEGIN MARKOV TABLE", "// END MARKOV TABLE"
+
+
+def symbol(c: str) -> str:
+    return "0" if c.isdigit() else c
+
+
+def label_of(line: str) -> str:
+    """Registrable label of a domain (or the word itself), lowercased; '' if unusable."""
+    line = line.strip().lower()
+    if "," in line:
+        line = line.split(",", 1)[1]
+    label = line.split(".")[0]
+    if label.startswith("xn--") or not re.fullmatch(r"[a-z0-9-]+", label):
+        return ""
+    return label
+
+
+def fit(labels: list) -> list:
+    """Add-one smoothed transition costs, row per FROM symbol."""
+    counts = [[1] * len(TO) for _ in FROM]
+    for label in labels:
+        seq = "^" + "".join(symbol(c) for c in label) + "$"
+        for a, b in zip(seq, seq[1:]):
+            counts[FROM.index(a)][TO.index(b)] += 1
+    table = []
+    for row in counts:
+        total = sum(row)
+        table.append([min(MAX_COST, round(-math.log2(n / total) * COST_SCALE)) for n in row])
+    return table
+
+
+def bits_per_char(table: list, label: str) -> float:
+    seq = "^" + "".join(symbol(c) for c in label) + "$"
+    costs = [table[FROM.index(a)][TO.index(b)] for a, b in zip(seq, seq[1:])]
+    return sum(costs) / COST_SCALE / len(costs)
+
+
+def render(table: list, source: str) -> str:
+    lines = [BEGIN, f"// fitted on {source} by model/markov.py", f"pub(crate) const MARKOV: [[u8; {len(TO)}]; {len(FROM)}] = ["]
+    for sym, row in zip(FROM, table):
+        lines.append("    [" + ", ".join(f"{c:3d}" for c in row) + f"], // {sym}")
+    lines += ["];", END]
+    return "\n".join(lines)
+
+
+def load_tranco(n: int) -> list:
+    from train import DATASETS, download_bytes
+    raw = download_bytes(DATASETS["tranco"]["url"], DATASETS["tranco"]["description"])
+    if not raw:
+        sys.exit("Tranco download failed; pass --from with a domain list")
+    zf = zipfile.ZipFile(io.BytesIO(raw))
+    name = [x for x in zf.namelist() if x.endswith(".csv")][0]
+    return zf.open(name).read().decode().splitlines()[:n]
+
+
+def main():
+    ap = argparse.ArgumentParser(description=__doc__, formatter_class=argparse.RawDescriptionHelpFormatter)
+    ap.add_argument("--from", dest="source", help="domain or word list, one per line")
+    ap.add_argument("--top", type=int, default=100_000, help="Tranco domains to fit on")
+    ap.add_argument("--dry-run", action="store_true", help="print instead of rewriting dga.rs")
+    args = ap.parse_args()
+
+    if args.source:
+        with open(args.source, encoding="utf-8") as f:
+            lines = f.read().splitlines()
+        source = os.path.basename(args.source)
+    else:
+        lines = load_tranco(args.top)
+        source = f"the Tranco top {args.top}"
+    labels = sorted({l for l in map(label_of, lines) if len(l) >= 2})
+    table = fit(labels)
+    scores = sorted(bits_per_char(table, l) for l in labels)
+    pct = lambda q: scores[min(len(scores) - 1, int(q * len(scores)))]
+    print(f"{len(labels)} labels; bits/char p50 {pct(0.5):.2f}, p95 {pct(0.95):.2f}, p99 {pct(0.99):.2f}", file=sys.stderr)
+
+    block = render(table, f"{len(labels)} labels from {source}")
+    if args.dry_run:
+        print(block)
+        return
+    with open(DGA_RS, encoding="utf-8") as f:
+        src = f.read()
+    start, end = src.index(BEGIN), src.index(END) + len(END)
+    with open(DGA_RS, "w", encoding="utf-8") as f:
+        f.write(src[:start] + block + src[end:])
+    print(f"wrote {DGA_RS}", file=sys.stderr)
+
+
+if __name__ == "__main__":
+    main()
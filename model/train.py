@@ -1,7 +1,7 @@
 """
 train.py — Browser Vigilant ML Training Pipeline v2.0
 ======================================================
-Downloads real phishing + legitimate URL datasets, extracts 56 math features,
+Downloads real phishing + legitimate URL datasets, extracts the math features,
 trains RF + XGBoost soft-vote ensemble with SMOTE + Platt calibration,
 evaluates with 10-fold stratified CV, exports model.onnx.
 
@@ -27,7 +27,7 @@ import requests
 
 warnings.filterwarnings("ignore")
 
-from features import extract_features, FEATURE_NAMES
+from features import extract_features, feature_schema_hash, FEATURE_NAMES
 
 from sklearn.ensemble import RandomForestClassifier, VotingClassifier, GradientBoostingClassifier
 from sklearn.calibration import CalibratedClassifierCV
@@ -39,7 +39,7 @@ from skl2onnx.common.data_types import FloatTensorType
 import onnx
 
 
-N_FEATURES = 56
+N_FEATURES = len(FEATURE_NAMES)
 
 # ── Dataset Sources ────────────────────────────────────────────────────────────
 
@@ -259,7 +259,7 @@ FALLBACK_PHISHING = [
 # ── Feature Extraction ────────────────────────────────────────────────────────
 
 def extract_all(urls: list, labels: list, desc: str = "Extracting features") -> tuple:
-    """Extract 56 features from each URL. Skip on error."""
+    """Extract the N_FEATURES features from each URL. Skip on error."""
     X, y, skipped = [], [], 0
     for url, label in tqdm(zip(urls, labels), total=len(urls), desc=desc):
         try:
@@ -394,6 +394,11 @@ def export_onnx(model, output_path: str = "model.onnx"):
             options={"zipmap": False},
             target_opset=17,
         )
+        # lets vigilant-core's load_onnx_model refuse a model for another vector
+        onnx.helper.set_model_props(onnx_model, {
+            "feature_version": str(FEATURE_VERSION),
+            "feature_schema": feature_schema_hash(FEATURE_VERSION),
+        })
         with open(output_path, "wb") as f:
             f.write(onnx_model.SerializeToString())
         size_kb = os.path.getsize(output_path) / 1024
@@ -424,6 +429,42 @@ def export_onnx(model, output_path: str = "model.onnx"):
         print(f"  [WARN] ONNX verification failed: {e}")
 
 
+# ── Tree Export (in-WASM scoring) ─────────────────────────────────────────────
+
+FEATURE_VERSION = 9   # must match vigilant-core's model::FEATURE_VERSION
+
+def export_trees(model, X: np.ndarray, output_path: str = "model.bvmd", license: str = "UNLICENSED"):
+    """Write the forest as a "trees" model container that vigilant-core
+    evaluates itself (see trees.rs for the payload layout)."""
+    import json, struct, hashlib, datetime
+    print(f"\n── Exporting trees ──────────────────────────────────────────────")
+    payload = bytearray(struct.pack("<BfI", 1, 0.0, len(model.estimators_)))
+    for est in model.estimators_:
+        t = est.tree_
+        payload += struct.pack("<I", t.node_count)
+        for i in range(t.node_count):
+            if t.children_left[i] == -1:
+                counts = t.value[i][0]
+                payload += struct.pack("<HfII", 0xFFFF, float(counts[1] / max(counts.sum(), 1e-12)), 0, 0)
+            else:
+                payload += struct.pack("<HfII", int(t.feature[i]), float(t.threshold[i]),
+                                       int(t.children_left[i]), int(t.children_right[i]))
+    header = json.dumps({
+        "container_version": 1,
+        "kind": "trees",
+        "trained_at": datetime.date.today().isoformat(),
+        "dataset_hash": hashlib.sha256(np.ascontiguousarray(X).tobytes()).hexdigest(),
+        "feature_version": FEATURE_VERSION,
+        "feature_count": N_FEATURES,
+        "feature_schema": feature_schema_hash(FEATURE_VERSION),
+        "license": license,
+    }).encode()
+    with open(output_path, "wb") as f:
+        f.write(b"BVMD" + struct.pack("<I", len(header)) + header + bytes(payload))
+    size_kb = os.path.getsize(output_path) / 1024
+    print(f"  ✓ {output_path} saved ({size_kb:.1f} KB, {len(model.estimators_)} trees)")
+
+
 # ── Main ──────────────────────────────────────────────────────────────────────
 
 if __name__ == "__main__":
@@ -440,8 +481,9 @@ if __name__ == "__main__":
 
     model = train(X, y)
     export_onnx(model, "model.onnx")
+    export_trees(model, X, "model.bvmd")
 
     print("\n" + "="*60)
     print("  ✓ Training complete!")
-    print("  Next: copy model.onnx to the extension root, rebuild popup.")
+    print("  Next: copy model.onnx (or model.bvmd for in-WASM scoring) to the extension root, rebuild popup.")
     print("="*60)
@@ -0,0 +1,363 @@
+// wasm-feature/crates/vigilant-core/src/analyzer.rs
+// Browser Vigilant — configurable analyzer handle (builder → immutable Analyzer)
+// Configured brands count next to the built-in ones in the brand-similarity
+// features and filename scoring; the rest of the configuration changes the
+// heuristic layer on top and how scores map to verdicts.
+
+use std::mem::size_of;
+use std::sync::OnceLock;
+use crate::brandalias::alias_brand;
+use crate::brandpath::mimicked_brand;
+use crate::compound::{compounds, COMPOUND_WEIGHT};
+use crate::composite::composite_report;
+use crate::compromised;
+use crate::display;
+use crate::download::{download_event_report_with_brands, download_report_with_brands};
+use crate::encoding::{decode_fully, encoding_features};
+use crate::keywords::{self, keyword_features};
+use crate::normalize::normalize_url;
+use crate::numbrand::numeric_brand;
+use crate::timing::Stages;
+use crate::json::Json;
+use crate::verdict::{Decision, Verdict};
+use crate::{
+    apk_report, archive_report, cert_report, features_with_brands,
+    html_attachment_report, ics_report, mail_report, office_report, page_report, parse_url, pdf_report, psl, resource_graph_report, score,
+    CertInfo, DownloadEvent, Evidence, PageSnapshot, Report, UrlReport, BRANDS, FRAUD_KW, FREE_KW, LOGIN_KW, PAY_KW, TRUST_KW,
+};
+
+/// How eagerly scores turn into warnings and blocks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Profile {
+    /// Enterprise / high-risk users: warn early, block on moderate evidence.
+    Strict,
+    #[default]
+    Balanced,
+    /// Fewer interruptions; only strong evidence blocks.
+    Permissive,
+}
+
+impl Profile {
+    pub fn parse(name: &str) -> Option<Profile> {
+        match name { "strict" => Some(Profile::Strict), "balanced" => Some(Profile::Balanced), "permissive" => Some(Profile::Permissive), _ => None }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self { Profile::Strict => "strict", Profile::Balanced => "balanced", Profile::Permissive => "permissive" }
+    }
+
+    /// (warn, block) score thresholds.
+    pub fn thresholds(self) -> (f32, f32) {
+        match self {
+            Profile::Strict     => (0.3, 0.55),
+            Profile::Balanced   => (0.4, 0.7),
+            Profile::Permissive => (0.55, 0.85),
+        }
+    }
+
+    pub fn verdict(self, score: f32) -> Verdict {
+        let (warn, block) = self.thresholds();
+        if score >= block { Verdict::Block } else if score >= warn { Verdict::Warn } else { Verdict::Allow }
+    }
+}
+
+/// Extra keyword lists scored on top of the model features.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeywordPack { Login, Trust, Payments, Giveaway, Fraud }
+
+impl KeywordPack {
+    pub fn parse(name: &str) -> Option<KeywordPack> {
+        match name {
+            "login"    => Some(KeywordPack::Login),
+            "trust"    => Some(KeywordPack::Trust),
+            "payments" => Some(KeywordPack::Payments),
+            "giveaway" => Some(KeywordPack::Giveaway),
+            "fraud"    => Some(KeywordPack::Fraud),
+            _ => None,
+        }
+    }
+
+    fn words(self) -> &'static [&'static str] {
+        match self {
+            KeywordPack::Login    => LOGIN_KW,
+            KeywordPack::Trust    => TRUST_KW,
+            KeywordPack::Payments => PAY_KW,
+            KeywordPack::Giveaway => FREE_KW,
+            KeywordPack::Fraud    => FRAUD_KW,
+        }
+    }
+}
+
+/// Input size caps applied before any scanner runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Caps {
+    /// Bytes of a download / attachment handed to the content scanners.
+    pub max_input_bytes: usize,
+    /// Bytes of text (mail JSON, ICS, page text) handed to the text scanners.
+    pub max_text_bytes:  usize,
+}
+
+impl Default for Caps {
+    fn default() -> Caps { Caps { max_input_bytes: 64 << 20, max_text_bytes: 4 << 20 } }
+}
+
+#[derive(Clone, Debug)]
+pub struct AnalyzerBuilder {
+    brands:   Vec<String>,
+    keywords: Vec<String>,
+    use_psl:  bool,
+    caps:     Caps,
+    profile:  Profile,
+}
+
+impl Default for AnalyzerBuilder {
+    fn default() -> AnalyzerBuilder {
+        AnalyzerBuilder { brands: Vec::new(), keywords: Vec::new(), use_psl: true, caps: Caps::default(), profile: Profile::default() }
+    }
+}
+
+impl AnalyzerBuilder {
+    /// Brands to protect in addition to the built-in list (e.g. a bank's own
+    /// product names for an enterprise deployment).
+    pub fn brands<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, brands: I) -> AnalyzerBuilder {
+        self.brands.extend(brands.into_iter().map(|b| b.as_ref().trim().to_lowercase()).filter(|b| b.len() >= 3));
+        self
+    }
+
+    pub fn keyword_pack(mut self, pack: KeywordPack) -> AnalyzerBuilder {
+        self.keywords.extend(pack.words().iter().map(|w| w.to_string()));
+        self
+    }
+
+    pub fn keywords<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, words: I) -> AnalyzerBuilder {
+        self.keywords.extend(words.into_iter().map(|w| w.as_ref().trim().to_lowercase()).filter(|w| !w.is_empty()));
+        self
+    }
+
+    /// Split hosts on the public-suffix list (default) or naively on the last two labels.
+    pub fn psl(mut self, on: bool) -> AnalyzerBuilder { self.use_psl = on; self }
+
+    pub fn caps(mut self, caps: Caps) -> AnalyzerBuilder { self.caps = caps; self }
+
+    pub fn max_input_bytes(mut self, n: usize) -> AnalyzerBuilder { self.caps.max_input_bytes = n; self }
+
+    pub fn max_text_bytes(mut self, n: usize) -> AnalyzerBuilder { self.caps.max_text_bytes = n; self }
+
+    pub fn profile(mut self, profile: Profile) -> AnalyzerBuilder { self.profile = profile; self }
+
+    pub fn build(mut self) -> Analyzer {
+        self.brands.retain(|b| !BRANDS.contains(&b.as_str()));
+        self.brands.sort();
+        self.brands.dedup();
+        self.keywords.sort();
+        self.keywords.dedup();
+        Analyzer { brands: self.brands, keywords: self.keywords, use_psl: self.use_psl, caps: self.caps, profile: self.profile }
+    }
+}
+
+/// Immutable, thread-safe analyzer. Build differently configured ones side by
+/// side with [`Analyzer::builder`]; [`Analyzer::shared`] is the default
+/// instance the WASM layer uses.
+#[derive(Clone, Debug)]
+pub struct Analyzer {
+    brands:   Vec<String>,
+    keywords: Vec<String>,
+    use_psl:  bool,
+    caps:     Caps,
+    profile:  Profile,
+}
+
+impl Default for Analyzer {
+    fn default() -> Analyzer { AnalyzerBuilder::default().build() }
+}
+
+fn truncate_str(s: &str, max: usize) -> &str {
+    if s.len() <= max { return s; }
+    let mut end = max;
+    while !s.is_char_boundary(end) { end -= 1; }
+    &s[..end]
+}
+
+impl Analyzer {
+    pub fn builder() -> AnalyzerBuilder { AnalyzerBuilder::default() }
+
+    pub fn shared() -> &'static Analyzer {
+        static SHARED: OnceLock<Analyzer> = OnceLock::new();
+        SHARED.get_or_init(Analyzer::default)
+    }
+
+    pub fn profile(&self) -> Profile { self.profile }
+
+    pub fn caps(&self) -> Caps { self.caps }
+
+    /// Configured brands beyond the built-in list.
+    pub fn custom_brands(&self) -> usize { self.brands.len() }
+
+    pub fn brands(&self) -> &[String] { &self.brands }
+
+    pub fn keywords(&self) -> &[String] { &self.keywords }
+
+    /// Configured and keyword-pack words.
+    pub fn custom_keywords(&self) -> usize { self.keywords.len() }
+
+    pub fn uses_psl(&self) -> bool { self.use_psl }
+
+    /// Heap held by the configured brand and keyword lists.
+    pub fn heap_bytes(&self) -> usize {
+        let list = |v: &[String]| v.iter().map(|s| s.capacity() + size_of::<String>()).sum::<usize>();
+        list(&self.brands) + list(&self.keywords)
+    }
+
+    pub fn registrable_domain(&self, host: &str) -> String {
+        if self.use_psl { return psl::registrable_domain(host); }
+        let host = host.trim_end_matches('.').to_lowercase();
+        let labels: Vec<&str> = host.split('.').collect();
+        labels[labels.len().saturating_sub(2)..].join(".")
+    }
+
+    /// URL verdict: built-in risk plus the configured brands and keyword packs.
+    /// On a popular site whose path looks planted, host signals count at a
+    /// quarter and the path signals are added. The features come from `url`
+    /// as given, the host and path checks from its [`normalize_url`] form;
+    /// keyword packs match the path decoded as often as it takes.
+    pub fn url(&self, url: &str) -> UrlReport {
+        let mut t = Stages::start();
+        let p = parse_url(&normalize_url(url));
+        let host = p.host.to_lowercase();
+        let reg = self.registrable_domain(&host);
+        let reg_label = reg.split('.').next().unwrap_or("");
+        t.mark("parse");
+        let features = features_with_brands(url, &self.brands);
+        t.mark("features");
+        let mut signals = score::contributions(&features);
+        let mut flags: Vec<&'static str> = Vec::new();
+        if let Some((path_flags, weight)) = compromised::compromised_mode(url, false) {
+            compromised::damp_host(&mut signals);
+            flags.extend(path_flags);
+            signals.push(weight);
+        }
+        t.mark("score");
+        // a configured brand anywhere but in the registrable label is borrowed
+        if self.brands.iter().any(|b| host.contains(b.as_str()) && reg_label != b.as_str()) {
+            flags.push("custom_brand_impersonation");
+            signals.push(0.35);
+        }
+        if display::homoglyph_brand(&host, &self.brands).is_some() {
+            flags.push("homoglyph_impersonation");
+            signals.push(0.6);
+        }
+        t.mark("homoglyph");
+        if alias_brand(&host, reg_label).is_some() {
+            flags.push("brand_alias_impersonation");
+            signals.push(0.5);
+        }
+        t.mark("brand_alias");
+        if numeric_brand(&host, reg_label).is_some() {
+            flags.push("numeric_brand_lookalike");
+            signals.push(0.45);
+        }
+        t.mark("numeric_brand");
+        let target = format!("{}{}", host, decode_fully(&p.path).0.to_lowercase());
+        if mimicked_brand(reg_label, &p.path).is_some() {
+            flags.push("brand_path_mimicry");
+            signals.push(0.3);
+        }
+        t.mark("brand_path");
+        if keywords::stuffed(&keyword_features(url)) {
+            flags.push("keyword_stuffing");
+            signals.push(0.15);
+        }
+        let hits = self.keywords.iter().filter(|k| target.contains(k.as_str())).count();
+        if hits > 0 {
+            flags.push("keyword_pack_match");
+            signals.push((0.1 * hits as f32).min(0.3));
+        }
+        t.mark("keywords");
+        let [depth, hidden_traversal, hidden_keyword] = encoding_features(url);
+        if depth >= 2.0 {
+            flags.push("nested_encoding");
+            signals.push(0.25);
+        }
+        if hidden_traversal > 0.0 {
+            flags.push("encoded_traversal");
+            signals.push(0.15);
+        }
+        if hidden_keyword > 0.0 {
+            flags.push("encoded_keyword");
+            signals.push(0.1);
+        }
+        t.mark("encoding");
+        for id in compounds(&flags, &features) {
+            flags.push(id);
+            signals.push(COMPOUND_WEIGHT);
+        }
+        let risk = signals.iter().sum::<f32>().min(1.0);
+        let uncertainty = score::uncertainty(&signals);
+        let decision = Decision::from_band(risk, uncertainty, |s| self.profile.verdict(s));
+        t.mark("rules");
+        UrlReport { features, risk, verdict: self.profile.verdict(risk), uncertainty, decision, flags, signals: Vec::new(), timings: t.into_spans() }
+    }
+
+    /// Re-derive `verdict` from `score` under this analyzer's profile.
+    fn finish(&self, report: Json) -> Json {
+        match report.get("score").and_then(Json::as_f64) {
+            Some(s) => { let v = self.profile.verdict(s as f32); report.set("verdict", v.as_str()) }
+            None => report,
+        }
+    }
+
+    fn clip<'a>(&self, bytes: &'a [u8]) -> &'a [u8] { &bytes[..bytes.len().min(self.caps.max_input_bytes)] }
+
+    pub fn download(&self, filename: &str, bytes: &[u8]) -> Json { self.finish(download_report_with_brands(filename, self.clip(bytes), &self.brands)) }
+
+    pub fn archive(&self, filename: &str, bytes: &[u8], page_text: &str) -> Json {
+        self.finish(archive_report(filename, self.clip(bytes), truncate_str(page_text, self.caps.max_text_bytes)))
+    }
+
+    pub fn pdf(&self, bytes: &[u8]) -> Json { self.finish(pdf_report(self.clip(bytes))) }
+
+    pub fn office(&self, bytes: &[u8]) -> Json { self.finish(office_report(self.clip(bytes))) }
+
+    pub fn html_attachment(&self, bytes: &[u8]) -> Json { self.finish(html_attachment_report(self.clip(bytes))) }
+
+    pub fn apk(&self, bytes: &[u8]) -> Json { self.finish(apk_report(self.clip(bytes))) }
+
+    pub fn mail(&self, message_json: &str) -> Json {
+        // cutting JSON mid-document would only produce a parse error
+        if message_json.len() > self.caps.max_text_bytes {
+            return Json::obj().with("error", "message exceeds max_text_bytes");
+        }
+        self.finish(mail_report(message_json))
+    }
+
+    pub fn ics(&self, ics_text: &str) -> Json { self.finish(ics_report(truncate_str(ics_text, self.caps.max_text_bytes))) }
+
+    pub fn resource_graph(&self, resources_json: &str, page_url: &str) -> Json {
+        if resources_json.len() > self.caps.max_text_bytes {
+            return Json::obj().with("error", "resource list exceeds max_text_bytes");
+        }
+        self.finish(resource_graph_report(resources_json, page_url))
+    }
+
+    // ── Typed entry points ───────────────────────────────────────────────────
+
+    pub fn page(&self, snapshot: &PageSnapshot) -> Report { Report::from_report("page", self.finish(page_report(snapshot))) }
+
+    pub fn download_event(&self, event: &DownloadEvent, bytes: &[u8]) -> Report {
+        Report::from_report("download", self.finish(download_event_report_with_brands(event, self.clip(bytes), &self.brands)))
+    }
+
+    pub fn cert(&self, cert: &CertInfo) -> Report { Report::from_report("cert", self.finish(cert_report(cert))) }
+
+    /// One verdict from the URL and whichever of the page, headers and
+    /// certificate were collected, marked with how complete that was.
+    pub fn composite(&self, evidence: &Evidence) -> Report {
+        Report::from_report("composite", composite_report(evidence, &self.url(&evidence.url), |s| self.profile.verdict(s)))
+    }
+
+    /// Address-bar spoof check of `url` for an omnibox `max_chars` wide,
+    /// protecting the configured brands as well as the built-in ones.
+    pub fn display_spoof(&self, url: &str, max_chars: usize) -> Report {
+        Report::from_report("display_spoof", self.finish(display::display_report(url, max_chars, &self.brands)))
+    }
+}
@@ -0,0 +1,212 @@
+// wasm-feature/crates/vigilant-core/src/api.rs
+// Browser Vigilant — native Rust entry points over the individual analyzers
+
+use std::collections::{HashSet, VecDeque};
+use crate::analyzer::Analyzer;
+use crate::json::Json;
+use crate::signals::CustomSignal;
+use crate::timing::timings_json;
+use crate::types::ToJson;
+use crate::verdict::{Decision, Verdict};
+use crate::{
+    analyze_form_action, combine, deep_link_report, download_context_report, extract_features, oauth_report, parse_url,
+    should_allow_autofill, url_risk,
+};
+
+/// Model features plus the heuristic score for one URL.
+#[derive(Clone, Debug)]
+pub struct UrlReport {
+    pub features: Vec<f32>,
+    pub risk:     f32,
+    pub verdict:  Verdict,
+    /// Half-width of the band `risk` may be off by.
+    pub uncertainty: f32,
+    /// Whether `verdict` can stand on-device or needs an online lookup.
+    pub decision: Decision,
+    /// Heuristics the configured analyzer added on top of the built-in score.
+    pub flags:    Vec<&'static str>,
+    /// Values from registered [`crate::SignalProvider`]s; filled by `Context::url`.
+    pub signals:  Vec<CustomSignal>,
+    /// Milliseconds per analysis stage, in order; empty unless a clock is
+    /// set with [`crate::set_timing_clock`].
+    pub timings:  Vec<(&'static str, f64)>,
+}
+
+impl ToJson for UrlReport {
+    fn to_json(&self) -> Json {
+        let o = Json::obj()
+            .with("features", self.features.clone())
+            .with("risk", self.risk)
+            .with("verdict", self.verdict.as_str())
+            .with("uncertainty", self.uncertainty)
+            .with("decision", self.decision.as_str())
+            .with("flags", self.flags.clone());
+        let o = if self.signals.is_empty() { o } else { o.with("signals", self.signals.iter().map(CustomSignal::to_json).collect::<Vec<_>>()) };
+        if self.timings.is_empty() { return o; }
+        o.with("timings", timings_json(&self.timings))
+    }
+}
+
+/// Stateless URL checks: the feature vector fed to the model, the
+/// heuristic risk score and the OAuth consent-link analyzer. Uses the
+/// default configuration; build an [`Analyzer`] for anything else.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UrlAnalyzer;
+
+impl UrlAnalyzer {
+    pub fn new() -> UrlAnalyzer { UrlAnalyzer }
+
+    pub fn features(&self, url: &str) -> Vec<f32> { extract_features(url) }
+
+    pub fn risk(&self, url: &str) -> f32 { url_risk(url) }
+
+    pub fn analyze(&self, url: &str) -> UrlReport { Analyzer::shared().url(url) }
+
+    pub fn oauth(&self, url: &str) -> Json { oauth_report(url) }
+}
+
+/// Checks that need to know which page they happen on: form posts, autofill,
+/// downloads and attachments the page hands the user.
+#[derive(Clone, Debug)]
+pub struct PageAnalyzer {
+    page_url: String,
+    host:     String,
+}
+
+impl PageAnalyzer {
+    pub fn new(page_url: &str) -> PageAnalyzer {
+        PageAnalyzer { page_url: page_url.to_string(), host: parse_url(page_url).host.to_lowercase() }
+    }
+
+    pub fn page_url(&self) -> &str { &self.page_url }
+
+    pub fn form_action(&self, action: &str) -> f32 { analyze_form_action(action, &self.host) }
+
+    pub fn autofill(&self, saved_origin: &str) -> Verdict { should_allow_autofill(&self.page_url, saved_origin) }
+
+    pub fn download(&self, filename: &str, bytes: &[u8]) -> Json { Analyzer::shared().download(filename, bytes) }
+
+    pub fn download_context(&self, mime: &str, ext: &str, user_gesture: bool) -> Json {
+        download_context_report(mime, ext, &self.page_url, user_gesture)
+    }
+
+    pub fn deep_link(&self, link: &str, user_gesture: bool) -> Json { deep_link_report(link, &self.page_url, user_gesture) }
+
+    pub fn archive(&self, filename: &str, bytes: &[u8], page_text: &str) -> Json {
+        Analyzer::shared().archive(filename, bytes, page_text)
+    }
+
+    pub fn attachment(&self, bytes: &[u8]) -> Json { Analyzer::shared().html_attachment(bytes) }
+}
+
+/// Values returned by [`RiskSession::navigation_features`]:
+/// navigations_per_minute, suspicious_tld_share, new_domain_streak.
+pub const SESSION_FEATURES: usize = 3;
+
+/// Look-back of the navigation features.
+pub const SESSION_WINDOW_MS: f64 = 5.0 * 60_000.0;
+
+/// Navigations a session remembers; older ones only stop counting as new domains.
+const SESSION_VISITS: usize = 64;
+
+/// Navigation rate and new-domain run at which a session counts as a
+/// redirect storm, and the signal it then records once.
+const STORM_PER_MINUTE: f32 = 6.0;
+const STORM_STREAK: f32 = 4.0;
+const STORM_SCORE: f32 = 0.6;
+
+/// Session signal recorded for a malvertising-style redirect storm.
+pub const REDIRECT_STORM: &str = "redirect_storm";
+
+#[derive(Clone, Debug)]
+pub(crate) struct Visit {
+    at_ms: f64,
+    pub(crate) domain: String,
+    suspicious_tld: bool,
+}
+
+/// Signals gathered over one tab or browsing session. Two medium findings
+/// together outweigh either one alone, same as the per-report `combine`.
+/// Timed navigations also feed rate-of-change features that catch redirect
+/// storms whose individual URLs look bland.
+#[derive(Clone, Debug, Default)]
+pub struct RiskSession {
+    signals: Vec<(String, f32)>,
+    visits:  VecDeque<Visit>,
+}
+
+impl RiskSession {
+    pub fn new() -> RiskSession { RiskSession::default() }
+
+    pub fn record(&mut self, source: &str, score: f32) {
+        self.signals.push((source.to_string(), score.clamp(0.0, 1.0)));
+    }
+
+    /// Record any analyzer report by its `score` field.
+    pub fn record_report(&mut self, source: &str, report: &Json) {
+        if let Some(s) = report.get("score").and_then(Json::as_f64) { self.record(source, s as f32); }
+    }
+
+    pub fn observe_url(&mut self, url: &str) -> UrlReport {
+        let r = UrlAnalyzer.analyze(url);
+        self.record(url, r.risk);
+        r
+    }
+
+    pub fn signals(&self) -> &[(String, f32)] { &self.signals }
+
+    pub(crate) fn visits(&self) -> &VecDeque<Visit> { &self.visits }
+
+    /// Note a navigation to registrable domain `domain` at `at_ms` (epoch
+    /// milliseconds, any clock as long as it is the same for every call).
+    /// Records [`REDIRECT_STORM`] the first time the session turns into one.
+    pub fn record_navigation(&mut self, domain: &str, suspicious_tld: bool, at_ms: f64) {
+        if self.visits.len() >= SESSION_VISITS { self.visits.pop_front(); }
+        self.visits.push_back(Visit { at_ms, domain: domain.to_string(), suspicious_tld });
+        if self.signals.iter().any(|(s, _)| s == REDIRECT_STORM) { return; }
+        let [rate, _, streak] = self.navigation_features(at_ms);
+        if rate >= STORM_PER_MINUTE && streak >= STORM_STREAK { self.record(REDIRECT_STORM, STORM_SCORE); }
+    }
+
+    /// `[navigations_per_minute, suspicious_tld_share, new_domain_streak]`
+    /// over the [`SESSION_WINDOW_MS`] before `now_ms`. The rate divides by
+    /// the time since the window's first navigation, at least a minute, so a
+    /// burst in a fresh tab isn't averaged away; the streak counts the
+    /// latest navigations in a row to domains the session hadn't seen.
+    pub fn navigation_features(&self, now_ms: f64) -> [f32; SESSION_FEATURES] {
+        let since = now_ms - SESSION_WINDOW_MS;
+        let mut seen: HashSet<&str> = HashSet::new();
+        let (mut n, mut suspicious, mut streak, mut first) = (0usize, 0usize, 0usize, now_ms);
+        for v in &self.visits {
+            let new = seen.insert(v.domain.as_str());
+            if v.at_ms < since { streak = 0; continue; }
+            n += 1;
+            suspicious += v.suspicious_tld as usize;
+            streak = if new { streak + 1 } else { 0 };
+            first = first.min(v.at_ms);
+        }
+        if n == 0 { return [0.0; SESSION_FEATURES]; }
+        let minutes = ((now_ms - first) / 60_000.0).max(1.0);
+        [(n as f64 / minutes) as f32, suspicious as f32 / n as f32, streak as f32]
+    }
+
+    /// Returns `{ navigations_per_minute, suspicious_tld_share, new_domain_streak, redirect_storm }`.
+    pub fn navigation_report(&self, now_ms: f64) -> Json {
+        let [rate, share, streak] = self.navigation_features(now_ms);
+        Json::obj()
+            .with("navigations_per_minute", rate)
+            .with("suspicious_tld_share", share)
+            .with("new_domain_streak", streak)
+            .with("redirect_storm", self.signals.iter().any(|(s, _)| s == REDIRECT_STORM))
+    }
+
+    pub fn score(&self) -> f32 {
+        let (mut top, mut second) = (0.0f32, 0.0f32);
+        for &(_, s) in &self.signals {
+            if s > top { second = top; top = s; } else if s > second { second = s; }
+        }
+        combine(top, second)
+    }
+
+    pub fn verdict(&self) -> Verdict { Verdict::from_score(self.score()) }
+}
@@ -0,0 +1,274 @@
+// wasm-feature/crates/vigilant-core/src/apk.rs
+// Browser Vigilant — APK quick-inspector for the Android sideload warning
+// Reads AndroidManifest.xml (binary AXML) just far enough for the package
+// name, requested permissions and service-bound permissions.
+
+use crate::json::Json;
+use crate::verdict::Verdict;
+use crate::{zip, BRANDS};
+
+const MANIFEST_LIMIT: usize = 2 << 20;
+
+const CHUNK_STRING_POOL: u16 = 0x0001;
+const CHUNK_XML:         u16 = 0x0003;
+const CHUNK_RESOURCE_MAP:u16 = 0x0180;
+const CHUNK_START_ELEM:  u16 = 0x0102;
+const UTF8_FLAG:         u32 = 0x100;
+/// Resource IDs of android:name / android:permission, for manifests whose
+/// attribute-name strings were stripped by an obfuscator.
+const ATTR_NAME_ID:       u32 = 0x0101_0003;
+const ATTR_PERMISSION_ID: u32 = 0x0101_0006;
+
+const SMS_PERMS: &[&str] = &["RECEIVE_SMS","READ_SMS","SEND_SMS","RECEIVE_MMS"];
+const OVERLAY_PERM: &str = "SYSTEM_ALERT_WINDOW";
+const ACCESSIBILITY_PERM: &str = "BIND_ACCESSIBILITY_SERVICE";
+
+fn u16_at(b: &[u8], o: usize) -> Option<u16> { b.get(o..o.checked_add(2)?).map(|s| u16::from_le_bytes([s[0], s[1]])) }
+fn u32_at(b: &[u8], o: usize) -> Option<u32> { b.get(o..o.checked_add(4)?).map(|s| u32::from_le_bytes([s[0], s[1], s[2], s[3]])) }
+
+fn read_pool(b: &[u8], base: usize) -> Option<Vec<String>> {
+    let header = u16_at(b, base + 2)? as usize;
+    let count = u32_at(b, base + 8)? as usize;
+    let flags = u32_at(b, base + 16)?;
+    let strings_start = base.checked_add(u32_at(b, base + 20)? as usize)?;
+    let utf8 = flags & UTF8_FLAG != 0;
+    let mut out = Vec::with_capacity(count.min(1 << 16));
+    for i in 0..count.min(1 << 16) {
+        let mut o = strings_start.checked_add(u32_at(b, base + header + i * 4)? as usize)?;
+        let s = if utf8 {
+            // utf16 length then utf8 length, each 1 or 2 bytes
+            let skip = |o: &mut usize| -> Option<usize> {
+                let l = *b.get(*o)? as usize;
+                *o += 1;
+                if l & 0x80 != 0 { let l2 = *b.get(*o)? as usize; *o += 1; Some(((l & 0x7f) << 8) | l2) } else { Some(l) }
+            };
+            skip(&mut o)?;
+            let n = skip(&mut o)?;
+            String::from_utf8_lossy(b.get(o..o + n)?).into_owned()
+        } else {
+            let mut n = u16_at(b, o)? as usize;
+            o += 2;
+            if n & 0x8000 != 0 { n = ((n & 0x7fff) << 16) | u16_at(b, o)? as usize; o += 2; }
+            // a length past the end of the buffer can't be real
+            let n = n.min(b.len().saturating_sub(o) / 2);
+            let units: Vec<u16> = (0..n).filter_map(|k| u16_at(b, o + k * 2)).collect();
+            String::from_utf16_lossy(&units)
+        };
+        out.push(s);
+    }
+    Some(out)
+}
+
+#[derive(Default)]
+struct Manifest {
+    package:      String,
+    permissions:  Vec<String>,
+    /// `android:permission` values declared on <service> elements.
+    service_perms:Vec<String>,
+}
+
+fn parse_axml(b: &[u8]) -> Option<Manifest> {
+    if u16_at(b, 0)? != CHUNK_XML { return None; }
+    let mut o = u16_at(b, 2)? as usize;
+    let (mut pool, mut res_ids): (Vec<String>, Vec<u32>) = (Vec::new(), Vec::new());
+    let mut m = Manifest::default();
+    while o + 8 <= b.len() {
+        let kind = u16_at(b, o)?;
+        let header = u16_at(b, o + 2)? as usize;
+        let size = u32_at(b, o + 4)? as usize;
+        // a chunk must hold its own header and fit in the document
+        let Some(end) = o.checked_add(size).filter(|e| *e <= b.len()) else { break };
+        if size < 8 || header > size { break; }
+        match kind {
+            CHUNK_STRING_POOL => pool = read_pool(b, o)?,
+            CHUNK_RESOURCE_MAP => res_ids = (0..(size - header) / 4).filter_map(|k| u32_at(b, o + header + k * 4)).collect(),
+            CHUNK_START_ELEM => {
+                let s = |idx: u32| pool.get(idx as usize).map(String::as_str).unwrap_or("");
+                let elem = s(u32_at(b, o + 20)?);
+                let attr_start = u16_at(b, o + 24)? as usize;
+                let attr_size = (u16_at(b, o + 26)? as usize).max(20);
+                let attr_count = u16_at(b, o + 28)? as usize;
+                for k in 0..attr_count {
+                    let a = k.checked_mul(attr_size).and_then(|x| x.checked_add(o + 16 + attr_start))?;
+                    if a + 12 > end { break; }
+                    let name_idx = u32_at(b, a + 4)?;
+                    let raw = u32_at(b, a + 8)?;
+                    let name = s(name_idx);
+                    let rid = res_ids.get(name_idx as usize).copied().unwrap_or(0);
+                    let value = if raw != u32::MAX { s(raw).to_string() } else { continue };
+                    let is_name = name == "name" || rid == ATTR_NAME_ID;
+                    let is_perm = name == "permission" || rid == ATTR_PERMISSION_ID;
+                    match elem {
+                        "manifest" if name == "package" => m.package = value,
+                        "uses-permission" | "uses-permission-sdk-23" if is_name => m.permissions.push(value),
+                        "service" if is_perm => m.service_perms.push(value),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+        // `end` is past `o` as `size` is at least 8, so every step moves on
+        o = end;
+    }
+    Some(m)
+}
+
+fn short(perm: &str) -> &str { perm.rsplit('.').next().unwrap_or(perm) }
+
+/// Inspect an APK (or a prefix of one that still contains the manifest).
+/// Returns `{ is_apk, package, permissions, service_permissions, flags, score, verdict }`.
+pub fn apk_report(bytes: &[u8]) -> Json {
+    let list = zip::entries(bytes);
+    let manifest = zip::read_named(bytes, &list, "AndroidManifest.xml", MANIFEST_LIMIT).and_then(|x| parse_axml(&x));
+    let Some(m) = manifest else {
+        let is_apk = list.iter().any(|e| e.name == "AndroidManifest.xml" || e.name == "classes.dex");
+        return Json::obj().with("is_apk", is_apk).with("package", Json::Null)
+            .with("score", 0.0f32).with("verdict", Verdict::Allow.as_str());
+    };
+    let has = |p: &str| m.permissions.iter().any(|x| short(x) == p);
+    let sms = SMS_PERMS.iter().any(|p| has(p));
+    let overlay = has(OVERLAY_PERM);
+    let accessibility = m.service_perms.iter().any(|p| short(p) == ACCESSIBILITY_PERM);
+    let notif = m.service_perms.iter().any(|p| short(p) == "BIND_NOTIFICATION_LISTENER_SERVICE");
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+
+    if sms && accessibility && overlay { flags.push("banking_trojan_combo"); risk += 0.9; }
+    else if sms && (accessibility || overlay) { flags.push("sms_with_screen_control"); risk += 0.6; }
+    else if accessibility && overlay { flags.push("accessibility_with_overlay"); risk += 0.5; }
+    if sms && notif { flags.push("otp_interception"); risk += 0.3; }
+    if sms && !flags.contains(&"banking_trojan_combo") && !flags.contains(&"sms_with_screen_control") { flags.push("sms_access"); risk += 0.2; }
+    if has("REQUEST_INSTALL_PACKAGES") { flags.push("installs_packages"); risk += 0.2; }
+    if m.service_perms.iter().any(|p| short(p) == "BIND_DEVICE_ADMIN") { flags.push("device_admin"); risk += 0.3; }
+    if has("READ_CONTACTS") && has("SEND_SMS") { flags.push("contact_spread"); risk += 0.2; }
+    // Sideloaded packages claiming a bank/payment brand are almost never the real app.
+    let pkg = m.package.to_lowercase();
+    let claimed: Vec<&str> = BRANDS.iter().copied().filter(|b| pkg.split('.').any(|seg| seg.contains(b))).collect();
+    if !claimed.is_empty() { flags.push("package_claims_brand"); risk += 0.2; }
+
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("is_apk", true)
+        .with("package", m.package.as_str())
+        .with("permissions", m.permissions.iter().map(|p| short(p).to_string()).collect::<Vec<_>>())
+        .with("service_permissions", m.service_perms.iter().map(|p| short(p).to_string()).collect::<Vec<_>>())
+        .with("brands_claimed", claimed)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(kind: u16, header: u16, body: &[u8]) -> Vec<u8> {
+        let mut c = kind.to_le_bytes().to_vec();
+        c.extend(header.to_le_bytes());
+        c.extend((body.len() as u32 + 8).to_le_bytes());
+        c.extend(body);
+        c
+    }
+
+    /// UTF-8 string pool chunk.
+    fn pool(strings: &[&str]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut offsets = Vec::new();
+        for s in strings {
+            offsets.extend((data.len() as u32).to_le_bytes());
+            data.extend([s.len() as u8, s.len() as u8]);
+            data.extend(s.as_bytes());
+            data.push(0);
+        }
+        let mut body = (strings.len() as u32).to_le_bytes().to_vec();
+        body.extend(0u32.to_le_bytes());
+        body.extend(UTF8_FLAG.to_le_bytes());
+        body.extend((28 + offsets.len() as u32).to_le_bytes());
+        body.extend(0u32.to_le_bytes());
+        body.extend(offsets);
+        body.extend(data);
+        chunk(CHUNK_STRING_POOL, 28, &body)
+    }
+
+    /// Start-element chunk with `(name, value)` string-index attributes.
+    fn elem(name: u32, attrs: &[(u32, u32)]) -> Vec<u8> {
+        let mut body = vec![0; 8];
+        body.extend(u32::MAX.to_le_bytes());
+        body.extend(name.to_le_bytes());
+        body.extend([20, 0, 20, 0]);
+        body.extend((attrs.len() as u16).to_le_bytes());
+        body.extend([0; 6]);
+        for (n, v) in attrs {
+            body.extend(u32::MAX.to_le_bytes());
+            body.extend(n.to_le_bytes());
+            body.extend(v.to_le_bytes());
+            body.extend([8, 0, 0, 3]);
+            body.extend(v.to_le_bytes());
+        }
+        chunk(CHUNK_START_ELEM, 16, &body)
+    }
+
+    fn axml(chunks: &[Vec<u8>]) -> Vec<u8> { chunk(CHUNK_XML, 8, &chunks.concat()) }
+
+    const STRINGS: &[&str] = &["manifest", "package", "com.example.bank", "uses-permission", "name", "android.permission.RECEIVE_SMS"];
+
+    fn manifest() -> Vec<u8> { axml(&[pool(STRINGS), elem(0, &[(1, 2)]), elem(3, &[(4, 5)])]) }
+
+    #[test]
+    fn reads_package_and_permissions() {
+        let m = parse_axml(&manifest()).unwrap();
+        assert_eq!(m.package, "com.example.bank");
+        assert_eq!(m.permissions, ["android.permission.RECEIVE_SMS"]);
+    }
+
+    #[test]
+    fn report_reads_manifest_from_zip() {
+        let apk = zip::tests::stored(&[("AndroidManifest.xml", &manifest())]);
+        let r = apk_report(&apk);
+        assert_eq!(r.get("is_apk"), Some(&Json::Bool(true)));
+        assert_eq!(r.str_field("package"), "com.example.bank");
+    }
+
+    #[test]
+    fn resource_map_header_larger_than_size_stops_parsing() {
+        // header 16 over a chunk of 8 used to underflow `size - header`
+        let mut map = chunk(CHUNK_RESOURCE_MAP, 16, &[]);
+        map.extend([0; 8]);
+        let m = parse_axml(&axml(&[pool(STRINGS), elem(0, &[(1, 2)]), map, elem(3, &[(4, 5)])])).unwrap();
+        assert_eq!(m.package, "com.example.bank");
+        assert!(m.permissions.is_empty());
+    }
+
+    #[test]
+    fn chunk_size_past_the_end_stops_parsing() {
+        let mut b = manifest();
+        let at = 8 + pool(STRINGS).len();
+        // a size that wraps the offset back to the start on 32-bit targets
+        b[at + 4..at + 8].copy_from_slice(&(u32::MAX - at as u32 + 1).to_le_bytes());
+        let m = parse_axml(&b).unwrap();
+        assert!(m.package.is_empty());
+    }
+
+    #[test]
+    fn utf16_length_is_bounded_by_the_buffer() {
+        let mut body = 1u32.to_le_bytes().to_vec();
+        body.extend([0; 4]);
+        body.extend(0u32.to_le_bytes());
+        body.extend(32u32.to_le_bytes());
+        body.extend([0; 4]);
+        body.extend(0u32.to_le_bytes());
+        // 31-bit length 0x7fff_ffff with only a few units behind it
+        body.extend([0xff, 0xff, 0xff, 0xff, b'a', 0]);
+        let b = chunk(CHUNK_STRING_POOL, 28, &body);
+        let strings = read_pool(&b, 0).unwrap();
+        assert_eq!(strings.len(), 1);
+        assert!(strings[0].starts_with('a'));
+    }
+
+    #[test]
+    fn truncated_manifest_does_not_panic() {
+        let b = manifest();
+        for n in 0..b.len() { let _ = parse_axml(&b[..n]); }
+    }
+}
@@ -0,0 +1,299 @@
+// wasm-feature/crates/vigilant-core/src/archive.rs
+// Browser Vigilant — archive evasion heuristics: encrypted archives with the
+// password handed out next to them, deep zip nesting, ISO/IMG/VHD containers
+
+use crate::json::Json;
+use crate::verdict::Verdict;
+use crate::zip::{self, ZipEntry};
+use crate::DANGEROUS_EXTS;
+
+/// Nesting beyond this many archive layers exists only to tire out scanners.
+const MAX_BENIGN_DEPTH: usize = 2;
+const MAX_WALK_DEPTH:   usize = 5;
+/// Total bytes decompressed while walking nested archives.
+const WALK_BUDGET:      usize = 32 << 20;
+const MAX_ISO_NAMES:    usize = 256;
+
+const ARCHIVE_EXTS: &[&str] = &["zip","rar","7z","gz","tgz","tar","cab","arj","lzh","ace","iso","img"];
+/// Payload types that ride inside containers and aren't in DANGEROUS_EXTS.
+const INNER_RISKY_EXTS: &[&str] = &["lnk","js","jse","vbe","wsh","iso","img","vhd","vhdx","one","chm","xll","url"];
+const DISK_IMAGE_EXTS: &[&str] = &["iso","img","vhd","vhdx","udf","dmg"];
+const PASSWORD_KW: &[&str] = &["password","passwort","kennwort","passwd","contraseña","senha","mot de passe","пароль","pass","pwd"];
+
+fn ext_of(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(name).to_lowercase();
+    base.rsplit_once('.').map(|(_, e)| e.to_string()).unwrap_or_default()
+}
+
+fn risky_ext(ext: &str) -> bool { DANGEROUS_EXTS.contains(&ext) || INNER_RISKY_EXTS.contains(&ext) }
+
+// ── Password clues ───────────────────────────────────────────────────────────
+
+fn clean_token(t: &str) -> Option<String> {
+    let t = t.trim_matches(|c: char| !c.is_alphanumeric() && !"!@#$%^&*_-+=".contains(c));
+    let n = t.chars().count();
+    if !(3..=32).contains(&n) || t.eq_ignore_ascii_case("is") { return None; }
+    Some(t.to_string())
+}
+
+/// `Password: Inv2024` / `pwd - 1234` / `the password is "abc"` in page or mail text.
+pub fn password_in_text(text: &str) -> Option<String> {
+    let low = text.to_lowercase();
+    // keep the original casing for the token when lowering didn't shift offsets
+    let src = if low.len() == text.len() { text } else { &low };
+    for kw in PASSWORD_KW {
+        let mut from = 0;
+        while let Some(p) = low[from..].find(kw).map(|p| p + from) {
+            from = p + kw.len();
+            // don't read "passport" or "bypass" as a password label
+            if low[..p].chars().next_back().is_some_and(char::is_alphanumeric) { continue; }
+            if low[from..].chars().next().is_some_and(char::is_alphanumeric) { continue; }
+            let Some(rest) = src.get(from..) else { continue };
+            let mut words = rest.split_whitespace();
+            let Some(mut w) = words.next() else { continue };
+            if ["is", "is:", "=", ":", "-", "–"].contains(&w.to_lowercase().as_str()) { w = match words.next() { Some(n) => n, None => continue }; }
+            let w = w.trim_start_matches([':', '=', '-']);
+            if let Some(tok) = clean_token(w) { return Some(tok); }
+        }
+    }
+    None
+}
+
+/// `invoice_pass123.zip`, `docs-pwd-4411.rar`, `report (password 9911).zip`.
+pub fn password_in_filename(filename: &str) -> Option<String> {
+    let low = filename.to_lowercase();
+    let stem = low.rsplit_once('.').map(|(s, _)| s).unwrap_or(&low);
+    let segs: Vec<&str> = stem.split(|c: char| "_-. ()[]".contains(c)).filter(|s| !s.is_empty()).collect();
+    for (i, seg) in segs.iter().enumerate() {
+        for kw in ["password", "passwd", "pass", "pwd", "pw"] {
+            let Some(rest) = seg.strip_prefix(kw) else { continue };
+            let cand = if rest.is_empty() { segs.get(i + 1).copied().unwrap_or("") } else { rest.trim_start_matches(['=', ':']) };
+            // "passport" is a word, "pass123" is a password
+            if cand.chars().any(|c| c.is_ascii_digit()) || rest.is_empty() || kw.starts_with("passw") {
+                if let Some(tok) = clean_token(cand) { return Some(tok); }
+            }
+            break;
+        }
+    }
+    None
+}
+
+// ── ZipCrypto password check ─────────────────────────────────────────────────
+
+fn crc32_table() -> [u32; 256] {
+    let mut t = [0u32; 256];
+    for (i, slot) in t.iter_mut().enumerate() {
+        let mut c = i as u32;
+        for _ in 0..8 { c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 }; }
+        *slot = c;
+    }
+    t
+}
+
+/// Whether `password` decrypts the 12-byte traditional-encryption header of
+/// `e` to the expected check byte. AES entries (method 99) can't be checked.
+fn zipcrypto_accepts(b: &[u8], e: &ZipEntry, password: &str) -> bool {
+    if e.method == 99 { return false; }
+    let Some(header) = zip::raw_data(b, e).and_then(|d| d.get(..12)) else { return false };
+    let table = crc32_table();
+    let crc = |c: u32, byte: u8| table[((c ^ byte as u32) & 0xff) as usize] ^ (c >> 8);
+    let mut k = [0x1234_5678u32, 0x2345_6789, 0x3456_7890];
+    let update = |k: &mut [u32; 3], p: u8| {
+        k[0] = crc(k[0], p);
+        k[1] = k[1].wrapping_add(k[0] & 0xff).wrapping_mul(134_775_813).wrapping_add(1);
+        k[2] = crc(k[2], (k[1] >> 24) as u8);
+    };
+    for &p in password.as_bytes() { update(&mut k, p); }
+    let mut last = 0u8;
+    for &c in header {
+        let t = (k[2] | 2) as u16;
+        last = c ^ ((t.wrapping_mul(t ^ 1) >> 8) as u8);
+        update(&mut k, last);
+    }
+    let expected = if e.flags & 0x08 != 0 { (e.mod_time >> 8) as u8 } else { (e.crc32 >> 24) as u8 };
+    last == expected
+}
+
+// ── Containers ───────────────────────────────────────────────────────────────
+
+/// File names in the root directory of an ISO 9660 image.
+fn iso_root_names(b: &[u8]) -> Option<Vec<String>> {
+    const SECTOR: usize = 2048;
+    let pvd = 16 * SECTOR;
+    if b.get(pvd + 1..pvd + 6)? != b"CD001" { return None; }
+    let root = pvd + 156;
+    let lba = u32::from_le_bytes(b.get(root + 2..root + 6)?.try_into().ok()?) as usize;
+    let len = u32::from_le_bytes(b.get(root + 10..root + 14)?.try_into().ok()?) as usize;
+    let dir = b.get(lba * SECTOR..(lba * SECTOR + len).min(b.len()))?;
+    let mut names = Vec::new();
+    let mut o = 0;
+    while o < dir.len() && names.len() < MAX_ISO_NAMES {
+        let rec = dir[o] as usize;
+        // records never straddle sectors; a zero length means skip to the next one
+        if rec == 0 { o = (o / SECTOR + 1) * SECTOR; continue; }
+        let nlen = *dir.get(o + 32)? as usize;
+        let name = dir.get(o + 33..o + 33 + nlen)?;
+        if nlen > 1 || (name != [0] && name != [1]) {
+            let n = String::from_utf8_lossy(name);
+            names.push(n.split(';').next().unwrap_or("").to_string());
+        }
+        o += rec;
+    }
+    Some(names)
+}
+
+/// Headers-encrypted RAR (RAR4 block-password flag or a RAR5 encryption header).
+fn rar_headers_encrypted(b: &[u8]) -> bool {
+    if b.starts_with(b"Rar!\x1a\x07\x01\x00") {
+        // crc32, header size vint, header type vint (4 = archive encryption)
+        let mut o = 12;
+        while b.get(o).is_some_and(|x| x & 0x80 != 0) { o += 1; }
+        return b.get(o + 1) == Some(&4);
+    }
+    b.starts_with(b"Rar!\x1a\x07\x00") && b.get(9) == Some(&0x73) && b.get(10).is_some_and(|f| f & 0x80 != 0)
+}
+
+struct Walk {
+    max_depth:  usize,
+    encrypted:  Vec<String>,
+    risky:      Vec<String>,
+    budget:     usize,
+}
+
+fn walk_zip(b: &[u8], depth: usize, w: &mut Walk) {
+    w.max_depth = w.max_depth.max(depth);
+    if depth >= MAX_WALK_DEPTH { return; }
+    for e in zip::entries(b) {
+        let ext = ext_of(&e.name);
+        if risky_ext(&ext) { w.risky.push(e.name.clone()); }
+        if e.encrypted() { w.encrypted.push(e.name.clone()); continue; }
+        if !ARCHIVE_EXTS.contains(&ext.as_str()) || w.budget == 0 { continue; }
+        let Some(inner) = zip::read(b, &e, w.budget) else { continue };
+        w.budget = w.budget.saturating_sub(inner.len());
+        if zip::is_zip(&inner) { walk_zip(&inner, depth + 1, w); }
+        else { w.max_depth = w.max_depth.max(depth + 1); }
+    }
+}
+
+/// Scan an archive download. `page_text` is the visible text of the page or
+/// message it came from, where droppers usually print the password.
+/// Returns `{ kind, encrypted, encrypted_entries, max_depth, risky_entries,
+/// password_hint, password_source, password_verified, flags, score, verdict }`.
+pub fn archive_report(filename: &str, bytes: &[u8], page_text: &str) -> Json {
+    let ext = ext_of(filename);
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+    let mut w = Walk { max_depth: 0, encrypted: Vec::new(), risky: Vec::new(), budget: WALK_BUDGET };
+    let mut kind = "unknown";
+    let mut header_encrypted = false;
+
+    if zip::is_zip(bytes) {
+        kind = "zip";
+        walk_zip(bytes, 1, &mut w);
+    } else if let Some(names) = iso_root_names(bytes) {
+        kind = "iso";
+        w.risky = names.into_iter().filter(|n| risky_ext(&ext_of(n))).collect();
+    } else if bytes.starts_with(b"Rar!") {
+        kind = "rar";
+        header_encrypted = rar_headers_encrypted(bytes);
+    }
+
+    // Mounted images drop Mark-of-the-Web from everything inside them.
+    if kind == "iso" || DISK_IMAGE_EXTS.contains(&ext.as_str()) {
+        flags.push("disk_image_container");
+        risk += 0.4;
+        if !w.risky.is_empty() { flags.push("disk_image_with_executable"); risk += 0.4; }
+    }
+    if w.max_depth > MAX_BENIGN_DEPTH { flags.push("deeply_nested_archive"); risk += 0.4; }
+
+    let encrypted = header_encrypted || !w.encrypted.is_empty();
+    let candidates: Vec<(String, &'static str)> = password_in_filename(filename).map(|p| (p, "filename")).into_iter()
+        .chain(password_in_text(page_text).map(|p| (p, "page_text")))
+        .collect();
+    let zip_entries = if encrypted && kind == "zip" { zip::entries(bytes) } else { Vec::new() };
+    let unlocks = |p: &str| zip_entries.iter().filter(|e| e.encrypted()).any(|e| zipcrypto_accepts(bytes, e, p));
+    // prefer a clue that actually opens the archive over the first one found
+    let verified_idx = candidates.iter().position(|(p, _)| unlocks(p));
+    let verified = verified_idx.is_some();
+    let (hint, source) = match verified_idx.or((!candidates.is_empty()).then_some(0)) {
+        Some(i) => (Some(candidates[i].0.clone()), Some(candidates[i].1)),
+        None => (None, None),
+    };
+    if encrypted {
+        flags.push("encrypted_archive");
+        risk += 0.2;
+        // A password shipped next to the archive exists only to blind gateway scanners.
+        if let Some(s) = source { flags.push(if s == "filename" { "password_in_filename" } else { "password_in_page_text" }); risk += 0.4; }
+        if verified { flags.push("password_unlocks_archive"); risk += 0.1; }
+        if w.encrypted.iter().any(|n| risky_ext(&ext_of(n))) { flags.push("encrypted_executable"); risk += 0.4; }
+        if header_encrypted { flags.push("encrypted_headers"); risk += 0.2; }
+    }
+    if !w.risky.is_empty() && !flags.contains(&"disk_image_with_executable") { flags.push("contains_executable"); risk += 0.3; }
+
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("kind", kind)
+        .with("encrypted", encrypted)
+        .with("encrypted_entries", w.encrypted)
+        .with("max_depth", w.max_depth)
+        .with("risky_entries", w.risky)
+        .with("password_hint", hint)
+        .with("password_source", source)
+        .with("password_verified", verified)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zip::tests::stored;
+
+    fn flags(r: &Json) -> Vec<String> { r.str_list("flags") }
+
+    #[test]
+    fn passwords_are_read_from_page_text() {
+        assert_eq!(password_in_text("Open it. The password is: Inv2024").as_deref(), Some("Inv2024"));
+        assert_eq!(password_in_text("Kennwort - 1234").as_deref(), Some("1234"));
+        assert_eq!(password_in_text("Bring your passport, no bypass"), None);
+    }
+
+    #[test]
+    fn passwords_are_read_from_filenames() {
+        assert_eq!(password_in_filename("invoice_pass123.zip").as_deref(), Some("123"));
+        assert_eq!(password_in_filename("docs-pwd-4411.rar").as_deref(), Some("4411"));
+        assert_eq!(password_in_filename("passport_scan.zip"), None);
+    }
+
+    #[test]
+    fn nested_archives_past_the_benign_depth_are_flagged() {
+        let inner = stored(&[("run.lnk", b"L")]);
+        let middle = stored(&[("b.zip", &inner)]);
+        let outer = stored(&[("a.zip", &middle)]);
+        let r = archive_report("a.zip", &outer, "");
+        assert_eq!(r.get("max_depth").and_then(Json::as_f64), Some(3.0));
+        assert_eq!(r.str_list("risky_entries"), ["run.lnk"]);
+        assert_eq!(flags(&r), ["deeply_nested_archive", "contains_executable"]);
+
+        assert!(flags(&archive_report("a.zip", &stored(&[("notes.txt", b"hi")]), "")).is_empty());
+    }
+
+    #[test]
+    fn an_encrypted_executable_with_a_password_nearby_is_blocked() {
+        let mut z = stored(&[("invoice.exe", b"MZ")]);
+        let cen = z.len() - 22 - (46 + "invoice.exe".len());
+        z[cen + 8] |= 1;
+        let r = archive_report("invoice.zip", &z, "Your document password: 4411");
+        assert_eq!(r.str_list("encrypted_entries"), ["invoice.exe"]);
+        assert_eq!((r.str_field("password_hint"), r.str_field("password_source")), ("4411", "page_text"));
+        assert_eq!(flags(&r), ["encrypted_archive", "password_in_page_text", "encrypted_executable", "contains_executable"]);
+        assert_eq!(r.str_field("verdict"), "block");
+    }
+
+    #[test]
+    fn a_disk_image_is_flagged_by_its_extension() {
+        let r = archive_report("statement.img", b"not an iso", "");
+        assert_eq!(flags(&r), ["disk_image_container"]);
+    }
+}
@@ -0,0 +1,115 @@
+// wasm-feature/crates/vigilant-core/src/audit.rs
+// Browser Vigilant — bounded verdict audit log for compliance exports and
+// triaging user-reported misses. URLs are recorded as (optionally salted)
+// SHA-256 only.
+
+use std::collections::VecDeque;
+use std::mem::size_of;
+use crate::hash::Privacy;
+use crate::json::Json;
+use crate::verdict::Verdict;
+
+/// Entries kept before the oldest are overwritten.
+pub const AUDIT_CAPACITY: usize = 1000;
+
+/// Version recorded when the host hasn't named a loaded model.
+pub const ENGINE_VERSION: &str = concat!("vigilant-core/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    /// Milliseconds since the Unix epoch, as supplied by the caller.
+    pub timestamp_ms:  f64,
+    pub url_hash:      String,
+    pub verdict:       Verdict,
+    pub score:         f32,
+    pub model_version: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditFormat { Jsonl, Csv }
+
+impl AuditFormat {
+    pub fn parse(name: &str) -> Option<AuditFormat> {
+        match name.to_ascii_lowercase().as_str() { "jsonl" | "ndjson" => Some(AuditFormat::Jsonl), "csv" => Some(AuditFormat::Csv), _ => None }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AuditLog {
+    entries:       VecDeque<AuditEntry>,
+    capacity:      usize,
+    model_version: String,
+    /// Entries dropped off the front since the last clear.
+    dropped:       u64,
+    privacy:       Privacy,
+}
+
+impl Default for AuditLog {
+    fn default() -> AuditLog { AuditLog::with_capacity(AUDIT_CAPACITY) }
+}
+
+impl AuditLog {
+    pub fn with_capacity(capacity: usize) -> AuditLog {
+        AuditLog { entries: VecDeque::new(), capacity, model_version: ENGINE_VERSION.to_string(), dropped: 0, privacy: Privacy::plain() }
+    }
+
+    /// Salt URL hashes from now on (hash-only privacy mode). Earlier entries
+    /// were hashed differently and are dropped.
+    pub fn set_privacy(&mut self, privacy: Privacy) {
+        self.clear();
+        self.privacy = privacy;
+    }
+
+    /// Name the model whose verdicts follow (e.g. the ONNX file's version).
+    pub fn set_model_version(&mut self, version: &str) { self.model_version = version.to_string(); }
+
+    pub fn record(&mut self, timestamp_ms: f64, url: &str, verdict: Verdict, score: f32) {
+        if self.capacity == 0 { return; }
+        if self.entries.len() == self.capacity { self.entries.pop_front(); self.dropped += 1; }
+        self.entries.push_back(AuditEntry {
+            timestamp_ms, url_hash: self.privacy.digest(url), verdict, score, model_version: self.model_version.clone(),
+        });
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &AuditEntry> { self.entries.iter() }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    pub fn dropped(&self) -> u64 { self.dropped }
+
+    pub fn clear(&mut self) { self.entries.clear(); self.dropped = 0; }
+
+    pub fn heap_bytes(&self) -> usize {
+        self.entries.iter().map(|e| size_of::<AuditEntry>() + e.url_hash.capacity() + e.model_version.capacity()).sum()
+    }
+
+    /// Oldest first; CSV starts with a header row.
+    pub fn export(&self, format: AuditFormat) -> String {
+        let mut out = String::new();
+        if format == AuditFormat::Csv { out.push_str("timestamp_ms,url_hash,verdict,score,model_version\n"); }
+        for e in &self.entries {
+            match format {
+                AuditFormat::Jsonl => {
+                    let line = Json::obj()
+                        .with("timestamp_ms", e.timestamp_ms)
+                        .with("url_hash", e.url_hash.as_str())
+                        .with("verdict", e.verdict.as_str())
+                        .with("score", e.score)
+                        .with("model_version", e.model_version.as_str());
+                    out.push_str(&line.to_string());
+                }
+                AuditFormat::Csv => {
+                    out.push_str(&format!("{},{},{},{},{}", e.timestamp_ms, e.url_hash, e.verdict.as_str(), e.score, csv_field(&e.model_version)));
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s.to_string() }
+}
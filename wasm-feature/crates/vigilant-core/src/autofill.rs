@@ -0,0 +1,103 @@
+// wasm-feature/crates/vigilant-core/src/autofill.rs
+// Browser Vigilant — credential-fill risk gate for the companion autofill
+
+use crate::display::{display_host, skeleton};
+use crate::psl::{public_suffix, registrable_domain};
+use crate::verdict::Verdict;
+use crate::{has_ip, levenshtein, parse_url};
+
+struct Origin {
+    scheme: String,
+    host:   String,
+    port:   u16,
+}
+
+fn default_port(scheme: &str) -> u16 {
+    match scheme { "https" => 443, "http" => 80, _ => 0 }
+}
+
+fn origin_of(url: &str) -> Option<Origin> {
+    let p = parse_url(url.trim());
+    if p.scheme.is_empty() || p.host.is_empty() { return None; }
+    let port = p.port.unwrap_or_else(|| default_port(&p.scheme));
+    Some(Origin { scheme: p.scheme, host: p.host.trim_end_matches('.').to_lowercase(), port })
+}
+
+/// Registrable label without its public suffix: `paypal` for `www.paypal.co.uk`.
+fn site_label(host: &str) -> String {
+    let reg = registrable_domain(host);
+    let suffix = public_suffix(host);
+    reg.strip_suffix(&suffix).map(|s| s.trim_end_matches('.').to_string()).unwrap_or(reg)
+}
+
+/// What a host looks like in the address bar: punycode decoded, then
+/// confusables folded, so `xn--pypal-4ve` reads `paypal`.
+fn looks_like(host: &str) -> String { skeleton(&display_host(host)) }
+
+/// True when `host` imitates the saved site without belonging to it:
+/// the same-looking label under another suffix (homoglyphs included), small
+/// edit distance, or the saved label embedded in a different registrable
+/// domain. An IDN host that looks like something else is just another site.
+fn imitates(host: &str, saved_host: &str) -> bool {
+    let label = looks_like(&site_label(host));
+    let saved = looks_like(&site_label(saved_host));
+    if saved.is_empty() { return false; }
+    if label == saved { return true; }
+    if saved.len() >= 4 && levenshtein(&label, &saved) <= 2 { return true; }
+    saved.len() >= 4 && looks_like(host).contains(saved.as_str())
+}
+
+/// Decide whether credentials saved for `saved_origin` may be filled into `url`.
+///
+/// Allow — exact origin match (scheme, host, effective port).
+/// Warn  — same registrable domain on another host, an http → https upgrade,
+///         or an unrelated site (manual fill only, never automatic).
+/// Block — scheme downgrade, port mismatch, embedded userinfo, non-web schemes,
+///         IP hosts that differ, and lookalikes of the saved site.
+pub fn should_allow_autofill(url: &str, saved_origin: &str) -> Verdict {
+    let (cur, saved) = match (origin_of(url), origin_of(saved_origin)) {
+        (Some(c), Some(s)) => (c, s),
+        _ => return Verdict::Block,
+    };
+    if cur.scheme != "https" && cur.scheme != "http" { return Verdict::Block; }
+    // user:pass@host renders the real host out of sight
+    let authority = url.split("://").nth(1).unwrap_or("").split(['/', '?', '#']).next().unwrap_or("");
+    if authority.contains('@') { return Verdict::Block; }
+
+    let scheme_ok = cur.scheme == saved.scheme || (saved.scheme == "http" && cur.scheme == "https");
+    if !scheme_ok { return Verdict::Block; }
+    let upgraded = cur.scheme != saved.scheme;
+    let port_ok = if upgraded {
+        saved.port == default_port(&saved.scheme) && cur.port == default_port(&cur.scheme)
+    } else { cur.port == saved.port };
+    if !port_ok { return Verdict::Block; }
+
+    if cur.host == saved.host {
+        return if upgraded { Verdict::Warn } else { Verdict::Allow };
+    }
+    // IP literals have no registrable domain to share
+    if has_ip(&cur.host) || has_ip(&saved.host) { return Verdict::Block; }
+    if registrable_domain(&cur.host) == registrable_domain(&saved.host) {
+        return Verdict::Warn;
+    }
+    if imitates(&cur.host, &saved.host) { return Verdict::Block; }
+    Verdict::Warn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::should_allow_autofill;
+    use crate::verdict::Verdict;
+
+    #[test]
+    fn homoglyph_hosts_of_the_saved_site_are_blocked() {
+        assert_eq!(should_allow_autofill("https://xn--pypal-4ve.com/login", "https://paypal.com"), Verdict::Block);
+        assert_eq!(should_allow_autofill("https://paypal.co.uk/login", "https://paypal.com"), Verdict::Block);
+    }
+
+    #[test]
+    fn unrelated_idn_sites_are_not_spoofs() {
+        assert_eq!(should_allow_autofill("https://xn--bcher-kva.de/login", "https://github.com"), Verdict::Warn);
+        assert_eq!(should_allow_autofill("https://www.xn--bcher-kva.de/", "https://xn--bcher-kva.de"), Verdict::Warn);
+    }
+}
@@ -0,0 +1,57 @@
+// wasm-feature/crates/vigilant-core/src/brandalias.rs
+// Browser Vigilant — brands written the way their customers write them.
+// `BRANDS` is Latin-only, so `एसबीआई-केवाईसी.com` or `statebankofindia-kyc.in`
+// name SBI without ever containing "sbi". Aliases here are matched on the
+// host as the address bar shows it, after folding away the marks a lookalike
+// can add or drop.
+
+use crate::display::display_host;
+use crate::translit::romanize;
+
+/// Brand (as in `BRANDS`) and its aliases: the name in native script, official
+/// regional names, and the long transliterations people type. Latin aliases
+/// are kept to eight letters or more so they don't turn up inside ordinary
+/// words.
+pub(crate) const BRAND_ALIASES: &[(&str, &[&str])] = &[
+    ("sbi",      &["एसबीआई", "भारतीय स्टेट बैंक", "स्टेट बैंक ऑफ इंडिया", "எஸ்பிஐ", "ఎస్బిఐ", "statebankofindia", "bharatiyastatebank", "bhartiyastatebank"]),
+    ("hdfc",     &["एचडीएफसी", "எச்டிஎஃப்சி", "హెచ్డిఎఫ్సి"]),
+    ("icici",    &["आईसीआईसीआई", "ஐசிஐசிஐ", "ఐసిఐసిఐ"]),
+    ("axis",     &["एक्सिस बैंक", "ऐक्सिस बैंक"]),
+    ("kotak",    &["कोटक", "kotakmahindra"]),
+    ("paytm",    &["पेटीएम", "பேடிஎம்", "పేటీఎం"]),
+    ("phonepe",  &["फोनपे", "फ़ोनपे", "போன்பே", "ఫోన్‌పే"]),
+    ("airtel",   &["एयरटेल", "ஏர்டெல்", "ఎయిర్టెల్"]),
+    ("jio",      &["जियो", "ஜியோ", "జియో"]),
+    ("bsnl",     &["बीएसएनएल", "भारत संचार निगम", "bharatsanchar"]),
+    ("flipkart", &["फ्लिपकार्ट", "பிளிப்கார்ட்", "ఫ్లిప్‌కార్ట్"]),
+    ("amazon",   &["अमेज़न", "अमेज़ॉन", "அமேசான்", "అమెజాన్"]),
+    ("google",   &["गूगल", "கூகுள்", "గూగుల్"]),
+    ("whatsapp", &["व्हाट्सएप", "वॉट्सऐप", "வாட்ஸ்அப்", "వాట్సాప్"]),
+];
+
+/// `text` with case, spaces, hyphens, nukta and zero-width joiners folded
+/// away, so `फ़ोन-पे` and `फोनपे` compare equal.
+fn fold(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !matches!(c, ' ' | '-' | '\u{093c}' | '\u{200c}' | '\u{200d}'))
+        .collect()
+}
+
+/// Brand a label of `host` names through one of its aliases, unless the
+/// label is the registrable one and is nothing but the alias (the brand's
+/// own internationalised domain). Punycode labels are decoded first; a
+/// non-ASCII label is also romanised and checked against the Latin aliases.
+pub(crate) fn alias_brand(host: &str, reg_label: &str) -> Option<&'static str> {
+    let shown = display_host(&host.trim_end_matches('.').to_lowercase());
+    let reg_shown = display_host(&reg_label.to_lowercase());
+    shown.split('.').find_map(|label| {
+        let folded = fold(label);
+        let romanized = if label.is_ascii() { None } else { Some(fold(&romanize(label))) };
+        BRAND_ALIASES.iter().find(|(_, aliases)| aliases.iter().any(|a| {
+            let a = fold(a);
+            let hit = |l: &str| l.contains(a.as_str()) && !(l == a && label == reg_shown);
+            hit(&folded) || (a.is_ascii() && romanized.as_deref().is_some_and(hit))
+        })).map(|(brand, _)| *brand)
+    })
+}
@@ -0,0 +1,59 @@
+// wasm-feature/crates/vigilant-core/src/brandpath.rs
+// Browser Vigilant — homepage-vs-deep-link asymmetry
+// Phishing kits copy the brand's real paths verbatim, so a first page at
+// `/webapps/mpp/home` on a host that isn't PayPal is a strong tell; genuine
+// visits to small sites rarely start several levels deep.
+
+use crate::{parse_url, BRANDS};
+
+/// Values returned by [`brand_path_features`]: brand_path_mimicry,
+/// path_depth, deep_link_off_brand.
+pub const BRAND_PATH_FEATURES: usize = 3;
+
+/// Depth from which a landing page counts as a deep link.
+const DEEP_LINK_DEPTH: usize = 3;
+
+/// Brand, the registrable labels it serves from, and path prefixes
+/// (lowercase) only those sites use. Generic paths (`/login.php`, Exchange's
+/// `/owa/`) are left out: plenty of unrelated servers use them.
+pub(crate) const BRAND_PATHS: &[(&str, &[&str], &[&str])] = &[
+    ("paypal",     &["paypal", "paypal-objects"], &["/webapps/mpp/", "/cgi-bin/webscr", "/myaccount/summary"]),
+    ("amazon",     &["amazon"], &["/ap/signin", "/ap/cvf/", "/gp/css/"]),
+    ("apple",      &["apple", "icloud"], &["/appleid/"]),
+    ("microsoft",  &["microsoft", "microsoftonline", "live", "office", "office365", "outlook"], &["/common/oauth2/", "/ppsecure/"]),
+    ("google",     &["google", "youtube", "gmail"], &["/servicelogin", "/accounts/servicelogin", "/signin/v2/", "/v3/signin/"]),
+    ("facebook",   &["facebook", "fb", "messenger"], &["/recover/initiate"]),
+    ("netflix",    &["netflix"], &["/youraccount"]),
+    ("wellsfargo", &["wellsfargo"], &["/das/cgi-bin/session.cgi"]),
+    ("coinbase",   &["coinbase"], &["/signin_step_two"]),
+    ("sbi",        &["onlinesbi", "sbi"], &["/retail/login.htm"]),
+];
+
+/// `reg_label` is one of `brand`'s own sites.
+fn owns(brand: &str, reg_label: &str) -> bool {
+    match BRAND_PATHS.iter().find(|(b, _, _)| *b == brand) {
+        Some((_, owners, _)) => owners.contains(&reg_label),
+        None => brand == reg_label,
+    }
+}
+
+/// Brand whose path `path` copies, when `reg_label` isn't one of its sites.
+pub(crate) fn mimicked_brand(reg_label: &str, path: &str) -> Option<&'static str> {
+    let path = path.to_lowercase();
+    BRAND_PATHS.iter()
+        .find(|(_, owners, prefixes)| prefixes.iter().any(|p| path.starts_with(p)) && !owners.contains(&reg_label))
+        .map(|(brand, _, _)| *brand)
+}
+
+/// `[brand_path_mimicry, path_depth, deep_link_off_brand]`: a path copied
+/// from a brand it's not served by, the number of path segments, and a deep
+/// landing path on a host that names a brand without belonging to it.
+pub fn brand_path_features(url: &str) -> [f32; BRAND_PATH_FEATURES] {
+    let p = parse_url(url);
+    let host = p.host.to_lowercase();
+    let reg_label = p.reg_domain.split('.').next().unwrap_or("").to_lowercase();
+    let depth = p.path.split('/').filter(|s| !s.is_empty()).count();
+    let borrowed = BRANDS.iter().any(|b| b.len() >= 4 && host.contains(b) && !owns(b, &reg_label));
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    [b(mimicked_brand(&reg_label, &p.path).is_some()), depth as f32, b(borrowed && depth >= DEEP_LINK_DEPTH)]
+}
@@ -0,0 +1,98 @@
+// wasm-feature/crates/vigilant-core/src/brands.rs
+// Browser Vigilant — brands added at runtime. `BRANDS` covers the names
+// phishing kits target everywhere; an enterprise deployment also needs its
+// own portals and the regional banks its staff use. A context's analyzer
+// carries its own list (see `Context::set_brands`), and names on it count
+// as brands for the brand-similarity features (F21–F23) and filename
+// scoring of that analyzer only, next to the built-in ones, so a lookalike
+// of an internal portal reads like a lookalike of PayPal without another
+// tenant's vectors changing.
+
+use crate::BRANDS;
+
+/// Most runtime brands accepted; each one is compared against every URL.
+pub const MAX_RUNTIME_BRANDS: usize = 1024;
+
+/// `name` lowercased, when it is 3–63 ASCII letters, digits or hyphens.
+fn normalize(name: &str) -> Result<String, String> {
+    let b = name.trim().to_ascii_lowercase();
+    if (3..=63).contains(&b.len()) && b.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-') { return Ok(b); }
+    Err(format!("brand {:?} must be 3–63 ASCII letters, digits or hyphens", name))
+}
+
+/// `brands` added to `current`, or replacing it when `replace`; built-in
+/// and repeated names are skipped. Fails on a malformed name or too many.
+pub(crate) fn merge(current: &[String], brands: &[&str], replace: bool) -> Result<Vec<String>, String> {
+    let names = brands.iter().map(|b| normalize(b)).collect::<Result<Vec<_>, _>>()?;
+    let mut next = if replace { Vec::new() } else { current.to_vec() };
+    for n in names {
+        if !BRANDS.contains(&n.as_str()) && !next.contains(&n) { next.push(n); }
+    }
+    if next.len() > MAX_RUNTIME_BRANDS {
+        return Err(format!("{} runtime brands exceed the limit of {}", next.len(), MAX_RUNTIME_BRANDS));
+    }
+    Ok(next)
+}
+
+/// Whether `pred` holds for a built-in brand or one of `extra`.
+pub(crate) fn any_brand(extra: &[String], mut pred: impl FnMut(&str) -> bool) -> bool {
+    BRANDS.iter().any(|b| pred(b)) || extra.iter().any(|b| pred(b))
+}
+
+/// `f` folded over the built-in brands and `extra`, from `init`.
+pub(crate) fn fold_brands<T>(extra: &[String], init: T, mut f: impl FnMut(T, &str) -> T) -> T {
+    BRANDS.iter().copied().chain(extra.iter().map(String::as_str)).fold(init, &mut f)
+}
+
+/// Smallest `f` over the built-in brands and `extra`.
+pub(crate) fn min_over_brands(extra: &[String], mut f: impl FnMut(&str) -> usize) -> Option<usize> {
+    BRANDS.iter().copied().chain(extra.iter().map(String::as_str)).map(&mut f).min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_replaces_and_add_appends() {
+        let set = merge(&["old-portal".to_string()], &["Acme-Portal", "northbank"], true).unwrap();
+        assert_eq!(set, ["acme-portal", "northbank"]);
+        let added = merge(&set, &["northbank", "southbank"], false).unwrap();
+        assert_eq!(added, ["acme-portal", "northbank", "southbank"]);
+        assert_eq!(merge(&added, &[], true).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn built_in_names_are_not_repeated() {
+        assert_eq!(merge(&[], &["PayPal", " chase "], false).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn malformed_names_and_overflow_are_refused() {
+        for bad in ["ab", "acme portal", "acme.com", "ünï", &"a".repeat(64)] {
+            assert!(merge(&[], &[bad], false).unwrap_err().contains("3–63 ASCII"), "{}", bad);
+        }
+        let many: Vec<String> = (0..=MAX_RUNTIME_BRANDS).map(|i| format!("brand{}", i)).collect();
+        let many: Vec<&str> = many.iter().map(String::as_str).collect();
+        assert!(merge(&[], &many, true).unwrap_err().contains("exceed the limit"));
+    }
+
+    #[test]
+    fn helpers_cover_built_in_and_extra_brands() {
+        let extra = vec!["acme-portal".to_string()];
+        assert!(any_brand(&extra, |b| b == "acme-portal"));
+        assert!(!any_brand(&[], |b| b == "acme-portal"));
+        assert_eq!(min_over_brands(&extra, |b| if b == "acme-portal" { 0 } else { 5 }), Some(0));
+        assert_eq!(fold_brands(&extra, 0, |n, _| n + 1), BRANDS.len() + 1);
+    }
+
+    #[test]
+    fn only_the_configured_analyzer_sees_its_brands() {
+        let url = "https://acme-portol.com/login";
+        let acme = crate::Analyzer::builder().brands(["acme-portal"]).build();
+        assert_eq!(acme.url(url).features[21], 1.0);
+        assert_eq!(crate::Analyzer::default().url(url).features[21], 0.0);
+        assert_eq!(crate::extract_features(url)[21], 0.0);
+        assert!(crate::score_filename_with_brands("acme-portal.exe", acme.brands()) > crate::score_filename("acme-portal.exe"));
+    }
+}
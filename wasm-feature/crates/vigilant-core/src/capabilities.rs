@@ -0,0 +1,143 @@
+// wasm-feature/crates/vigilant-core/src/capabilities.rs
+// Browser Vigilant — what this build can detect, for the extension UI and
+// support. Every embedded table is listed once here; memory stats reuse it.
+
+use crate::analyzer::Analyzer;
+use crate::audit::ENGINE_VERSION;
+use crate::hash::sha256_hex;
+use crate::json::Json;
+use crate::model::FEATURE_VERSION;
+use crate::schema::feature_schema_hash;
+use crate::timing::timing_enabled;
+use crate::types::SCHEMA_VERSION;
+use crate::{
+    brandalias, brandpath, compound, compromised, contact, datauri, display, exfil, extensions, feature_mask, intent, lang, lexical, mail, messages, normalize, oauth, policy, psl, rank,
+    resources, scheme, schemepayload, score, signals, subdomain, template, translit, BRAND_PATH_FEATURES, BRANDS, CHAR_CLASS_FEATURES, CONTACT_FEATURES, DANGEROUS_EXTS, DGA_FEATURES,
+    ENCODING_FEATURES, ENTROPY_FEATURES, EXPANSION_FEATURES, EXTENSION_FEATURES, FRAUD_KW, FREE_KW, HOMOGLYPH_FEATURES, HOST_SIGNAL_FEATURES, INTENT_FEATURES, KEYWORD_FEATURES, LOGIN_KW, MALFORMED_FEATURES, NUMERIC_BRAND_FEATURES, PAY_KW, PRONOUNCE_FEATURES, RESOURCE_FEATURES,
+    SCHEME_FEATURES, SCHEME_RISK_FEATURES, SESSION_FEATURES, SHORT_SERVICES, SUBDOMAIN_FEATURES, SUSPICIOUS_TLDS, TIME_FEATURES, TOKEN_FEATURES, TRUST_KW, TYPOSQUAT_FEATURES,
+};
+
+/// Length of the vector fed to the URL model.
+pub const MODEL_FEATURES: usize = 77;
+
+/// Report kinds the analyzers produce.
+const ANALYZERS: &[&str] = &[
+    "url", "page", "composite", "form_action", "autofill", "download", "download_event", "download_context", "filename", "archive",
+    "apk", "office", "pdf", "html_attachment", "ics", "mail", "mail_auth", "oauth", "cert", "stream", "lang",
+    "transliteration", "time_features", "brand_path_features", "brand_aliases", "compound_rules", "keyword_features", "char_class_features", "token_features", "entropy_features", "pronounce_features", "homoglyph_features", "typosquat_features", "numeric_brand_features", "dga_features", "subdomain_features", "encoding_features", "malformed_features", "extension_features", "scheme_features", "scheme_risk_features", "contact_features", "host_signal_features", "intent_features", "label_length_percentile", "site_templates", "resource_graph", "csp",
+    "dnr_rules", "clipboard", "display_spoof", "intent", "deep_link", "embedded_urls", "endpoint", "compromised_site", "test_vectors", "normalize_url", "host_consistency", "upi_request", "short_url_expansion", "data_uri", "scheme_payload", "contact_link",
+];
+
+/// An embedded table: its entry count and every string it holds.
+pub(crate) struct Table {
+    pub(crate) name:    &'static str,
+    pub(crate) entries: usize,
+    pub(crate) items:   Vec<&'static str>,
+}
+
+impl Table {
+    fn flat(name: &'static str, list: &[&'static str]) -> Table { Table { name, entries: list.len(), items: list.to_vec() } }
+
+    /// Content version: the first 16 hex digits of SHA-256 over the entries.
+    pub(crate) fn digest(&self) -> String { sha256_hex(self.items.join("\n").as_bytes())[..16].to_string() }
+}
+
+fn grouped(name: &'static str, groups: &[(&'static str, &[&'static str])]) -> Table {
+    let items = groups.iter().flat_map(|(k, v)| std::iter::once(*k).chain(v.iter().copied())).collect();
+    Table { name, entries: groups.iter().map(|(_, v)| v.len()).sum(), items }
+}
+
+/// Built-in lists compiled into the module. Their digests are pinned in the
+/// self-test, so a table edit also updates `selftest::TABLE_DIGESTS`.
+pub(crate) fn tables() -> Vec<Table> {
+    let webhooks: Vec<&'static str> = exfil::WEBHOOK_ENDPOINTS.iter().map(|(_, p)| *p).collect();
+    let kw: Vec<&'static str> = [LOGIN_KW, TRUST_KW, PAY_KW, FREE_KW, FRAUD_KW].concat();
+    let idps: Vec<&'static str> = oauth::IDPS.iter().flat_map(|(a, b, c)| [*a, *b, *c]).collect();
+    let brand_paths: Vec<&'static str> = brandpath::BRAND_PATHS.iter()
+        .flat_map(|(b, owners, paths)| std::iter::once(*b).chain(owners.iter().copied()).chain(paths.iter().copied()))
+        .collect();
+    let messages: Vec<&'static str> = messages::TRANSLATIONS.iter()
+        .flat_map(|(l, t)| std::iter::once(*l).chain(t.iter().flat_map(|(k, v)| [*k, *v])))
+        .collect();
+    let severities: Vec<&'static str> = rank::SEVERITIES.iter().flat_map(|(n, s)| [*n, s.as_str()]).collect();
+    let overlaps: Vec<&'static str> = rank::OVERLAPS.concat();
+    let digit_letters: Vec<&'static str> = lexical::DIGIT_LETTERS.iter().flat_map(|(d, l)| [*d, *l]).collect();
+    let tracking: Vec<&'static str> = [normalize::TRACKING_PARAMS, normalize::TRACKING_PREFIXES].concat();
+    let script_patterns: Vec<&'static str> = [schemepayload::OBFUSCATION_CALLS, schemepayload::PAGE_WRITES, schemepayload::NAVIGATIONS, schemepayload::CREDENTIAL_WORDS].concat();
+    let confusables: Vec<&'static str> = display::CONFUSABLES.iter().flat_map(|(f, t)| [*f, *t]).collect();
+    vec![
+        Table::flat("brands", BRANDS),
+        grouped("brand_aliases", brandalias::BRAND_ALIASES),
+        grouped("compound_rules", compound::COMPOUNDS),
+        Table::flat("suspicious_tlds", SUSPICIOUS_TLDS),
+        Table::flat("short_services", SHORT_SERVICES),
+        Table::flat("dangerous_exts", DANGEROUS_EXTS),
+        grouped("data_uri_mimes", datauri::DATA_URI_MIMES),
+        grouped("extension_classes", extensions::EXTENSION_CLASSES),
+        Table::flat("server_script_exts", extensions::SERVER_SCRIPT_EXTS),
+        Table::flat("scheme_classes", scheme::SCHEME_CLASSES),
+        Table::flat("premium_prefixes", contact::PREMIUM_PREFIXES),
+        Table::flat("high_risk_prefixes", contact::HIGH_RISK_PREFIXES),
+        Table { name: "script_patterns", entries: script_patterns.len(), items: script_patterns },
+        Table { name: "tracking_params", entries: tracking.len(), items: tracking },
+        Table { name: "keywords", entries: kw.len(), items: kw },
+        Table::flat("public_suffixes", psl::MULTI_LABEL_SUFFIXES),
+        Table::flat("suffix_wildcards", psl::WILDCARD_SUFFIXES),
+        Table::flat("suffix_exceptions", psl::SUFFIX_EXCEPTIONS),
+        Table::flat("freemail", mail::FREEMAIL),
+        Table { name: "identity_providers", entries: oauth::IDPS.len(), items: idps },
+        Table::flat("app_stores", policy::APP_STORES),
+        grouped("app_packages", intent::APP_PACKAGES),
+        grouped("language_profiles", lang::PROFILES),
+        grouped("lure_words", lang::LURE_WORDS),
+        Table::flat("romanized_lures", translit::ROMANIZED_LURES),
+        Table::flat("subdomain_words", subdomain::SUBDOMAIN_WORDS),
+        Table { name: "digit_letters", entries: lexical::DIGIT_LETTERS.len(), items: digit_letters },
+        Table { name: "brand_paths", entries: brandpath::BRAND_PATHS.len(), items: brand_paths },
+        Table::flat("kit_dirs", template::KIT_DIRS),
+        Table::flat("kit_files", compromised::KIT_FILES),
+        Table::flat("popular_sites", compromised::POPULAR_SITES),
+        Table::flat("credential_sinks", resources::CREDENTIAL_SINKS),
+        Table { name: "messaging_webhooks", entries: exfil::WEBHOOK_ENDPOINTS.len(), items: webhooks },
+        Table { name: "severities", entries: rank::SEVERITIES.len(), items: severities },
+        Table { name: "signal_overlaps", entries: rank::OVERLAPS.len(), items: overlaps },
+        Table { name: "confusables", entries: display::CONFUSABLES.len(), items: confusables },
+        Table { name: "messages", entries: messages::TRANSLATIONS.iter().map(|(_, t)| t.len()).sum(), items: messages },
+    ]
+}
+
+/// Returns `{ engine_version, schema_version, feature_version, feature_schema, analyzers, features: { model,
+/// time, brand_path, keyword, char_class, token, entropy, pronounce, homoglyph, typosquat, numeric_brand, dga, subdomain, encoding, malformed, extension, scheme, scheme_risk, contact, host_signal, intent, expansion, resource_graph, session }, languages, locales, feature_mask,
+/// signals: [{ name, features, weights, severity, masked }], signal_mask, runtime_brands, timing,
+/// dictionaries: { <name>: { entries, version } }, models: { <name>: { kind,
+/// entries, loaded } }, analyzer: { profile, custom_brands, custom_keywords, psl } }`.
+pub fn capabilities_report(analyzer: &Analyzer) -> Json {
+    let dictionaries = tables().iter()
+        .map(|t| (t.name.to_string(), Json::obj().with("entries", t.entries).with("version", t.digest())))
+        .collect();
+    let mut languages: Vec<&str> = lang::LURE_WORDS.iter().map(|(l, _)| *l).collect();
+    languages.sort();
+    let models = Json::obj().with("url_weights", Json::obj().with("kind", "heuristic").with("entries", score::WEIGHTS.len()).with("loaded", true));
+    Json::obj()
+        .with("engine_version", ENGINE_VERSION)
+        .with("schema_version", SCHEMA_VERSION)
+        .with("feature_version", FEATURE_VERSION)
+        .with("feature_schema", feature_schema_hash())
+        .with("analyzers", ANALYZERS.to_vec())
+        .with("features", Json::obj().with("model", MODEL_FEATURES).with("time", TIME_FEATURES).with("brand_path", BRAND_PATH_FEATURES)
+            .with("keyword", KEYWORD_FEATURES).with("char_class", CHAR_CLASS_FEATURES).with("token", TOKEN_FEATURES).with("entropy", ENTROPY_FEATURES).with("pronounce", PRONOUNCE_FEATURES).with("homoglyph", HOMOGLYPH_FEATURES).with("typosquat", TYPOSQUAT_FEATURES).with("numeric_brand", NUMERIC_BRAND_FEATURES).with("dga", DGA_FEATURES).with("subdomain", SUBDOMAIN_FEATURES).with("encoding", ENCODING_FEATURES).with("malformed", MALFORMED_FEATURES).with("extension", EXTENSION_FEATURES).with("scheme", SCHEME_FEATURES).with("scheme_risk", SCHEME_RISK_FEATURES).with("contact", CONTACT_FEATURES).with("host_signal", HOST_SIGNAL_FEATURES).with("intent", INTENT_FEATURES).with("expansion", EXPANSION_FEATURES).with("resource_graph", RESOURCE_FEATURES).with("session", SESSION_FEATURES))
+        .with("languages", languages)
+        .with("locales", messages::TRANSLATIONS.iter().map(|(l, _)| *l).collect::<Vec<_>>())
+        .with("feature_mask", feature_mask())
+        .with("signals", signals::providers_report())
+        .with("signal_mask", signals::signal_mask())
+        .with("runtime_brands", analyzer.brands().to_vec())
+        .with("timing", timing_enabled())
+        .with("dictionaries", Json::Obj(dictionaries))
+        .with("models", models)
+        .with("analyzer", Json::obj()
+            .with("profile", analyzer.profile().as_str())
+            .with("custom_brands", analyzer.custom_brands())
+            .with("custom_keywords", analyzer.custom_keywords())
+            .with("psl", analyzer.uses_psl()))
+}
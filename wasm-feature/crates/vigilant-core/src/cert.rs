@@ -0,0 +1,58 @@
+// wasm-feature/crates/vigilant-core/src/cert.rs
+// Browser Vigilant — leaf-certificate heuristics (fresh DV certs on brand lookalikes)
+
+use crate::json::Json;
+use crate::psl::registrable_domain;
+use crate::types::CertInfo;
+use crate::verdict::Verdict;
+use crate::BRANDS;
+
+/// Issuers that hand out domain-validated certificates automatically and for free.
+const FREE_DV_ISSUERS: &[&str] = &["let's encrypt","zerossl","cpanel","buypass","google trust services","ssl.com free"];
+/// CA/B Forum cap for publicly trusted leaf certificates.
+const MAX_PUBLIC_VALIDITY_DAYS: f64 = 398.0;
+
+fn san_covers(san: &str, host: &str) -> bool {
+    let san = san.trim().trim_end_matches('.').to_lowercase();
+    match san.strip_prefix("*.") {
+        // a wildcard covers exactly one extra label
+        Some(base) => host.strip_suffix(base).and_then(|h| h.strip_suffix('.')).is_some_and(|l| !l.is_empty() && !l.contains('.')),
+        None => san == host,
+    }
+}
+
+/// Score the leaf certificate a page was served with.
+/// Returns `{ host, issuer, free_dv_issuer, brands_claimed, flags, score, verdict }`.
+pub fn cert_report(c: &CertInfo) -> Json {
+    let host = c.host.trim_end_matches('.').to_lowercase();
+    let issuer = c.issuer.to_lowercase();
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+
+    if c.self_signed { flags.push("self_signed"); risk += 0.6; }
+    if !c.san.is_empty() && !c.san.iter().any(|s| san_covers(s, &host)) { flags.push("name_mismatch"); risk += 0.6; }
+    // phishing kits request a certificate minutes before the campaign goes out
+    if c.age_days < 2.0 { flags.push("fresh_certificate"); risk += 0.3; }
+    else if c.age_days < 14.0 { flags.push("recent_certificate"); risk += 0.15; }
+    if c.san.len() > 50 { flags.push("bulk_san_list"); risk += 0.1; }
+    if !c.self_signed && c.validity_days > MAX_PUBLIC_VALIDITY_DAYS { flags.push("over_long_validity"); risk += 0.1; }
+
+    let free_dv = FREE_DV_ISSUERS.iter().any(|i| issuer.contains(i));
+    let reg = registrable_domain(&host);
+    let reg_label = reg.split('.').next().unwrap_or("");
+    let borrowed: Vec<&str> = BRANDS.iter().copied().filter(|b| b.len() >= 4 && host.contains(b) && reg_label != *b).collect();
+    if !borrowed.is_empty() {
+        flags.push("brand_in_certificate_host");
+        risk += if free_dv { 0.35 } else { 0.15 };
+    }
+
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("host", host.as_str())
+        .with("issuer", c.issuer.as_str())
+        .with("free_dv_issuer", free_dv)
+        .with("brands_claimed", borrowed)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
@@ -0,0 +1,194 @@
+// wasm-feature/crates/vigilant-core/src/chunks.rs
+// Browser Vigilant — split a `Context::export_state` document into chunks of
+// bounded size for stores with per-item limits (chrome.storage, IndexedDB
+// records). Every chunk is itself a valid state document carrying a slice of
+// the entries plus a `chunk: { set, index, count }` header, so chunks import
+// in any order and a half-written set still merges cleanly.
+
+use crate::hash::sha256_hex;
+use crate::json::Json;
+use crate::types::{err, Fields, SchemaError, SCHEMA_VERSION};
+
+/// Smallest `max_chunk` accepted; below this the header alone barely fits.
+pub const MIN_CHUNK_BYTES: usize = 512;
+
+/// Keyed sections of the export, as (path, section in the document).
+const SECTIONS: &[(&str, &[&str])] = &[
+    ("overrides", &["overrides"]),
+    ("state.reputation", &["state", "reputation"]),
+    ("state.first_seen", &["state", "first_seen"]),
+    ("state.templates", &["state", "templates"]),
+];
+
+fn section<'a>(doc: &'a Json, path: &[&str]) -> &'a [(String, Json)] {
+    path.iter().try_fold(doc, |v, k| v.get(k)).and_then(Json::as_object).unwrap_or(&[])
+}
+
+fn json_len(v: &Json) -> usize { v.to_string().len() }
+
+/// Entries of one chunk, per section, plus feed hosts by feed name.
+#[derive(Default)]
+struct Slice {
+    sections: [Vec<(String, Json)>; 4],
+    feeds:    Vec<(String, Vec<Json>)>,
+    bytes:    usize,
+}
+
+impl Slice {
+    fn feed_cost(&self, name: &str, host: &Json) -> usize {
+        let open = if self.feeds.last().is_some_and(|(n, _)| n == name) { 0 } else { json_len(&Json::from(name)) + 4 };
+        open + json_len(host) + 1
+    }
+
+    fn render(self, set: &str, index: usize, count: usize, key_check: &Json) -> Json {
+        let [overrides, reputation, first_seen, templates] = self.sections;
+        let feeds = self.feeds.into_iter().map(|(n, hosts)| (n, Json::Arr(hosts))).collect();
+        Json::obj()
+            .with("schema_version", SCHEMA_VERSION)
+            .with("chunk", Json::obj().with("set", set).with("index", index).with("count", count))
+            .with("overrides", Json::Obj(overrides))
+            .with("feeds", Json::Obj(feeds))
+            .with("state", Json::obj()
+                .with("schema_version", SCHEMA_VERSION)
+                .with("key_check", key_check.clone())
+                .with("reputation", Json::Obj(reputation))
+                .with("first_seen", Json::Obj(first_seen))
+                .with("templates", Json::Obj(templates)))
+    }
+}
+
+/// The slice the next entry goes into, opening a new one when it doesn't
+/// fit; `cost` prices the entry against a given slice.
+fn slot<'a>(slices: &'a mut Vec<Slice>, budget: usize, path: &str, cost: impl Fn(&Slice) -> usize) -> Result<&'a mut Slice, SchemaError> {
+    let last = slices.last().expect("slices is never empty");
+    if last.bytes + cost(last) > budget {
+        if cost(&Slice::default()) > budget { return Err(err(path, "entry alone is larger than max_chunk")); }
+        slices.push(Slice::default());
+    }
+    let s = slices.last_mut().expect("slices is never empty");
+    s.bytes += cost(s);
+    Ok(s)
+}
+
+/// Split `doc` (from `Context::export_state`) into documents whose JSON is
+/// at most `max_chunk` bytes each. Fails if one entry can't fit on its own.
+pub(crate) fn split(doc: &Json, max_chunk: usize) -> Result<Vec<Json>, SchemaError> {
+    if max_chunk < MIN_CHUNK_BYTES { return Err(err("max_chunk", &format!("must be at least {} bytes", MIN_CHUNK_BYTES))); }
+    let set = sha256_hex(doc.to_string().as_bytes())[..16].to_string();
+    let key_check = doc.get("state").and_then(|s| s.get("key_check")).cloned().unwrap_or(Json::Null);
+    // the personal layer is small and rides along in the first chunk
+    let personal = doc.get("personal").cloned();
+    let header = json_len(&Slice::default().render(&set, usize::MAX, usize::MAX, &key_check))
+        + personal.as_ref().map_or(0, |p| json_len(p) + 12);
+    let budget = max_chunk - header.min(max_chunk);
+
+    let mut slices = vec![Slice::default()];
+    for (i, (name, path)) in SECTIONS.iter().enumerate() {
+        for (k, v) in section(doc, path) {
+            let cost = json_len(&Json::from(k.as_str())) + json_len(v) + 2;
+            slot(&mut slices, budget, &format!("{}.{}", name, k), |_| cost)?.sections[i].push((k.clone(), v.clone()));
+        }
+    }
+    for (name, hosts) in section(doc, &["feeds"]) {
+        for h in hosts.as_array().unwrap_or(&[]) {
+            let s = slot(&mut slices, budget, &format!("feeds.{}", name), |s| s.feed_cost(name, h))?;
+            match s.feeds.last_mut() {
+                Some((n, list)) if n == name => list.push(h.clone()),
+                _ => s.feeds.push((name.clone(), vec![h.clone()])),
+            }
+        }
+    }
+
+    let count = slices.len();
+    Ok(slices.into_iter().enumerate().map(|(i, s)| {
+        let chunk = s.render(&set, i, count, &key_check);
+        match &personal { Some(p) if i == 0 => chunk.with("personal", p.clone()), _ => chunk }
+    }).collect())
+}
+
+/// Check the `chunk` header of a document from [`split`]; returns
+/// `(set, index, count)`.
+pub(crate) fn header(v: &Json) -> Result<(String, usize, usize), SchemaError> {
+    let f = Fields::new(v, "")?;
+    let c = v.get("chunk").ok_or_else(|| err("chunk", "missing; not a chunk from export_state_chunks"))?;
+    let h = Fields::new(c, "chunk")?;
+    let set = h.string("set")?;
+    let index = h.opt_count("index")?.ok_or_else(|| err("chunk.index", "missing"))?;
+    let count = h.opt_count("count")?.ok_or_else(|| err("chunk.count", "missing"))?;
+    if index >= count { return Err(err(&f.at("chunk"), "index out of range")); }
+    Ok((set, index, count))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json::Json;
+    use crate::{Context, Verdict};
+
+    fn chunks() -> Vec<Json> {
+        let mut ctx = Context::default();
+        for i in 0..40 { assert!(ctx.set_override(&format!("site-{}.example", i), Verdict::Block)); }
+        let chunks = ctx.export_state_chunks(512).unwrap();
+        assert!(chunks.len() > 1);
+        chunks
+    }
+
+    fn overrides(ctx: &Context) -> usize {
+        ctx.export_state().get("overrides").and_then(Json::as_object).map_or(0, <[_]>::len)
+    }
+
+    fn header(index: impl Into<Json>, count: Option<u32>) -> Json {
+        let h = Json::obj().with("set", "x").with("index", index);
+        match count { Some(n) => h.with("count", n), None => h }
+    }
+
+    fn path_of(chunk: &Json) -> (String, String) {
+        let e = Context::default().import_state_chunk(chunk).unwrap_err();
+        (e.path, e.message)
+    }
+
+    #[test]
+    fn chunks_import_in_any_order() {
+        let mut ctx = Context::default();
+        for c in chunks().iter().rev() { ctx.import_state_chunk(c).unwrap(); }
+        assert_eq!(overrides(&ctx), 40);
+    }
+
+    #[test]
+    fn a_truncated_chunk_is_refused() {
+        let text = chunks()[0].to_string();
+        assert!(Json::parse(&text[..text.len() / 2]).is_err());
+        let headless = Json::parse(&text).unwrap().set("chunk", Json::Null);
+        assert_eq!(path_of(&headless).0, "chunk");
+        assert_eq!(path_of(&chunks()[0].clone().set("chunk", header(0u32, None))).0, "chunk.count");
+        assert_eq!(path_of(&chunks()[0].clone().set("chunk", header(3u32, Some(3)))).1, "index out of range");
+    }
+
+    #[test]
+    fn a_bad_schema_version_is_refused() {
+        let c = chunks()[0].clone();
+        assert_eq!(path_of(&c.clone().set("schema_version", 99u32)).0, "schema_version");
+        assert_eq!(path_of(&c.clone().set("schema_version", 1.5)).1, "expected a positive integer");
+        let state = c.get("state").cloned().unwrap().set("schema_version", "1");
+        assert_eq!(path_of(&c.set("state", state)), ("state.schema_version".to_string(), "expected number".to_string()));
+    }
+
+    #[test]
+    fn a_wrong_field_type_is_refused_with_its_path() {
+        let c = chunks()[0].clone();
+        let bad = c.clone().set("overrides", Json::obj().with("a.example", 1u32));
+        assert_eq!(path_of(&bad).0, "overrides.a.example");
+        assert_eq!(path_of(&c.clone().set("feeds", Json::obj().with("mine", "a.example"))), ("feeds.mine".to_string(), "expected array".to_string()));
+        assert_eq!(path_of(&c.clone().set("chunk", header("0", Some(2)))), ("chunk.index".to_string(), "expected number".to_string()));
+        let state = c.get("state").cloned().unwrap().set("reputation", Json::obj().with("a.example", "high"));
+        assert_eq!(path_of(&c.set("state", state)).0, "state.reputation.a.example");
+    }
+
+    #[test]
+    fn a_refused_chunk_changes_nothing() {
+        let mut ctx = Context::default();
+        let c = chunks()[0].clone();
+        let bad = c.clone().set("overrides", Json::obj().with("a.example", "block").with("b.example", "maybe"));
+        assert!(ctx.import_state_chunk(&bad).is_err());
+        assert_eq!(overrides(&ctx), 0);
+    }
+}
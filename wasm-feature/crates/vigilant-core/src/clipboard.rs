@@ -0,0 +1,115 @@
+// wasm-feature/crates/vigilant-core/src/clipboard.rs
+// Browser Vigilant — clipboard hijack ("clipper") detection for payment
+// addresses. Clipper malware watches the clipboard for a UPI VPA or crypto
+// address and swaps in its own, often a vanity address sharing the first
+// and last characters. The content script reports what was copied and what
+// got pasted; any change of payment target between the two is a hijack.
+
+use crate::find_upi_vpa;
+use crate::json::Json;
+use crate::verdict::Verdict;
+
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Shared leading (after the asset's fixed head) and trailing characters at
+/// which a swapped address counts as a lookalike made to pass a glance.
+const LOOKALIKE_PREFIX: usize = 4;
+const LOOKALIKE_SUFFIX: usize = 3;
+
+/// A payment target found in text: asset name, its comparable form and the
+/// length of the head every address of the asset shares (`0x`, `bc1q`, …).
+struct Target {
+    asset: &'static str,
+    value: String,
+    head:  usize,
+}
+
+fn all_in(s: &str, set: &[u8]) -> bool { s.bytes().all(|b| set.contains(&b)) }
+
+/// Base58 address of `len` characters that isn't just a long number.
+fn base58(s: &str, len: std::ops::RangeInclusive<usize>) -> bool {
+    len.contains(&s.len()) && all_in(s, BASE58) && s.bytes().any(|b| b.is_ascii_alphabetic())
+}
+
+fn bech32(s: &str, hrp: &str) -> bool {
+    let low = s.to_ascii_lowercase();
+    // bech32 is all one case
+    (s == low || s == s.to_ascii_uppercase())
+        && low.strip_prefix(hrp).is_some_and(|d| (11..=71).contains(&d.len()) && all_in(d, BECH32))
+}
+
+/// Asset a single token is an address of, its fixed head and whether its
+/// case is meaningful (base58) or not (hex, bech32).
+fn crypto_asset(t: &str) -> Option<(&'static str, usize, bool)> {
+    if t.len() == 42 && t.starts_with("0x") && t[2..].bytes().all(|b| b.is_ascii_hexdigit()) { return Some(("ethereum", 2, false)); }
+    // hrp, separator and witness version
+    if bech32(t, "bc1") { return Some(("bitcoin", 4, false)); }
+    if bech32(t, "ltc1") { return Some(("litecoin", 5, false)); }
+    if t.starts_with(['1', '3']) && base58(t, 26..=35) { return Some(("bitcoin", 1, true)); }
+    if t.starts_with(['L', 'M']) && base58(t, 26..=34) { return Some(("litecoin", 1, true)); }
+    if t.starts_with('T') && base58(t, 34..=34) { return Some(("tron", 1, true)); }
+    None
+}
+
+/// First crypto address in `text`, else its first UPI VPA, in the form
+/// two copies of the same target compare equal.
+fn payment_target(text: &str) -> Option<Target> {
+    let crypto = text.split(|c: char| !c.is_ascii_alphanumeric()).find_map(|t| {
+        let (asset, head, cased) = crypto_asset(t)?;
+        Some(Target { asset, value: if cased { t.to_string() } else { t.to_ascii_lowercase() }, head })
+    });
+    crypto.or_else(|| find_upi_vpa(text).into_iter().next().map(|(prefix, handle)| Target { asset: "upi", value: format!("{}@{}", prefix, handle), head: 0 }))
+}
+
+/// Lengths of the prefix and suffix two ASCII addresses share, never
+/// overlapping in the shorter one.
+fn diff(copied: &str, pasted: &str) -> (usize, usize) {
+    let (a, b) = (copied.as_bytes(), pasted.as_bytes());
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let room = a.len().min(b.len()) - prefix;
+    let suffix = a.iter().rev().zip(b.iter().rev()).take(room).take_while(|(x, y)| x == y).count();
+    (prefix, suffix)
+}
+
+/// Compare the payment target the user copied with the one that got
+/// pasted. A different address (or a different asset) is a clipboard
+/// hijack; one that keeps the original's first and last characters is a
+/// generated lookalike as well. Text without a payment target, or a paste
+/// without one, is nothing to judge. Returns `{ asset, copied, pasted,
+/// pasted_asset, diff: { prefix, copied, pasted, suffix } | null, flags,
+/// score, verdict }`; `diff` splits the two addresses into the shared
+/// prefix, the differing middles and the shared suffix.
+pub fn compare_payment_targets(copied_text: &str, pasted_text: &str) -> Json {
+    let copied = payment_target(copied_text);
+    let pasted = payment_target(pasted_text);
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+    let mut split = Json::Null;
+    if let (Some(c), Some(p)) = (&copied, &pasted) {
+        if c.asset != p.asset || c.value != p.value {
+            flags.push("clipboard_hijack");
+            risk += 0.95;
+            let (prefix, suffix) = diff(&c.value, &p.value);
+            if c.asset == p.asset && prefix >= c.head + LOOKALIKE_PREFIX && suffix >= LOOKALIKE_SUFFIX {
+                flags.push("lookalike_payment_address");
+                risk += 0.05;
+            }
+            split = Json::obj()
+                .with("prefix", &c.value[..prefix])
+                .with("copied", &c.value[prefix..c.value.len() - suffix])
+                .with("pasted", &p.value[prefix..p.value.len() - suffix])
+                .with("suffix", &c.value[c.value.len() - suffix..]);
+        }
+    }
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("asset", copied.as_ref().map(|t| t.asset))
+        .with("copied", copied.as_ref().map(|t| t.value.clone()))
+        .with("pasted", pasted.as_ref().map(|t| t.value.clone()))
+        .with("pasted_asset", pasted.as_ref().map(|t| t.asset))
+        .with("diff", split)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
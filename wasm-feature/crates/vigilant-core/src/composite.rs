@@ -0,0 +1,105 @@
+// wasm-feature/crates/vigilant-core/src/composite.rs
+// Browser Vigilant — one verdict from whatever was collected. The URL is
+// always there; the DOM snapshot, the response headers and the certificate
+// often aren't (a prerendered tab, a blocked content script, a browser that
+// hides certificates). Reading a missing channel as a zero risk makes a
+// lookalike that kept its page away look as safe as one that was checked,
+// so each channel has a weight, missing channels drop out and the rest are
+// renormalized, and the report carries how much of the weight was actually
+// seen. The uncertainty band widens as completeness drops, so a thin
+// verdict lands in the online-check band instead of reading as safe.
+
+use crate::api::UrlReport;
+use crate::cert::cert_report;
+use crate::disposition::filename_report;
+use crate::json::Json;
+use crate::page::page_report;
+use crate::score::UNCERTAINTY_FLOOR;
+use crate::types::Evidence;
+use crate::verdict::{Decision, Verdict};
+use crate::url_risk;
+
+/// Evidence channel and its share of the combined score.
+pub(crate) const CHANNELS: &[(&str, f32)] = &[("url", 0.4), ("page", 0.35), ("cert", 0.2), ("headers", 0.05)];
+
+/// Uncertainty added on top of the URL's own when nothing but the URL was
+/// seen, scaled down linearly as completeness rises.
+const MISSING_BAND: f32 = 0.4;
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+}
+
+/// Target of a `Refresh: 5; url=…` header.
+fn refresh_target(value: &str) -> Option<&str> {
+    let at = value.to_ascii_lowercase().find("url=")?;
+    let target = value[at + 4..].trim().trim_matches(['\'', '"']);
+    (!target.is_empty()).then_some(target)
+}
+
+/// Risk of what the headers alone say: a served attachment's filename and
+/// where a `Refresh` header sends the tab. Returns `(risk, flags, details)`.
+fn headers_risk(headers: &[(String, String)]) -> (f32, Vec<String>, Json) {
+    let attachment = header(headers, "content-disposition").filter(|h| h.to_ascii_lowercase().contains("filename")).map(filename_report);
+    let refresh = header(headers, "refresh").and_then(refresh_target);
+    let file_risk = attachment.as_ref().and_then(|a| a.get("score")).and_then(Json::as_f64).unwrap_or(0.0) as f32;
+    let flags = attachment.as_ref().map(|a| a.str_list("flags")).unwrap_or_default();
+    let risk = file_risk.max(refresh.map_or(0.0, url_risk));
+    (risk, flags, Json::obj().with("attachment", attachment).with("refresh", refresh))
+}
+
+fn score_of(report: &Json) -> f32 { report.get("score").and_then(Json::as_f64).unwrap_or(0.0) as f32 }
+
+/// Combine `url` (the analyzer's report for `e.url`) with the page, header
+/// and certificate channels `e` has. A channel that reaches `verdict`'s
+/// block line carries the score on its own; otherwise the score is the
+/// weighted mean over the channels present. Returns `{ url, channels: {
+/// url, page, cert, headers }, missing, completeness, page_report?,
+/// cert_report?, headers?, flags, score, verdict, uncertainty, decision }`;
+/// a missing channel is `null` under `channels` and named in `missing`.
+pub(crate) fn composite_report(e: &Evidence, url: &UrlReport, verdict: impl Fn(f32) -> Verdict) -> Json {
+    let mut flags: Vec<String> = url.flags.iter().map(|f| f.to_string()).collect();
+    let mut merge = |more: Vec<String>| for f in more { if !flags.contains(&f) { flags.push(f); } };
+
+    // a CSP header stands in for one the content script didn't capture
+    let page = e.page.as_ref().map(|p| {
+        let mut p = p.clone();
+        if p.csp.is_none() {
+            if let Some(h) = &e.headers { p.csp = Some(header(h, "content-security-policy").unwrap_or("").to_string()); }
+        }
+        page_report(&p)
+    });
+    let cert = e.cert.as_ref().map(cert_report);
+    let headers = e.headers.as_deref().map(headers_risk);
+    if let Some(p) = &page { merge(p.str_list("flags")); }
+    if let Some(c) = &cert { merge(c.str_list("flags")); }
+    if let Some((_, f, _)) = &headers { merge(f.clone()); }
+
+    let risks = [Some(url.risk), page.as_ref().map(score_of), cert.as_ref().map(score_of), headers.as_ref().map(|(r, _, _)| *r)];
+    let seen: Vec<(f32, f32)> = CHANNELS.iter().zip(risks).filter_map(|((_, w), r)| r.map(|r| (*w, r))).collect();
+    let total: f32 = CHANNELS.iter().map(|(_, w)| w).sum();
+    let weight: f32 = seen.iter().map(|(w, _)| w).sum();
+    let completeness = weight / total;
+    let mean = seen.iter().map(|(w, r)| w * r).sum::<f32>() / weight;
+    let decisive = seen.iter().map(|(_, r)| *r).filter(|r| verdict(*r) == Verdict::Block).fold(0.0, f32::max);
+    let score = mean.max(decisive).min(1.0);
+
+    let uncertainty = (url.uncertainty.max(UNCERTAINTY_FLOOR) + MISSING_BAND * (1.0 - completeness)).min(1.0);
+    let decision = Decision::from_band(score, uncertainty, &verdict);
+    let channels = CHANNELS.iter().zip(risks).fold(Json::obj(), |o, ((name, _), r)| o.with(name, r));
+    let missing: Vec<&str> = CHANNELS.iter().zip(risks).filter(|(_, r)| r.is_none()).map(|((name, _), _)| *name).collect();
+
+    Json::obj()
+        .with("url", e.url.as_str())
+        .with("channels", channels)
+        .with("missing", missing)
+        .with("completeness", completeness)
+        .with("page_report", page)
+        .with("cert_report", cert)
+        .with("headers", headers.map(|(_, _, d)| d))
+        .with("flags", flags)
+        .with("score", score)
+        .with("verdict", verdict(score).as_str())
+        .with("uncertainty", uncertainty)
+        .with("decision", decision.as_str())
+}
@@ -0,0 +1,36 @@
+// wasm-feature/crates/vigilant-core/src/compound.rs
+// Browser Vigilant — conjunctions worth a finding of their own. A punycode
+// host, a brand skeleton and a throwaway TLD are each weak on their own and
+// together almost never benign, but the combination is too rare in training
+// data for the model to learn it. Each rule names the flags and model
+// features that must all be present; a match adds its own flag on top, which
+// outranks and subsumes its parts in `ranked_signals`.
+
+use crate::named::feature_name;
+
+/// Risk a matched rule adds on top of its parts.
+pub(crate) const COMPOUND_WEIGHT: f32 = 0.25;
+
+/// Rule id (a flag, ranked in `rank::SEVERITIES`) and the flags or model
+/// feature names it needs, all of them.
+pub(crate) const COMPOUNDS: &[(&str, &[&str])] = &[
+    ("idn_brand_risky_tld",        &["is_punycode", "homoglyph_impersonation", "suspicious_tld"]),
+    ("alias_brand_risky_tld",      &["brand_alias_impersonation", "suspicious_tld"]),
+    ("brand_login_risky_tld",      &["brand_spoof_flag", "has_login_kw", "suspicious_tld"]),
+    ("ip_host_login",              &["ip_in_url", "has_login_kw"]),
+    ("upi_collect_unknown_handle", &["upi_collect_request", "suspicious_upi_vpa"]),
+    ("redirect_to_executable",     &["open_redirect", "ext_executable"]),
+];
+
+/// `name` is set: a raised flag, or a model feature above zero.
+fn present(name: &str, flags: &[&str], features: &[f32]) -> bool {
+    flags.contains(&name) || features.iter().enumerate().any(|(i, v)| *v > 0.0 && feature_name(i) == Some(name))
+}
+
+/// Rules whose parts are all present among `flags` and `features`.
+pub(crate) fn compounds(flags: &[&str], features: &[f32]) -> Vec<&'static str> {
+    COMPOUNDS.iter()
+        .filter(|(_, parts)| parts.iter().all(|p| present(p, flags, features)))
+        .map(|(id, _)| *id)
+        .collect()
+}
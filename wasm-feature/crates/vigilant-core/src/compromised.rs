@@ -0,0 +1,137 @@
+// wasm-feature/crates/vigilant-core/src/compromised.rs
+// Browser Vigilant — phishing planted on hacked legitimate sites. The host
+// is old, popular and clean, so every host-level signal (and any good
+// reputation) argues for it; the giveaways are all in the path: folders of
+// random characters, a kit's stock file names, a login form where the site
+// has none. When a popular domain shows such a path the analyzer switches to
+// this mode, which weighs the path and mostly ignores the host.
+
+use crate::json::Json;
+use crate::lexical::{pronounceability, transitions};
+use crate::psl::registrable_domain;
+use crate::score::{self, WEIGHTS};
+use crate::template::KIT_DIRS;
+use crate::verdict::Verdict;
+use crate::{extract_features, parse_url, LOGIN_KW};
+
+/// Popular registrable domains, most visited first.
+pub(crate) const POPULAR_SITES: &[&str] = &[
+    "google.com", "youtube.com", "facebook.com", "wikipedia.org", "instagram.com", "amazon.com", "twitter.com",
+    "x.com", "yahoo.com", "linkedin.com", "reddit.com", "whatsapp.com", "bing.com", "live.com", "microsoft.com",
+    "office.com", "netflix.com", "apple.com", "pinterest.com", "tiktok.com", "ebay.com", "github.com",
+    "wordpress.com", "blogspot.com", "tumblr.com", "medium.com", "dropbox.com", "adobe.com", "paypal.com",
+    "imdb.com", "cnn.com", "bbc.co.uk", "nytimes.com", "espn.com", "weather.com", "stackoverflow.com",
+    "zoom.us", "spotify.com", "booking.com", "walmart.com", "etsy.com", "craigslist.org", "quora.com",
+    "wix.com", "squarespace.com", "shopify.com", "godaddy.com", "archive.org", "mozilla.org", "salesforce.com",
+];
+
+/// File names phishing kits ship with.
+pub(crate) const KIT_FILES: &[&str] = &[
+    "login.php", "signin.php", "verify.php", "validate.php", "next.php", "post.php", "send.php", "mailer.php",
+    "index2.php", "auth.php", "billing.php", "update.php", "confirm.php", "account.php",
+];
+
+/// Popularity from which a path signal switches to this mode.
+const POPULAR_FLOOR: f32 = 0.5;
+
+/// Share of the host-level contributions kept in this mode.
+const HOST_WEIGHT: f32 = 0.25;
+
+/// Model-vector indices of host-level signals: IP host, punycode, odd port,
+/// brand spoof, brand in subdomain, trust word in domain, suspicious TLD.
+const HOST_SIGNALS: &[usize] = &[12, 13, 15, 21, 23, 25, 38];
+
+/// Path signals and their weight in this mode.
+const PATH_WEIGHTS: &[(&str, f32)] = &[
+    ("kit_fingerprint_path", 0.35),
+    ("deep_random_path", 0.3),
+    ("login_form_on_compromised_path", 0.3),
+];
+
+/// Path depth from which a random segment counts as buried.
+const MIN_DEPTH: usize = 3;
+
+/// Popularity rank of `host`'s registrable domain: 1 for the most visited
+/// site, falling to [`POPULAR_FLOOR`] at the end of the list; 0 when
+/// unlisted.
+pub fn popularity_rank(host: &str) -> f32 {
+    let reg = registrable_domain(&host.trim_end_matches('.').to_lowercase());
+    match POPULAR_SITES.iter().position(|s| *s == reg) {
+        Some(i) => 1.0 - POPULAR_FLOOR * i as f32 / POPULAR_SITES.len() as f32,
+        None => 0.0,
+    }
+}
+
+/// A segment no one would name by hand: long, alphanumeric, and without
+/// vowels, with a long consonant run, or flipping between letters and digits.
+fn random_segment(seg: &str) -> bool {
+    if seg.len() < 8 || !seg.bytes().all(|b| b.is_ascii_alphanumeric()) { return false; }
+    let [consonants, digits, vowels] = pronounceability(seg);
+    let (switches, _) = transitions(seg);
+    (vowels < 0.2 && digits < seg.len() as f32) || consonants >= 5.0 || switches >= 3
+}
+
+/// Path signals of `url` that point to a planted kit: a CMS directory with a
+/// kit file or sign-in word, and a sign-in page under a random folder deep
+/// in the path (opaque ids alone are how big sites name documents). A
+/// login form only counts alongside another.
+pub(crate) fn path_signals(url: &str, login_form: bool) -> Vec<&'static str> {
+    let path = parse_url(url).path.to_lowercase();
+    let segs: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let mut flags: Vec<&'static str> = Vec::new();
+    let last = segs.last().copied().unwrap_or("");
+    let login = |s: &&str| LOGIN_KW.iter().any(|k| s.contains(k));
+    if segs.iter().any(|s| KIT_DIRS.contains(s)) && (KIT_FILES.contains(&last) || segs.iter().any(login)) {
+        flags.push("kit_fingerprint_path");
+    }
+    let random = segs.iter().position(|s| random_segment(s));
+    if segs.len() >= MIN_DEPTH && random.is_some_and(|i| segs[i + 1..].iter().any(login)) { flags.push("deep_random_path"); }
+    if login_form && !flags.is_empty() { flags.push("login_form_on_compromised_path"); }
+    flags
+}
+
+/// Path signals and their combined weight when `url` is on a popular site
+/// and its path looks planted; `None` leaves the standard scoring.
+pub(crate) fn compromised_mode(url: &str, login_form: bool) -> Option<(Vec<&'static str>, f32)> {
+    if popularity_rank(&parse_url(url).host) < POPULAR_FLOOR { return None; }
+    let flags = path_signals(url, login_form);
+    if flags.is_empty() { return None; }
+    let weight = flags.iter().filter_map(|f| PATH_WEIGHTS.iter().find(|(n, _)| n == f)).map(|(_, w)| w).sum();
+    Some((flags, weight))
+}
+
+/// Scale the host-level entries of `contributions` (as `score::contributions`
+/// orders them) down to [`HOST_WEIGHT`]: the host's standing is what the
+/// kit borrows.
+pub(crate) fn damp_host(contributions: &mut [f32]) {
+    for (c, (i, _)) in contributions.iter_mut().zip(WEIGHTS) {
+        if HOST_SIGNALS.contains(i) { *c *= HOST_WEIGHT; }
+    }
+}
+
+/// Score `url` for phishing planted on a hacked popular site;
+/// `login_form` says whether the page shows a password form. Returns `{
+/// mode, site, popularity, flags, score, verdict }`, where `mode` is
+/// "compromised_site" when the host is popular and the path looks planted,
+/// else "standard" with the plain URL score.
+pub fn compromised_site_report(url: &str, login_form: bool) -> Json {
+    let host = parse_url(url).host.to_lowercase();
+    let popularity = popularity_rank(&host);
+    let mut signals = score::contributions(&extract_features(url));
+    let (mode, flags) = match compromised_mode(url, login_form) {
+        Some((flags, weight)) => {
+            damp_host(&mut signals);
+            signals.push(weight);
+            ("compromised_site", flags)
+        }
+        None => ("standard", Vec::new()),
+    };
+    let risk = signals.iter().sum::<f32>().min(1.0);
+    Json::obj()
+        .with("mode", mode)
+        .with("site", registrable_domain(&host))
+        .with("popularity", popularity)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
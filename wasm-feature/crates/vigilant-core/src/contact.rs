@@ -0,0 +1,274 @@
+// wasm-feature/crates/vigilant-core/src/contact.rs
+// Browser Vigilant — links that open a mail client or a dialer. A kit that
+// can't host a login page asks for a reply instead: `mailto:` to
+// `support@paypa1-help.com` behind a button reading "support@paypal.com",
+// or `tel:` to a "fraud desk" that is a premium-rate line reached through
+// a foreign prefix, or a USSD code that forwards the victim's calls.
+// The recipients and the number are read here rather than in `parse_url`,
+// whose split has to stay the one `features.py` trains on; the features
+// mark look-alike mail domains and the number patterns vishing relies on.
+
+use crate::display::homoglyph_brand;
+use crate::json::Json;
+use crate::mail::FREEMAIL;
+use crate::numbrand::numeric_brand;
+use crate::psl::registrable_domain;
+use crate::scheme::scheme_of;
+use crate::typosquat::damerau;
+use crate::verdict::Verdict;
+use crate::{percent_decode, BRANDS};
+
+/// Values returned by [`contact_features`]: mailto_lookalike_domain,
+/// mailto_display_spoof, mailto_brand_freemail, tel_premium_rate,
+/// tel_international, tel_high_risk_prefix, tel_ussd.
+pub const CONTACT_FEATURES: usize = 7;
+
+/// International prefixes (digits after `+`) of premium-rate and
+/// shared-cost ranges.
+pub(crate) const PREMIUM_PREFIXES: &[&str] = &[
+    "1900", "1976", "449", "4487", "4470", "49900", "49137", "3389", "34803", "34806", "34807", "34905", "39899", "39892",
+    "61190", "431900", "41900", "41906", "32900", "31900", "31906", "31909",
+];
+
+/// International prefixes used for revenue-share fraud: satellite and
+/// international networks, and regions with high termination rates.
+pub(crate) const HIGH_RISK_PREFIXES: &[&str] = &[
+    "881", "882", "883", "870", "53", "252", "232", "222", "225", "247", "290", "675", "682", "683", "688", "690", "691", "692",
+];
+
+/// Brands at most this many edits from a mail domain's label count as the
+/// one it imitates.
+const MAX_LOOKALIKE_EDITS: usize = 2;
+
+/// Shortest label the edit distance is tried on; shorter ones go through
+/// the exact numeric-brand table.
+const MIN_LOOKALIKE_LABEL: usize = 5;
+
+/// `(display, address)` of a mailto recipient written `Name <a@b>` or bare.
+fn split_display(recipient: &str) -> (Option<String>, String) {
+    let r = recipient.trim();
+    match (r.find('<'), r.rfind('>')) {
+        (Some(s), Some(e)) if s < e => (Some(r[..s].trim().trim_matches('"').to_string()).filter(|d| !d.is_empty()), r[s + 1..e].trim().to_string()),
+        _ => (None, r.to_string()),
+    }
+}
+
+/// Recipients of a `mailto:` URL, decoded: the path's, then `to`'s.
+fn recipients(body: &str) -> Vec<(Option<String>, String)> {
+    let (to, query) = body.split_once('?').unwrap_or((body, ""));
+    let extra = query.split('&').filter_map(|kv| kv.split_once('=')).filter(|(k, _)| k.eq_ignore_ascii_case("to")).map(|(_, v)| v);
+    std::iter::once(to).chain(extra)
+        .flat_map(|list| percent_decode(list, false).split(',').map(str::to_string).collect::<Vec<_>>())
+        .filter(|r| !r.trim().is_empty())
+        .map(|r| split_display(&r))
+        .collect()
+}
+
+fn domain_of(address: &str) -> String { address.rsplit_once('@').map_or("", |(_, d)| d).trim().trim_end_matches('.').to_lowercase() }
+
+/// Number a `tel:` URL dials: digits, a leading `+`, and `*`/`#` of a
+/// service code; visual separators and `;` parameters dropped.
+fn dialed(body: &str) -> String {
+    let number = percent_decode(body.split(';').next().unwrap_or(""), false);
+    number.chars().filter(|c| c.is_ascii_digit() || matches!(c, '+' | '*' | '#')).collect()
+}
+
+/// Digits after the international prefix (`+` or `00`), or the national
+/// number of a NANP `1-900` style one; `None` for other national numbers.
+fn international(number: &str) -> Option<&str> {
+    if let Some(n) = number.strip_prefix('+').or_else(|| number.strip_prefix("00")) { return Some(n); }
+    // North America dials premium numbers as 1-900-…
+    (number.len() == 11 && number.starts_with('1')).then_some(number)
+}
+
+/// Brand a mail domain imitates: a look-alike spelling, letters swapped
+/// for homoglyphs, or within [`MAX_LOOKALIKE_EDITS`] of a brand while not
+/// being it.
+fn lookalike_brand(domain: &str) -> Option<String> {
+    let reg = registrable_domain(domain);
+    let label = reg.split('.').next().unwrap_or("");
+    if label.is_empty() || BRANDS.contains(&label) { return None; }
+    if let Some((b, _, _)) = numeric_brand(domain, label) { return Some(b.to_string()); }
+    if let Some(b) = homoglyph_brand(domain, &[]) { return Some(b); }
+    if label.chars().count() < MIN_LOOKALIKE_LABEL { return None; }
+    BRANDS.iter().find(|b| b.len() >= MIN_LOOKALIKE_LABEL && damerau(label, b) <= MAX_LOOKALIKE_EDITS).map(|b| b.to_string())
+}
+
+/// Brand named in `text` (a local part or display name), lowercased.
+fn brand_named(text: &str) -> Option<&'static str> {
+    let low: String = text.to_lowercase().chars().filter(|c| c.is_alphanumeric()).collect();
+    BRANDS.iter().copied().filter(|b| b.len() >= MIN_LOOKALIKE_LABEL).find(|b| low.contains(b))
+}
+
+/// What one `mailto:` or `tel:` link targets.
+struct Target {
+    kind: &'static str,
+    recipients: Vec<(Option<String>, String)>,
+    number: String,
+}
+
+impl Target {
+    fn of(url: &str) -> Option<Target> {
+        let scheme = scheme_of(url);
+        let body = url.trim_start().get(scheme.len() + 1..).unwrap_or("");
+        match scheme.as_str() {
+            "mailto" => Some(Target { kind: "mailto", recipients: recipients(body), number: String::new() }),
+            "tel" => Some(Target { kind: "tel", recipients: Vec::new(), number: dialed(body) }),
+            _ => None,
+        }
+    }
+
+    /// First recipient whose domain imitates a brand, with the brand.
+    fn lookalike(&self) -> Option<(String, String)> {
+        self.recipients.iter().find_map(|(_, a)| { let d = domain_of(a); lookalike_brand(&d).map(|b| (d, b)) })
+    }
+
+    /// First recipient whose display name is itself an address on another
+    /// domain: `"support@paypal.com" <x@evil.tk>`.
+    fn display_spoof(&self) -> Option<String> {
+        self.recipients.iter().find_map(|(shown, a)| {
+            let shown = shown.as_deref().filter(|s| s.contains('@'))?;
+            (registrable_domain(&domain_of(shown)) != registrable_domain(&domain_of(a))).then(|| shown.to_string())
+        })
+    }
+
+    /// First freemail recipient whose name claims a brand, with the brand.
+    fn brand_freemail(&self) -> Option<(String, &'static str)> {
+        self.recipients.iter().find_map(|(shown, a)| {
+            let d = domain_of(a);
+            if !FREEMAIL.contains(&d.as_str()) { return None; }
+            let local = a.rsplit_once('@').map_or("", |(l, _)| l);
+            brand_named(local).or_else(|| shown.as_deref().and_then(brand_named)).map(|b| (d, b))
+        })
+    }
+
+    fn premium(&self) -> bool { international(&self.number).is_some_and(|n| PREMIUM_PREFIXES.iter().any(|p| n.starts_with(p))) }
+
+    fn international(&self) -> bool {
+        let n = self.number.strip_prefix('+').or_else(|| self.number.strip_prefix("00"));
+        n.is_some_and(|n| n.starts_with(|c: char| c.is_ascii_digit()))
+    }
+
+    fn high_risk(&self) -> bool {
+        self.international() && international(&self.number).is_some_and(|n| HIGH_RISK_PREFIXES.iter().any(|p| n.starts_with(p)))
+    }
+
+    fn ussd(&self) -> bool { self.number.contains(['*', '#']) }
+
+    fn features(&self) -> [f32; CONTACT_FEATURES] {
+        let b = |v: bool| if v { 1.0 } else { 0.0 };
+        [
+            b(self.lookalike().is_some()), b(self.display_spoof().is_some()), b(self.brand_freemail().is_some()),
+            b(self.premium()), b(self.international()), b(self.high_risk()), b(self.ussd()),
+        ]
+    }
+}
+
+/// `[mailto_lookalike_domain, mailto_display_spoof, mailto_brand_freemail,
+/// tel_premium_rate, tel_international, tel_high_risk_prefix, tel_ussd]`:
+/// a mailto recipient on a domain imitating a brand, one whose display name
+/// is an address on another domain, and a freemail address named after a
+/// brand; a tel number in a premium-rate range, dialed internationally, in
+/// a range used for revenue-share fraud, and carrying a USSD service code
+/// (`*`, `#`). All 0 for other schemes.
+pub fn contact_features(url: &str) -> [f32; CONTACT_FEATURES] {
+    Target::of(url).map_or([0.0; CONTACT_FEATURES], |t| t.features())
+}
+
+/// Analyze a `mailto:` or `tel:` link, with `link_text` what the page shows
+/// for it ("" when unknown); text that names another address or number
+/// than the link dials is a mismatch. Returns `{ kind, recipients: [{
+/// address, display }], domain, brand, number, shown, features, flags,
+/// score, verdict }`; `kind` is null, and only it, `flags`, `score` and
+/// `verdict` are set, for other links.
+pub fn contact_link_report(href: &str, link_text: &str) -> Json {
+    let Some(t) = Target::of(href) else {
+        return Json::obj().with("kind", Json::Null).with("flags", Vec::<&str>::new()).with("score", 0.0f32).with("verdict", Verdict::from_score(0.0).as_str());
+    };
+    let text = link_text.trim();
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+    let lookalike = t.lookalike();
+    let freemail = t.brand_freemail();
+    if lookalike.is_some() { flags.push("mailto_lookalike_domain"); risk += 0.5; }
+    if t.display_spoof().is_some() { flags.push("mailto_display_spoof"); risk += 0.5; }
+    if freemail.is_some() { flags.push("mailto_brand_freemail"); risk += 0.4; }
+    if t.premium() { flags.push("tel_premium_rate"); risk += 0.5; }
+    if t.high_risk() { flags.push("tel_high_risk_prefix"); risk += 0.4; }
+    else if t.international() { flags.push("tel_international"); risk += 0.1; }
+    if t.ussd() { flags.push("tel_ussd"); risk += 0.6; }
+
+    // the visible text shows an address or number the link doesn't go to
+    let shown_digits: String = text.chars().filter(|c| c.is_ascii_digit()).collect();
+    let dialed_digits: String = t.number.chars().filter(|c| c.is_ascii_digit()).collect();
+    let mismatch = match t.kind {
+        "mailto" => text.contains('@') && !t.recipients.iter().any(|(_, a)| a.eq_ignore_ascii_case(text)),
+        _ => shown_digits.len() >= 6 && !dialed_digits.ends_with(&shown_digits) && !shown_digits.ends_with(&dialed_digits),
+    };
+    if mismatch { flags.push("contact_text_mismatch"); risk += 0.4; }
+
+    let risk = risk.min(1.0);
+    let (domain, brand) = match (&lookalike, &freemail) {
+        (Some((d, b)), _) => (Some(d.clone()), Some(b.clone())),
+        (None, Some((d, b))) => (Some(d.clone()), Some(b.to_string())),
+        _ => (t.recipients.first().map(|(_, a)| domain_of(a)).filter(|d| !d.is_empty()), None),
+    };
+    let recipients: Vec<Json> = t.recipients.iter().map(|(shown, a)| Json::obj().with("address", a.as_str()).with("display", shown.as_deref())).collect();
+    Json::obj()
+        .with("kind", t.kind)
+        .with("recipients", recipients)
+        .with("domain", domain)
+        .with("brand", brand)
+        .with("number", Some(t.number.as_str()).filter(|n| !n.is_empty()))
+        .with("shown", Some(text).filter(|s| !s.is_empty()))
+        .with("features", t.features().to_vec())
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(href: &str) -> Vec<String> { contact_link_report(href, "").str_list("flags") }
+
+    fn first_address(href: &str) -> Option<String> {
+        let r = contact_link_report(href, "");
+        r.get("recipients").and_then(Json::as_array).and_then(|r| r.first()).map(|a| a.str_field("address").to_string())
+    }
+
+    #[test]
+    fn an_empty_mailto_has_no_recipients() {
+        for href in ["mailto:", "mailto:?subject=hi", "mailto:?to=", "mailto:%20,%20", "mailto:Support <>"] {
+            let r = contact_link_report(href, "");
+            assert_eq!(r.str_field("kind"), "mailto", "{}", href);
+            assert!(matches!(r.get("domain"), Some(Json::Null)), "{}", href);
+            assert_eq!(contact_features(href), [0.0; CONTACT_FEATURES], "{}", href);
+        }
+        assert!(contact_link_report("mailto:", "").get("recipients").and_then(Json::as_array).is_some_and(|r| r.is_empty()));
+    }
+
+    #[test]
+    fn bad_percent_escapes_stay_literal() {
+        assert_eq!(first_address("mailto:help%zz@paypa1.com%").as_deref(), Some("help%zz@paypa1.com%"));
+        assert_eq!(first_address("mailto:a%40b").as_deref(), Some("a@b"));
+        assert_eq!(contact_link_report("tel:%2B%4", "").str_field("number"), "+4");
+    }
+
+    #[test]
+    fn a_tel_without_digits_dials_nothing() {
+        for href in ["tel:", "tel:;phone-context=example.com", "tel:()-", "tel:+", "tel:%2B", "tel:00"] {
+            assert!(flags(href).is_empty(), "{}: {:?}", href, flags(href));
+            assert_eq!(contact_features(href), [0.0; CONTACT_FEATURES], "{}", href);
+        }
+        assert!(matches!(contact_link_report("tel:", "").get("number"), Some(Json::Null)));
+    }
+
+    #[test]
+    fn other_schemes_are_not_contact_links() {
+        for href in ["https://example.com/mailto:a@b", "mail:a@b", "", "tel"] {
+            assert!(matches!(contact_link_report(href, "").get("kind"), Some(Json::Null)), "{}", href);
+        }
+    }
+}
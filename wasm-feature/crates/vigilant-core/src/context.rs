@@ -0,0 +1,657 @@
+// wasm-feature/crates/vigilant-core/src/context.rs
+// Browser Vigilant — isolated tenants: each context owns its analyzer
+// configuration, overrides, feeds and State, so a managed work profile and a
+// personal profile in one process never see each other's data.
+
+use std::collections::{BTreeMap, HashMap};
+use crate::analyzer::{Analyzer, Profile};
+use crate::api::UrlReport;
+use crate::audit::{AuditLog, ENGINE_VERSION};
+use crate::brands;
+use crate::chunks;
+use crate::deeplink::deep_link_report;
+use crate::dnr;
+use crate::hash::Privacy;
+use crate::json::Json;
+use crate::managed::ManagedPolicy;
+use crate::mining::{HardNegative, HardNegatives, MiningSource, MIN_DISAGREEMENT};
+use crate::normalize::normalize_url;
+use crate::overrides::{match_keys, OverrideRule};
+use crate::policy::download_context_report;
+use crate::signals;
+use crate::state::{State, KEY_CHECK};
+use crate::timing::Stages;
+use crate::types::{err, ContextConfig, Fields, FromJson, SchemaError, ToJson, SCHEMA_VERSION};
+use crate::userdata;
+use crate::verdict::{Decision, Verdict};
+use crate::{parse_url, UNCERTAINTY_FLOOR};
+use crate::personal::Personalizer;
+
+/// Handle of the context that exists from start-up and can't be destroyed.
+pub const DEFAULT_CONTEXT: u32 = 0;
+
+fn norm_host(host: &str) -> String { host.trim().trim_end_matches('.').to_lowercase() }
+
+/// `host` followed by each parent domain (`a.b.com`, `b.com`, `com`).
+fn host_and_parents(host: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(host), |h| h.split_once('.').map(|(_, rest)| rest)).filter(|h| !h.is_empty())
+}
+
+#[derive(Debug, Default)]
+pub struct Context {
+    analyzer:  Analyzer,
+    state:     State,
+    overrides: HashMap<String, Verdict>,
+    policy:    ManagedPolicy,
+    audit:     AuditLog,
+    /// Feed name → hosts it lists.
+    feeds:     HashMap<String, Vec<String>>,
+    personal:  Personalizer,
+    mined:     HardNegatives,
+}
+
+/// Sample lines one [`Context::simulate_policy`] call reads.
+pub const MAX_SIMULATION_URLS: usize = 10_000;
+
+/// Changed URLs a simulation lists as examples.
+const SIMULATION_EXAMPLES: usize = 20;
+
+/// Shift the personal layer must make to a score before reports flag it.
+const PERSONAL_FLAG_DELTA: f32 = 0.05;
+
+impl Context {
+    pub fn new(analyzer: Analyzer) -> Context {
+        Context { analyzer, ..Context::default() }
+    }
+
+    pub fn from_config(c: &ContextConfig) -> Context {
+        let mut b = Analyzer::builder().brands(&c.brands).keywords(&c.keywords).psl(c.psl).caps(c.caps).profile(c.profile);
+        for &p in &c.keyword_packs { b = b.keyword_pack(p); }
+        let mut ctx = Context { analyzer: b.build(), state: State::with_feature_capacity(c.feature_cache_entries), ..Context::default() };
+        if let Some(salt) = &c.privacy_salt { ctx.set_privacy(Privacy::hashed(salt.as_bytes())); }
+        ctx.state.set_template_learning(c.learn_templates);
+        for h in &c.allow { ctx.set_override(h, Verdict::Allow); }
+        for h in &c.block { ctx.set_override(h, Verdict::Block); }
+        ctx
+    }
+
+    pub fn analyzer(&self) -> &Analyzer { &self.analyzer }
+
+    /// Replace the brands this context protects beyond the built-in list.
+    /// Each name is 3–63 ASCII letters, digits or hyphens, at most
+    /// [`MAX_RUNTIME_BRANDS`](crate::MAX_RUNTIME_BRANDS) of them; on an
+    /// error nothing changes. Other contexts keep their own lists.
+    pub fn set_brands(&mut self, brands: &[&str]) -> Result<(), String> { self.update_brands(brands, true) }
+
+    /// Add `brands` to the ones this context protects (see [`Context::set_brands`]).
+    pub fn add_brands(&mut self, brands: &[&str]) -> Result<(), String> { self.update_brands(brands, false) }
+
+    fn update_brands(&mut self, brands: &[&str], replace: bool) -> Result<(), String> {
+        let next = brands::merge(self.analyzer.brands(), brands, replace)?;
+        let a = &self.analyzer;
+        self.analyzer = Analyzer::builder().brands(&next).keywords(a.keywords()).psl(a.uses_psl()).caps(a.caps()).profile(a.profile()).build();
+        self.state.clear_reports();
+        Ok(())
+    }
+
+    pub fn state(&self) -> &State { &self.state }
+
+    pub fn state_mut(&mut self) -> &mut State { &mut self.state }
+
+    pub fn audit(&self) -> &AuditLog { &self.audit }
+
+    pub fn audit_mut(&mut self) -> &mut AuditLog { &mut self.audit }
+
+    pub fn policy(&self) -> &ManagedPolicy { &self.policy }
+
+    pub fn privacy(&self) -> &Privacy { self.state.privacy() }
+
+    /// Switch to (or out of) hash-only mode. Everything keyed by identifier —
+    /// overrides, feeds, cached state and the audit log — is dropped, since
+    /// it can't be re-keyed; callers reload feeds and overrides afterwards.
+    pub fn set_privacy(&mut self, privacy: Privacy) {
+        self.overrides.clear();
+        self.feeds.clear();
+        self.audit.set_privacy(privacy.clone());
+        self.mined.set_privacy(privacy.clone());
+        self.state.set_privacy(privacy);
+    }
+
+    fn key(&self, host: &str) -> String { self.state.privacy().key(host) }
+
+    /// Install the admin policy; user overrides it locks out are dropped.
+    /// Hashed overrides can't be matched against locked domains, so they're
+    /// kept and filtered at lookup time instead.
+    pub fn set_policy(&mut self, policy: ManagedPolicy) {
+        if !self.privacy().is_hashed() { self.overrides.retain(|h, _| policy.user_override_allowed(h)); }
+        self.policy = policy;
+    }
+
+    /// Pin the sites `rule` covers to `verdict`: a host and its subdomains,
+    /// `*.host` for the subdomains only, either with a path prefix such as
+    /// `example.com/admin` (see [`OverrideRule`]). The most specific host
+    /// wins, then the longest path prefix, and a tie goes to the stricter
+    /// verdict. Returns false for a malformed rule or when the managed
+    /// policy doesn't allow it.
+    pub fn set_override(&mut self, rule: &str, verdict: Verdict) -> bool {
+        let Ok(r) = OverrideRule::parse(rule) else { return false };
+        if !self.policy.user_override_allowed(&r.host) { return false; }
+        let k = self.key(&r.key());
+        self.overrides.insert(k, verdict);
+        true
+    }
+
+    pub fn clear_override(&mut self, rule: &str) -> bool {
+        let Ok(r) = OverrideRule::parse(rule) else { return false };
+        let k = self.key(&r.key());
+        self.overrides.remove(&k).is_some()
+    }
+
+    /// Override that settles `host` regardless of path.
+    pub fn override_for(&self, host: &str) -> Option<Verdict> { self.override_at(&norm_host(host), "") }
+
+    /// Override that settles `path` on `host`, by the precedence of [`Context::set_override`].
+    pub fn override_at(&self, host: &str, path: &str) -> Option<Verdict> {
+        match_keys(host, path).into_iter()
+            .map(|(_, keys)| keys.iter().filter_map(|k| self.overrides.get(&self.key(k)).copied()).max())
+            .find(Option::is_some)
+            .flatten()
+    }
+
+    /// Replace the host list of feed `name`.
+    pub fn load_feed<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, name: &str, hosts: I) -> usize {
+        let mut list: Vec<String> = hosts.into_iter()
+            .map(|h| norm_host(h.as_ref()))
+            .filter(|h| !h.is_empty())
+            .map(|h| self.key(&h))
+            .collect();
+        list.sort();
+        list.dedup();
+        let n = list.len();
+        self.feeds.insert(name.to_string(), list);
+        n
+    }
+
+    pub fn feed_match(&self, host: &str) -> Option<&str> {
+        let h = norm_host(host);
+        self.feeds.iter()
+            .find(|(_, list)| host_and_parents(&h).any(|d| list.binary_search(&self.key(d)).is_ok()))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// URL verdict under this context: cached analyzer result through the
+    /// personal layer, then feeds, stored reputation and registered signal
+    /// providers, then the managed policy and finally user overrides where
+    /// the policy permits them. Feeds, a stored reputation, admin rules and
+    /// overrides settle the question, so they leave no uncertainty and never
+    /// ask to escalate. Everything but the analyzer report sees the URL in
+    /// its [`normalize_url`] form.
+    pub fn url(&mut self, url: &str) -> UrlReport {
+        let mut t = Stages::start();
+        let canon = normalize_url(url);
+        let p = parse_url(&canon);
+        let custom = signals::extract(&p, self);
+        t.mark("signals");
+        let add: f32 = custom.iter().map(|s| s.contribution).sum();
+        let mut r = self.assess(url, &canon, &p.host, add, true, &mut t);
+        r.signals = custom;
+        r.verdict = self.verdict_for(r.risk);
+        r.decision = Decision::from_band(r.risk, r.uncertainty, |s| self.verdict_for(s));
+        if let Some((v, flag)) = self.settled(&p.host, &p.path, r.verdict) {
+            r.flags.push(flag);
+            r.verdict = v;
+            r.uncertainty = 0.0;
+            r.decision = if v == Verdict::Allow { Decision::Safe } else { Decision::Unsafe };
+        }
+        t.mark("context");
+        let mut spans = t.into_spans();
+        if !spans.is_empty() {
+            spans.splice(1..1, r.timings.drain(..));
+            r.timings = spans;
+        }
+        r
+    }
+
+    /// Risk of `url` before the policy and overrides have their say: the
+    /// cached analyzer report through the personal layer, feeds, stored
+    /// reputation, template anomalies of `canon` (only when `learn`, which
+    /// also learns the template) and `signal_risk` from the signal providers.
+    fn assess(&mut self, url: &str, canon: &str, host: &str, signal_risk: f32, learn: bool, t: &mut Stages) -> UrlReport {
+        let mut r = self.state.url(&self.analyzer, url);
+        t.skip();
+        let global = r.risk;
+        r.risk = self.personal.score(global, &r.features);
+        if (r.risk - global).abs() >= PERSONAL_FLAG_DELTA { r.flags.push("personal_adjustment"); }
+        let fed = self.feed_match(host).is_some();
+        if fed { r.flags.push("threat_feed_match"); r.risk = r.risk.max(0.9); }
+        let rep = self.state.reputation(host);
+        if let Some(rep) = rep {
+            if rep >= 0.5 { r.flags.push("bad_reputation"); }
+            r.risk = r.risk.max(rep);
+        }
+        if let Some(flag) = learn.then(|| self.state.template_anomaly(canon)).flatten() {
+            let add = if flag == "kit_path_on_known_site" { 0.35 } else { 0.2 };
+            r.flags.push(flag);
+            r.risk = (r.risk + add).min(1.0);
+            r.uncertainty = r.uncertainty.max(UNCERTAINTY_FLOOR + 0.5 * add);
+        }
+        if signal_risk > 0.0 {
+            r.risk = (r.risk + signal_risk).min(1.0);
+            r.uncertainty = r.uncertainty.max(UNCERTAINTY_FLOOR + 0.5 * signal_risk.min(1.0));
+        }
+        // a feed hit or a stored lookup result is the answer escalation would fetch
+        if fed || rep.is_some() { r.uncertainty = 0.0; }
+        r
+    }
+
+    /// The analyzer report of `url` from this context's cache, before the
+    /// personal layer, feeds, reputation and overrides.
+    pub fn analyzer_report(&mut self, url: &str) -> UrlReport { self.state.url(&self.analyzer, url) }
+
+    /// [`State::features_with_time`] under this context's analyzer.
+    pub fn features_with_time(&mut self, url: &str, local_ms: f64) -> Vec<f32> {
+        self.state.features_with_time(&self.analyzer, url, local_ms)
+    }
+
+    /// [`Context::url`]'s verdict from the per-template memo
+    /// ([`State::memo_risk`]) for high-volume callers such as a request
+    /// observer. Feeds, reputation, managed rules and overrides still apply
+    /// on every call; the personal layer, template learning and signal
+    /// providers need a full report and are left to `url`.
+    pub fn memo_verdict(&mut self, url: &str) -> Verdict {
+        let p = parse_url(&normalize_url(url));
+        let host = p.host;
+        let mut risk = self.state.memo_risk(&self.analyzer, url);
+        if self.feed_match(&host).is_some() { risk = risk.max(0.9); }
+        if let Some(rep) = self.state.reputation(&host) { risk = risk.max(rep); }
+        let verdict = self.verdict_for(risk);
+        self.settled(&host, &p.path, verdict).map_or(verdict, |(v, _)| v)
+    }
+
+    /// Verdict a managed rule or user override imposes on `path` at `host`
+    /// over `verdict`, with the flag naming it.
+    fn settled(&self, host: &str, path: &str, verdict: Verdict) -> Option<(Verdict, &'static str)> {
+        self.settled_under(&self.policy, host, path, verdict)
+    }
+
+    fn settled_under(&self, policy: &ManagedPolicy, host: &str, path: &str, verdict: Verdict) -> Option<(Verdict, &'static str)> {
+        match policy.host_rule(host, path) {
+            Some(Verdict::Allow) => Some((Verdict::Allow, "managed_allow")),
+            Some(v) => Some((v, "managed_block")),
+            None if !policy.user_override_allowed(host) => None,
+            None => match self.override_at(host, path) {
+                Some(Verdict::Allow) => Some((Verdict::Allow, "allowlisted")),
+                Some(v) => Some((verdict.max(v), "blocklisted")),
+                None => None,
+            },
+        }
+    }
+
+    /// Verdict for `score` under the managed thresholds, else the analyzer's profile.
+    fn verdict_for(&self, score: f32) -> Verdict { self.verdict_under(&self.policy, score) }
+
+    fn verdict_under(&self, policy: &ManagedPolicy, score: f32) -> Verdict {
+        policy.verdict(score, self.analyzer.profile().verdict(score))
+    }
+
+    /// Preview `policy` before installing it: score each URL of `jsonl` (a
+    /// JSON string or `{ url }` per line) as [`Context::url`] would and set
+    /// its verdict under the installed policy against the one `policy` would
+    /// give. Site templates are neither learned nor consulted and nothing is
+    /// audited. Reads the first [`MAX_SIMULATION_URLS`] non-blank lines and
+    /// returns `{ urls, skipped, errors: [{ line, error }], current: { allow,
+    /// warn, block }, proposed: { allow, warn, block }, changed, raised,
+    /// lowered, transitions: [{ from, to, count }], examples: [{ url, risk,
+    /// from, to }] }`, the first [`SIMULATION_EXAMPLES`] changed URLs as
+    /// examples.
+    pub fn simulate_policy(&mut self, policy: &ManagedPolicy, jsonl: &str) -> Json {
+        let (mut current, mut proposed) = ([0u64; 3], [0u64; 3]);
+        let mut transitions: BTreeMap<(Verdict, Verdict), u64> = BTreeMap::new();
+        let (mut urls, mut skipped) = (0u64, 0u64);
+        let (mut errors, mut examples) = (Vec::new(), Vec::new());
+        for (n, (i, line)) in jsonl.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).enumerate() {
+            if n >= MAX_SIMULATION_URLS { skipped += 1; continue; }
+            let url = match Json::parse(line.trim()) {
+                Ok(Json::Str(u)) => u,
+                Ok(v) => match v.get("url").and_then(Json::as_str) {
+                    Some(u) => u.to_string(),
+                    None => { errors.push((i + 1, "expected a URL string or { url }".to_string())); continue; }
+                },
+                Err(e) => { errors.push((i + 1, e)); continue; }
+            };
+            urls += 1;
+            let canon = normalize_url(&url);
+            let p = parse_url(&canon);
+            let add: f32 = signals::extract(&p, self).iter().map(|s| s.contribution).sum();
+            let r = self.assess(&url, &canon, &p.host, add, false, &mut Stages::start());
+            let under = |policy: &ManagedPolicy| {
+                let v = self.verdict_under(policy, r.risk);
+                self.settled_under(policy, &p.host, &p.path, v).map_or(v, |(v, _)| v)
+            };
+            let (now, then) = (under(&self.policy), under(policy));
+            current[now as usize] += 1;
+            proposed[then as usize] += 1;
+            if now != then {
+                *transitions.entry((now, then)).or_default() += 1;
+                if examples.len() < SIMULATION_EXAMPLES {
+                    examples.push(Json::obj().with("url", url.as_str()).with("risk", r.risk).with("from", now.as_str()).with("to", then.as_str()));
+                }
+            }
+        }
+        let counts = |c: [u64; 3]| Json::obj().with("allow", c[0]).with("warn", c[1]).with("block", c[2]);
+        let (raised, lowered) = transitions.iter().fold((0, 0), |(up, down), ((a, b), n)| if b > a { (up + n, down) } else { (up, down + n) });
+        Json::obj()
+            .with("urls", urls)
+            .with("skipped", skipped)
+            .with("errors", errors.iter().map(|(l, e)| Json::obj().with("line", *l).with("error", e.as_str())).collect::<Vec<_>>())
+            .with("current", counts(current))
+            .with("proposed", counts(proposed))
+            .with("changed", raised + lowered)
+            .with("raised", raised)
+            .with("lowered", lowered)
+            .with("transitions", transitions.iter().map(|((a, b), n)| Json::obj().with("from", a.as_str()).with("to", b.as_str()).with("count", *n)).collect::<Vec<_>>())
+            .with("examples", examples)
+    }
+
+    pub fn personal(&self) -> &Personalizer { &self.personal }
+
+    /// Teach the personal layer the user's decision on `url`: `Allow` for a
+    /// false positive, `Block` for a miss. A decision overturning a block (or
+    /// an allow) of the global score is kept as a hard negative. Returns the
+    /// URL's new personal score, or `None` for `Warn`, which says nothing
+    /// either way.
+    pub fn personal_feedback(&mut self, url: &str, verdict: Verdict) -> Option<f32> {
+        if verdict == Verdict::Warn { return None; }
+        let r = self.state.url(&self.analyzer, url);
+        let block = verdict == Verdict::Block;
+        let scored = self.analyzer.profile().verdict(r.risk);
+        if (block && scored == Verdict::Allow) || (!block && scored == Verdict::Block) {
+            self.mined.record(url, &r.features, block, r.risk, MiningSource::Feedback);
+        }
+        Some(self.personal.learn(r.risk, &r.features, block))
+    }
+
+    /// Compare the loaded model's `model_score` for `url` with this
+    /// context's verdict. When one blocks what the other allows and the
+    /// scores are at least [`MIN_DISAGREEMENT`] apart, the URL is kept as a
+    /// hard negative labelled by the context and true is returned.
+    pub fn model_disagreement(&mut self, url: &str, model_score: f32) -> bool {
+        let r = self.url(url);
+        let model = self.verdict_for(model_score);
+        let opposed = matches!((r.verdict, model), (Verdict::Allow, Verdict::Block) | (Verdict::Block, Verdict::Allow));
+        if !opposed || (r.risk - model_score).abs() < MIN_DISAGREEMENT { return false; }
+        self.mined.record(url, &r.features, r.verdict == Verdict::Block, model_score, MiningSource::Disagreement);
+        true
+    }
+
+    pub fn hard_negatives(&self) -> &HardNegatives { &self.mined }
+
+    pub fn hard_negatives_mut(&mut self) -> &mut HardNegatives { &mut self.mined }
+
+    /// Drop everything the personal layer learned.
+    pub fn reset_personalization(&mut self) { self.personal.reset(); }
+
+    /// `download_context_report` with the managed category blocks applied.
+    pub fn download_context(&self, mime: &str, ext: &str, initiating_origin: &str, user_gesture: bool) -> Json {
+        let r = download_context_report(mime, ext, initiating_origin, user_gesture);
+        if self.policy.category_blocked(r.str_field("category")) {
+            return r.set("rule", "managed_category_block").set("verdict", Verdict::Block.as_str());
+        }
+        r
+    }
+
+    /// `deep_link_report` for a link tab `tab` opened, with managed blocks of
+    /// the "mobile_app" category applied to installs; the score is folded
+    /// into the tab's session.
+    pub fn deep_link(&mut self, tab: u32, link: &str, initiating_origin: &str, user_gesture: bool) -> Json {
+        let mut r = deep_link_report(link, initiating_origin, user_gesture);
+        if r.str_field("kind") == "enterprise_manifest" && self.policy.category_blocked("mobile_app") {
+            r = r.set("rule", "managed_category_block").set("verdict", Verdict::Block.as_str());
+        }
+        self.state.session(tab).record_report("deep_link", &r);
+        r
+    }
+
+    /// Whether the managed policy wants `verdict` reported upstream.
+    pub fn must_report(&self, verdict: Verdict) -> bool { self.policy.must_report(verdict) }
+
+    /// Fold a navigation into tab `tab`'s session under this context's analyzer.
+    pub fn observe_url(&mut self, tab: u32, url: &str) -> Verdict { self.state.observe_url(&self.analyzer, tab, url) }
+
+    /// [`Context::observe_url`] for a navigation at `at_ms`, feeding the tab's navigation features.
+    pub fn observe_url_at(&mut self, tab: u32, url: &str, at_ms: f64) -> Verdict {
+        self.state.observe_url_at(&self.analyzer, tab, url, at_ms)
+    }
+
+    /// User overrides, feeds, the durable parts of State and the personal
+    /// layer as `{ schema_version, overrides: { host: verdict }, feeds: {
+    /// name: [host] }, state, personal }`, for the extension to keep in storage. Hosts appear as stored
+    /// (hashed in hash-only mode). The managed policy and audit log aren't
+    /// included: one comes from the admin, the other has its own export.
+    pub fn export_state(&self) -> Json {
+        let mut overrides: Vec<(String, Json)> = self.overrides.iter().map(|(k, v)| (k.clone(), Json::from(v.as_str()))).collect();
+        overrides.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut feeds: Vec<(String, Json)> = self.feeds.iter().map(|(k, v)| (k.clone(), Json::from(v.clone()))).collect();
+        feeds.sort_by(|a, b| a.0.cmp(&b.0));
+        Json::obj()
+            .with("schema_version", SCHEMA_VERSION)
+            .with("overrides", Json::Obj(overrides))
+            .with("feeds", Json::Obj(feeds))
+            .with("state", self.state.export())
+            .with("personal", self.personal.export())
+    }
+
+    /// Merge a document from [`Context::export_state`]; returns the entries
+    /// loaded. Overrides the managed policy forbids are skipped.
+    pub fn import_state(&mut self, v: &Json) -> Result<usize, SchemaError> { self.import_doc(v, false) }
+
+    /// [`Context::export_state`] split into documents of at most `max_chunk`
+    /// bytes of JSON each (at least [`MIN_CHUNK_BYTES`](crate::MIN_CHUNK_BYTES)), for stores with a
+    /// per-item size limit.
+    pub fn export_state_chunks(&self, max_chunk: usize) -> Result<Vec<Json>, SchemaError> {
+        chunks::split(&self.export_state(), max_chunk)
+    }
+
+    /// Merge one chunk from [`Context::export_state_chunks`]; chunks of a set
+    /// may arrive in any order. Feed hosts add to what earlier chunks loaded
+    /// rather than replacing the feed.
+    pub fn import_state_chunk(&mut self, v: &Json) -> Result<usize, SchemaError> {
+        chunks::header(v)?;
+        self.import_doc(v, true)
+    }
+
+    /// Overrides and feeds as declarativeNetRequest rules; see
+    /// [`dnr_rules`](crate::dnr_rules). Fails under hashed privacy.
+    pub fn dnr_rules(&self, max_rules: usize) -> Result<Json, SchemaError> {
+        dnr::dnr_rules(&self.export_state(), max_rules)
+    }
+
+    fn import_doc(&mut self, v: &Json, extend_feeds: bool) -> Result<usize, SchemaError> {
+        let f = Fields::new(v, "")?;
+        f.schema_version()?;
+        let overrides = f.map("overrides", |x, p| {
+            x.as_str().and_then(Verdict::parse).ok_or_else(|| err(p, "expected \"allow\", \"warn\" or \"block\""))
+        })?;
+        let feeds = f.map("feeds", |x, p| {
+            let list = x.as_array().ok_or_else(|| err(p, "expected array"))?;
+            list.iter().map(|h| h.as_str().map(str::to_string).ok_or_else(|| err(p, "expected string"))).collect::<Result<Vec<_>, _>>()
+        })?;
+        let mut n = match v.get("state") { Some(s) => self.state.import(s, "state")?, None => 0 };
+        if let Some(p) = v.get("personal") { self.personal.import(p, "personal")?; n += 1; }
+        let hashed = self.privacy().is_hashed();
+        for (h, verdict) in overrides {
+            if hashed || self.policy.user_override_allowed(&h) { self.overrides.insert(h, verdict); n += 1; }
+        }
+        for (name, mut list) in feeds {
+            n += list.len();
+            if extend_feeds { list.extend(self.feeds.remove(&name).unwrap_or_default()); }
+            list.sort();
+            list.dedup();
+            self.feeds.insert(name, list);
+        }
+        Ok(n)
+    }
+
+    /// The user's own settings and teaching — profile, protected brands,
+    /// overrides, the personal layer and feedback hard negatives — as a
+    /// user-data file (see [`USER_DATA_FORMAT`](crate::USER_DATA_FORMAT)) for moving to
+    /// another machine or attaching to a support request.
+    pub fn export_user_data(&self) -> Vec<u8> {
+        let mut overrides: Vec<(String, Json)> = self.overrides.iter().map(|(k, v)| (k.clone(), Json::from(v.as_str()))).collect();
+        overrides.sort_by(|a, b| a.0.cmp(&b.0));
+        let feedback: Vec<Json> = self.mined.entries().filter(|e| e.source == MiningSource::Feedback).map(ToJson::to_json).collect();
+        userdata::seal(&Json::obj()
+            .with("engine", ENGINE_VERSION)
+            .with("key_check", self.key(KEY_CHECK))
+            .with("profile", self.analyzer.profile().as_str())
+            .with("protected_brands", self.analyzer.brands().to_vec())
+            .with("overrides", Json::Obj(overrides))
+            .with("personal", self.personal.export())
+            .with("feedback", feedback))
+    }
+
+    /// Load a file from [`Context::export_user_data`], replacing the profile,
+    /// protected brands and personal layer and merging overrides and
+    /// feedback; returns the entries loaded. Nothing changes when any part
+    /// fails to parse, or when the file was written under another privacy
+    /// salt. A plain file loads into a hash-only context, hashed on the way
+    /// in; overrides the managed policy forbids are skipped.
+    pub fn import_user_data(&mut self, bytes: &[u8]) -> Result<usize, SchemaError> {
+        let doc = userdata::open(bytes)?;
+        let f = Fields::new(&doc, "")?;
+        let plain = match f.opt_string("key_check")? {
+            None => true,
+            Some(k) if k == KEY_CHECK => true,
+            Some(k) if k == self.key(KEY_CHECK) => false,
+            Some(_) => return Err(err("key_check", "file was exported under a different privacy mode or salt")),
+        };
+        let profile = match f.opt_string("profile")? {
+            None => self.analyzer.profile(),
+            Some(p) => Profile::parse(&p).ok_or_else(|| err("profile", &format!("unknown profile \"{}\"", p)))?,
+        };
+        let brands = f.strings("protected_brands")?;
+        let overrides = f.map("overrides", |x, p| {
+            x.as_str().and_then(Verdict::parse).ok_or_else(|| err(p, "expected \"allow\", \"warn\" or \"block\""))
+        })?;
+        let feedback = f.list("feedback", HardNegative::from_json)?;
+        let mut personal = None;
+        if let Some(p) = doc.get("personal") { personal.get_or_insert_with(Personalizer::new).import(p, "personal")?; }
+
+        if profile != self.analyzer.profile() || brands != self.analyzer.brands() {
+            let a = &self.analyzer;
+            self.analyzer = Analyzer::builder().brands(&brands).keywords(a.keywords()).psl(a.uses_psl()).caps(a.caps()).profile(profile).build();
+            self.state.clear_reports();
+        }
+        let mut n = personal.is_some() as usize;
+        if let Some(p) = personal { self.personal = p; }
+        for (rule, verdict) in overrides {
+            let loaded = if plain { self.set_override(&rule, verdict) } else { self.overrides.insert(rule, verdict); true };
+            n += loaded as usize;
+        }
+        n += feedback.len();
+        for e in feedback {
+            match e.url.clone() {
+                Some(url) if plain => self.mined.record(&url, &e.features, e.label == 1, e.score, e.source),
+                _ => self.mined.insert(HardNegative { url: None, ..e }),
+            }
+        }
+        Ok(n)
+    }
+
+    /// Returns `{ hashed, overrides, feeds: { <name>: entries }, audit: { entries, bytes },
+    /// hard_negatives: { entries, bytes }, state: { … } }` sizes.
+    pub fn memory(&self) -> Json {
+        let feeds = Json::Obj(self.feeds.iter().map(|(k, v)| (k.clone(), Json::from(v.len()))).collect());
+        let audit = Json::obj().with("entries", self.audit.len()).with("bytes", self.audit.heap_bytes());
+        Json::obj()
+            .with("hashed", self.privacy().is_hashed())
+            .with("overrides", self.overrides.len())
+            .with("feeds", feeds)
+            .with("audit", audit)
+            .with("hard_negatives", Json::obj().with("entries", self.mined.len()).with("bytes", self.mined.heap_bytes()))
+            .with("state", self.state.memory())
+    }
+}
+
+/// Handle table for [`Context`]s. [`DEFAULT_CONTEXT`] is always present.
+#[derive(Debug)]
+pub struct Contexts {
+    map:  HashMap<u32, Context>,
+    next: u32,
+}
+
+impl Default for Contexts {
+    fn default() -> Contexts {
+        let mut map = HashMap::new();
+        map.insert(DEFAULT_CONTEXT, Context::default());
+        Contexts { map, next: DEFAULT_CONTEXT + 1 }
+    }
+}
+
+impl Contexts {
+    pub fn new() -> Contexts { Contexts::default() }
+
+    /// Register a new context; handles are never reused.
+    pub fn create(&mut self, ctx: Context) -> u32 {
+        let h = self.next;
+        self.next += 1;
+        self.map.insert(h, ctx);
+        h
+    }
+
+    pub fn get(&self, handle: u32) -> Option<&Context> { self.map.get(&handle) }
+
+    pub fn get_mut(&mut self, handle: u32) -> Option<&mut Context> { self.map.get_mut(&handle) }
+
+    pub fn default_mut(&mut self) -> &mut Context { self.map.entry(DEFAULT_CONTEXT).or_default() }
+
+    /// Drop a context and everything it stored. The default context is only reset.
+    pub fn destroy(&mut self, handle: u32) -> bool {
+        if handle == DEFAULT_CONTEXT {
+            self.map.insert(DEFAULT_CONTEXT, Context::default());
+            return true;
+        }
+        self.map.remove(&handle).is_some()
+    }
+
+    pub fn handles(&self) -> Vec<u32> {
+        let mut h: Vec<u32> = self.map.keys().copied().collect();
+        h.sort();
+        h
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Context> { self.map.values_mut() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_context_never_sees_another_s_data() {
+        let mut all = Contexts::new();
+        let (a, b) = (all.create(Context::default()), all.create(Context::default()));
+        let url = "https://intranet-login.example.tk/portal";
+        let lookalike = "https://acme-portol.com/";
+        let before = all.get_mut(b).unwrap().url(url);
+
+        let ctx = all.get_mut(a).unwrap();
+        assert!(ctx.set_override("example.tk", Verdict::Block));
+        assert_eq!(ctx.load_feed("corp", ["evil.example"]), 1);
+        for _ in 0..10 { ctx.personal_feedback(url, Verdict::Allow); }
+        ctx.set_brands(&["acme-portal"]).unwrap();
+        assert_eq!(ctx.url(url).verdict, Verdict::Block);
+        assert!(ctx.feed_match("evil.example").is_some());
+        assert_eq!(ctx.personal.updates(), 10);
+        assert_eq!(ctx.url(lookalike).features[21], 1.0);
+
+        let other = all.get_mut(b).unwrap();
+        assert_eq!(other.override_for("example.tk"), None);
+        assert_eq!(other.feed_match("evil.example"), None);
+        assert_eq!(other.personal.updates(), 0);
+        assert!(other.analyzer().brands().is_empty());
+        let after = other.url(url);
+        assert_eq!((after.risk, after.verdict, &after.features, &after.flags), (before.risk, before.verdict, &before.features, &before.flags));
+        assert_eq!(other.url(lookalike).features[21], 0.0);
+    }
+}
@@ -0,0 +1,138 @@
+// wasm-feature/crates/vigilant-core/src/csp.rs
+// Browser Vigilant — Content-Security-Policy grading. Banks and identity
+// providers ship strict policies on their login pages; cloned kits ship none
+// or a copied one that no longer restricts anything. Only a credential page
+// with a missing or permissive policy adds risk.
+
+use crate::json::Json;
+
+/// Risk a password page adds with no policy or a weak one.
+pub(crate) const WEAK_CSP_RISK: f32 = 0.1;
+
+/// Sources that allow any script or endpoint.
+const WILDCARDS: &[&str] = &["*", "https:", "http:", "data:", "blob:", "https://*", "http://*"];
+
+/// One policy: directive name → source list, directives lowercased.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Csp {
+    pub directives: Vec<(String, Vec<String>)>,
+}
+
+impl Csp {
+    fn get(&self, name: &str) -> Option<&[String]> {
+        self.directives.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_slice())
+    }
+
+    /// Sources in force for `name`, falling back to `default-src` like the browser does.
+    fn effective(&self, name: &str) -> Option<&[String]> { self.get(name).or_else(|| self.get("default-src")) }
+}
+
+/// Parse a header value. Only the first policy of a comma-joined header is
+/// read; a later duplicate directive is ignored, as browsers do.
+pub fn parse_csp(header: &str) -> Csp {
+    let first = header.split(',').next().unwrap_or("");
+    let mut directives: Vec<(String, Vec<String>)> = Vec::new();
+    for d in first.split(';') {
+        let mut parts = d.split_ascii_whitespace();
+        let Some(name) = parts.next().map(str::to_ascii_lowercase) else { continue };
+        if directives.iter().any(|(n, _)| *n == name) { continue; }
+        directives.push((name, parts.map(str::to_ascii_lowercase).collect()));
+    }
+    Csp { directives }
+}
+
+fn any_wild(sources: &[String]) -> bool { sources.iter().any(|s| WILDCARDS.contains(&s.as_str())) }
+
+/// Grade a policy. Returns `{ present, report_only, directives, issues,
+/// strength, grade }`; `strength` runs 0 (no policy) to 1 and `grade` is
+/// none, weak, moderate or strong.
+pub fn csp_report(header: &str, report_only: bool) -> Json {
+    let csp = parse_csp(header);
+    let mut issues: Vec<&'static str> = Vec::new();
+    let present = !csp.directives.is_empty();
+    let mut strength = if present { 1.0f32 } else { 0.0 };
+    let mut penalise = |issue: &'static str, cost: f32| { issues.push(issue); strength -= cost; };
+
+    if present {
+        match csp.effective("script-src") {
+            None => penalise("no_script_src", 0.4),
+            Some(src) => {
+                // 'unsafe-inline' is ignored once a nonce or hash is present
+                let keyed = src.iter().any(|s| s.starts_with("'nonce-") || s.starts_with("'sha"));
+                if src.iter().any(|s| s == "'unsafe-inline'") && !keyed { penalise("unsafe_inline_script", 0.3); }
+                if src.iter().any(|s| s == "'unsafe-eval'") { penalise("unsafe_eval", 0.1); }
+                if any_wild(src) && !src.iter().any(|s| s == "'strict-dynamic'") { penalise("wildcard_script_src", 0.3); }
+            }
+        }
+        match csp.effective("connect-src") {
+            None => penalise("no_connect_src", 0.15),
+            Some(src) if any_wild(src) => penalise("wildcard_connect_src", 0.2),
+            Some(_) => {}
+        }
+        // form-action doesn't fall back to default-src
+        match csp.get("form-action") {
+            None => penalise("no_form_action", 0.1),
+            Some(src) if any_wild(src) => penalise("wildcard_form_action", 0.15),
+            Some(_) => {}
+        }
+        if csp.get("frame-ancestors").is_none() { penalise("no_frame_ancestors", 0.05); }
+        if report_only { penalise("report_only", 0.5); }
+    }
+    let strength = strength.clamp(0.0, 1.0);
+    let grade = match strength {
+        _ if !present => "none",
+        s if s < 0.5 => "weak",
+        s if s < 0.8 => "moderate",
+        _ => "strong",
+    };
+    let directives: Vec<String> = csp.directives.iter().map(|(n, _)| n.clone()).collect();
+    Json::obj()
+        .with("present", present)
+        .with("report_only", report_only)
+        .with("directives", directives)
+        .with("issues", issues)
+        .with("strength", strength)
+        .with("grade", grade)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(r: &Json) -> Vec<&str> {
+        r.get("issues").and_then(Json::as_array).unwrap_or(&[]).iter().filter_map(Json::as_str).collect()
+    }
+
+    #[test]
+    fn an_empty_policy_is_no_policy() {
+        for h in ["", "   ", ";", " ; ;; ", ",script-src 'self'"] {
+            assert_eq!(parse_csp(h), Csp::default(), "{h:?}");
+            let r = csp_report(h, false);
+            assert_eq!(r.get("present").and_then(Json::as_bool), Some(false), "{h:?}");
+            assert_eq!(r.get("grade").and_then(Json::as_str), Some("none"), "{h:?}");
+            assert_eq!(issues(&r), Vec::<&str>::new(), "{h:?}");
+        }
+    }
+
+    #[test]
+    fn a_duplicate_directive_keeps_the_first() {
+        let csp = parse_csp("script-src 'self'; SCRIPT-SRC *; default-src 'none'; default-src *");
+        assert_eq!(csp.get("script-src"), Some(&["'self'".to_string()][..]));
+        assert_eq!(csp.get("default-src"), Some(&["'none'".to_string()][..]));
+        assert_eq!(csp.directives.len(), 2);
+        let r = csp_report("script-src 'self'; script-src 'unsafe-inline' *", false);
+        assert!(!issues(&r).contains(&"unsafe_inline_script"));
+        assert!(!issues(&r).contains(&"wildcard_script_src"));
+    }
+
+    #[test]
+    fn unknown_directives_restrict_nothing() {
+        let r = csp_report("frobnicate-src 'self'; x-made-up", false);
+        assert_eq!(r.get("present").and_then(Json::as_bool), Some(true));
+        assert_eq!(issues(&r), ["no_script_src", "no_connect_src", "no_form_action", "no_frame_ancestors"]);
+        assert_eq!(r.get("grade").and_then(Json::as_str), Some("weak"));
+        // a misspelt fallback doesn't stand in for default-src
+        let csp = parse_csp("defualt-src 'none'");
+        assert_eq!(csp.effective("script-src"), None);
+    }
+}
@@ -0,0 +1,194 @@
+// wasm-feature/crates/vigilant-core/src/datauri.rs
+// Browser Vigilant — what a `data:` URI carries. F36 notes that a URL is a
+// `data:` URI and stops there, but the URI is the page: a whole login
+// form, script or executable travels in the link itself, with no host to
+// look up and nothing on the wire to scan. Lures base64-encode it so no
+// keyword shows, label it `image/png` so it passes as an inline picture,
+// and open it in a frame or a new tab. The payload is decoded here and
+// read like the attachment it is.
+
+use crate::download::sniff;
+use crate::html_scan::html_attachment_report;
+use crate::json::Json;
+use crate::score::combine;
+use crate::verdict::Verdict;
+use crate::percent_decode;
+
+/// Decoded payload bytes inspected; the rest is reported as truncated.
+pub const MAX_DATA_URI_BYTES: usize = 4 << 20;
+
+/// Media types each sniffed payload kind may be labelled with.
+pub(crate) const DATA_URI_MIMES: &[(&str, &[&str])] = &[
+    ("html",  &["text/html", "application/xhtml+xml"]),
+    ("svg",   &["image/svg+xml", "text/xml", "application/xml"]),
+    ("pdf",   &["application/pdf"]),
+    ("zip",   &["application/zip", "application/x-zip-compressed", "application/java-archive", "application/vnd.android.package-archive", "application/octet-stream"]),
+    ("gzip",  &["application/gzip", "application/x-gzip", "application/octet-stream"]),
+    ("pe",    &["application/x-msdownload", "application/vnd.microsoft.portable-executable", "application/octet-stream"]),
+    ("elf",   &["application/x-executable", "application/x-elf", "application/octet-stream"]),
+    ("macho", &["application/x-mach-binary", "application/octet-stream"]),
+];
+
+/// Media types under which the payload is script.
+const SCRIPT_MIMES: &[&str] = &["text/javascript", "application/javascript", "application/x-javascript", "application/ecmascript", "text/ecmascript"];
+
+/// Sniffed kinds that run as a program once saved.
+const EXECUTABLE_KINDS: &[&str] = &["pe", "elf", "macho"];
+
+/// `s` as standard or URL-safe base64, whitespace ignored and padding
+/// optional; `None` on any other character or a dangling sextet.
+pub(crate) fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() / 4 * 3);
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for b in s.bytes().filter(|b| !b.is_ascii_whitespace()) {
+        let v = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+        acc = (acc << 6) | u32::from(v);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    if bits >= 6 { return None; }
+    Some(out)
+}
+
+/// Shannon entropy of `bytes` in bits per byte; 0 when empty.
+fn byte_entropy(bytes: &[u8]) -> f32 {
+    if bytes.is_empty() { return 0.0; }
+    let mut freq = [0u32; 256];
+    for &b in bytes { freq[b as usize] += 1; }
+    let n = bytes.len() as f32;
+    freq.iter().filter(|&&c| c > 0).map(|&c| { let p = c as f32 / n; -p * p.log2() }).sum()
+}
+
+/// Analyze a `data:` URI: its media type and parameters, the decoded
+/// payload (base64 or percent-encoded) and what it holds. A markup payload,
+/// or any payload declared as markup, is scanned as an HTML attachment, whose flags and score fold into the
+/// URI's. Returns `{ data_uri, mime, params, base64, decoded, truncated,
+/// payload_bytes, entropy, content_kind, mime_mismatch, script, forms,
+/// content: HtmlAttachmentReport | null, flags, score, verdict }`; only
+/// `data_uri`, `flags`, `score` and `verdict` are set for anything else.
+pub fn data_uri_report(uri: &str) -> Json {
+    let uri = uri.trim();
+    let is_data = uri.get(..5).is_some_and(|s| s.eq_ignore_ascii_case("data:"));
+    let Some((meta, data)) = uri.get(5..).filter(|_| is_data).and_then(|r| r.split_once(',')) else {
+        return Json::obj().with("data_uri", false).with("flags", Vec::<&str>::new()).with("score", 0.0f32).with("verdict", Verdict::from_score(0.0).as_str());
+    };
+    let first = meta.split(';').next().unwrap_or("").trim().to_lowercase();
+    // RFC 2397: an omitted type is text/plain
+    let mime = if first.contains('/') { first } else { "text/plain".to_string() };
+    let mut base64 = false;
+    let mut params: Vec<(String, Json)> = Vec::new();
+    for p in meta.split(';').skip(1).map(str::trim).filter(|p| !p.is_empty()) {
+        match p.split_once('=') {
+            Some((k, v)) => params.push((k.trim().to_lowercase(), Json::from(v.trim()))),
+            None if p.eq_ignore_ascii_case("base64") => base64 = true,
+            None => {}
+        }
+    }
+
+    let text = percent_decode(data, false);
+    let (payload, decoded) = if base64 {
+        match base64_decode(&text) { Some(b) => (b, true), None => (Vec::new(), false) }
+    } else {
+        (text.into_bytes(), true)
+    };
+    let (size, truncated) = (payload.len(), payload.len() > MAX_DATA_URI_BYTES);
+    let payload = &payload[..size.min(MAX_DATA_URI_BYTES)];
+    let kind = if payload.is_empty() { "unknown" } else { sniff(payload) };
+    let mime_mismatch = DATA_URI_MIMES.iter().find(|(k, _)| *k == kind).is_some_and(|(_, mimes)| !mimes.contains(&mime.as_str()));
+    // a browser renders a declared document type whatever the bytes look like
+    let declared = |k: &str| DATA_URI_MIMES.iter().any(|(d, mimes)| *d == k && mimes.contains(&mime.as_str()));
+    let document = matches!(kind, "html" | "svg") || declared("html") || declared("svg");
+    let script = SCRIPT_MIMES.contains(&mime.as_str());
+    let low = String::from_utf8_lossy(payload).to_lowercase();
+    let forms = low.matches("<form").count();
+
+    let mut flags: Vec<String> = Vec::new();
+    let mut risk = 0.0f32;
+    let mut flag = |f: &str, w: f32| { flags.push(f.to_string()); risk += w; };
+    if !decoded { flag("data_uri_malformed", 0.1); }
+    if document { flag("data_uri_document", 0.3); }
+    if script { flag("data_uri_script", 0.3); }
+    if forms > 0 { flag("data_uri_form", 0.2); }
+    if mime_mismatch { flag("data_uri_mime_mismatch", 0.3); }
+    if EXECUTABLE_KINDS.contains(&kind) { flag("data_uri_executable", 0.7); }
+
+    let content = if document { html_attachment_report(payload) } else { Json::Null };
+    flags.extend(content.str_list("flags").into_iter().filter(|f| !flags.contains(f)).collect::<Vec<_>>());
+    let content_score = content.get("score").and_then(Json::as_f64).unwrap_or(0.0) as f32;
+    let score = combine(risk.min(1.0), content_score);
+
+    Json::obj()
+        .with("data_uri", true)
+        .with("mime", mime.as_str())
+        .with("params", Json::Obj(params))
+        .with("base64", base64)
+        .with("decoded", decoded)
+        .with("truncated", truncated)
+        .with("payload_bytes", size)
+        .with("entropy", byte_entropy(payload))
+        .with("content_kind", kind)
+        .with("mime_mismatch", mime_mismatch)
+        .with("script", script)
+        .with("forms", forms)
+        .with("content", content)
+        .with("flags", flags)
+        .with("score", score)
+        .with("verdict", Verdict::from_score(score).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flag(r: &Json, f: &str) -> bool { r.str_list("flags").iter().any(|x| x == f) }
+
+    #[test]
+    fn a_uri_without_a_comma_is_not_a_data_uri() {
+        let r = data_uri_report("data:text/html;base64PHNjcmlwdD4=");
+        assert_eq!(r.get("data_uri").and_then(Json::as_bool), Some(false));
+        assert_eq!(r.get("score").and_then(Json::as_f64), Some(0.0));
+        assert_eq!(data_uri_report("https://example.com/a,b").get("data_uri").and_then(Json::as_bool), Some(false));
+    }
+
+    #[test]
+    fn bad_base64_is_flagged_as_malformed() {
+        for uri in ["data:text/plain;base64,SGk@", "data:text/plain;base64,SGVsb"] {
+            let r = data_uri_report(uri);
+            assert_eq!(r.get("decoded").and_then(Json::as_bool), Some(false), "{}", uri);
+            assert_eq!(r.get("payload_bytes").and_then(Json::as_f64), Some(0.0), "{}", uri);
+            assert!(flag(&r, "data_uri_malformed"), "{}", uri);
+        }
+        assert_eq!(base64_decode("SGk="), Some(b"Hi".to_vec()));
+        assert_eq!(base64_decode("SG k"), Some(b"Hi".to_vec()));
+        assert_eq!(base64_decode("S"), None);
+    }
+
+    #[test]
+    fn an_empty_media_type_is_text_plain() {
+        let r = data_uri_report("data:,<form>");
+        assert_eq!(r.str_field("mime"), "text/plain");
+        assert!(!flag(&r, "data_uri_document"));
+        let r = data_uri_report("data:;charset=utf-8;base64,SGk=");
+        assert_eq!((r.str_field("mime"), r.get("base64").and_then(Json::as_bool)), ("text/plain", Some(true)));
+        assert_eq!(r.get("params").and_then(|p| p.get("charset")).and_then(Json::as_str), Some("utf-8"));
+        assert!(!flag(&r, "data_uri_malformed"));
+    }
+
+    #[test]
+    fn a_declared_document_is_scanned_whatever_its_bytes() {
+        let r = data_uri_report("data:text/html,hello");
+        assert!(flag(&r, "data_uri_document"));
+        assert!(!r.get("content").is_some_and(|c| matches!(c, Json::Null)));
+    }
+}
@@ -0,0 +1,84 @@
+// wasm-feature/crates/vigilant-core/src/deeplink.rs
+// Browser Vigilant — app-store deep links and iOS enterprise installs. A
+// `market://` or `itms-apps://` link opens the store on a listing the page
+// chose; an `itms-services://` link installs an enterprise-signed app from
+// any server's manifest, skipping App Review. Both are judged like
+// downloads: by the page that started them and whether the user asked.
+
+use crate::json::Json;
+use crate::intent::package_brand_mismatch;
+use crate::policy::download_context_report;
+use crate::psl::registrable_domain;
+use crate::scheme::scheme_of;
+use crate::verdict::Verdict;
+use crate::{parse_url, query_pairs};
+
+/// What a deep link opens: store and listing, or an install manifest.
+enum Link {
+    Store { store: &'static str, listing: Option<String> },
+    Manifest(Option<String>),
+}
+
+fn parse_link(link: &str) -> Option<Link> {
+    let p = parse_url(link);
+    let param = |k: &str| query_pairs(&p.query).into_iter().find(|(pk, _)| pk == k).map(|(_, v)| v);
+    match scheme_of(link).as_str() {
+        "market" => Some(Link::Store { store: "google_play", listing: param("id").map(|id| id.to_lowercase()) }),
+        "itms-apps" | "itms-appss" => {
+            let id = p.path.rsplit('/').find(|s| s.starts_with("id") && s.len() > 2).map(str::to_string);
+            Some(Link::Store { store: "app_store", listing: id.or_else(|| param("id")) })
+        }
+        "itms-services" => Some(Link::Manifest(param("url").filter(|_| param("action").as_deref() == Some("download-manifest")))),
+        _ => None,
+    }
+}
+
+/// Analyze an app-store or enterprise-install link started from
+/// `initiating_origin`. Store links opened without a gesture are forced
+/// redirects; an `itms-services` install goes through the download policy
+/// as a mobile app, so one from a hostile page or without a gesture is a
+/// sideload lure. Returns `{ kind, store, package, manifest_url,
+/// manifest_host, rule, brand, origin_class, flags, score, verdict }`;
+/// `kind` is "store", "enterprise_manifest" or null for other links.
+pub fn deep_link_report(link: &str, initiating_origin: &str, user_gesture: bool) -> Json {
+    let link = link.trim();
+    let policy = download_context_report("", "ipa", initiating_origin, user_gesture);
+    let origin_host = parse_url(initiating_origin.trim()).host.to_lowercase();
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+    let (kind, store, package, manifest, manifest_host, brand) = match parse_link(link) {
+        Some(Link::Store { store, listing }) => {
+            if !user_gesture { flags.push("store_redirect_without_gesture"); risk += 0.3; }
+            // Play listings are package names; App Store ones only numeric ids
+            let brand = listing.as_deref().filter(|_| store == "google_play").and_then(|pkg| package_brand_mismatch(pkg, &origin_host));
+            if brand.is_some() { flags.push("store_package_mismatch"); risk += 0.5; }
+            (Some("store"), Some(store), listing, None, None, brand)
+        }
+        Some(Link::Manifest(url)) => {
+            flags.push("enterprise_app_install");
+            risk += 0.45;
+            if policy.str_field("verdict") == Verdict::Block.as_str() { flags.push("sideload_from_untrusted_page"); risk += 0.4; }
+            let host = url.as_deref().map(|u| parse_url(u).host.to_lowercase()).filter(|h| !h.is_empty());
+            if host.as_deref().is_some_and(|h| !origin_host.is_empty() && registrable_domain(h) != registrable_domain(&origin_host)) {
+                flags.push("manifest_off_origin");
+                risk += 0.15;
+            }
+            (Some("enterprise_manifest"), None, None, url, host, None)
+        }
+        None => (None, None, None, None, None, None),
+    };
+
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("kind", kind)
+        .with("store", store)
+        .with("package", package)
+        .with("manifest_url", manifest)
+        .with("manifest_host", manifest_host)
+        .with("rule", kind.filter(|k| *k == "enterprise_manifest").map(|_| policy.str_field("rule")))
+        .with("brand", brand)
+        .with("origin_class", policy.str_field("origin_class"))
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
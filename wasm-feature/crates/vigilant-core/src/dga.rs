@@ -0,0 +1,168 @@
+// wasm-feature/crates/vigilant-core/src/dga.rs
+// Browser Vigilant — algorithmically generated domains. Malware and
+// fast-flux phishing register throwaway names from a seeded generator;
+// the long ones show in the host entropies (F16–F20), but a short
+// `qxkzt.top` carries too few characters for entropy to tell it from
+// `bbc.com`. A first-order Markov chain over the letters of ordinary names
+// scores how unlike a name a label reads at any length, next to how often
+// it flips between letters and digits and how much of it can be said
+// aloud. The built-in chain is fitted on English words and popular site
+// names; `model/markov.py` refits it on the Tranco list.
+
+use crate::{has_ip, parse_url};
+
+/// Values returned by [`dga_features`]: markov_bits_per_char,
+/// digit_interleave_ratio, pronounceable_share, dga_like.
+pub const DGA_FEATURES: usize = 4;
+
+/// Bits per transition from which a label reads as generated; the fitting
+/// corpus sits at about 3.6 on average and random letters at 6.
+const DGA_BITS: f32 = 6.0;
+
+/// Shortest label `dga_like` is raised for.
+const MIN_DGA_LABEL: usize = 5;
+
+/// Length of the consonant run from which a stretch can't be said aloud.
+const UNSPOKEN_RUN: usize = 3;
+
+/// Quarter bits per unit in [`MARKOV`].
+const COST_SCALE: f32 = 4.0;
+
+/// Row of [`MARKOV`] for `c`: 0 is the start of the label, then a–z, any
+/// digit and the hyphen.
+fn from_index(c: char) -> Option<usize> {
+    match c {
+        'a'..='z' => Some(c as usize - 'a' as usize + 1),
+        '0'..='9' => Some(27),
+        '-' => Some(28),
+        _ => None,
+    }
+}
+
+/// Column of [`MARKOV`] for `c`, `None` for the end of the label.
+fn to_index(c: Option<char>) -> Option<usize> {
+    match c {
+        None => Some(28),
+        Some(c) => from_index(c).map(|i| i - 1),
+    }
+}
+
+/// Transition cost (-log2 p, in quarter bits) from row to column, add-one
+/// smoothed; rows start, a–z, digit, hyphen; columns a–z, digit, hyphen,
+/// end. Refit with `model/markov.py`, which rewrites this block.
+// BEGIN MARKOV TABLE
+// fitted on 5481 labels from english-words-and-popular-sites.txt by model/markov.py
+pub(crate) const MARKOV: [[u8; 29]; 29] = [
+    [ 16,  18,  14,  17,  17,  17,  21,  21,  17,  25,  27,  19,  17,  21,  21,  15,  30,  15,  13,  17,  22,  24,  21,  32,  31,  34,  50,  50,  50], // ^
+    [ 28,  19,  16,  18,  31,  27,  21,  33,  19,  36,  26,  12,  19,  13,  35,  19,  40,  13,  16,  10,  24,  24,  28,  27,  24,  36,  46,  46,  20], // a
+    [ 12,  24,  23,  25,  12,  25,  32,  32,  13,  22,  34,  10,  28,  32,  16,  34,  38,  16,  17,  25,  14,  32,  38,  38,  21,  38,  38,  38,  17], // b
+    [ 13,  31,  21,  28,  12,  31,  35,  13,  19,  39,  18,  18,  30,  39,   9,  34,  39,  18,  27,  13,  20,  43,  39,  39,  27,  37,  43,  43,  16], // c
+    [ 18,  26,  27,  21,   9,  26,  29,  31,  13,  31,  35,  23,  27,  28,  17,  33,  43,  24,  19,  31,  19,  32,  36,  39,  28,  39,  43,  43,   6], // d
+    [ 18,  27,  18,  13,  23,  23,  24,  33,  28,  37,  41,  19,  20,  13,  30,  21,  27,  11,  12,  19,  32,  24,  29,  20,  29,  37,  49,  49,  10], // e
+    [ 15,  27,  26,  28,  14,  15,  38,  38,   9,  32,  34,  16,  34,  29,  13,  32,  38,  17,  23,  19,  16,  34,  38,  38,  19,  34,  38,  38,  15], // f
+    [ 17,  34,  30,  40,  11,  30,  26,  17,  15,  36,  36,  21,  26,  18,  22,  29,  40,  15,  20,  30,  19,  34,  34,  40,  32,  31,  40,  40,   6], // g
+    [ 10,  27,  29,  31,  10,  39,  39,  39,  11,  35,  35,  24,  24,  24,  13,  31,  35,  18,  23,  17,  24,  39,  32,  39,  24,  32,  39,  39,  12], // h
+    [ 20,  22,  16,  20,  19,  21,  19,  40,  43,  35,  29,  17,  18,   8,  14,  21,  36,  21,  15,  14,  40,  21,  47,  28,  39,  23,  47,  47,  24], // i
+    [ 13,  30,  30,  30,  10,  30,  24,  26,  22,  26,  26,  30,  21,  24,  13,  22,  26,  30,   8,  26,  13,  30,  30,  30,  30,  30,  30,  30,  17], // j
+    [ 16,  27,  24,  24,   8,  28,  28,  28,  12,  35,  28,  20,  31,  23,  18,  35,  31,  24,  15,  26,  20,  35,  27,  35,  24,  35,  35,  35,   9], // k
+    [ 14,  29,  30,  20,  10,  31,  37,  37,  10,  35,  30,  14,  33,  35,  13,  27,  40,  33,  21,  22,  19,  28,  36,  44,  15,  40,  44,  44,  12], // l
+    [  9,  20,  29,  26,   9,  32,  33,  37,  12,  32,  31,  22,  20,  28,  15,  12,  41,  35,  20,  31,  21,  35,  41,  37,  23,  41,  41,  41,  16], // m
+    [ 17,  34,  16,  15,  15,  24,  11,  30,  18,  31,  23,  27,  33,  23,  18,  31,  42,  35,  14,  11,  24,  25,  38,  37,  28,  37,  46,  46,  11], // n
+    [ 24,  21,  20,  18,  30,  23,  24,  34,  25,  34,  23,  17,  15,   9,  19,  18,  45,  12,  19,  18,  16,  21,  19,  32,  37,  39,  45,  45,  19], // o
+    [ 12,  33,  29,  28,  11,  34,  34,  21,  18,  42,  32,  14,  30,  29,  13,  16,  42,  11,  21,  15,  20,  34,  42,  42,  28,  42,  42,  42,  16], // p
+    [ 28,  28,  28,  24,  28,  28,  24,  28,  28,  28,  28,  17,  28,  28,  24,  28,  28,  24,  22,  28,   3,  28,  22,  28,  28,  24,  28,  28,  20], // q
+    [ 13,  32,  21,  23,   8,  29,  25,  32,  13,  42,  25,  26,  21,  22,  14,  27,  46,  22,  16,  18,  22,  28,  33,  46,  22,  46,  46,  46,  13], // r
+    [ 21,  37,  19,  31,  13,  31,  37,  18,  15,  42,  29,  26,  28,  31,  19,  20,  31,  40,  17,  12,  19,  35,  32,  36,  24,  38,  46,  46,   6], // s
+    [ 15,  34,  24,  33,   9,  33,  38,  17,  10,  38,  34,  25,  26,  33,  16,  30,  40,  15,  17,  20,  23,  40,  30,  40,  19,  42,  46,  46,  10], // t
+    [ 17,  18,  17,  23,  16,  25,  22,  41,  20,  41,  30,  14,  16,  12,  28,  18,  41,  12,  13,  14,  35,  37,  35,  35,  41,  28,  41,  41,  22], // u
+    [ 11,  32,  32,  28,   5,  32,  29,  28,  10,  36,  29,  29,  29,  36,  16,  32,  36,  32,  36,  28,  28,  36,  36,  36,  36,  36,  36,  36,  17], // v
+    [ 11,  28,  34,  26,  11,  34,  30,  15,  12,  34,  30,  23,  30,  17,  14,  34,  34,  14,  17,  26,  34,  28,  28,  34,  34,  34,  34,  34,  13], // w
+    [ 19,  29,  16,  29,  15,  23,  29,  26,  14,  29,  33,  25,  20,  33,  26,   9,  33,  26,  33,  11,  26,  33,  33,  20,  26,  33,  29,  33,  10], // x
+    [ 21,  28,  23,  29,  21,  28,  31,  31,  20,  37,  33,  24,  20,  17,  21,  13,  37,  31,  18,  19,  27,  37,  26,  37,  37,  27,  37,  37,   4], // y
+    [ 14,  29,  23,  29,   6,  29,  29,  23,  11,  29,  29,  23,  25,  29,  20,  23,  29,  25,  25,  29,  21,  29,  25,  29,  20,  17,  29,  29,  14], // z
+    [ 20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  20,  16], // 0
+    [ 19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19,  19], // -
+];
+// END MARKOV TABLE
+
+/// Mean bits per transition of `label` under [`MARKOV`], start and end
+/// included; `None` when it has a character outside the chain.
+fn bits_per_char(label: &str) -> Option<f32> {
+    let mut prev = 0usize;
+    let mut total = 0u32;
+    let mut n = 0u32;
+    for c in label.chars().map(Some).chain([None]) {
+        let to = to_index(c)?;
+        total += u32::from(MARKOV[prev][to]);
+        n += 1;
+        if let Some(c) = c { prev = from_index(c)?; }
+    }
+    Some(total as f32 / COST_SCALE / n as f32)
+}
+
+/// Share of neighbouring letter/digit pairs that switch between the two.
+fn digit_interleave(label: &str) -> f32 {
+    let kinds: Vec<bool> = label.chars().filter(char::is_ascii_alphanumeric).map(|c| c.is_ascii_digit()).collect();
+    if kinds.len() < 2 { return 0.0; }
+    kinds.windows(2).filter(|w| w[0] != w[1]).count() as f32 / (kinds.len() - 1) as f32
+}
+
+/// Share of the letters outside consonant runs of [`UNSPOKEN_RUN`] or more,
+/// `y` counting as a vowel.
+fn pronounceable_share(label: &str) -> f32 {
+    let letters: Vec<char> = label.chars().filter(char::is_ascii_alphabetic).collect();
+    if letters.is_empty() { return 0.0; }
+    let mut unspoken = 0usize;
+    let mut run = 0usize;
+    for c in letters.iter().map(Some).chain([None]) {
+        if c.is_some_and(|c| !"aeiouy".contains(*c)) { run += 1; continue; }
+        if run >= UNSPOKEN_RUN { unspoken += run; }
+        run = 0;
+    }
+    1.0 - unspoken as f32 / letters.len() as f32
+}
+
+/// `[markov_bits_per_char, digit_interleave_ratio, pronounceable_share,
+/// dga_like]` of the registrable label: mean bits per character under the
+/// chain (higher is less name-like), letter–digit switches per neighbouring
+/// pair, letters outside unpronounceable consonant runs, and whether the
+/// label is long enough and unlikely enough to read as generated. All 0 for
+/// an IP host or a label the chain doesn't cover (punycode, an IDN).
+pub fn dga_features(url: &str) -> [f32; DGA_FEATURES] {
+    let p = parse_url(url);
+    let label = p.reg_domain.split('.').next().unwrap_or("").to_lowercase();
+    if label.is_empty() || label.starts_with("xn--") || has_ip(&p.host) { return [0.0; DGA_FEATURES]; }
+    let Some(bits) = bits_per_char(&label) else { return [0.0; DGA_FEATURES] };
+    let dga = bits >= DGA_BITS && label.chars().count() >= MIN_DGA_LABEL;
+    [bits, digit_interleave(&label), pronounceable_share(&label), if dga { 1.0 } else { 0.0 }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_dictionary_name_reads_as_a_name() {
+        let f = dga_features("https://www.wikipedia.org/");
+        assert!(f[0] < 4.5, "{:?}", f);
+        assert_eq!((f[1], f[2], f[3]), (0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn twenty_random_letters_read_as_generated() {
+        let f = dga_features("http://xqzkvjwpfbtgmrnhdlcy.top/");
+        assert!(f[0] >= DGA_BITS, "{:?}", f);
+        assert!(f[2] < 0.2, "{:?}", f);
+        assert_eq!(f[3], 1.0);
+    }
+
+    #[test]
+    fn empty_and_one_letter_labels_stay_finite() {
+        for url in ["", "http://", "http:///path", "https://x.com/", "https://7.io/", "https://-.net/"] {
+            let f = dga_features(url);
+            assert!(f.iter().all(|v| v.is_finite()), "{}: {:?}", url, f);
+            assert_eq!(f[3], 0.0, "{}", url);
+        }
+        assert_eq!((digit_interleave("a"), pronounceable_share("")), (0.0, 0.0));
+    }
+}
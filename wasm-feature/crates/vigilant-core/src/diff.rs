@@ -0,0 +1,111 @@
+// wasm-feature/crates/vigilant-core/src/diff.rs
+// Browser Vigilant — field-level comparison of two reports for release QA.
+// Scoring the golden corpus with two engine versions and diffing the
+// reports pair by pair shows which URLs moved and why: the score and
+// verdict, flags and findings that came or went, features that changed, and
+// every other field that reads differently.
+
+use crate::json::Json;
+use crate::rank::URL_SIGNALS;
+
+/// Changes in a number below this are rounding, not regressions.
+const EPSILON: f64 = 1e-6;
+
+/// Fields given their own section of the diff rather than listed under `fields`.
+const SECTIONS: &[&str] = &["score", "risk", "verdict", "flags", "findings", "features"];
+
+/// `key` of the report or of its `Report` envelope's `details`.
+fn field<'a>(r: &'a Json, key: &str) -> Option<&'a Json> {
+    r.get(key).or_else(|| r.get("details").and_then(|d| d.get(key)))
+}
+
+/// `score`, or `risk` as URL reports call it.
+fn score_of(r: &Json) -> Option<f64> {
+    field(r, "score").or_else(|| field(r, "risk")).and_then(Json::as_f64)
+}
+
+fn same(a: &Json, b: &Json) -> bool {
+    match (a, b) {
+        (Json::Num(x), Json::Num(y)) => (x - y).abs() < EPSILON,
+        (Json::Arr(x), Json::Arr(y)) => x.len() == y.len() && x.iter().zip(y).all(|(a, b)| same(a, b)),
+        (Json::Obj(x), Json::Obj(y)) => {
+            x.len() == y.len() && x.iter().all(|(k, v)| y.iter().find(|(l, _)| l == k).is_some_and(|(_, w)| same(v, w)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Entries of `from` missing in `to`, compared by `key`.
+fn missing<K: PartialEq>(from: &[Json], to: &[Json], key: impl Fn(&Json) -> K) -> Vec<Json> {
+    from.iter().filter(|x| !to.iter().any(|y| key(y) == key(x))).cloned().collect()
+}
+
+/// A finding's identity: the tag and where it points.
+fn finding_key(f: &Json) -> (Option<Json>, Option<Json>, Option<Json>) {
+    (f.get("tag").cloned(), f.get("service").cloned(), f.get("endpoint").cloned())
+}
+
+/// Leaves that differ between `a` and `b` under `path`, as `{ path, a, b }`.
+fn walk(path: &str, a: Option<&Json>, b: Option<&Json>, out: &mut Vec<Json>) {
+    let at = |k: &str| if path.is_empty() { k.to_string() } else { format!("{}.{}", path, k) };
+    match (a, b) {
+        (Some(Json::Obj(x)), Some(Json::Obj(y))) => {
+            let mut keys: Vec<&str> = x.iter().map(|(k, _)| k.as_str()).collect();
+            keys.extend(y.iter().map(|(k, _)| k.as_str()).filter(|k| !x.iter().any(|(l, _)| l == k)));
+            for k in keys {
+                if (path.is_empty() || path == "details") && SECTIONS.contains(&k) { continue; }
+                walk(&at(k), x.iter().find(|(l, _)| l == k).map(|(_, v)| v), y.iter().find(|(l, _)| l == k).map(|(_, v)| v), out);
+            }
+        }
+        (Some(Json::Arr(x)), Some(Json::Arr(y))) if x.len() == y.len() => {
+            for (i, (v, w)) in x.iter().zip(y).enumerate() { walk(&format!("{}[{}]", path, i), Some(v), Some(w), out); }
+        }
+        (Some(x), Some(y)) if same(x, y) => {}
+        (a, b) => out.push(Json::obj().with("path", path).with("a", a.cloned()).with("b", b.cloned())),
+    }
+}
+
+/// Compare report `a` (say, from the released engine) with report `b` of
+/// the same input. Returns `{ identical, score: { a, b, delta }, verdict: {
+/// a, b, changed }, flags: { added, removed }, findings: { added, removed },
+/// features: [{ index, name, a, b, delta }], fields: [{ path, a, b }] }`:
+/// `added` is what `b` has and `a` lacks, `features` compares the model
+/// vectors index by index, and `fields` lists every other leaf that
+/// differs. Works on plain reports and on `Report` envelopes.
+pub fn diff_reports(a: &Json, b: &Json) -> Json {
+    let (sa, sb) = (score_of(a), score_of(b));
+    let delta = sa.zip(sb).map(|(x, y)| (y - x) as f32);
+    let (va, vb) = (field(a, "verdict").cloned(), field(b, "verdict").cloned());
+    let verdict_changed = va != vb;
+
+    let list = |r: &Json, k: &str| field(r, k).and_then(Json::as_array).map(<[Json]>::to_vec).unwrap_or_default();
+    let (fa, fb) = (list(a, "flags"), list(b, "flags"));
+    let flags_added = missing(&fb, &fa, Json::clone);
+    let flags_removed = missing(&fa, &fb, Json::clone);
+    let (ga, gb) = (list(a, "findings"), list(b, "findings"));
+    let findings_added = missing(&gb, &ga, finding_key);
+    let findings_removed = missing(&ga, &gb, finding_key);
+
+    let (xa, xb) = (list(a, "features"), list(b, "features"));
+    let features: Vec<Json> = (0..xa.len().max(xb.len())).filter_map(|i| {
+        let (x, y) = (xa.get(i), xb.get(i));
+        if x.zip(y).is_some_and(|(x, y)| same(x, y)) { return None; }
+        let name = URL_SIGNALS.iter().find(|(n, _)| *n == i).map(|(_, s)| *s);
+        let d = x.and_then(Json::as_f64).zip(y.and_then(Json::as_f64)).map(|(x, y)| (y - x) as f32);
+        Some(Json::obj().with("index", i).with("name", name).with("a", x.cloned()).with("b", y.cloned()).with("delta", d))
+    }).collect();
+
+    let mut fields = Vec::new();
+    walk("", Some(a), Some(b), &mut fields);
+    let identical = !delta.is_some_and(|d| f64::from(d).abs() >= EPSILON) && sa.is_some() == sb.is_some() && !verdict_changed
+        && flags_added.is_empty() && flags_removed.is_empty() && findings_added.is_empty() && findings_removed.is_empty()
+        && features.is_empty() && fields.is_empty();
+    Json::obj()
+        .with("identical", identical)
+        .with("score", Json::obj().with("a", sa).with("b", sb).with("delta", delta))
+        .with("verdict", Json::obj().with("a", va).with("b", vb).with("changed", verdict_changed))
+        .with("flags", Json::obj().with("added", flags_added).with("removed", flags_removed))
+        .with("findings", Json::obj().with("added", findings_added).with("removed", findings_removed))
+        .with("features", features)
+        .with("fields", fields)
+}
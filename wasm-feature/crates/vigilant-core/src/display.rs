@@ -0,0 +1,236 @@
+// wasm-feature/crates/vigilant-core/src/display.rs
+// Browser Vigilant — would the URL, as the address bar renders it, pass for
+// another site? Punycode labels are shown decoded, so a Cyrillic `раураl`
+// looks like `paypal`; on a narrow mobile omnibox a long host is cut off and
+// `paypal.com.account-check.example.tk` shows only its borrowed start. Feeds
+// the address-bar overlay warning.
+
+use crate::json::Json;
+use crate::psl::registrable_domain;
+use crate::verdict::Verdict;
+use crate::{parse_url, BRANDS};
+
+/// Host characters a mobile omnibox shows when the caller passes 0.
+pub const MOBILE_OMNIBOX_CHARS: usize = 32;
+
+/// Values returned by [`homoglyph_features`]: homoglyph_impersonation,
+/// confusable_share, mixed_script, undecodable_punycode.
+pub const HOMOGLYPH_FEATURES: usize = 4;
+
+/// What renders like an ASCII letter: Cyrillic, Greek, Latin with diacritics
+/// or IPA forms, digits read as letters and letter pairs. Applied in order,
+/// so a folded letter can still complete a pair.
+pub(crate) const CONFUSABLES: &[(&str, &str)] = &[
+    ("а", "a"), ("с", "c"), ("ԁ", "d"), ("е", "e"), ("ҽ", "e"), ("һ", "h"), ("і", "i"), ("ј", "j"), ("к", "k"),
+    ("ӏ", "l"), ("м", "m"), ("о", "o"), ("р", "p"), ("ԛ", "q"), ("г", "r"), ("ѕ", "s"), ("т", "t"), ("у", "y"),
+    ("ѵ", "v"), ("ԝ", "w"), ("х", "x"), ("ү", "y"),
+    ("α", "a"), ("β", "b"), ("ε", "e"), ("η", "n"), ("ι", "i"), ("κ", "k"), ("ν", "v"), ("ο", "o"), ("ρ", "p"),
+    ("τ", "t"), ("υ", "u"), ("χ", "x"), ("ω", "w"),
+    ("à", "a"), ("á", "a"), ("â", "a"), ("ã", "a"), ("ä", "a"), ("å", "a"), ("ą", "a"), ("ç", "c"), ("ć", "c"),
+    ("è", "e"), ("é", "e"), ("ê", "e"), ("ë", "e"), ("ę", "e"), ("ì", "i"), ("í", "i"), ("î", "i"), ("ï", "i"),
+    ("ı", "i"), ("ɩ", "i"), ("ł", "l"), ("ǀ", "l"), ("ñ", "n"), ("ń", "n"), ("ò", "o"), ("ó", "o"), ("ô", "o"),
+    ("õ", "o"), ("ö", "o"), ("ø", "o"), ("ś", "s"), ("ù", "u"), ("ú", "u"), ("û", "u"), ("ü", "u"), ("ý", "y"),
+    ("ÿ", "y"), ("ź", "z"), ("ż", "z"), ("ɡ", "g"),
+    ("0", "o"), ("1", "l"), ("|", "l"),
+    // letter pairs that read as one letter at address-bar size
+    ("rn", "m"), ("vv", "w"), ("cl", "d"),
+];
+
+/// RFC 3492 bias adaptation.
+fn adapt(delta: u32, points: u32, first: bool) -> u32 {
+    let mut delta = if first { delta / 700 } else { delta / 2 };
+    delta += delta / points;
+    let mut k = 0;
+    while delta > 455 { delta /= 35; k += 36; }
+    k + 36 * delta / (delta + 38)
+}
+
+/// Unicode form of an `xn--` label; `None` for anything that isn't valid punycode.
+fn decode_label(label: &str) -> Option<String> {
+    let s = label.strip_prefix("xn--")?;
+    let (basic, ext) = s.rfind('-').map_or(("", s), |i| (&s[..i], &s[i + 1..]));
+    if !basic.is_ascii() || ext.is_empty() { return None; }
+    let mut out: Vec<char> = basic.chars().collect();
+    let (mut n, mut bias, mut i) = (128u32, 72u32, 0u32);
+    let mut digits = ext.bytes();
+    while digits.len() > 0 {
+        let (old, mut w, mut k) = (i, 1u32, 36u32);
+        loop {
+            let d = match digits.next()? { b @ b'a'..=b'z' => b - b'a', b @ b'0'..=b'9' => b - b'0' + 26, _ => return None } as u32;
+            i = i.checked_add(d.checked_mul(w)?)?;
+            let t = if k <= bias { 1 } else if k >= bias + 26 { 26 } else { k - bias };
+            if d < t { break; }
+            w = w.checked_mul(36 - t)?;
+            k += 36;
+        }
+        let len = out.len() as u32 + 1;
+        bias = adapt(i - old, len, old == 0);
+        n = n.checked_add(i / len)?;
+        i %= len;
+        out.insert(i as usize, char::from_u32(n)?);
+        i += 1;
+    }
+    Some(out.into_iter().collect())
+}
+
+/// `host` as the address bar renders it, with punycode labels decoded.
+pub(crate) fn display_host(host: &str) -> String {
+    host.split('.').map(|l| decode_label(l).unwrap_or_else(|| l.to_string())).collect::<Vec<_>>().join(".")
+}
+
+/// What `text` looks like at a glance: confusables folded to ASCII,
+/// fullwidth forms narrowed, look-alike letter pairs merged.
+pub(crate) fn skeleton(text: &str) -> String {
+    let mut s: String = text.chars().flat_map(char::to_lowercase).map(|c| match c {
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(c as u32 - 0xfee0).unwrap_or(c),
+        _ => c,
+    }).collect();
+    for (from, to) in CONFUSABLES {
+        if s.contains(from) { s = s.replace(from, to); }
+    }
+    s
+}
+
+/// Brand a label of `host` (or a hyphenated part of one) spells with
+/// non-ASCII look-alikes, as in a Cyrillic `аpple`; punycode labels are
+/// decoded first.
+pub(crate) fn homoglyph_brand(host: &str, extra_brands: &[String]) -> Option<String> {
+    let shown = display_host(&host.trim_end_matches('.').to_lowercase());
+    let brands = BRANDS.iter().copied().chain(extra_brands.iter().map(String::as_str));
+    shown.split('.').filter(|l| !l.is_ascii()).find_map(|l| {
+        let sk = skeleton(l);
+        brands.clone().find(|b| b.len() >= 4 && sk.split('-').any(|part| part == skeleton(b))).map(str::to_string)
+    })
+}
+
+/// Homoglyph signals of `url`'s host, decoded as the address bar shows it:
+/// a label forging a built-in brand with look-alike letters, the share of
+/// the host written in non-ASCII confusables, Latin mixed with Greek or
+/// Cyrillic in one label, and an `xn--` label that isn't valid punycode.
+pub fn homoglyph_features(url: &str) -> [f32; HOMOGLYPH_FEATURES] {
+    let host = parse_url(url.trim()).host.trim_end_matches('.').to_lowercase();
+    let shown = display_host(&host);
+    let n = shown.chars().count().max(1) as f32;
+    let confusable = shown.chars().filter(|c| {
+        let mut b = [0u8; 4];
+        !c.is_ascii() && CONFUSABLES.iter().any(|(from, _)| *from == c.encode_utf8(&mut b))
+    }).count();
+    [
+        homoglyph_brand(&host, &[]).is_some() as u8 as f32,
+        confusable as f32 / n,
+        shown.split('.').any(mixed_script) as u8 as f32,
+        host.split('.').any(|l| l.starts_with("xn--") && decode_label(l).is_none()) as u8 as f32,
+    ]
+}
+
+fn greek_or_cyrillic(c: char) -> bool { ('\u{370}'..='\u{52f}').contains(&c) }
+
+/// One label writing Latin letters next to Greek or Cyrillic ones.
+fn mixed_script(label: &str) -> bool {
+    label.chars().any(|c| c.is_ascii_alphabetic()) && label.chars().any(greek_or_cyrillic)
+}
+
+/// [`display_spoof_risk`] protecting `extra_brands` alongside the built-in ones.
+pub(crate) fn display_report(url: &str, max_chars: usize, extra_brands: &[String]) -> Json {
+    let max_chars = if max_chars == 0 { MOBILE_OMNIBOX_CHARS } else { max_chars };
+    let host = parse_url(url.trim()).host.trim_end_matches('.').to_lowercase();
+    let shown = display_host(&host);
+    let brands = BRANDS.iter().copied().chain(extra_brands.iter().map(String::as_str));
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+
+    // a label that reads as a brand without being spelled as one
+    let lookalike = shown.split('.').find_map(|l| {
+        let sk = skeleton(l);
+        brands.clone().find(|b| b.len() >= 4 && sk == skeleton(b) && l != *b)
+    });
+    if lookalike.is_some() { flags.push("confusable_domain"); risk += 0.8; }
+    if shown.split('.').any(mixed_script) { flags.push("mixed_script_label"); risk += 0.4; }
+
+    // the registrable domain sits at the end, so a cut-off host hides it first
+    let reg = registrable_domain(&shown);
+    let visible: String = shown.chars().take(max_chars).collect();
+    let hidden = !reg.is_empty() && shown.chars().count() > max_chars;
+    let reg_label = reg.split('.').next().unwrap_or("");
+    let visible_brand = if hidden {
+        let vis = skeleton(&visible);
+        brands.clone().find(|b| b.len() >= 4 && vis.contains(&skeleton(b)) && skeleton(reg_label) != skeleton(b))
+    } else {
+        None
+    };
+    if hidden { flags.push("registrable_domain_hidden"); risk += 0.25; }
+    if visible_brand.is_some() { flags.push("visible_brand_lure"); risk += 0.45; }
+
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("host", host.as_str())
+        .with("display_host", shown.as_str())
+        .with("skeleton", skeleton(&shown))
+        .with("registrable_domain", reg)
+        .with("lookalike_of", lookalike.or(visible_brand))
+        .with("max_chars", max_chars)
+        .with("visible", visible)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
+
+/// Whether `url` as rendered could pass for another domain: a label whose
+/// confusable skeleton equals a protected brand, Latin mixed with Greek or
+/// Cyrillic in one label, or a host so long that an omnibox `max_chars`
+/// wide (0 means [`MOBILE_OMNIBOX_CHARS`]) cuts off the registrable domain,
+/// worse when the part left in view names a brand. Returns `{ host,
+/// display_host, skeleton, registrable_domain, lookalike_of, max_chars,
+/// visible, flags, score, verdict }`.
+pub fn display_spoof_risk(url: &str, max_chars: usize) -> Json { display_report(url, max_chars, &[]) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punycode_labels_decode_to_unicode() {
+        assert_eq!(decode_label("xn--bcher-kva").as_deref(), Some("bücher"));
+        assert_eq!(decode_label("xn--pypal-4ve").as_deref(), Some("pаypal"));
+        assert_eq!(decode_label("example"), None);
+    }
+
+    #[test]
+    fn labels_without_a_delimiter_are_all_digits() {
+        assert_eq!(decode_label("xn--tda").as_deref(), Some("ü"));
+        assert_eq!(decode_label("xn--ls8h").as_deref(), Some("\u{1f4a9}"));
+    }
+
+    #[test]
+    fn empty_or_ascii_only_labels_are_rejected() {
+        assert_eq!(decode_label("xn--"), None);
+        assert_eq!(decode_label("xn--abc-"), None);
+    }
+
+    #[test]
+    fn invalid_digits_are_rejected() {
+        assert_eq!(decode_label("xn--bcher-k!a"), None);
+        assert_eq!(decode_label("xn--bcher-kvä"), None);
+        assert_eq!(decode_label("xn--bücher-kva"), None);
+    }
+
+    #[test]
+    fn truncated_digit_runs_are_rejected() {
+        // `z` never ends a variable-length integer on its own.
+        assert_eq!(decode_label("xn--abc-z"), None);
+    }
+
+    #[test]
+    fn overflowing_digit_runs_are_rejected() {
+        assert_eq!(decode_label(&format!("xn--{}a", "9".repeat(20))), None);
+        assert_eq!(decode_label("xn--a-99999999999999999999a"), None);
+    }
+
+    #[test]
+    fn undecodable_labels_show_as_written_and_are_flagged() {
+        assert_eq!(display_host("xn--bcher-k!a.example"), "xn--bcher-k!a.example");
+        assert_eq!(display_host("xn--bcher-kva.example"), "bücher.example");
+        assert_eq!(homoglyph_features("https://xn--abc-z.example/")[3], 1.0);
+        assert_eq!(homoglyph_features("https://xn--bcher-kva.example/")[3], 0.0);
+    }
+}
@@ -0,0 +1,202 @@
+// wasm-feature/crates/vigilant-core/src/disposition.rs
+// Browser Vigilant — Content-Disposition filename decoding (RFC 6266 / 5987 / 2231)
+// and normalisation against right-to-left-override and path tricks
+
+use crate::json::Json;
+use crate::verdict::Verdict;
+use crate::{percent_decode_bytes, score_filename_with_brands};
+
+/// Bidi embedding/override/isolate controls plus the LRM/RLM marks.
+const BIDI_CONTROLS: &[char] = &[
+    '\u{200E}','\u{200F}','\u{202A}','\u{202B}','\u{202C}','\u{202D}','\u{202E}',
+    '\u{2066}','\u{2067}','\u{2068}','\u{2069}',
+];
+const RLO: char = '\u{202E}';
+const PDF: char = '\u{202C}';
+
+pub fn has_bidi_override(name: &str) -> bool { name.contains(RLO) || name.contains('\u{2067}') }
+
+/// The name a filesystem would store: last path component, no bidi or other
+/// control characters, no trailing dots/spaces (Windows strips those).
+pub fn safe_filename(raw: &str) -> String {
+    let base = raw.rsplit(['/', '\\']).next().unwrap_or(raw);
+    let cleaned: String = base.chars().filter(|c| !BIDI_CONTROLS.contains(c) && !c.is_control()).collect();
+    cleaned.trim().trim_end_matches(['.', ' ']).to_string()
+}
+
+/// Roughly how the name renders: text after an RLO runs right-to-left until
+/// the matching PDF, so `invoice\u{202E}fdp.exe` shows as `invoiceexe.pdf`.
+pub fn displayed_filename(raw: &str) -> String {
+    let mut out = String::new();
+    let mut rest = raw;
+    while let Some(p) = rest.find(RLO) {
+        out.push_str(&rest[..p]);
+        let after = &rest[p + RLO.len_utf8()..];
+        let end = after.find(PDF).unwrap_or(after.len());
+        out.extend(after[..end].chars().rev());
+        rest = after.get(end + PDF.len_utf8()..).unwrap_or("");
+    }
+    out.push_str(rest);
+    out.chars().filter(|c| !BIDI_CONTROLS.contains(c)).collect()
+}
+
+fn ext_of(name: &str) -> String {
+    name.to_lowercase().rsplit_once('.').map(|(_, e)| e.to_string()).unwrap_or_default()
+}
+
+// ── Header parsing ───────────────────────────────────────────────────────────
+
+/// Split `attachment; filename="a;b.txt"; size=3` into (name, value) pairs,
+/// honouring quoted strings and backslash escapes.
+fn params(header: &str) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    let mut chars = header.chars().peekable();
+    // disposition type
+    for c in chars.by_ref() { if c == ';' { break; } }
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace() || *c == ';') { chars.next(); }
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '=' || c == ';' { break; }
+            name.push(c);
+            chars.next();
+        }
+        if name.is_empty() && chars.peek().is_none() { break; }
+        let mut value = String::new();
+        if chars.peek() == Some(&'=') {
+            chars.next();
+            while chars.peek().is_some_and(|c| *c == ' ') { chars.next(); }
+            if chars.peek() == Some(&'"') {
+                chars.next();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => { if let Some(n) = chars.next() { value.push(n); } }
+                        '"' => break,
+                        _ => value.push(c),
+                    }
+                }
+                for c in chars.by_ref() { if c == ';' { break; } }
+            } else {
+                for c in chars.by_ref() { if c == ';' { break; } value.push(c); }
+            }
+        }
+        out.push((name.trim().to_lowercase(), value.trim_end().to_string()));
+    }
+    out
+}
+
+/// RFC 8187 ext-value: `charset'lang'pct-encoded`. UTF-8 and Latin-1 only.
+fn decode_ext_value(v: &str) -> Option<String> {
+    let mut it = v.splitn(3, '\'');
+    let (charset, _lang, data) = (it.next()?, it.next()?, it.next()?);
+    let bytes = percent_decode_bytes(data, false);
+    match charset.to_lowercase().as_str() {
+        "utf-8" | "" => Some(String::from_utf8_lossy(&bytes).into_owned()),
+        "iso-8859-1" | "latin1" | "us-ascii" => Some(bytes.iter().map(|&b| b as char).collect()),
+        _ => None,
+    }
+}
+
+/// Plain `filename=` and extended `filename*=` values, the latter also
+/// assembled from RFC 2231 continuations (`filename*0*=`, `filename*1=`…).
+fn filename_params(header: &str) -> (Option<String>, Option<String>) {
+    let ps = params(header);
+    let plain = ps.iter().find(|(k, _)| k == "filename").map(|(_, v)| v.clone());
+    let ext = ps.iter().find(|(k, _)| k == "filename*").and_then(|(_, v)| decode_ext_value(v)).or_else(|| {
+        let mut parts: Vec<(usize, bool, &str)> = ps.iter().filter_map(|(k, v)| {
+            let rest = k.strip_prefix("filename*")?;
+            let (idx, encoded) = match rest.strip_suffix('*') { Some(n) => (n, true), None => (rest, false) };
+            Some((idx.parse().ok()?, encoded, v.as_str()))
+        }).collect();
+        if parts.is_empty() { return None; }
+        parts.sort_by_key(|p| p.0);
+        // only the first segment carries charset'lang'; plain segments are literal
+        let joined: String = parts.iter()
+            .map(|(_, encoded, v)| if *encoded { v.to_string() } else { v.replace('%', "%25") })
+            .collect();
+        if parts[0].1 { decode_ext_value(&joined) } else { Some(joined) }
+    });
+    (plain, ext)
+}
+
+/// Filename a browser would use for this header (`filename*` wins).
+pub fn parse_content_disposition(header: &str) -> Option<String> {
+    let (plain, ext) = filename_params(header);
+    ext.or(plain)
+}
+
+// ── Report ───────────────────────────────────────────────────────────────────
+
+/// Decode a Content-Disposition header (or a bare filename) and score the
+/// result. Returns `{ filename, safe_name, displayed_as, ext, displayed_ext,
+/// bidi_override, flags, score, verdict }`.
+pub fn filename_report(header: &str) -> Json { filename_report_with_brands(header, &[]) }
+
+/// [`filename_report`] with `brands` counted next to the built-in list.
+pub(crate) fn filename_report_with_brands(header: &str, brands: &[String]) -> Json {
+    let is_header = header.contains('=') && header.to_lowercase().contains("filename");
+    let (plain, ext) = if is_header { filename_params(header) } else { (Some(header.to_string()), None) };
+    let raw = ext.clone().or(plain.clone()).unwrap_or_default();
+    let safe = safe_filename(&raw);
+    let shown = displayed_filename(&raw);
+    let (real_ext, shown_ext) = (ext_of(&safe), ext_of(&shown));
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = score_filename_with_brands(&raw, brands);
+
+    let bidi = has_bidi_override(&raw);
+    if bidi { flags.push("rtlo_override"); }
+    else if raw.chars().any(|c| BIDI_CONTROLS.contains(&c)) { flags.push("bidi_control"); risk += 0.3; }
+    if real_ext != shown_ext { flags.push("extension_disguised"); risk += 0.3; }
+    if raw.contains(['/', '\\']) { flags.push("path_in_filename"); risk += 0.2; }
+    if raw.trim_end() != raw || raw.ends_with('.') { flags.push("trailing_dot_or_space"); risk += 0.2; }
+    if raw.chars().any(|c| c.is_control()) { flags.push("control_characters"); risk += 0.2; }
+    // Different names for old and new clients: one of them is meant to be missed.
+    if let (Some(p), Some(e)) = (&plain, &ext) {
+        if ext_of(&safe_filename(p)) != ext_of(&safe_filename(e)) { flags.push("fallback_name_mismatch"); risk += 0.3; }
+    }
+
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("filename", raw.as_str())
+        .with("safe_name", safe.as_str())
+        .with("displayed_as", shown.as_str())
+        .with("ext", real_ext)
+        .with("displayed_ext", shown_ext)
+        .with("bidi_override", bidi)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_ext_value_without_its_charset_falls_back_to_the_plain_name() {
+        assert_eq!(parse_content_disposition("attachment; filename=\"plain.pdf\"; filename*=report%20x.exe"), Some("plain.pdf".to_string()));
+        assert_eq!(parse_content_disposition("attachment; filename*=report.exe"), None);
+        assert_eq!(parse_content_disposition("attachment; filename*=koi8-r''x.exe; filename=y.pdf"), Some("y.pdf".to_string()));
+        // an empty charset reads as UTF-8
+        assert_eq!(parse_content_disposition("attachment; filename*=''r%C3%A9sum%C3%A9.pdf"), Some("résumé.pdf".to_string()));
+        assert_eq!(decode_ext_value("utf-8'en"), None);
+    }
+
+    #[test]
+    fn truncated_escapes_are_kept_literally() {
+        assert_eq!(decode_ext_value("UTF-8''a%2"), Some("a%2".to_string()));
+        assert_eq!(decode_ext_value("UTF-8''a%"), Some("a%".to_string()));
+        assert_eq!(decode_ext_value("UTF-8''%zzb.txt"), Some("%zzb.txt".to_string()));
+        assert_eq!(parse_content_disposition("attachment; filename*0*=UTF-8''inv%6; filename*1=oice.exe"), Some("inv%6oice.exe".to_string()));
+    }
+
+    #[test]
+    fn invalid_utf8_decodes_to_replacement_characters() {
+        assert_eq!(decode_ext_value("UTF-8''%ff%fe.exe"), Some("\u{FFFD}\u{FFFD}.exe".to_string()));
+        // a multi-byte sequence cut short
+        assert_eq!(decode_ext_value("UTF-8''a%E2%80.pdf"), Some("a\u{FFFD}.pdf".to_string()));
+        assert_eq!(decode_ext_value("iso-8859-1''%ff.txt"), Some("ÿ.txt".to_string()));
+        let r = filename_report("attachment; filename*=UTF-8''%ff.exe");
+        assert_eq!(r.get("ext").and_then(Json::as_str), Some("exe"));
+    }
+}
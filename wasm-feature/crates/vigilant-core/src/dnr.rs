@@ -0,0 +1,144 @@
+// wasm-feature/crates/vigilant-core/src/dnr.rs
+// Browser Vigilant — overrides and feeds as Chrome declarativeNetRequest
+// rules, so MV3 network-level blocking stays in step with what the engine
+// knows. Works on the `export_state` document, which the extension already
+// keeps, so the service worker can rebuild rules without calling in.
+
+use crate::json::Json;
+use crate::state::KEY_CHECK;
+use crate::types::{err, Fields, SchemaError};
+use crate::verdict::Verdict;
+
+/// Domains listed in one rule's `requestDomains`.
+pub const DNR_DOMAINS_PER_RULE: usize = 1000;
+
+/// Chrome's guaranteed dynamic-rule quota, used when the caller passes 0.
+pub const DNR_DEFAULT_MAX_RULES: usize = 5000;
+
+/// Navigations only: blocking subresources of a blocked host is the page
+/// analyzers' job, and DNR would break pages that merely embed it.
+const RESOURCE_TYPES: &[&str] = &["main_frame", "sub_frame"];
+
+/// Bare ASCII hostname as `requestDomains` accepts it (IDNs must arrive as punycode).
+fn valid_domain(h: &str) -> bool {
+    !h.is_empty() && h.len() <= 253 && h.contains('.') && !h.starts_with('.') && !h.ends_with('.')
+        && h.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'.')
+}
+
+fn rule(id: usize, priority: u32, action: &str, domains: &[String]) -> Json {
+    Json::obj()
+        .with("id", id)
+        .with("priority", priority)
+        .with("action", Json::obj().with("type", action))
+        .with("condition", Json::obj()
+            .with("requestDomains", domains.to_vec())
+            .with("resourceTypes", RESOURCE_TYPES.to_vec()))
+}
+
+/// Convert a `Context::export_state` document into DNR rules, at most
+/// `max_rules` of them (0 means [`DNR_DEFAULT_MAX_RULES`]). User allows
+/// (priority 3) outrank user blocks (2), which outrank feed hosts (1); when
+/// the quota runs out, feed hosts are dropped first. Rule ids start at 1.
+/// Wildcard and path-scoped overrides have no `requestDomains` form and
+/// count as invalid. Returns `{ rules, hosts, emitted, dropped, invalid }`.
+/// Fails on a hash-only state, whose hosts can't be turned back into domains.
+pub fn dnr_rules(state: &Json, max_rules: usize) -> Result<Json, SchemaError> {
+    let f = Fields::new(state, "")?;
+    f.schema_version()?;
+    let check = state.get("state").and_then(|s| s.get("key_check")).and_then(Json::as_str);
+    if check.is_some_and(|k| k != KEY_CHECK) {
+        return Err(err("state.key_check", "hash-only state has no hosts to export"));
+    }
+    let overrides = f.map("overrides", |x, p| x.as_str().and_then(Verdict::parse).ok_or_else(|| err(p, "expected verdict")))?;
+    let feeds = f.map("feeds", |x, p| {
+        let list = x.as_array().ok_or_else(|| err(p, "expected array"))?;
+        Ok(list.iter().filter_map(Json::as_str).map(str::to_string).collect::<Vec<_>>())
+    })?;
+
+    let pick = |v: Verdict| -> Vec<String> { overrides.iter().filter(|(_, o)| *o == v).map(|(h, _)| h.clone()).collect() };
+    let mut feed_hosts: Vec<String> = feeds.into_iter().flat_map(|(_, l)| l).collect();
+    feed_hosts.sort();
+    feed_hosts.dedup();
+    let groups = [(3, "allow", pick(Verdict::Allow)), (2, "block", pick(Verdict::Block)), (1, "block", feed_hosts)];
+
+    let budget = if max_rules == 0 { DNR_DEFAULT_MAX_RULES } else { max_rules };
+    let (mut rules, mut hosts, mut emitted, mut dropped, mut invalid) = (Vec::new(), 0, 0, 0, 0);
+    for (priority, action, list) in groups {
+        let total = list.len();
+        let mut valid: Vec<String> = list.into_iter().filter(|h| valid_domain(h)).collect();
+        hosts += total;
+        invalid += total - valid.len();
+        valid.sort();
+        let mut chunks = valid.chunks(DNR_DOMAINS_PER_RULE);
+        for chunk in chunks.by_ref().take(budget - rules.len()) {
+            rules.push(rule(rules.len() + 1, priority, action, chunk));
+            emitted += chunk.len();
+        }
+        dropped += chunks.map(<[String]>::len).sum::<usize>();
+    }
+    Ok(Json::obj()
+        .with("rules", Json::Arr(rules))
+        .with("hosts", hosts)
+        .with("emitted", emitted)
+        .with("dropped", dropped)
+        .with("invalid", invalid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Context;
+    use crate::hash::Privacy;
+
+    fn context() -> Context {
+        let mut ctx = Context::default();
+        ctx.set_override("good.example", Verdict::Allow);
+        ctx.set_override("bad.example", Verdict::Block);
+        ctx.set_override("*.wild.example", Verdict::Block);
+        ctx.load_feed("feed", ["phish.example", "other.example", "localhost"]);
+        ctx
+    }
+
+    fn count(r: &Json, key: &str) -> f64 { r.get(key).and_then(Json::as_f64).unwrap_or(-1.0) }
+
+    fn summary(rule: &Json) -> (f64, f64, String, Vec<String>) {
+        let cond = rule.get("condition").unwrap();
+        (count(rule, "id"), count(rule, "priority"), rule.get("action").unwrap().str_field("type").to_string(), cond.str_list("requestDomains"))
+    }
+
+    #[test]
+    fn allows_outrank_blocks_outrank_feeds() {
+        let r = context().dnr_rules(0).unwrap();
+        let rules: Vec<_> = r.get("rules").and_then(Json::as_array).unwrap().iter().map(summary).collect();
+        assert_eq!(rules, [
+            (1.0, 3.0, "allow".to_string(), vec!["good.example".to_string()]),
+            (2.0, 2.0, "block".to_string(), vec!["bad.example".to_string()]),
+            (3.0, 1.0, "block".to_string(), vec!["other.example".to_string(), "phish.example".to_string()]),
+        ]);
+        // the wildcard override and the dotless feed host have no requestDomains form
+        assert_eq!((count(&r, "hosts"), count(&r, "emitted"), count(&r, "invalid"), count(&r, "dropped")), (6.0, 4.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn feed_hosts_go_first_when_the_quota_runs_out() {
+        let r = context().dnr_rules(2).unwrap();
+        assert_eq!(r.get("rules").and_then(Json::as_array).map(|a| a.len()), Some(2));
+        assert_eq!((count(&r, "emitted"), count(&r, "dropped")), (2.0, 2.0));
+    }
+
+    #[test]
+    fn hash_only_state_is_refused() {
+        let mut ctx = context();
+        ctx.set_privacy(Privacy::hashed(b"salt"));
+        ctx.set_override("bad.example", Verdict::Block);
+        assert_eq!(ctx.dnr_rules(0).unwrap_err().path, "state.key_check");
+    }
+
+    #[test]
+    fn domains_must_be_bare_ascii_hosts() {
+        assert!(valid_domain("a-b.example.co.uk"));
+        for h in ["", "localhost", ".example.com", "example.com.", "Example.com", "bücher.de", "a_b.example"] {
+            assert!(!valid_domain(h), "{}", h);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+// wasm-feature/crates/vigilant-core/src/download.rs
+// Browser Vigilant — download verdict: filename risk plus content sniffing
+
+use crate::apk::apk_report;
+use crate::archive::archive_report;
+use crate::disposition::filename_report_with_brands;
+use crate::html_scan::{html_attachment_report, looks_like_markup};
+use crate::json::Json;
+use crate::office::office_report;
+use crate::pdf::pdf_report;
+use crate::policy::download_context_report;
+use crate::score::combine;
+use crate::types::DownloadEvent;
+use crate::verdict::Verdict;
+use crate::{score_filename_with_brands, zip};
+
+/// Container type from magic bytes.
+pub fn sniff(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"%PDF-") || bytes[..bytes.len().min(1024)].windows(5).any(|w| w == b"%PDF-") { return "pdf"; }
+    if bytes.starts_with(b"MZ") { return "pe"; }
+    if bytes.starts_with(b"\x7fELF") { return "elf"; }
+    if bytes.starts_with(&[0xCF, 0xFA, 0xED, 0xFE]) || bytes.starts_with(&[0xCA, 0xFE, 0xBA, 0xBE]) { return "macho"; }
+    if bytes.starts_with(&[0xD0, 0xCF, 0x11, 0xE0]) { return "ole2"; }
+    if bytes.starts_with(b"Rar!") { return "rar"; }
+    if bytes.starts_with(&[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]) { return "7z"; }
+    if bytes.starts_with(&[0x1F, 0x8B]) { return "gzip"; }
+    if zip::is_zip(bytes) { return "zip"; }
+    if bytes.get(0x8001..0x8006) == Some(b"CD001") || bytes.get(0x8001..0x8006) == Some(b"BEA01") { return "iso"; }
+    if bytes.starts_with(b"conectix") || bytes.starts_with(b"vhdxfile") { return "disk_image"; }
+    if let Some(markup) = looks_like_markup(bytes) { return markup; }
+    "unknown"
+}
+
+/// Extensions each sniffed type may legitimately carry.
+fn expected_exts(kind: &str) -> &'static [&'static str] {
+    match kind {
+        "pdf"   => &["pdf"],
+        "pe"    => &["exe","dll","scr","sys","cpl","msi","com","efi"],
+        "elf"   => &["so","bin","run","elf",""],
+        "macho" => &["dylib","bin",""],
+        "ole2"  => &["doc","xls","ppt","msi","msg","vsd","pub"],
+        "rar"   => &["rar"],
+        "7z"    => &["7z"],
+        "gzip"  => &["gz","tgz"],
+        "svg"   => &["svg","svgz"],
+        "iso"   => &["iso","udf","img"],
+        "disk_image" => &["vhd","vhdx","img"],
+        "html"  => &["html","htm","xhtml","shtml","mht","mhtml","hta"],
+        "zip"   => &["zip","docx","xlsx","pptx","docm","xlsm","pptm","jar","apk","ipa","odt","ods","epub","xpi","crx","appx","msix","nupkg","whl"],
+        _       => &[],
+    }
+}
+
+/// Analyze a completed (or partially buffered) download.
+/// Returns `{ filename_score, kind, ext_mismatch, content, flags, score, verdict }`.
+pub fn download_report(filename: &str, bytes: &[u8]) -> Json { download_report_with_brands(filename, bytes, &[]) }
+
+/// [`download_report`] with `brands` counted next to the built-in list.
+pub(crate) fn download_report_with_brands(filename: &str, bytes: &[u8], brands: &[String]) -> Json {
+    let name_score = score_filename_with_brands(filename, brands);
+    let ext = filename.to_lowercase().rsplit_once('.').map(|(_, e)| e.to_string()).unwrap_or_default();
+    let kind = sniff(bytes);
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut score = name_score;
+
+    let expected = expected_exts(kind);
+    let ext_mismatch = !expected.is_empty() && !expected.contains(&ext.as_str());
+    if ext_mismatch {
+        flags.push("extension_content_mismatch");
+        // an executable dressed up as a document is the worst case
+        score += if matches!(kind, "pe" | "elf" | "macho") { 0.7 } else { 0.2 };
+    }
+
+    let content = match kind {
+        "pdf" => pdf_report(bytes),
+        "zip" => {
+            let is = |r: &Json, key: &str| r.get(key).and_then(Json::as_bool) == Some(true);
+            let office = office_report(bytes);
+            if is(&office, "is_ooxml") { office } else {
+                let apk = apk_report(bytes);
+                if is(&apk, "is_apk") { apk } else { archive_report(filename, bytes, "") }
+            }
+        }
+        "rar" | "7z" | "iso" | "disk_image" => archive_report(filename, bytes, ""),
+        "svg" | "html" => html_attachment_report(bytes),
+        _ => Json::Null,
+    };
+    let content_score = content.get("score").and_then(Json::as_f64).unwrap_or(0.0) as f32;
+    score = score.max(content_score) + 0.25 * score.min(content_score);
+    let score = score.min(1.0);
+
+    Json::obj()
+        .with("filename_score", name_score)
+        .with("kind", kind)
+        .with("ext_mismatch", ext_mismatch)
+        .with("content", content)
+        .with("flags", flags)
+        .with("score", score)
+        .with("verdict", Verdict::from_score(score).as_str())
+}
+
+/// Everything known about one download at once: the served name, the policy
+/// context and, when `bytes` is non-empty, the content itself.
+/// Returns `{ filename, name, context, content, flags, score, verdict }`.
+pub fn download_event_report(e: &DownloadEvent, bytes: &[u8]) -> Json { download_event_report_with_brands(e, bytes, &[]) }
+
+/// [`download_event_report`] with `brands` counted next to the built-in list.
+pub(crate) fn download_event_report_with_brands(e: &DownloadEvent, bytes: &[u8], brands: &[String]) -> Json {
+    let name = filename_report_with_brands(e.content_disposition.as_deref().unwrap_or(&e.filename), brands);
+    let filename = match name.str_field("safe_name") { "" => e.filename.clone(), s => s.to_string() };
+    let ext = filename.to_lowercase().rsplit_once('.').map(|(_, x)| x.to_string()).unwrap_or_default();
+    let context = download_context_report(&e.mime, &ext, &e.initiating_origin, e.user_gesture);
+    let content = if bytes.is_empty() { Json::Null } else { download_report_with_brands(&filename, bytes, brands) };
+
+    let policy_score = match context.str_field("verdict") { "block" => 0.85, "warn" => 0.5, _ => 0.0 };
+    let score_of = |r: &Json| r.get("score").and_then(Json::as_f64).unwrap_or(0.0) as f32;
+    let mut scores = [score_of(&name), policy_score, score_of(&content)];
+    scores.sort_by(|a, b| b.total_cmp(a));
+    let score = combine(scores[0], scores[1]);
+
+    let mut flags = name.str_list("flags");
+    flags.extend(content.str_list("flags"));
+    if context.get("mime_mismatch").and_then(Json::as_bool) == Some(true) { flags.push("mime_mismatch".to_string()); }
+    flags.sort();
+    flags.dedup();
+
+    Json::obj()
+        .with("filename", filename)
+        .with("name", name)
+        .with("context", context)
+        .with("content", content)
+        .with("flags", flags)
+        .with("score", score)
+        .with("verdict", Verdict::from_score(score).as_str())
+}
@@ -0,0 +1,103 @@
+// wasm-feature/crates/vigilant-core/src/embedded.rs
+// Browser Vigilant — URLs inside URLs. An open redirect on a trusted site
+// (`https://bank.example/out?u=https%3A%2F%2Fevil.tk%2Flogin`) lends the
+// lure its host: the model scores the bank, F53 only notices that some
+// `url=http` is there, and the page the user lands on is never looked at
+// until the redirect fires. The payloads sit in query and fragment
+// values, percent-encoded once or more, and often chain through a second
+// redirector, so they are decoded until they read as URLs and unpacked
+// level by level.
+
+use crate::encoding::MAX_DECODE_DEPTH;
+use crate::json::Json;
+use crate::psl::registrable_domain;
+use crate::verdict::Verdict;
+use crate::{parse_url, percent_decode, query_pairs, url_risk};
+
+/// Redirect hops unpacked below the outer URL.
+pub const MAX_EMBED_DEPTH: usize = 4;
+
+/// Most embedded URLs one call reports.
+pub const MAX_EMBEDDED_URLS: usize = 32;
+
+/// `value` as an absolute URL, decoding it again while it doesn't read as
+/// one; `None` when no decoding makes it one.
+fn as_url(value: &str) -> Option<String> {
+    let mut v = value.trim().to_string();
+    for _ in 0..=MAX_DECODE_DEPTH {
+        let low = v.to_ascii_lowercase();
+        if ["http://", "https://", "ftp://"].iter().any(|s| low.starts_with(s)) { return Some(v); }
+        if low.starts_with("//") && low.len() > 2 { return Some(format!("https:{v}")); }
+        if low.starts_with("www.") { return Some(format!("https://{v}")); }
+        let next = percent_decode(&v, false);
+        if next == v { break; }
+        v = next.trim().to_string();
+    }
+    None
+}
+
+/// `(source, param, value)` of every query and fragment value of `url`. A
+/// fragment is a value of its own, and a client route's parameters
+/// (`#/out?u=…`, `#u=…`) are values too.
+fn values(url: &str) -> Vec<(&'static str, String, String)> {
+    let p = parse_url(url);
+    let mut out: Vec<(&'static str, String, String)> = query_pairs(&p.query).into_iter().map(|(k, v)| ("query", k, v)).collect();
+    if !p.fragment.is_empty() {
+        out.push(("fragment", String::new(), percent_decode(&p.fragment, false)));
+        let params = p.fragment.rsplit_once('?').map_or(p.fragment.as_str(), |(_, q)| q);
+        if params.contains('=') {
+            out.extend(query_pairs(params).into_iter().map(|(k, v)| ("fragment", k, v)));
+        }
+    }
+    out
+}
+
+/// URLs hidden in the query and fragment values of `url`, decoded, and the
+/// ones hidden in theirs down to [`MAX_EMBED_DEPTH`] hops, outermost first
+/// and each once. With `analyze`, every one is scored on its own
+/// features. Returns `{ url, embedded: [{ url, source, param, depth, host,
+/// reg_domain, off_site, risk, verdict }], max_risk, verdict }`: `source`
+/// is "query" or "fragment", `param` the key ("" for a whole fragment),
+/// `depth` 1 for a URL in `url` itself, and `off_site` whether it leaves
+/// the registrable domain of the URL it sits in. Without `analyze` the
+/// risks and verdicts are null.
+pub fn extract_embedded_urls(url: &str, analyze: bool) -> Json {
+    let url = url.trim();
+    let mut seen: Vec<String> = vec![url.to_string()];
+    let mut embedded: Vec<Json> = Vec::new();
+    let mut max_risk = 0.0f32;
+    let mut level: Vec<String> = vec![url.to_string()];
+    for depth in 1..=MAX_EMBED_DEPTH {
+        let mut next: Vec<String> = Vec::new();
+        for outer in &level {
+            let outer_site = registrable_domain(&parse_url(outer).host.to_lowercase());
+            for (source, param, value) in values(outer) {
+                let Some(inner) = as_url(&value) else { continue };
+                if seen.contains(&inner) || embedded.len() >= MAX_EMBEDDED_URLS { continue; }
+                let host = parse_url(&inner).host.to_lowercase();
+                let site = registrable_domain(&host);
+                let risk = analyze.then(|| url_risk(&inner));
+                max_risk = max_risk.max(risk.unwrap_or(0.0));
+                embedded.push(Json::obj()
+                    .with("url", inner.as_str())
+                    .with("source", source)
+                    .with("param", param)
+                    .with("depth", depth)
+                    .with("host", host)
+                    .with("reg_domain", site.as_str())
+                    .with("off_site", site != outer_site)
+                    .with("risk", risk)
+                    .with("verdict", risk.map(|r| Verdict::from_score(r).as_str())));
+                seen.push(inner.clone());
+                next.push(inner);
+            }
+        }
+        if next.is_empty() { break; }
+        level = next;
+    }
+    Json::obj()
+        .with("url", url)
+        .with("embedded", embedded)
+        .with("max_risk", analyze.then_some(max_risk))
+        .with("verdict", analyze.then(|| Verdict::from_score(max_risk).as_str()))
+}
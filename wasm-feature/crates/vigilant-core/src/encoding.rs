@@ -0,0 +1,111 @@
+// wasm-feature/crates/vigilant-core/src/encoding.rs
+// Browser Vigilant — escapes of escapes. `%2e%2e` is caught by a raw-text
+// check and resolved by `normalize_url`, so kits encode twice: `%252e%252e`
+// turns into `%2e%2e` at the first server that decodes it and into `..` at
+// the next, and `%256c%256f%2567%2569%256e` spells `login` for a backend
+// while looking like noise to any check that decodes once. Ordinary links
+// need a single pass. Decoding until nothing changes recovers what the last
+// hop sees, which is what the model's keyword and traversal features (F24–
+// F29, F37) read, and the number of passes that took is a signal of its own.
+
+use crate::{parse_url, percent_decode, FRAUD_KW, FREE_KW, LOGIN_KW, PAY_KW, TRUST_KW};
+
+/// Values returned by [`encoding_features`]: encoding_depth,
+/// hidden_traversal, hidden_keyword.
+pub const ENCODING_FEATURES: usize = 3;
+
+/// Decoding passes after which [`decode_fully`] stops; no client or server
+/// chain decodes this often, so anything deeper is reported at the cap.
+pub(crate) const MAX_DECODE_DEPTH: usize = 8;
+
+/// `s` percent-decoded until a pass changes nothing (at most
+/// [`MAX_DECODE_DEPTH`] passes), and the number of passes that changed it.
+/// `+` stays as it is: only the query gives it a meaning.
+pub(crate) fn decode_fully(s: &str) -> (String, usize) {
+    let mut out = s.to_string();
+    let mut depth = 0;
+    while depth < MAX_DECODE_DEPTH {
+        let next = percent_decode(&out, false);
+        if next == out { break; }
+        out = next;
+        depth += 1;
+    }
+    (out, depth)
+}
+
+/// Whether `path` has a `..` segment, with `\` as a separator too.
+fn has_traversal(path: &str) -> bool { path.split(['/', '\\']).any(|s| s == "..") }
+
+/// `[encoding_depth, hidden_traversal, hidden_keyword]`: decoding passes
+/// the URL takes to stop changing (0 for plain text, 1 for ordinary
+/// escapes); a `..` segment in the fully decoded path that the raw one
+/// doesn't show; and a phishing keyword in the fully decoded URL that the
+/// raw text doesn't contain.
+pub fn encoding_features(url: &str) -> [f32; ENCODING_FEATURES] {
+    let (decoded, depth) = decode_fully(url);
+    let raw = url.to_lowercase();
+    let decoded = decoded.to_lowercase();
+    let path = parse_url(url).path;
+    // F37's test before decoding
+    let raw_traversal = path.contains("..") || raw.contains("%2e%2e");
+    let hidden_traversal = !raw_traversal && has_traversal(&decode_fully(&path).0);
+    let hidden_keyword = [LOGIN_KW, TRUST_KW, PAY_KW, FREE_KW, FRAUD_KW].concat().iter()
+        .any(|k| decoded.contains(k) && !raw.contains(k));
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    [depth as f32, b(hidden_traversal), b(hidden_keyword)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract_features;
+
+    #[test]
+    fn keyword_and_traversal_features_read_the_decoded_url() {
+        let f = extract_features("https://example.com/%252e%252e/%256c%256f%2567%2569%256e");
+        assert_eq!(f[37], 1.0, "traversal");
+        assert_eq!(f[24], 1.0, "login keyword");
+        assert_eq!(f[29], 1.0 / 6.0, "keyword density");
+        let f = extract_features("https://%2573ecure.example/");
+        assert_eq!(f[25], 1.0, "trust keyword in host");
+    }
+
+    #[test]
+    fn bad_escapes_are_kept_literally() {
+        for s in ["%zz", "%2", "%", "a%4", "%g1b", "100%"] {
+            assert_eq!(decode_fully(s), (s.to_string(), 0), "{s}");
+        }
+        // a stray `%` can pair with what the next pass decodes
+        assert_eq!(decode_fully("%%32%65"), (".".to_string(), 2));
+    }
+
+    #[test]
+    fn overlong_escapes_do_not_decode_to_dots() {
+        let (path, depth) = decode_fully("/a/%c0%ae%c0%ae/b");
+        assert_eq!(depth, 1);
+        assert!(!path.contains(".."), "{path}");
+        assert!(path.contains('\u{FFFD}'), "{path}");
+        assert_eq!(extract_features("https://example.com/a/%c0%ae%c0%ae/b")[37], 0.0);
+        assert_eq!(encoding_features("https://example.com/a/%25c0%25ae%25c0%25ae/b")[1], 0.0);
+    }
+
+    #[test]
+    fn invalid_utf8_after_decoding_is_replaced_once() {
+        let (s, depth) = decode_fully("/%ff%fe/%6c%6f%67%69%6e%c3");
+        assert_eq!(s, "/\u{FFFD}\u{FFFD}/login\u{FFFD}");
+        assert_eq!(depth, 1);
+        let f = extract_features("https://example.com/%ff%fe/%6c%6f%67%69%6e%c3");
+        assert_eq!(f[24], 1.0);
+        assert_eq!(encoding_features("https://example.com/%ff%25fe%c3")[0], 2.0);
+    }
+
+    #[test]
+    fn nesting_past_the_cap_stops_at_max_depth() {
+        let mut s = "%2e".to_string();
+        for _ in 0..MAX_DECODE_DEPTH + 2 { s = s.replacen('%', "%25", 1); }
+        let (out, depth) = decode_fully(&s);
+        assert_eq!(depth, MAX_DECODE_DEPTH);
+        assert!(out.starts_with("%25"), "{out}");
+        assert_eq!(encoding_features(&format!("https://example.com/{s}"))[0], MAX_DECODE_DEPTH as f32);
+    }
+}
@@ -0,0 +1,100 @@
+// wasm-feature/crates/vigilant-core/src/endpoint.rs
+// Browser Vigilant — scoring of XHR, fetch, WebSocket and beacon targets.
+// Requests aren't navigations: a page never shows their URL, so the lexical
+// lures the URL model weighs don't occur, while raw IPs, odd ports, tunnels
+// and collectors are far more telling. Feeds the background request observer.
+
+use std::net::IpAddr;
+use crate::exfil::webhook_service;
+use crate::json::Json;
+use crate::oauth::TUNNEL_HOSTS;
+use crate::psl::registrable_domain;
+use crate::resources::{matches_endpoint, CREDENTIAL_SINKS};
+use crate::verdict::Verdict;
+use crate::{has_ip, ip_host, parse_url, SUSPICIOUS_TLDS};
+
+/// Request types [`score_endpoint`] tells apart; anything else scores as "fetch".
+const ENDPOINT_KINDS: &[&str] = &["fetch", "xhr", "websocket", "beacon"];
+
+/// Weight of each signal for fetch/XHR, WebSocket and beacon requests.
+/// Long-lived sockets to raw hosts are how miners and C2 panels talk;
+/// beacons fire on unload and suit one-shot exfiltration.
+const SIGNAL_WEIGHTS: &[(&str, [f32; 3])] = &[
+    ("messaging_webhook",        [0.9, 0.9, 0.9]),
+    ("endpoint_private_network", [0.6, 0.6, 0.5]),
+    ("credential_sink",          [0.45, 0.3, 0.5]),
+    ("endpoint_ip_host",         [0.35, 0.45, 0.4]),
+    ("endpoint_tunnel_host",     [0.35, 0.4, 0.4]),
+    ("endpoint_suspicious_tld",  [0.2, 0.2, 0.25]),
+    ("endpoint_raw_port",        [0.15, 0.3, 0.2]),
+];
+
+/// Signals that count in full even when the endpoint is the page's own site.
+const SAME_SITE_FULL: &[&str] = &["messaging_webhook", "endpoint_private_network", "credential_sink"];
+
+fn weight_column(kind: &str) -> usize {
+    match kind { "websocket" => 1, "beacon" => 2, _ => 0 }
+}
+
+/// Loopback, RFC 1918 or link-local host, or the IPv6 equivalents.
+fn private_host(host: &str) -> bool {
+    if host == "localhost" || host.ends_with(".localhost") { return true; }
+    match ip_host(host) {
+        Some(IpAddr::V4(a)) => a.is_loopback() || a.is_private() || a.is_link_local(),
+        // fc00::/7 unique local, fe80::/10 link-local
+        Some(IpAddr::V6(a)) => a.is_loopback() || (a.segments()[0] & 0xfe00) == 0xfc00 || (a.segments()[0] & 0xffc0) == 0xfe80,
+        None => false,
+    }
+}
+
+/// Registrable domain of `host`, or the address itself for an IP.
+pub(crate) fn site_of(host: &str) -> String {
+    if has_ip(host) { host.to_string() } else { registrable_domain(host) }
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme { "http" | "ws" => Some(80), "https" | "wss" => Some(443), _ => None }
+}
+
+/// Score a subresource request to `url` made by the page at
+/// `initiator_origin`; `kind` is one of "fetch", "xhr", "websocket" or
+/// "beacon" and picks the weights. Same-site endpoints count at half
+/// weight, except for collectors, chat bots and local-network targets.
+/// Returns `{ kind, host, site, port, third_party, flags, score, verdict }`.
+pub fn score_endpoint(url: &str, initiator_origin: &str, kind: &str) -> Json {
+    let kind = ENDPOINT_KINDS.iter().copied().find(|k| k.eq_ignore_ascii_case(kind.trim())).unwrap_or("fetch");
+    let p = parse_url(url.trim());
+    let host = p.host.trim_end_matches('.').to_lowercase();
+    let site = site_of(&host);
+    let origin_host = parse_url(initiator_origin.trim()).host.to_lowercase();
+    let third_party = !origin_host.is_empty() && site_of(&origin_host) != site;
+
+    let mut flags: Vec<&'static str> = Vec::new();
+    if webhook_service(url).is_some() { flags.push("messaging_webhook"); }
+    if matches_endpoint(CREDENTIAL_SINKS, url) { flags.push("credential_sink"); }
+    if private_host(&host) {
+        // a local page talking to itself is ordinary
+        if !origin_host.is_empty() && !private_host(&origin_host) { flags.push("endpoint_private_network"); }
+    } else if has_ip(&host) {
+        flags.push("endpoint_ip_host");
+    }
+    if TUNNEL_HOSTS.contains(&site.as_str()) { flags.push("endpoint_tunnel_host"); }
+    if SUSPICIOUS_TLDS.contains(&p.tld.as_str()) { flags.push("endpoint_suspicious_tld"); }
+    if p.port.is_some_and(|port| Some(port) != default_port(&p.scheme)) { flags.push("endpoint_raw_port"); }
+
+    let column = weight_column(kind);
+    let risk: f32 = flags.iter().map(|f| {
+        let w = SIGNAL_WEIGHTS.iter().find(|(n, _)| n == f).map_or(0.0, |(_, w)| w[column]);
+        if third_party || origin_host.is_empty() || SAME_SITE_FULL.contains(f) { w } else { w * 0.5 }
+    }).sum();
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("kind", kind)
+        .with("host", host.as_str())
+        .with("site", site)
+        .with("port", p.port.map(|port| port as f64))
+        .with("third_party", third_party)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
@@ -0,0 +1,89 @@
+// wasm-feature/crates/vigilant-core/src/exfil.rs
+// Browser Vigilant — chat-bot webhook exfiltration. Kits increasingly skip a
+// collection server and post harvested credentials straight to a Telegram
+// bot or Discord webhook. No legitimate login page does that, so a hit in a
+// form action, a network request or an inline script is a critical finding.
+
+use crate::json::Json;
+use crate::parse_url;
+
+/// Taxonomy tag every webhook finding carries.
+pub const WEBHOOK_EXFIL_TAG: &str = "exfiltration.messaging_webhook";
+
+/// Chat bot / webhook APIs by service, as `host/path-prefix` (lowercase).
+pub(crate) const WEBHOOK_ENDPOINTS: &[(&str, &str)] = &[
+    ("telegram", "api.telegram.org/bot"),
+    ("discord",  "discord.com/api/webhooks/"),
+    ("discord",  "discordapp.com/api/webhooks/"),
+    ("discord",  "ptb.discord.com/api/webhooks/"),
+    ("discord",  "canary.discord.com/api/webhooks/"),
+    ("slack",    "hooks.slack.com/services/"),
+];
+
+/// Inline script text scanned per page or attachment.
+const MAX_SCRIPT_BYTES: usize = 1 << 20;
+
+/// Service whose webhook API `url` targets.
+pub(crate) fn webhook_service(url: &str) -> Option<&'static str> {
+    let p = parse_url(url.trim());
+    let host = p.host.to_lowercase();
+    let path = p.path.to_lowercase();
+    WEBHOOK_ENDPOINTS.iter().find(|(_, pat)| {
+        let (h, prefix) = pat.split_once('/').map_or((*pat, ""), |(h, _)| (h, &pat[h.len()..]));
+        (host == h || host.ends_with(&format!(".{}", h))) && path.starts_with(prefix)
+    }).map(|(s, _)| *s)
+}
+
+fn is_delim(b: u8) -> bool { matches!(b, b'"' | b'\'' | b'`' | b'(' | b')' | b',' | b';' | b'<' | b'>' | b'{' | b'}') || b.is_ascii_whitespace() }
+
+/// Webhook endpoints written into script text, as (service, endpoint). The
+/// endpoint runs to the end of the string literal, so concatenated bot
+/// tokens stay out of it.
+pub(crate) fn webhooks_in_script(script: &str) -> Vec<(&'static str, String)> {
+    let text = &script.as_bytes()[..script.len().min(MAX_SCRIPT_BYTES)];
+    let low = text.to_ascii_lowercase();
+    let mut out: Vec<(&'static str, String)> = Vec::new();
+    for (service, pat) in WEBHOOK_ENDPOINTS {
+        let mut from = 0;
+        while let Some(i) = find(&low[from..], pat.as_bytes()).map(|i| i + from) {
+            let start = (0..i).rev().find(|&j| is_delim(low[j])).map_or(0, |j| j + 1);
+            let token = &text[start..];
+            let end = token.iter().position(|&b| is_delim(b)).unwrap_or(token.len());
+            let endpoint = String::from_utf8_lossy(&token[..end]).into_owned();
+            if webhook_service(&endpoint_url(&endpoint)) == Some(service) && !out.iter().any(|(_, e)| *e == endpoint) {
+                out.push((service, endpoint));
+            }
+            from = i + pat.len();
+        }
+    }
+    out
+}
+
+fn find(hay: &[u8], needle: &[u8]) -> Option<usize> { hay.windows(needle.len()).position(|w| w == needle) }
+
+/// Scheme-less and protocol-relative endpoints parse as https.
+fn endpoint_url(e: &str) -> String {
+    if e.contains("://") { e.to_string() } else { format!("https://{}", e.trim_start_matches('/')) }
+}
+
+/// `{ tag, severity, source, service, endpoint }` for one hit; `source` is
+/// form_action, resource or inline_script.
+pub(crate) fn finding(source: &str, service: &str, endpoint: &str) -> Json {
+    Json::obj()
+        .with("tag", WEBHOOK_EXFIL_TAG)
+        .with("severity", "critical")
+        .with("source", source)
+        .with("service", service)
+        .with("endpoint", endpoint)
+}
+
+/// Findings for form actions and inline scripts together.
+pub(crate) fn scan<'a>(form_actions: impl IntoIterator<Item = &'a str>, scripts: impl IntoIterator<Item = &'a str>) -> Vec<Json> {
+    let mut out: Vec<Json> = form_actions.into_iter()
+        .filter_map(|a| webhook_service(a).map(|s| finding("form_action", s, a)))
+        .collect();
+    for s in scripts {
+        out.extend(webhooks_in_script(s).into_iter().map(|(svc, e)| finding("inline_script", svc, &e)));
+    }
+    out
+}
@@ -0,0 +1,147 @@
+// wasm-feature/crates/vigilant-core/src/expand.rs
+// Browser Vigilant — where a short link really goes. F45 knows that
+// `bit.ly` is a shortener and nothing about what it points to; lures chain
+// a shortener into a second one, or into a tracker redirect, and the page
+// at the end is the one that matters. The crate does no I/O, so expansion
+// is a small state machine: the host fetches the URL it is handed without
+// following redirects, reports the status and `Location`, and gets the
+// next URL to fetch until the chain ends. The report scores the final
+// destination and how the chain got there.
+
+use crate::analyzer::Analyzer;
+use crate::json::Json;
+use crate::verdict::Verdict;
+use crate::{combine, parse_url, ToJson, SHORT_SERVICES};
+
+/// Redirects followed before the expansion stops as truncated.
+pub const MAX_REDIRECT_HOPS: usize = 10;
+
+/// Values returned by [`ShortExpansion::features`]: chain_length,
+/// cross_domain_hops, shortener_hops, ended_early.
+pub const EXPANSION_FEATURES: usize = 4;
+
+/// Redirects from which a chain counts as long.
+const LONG_CHAIN: usize = 3;
+
+/// `location` resolved against the URL that answered with it.
+fn resolve(base: &str, location: &str) -> String {
+    let location = location.trim();
+    if location.contains("://") { return location.to_string(); }
+    let (scheme, rest) = base.split_once("://").unwrap_or(("https", base));
+    if let Some(net) = location.strip_prefix("//") { return format!("{scheme}://{net}"); }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    if location.starts_with('/') { return format!("{scheme}://{authority}{location}"); }
+    let path = rest[authority.len()..].split(['?', '#']).next().unwrap_or("");
+    let dir = path.rfind('/').map_or("/", |at| &path[..=at]);
+    format!("{scheme}://{authority}{dir}{location}")
+}
+
+fn is_shortener(url: &str) -> bool { SHORT_SERVICES.contains(&parse_url(url).reg_domain.as_str()) }
+
+/// One redirect chain being followed.
+#[derive(Clone, Debug)]
+pub struct ShortExpansion {
+    /// URLs visited, the starting one first.
+    chain: Vec<String>,
+    /// Status each answered with; `None` for the one awaiting a response.
+    statuses: Vec<Option<u16>>,
+    done: bool,
+    looped: bool,
+    truncated: bool,
+    error: Option<String>,
+}
+
+impl ShortExpansion {
+    /// A chain that starts at `url`.
+    pub fn new(url: &str) -> ShortExpansion {
+        ShortExpansion { chain: vec![url.trim().to_string()], statuses: vec![None], done: false, looped: false, truncated: false, error: None }
+    }
+
+    /// URL to fetch next (without following redirects); `None` once the
+    /// chain has ended.
+    pub fn next_url(&self) -> Option<&str> {
+        if self.done { None } else { self.chain.last().map(String::as_str) }
+    }
+
+    /// Record the response to [`next_url`](Self::next_url): a 3xx with a
+    /// `Location` moves on to it, anything else ends the chain there.
+    pub fn respond(&mut self, status: u16, location: Option<&str>) {
+        if self.done { return; }
+        let at = self.chain.len() - 1;
+        self.statuses[at] = Some(status);
+        let target = location.map(str::trim).filter(|l| (300..400).contains(&status) && !l.is_empty()).map(|l| resolve(&self.chain[at], l));
+        match target {
+            Some(t) if self.chain.contains(&t) => { self.looped = true; self.done = true; }
+            Some(_) if at >= MAX_REDIRECT_HOPS => { self.truncated = true; self.done = true; }
+            Some(t) => { self.chain.push(t); self.statuses.push(None); }
+            None => self.done = true,
+        }
+    }
+
+    /// End the chain on a fetch that failed; the report keeps what was
+    /// reached.
+    pub fn fail(&mut self, error: &str) {
+        if self.done { return; }
+        self.error = Some(error.to_string());
+        self.done = true;
+    }
+
+    pub fn is_done(&self) -> bool { self.done }
+
+    /// Last URL the chain reached.
+    pub fn final_url(&self) -> &str { self.chain.last().map_or("", String::as_str) }
+
+    fn cross_domain_hops(&self) -> usize {
+        self.chain.windows(2).filter(|w| parse_url(&w[0]).reg_domain != parse_url(&w[1]).reg_domain).count()
+    }
+
+    fn shortener_hops(&self) -> usize {
+        self.chain[..self.chain.len() - 1].iter().filter(|u| is_shortener(u)).count()
+    }
+
+    /// `[chain_length, cross_domain_hops, shortener_hops, ended_early]`:
+    /// redirects followed, those that changed registrable domain, those
+    /// that left a known shortener, and whether a loop, the hop limit or a
+    /// failed fetch ended the chain before a final page.
+    pub fn features(&self) -> [f32; EXPANSION_FEATURES] {
+        let early = self.looped || self.truncated || self.error.is_some();
+        [(self.chain.len() - 1) as f32, self.cross_domain_hops() as f32, self.shortener_hops() as f32, if early { 1.0 } else { 0.0 }]
+    }
+
+    /// The chain so far and its final destination under `analyzer`. Returns
+    /// `{ url, final_url, done, chain: [{ url, status, host, shortener }],
+    /// chain_length, cross_domain_hops, shortener_hops, looped, truncated,
+    /// error, features, final: UrlReport, flags, score, verdict }`; the
+    /// score folds the final URL's risk into the chain's own.
+    pub fn report(&self, analyzer: &Analyzer) -> Json {
+        let (hops, cross, shorteners) = (self.chain.len() - 1, self.cross_domain_hops(), self.shortener_hops());
+        let mut flags: Vec<&'static str> = Vec::new();
+        let mut risk = 0.0f32;
+        if hops >= LONG_CHAIN { flags.push("redirect_chain_long"); risk += 0.2; }
+        if shorteners >= 2 { flags.push("shortener_chain"); risk += 0.25; }
+        if self.looped { flags.push("redirect_loop"); risk += 0.15; }
+        let last = analyzer.url(self.final_url());
+        let score = combine(risk.min(1.0), last.risk);
+        let chain: Vec<Json> = self.chain.iter().zip(&self.statuses).map(|(u, s)| Json::obj()
+            .with("url", u.as_str())
+            .with("status", s.map(u32::from))
+            .with("host", parse_url(u).host.to_lowercase())
+            .with("shortener", is_shortener(u))).collect();
+        Json::obj()
+            .with("url", self.chain[0].as_str())
+            .with("final_url", self.final_url())
+            .with("done", self.done)
+            .with("chain", chain)
+            .with("chain_length", hops)
+            .with("cross_domain_hops", cross)
+            .with("shortener_hops", shorteners)
+            .with("looped", self.looped)
+            .with("truncated", self.truncated)
+            .with("error", self.error.as_deref())
+            .with("features", self.features().to_vec())
+            .with("final", last.to_json())
+            .with("flags", flags)
+            .with("score", score)
+            .with("verdict", Verdict::from_score(score).as_str())
+    }
+}
@@ -0,0 +1,55 @@
+// wasm-feature/crates/vigilant-core/src/extensions.rs
+// Browser Vigilant — what kind of file a URL's path names. A single
+// dangerous-extension bit puts a `.pdf` invoice lure and a plain page in the
+// same bucket; the class of the extension tells them apart, and the model
+// vector carries the whole group from F51 and F56 on. Server-side
+// script names (`/login.php`) are ordinary on a named web host but a kit
+// tell on a bare IP, an odd port or a throwaway TLD.
+
+use crate::{has_ip, parse_url, UrlParts, SUSPICIOUS_TLDS};
+
+/// Values returned by [`extension_features`]: ext_document, ext_media,
+/// ext_script, ext_executable, ext_archive, ext_none, server_script,
+/// server_script_odd_host.
+pub const EXTENSION_FEATURES: usize = 8;
+
+/// Extension classes in one-hot order; a path with no listed extension is
+/// the sixth, `none`.
+pub(crate) const EXTENSION_CLASSES: &[(&str, &[&str])] = &[
+    ("document",   &["pdf", "doc", "docx", "docm", "xls", "xlsx", "xlsm", "ppt", "pptx", "odt", "rtf", "txt", "csv"]),
+    ("media",      &["jpg", "jpeg", "png", "gif", "webp", "svg", "ico", "mp3", "mp4", "webm", "avi", "mov", "wav"]),
+    ("script",     &["js", "mjs", "vbs", "vbe", "wsf", "ps1", "bat", "cmd", "sh", "hta", "jse", "py"]),
+    ("executable", &["exe", "scr", "msi", "msp", "dll", "pif", "com", "cpl", "jar", "apk", "ipa", "dmg", "pkg", "deb", "rpm", "appimage"]),
+    ("archive",    &["zip", "rar", "7z", "tar", "gz", "tgz", "bz2", "xz", "iso", "img", "cab"]),
+];
+
+/// Extensions of pages rendered by a server-side script.
+pub(crate) const SERVER_SCRIPT_EXTS: &[&str] = &["php", "php5", "phtml", "asp", "aspx", "jsp", "cgi", "pl"];
+
+/// Default port for `scheme`, if it has one browsers navigate to.
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme { "http" => Some(80), "https" => Some(443), _ => None }
+}
+
+/// `[ext_document, ext_media, ext_script, ext_executable, ext_archive,
+/// ext_none, server_script, server_script_odd_host]`: the last path
+/// segment's extension class, one-hot; a server-side script extension
+/// (`.php`, `.asp`, …); and one served from an IP address, a non-default
+/// port or a suspicious TLD.
+pub fn extension_features(url: &str) -> [f32; EXTENSION_FEATURES] { extension_values(&parse_url(url)) }
+
+/// [`extension_features`] of an already parsed URL.
+pub(crate) fn extension_values(p: &UrlParts) -> [f32; EXTENSION_FEATURES] {
+    let path = p.path.to_lowercase();
+    let last = path.rsplit('/').next().unwrap_or("");
+    let ext = last.rsplit_once('.').map_or("", |(_, e)| e);
+    let mut f = [0.0f32; EXTENSION_FEATURES];
+    let class = EXTENSION_CLASSES.iter().position(|(_, exts)| exts.contains(&ext)).unwrap_or(EXTENSION_CLASSES.len());
+    f[class] = 1.0;
+    let server = SERVER_SCRIPT_EXTS.contains(&ext);
+    let odd_port = p.port.is_some_and(|port| Some(port) != default_port(&p.scheme));
+    let odd_host = has_ip(&p.host) || odd_port || SUSPICIOUS_TLDS.contains(&p.tld.as_str());
+    f[6] = if server { 1.0 } else { 0.0 };
+    f[7] = if server && odd_host { 1.0 } else { 0.0 };
+    f
+}
@@ -0,0 +1,85 @@
+// wasm-feature/crates/vigilant-core/src/fronting.rs
+// Browser Vigilant — domain fronting at the gateway. A fronted request sets
+// its TLS SNI to an innocuous name on a shared CDN and its Host header to
+// the site it really wants; the CDN routes on the Host header, so anything
+// that classifies by SNI waves it through. The extension never sees either
+// value, but a proxy integration sees both, next to the URL the client
+// asked for. Findings use the same taxonomy as the page analyzers, so a
+// gateway report ranks and renders like an extension one. A gateway that
+// terminates ECH should pass the inner SNI: the outer one names the
+// client-facing server by design.
+
+use crate::endpoint::site_of;
+use crate::json::Json;
+use crate::verdict::Verdict;
+use crate::{combine, has_ip, parse_url, url_risk};
+
+/// Taxonomy tag of a domain-fronting finding.
+pub const DOMAIN_FRONTING_TAG: &str = "evasion.domain_fronting";
+
+/// `h` (a host, `host:port`, `[v6]:port` or a URL) as a bare lowercase host.
+fn bare_host(h: &str) -> String {
+    let h = h.trim();
+    let h = if h.contains("://") { parse_url(h).host } else { h.to_string() };
+    let h = match h.strip_prefix('[').and_then(|r| r.split_once(']')) {
+        Some((v6, _)) => format!("[{v6}]"),
+        None => match h.rsplit_once(':') {
+            Some((name, port)) if port.bytes().all(|b| b.is_ascii_digit()) => name.to_string(),
+            _ => h,
+        },
+    };
+    h.trim_end_matches('.').to_lowercase()
+}
+
+/// Check that the host a client asked for (`url_host`), the TLS SNI and
+/// the HTTP Host header agree; empty values are treated as unseen. An SNI
+/// on another site than the Host header is fronting: the finding carries
+/// both names and the score folds in the risk of the real target. Returns
+/// `{ url_host, sni, host_header, target, flags, findings: [{ tag,
+/// severity, source, sni, host }], target_risk, score, verdict }`, where
+/// `target` is the Host header, or the URL host without one.
+pub fn check_host_consistency(url_host: &str, sni: &str, host_header: &str) -> Json {
+    let (url_host, sni, host_header) = (bare_host(url_host), bare_host(sni), bare_host(host_header));
+    let target = if host_header.is_empty() { url_host.clone() } else { host_header.clone() };
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut findings: Vec<Json> = Vec::new();
+    let mut risk = 0.0f32;
+
+    // RFC 6066 allows only host names in server_name
+    if has_ip(&sni) { flags.push("sni_ip_literal"); risk += 0.1; }
+    let fronted = !sni.is_empty() && !target.is_empty() && site_of(&sni) != site_of(&target);
+    if fronted {
+        flags.push("sni_host_mismatch");
+        findings.push(Json::obj()
+            .with("tag", DOMAIN_FRONTING_TAG)
+            .with("severity", "high")
+            .with("source", "gateway")
+            .with("sni", sni.as_str())
+            .with("host", target.as_str()));
+        risk += 0.6;
+    } else if !sni.is_empty() && !target.is_empty() && sni != target {
+        flags.push("sni_subdomain_mismatch");
+        risk += 0.1;
+    }
+    // a proxy-form request whose Host header names another site than its
+    // URL; a URL on the SNI's site is the fronting already reported
+    let url_site = site_of(&url_host);
+    let reported = fronted && url_site == site_of(&sni);
+    if !url_host.is_empty() && !host_header.is_empty() && url_site != site_of(&host_header) && !reported {
+        flags.push("url_host_mismatch");
+        risk += 0.35;
+    }
+
+    let target_risk = if target.is_empty() { 0.0 } else { url_risk(&format!("https://{target}/")) };
+    let score = if fronted { combine(risk.min(1.0), target_risk) } else { risk.min(1.0) };
+    Json::obj()
+        .with("url_host", url_host.as_str())
+        .with("sni", sni.as_str())
+        .with("host_header", host_header.as_str())
+        .with("target", target.as_str())
+        .with("flags", flags)
+        .with("findings", findings)
+        .with("target_risk", target_risk)
+        .with("score", score)
+        .with("verdict", Verdict::from_score(score).as_str())
+}
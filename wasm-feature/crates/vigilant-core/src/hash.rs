@@ -0,0 +1,134 @@
+// wasm-feature/crates/vigilant-core/src/hash.rs
+// Browser Vigilant — SHA-256 (FIPS 180-4) for identifiers that must not be kept raw
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H0: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+
+fn compress(h: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0u32; 64];
+    for (i, c) in block.chunks_exact(4).enumerate() { w[i] = u32::from_be_bytes([c[0], c[1], c[2], c[3]]); }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = *h;
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        hh = g; g = f; f = e; e = d.wrapping_add(t1);
+        d = c; c = b; b = a; a = t1.wrapping_add(t2);
+    }
+    for (x, y) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) { *x = x.wrapping_add(y); }
+}
+
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut h = H0;
+    let mut chunks = data.chunks_exact(64);
+    for block in &mut chunks { compress(&mut h, block); }
+    let rest = chunks.remainder();
+    let mut tail = [0u8; 128];
+    tail[..rest.len()].copy_from_slice(rest);
+    tail[rest.len()] = 0x80;
+    let len = if rest.len() < 56 { 64 } else { 128 };
+    tail[len - 8..len].copy_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    for block in tail[..len].chunks_exact(64) { compress(&mut h, block); }
+    let mut out = [0u8; 32];
+    for (o, v) in out.chunks_exact_mut(4).zip(h) { o.copy_from_slice(&v.to_be_bytes()); }
+    out
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    bytes.iter().flat_map(|b| [HEX[(b >> 4) as usize] as char, HEX[(b & 15) as usize] as char]).collect()
+}
+
+pub fn sha256_hex(data: &[u8]) -> String { to_hex(&sha256(data)) }
+
+/// How identifiers (URLs, hosts, VPAs) are keyed in anything that outlives a
+/// call. Hashed mode keeps only `sha256(salt ‖ id)`, so lookups still work
+/// but raw browsing data is never retained.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Privacy {
+    salt: Option<Vec<u8>>,
+}
+
+// The salt is the only secret protecting the hashes; keep it out of logs.
+impl std::fmt::Debug for Privacy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Privacy").field("hashed", &self.is_hashed()).finish()
+    }
+}
+
+impl Privacy {
+    pub fn plain() -> Privacy { Privacy { salt: None } }
+
+    pub fn hashed(salt: &[u8]) -> Privacy { Privacy { salt: Some(salt.to_vec()) } }
+
+    pub fn is_hashed(&self) -> bool { self.salt.is_some() }
+
+    /// Storage key for `id`: the identifier itself, or its salted hash.
+    pub fn key(&self, id: &str) -> String {
+        match &self.salt {
+            None => id.to_string(),
+            Some(salt) => {
+                let mut buf = Vec::with_capacity(salt.len() + id.len());
+                buf.extend_from_slice(salt);
+                buf.extend_from_slice(id.as_bytes());
+                sha256_hex(&buf)
+            }
+        }
+    }
+
+    /// Identifier as it may appear in exported reports: always a hash,
+    /// salted when a salt is configured.
+    pub fn digest(&self, id: &str) -> String {
+        if self.is_hashed() { self.key(id) } else { sha256_hex(id.as_bytes()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_matches_the_fips_vectors() {
+        assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        // 56 bytes: the length no longer fits the first padding block
+        assert_eq!(sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+        assert_eq!(sha256_hex(&[b'a'; 1000]), "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3");
+    }
+
+    #[test]
+    fn hashed_keys_are_salted() {
+        let plain = Privacy::plain();
+        assert_eq!(plain.key("example.com"), "example.com");
+        assert_eq!(plain.digest("example.com"), sha256_hex(b"example.com"));
+
+        let hashed = Privacy::hashed(b"salt");
+        assert_eq!(hashed.key("example.com"), "1853bf0469d93a17777d47df199584a87c7ae1c123528988e153829ccbd7121e");
+        assert_eq!(hashed.digest("example.com"), hashed.key("example.com"));
+        assert_ne!(Privacy::hashed(b"pepper").key("example.com"), hashed.key("example.com"));
+    }
+
+    #[test]
+    fn debug_output_hides_the_salt() {
+        assert_eq!(format!("{:?}", Privacy::hashed(b"secret-salt")), "Privacy { hashed: true }");
+    }
+}
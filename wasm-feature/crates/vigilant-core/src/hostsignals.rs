@@ -0,0 +1,61 @@
+// wasm-feature/crates/vigilant-core/src/hostsignals.rs
+// Browser Vigilant — signals only the host can fetch. Domain age is one of
+// the strongest tells (most phishing domains are days old when used) but
+// needs a WHOIS or RDAP lookup, and the module makes no network calls; the
+// same goes for a full popularity list or the certificate of the page.
+// The host looks them up and passes them in, and they extend the model
+// vector as a group of their own. Unknown values read as unknown, not as
+// new: every value has a channel saying whether it was supplied.
+
+use crate::compromised::popularity_rank;
+use crate::types::HostSignals;
+use crate::{has_ip, parse_url};
+
+/// Values returned by [`host_signal_features`]: domain_age_known,
+/// domain_age_log_days, young_domain, popularity, cert_age_known,
+/// cert_age_log_days, fresh_certificate.
+pub const HOST_SIGNAL_FEATURES: usize = 7;
+
+/// Domains registered fewer days ago than this count as young.
+const YOUNG_DOMAIN_DAYS: f64 = 30.0;
+
+/// Certificates issued fewer days ago than this count as fresh, as in the
+/// certificate report.
+const FRESH_CERT_DAYS: f64 = 2.0;
+
+/// Orders of magnitude of a list rank scaled to 0–1: rank 1 is 1, rank
+/// 1,000,000 and below is 0.
+const RANK_DECADES: f64 = 6.0;
+
+/// Domain a WHOIS or RDAP lookup for `url` is made on: its registrable
+/// domain, lowercased; "" for an IP host or a URL without a host.
+pub fn domain_age_key(url: &str) -> String {
+    let p = parse_url(url);
+    if p.host.is_empty() || has_ip(&p.host) { return String::new(); }
+    p.reg_domain
+}
+
+/// `[domain_age_known, domain_age_log_days, young_domain, popularity,
+/// cert_age_known, cert_age_log_days, fresh_certificate]` from what the
+/// host supplied for `url`: whether the domain's age is known, `ln(1 +
+/// days)` of it and whether it is under 30 days; popularity from the
+/// host's list rank (log-scaled, 1 for the top site) or, without one, the
+/// embedded list; and the same three for the certificate, fresh under two
+/// days. Unknown values are 0 with their `_known` channel 0.
+pub fn host_signal_features(url: &str, signals: &HostSignals) -> [f32; HOST_SIGNAL_FEATURES] {
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    let log_days = |d: Option<f64>| d.map_or(0.0, |d| d.ln_1p() as f32);
+    let popularity = match signals.rank {
+        Some(r) if r >= 1.0 => (1.0 - r.log10() / RANK_DECADES).clamp(0.0, 1.0) as f32,
+        _ => popularity_rank(&parse_url(url).host),
+    };
+    [
+        b(signals.domain_age_days.is_some()),
+        log_days(signals.domain_age_days),
+        b(signals.domain_age_days.is_some_and(|d| d < YOUNG_DOMAIN_DAYS)),
+        popularity,
+        b(signals.cert_age_days.is_some()),
+        log_days(signals.cert_age_days),
+        b(signals.cert_age_days.is_some_and(|d| d < FRESH_CERT_DAYS)),
+    ]
+}
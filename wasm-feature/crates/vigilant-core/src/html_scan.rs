@@ -0,0 +1,140 @@
+// wasm-feature/crates/vigilant-core/src/html_scan.rs
+// Browser Vigilant — SVG / HTML attachment payload scanner (HTML smuggling)
+
+use crate::exfil;
+use crate::json::Json;
+use crate::office::xml_elements;
+use crate::score::{combine, url_risk};
+use crate::verdict::Verdict;
+use crate::find_urls;
+
+/// Cap on how much of an attachment is inspected.
+const SCAN_LIMIT: usize = 8 << 20;
+/// An unbroken base64 run this long inside markup is a payload, not an icon.
+const B64_BLOB_MIN: usize = 2048;
+
+const EVENT_ATTRS: &[&str] = &["onload=","onerror=","onclick=","onmouseover=","onfocus=","onbegin=","onanimationstart=","onpageshow="];
+const SMUGGLE_APIS: &[&str] = &["atob(","new blob(","createobjecturl(","mssaveoropenblob(","uint8array(",".download=","fromcharcode("];
+
+fn count(hay: &str, needle: &str) -> usize { hay.matches(needle).count() }
+
+/// Longest run of base64 alphabet characters (ignoring line breaks).
+fn longest_b64_run(s: &str) -> usize {
+    let (mut best, mut run) = (0usize, 0usize);
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || b == b'+' || b == b'/' || b == b'=' { run += 1; }
+        else if b == b'\n' || b == b'\r' { continue; }
+        else { best = best.max(run); run = 0; }
+    }
+    best.max(run)
+}
+
+/// Target of `<meta http-equiv="refresh" content="0;url=…">`, if any.
+fn meta_refresh(html: &str) -> Option<String> {
+    xml_elements(html, "meta").into_iter().find_map(|attrs| {
+        let get = |k: &str| attrs.iter().find(|(ak, _)| ak.eq_ignore_ascii_case(k)).map(|(_, v)| v.clone());
+        if !get("http-equiv")?.eq_ignore_ascii_case("refresh") { return None; }
+        let content = get("content")?;
+        let low = content.to_lowercase();
+        let at = low.find("url=")?;
+        Some(content[at + 4..].trim().trim_matches(['\'', '"']).to_string())
+    })
+}
+
+pub fn looks_like_markup(bytes: &[u8]) -> Option<&'static str> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(2048)]).to_lowercase();
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    if head.contains("<svg") { return Some("svg"); }
+    if head.starts_with("<!doctype html") || head.contains("<html") || head.starts_with("<script")
+        || head.starts_with("<head") || head.starts_with("<body") { return Some("html"); }
+    None
+}
+
+/// Scan an `.svg` / `.html` attachment body. Returns `{ kind, scripts,
+/// event_handlers, meta_refresh, smuggling, auto_submit, password_fields,
+/// form_actions, links, findings, flags, score, verdict }`.
+pub fn html_attachment_report(bytes: &[u8]) -> Json {
+    let text = String::from_utf8_lossy(&bytes[..bytes.len().min(SCAN_LIMIT)]).into_owned();
+    let low = text.to_lowercase();
+    let kind = looks_like_markup(bytes).unwrap_or("text");
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+
+    let scripts = count(&low, "<script");
+    let handlers: usize = EVENT_ATTRS.iter().map(|a| count(&low, a)).sum();
+    let js_urls = count(&low, "javascript:");
+    if scripts > 0 { flags.push(if kind == "svg" { "svg_script" } else { "script" }); risk += if kind == "svg" { 0.4 } else { 0.1 }; }
+    if handlers > 0 { flags.push("event_handler"); risk += if kind == "svg" { 0.3 } else { 0.1 }; }
+    if js_urls > 0 { flags.push("javascript_url"); risk += 0.2; }
+    if kind == "svg" && (low.contains("<foreignobject") || low.contains("<iframe") || low.contains("<embed")) {
+        flags.push("svg_embedded_html");
+        risk += 0.3;
+    }
+
+    let refresh = meta_refresh(&low);
+    if refresh.is_some() { flags.push("meta_refresh"); risk += 0.3; }
+    if low.contains("location.href=") || low.contains("location.replace(") || low.contains("window.location=")
+        || low.contains("location.assign(") {
+        flags.push("script_redirect");
+        risk += 0.2;
+    }
+
+    // HTML smuggling: a big encoded blob plus the APIs that turn it into a file.
+    let squashed = low.replace(' ', "");
+    let apis: Vec<&str> = SMUGGLE_APIS.iter().copied().filter(|a| squashed.contains(a)).collect();
+    let blob = longest_b64_run(&text) >= B64_BLOB_MIN;
+    let data_payload = low.contains("data:application/");
+    let smuggling = (blob || data_payload) && apis.len() >= 2;
+    if smuggling { flags.push("html_smuggling"); risk += 0.7; }
+    else if blob { flags.push("large_base64_blob"); risk += 0.2; }
+    if low.contains("document.write(unescape(") || low.contains("eval(atob(") || low.contains("eval(unescape(") {
+        flags.push("obfuscated_script");
+        risk += 0.3;
+    }
+
+    // tag and attribute names are case-insensitive in HTML; scan the lowered copy
+    let forms = xml_elements(&low, "form");
+    let form_actions: Vec<String> = forms.iter()
+        .filter_map(|a| a.iter().find(|(k, _)| k.eq_ignore_ascii_case("action")).map(|(_, v)| v.clone()))
+        .collect();
+    let password_fields = count(&squashed, "type=\"password\"") + count(&squashed, "type='password'");
+    let auto_submit = !forms.is_empty() && (squashed.contains(".submit()") || squashed.contains("requestsubmit("));
+    if auto_submit { flags.push("auto_submit_form"); risk += 0.4; }
+    // A local file asking for a password can only be sending it somewhere else.
+    if password_fields > 0 {
+        flags.push("credential_form");
+        risk += 0.4;
+        if form_actions.iter().any(|a| a.starts_with("http")) { flags.push("credential_form_remote_action"); risk += 0.2; }
+    }
+
+    // scripts and handler attributes alike; a form action is reported once, as itself
+    let mut findings = exfil::scan(form_actions.iter().map(String::as_str), []);
+    findings.extend(exfil::scan([], [text.as_str()]).into_iter()
+        .filter(|f| !form_actions.iter().any(|a| a.eq_ignore_ascii_case(f.str_field("endpoint")))));
+    if !findings.is_empty() { flags.push("messaging_webhook_exfil"); risk = 1.0; }
+
+    // xmlns declarations aren't navigations
+    let mut links: Vec<String> = find_urls(&text).into_iter().filter(|u| !u.starts_with("http://www.w3.org/")).collect();
+    links.extend(refresh.iter().cloned());
+    links.extend(form_actions.iter().filter(|a| a.contains("://")).cloned());
+    links.sort();
+    links.dedup();
+    let worst = links.iter().map(|u| url_risk(u)).fold(0.0f32, f32::max);
+    let total = combine(risk.min(1.0), worst);
+
+    Json::obj()
+        .with("kind", kind)
+        .with("scripts", scripts)
+        .with("event_handlers", handlers)
+        .with("meta_refresh", refresh)
+        .with("smuggling", smuggling)
+        .with("smuggling_apis", apis)
+        .with("auto_submit", auto_submit)
+        .with("password_fields", password_fields)
+        .with("form_actions", form_actions)
+        .with("links", links)
+        .with("findings", findings)
+        .with("flags", flags)
+        .with("score", total)
+        .with("verdict", Verdict::from_score(total).as_str())
+}
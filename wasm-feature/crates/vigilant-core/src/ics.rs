@@ -0,0 +1,206 @@
+// wasm-feature/crates/vigilant-core/src/ics.rs
+// Browser Vigilant — calendar invite (iCalendar / RFC 5545) phishing scanner
+
+use crate::json::Json;
+use crate::psl::registrable_domain;
+use crate::score::{combine, url_risk};
+use crate::verdict::Verdict;
+use crate::typosquat::{misspells, SHORT_LABEL};
+use crate::{find_urls, parse_url, BRANDS};
+
+struct Prop {
+    name:   String,
+    params: Vec<(String, String)>,
+    value:  String,
+}
+
+impl Prop {
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Undo RFC 5545 line folding (CRLF followed by a space or tab).
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match raw.chars().next() {
+            Some(' ') | Some('\t') if !lines.is_empty() => {
+                if let Some(last) = lines.last_mut() { last.push_str(&raw[1..]); }
+            }
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape(v: &str) -> String {
+    v.replace("\\n", "\n").replace("\\N", "\n").replace("\\,", ",").replace("\\;", ";").replace("\\\\", "\\")
+}
+
+fn parse_line(line: &str) -> Option<Prop> {
+    // the first ':' outside a quoted parameter value separates name;params from value
+    let mut in_q = false;
+    let colon = line.char_indices().find(|&(_, c)| {
+        if c == '"' { in_q = !in_q; }
+        c == ':' && !in_q
+    })?.0;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_uppercase();
+    let params = parts.filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.trim().to_uppercase(), v.trim_matches('"').to_string())).collect();
+    Some(Prop { name, params, value: value.to_string() })
+}
+
+fn mailto_domain(v: &str) -> String {
+    let v = v.trim();
+    let addr = v.strip_prefix("mailto:").or_else(|| v.strip_prefix("MAILTO:")).unwrap_or(v);
+    addr.rsplit('@').next().unwrap_or("").to_lowercase()
+}
+
+/// Scan an ICS invite. Returns `{ events, organizer, organizer_domain, links,
+/// flags, score, verdict }`; each link carries its own `score`.
+pub fn ics_report(ics_text: &str) -> Json {
+    let props: Vec<Prop> = unfold(ics_text).iter().filter_map(|l| parse_line(l)).collect();
+    let events = props.iter().filter(|p| p.name == "BEGIN" && p.value.eq_ignore_ascii_case("VEVENT")).count();
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut score = 0.0f32;
+
+    let organizer = props.iter().find(|p| p.name == "ORGANIZER");
+    let org_domain = organizer.map(|o| mailto_domain(&o.value)).unwrap_or_default();
+    let org_reg = registrable_domain(&org_domain);
+    if let Some(org) = organizer {
+        let cn = org.param("CN").unwrap_or("").to_lowercase();
+        let cn_squashed: String = cn.chars().filter(|c| c.is_alphanumeric()).collect();
+        let claimed: Vec<&str> = BRANDS.iter().copied().filter(|b| b.len() >= 4 && cn_squashed.contains(b)).collect();
+        if claimed.iter().any(|b| !org_reg.contains(b)) {
+            flags.push("organizer_brand_mismatch");
+            score += 0.35;
+        }
+        // one slip off a short brand is often a word (case/chase, team/steam),
+        // so those only count when the CN names the brand as well
+        let label = org_reg.split('.').next().unwrap_or("");
+        let imitated = |b: &&str| b.len() >= 4 && label != *b && misspells(label, b)
+            && (b.len() >= SHORT_LABEL || claimed.contains(b));
+        if BRANDS.iter().any(imitated) {
+            flags.push("organizer_domain_lookalike");
+            score += 0.35;
+        }
+        if let Some(sent_by) = org.param("SENT-BY") {
+            if registrable_domain(&mailto_domain(sent_by)) != org_reg {
+                flags.push("organizer_sent_by_mismatch");
+                score += 0.2;
+            }
+        }
+        if cn.contains('@') && registrable_domain(cn.rsplit('@').next().unwrap_or("")) != org_reg {
+            flags.push("organizer_cn_address_spoof");
+            score += 0.3;
+        }
+    } else if events > 0 {
+        flags.push("no_organizer");
+    }
+
+    let mut urls: Vec<String> = Vec::new();
+    for p in &props {
+        let text = unescape(&p.value);
+        let found = match p.name.as_str() {
+            "URL" | "ATTACH" | "CONFERENCE" => vec![text.trim().to_string()],
+            "DESCRIPTION" | "LOCATION" | "SUMMARY" | "COMMENT" | "X-ALT-DESC" => find_urls(&text),
+            _ => continue,
+        };
+        for u in found {
+            if (u.contains("://") || u.starts_with("www.")) && !urls.contains(&u) { urls.push(u); }
+        }
+    }
+    if props.iter().any(|p| p.name == "ATTACH" && p.param("VALUE").is_some_and(|v| v.eq_ignore_ascii_case("BINARY"))) {
+        flags.push("inline_binary_attachment");
+        score += 0.2;
+    }
+
+    let links: Vec<Json> = urls.iter().map(|u| {
+        let reg = registrable_domain(&parse_url(u).host);
+        let mut s = url_risk(u);
+        // Links off the organizer's own domain are the norm for meetings, but not for brands.
+        if flags.contains(&"organizer_brand_mismatch") && reg != org_reg { s += 0.1; }
+        Json::obj().with("url", u.as_str()).with("score", s.min(1.0))
+    }).collect();
+    let max_link = links.iter().filter_map(|l| l.get("score").and_then(Json::as_f64)).fold(0.0, f64::max) as f32;
+    let total = combine(score, max_link);
+
+    Json::obj()
+        .with("events", events)
+        .with("organizer", organizer.map(|o| o.value.clone()))
+        .with("organizer_domain", org_domain)
+        .with("links", links)
+        .with("flags", flags)
+        .with("score", total)
+        .with("verdict", Verdict::from_score(total).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(r: &Json) -> Vec<&str> {
+        r.get("flags").and_then(Json::as_array).unwrap_or(&[]).iter().filter_map(Json::as_str).collect()
+    }
+
+    fn links(r: &Json) -> Vec<&str> {
+        r.get("links").and_then(Json::as_array).unwrap_or(&[]).iter().filter_map(|l| l.get("url").and_then(Json::as_str)).collect()
+    }
+
+    #[test]
+    fn an_unterminated_event_is_still_scanned() {
+        let r = ics_report("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:Verify at https://paypa1-login.tk/signin\r\n");
+        assert_eq!(r.get("events").and_then(Json::as_f64), Some(1.0));
+        assert_eq!(flags(&r), ["no_organizer"]);
+        assert_eq!(links(&r), ["https://paypa1-login.tk/signin"]);
+    }
+
+    #[test]
+    fn folds_without_a_line_to_continue_start_a_line() {
+        // a continuation before any line, and one that is only whitespace
+        let r = ics_report(" URL:https://a.example/x\r\nDESCRIPTION:see https://b.example/\r\n  \r\n\tpath\r\n");
+        assert_eq!(links(&r), ["https://a.example/x", "https://b.example/"]);
+        let lines = unfold(" X:1\n\tY\nZ:2\n ");
+        assert_eq!(lines, [" X:1Y", "Z:2"]);
+    }
+
+    #[test]
+    fn properties_without_a_value_are_skipped() {
+        assert!(parse_line("SUMMARY").is_none());
+        assert!(parse_line("ORGANIZER;CN=\"Pay:Pal\"").is_none());
+        assert!(parse_line("ORGANIZER;CN=\"unterminated:mailto:a@b.example").is_none());
+        let p = parse_line("SUMMARY:").unwrap();
+        assert_eq!((p.name.as_str(), p.value.as_str()), ("SUMMARY", ""));
+        let r = ics_report("BEGIN:VEVENT\nORGANIZER;CN=PayPal Support\nURL\nATTACH;VALUE=BINARY\nEND:VEVENT\n");
+        assert_eq!(r.get("organizer"), Some(&Json::Null));
+        assert_eq!(flags(&r), ["no_organizer"]);
+        assert_eq!(links(&r), Vec::<&str>::new());
+    }
+
+    fn organized_by(cn: &str, addr: &str) -> Json {
+        ics_report(&format!("BEGIN:VEVENT\r\nORGANIZER;CN={}:mailto:{}\r\nEND:VEVENT\r\n", cn, addr))
+    }
+
+    #[test]
+    fn ordinary_organizer_domains_near_short_brands_are_not_lookalikes() {
+        for (cn, addr) in [
+            ("Registrar", "registrar@case.edu"),
+            ("Standup", "pm@team.io"),
+            ("Council", "clerk@city.gov"),
+            ("Club", "games@chess.com"),
+        ] {
+            assert!(flags(&organized_by(cn, addr)).is_empty(), "{}", addr);
+        }
+    }
+
+    #[test]
+    fn organizer_domains_imitating_a_brand_are_lookalikes() {
+        // a long brand two edits away, and a short one the CN also names
+        assert_eq!(flags(&organized_by("Billing", "billing@faceb00k.com")), ["organizer_domain_lookalike"]);
+        assert_eq!(flags(&organized_by("Chase Bank", "alerts@chasse.com")), ["organizer_brand_mismatch", "organizer_domain_lookalike"]);
+    }
+}
@@ -0,0 +1,213 @@
+// wasm-feature/crates/vigilant-core/src/inflate.rs
+// Browser Vigilant — DEFLATE (RFC 1951) decoder for PDF streams and zip entries
+// Output is capped so a decompression bomb costs at most `limit` bytes.
+
+struct Bits<'a> {
+    data: &'a [u8],
+    pos:  usize,
+    bit:  u32,
+    nbit: u32,
+}
+
+impl Bits<'_> {
+    fn need(&mut self, n: u32) -> Result<(), &'static str> {
+        while self.nbit < n {
+            let b = *self.data.get(self.pos).ok_or("truncated deflate stream")?;
+            self.pos += 1;
+            self.bit |= (b as u32) << self.nbit;
+            self.nbit += 8;
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, n: u32) -> Result<u32, &'static str> {
+        if n == 0 { return Ok(0); }
+        self.need(n)?;
+        let v = self.bit & ((1u32 << n) - 1);
+        self.bit >>= n;
+        self.nbit -= n;
+        Ok(v)
+    }
+
+    fn align(&mut self) {
+        self.bit = 0;
+        self.nbit = 0;
+    }
+}
+
+/// Canonical Huffman table: symbol counts per length plus symbols in code order.
+struct Huffman {
+    counts:  [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for &l in lengths { counts[l as usize] += 1; }
+        counts[0] = 0;
+        let mut offs = [0u16; 16];
+        for i in 1..16 { offs[i] = offs[i - 1] + counts[i - 1]; }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (sym, &l) in lengths.iter().enumerate() {
+            if l != 0 {
+                symbols[offs[l as usize] as usize] = sym as u16;
+                offs[l as usize] += 1;
+            }
+        }
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<u16, &'static str> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= bits.take(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - count < first {
+                return self.symbols.get((index + code - first) as usize).copied().ok_or("bad huffman code");
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        Err("bad huffman code")
+    }
+}
+
+const LEN_BASE: [u16; 29] = [3,4,5,6,7,8,9,10,11,13,15,17,19,23,27,31,35,43,51,59,67,83,99,115,131,163,195,227,258];
+const LEN_EXTRA: [u8; 29] = [0,0,0,0,0,0,0,0,1,1,1,1,2,2,2,2,3,3,3,3,4,4,4,4,5,5,5,5,0];
+const DIST_BASE: [u16; 30] = [1,2,3,4,5,7,9,13,17,25,33,49,65,97,129,193,257,385,513,769,1025,1537,2049,3073,4097,6145,8193,12289,16385,24577];
+const DIST_EXTRA: [u8; 30] = [0,0,0,0,1,1,2,2,3,3,4,4,5,5,6,6,7,7,8,8,9,9,10,10,11,11,12,12,13,13];
+const CL_ORDER: [usize; 19] = [16,17,18,0,8,7,9,6,10,5,11,4,12,3,13,2,14,1,15];
+
+fn fixed_tables() -> (Huffman, Huffman) {
+    let mut l = [0u8; 288];
+    l[..144].fill(8);
+    l[144..256].fill(9);
+    l[256..280].fill(7);
+    l[280..].fill(8);
+    (Huffman::new(&l), Huffman::new(&[5u8; 30]))
+}
+
+fn dynamic_tables(bits: &mut Bits) -> Result<(Huffman, Huffman), &'static str> {
+    let hlit = bits.take(5)? as usize + 257;
+    let hdist = bits.take(5)? as usize + 1;
+    let hclen = bits.take(4)? as usize + 4;
+    let mut cl = [0u8; 19];
+    for &i in CL_ORDER.iter().take(hclen) { cl[i] = bits.take(3)? as u8; }
+    let clh = Huffman::new(&cl);
+    let mut lengths = vec![0u8; hlit + hdist];
+    let mut i = 0;
+    while i < hlit + hdist {
+        let sym = clh.decode(bits)?;
+        let (val, rep) = match sym {
+            0..=15 => (sym as u8, 1),
+            16 => (*lengths.get(i.wrapping_sub(1)).ok_or("repeat with no previous length")?, 3 + bits.take(2)? as usize),
+            17 => (0, 3 + bits.take(3)? as usize),
+            _  => (0, 11 + bits.take(7)? as usize),
+        };
+        if i + rep > lengths.len() { return Err("code lengths overflow"); }
+        lengths[i..i + rep].fill(val);
+        i += rep;
+    }
+    Ok((Huffman::new(&lengths[..hlit]), Huffman::new(&lengths[hlit..])))
+}
+
+/// Inflate a raw DEFLATE stream, stopping with what was decoded so far if the
+/// output would exceed `limit` bytes.
+pub fn inflate(data: &[u8], limit: usize) -> Result<Vec<u8>, &'static str> {
+    let mut bits = Bits { data, pos: 0, bit: 0, nbit: 0 };
+    let mut out: Vec<u8> = Vec::new();
+    loop {
+        let last = bits.take(1)? == 1;
+        match bits.take(2)? {
+            0 => {
+                bits.align();
+                let p = bits.pos;
+                let hdr = data.get(p..p + 4).ok_or("truncated stored block")?;
+                let len = u16::from_le_bytes([hdr[0], hdr[1]]) as usize;
+                let block = data.get(p + 4..p + 4 + len).ok_or("truncated stored block")?;
+                out.extend_from_slice(block);
+                bits.pos = p + 4 + len;
+            }
+            t @ (1 | 2) => {
+                let (lit, dist) = if t == 1 { fixed_tables() } else { dynamic_tables(&mut bits)? };
+                loop {
+                    let sym = lit.decode(&mut bits)? as usize;
+                    if sym < 256 {
+                        out.push(sym as u8);
+                    } else if sym == 256 {
+                        break;
+                    } else {
+                        let li = sym - 257;
+                        if li >= 29 { return Err("bad length code"); }
+                        let len = LEN_BASE[li] as usize + bits.take(LEN_EXTRA[li] as u32)? as usize;
+                        let di = dist.decode(&mut bits)? as usize;
+                        if di >= 30 { return Err("bad distance code"); }
+                        let d = DIST_BASE[di] as usize + bits.take(DIST_EXTRA[di] as u32)? as usize;
+                        if d > out.len() { return Err("distance past start"); }
+                        let start = out.len() - d;
+                        for k in 0..len { out.push(out[start + k]); }
+                    }
+                    if out.len() > limit { out.truncate(limit); return Ok(out); }
+                }
+            }
+            _ => return Err("reserved block type"),
+        }
+        if out.len() > limit { out.truncate(limit); return Ok(out); }
+        if last { return Ok(out); }
+    }
+}
+
+/// Inflate a zlib-wrapped stream (PDF /FlateDecode); the Adler-32 trailer is not checked.
+pub fn inflate_zlib(data: &[u8], limit: usize) -> Result<Vec<u8>, &'static str> {
+    if data.len() < 2 || !(((data[0] as u16) << 8) | data[1] as u16).is_multiple_of(31) || data[0] & 0x0f != 8 {
+        return inflate(data, limit); // some producers omit the header
+    }
+    inflate(&data[2..], limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `ab` × 3000 as zlib level 9 writes it: one dynamic-Huffman block.
+    const DYNAMIC: [u8; 25] = [237, 194, 49, 13, 0, 0, 0, 2, 160, 172, 218, 63, 132, 33, 124, 25, 164, 1, 0, 0, 0, 0, 0, 78, 3];
+
+    #[test]
+    fn stored_block_is_copied() {
+        assert_eq!(inflate(&[1, 5, 0, 0xfa, 0xff, b'h', b'e', b'l', b'l', b'o'], 100).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn fixed_huffman_block_with_back_references() {
+        let data = [203, 72, 205, 201, 201, 87, 200, 64, 144, 0];
+        assert_eq!(inflate(&data, 100).unwrap(), b"hello hello hello");
+    }
+
+    #[test]
+    fn dynamic_huffman_block() {
+        assert_eq!(inflate(&DYNAMIC, 10_000).unwrap(), b"ab".repeat(3000));
+    }
+
+    #[test]
+    fn output_stops_at_the_limit() {
+        assert_eq!(inflate(&DYNAMIC, 1000).unwrap(), b"ab".repeat(500));
+    }
+
+    #[test]
+    fn zlib_header_is_optional() {
+        let wrapped = [120, 156, 75, 76, 74, 6, 0, 2, 77, 1, 39];
+        assert_eq!(inflate_zlib(&wrapped, 100).unwrap(), b"abc");
+        assert_eq!(inflate_zlib(&wrapped[2..], 100).unwrap(), b"abc");
+    }
+
+    #[test]
+    fn malformed_streams_are_errors() {
+        assert_eq!(inflate(&[], 100), Err("truncated deflate stream"));
+        assert_eq!(inflate(&[1, 5, 0, 0xfa, 0xff, b'h'], 100), Err("truncated stored block"));
+        assert_eq!(inflate(&[0b111], 100), Err("reserved block type"));
+        assert!(inflate(&DYNAMIC[..12], 10_000).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+// wasm-feature/crates/vigilant-core/src/intent.rs
+// Browser Vigilant — Android `intent://` links. In a WebView such a link
+// hands off to an app, and when the app isn't installed Chrome loads
+// `S.browser_fallback_url` instead. Lures use that: the visible part names
+// `paypal.com` while the fallback goes elsewhere, or the package is a
+// look-alike app rather than the brand's own. Feeds the Android deployment.
+
+use crate::json::Json;
+use crate::psl::registrable_domain;
+use crate::scheme::scheme_of;
+use crate::verdict::Verdict;
+use crate::{parse_url, percent_decode, url_risk, BRANDS};
+
+/// Values returned by [`intent_features`]: has_fallback, fallback_off_host,
+/// fallback_unsafe_scheme, fallback_risk, package_brand_mismatch.
+pub const INTENT_FEATURES: usize = 5;
+
+/// Brands whose apps ship under a package not named after them: brand,
+/// then the package prefixes it publishes under.
+pub(crate) const APP_PACKAGES: &[(&str, &[&str])] = &[
+    ("youtube", &["com.google.android.youtube"]),
+    ("gpay",    &["com.google.android.apps.nbu.paisa"]),
+    ("paytm",   &["net.one97.paytm"]),
+    ("bhim",    &["in.org.npci.upiapp"]),
+    ("hdfc",    &["com.snapwork.hdfc"]),
+    ("icici",   &["com.csam.icici"]),
+    ("chase",   &["com.chase"]),
+    ("citi",    &["com.citi"]),
+];
+
+/// Fallback risk from which the fallback itself counts as risky.
+const RISKY_FALLBACK: f32 = 0.5;
+
+/// An `intent:` URI: `intent://TARGET#Intent;key=value;…;end`.
+struct Intent {
+    /// Host and path after `intent://`, as a web URL would carry them.
+    target:    String,
+    scheme:    Option<String>,
+    package:   Option<String>,
+    action:    Option<String>,
+    component: Option<String>,
+    fallback:  Option<String>,
+    /// Typed extras (`S.`, `i.`, `B.` …) other than the fallback URL.
+    extras:    Vec<(String, String)>,
+}
+
+fn parse_intent(uri: &str) -> Option<Intent> {
+    let uri = uri.trim();
+    if scheme_of(uri) != "intent" { return None; }
+    let rest = &uri["intent:".len()..];
+    let (target, params) = rest.split_once("#Intent;").unwrap_or((rest, ""));
+    let mut intent = Intent {
+        target: target.trim_start_matches("//").to_string(),
+        scheme: None, package: None, action: None, component: None, fallback: None, extras: Vec::new(),
+    };
+    for kv in params.split(';').take_while(|kv| *kv != "end") {
+        // an empty value sets nothing: `S.browser_fallback_url=` is no fallback
+        let Some((k, v)) = kv.split_once('=').filter(|(_, v)| !v.is_empty()) else { continue };
+        let v = percent_decode(v, false);
+        match k {
+            "scheme" => intent.scheme = Some(v.to_lowercase()),
+            "package" => intent.package = Some(v.to_lowercase()),
+            "action" => intent.action = Some(v),
+            "component" => intent.component = Some(v),
+            "S.browser_fallback_url" => intent.fallback = Some(v),
+            _ if k.len() > 2 && k.as_bytes()[1] == b'.' => intent.extras.push((k[2..].to_string(), v)),
+            _ => {}
+        }
+    }
+    Some(intent)
+}
+
+/// Brand the package or target names that the package isn't published by.
+fn package_mismatch(intent: &Intent) -> Option<&'static str> {
+    let target = parse_url(&format!("https://{}", intent.target)).host.to_lowercase();
+    package_brand_mismatch(intent.package.as_deref()?, &target)
+}
+
+/// Brand an app package (or the host whose link it opens) names when the
+/// package isn't one the brand publishes.
+pub(crate) fn package_brand_mismatch(package: &str, target_host: &str) -> Option<&'static str> {
+    let owner = package.split('.').nth(1).unwrap_or("");
+    let target_label = registrable_domain(target_host).split('.').next().unwrap_or("").to_string();
+    let named = |b: &str| {
+        let in_package = package.split('.').any(|s| if b.len() >= 4 { s.contains(b) } else { s == b });
+        in_package || target_label == b
+    };
+    BRANDS.iter().copied().filter(|b| named(b)).find(|b| match APP_PACKAGES.iter().find(|(a, _)| a == b) {
+        Some((_, prefixes)) => !prefixes.iter().any(|p| package.starts_with(p)),
+        None => owner != *b,
+    })
+}
+
+/// What the intent's fallback does: its host, whether that's another site
+/// than the target's, whether it isn't a web page at all, and its own risk.
+fn fallback_checks(intent: &Intent) -> (String, bool, bool, f32) {
+    let Some(fallback) = intent.fallback.as_deref() else { return (String::new(), false, false, 0.0) };
+    if !matches!(scheme_of(fallback).as_str(), "http" | "https") { return (String::new(), false, true, url_risk(fallback)); }
+    let host = parse_url(fallback).host.to_lowercase();
+    let target = parse_url(&format!("https://{}", intent.target)).host.to_lowercase();
+    let off_host = !target.is_empty() && !host.is_empty() && registrable_domain(&host) != registrable_domain(&target);
+    (host, off_host, false, url_risk(fallback))
+}
+
+/// `[has_fallback, fallback_off_host, fallback_unsafe_scheme, fallback_risk,
+/// package_brand_mismatch]` for an `intent:` link, all 0 for anything else:
+/// a browser fallback URL, one on another site than the link's target, one
+/// that isn't http(s) (`javascript:`, `data:`, another intent), the
+/// fallback's own URL risk, and a package that names a brand (or opens a
+/// brand's link) without being published by it.
+pub fn intent_features(uri: &str) -> [f32; INTENT_FEATURES] {
+    let Some(intent) = parse_intent(uri) else { return [0.0; INTENT_FEATURES] };
+    let (_, off_host, unsafe_scheme, risk) = fallback_checks(&intent);
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    [b(intent.fallback.is_some()), b(off_host), b(unsafe_scheme), risk, b(package_mismatch(&intent).is_some())]
+}
+
+/// Analyze an Android `intent:` link. Returns `{ intent, target, scheme,
+/// package, action, component, fallback_url, fallback_host, brand, extras:
+/// [{ key, value }], flags, score, verdict }`; `intent` is false (and the
+/// rest empty) for any other URL.
+pub fn intent_report(uri: &str) -> Json {
+    let Some(intent) = parse_intent(uri) else {
+        return Json::obj().with("intent", false).with("flags", Vec::<&str>::new()).with("score", 0.0f32).with("verdict", Verdict::from_score(0.0).as_str());
+    };
+    let (fallback_host, off_host, unsafe_scheme, fallback_risk) = fallback_checks(&intent);
+    let brand = package_mismatch(&intent);
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+    if intent.fallback.is_some() {
+        if unsafe_scheme { flags.push("intent_fallback_script"); risk += 0.6; }
+        if off_host { flags.push("intent_fallback_off_host"); risk += 0.35; }
+        if fallback_risk >= RISKY_FALLBACK { flags.push("intent_fallback_risky"); risk += 0.5 * fallback_risk; }
+    }
+    if brand.is_some() { flags.push("intent_package_mismatch"); risk += 0.5; }
+    let extras: Vec<Json> = intent.extras.iter().map(|(k, v)| Json::obj().with("key", k.as_str()).with("value", v.as_str())).collect();
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("intent", true)
+        .with("target", intent.target.as_str())
+        .with("scheme", intent.scheme)
+        .with("package", intent.package)
+        .with("action", intent.action)
+        .with("component", intent.component)
+        .with("fallback_url", intent.fallback)
+        .with("fallback_host", fallback_host)
+        .with("brand", brand)
+        .with("extras", extras)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameters_after_a_missing_end_are_still_read() {
+        let i = parse_intent("intent://scan/#Intent;scheme=zxing;package=com.paypal.fake;S.browser_fallback_url=https%3A%2F%2Fevil.tk%2F").unwrap();
+        assert_eq!(i.scheme.as_deref(), Some("zxing"));
+        assert_eq!(i.package.as_deref(), Some("com.paypal.fake"));
+        assert_eq!(i.fallback.as_deref(), Some("https://evil.tk/"));
+        // and nothing after `end` is
+        let i = parse_intent("intent://scan/#Intent;scheme=zxing;end;package=com.x.y").unwrap();
+        assert_eq!(i.package, None);
+        // no `#Intent;` at all is a target without parameters
+        let i = parse_intent("intent://scan/;package=com.x.y").unwrap();
+        assert_eq!((i.target.as_str(), i.package), ("scan/;package=com.x.y", None));
+    }
+
+    #[test]
+    fn extras_without_a_value_are_skipped() {
+        let i = parse_intent("intent://x#Intent;S.token;S.=v;i.count=3;S.name=a%20b;B.flag=;end").unwrap();
+        assert_eq!(i.extras, [("count".to_string(), "3".to_string()), ("name".to_string(), "a b".to_string())]);
+        let i = parse_intent("intent://x#Intent;S.browser_fallback_url=;package=;end").unwrap();
+        assert_eq!((i.fallback, i.package), (None, None));
+        assert_eq!(intent_features("intent://x#Intent;S.browser_fallback_url=;end"), [0.0; INTENT_FEATURES]);
+    }
+
+    #[test]
+    fn an_empty_scheme_is_absent() {
+        let i = parse_intent("intent://x#Intent;scheme=;end").unwrap();
+        assert_eq!(i.scheme, None);
+        assert!(parse_intent("://x#Intent;scheme=https;end").is_none());
+        assert!(parse_intent("#Intent;scheme=https;end").is_none());
+        let r = intent_report(":#Intent;S.browser_fallback_url=javascript:alert(1);end");
+        assert_eq!(r.get("intent").and_then(Json::as_bool), Some(false));
+        let r = intent_report("INTENT:#Intent;end");
+        assert_eq!(r.get("intent").and_then(Json::as_bool), Some(true));
+        assert_eq!(r.get("target").and_then(Json::as_str), Some(""));
+    }
+}
@@ -0,0 +1,290 @@
+// wasm-feature/crates/vigilant-core/src/json.rs
+// Browser Vigilant — minimal JSON value, parser and serializer
+// Reports are built as `Json` trees and handed to JS via JSON.parse, so the
+// crate stays on wasm-bindgen alone.
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn obj() -> Json { Json::Obj(Vec::new()) }
+
+    /// Builder-style insert for objects; no-op on other variants.
+    pub fn with(mut self, key: &str, val: impl Into<Json>) -> Json {
+        if let Json::Obj(ref mut fields) = self { fields.push((key.to_string(), val.into())); }
+        self
+    }
+
+    /// Like `with`, but replaces an existing field of the same name in place.
+    pub fn set(mut self, key: &str, val: impl Into<Json>) -> Json {
+        if let Json::Obj(ref mut fields) = self {
+            let val = val.into();
+            match fields.iter_mut().find(|(k, _)| k == key) {
+                Some(slot) => slot.1 = val,
+                None => fields.push((key.to_string(), val)),
+            }
+        }
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Obj(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        if let Json::Str(s) = self { Some(s) } else { None }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        if let Json::Num(n) = self { Some(*n) } else { None }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        if let Json::Bool(b) = self { Some(*b) } else { None }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        if let Json::Arr(a) = self { Some(a) } else { None }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Json)]> {
+        if let Json::Obj(o) = self { Some(o) } else { None }
+    }
+
+    /// String field lookup with empty-string default.
+    pub fn str_field(&self, key: &str) -> &str {
+        self.get(key).and_then(Json::as_str).unwrap_or("")
+    }
+
+    /// Array of strings; non-string entries are skipped.
+    pub fn str_list(&self, key: &str) -> Vec<String> {
+        self.get(key).and_then(Json::as_array).unwrap_or(&[])
+            .iter().filter_map(|v| v.as_str().map(str::to_string)).collect()
+    }
+
+    pub fn parse(src: &str) -> Result<Json, String> {
+        let mut p = Parser { b: src.as_bytes(), i: 0 };
+        let v = p.value()?;
+        p.ws();
+        if p.i != p.b.len() { return Err(format!("trailing data at byte {}", p.i)); }
+        Ok(v)
+    }
+}
+
+impl From<bool> for Json { fn from(v: bool) -> Json { Json::Bool(v) } }
+impl From<f64>  for Json { fn from(v: f64)  -> Json { Json::Num(v) } }
+impl From<f32>  for Json {
+    // go through the shortest f32 repr so 0.8f32 serializes as 0.8
+    fn from(v: f32) -> Json { Json::Num(v.to_string().parse().unwrap_or(v as f64)) }
+}
+impl From<u32>  for Json { fn from(v: u32)  -> Json { Json::Num(v as f64) } }
+impl From<i64>  for Json { fn from(v: i64)  -> Json { Json::Num(v as f64) } }
+impl From<u64>  for Json { fn from(v: u64)  -> Json { Json::Num(v as f64) } }
+impl From<usize> for Json { fn from(v: usize) -> Json { Json::Num(v as f64) } }
+impl From<&str> for Json { fn from(v: &str) -> Json { Json::Str(v.to_string()) } }
+impl From<String> for Json { fn from(v: String) -> Json { Json::Str(v) } }
+impl From<&String> for Json { fn from(v: &String) -> Json { Json::Str(v.clone()) } }
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(v: Vec<T>) -> Json { Json::Arr(v.into_iter().map(Into::into).collect()) }
+}
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(v: Option<T>) -> Json { v.map(Into::into).unwrap_or(Json::Null) }
+}
+
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"'  => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Num(n) if !n.is_finite() => f.write_str("null"),
+            Json::Num(n) if n.fract() == 0.0 && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+            Json::Num(n) => write!(f, "{}", n),
+            Json::Str(s) => write_str(f, s),
+            Json::Arr(a) => {
+                f.write_str("[")?;
+                for (i, v) in a.iter().enumerate() {
+                    if i > 0 { f.write_str(",")?; }
+                    write!(f, "{}", v)?;
+                }
+                f.write_str("]")
+            }
+            Json::Obj(fields) => {
+                f.write_str("{")?;
+                for (i, (k, v)) in fields.iter().enumerate() {
+                    if i > 0 { f.write_str(",")?; }
+                    write_str(f, k)?;
+                    write!(f, ":{}", v)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+// ── Parser ────────────────────────────────────────────────────────────────────
+
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    b: &'a [u8],
+    i: usize,
+}
+
+impl Parser<'_> {
+    fn ws(&mut self) {
+        while self.i < self.b.len() && matches!(self.b[self.i], b' ' | b'\t' | b'\n' | b'\r') {
+            self.i += 1;
+        }
+    }
+
+    fn err<T>(&self, what: &str) -> Result<T, String> {
+        Err(format!("{} at byte {}", what, self.i))
+    }
+
+    fn lit(&mut self, word: &str, v: Json) -> Result<Json, String> {
+        if self.b[self.i..].starts_with(word.as_bytes()) {
+            self.i += word.len();
+            Ok(v)
+        } else { self.err("invalid literal") }
+    }
+
+    fn value(&mut self) -> Result<Json, String> { self.value_at(0) }
+
+    fn value_at(&mut self, depth: usize) -> Result<Json, String> {
+        if depth > MAX_DEPTH { return self.err("nesting too deep"); }
+        self.ws();
+        match self.b.get(self.i) {
+            None => self.err("unexpected end"),
+            Some(b'n') => self.lit("null", Json::Null),
+            Some(b't') => self.lit("true", Json::Bool(true)),
+            Some(b'f') => self.lit("false", Json::Bool(false)),
+            Some(b'"') => self.string().map(Json::Str),
+            Some(b'[') => {
+                self.i += 1;
+                let mut out = Vec::new();
+                self.ws();
+                if self.b.get(self.i) == Some(&b']') { self.i += 1; return Ok(Json::Arr(out)); }
+                loop {
+                    out.push(self.value_at(depth + 1)?);
+                    self.ws();
+                    match self.b.get(self.i) {
+                        Some(b',') => self.i += 1,
+                        Some(b']') => { self.i += 1; return Ok(Json::Arr(out)); }
+                        _ => return self.err("expected ',' or ']'"),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.i += 1;
+                let mut out = Vec::new();
+                self.ws();
+                if self.b.get(self.i) == Some(&b'}') { self.i += 1; return Ok(Json::Obj(out)); }
+                loop {
+                    self.ws();
+                    if self.b.get(self.i) != Some(&b'"') { return self.err("expected key"); }
+                    let k = self.string()?;
+                    self.ws();
+                    if self.b.get(self.i) != Some(&b':') { return self.err("expected ':'"); }
+                    self.i += 1;
+                    let v = self.value_at(depth + 1)?;
+                    out.push((k, v));
+                    self.ws();
+                    match self.b.get(self.i) {
+                        Some(b',') => self.i += 1,
+                        Some(b'}') => { self.i += 1; return Ok(Json::Obj(out)); }
+                        _ => return self.err("expected ',' or '}'"),
+                    }
+                }
+            }
+            Some(_) => self.number(),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let h = self.b.get(self.i..self.i + 4).and_then(|h| std::str::from_utf8(h).ok());
+        match h.and_then(|h| u32::from_str_radix(h, 16).ok()) {
+            Some(v) => { self.i += 4; Ok(v) }
+            None => self.err("bad \\u escape"),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.i += 1; // opening quote
+        let mut out = String::new();
+        loop {
+            let start = self.i;
+            while self.i < self.b.len() && self.b[self.i] != b'"' && self.b[self.i] != b'\\' {
+                self.i += 1;
+            }
+            // input is &str and we only split at ASCII bytes, so this is valid UTF-8
+            out.push_str(std::str::from_utf8(&self.b[start..self.i]).map_err(|e| e.to_string())?);
+            match self.b.get(self.i) {
+                None => return self.err("unterminated string"),
+                Some(b'"') => { self.i += 1; return Ok(out); }
+                _ => {}
+            }
+            self.i += 1;
+            let esc = match self.b.get(self.i) { Some(&c) => c, None => return self.err("bad escape") };
+            self.i += 1;
+            match esc {
+                b'"' => out.push('"'),
+                b'\\' => out.push('\\'),
+                b'/' => out.push('/'),
+                b'b' => out.push('\u{8}'),
+                b'f' => out.push('\u{c}'),
+                b'n' => out.push('\n'),
+                b'r' => out.push('\r'),
+                b't' => out.push('\t'),
+                b'u' => {
+                    let mut cp = self.hex4()?;
+                    if (0xD800..0xDC00).contains(&cp) && self.b[self.i..].starts_with(b"\\u") {
+                        self.i += 2;
+                        let lo = self.hex4()?;
+                        cp = 0x10000 + ((cp - 0xD800) << 10) + (lo.wrapping_sub(0xDC00) & 0x3FF);
+                    }
+                    out.push(char::from_u32(cp).unwrap_or('\u{FFFD}'));
+                }
+                _ => return self.err("bad escape"),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.i;
+        while self.i < self.b.len() && matches!(self.b[self.i], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') {
+            self.i += 1;
+        }
+        std::str::from_utf8(&self.b[start..self.i]).ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(Json::Num)
+            .ok_or_else(|| format!("invalid value at byte {}", start))
+    }
+}
@@ -0,0 +1,69 @@
+// wasm-feature/crates/vigilant-core/src/keywords.rs
+// Browser Vigilant — keyword co-occurrence and proximity. A real bank's URL
+// carries one keyword where it needs it (`/login`); lure URLs stack them,
+// often in one label (`secure-login-verify-bank.example`), and pair payment
+// words with giveaway words. The presence bits of the model vector can't
+// tell the two apart; these counts and distances can.
+
+use crate::{parse_url, BRANDS, FRAUD_KW, FREE_KW, LOGIN_KW, PAY_KW, TRUST_KW};
+
+/// Values returned by [`keyword_features`]: label_keyword_max,
+/// pay_free_in_host, login_trust_in_host, keyword_proximity, keyword_density.
+pub const KEYWORD_FEATURES: usize = 5;
+
+/// Distinct keywords in one host label from which the label counts as stuffed.
+const STUFFED_LABEL: f32 = 3.0;
+
+/// Keywords in `text`, once each and without those only found inside a
+/// longer hit (`pay` in `payment`). Brand names are cut out first, so
+/// `bankofamerica` doesn't read as the trust word `bank`.
+fn keywords_in(text: &str) -> Vec<&'static str> {
+    let mut t = text.to_string();
+    for b in BRANDS.iter().filter(|b| b.len() >= 4) { t = t.replace(b, " "); }
+    let mut hits: Vec<&'static str> = [LOGIN_KW, TRUST_KW, PAY_KW, FREE_KW, FRAUD_KW].concat().into_iter()
+        .filter(|k| t.contains(k))
+        .collect();
+    hits.sort();
+    hits.dedup();
+    let all = hits.clone();
+    hits.retain(|k| !all.iter().any(|o| o != k && o.contains(k)));
+    hits
+}
+
+fn group_in(host_hits: &[&str], group: &[&str]) -> bool { host_hits.iter().any(|k| group.contains(k)) }
+
+/// `[label_keyword_max, pay_free_in_host, login_trust_in_host,
+/// keyword_proximity, keyword_density]`: the most distinct keywords in any
+/// one host label; payment and giveaway words together in the host; login
+/// and trust words together in the host; 1 / (1 + token distance between
+/// the two closest keyword hits), 1 when one token holds two and 0 with
+/// fewer than two; and the share of host and path tokens that hold a keyword.
+pub fn keyword_features(url: &str) -> [f32; KEYWORD_FEATURES] {
+    let p = parse_url(url);
+    let host = p.host.to_lowercase();
+    let path = p.path.to_lowercase();
+    let label_max = host.split('.').map(|l| keywords_in(l).len()).max().unwrap_or(0);
+    let host_hits = keywords_in(&host);
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+
+    let text = format!("{}/{}", host, path);
+    let tokens: Vec<&str> = text.split(|c: char| !c.is_ascii_alphanumeric()).filter(|t| !t.is_empty()).collect();
+    let hits: Vec<(usize, usize)> = tokens.iter().enumerate().map(|(i, t)| (i, keywords_in(t).len())).filter(|&(_, n)| n > 0).collect();
+    let proximity = if hits.iter().any(|&(_, n)| n >= 2) {
+        1.0
+    } else {
+        hits.windows(2).map(|w| w[1].0 - w[0].0).min().map_or(0.0, |d| 1.0 / (1.0 + d as f32))
+    };
+    let density = hits.len() as f32 / tokens.len().max(1) as f32;
+    [
+        label_max as f32,
+        b(group_in(&host_hits, PAY_KW) && group_in(&host_hits, FREE_KW)),
+        b(group_in(&host_hits, LOGIN_KW) && group_in(&host_hits, TRUST_KW)),
+        proximity,
+        density,
+    ]
+}
+
+/// Keyword stuffing by [`keyword_features`]: a label stacking keywords, or
+/// a host pairing payment with giveaway words.
+pub(crate) fn stuffed(f: &[f32; KEYWORD_FEATURES]) -> bool { f[0] >= STUFFED_LABEL || f[1] > 0.0 }
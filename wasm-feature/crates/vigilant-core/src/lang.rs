@@ -0,0 +1,102 @@
+// wasm-feature/crates/vigilant-core/src/lang.rs
+// Browser Vigilant — compact language identifier for page text
+// Script ranges settle non-Latin text outright; Latin-script languages are
+// told apart by hits against short profiles of their most frequent trigrams.
+
+/// Characters looked at; a few sentences are plenty to call the language.
+const MAX_SAMPLE_CHARS: usize = 2000;
+
+/// Trigram hits a profile needs before it's trusted at all.
+const MIN_HITS: usize = 3;
+
+/// Most frequent trigrams per language, word boundaries padded with spaces.
+pub(crate) const PROFILES: &[(&str, &[&str])] = &[
+    ("en", &[" th", "the", "he ", "ing", "ng ", " an", "and", "nd ", " of", "of ", " to", "to ", "ion", "tio", " in",
+             "ed ", "er ", "is ", "at ", " yo", "you", "ou ", "our", " wi", "ith", "wit", "ase", "ill"]),
+    ("es", &[" de", "de ", "os ", " la", "la ", " qu", "que", "ue ", " el", "el ", " en", "ión", "ció", "as ", "ado",
+             " co", "con", " su", "su ", " pa", "par", " lo", " es", "est", "ta ", "una", "ía ", "sus"]),
+    ("fr", &[" de", "de ", "es ", " le", "le ", " la", " et", "et ", "les", " pa", "nt ", " qu", "que", "ue ", "re ",
+             " vo", "vou", "ous", "us ", "ée ", " un", "une", "ez ", "tre", "ait", " po", "pou", "eur", "ire"]),
+    ("de", &["en ", "er ", " de", "der", "ie ", "ich", " di", "die", "ein", "sch", " un", "und", "nd ", "che", "ch ",
+             "den", " ei", " zu", "ung", "ng ", " si", "sie", " ih", "ihr", "gen", "ist", " is", "cht", "nen"]),
+    ("pt", &[" de", "de ", "os ", " qu", "que", "ue ", "ão ", "ção", "ões", " co", "do ", " da", "da ", " e ", "as ",
+             " pa", "par", " se", "nte", " vo", "voc", "ocê", "em ", " um", "uma", "ma ", "sua", " su", "ar "]),
+    ("it", &[" di", "di ", "la ", " la", "che", " ch", "to ", "re ", " co", "one", " il", "il ", "per", " pe", "ell",
+             "del", " de", "no ", "are", "zio", " un", "lla", "ti ", "gli", " ac", "ul ", "sta", "tà ", "ere"]),
+    ("nl", &[" de", "de ", "en ", " he", "het", "et ", " va", "van", "an ", " ee", "een", "ij ", "aar", "oor", " ge",
+             "cht", "ver", " zi", "uw ", " uw", "ijn", " vo", "voo", " we", "wor", "ord", "rd ", "gen", " te"]),
+];
+
+/// Lure phrases by language, matched against lowercased page text.
+pub(crate) const LURE_WORDS: &[(&str, &[&str])] = &[
+    ("en", &["verify your", "account", "password", "suspended", "urgent", "confirm your", "sign in", "unusual activity",
+             "locked", "update your", "security alert", "kyc", "refund", "prize", "winner"]),
+    ("es", &["verifique", "verificar", "cuenta", "contraseña", "suspendida", "urgente", "confirme", "iniciar sesión",
+             "actividad inusual", "bloqueada", "actualice", "alerta de seguridad", "reembolso", "premio", "ganador"]),
+    ("fr", &["vérifiez", "vérifier", "compte", "mot de passe", "suspendu", "urgent", "confirmez", "connexion",
+             "activité inhabituelle", "bloqué", "mettre à jour", "alerte de sécurité", "remboursement", "gagnant"]),
+    ("de", &["bestätigen", "überprüfen", "konto", "passwort", "kennwort", "gesperrt", "dringend", "anmelden",
+             "ungewöhnliche aktivität", "aktualisieren", "sicherheitswarnung", "rückerstattung", "gewinner", "gewinnspiel"]),
+    ("pt", &["verifique", "confirmar", "conta", "senha", "suspensa", "urgente", "entrar", "atividade incomum",
+             "bloqueada", "atualize", "alerta de segurança", "reembolso", "prêmio", "ganhador"]),
+    ("it", &["verifica", "conferma", "account", "password", "sospeso", "urgente", "accedi", "attività insolita",
+             "bloccato", "aggiorna", "avviso di sicurezza", "rimborso", "vincitore", "premio"]),
+    ("nl", &["verifieer", "bevestig", "rekening", "wachtwoord", "geblokkeerd", "dringend", "inloggen",
+             "ongebruikelijke activiteit", "bijwerken", "beveiligingswaarschuwing", "terugbetaling", "winnaar"]),
+    ("hi", &["सत्यापित", "खाता", "पासवर्ड", "ब्लॉक", "तुरंत", "लॉगिन", "केवाईसी", "रिफंड", "इनाम", "बधाई", "अपडेट"]),
+    ("ta", &["கணக்கு", "சரிபார்", "கடவுச்சொல்", "உடனடியாக", "முடக்கப்ப", "கேஒய்சி", "பரிசு", "புதுப்பி"]),
+    ("te", &["ఖాతా", "ధృవీకరించ", "పాస్‌వర్డ్", "వెంటనే", "బ్లాక్", "కేవైసీ", "బహుమతి", "అప్‌డేట్"]),
+    ("ru", &["подтвердите", "аккаунт", "учетная запись", "пароль", "заблокирован", "срочно", "войти",
+             "необычная активность", "обновите", "возврат", "выигрыш", "приз"]),
+    ("ar", &["تحقق", "حساب", "كلمة المرور", "تعليق", "عاجل", "تسجيل الدخول", "تحديث", "استرداد", "جائزة"]),
+];
+
+/// Scripts that identify a language on their own: Devanagari, Tamil, Telugu,
+/// Cyrillic, Arabic.
+const SCRIPTS: [(&str, char, char); 5] = [
+    ("hi", '\u{0900}', '\u{097F}'), ("ta", '\u{0B80}', '\u{0BFF}'), ("te", '\u{0C00}', '\u{0C7F}'),
+    ("ru", '\u{0400}', '\u{04FF}'), ("ar", '\u{0600}', '\u{06FF}'),
+];
+
+/// Languages whose text is also romanised and matched against
+/// [`ROMANIZED_LURES`](crate::translit::ROMANIZED_LURES).
+pub(crate) const INDIC: &[&str] = &["hi", "ta", "te"];
+
+/// ISO 639-1 code of the dominant language of `text`, or `"und"` when
+/// there is too little text to tell.
+pub fn detect_lang(text: &str) -> &'static str {
+    let sample: String = text.chars().take(MAX_SAMPLE_CHARS).flat_map(char::to_lowercase).collect();
+
+    let (mut letters, mut scripted) = (0usize, [0usize; SCRIPTS.len()]);
+    for c in sample.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        if let Some(i) = SCRIPTS.iter().position(|(_, lo, hi)| (*lo..=*hi).contains(&c)) { scripted[i] += 1; }
+    }
+    if letters == 0 { return "und"; }
+    for (i, (lang, _, _)) in SCRIPTS.iter().enumerate() {
+        if scripted[i] * 2 > letters { return lang; }
+    }
+
+    // normalise to single-space-separated words padded at both ends
+    let mut norm = String::with_capacity(sample.len() + 2);
+    norm.push(' ');
+    for w in sample.split(|c: char| !c.is_alphabetic()).filter(|w| !w.is_empty()) { norm.push_str(w); norm.push(' '); }
+
+    let chars: Vec<char> = norm.chars().collect();
+    let mut grams: Vec<String> = chars.windows(3).map(|w| w.iter().collect()).collect();
+    grams.sort();
+    let count = |g: &str| {
+        let lo = grams.partition_point(|x| x.as_str() < g);
+        grams[lo..].iter().take_while(|x| x.as_str() == g).count()
+    };
+    PROFILES.iter()
+        .map(|(lang, profile)| (*lang, profile.iter().map(|g| count(g)).sum::<usize>()))
+        .filter(|(_, hits)| *hits >= MIN_HITS)
+        .max_by_key(|(_, hits)| *hits)
+        .map_or("und", |(lang, _)| lang)
+}
+
+/// Lure phrases for `lang`; undetermined text falls back to English.
+pub(crate) fn lure_words(lang: &str) -> &'static [&'static str] {
+    LURE_WORDS.iter().find(|(l, _)| *l == lang).or_else(|| LURE_WORDS.first()).map_or(&[], |(_, w)| w)
+}
@@ -0,0 +1,118 @@
+// wasm-feature/crates/vigilant-core/src/legacy.rs
+// Browser Vigilant — the vector the shipped `model.onnx` was trained on.
+// That model predates the feature versions: it reads the 56 slots of
+// version 1, computed the way the baseline `features.py` does. Later
+// versions redefine slots inside those 56 (a PSL registrable domain for
+// F14/F21–F23/F40/F45, any-encoding IPs for F12, Damerau distance and
+// configured brands for F21–F22, decoded keywords for F24–F29 and F37, the
+// extension class for F51), so the extension feeds it this vector instead of
+// a prefix of [`crate::extract_features`] until it is retrained.
+
+use crate::{
+    extract_features, levenshtein, mask, parse_url, BRANDS, DANGEROUS_EXTS, FRAUD_KW, FREE_KW, LOGIN_KW, PAY_KW,
+    SHORT_SERVICES, TRUST_KW,
+};
+
+/// Feature version of [`extract_legacy_features`].
+pub const LEGACY_FEATURE_VERSION: u32 = 1;
+
+/// Values returned by [`extract_legacy_features`].
+pub const LEGACY_FEATURES: usize = 56;
+
+/// Dotted-quad IPv4 host, the only IP form version 1 recognised.
+fn dotted_ipv4(host: &str) -> bool {
+    let parts: Vec<&str> = host.split('.').collect();
+    parts.len() == 4 && parts.iter().all(|p| p.parse::<u8>().is_ok())
+}
+
+/// The 56 version-1 features of `url`, for models trained before
+/// [`crate::FEATURE_VERSION`] existed. Slots that version 1 computes the
+/// same way are taken from [`extract_features`]; the rest use its rules:
+/// the last two host labels as the registrable domain, Levenshtein against
+/// the built-in brands, keywords on the text as written.
+pub fn extract_legacy_features(url: &str) -> Vec<f32> {
+    let mut f = extract_features(url);
+    f.truncate(LEGACY_FEATURES);
+    let p = parse_url(url);
+    let low = url.to_lowercase();
+    let host = p.host.as_str();
+    let labels: Vec<String> = host.split('.').map(str::to_lowercase).collect();
+    let (domain, sub) = match labels.len() {
+        n if n > 2 => (labels[n - 2..].join("."), labels[..n - 2].join(".")),
+        2 => (labels.join("."), String::new()),
+        _ => (host.to_string(), String::new()),
+    };
+
+    f[11] = if p.scheme == "https" { 1.0 } else { 0.0 };
+    f[12] = if dotted_ipv4(host) { 1.0 } else { 0.0 };
+    f[14] = labels.len().saturating_sub(2) as f32;
+
+    let core = domain.split('.').next().unwrap_or("").to_lowercase();
+    let min_dist = BRANDS.iter().map(|b| levenshtein(&core, b)).min().unwrap_or(99);
+    f[21] = if min_dist > 0 && min_dist <= 2 { 1.0 } else { 0.0 };
+    f[22] = (min_dist.min(10) as f32) / 10.0;
+    let brand_sub = BRANDS.iter().any(|b| sub.contains(b));
+    let brand_reg = BRANDS.iter().any(|b| core.contains(b));
+    f[23] = if brand_sub && !brand_reg { 1.0 } else { 0.0 };
+
+    let has = |kw: &[&str], text: &str| if kw.iter().any(|k| text.contains(k)) { 1.0 } else { 0.0 };
+    f[24] = has(LOGIN_KW, &low);
+    f[25] = if TRUST_KW.iter().chain(LOGIN_KW).any(|k| host.contains(k)) { 1.0 } else { 0.0 };
+    f[26] = has(PAY_KW, &low);
+    f[27] = has(FREE_KW, &low);
+    f[28] = has(FRAUD_KW, &low);
+    let hits = LOGIN_KW.iter().chain(TRUST_KW).chain(PAY_KW).chain(FREE_KW).chain(FRAUD_KW)
+        .filter(|k| low.contains(**k)).count();
+    f[29] = (hits as f32 / 6.0).min(1.0);
+    f[37] = if p.path.contains("..") || low.contains("%2e%2e") { 1.0 } else { 0.0 };
+
+    f[40] = if sub.is_empty() { 0.0 } else { 1.0 };
+    f[45] = if SHORT_SERVICES.contains(&domain.as_str()) { 1.0 } else { 0.0 };
+    let path_low = p.path.to_lowercase();
+    let ext = path_low.rsplit('.').next().unwrap_or("").split('?').next().unwrap_or("").split('#').next().unwrap_or("");
+    f[51] = if DANGEROUS_EXTS.contains(&ext) { 1.0 } else { 0.0 };
+
+    mask::apply(&mut f);
+    f
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_later_versions_redefined_keep_their_version_1_meaning() {
+        let f = extract_legacy_features("https://a.b.example.co.uk/%6c%6f%67%69%6e/../x.apk");
+        assert_eq!(f.len(), LEGACY_FEATURES);
+        assert_eq!(f[14], 3.0, "labels past the last two");
+        assert_eq!(f[24], 0.0, "keywords are matched as written");
+        assert_eq!(f[51], 1.0, "apk was a dangerous extension");
+        let v = extract_features("https://a.b.example.co.uk/%6c%6f%67%69%6e/../x.apk");
+        assert_eq!((v[14], v[24]), (2.0, 1.0));
+    }
+
+    #[test]
+    fn only_dotted_quads_are_ip_hosts() {
+        assert_eq!(extract_legacy_features("http://192.168.0.1/login")[12], 1.0);
+        assert_eq!(extract_legacy_features("http://0x7f000001/login")[12], 0.0);
+        assert_eq!(extract_features("http://0x7f000001/login")[12], 1.0);
+    }
+
+    #[test]
+    fn brand_distance_is_levenshtein_on_the_last_two_labels() {
+        // a swap is two Levenshtein edits, one Damerau edit
+        let f = extract_legacy_features("https://paypla.com/");
+        assert_eq!((f[21], f[22]), (1.0, 0.2));
+        let f = extract_legacy_features("https://paypal.com.evil.example/");
+        assert_eq!((f[23], f[40]), (1.0, 1.0));
+    }
+
+    #[test]
+    fn unchanged_slots_match_the_current_vector() {
+        let url = "http://secure-login.example.tk:8081/wp-admin/a_b?redirect=http://x#f";
+        let (l, v) = (extract_legacy_features(url), extract_features(url));
+        for i in [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 13, 15, 16, 17, 18, 19, 20, 30, 31, 32, 33, 34, 35, 36, 38, 39, 41, 42, 43, 44, 46, 47, 48, 49, 50, 52, 53, 54, 55] {
+            assert_eq!(l[i], v[i], "F{}", i);
+        }
+    }
+}
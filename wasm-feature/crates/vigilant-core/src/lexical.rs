@@ -0,0 +1,263 @@
+// wasm-feature/crates/vigilant-core/src/lexical.rs
+// Browser Vigilant — character-class shape of the host and how ordinary its
+// name's length is. Substitution squats (`faceb00k`, `paypa1-l0gin`) flip
+// between letters and digits where real names don't; entropy and digit
+// ratios average that away over the URL, as they do one random token (a
+// session blob, an encoded victim address) in an otherwise plain URL. Raw
+// lengths scale badly (a 40-char label isn't four times as odd as a 10-char
+// one), so the label's length is also given as a percentile of legitimate names.
+// The model's entropies (F16–F20) likewise climb with length; a normalized
+// variant divides by the most a string of that length could carry. The
+// host's pronounceability (F43–F44) is also measured on the path and query,
+// where kits on compromised sites put their random tokens.
+
+use crate::psl::{public_suffix, registrable_domain};
+use crate::{char_ngram_entropy, max_consecutive_consonants, parse_url, percent_decode, query_pairs, shannon_entropy, BRANDS};
+
+/// Values returned by [`char_class_features`]: class_transitions,
+/// letter_digit_alternations, digit_embedded_brand, transition_ratio.
+pub const CHAR_CLASS_FEATURES: usize = 4;
+
+/// Values returned by [`token_features`]: query_value_entropy_max,
+/// max_token_length.
+pub const TOKEN_FEATURES: usize = 2;
+
+/// Values returned by [`entropy_features`]: url, host, path, host_bigram and
+/// host_trigram entropy, then the same five normalized.
+pub const ENTROPY_FEATURES: usize = 10;
+
+/// Values returned by [`pronounce_features`]: max_consonant_run,
+/// max_digit_run and vowel_ratio of the first path segment, then of the
+/// longest query value.
+pub const PRONOUNCE_FEATURES: usize = 6;
+
+/// How [`entropy_features`] measures entropy.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EntropyOptions {
+    /// Logarithm base of the raw values: 2 gives bits per symbol, as in the
+    /// model vector; `e` gives nats.
+    base: f32,
+}
+
+impl Default for EntropyOptions {
+    fn default() -> EntropyOptions { EntropyOptions { base: 2.0 } }
+}
+
+impl EntropyOptions {
+    /// `None` unless `base` is finite and above 1.
+    pub fn with_base(base: f32) -> Option<EntropyOptions> {
+        (base.is_finite() && base > 1.0).then_some(EntropyOptions { base })
+    }
+
+    pub fn base(&self) -> f32 { self.base }
+}
+
+/// Digits as the letters they stand in for.
+pub(crate) const DIGIT_LETTERS: &[(&str, &str)] = &[("0", "o"), ("1", "l"), ("3", "e"), ("4", "a"), ("5", "s"), ("7", "t"), ("8", "b")];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Class { Letter, Digit, Other }
+
+fn class(c: char) -> Class {
+    if c.is_ascii_digit() { Class::Digit } else if c.is_alphabetic() { Class::Letter } else { Class::Other }
+}
+
+/// Letter↔digit switches in `label`, and digit runs with letters on both sides.
+pub(crate) fn transitions(label: &str) -> (usize, usize) {
+    let classes: Vec<Class> = label.chars().map(class).filter(|c| *c != Class::Other).collect();
+    let switches = classes.windows(2).filter(|w| w[0] != w[1]).count();
+    let mut sandwiched = 0;
+    let mut i = 0;
+    while i < classes.len() {
+        if classes[i] != Class::Digit { i += 1; continue; }
+        let start = i;
+        while i < classes.len() && classes[i] == Class::Digit { i += 1; }
+        if start > 0 && i < classes.len() { sandwiched += 1; }
+    }
+    (switches, sandwiched)
+}
+
+/// `label` with its digits read as letters names a brand it doesn't spell.
+fn digit_brand(label: &str) -> bool {
+    if !label.bytes().any(|b| b.is_ascii_digit()) { return false; }
+    let read = DIGIT_LETTERS.iter().fold(label.to_string(), |s, (d, l)| s.replace(d, l));
+    BRANDS.iter().any(|b| b.len() >= 4 && read.contains(b) && !label.contains(b))
+}
+
+/// `[class_transitions, letter_digit_alternations, digit_embedded_brand,
+/// transition_ratio]` over the host's labels: letter↔digit switches, digit
+/// runs with letters on both sides, a label whose digits read as letters
+/// spell a brand (`faceb00k`), and the switches per host character.
+pub fn char_class_features(url: &str) -> [f32; CHAR_CLASS_FEATURES] {
+    let host = parse_url(url).host.to_lowercase();
+    let (mut switches, mut sandwiched) = (0, 0);
+    for label in host.split('.') {
+        let (s, a) = transitions(label);
+        switches += s;
+        sandwiched += a;
+    }
+    let brand = host.split('.').any(digit_brand);
+    [switches as f32, sandwiched as f32, if brand { 1.0 } else { 0.0 }, switches as f32 / host.len().max(1) as f32]
+}
+
+/// `[query_value_entropy_max, max_token_length]`: the highest Shannon
+/// entropy of any one decoded query value, and the longest run of letters
+/// and digits anywhere after the scheme.
+pub fn token_features(url: &str) -> [f32; TOKEN_FEATURES] {
+    let p = parse_url(url);
+    let entropy = query_pairs(&p.query).iter().map(|(_, v)| shannon_entropy(v)).fold(0.0f32, f32::max);
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    let longest = rest.split(|c: char| !c.is_alphanumeric()).map(|t| t.chars().count()).max().unwrap_or(0);
+    [entropy, longest as f32]
+}
+
+/// `[url, host, path, host_bigram, host_trigram]` entropy as F16–F20
+/// compute it but in `opts`' log base, then each divided by the maximum for
+/// its length (log of the symbol count): 0 for a repeated character, 1 when
+/// every symbol differs, whatever the length. The normalized half doesn't
+/// depend on the base.
+pub fn entropy_features(url: &str, opts: EntropyOptions) -> [f32; ENTROPY_FEATURES] {
+    let p = parse_url(url);
+    let grams = |s: &str, n: usize| s.chars().count().saturating_sub(n - 1);
+    let bits = [
+        (shannon_entropy(url), url.len()),
+        (shannon_entropy(&p.host), p.host.len()),
+        (shannon_entropy(&p.path), p.path.len()),
+        (char_ngram_entropy(&p.host, 2), grams(&p.host, 2)),
+        (char_ngram_entropy(&p.host, 3), grams(&p.host, 3)),
+    ];
+    let mut f = [0.0f32; ENTROPY_FEATURES];
+    for (i, (h, n)) in bits.into_iter().enumerate() {
+        f[i] = h / opts.base.log2();
+        f[i + 5] = if n > 1 { (h / (n as f32).log2()).min(1.0) } else { 0.0 };
+    }
+    f
+}
+
+/// Longest consonant run, longest digit run and vowels per letter of `s`.
+pub(crate) fn pronounceability(s: &str) -> [f32; 3] {
+    let digits = s.split(|c: char| !c.is_ascii_digit()).map(str::len).max().unwrap_or(0);
+    let vowels = s.chars().filter(|c| "aeiou".contains(c.to_ascii_lowercase())).count();
+    let alpha = s.chars().filter(|c| c.is_alphabetic()).count();
+    [max_consecutive_consonants(s) as f32, digits as f32, vowels as f32 / alpha.max(1) as f32]
+}
+
+/// `[path_max_consonant_run, path_max_digit_run, path_vowel_ratio,
+/// query_max_consonant_run, query_max_digit_run, query_vowel_ratio]`: F44's
+/// consonant run and F43's vowel ratio, plus the longest digit run, for the
+/// decoded first path segment and the longest decoded query value. All 0
+/// when the part is missing. A generated `/xkq7rtzp/` on a known site shows
+/// here while the host looks clean.
+pub fn pronounce_features(url: &str) -> [f32; PRONOUNCE_FEATURES] {
+    let p = parse_url(url);
+    let segment = p.path.split('/').find(|s| !s.is_empty()).map(|s| percent_decode(s, false)).unwrap_or_default();
+    let value = query_pairs(&p.query).into_iter().map(|(_, v)| v).max_by_key(|v| v.chars().count()).unwrap_or_default();
+    let mut f = [0.0f32; PRONOUNCE_FEATURES];
+    f[..3].copy_from_slice(&pronounceability(&segment));
+    f[3..].copy_from_slice(&pronounceability(&value));
+    f
+}
+
+/// (length, share of legitimate registrable labels at most that long);
+/// lengths in between interpolate, longer ones are at 1.
+pub(crate) const LABEL_LENGTH_CDF: &[(usize, f32)] = &[
+    (1, 0.001), (2, 0.010), (3, 0.040), (4, 0.090), (5, 0.170), (6, 0.270), (7, 0.380), (8, 0.490),
+    (9, 0.590), (10, 0.680), (11, 0.750), (12, 0.810), (13, 0.860), (14, 0.895), (15, 0.925), (16, 0.945),
+    (17, 0.960), (18, 0.972), (19, 0.980), (20, 0.986), (22, 0.993), (25, 0.997), (30, 0.999), (40, 1.0),
+];
+
+/// Percentile (0–1) of a `len`-character label among legitimate ones.
+pub(crate) fn length_percentile(len: usize) -> f32 {
+    let mut below = (0, 0.0f32);
+    for &(l, p) in LABEL_LENGTH_CDF {
+        if len == l { return p; }
+        if len < l { return below.1 + (p - below.1) * (len - below.0) as f32 / (l - below.0) as f32; }
+        below = (l, p);
+    }
+    1.0
+}
+
+/// Percentile (0–1) of the URL's registrable label length (`paypal` in
+/// `www.paypal.co.uk`) among legitimate sites' labels; 0 for hosts without
+/// one, such as IP addresses. A bounded, evenly spread stand-in for the raw
+/// length the model vector keeps for compatibility with trained models.
+pub fn label_length_percentile(url: &str) -> f32 {
+    let host = parse_url(url).host.to_lowercase();
+    let reg = registrable_domain(&host);
+    let suffix = public_suffix(&host);
+    let label = reg.strip_suffix(&suffix).map_or(reg.as_str(), |l| l.trim_end_matches('.'));
+    if label.is_empty() || label.bytes().all(|b| b.is_ascii_digit()) { return 0.0; }
+    length_percentile(label.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_substitutions_switch_classes_and_spell_a_brand() {
+        let f = char_class_features("https://faceb00k-login.com/");
+        assert_eq!(f[..3], [2.0, 1.0, 1.0]);
+        assert!((f[3] - 2.0 / 18.0).abs() < 1e-6);
+        assert_eq!(char_class_features("https://facebook.com/"), [0.0; CHAR_CLASS_FEATURES]);
+    }
+
+    #[test]
+    fn only_digit_runs_between_letters_alternate() {
+        assert_eq!(transitions("abc123"), (1, 0));
+        assert_eq!(transitions("a1b2c"), (4, 2));
+        assert_eq!(transitions("a-1"), (1, 0), "separators don't break a switch");
+        assert_eq!(char_class_features("http://192.168.0.1/")[..3], [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn label_length_reads_off_the_cdf() {
+        assert_eq!(label_length_percentile("https://www.paypal.co.uk/"), 0.27);
+        assert_eq!(label_length_percentile("https://paypal.com/"), 0.27);
+        assert!((length_percentile(21) - 0.9895).abs() < 1e-6, "interpolated");
+        assert_eq!(length_percentile(0), 0.0);
+        assert_eq!(length_percentile(63), 1.0);
+        assert_eq!(label_length_percentile("http://192.168.0.1/"), 0.0);
+    }
+
+    #[test]
+    fn cdf_is_increasing() {
+        assert!(LABEL_LENGTH_CDF.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+        assert_eq!(LABEL_LENGTH_CDF.last().map(|e| e.1), Some(1.0));
+    }
+
+    #[test]
+    fn the_most_random_decoded_value_and_longest_token_stand_out() {
+        assert_eq!(token_features("https://a.com/?x=aaaa&y=abcd"), [2.0, 4.0]);
+        assert_eq!(token_features("https://a.com/?v=%61%62")[0], 1.0, "values are decoded");
+        assert_eq!(token_features("https://a.co/x")[1], 2.0, "the scheme isn't a token");
+        assert_eq!(token_features("https://example.com/"), [0.0, 7.0]);
+    }
+
+    #[test]
+    fn normalized_entropy_ignores_length_and_base() {
+        let bits = entropy_features("http://abcd/", EntropyOptions::default());
+        assert_eq!((bits[1], bits[6], bits[8]), (2.0, 1.0, 1.0));
+        let nats = entropy_features("http://abcd/", EntropyOptions::with_base(std::f32::consts::E).unwrap());
+        assert!((nats[1] - 2.0 * std::f32::consts::LN_2).abs() < 1e-6);
+        assert_eq!(nats[5..], bits[5..]);
+        assert_eq!(entropy_features("http://aaaa/", EntropyOptions::default())[6], 0.0);
+    }
+
+    #[test]
+    fn only_finite_bases_above_one_are_accepted() {
+        for base in [1.0, 0.5, -2.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(EntropyOptions::with_base(base), None, "{}", base);
+        }
+        assert_eq!(EntropyOptions::with_base(10.0).map(|o| o.base()), Some(10.0));
+    }
+
+    #[test]
+    fn generated_path_segments_read_as_unpronounceable() {
+        let f = pronounce_features("https://site.com/xkq7rtzp/index.php?a=hello&b=hi");
+        assert_eq!(f[..3], [4.0, 1.0, 0.0]);
+        assert_eq!(f[3..], [2.0, 0.0, 0.4], "the longest value, hello");
+        assert_eq!(pronounce_features("https://site.com/%61%62%63")[..3], [2.0, 0.0, 1.0 / 3.0]);
+        assert_eq!(pronounce_features("https://site.com/"), [0.0; PRONOUNCE_FEATURES]);
+    }
+}
@@ -0,0 +1,765 @@
+// wasm-feature/crates/vigilant-core/src/lib.rs
+// Browser Vigilant — core detection logic, free of any wasm/JS dependency.
+// The model-vector URL extractor below uses real mathematical formulas;
+// feature order MUST match model/features.py exactly.
+
+mod analyzer;
+mod api;
+mod apk;
+mod archive;
+mod audit;
+mod autofill;
+mod brandalias;
+mod brandpath;
+mod brands;
+mod capabilities;
+mod cert;
+mod chunks;
+mod clipboard;
+mod composite;
+mod compound;
+mod compromised;
+mod contact;
+mod context;
+mod csp;
+mod datauri;
+mod deeplink;
+mod dga;
+mod diff;
+mod display;
+mod disposition;
+mod dnr;
+mod download;
+mod embedded;
+mod encoding;
+mod endpoint;
+mod exfil;
+mod expand;
+mod extensions;
+mod fronting;
+mod hash;
+mod hostsignals;
+mod html_scan;
+mod ics;
+mod inflate;
+mod intent;
+pub mod json;
+mod keywords;
+mod lang;
+mod legacy;
+mod lexical;
+mod mail;
+mod mailauth;
+mod malformed;
+mod managed;
+mod manifest;
+mod mask;
+mod memory;
+mod messages;
+mod mining;
+mod model;
+mod named;
+mod normalize;
+mod numbrand;
+mod oauth;
+mod office;
+mod onnx;
+mod overrides;
+mod packed;
+mod page;
+mod pdf;
+mod personal;
+mod policy;
+mod psl;
+mod rank;
+mod resources;
+mod schema;
+mod scheme;
+mod schemepayload;
+mod score;
+mod selftest;
+mod signals;
+mod state;
+mod stream;
+mod subdomain;
+mod template;
+mod time;
+mod timing;
+mod translit;
+mod trees;
+mod types;
+mod typosquat;
+mod upi;
+mod userdata;
+mod vectors;
+mod verdict;
+mod zip;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+pub use analyzer::{Analyzer, AnalyzerBuilder, Caps, KeywordPack, Profile};
+pub use api::{PageAnalyzer, RiskSession, UrlAnalyzer, UrlReport, REDIRECT_STORM, SESSION_FEATURES, SESSION_WINDOW_MS};
+pub use apk::apk_report;
+pub use archive::archive_report;
+pub use audit::{AuditEntry, AuditFormat, AuditLog, AUDIT_CAPACITY, ENGINE_VERSION};
+pub use autofill::should_allow_autofill;
+pub use brandpath::{brand_path_features, BRAND_PATH_FEATURES};
+pub use brands::MAX_RUNTIME_BRANDS;
+pub use capabilities::{capabilities_report, MODEL_FEATURES};
+pub use cert::cert_report;
+pub use chunks::MIN_CHUNK_BYTES;
+pub use clipboard::compare_payment_targets;
+pub use compromised::{compromised_site_report, popularity_rank};
+pub use contact::{contact_features, contact_link_report, CONTACT_FEATURES};
+pub use context::{Context, Contexts, DEFAULT_CONTEXT, MAX_SIMULATION_URLS};
+pub use csp::{csp_report, parse_csp, Csp};
+pub use datauri::{data_uri_report, MAX_DATA_URI_BYTES};
+pub use deeplink::deep_link_report;
+pub use dga::{dga_features, DGA_FEATURES};
+pub use diff::diff_reports;
+pub use display::{display_spoof_risk, homoglyph_features, HOMOGLYPH_FEATURES, MOBILE_OMNIBOX_CHARS};
+pub use disposition::{filename_report, parse_content_disposition};
+pub use dnr::{dnr_rules, DNR_DEFAULT_MAX_RULES, DNR_DOMAINS_PER_RULE};
+pub use download::{download_event_report, download_report};
+pub use embedded::{extract_embedded_urls, MAX_EMBEDDED_URLS, MAX_EMBED_DEPTH};
+pub use encoding::{encoding_features, ENCODING_FEATURES};
+pub use endpoint::score_endpoint;
+pub use exfil::WEBHOOK_EXFIL_TAG;
+pub use expand::{ShortExpansion, EXPANSION_FEATURES, MAX_REDIRECT_HOPS};
+pub use extensions::{extension_features, EXTENSION_FEATURES};
+pub use fronting::{check_host_consistency, DOMAIN_FRONTING_TAG};
+pub use hash::{sha256, sha256_hex, Privacy};
+pub use hostsignals::{domain_age_key, host_signal_features, HOST_SIGNAL_FEATURES};
+pub use html_scan::html_attachment_report;
+pub use ics::ics_report;
+pub use intent::{intent_features, intent_report, INTENT_FEATURES};
+pub use keywords::{keyword_features, KEYWORD_FEATURES};
+pub use lang::detect_lang;
+pub use legacy::{extract_legacy_features, LEGACY_FEATURES, LEGACY_FEATURE_VERSION};
+pub use lexical::{
+    char_class_features, entropy_features, label_length_percentile, pronounce_features, token_features, EntropyOptions,
+    CHAR_CLASS_FEATURES, ENTROPY_FEATURES, PRONOUNCE_FEATURES, TOKEN_FEATURES,
+};
+pub use mail::mail_report;
+pub use mailauth::{
+    auth_results_json, dmarc_json, parse_authentication_results, parse_dmarc, parse_spf, spf_json,
+    AuthClause, AuthResults, DmarcPolicy, SpfPolicy,
+};
+pub use malformed::{browser_url, malformed_features, ParseMode, MALFORMED_FEATURES};
+pub use managed::ManagedPolicy;
+pub use manifest::data_manifest;
+pub use mask::{feature_mask, set_feature_mask, MASKABLE_FEATURES};
+pub use memory::memory_report;
+pub use messages::{render_explanation, DEFAULT_LOCALE};
+pub use mining::{HardNegative, HardNegatives, MiningSource, HARD_NEGATIVE_CAPACITY, MIN_DISAGREEMENT};
+pub use model::{parse_model, Calibration, ModelInfo, FEATURE_VERSION};
+pub use named::{extract_features_named, name_features};
+pub use normalize::normalize_url;
+pub use numbrand::{numeric_brand_features, NUMERIC_BRAND_FEATURES};
+pub use oauth::oauth_report;
+pub use office::office_report;
+pub use onnx::load_onnx_model;
+pub use overrides::{test_rule, OverrideRule};
+pub use packed::{extract_features_packed, pack_features, unpack_features, Precision};
+pub use page::page_report;
+pub use pdf::pdf_report;
+pub use personal::{Personalizer, PERSONAL_FEATURES};
+pub use policy::download_context_report;
+pub use psl::registrable_domain;
+pub use rank::{ranked_signals, Severity};
+pub use resources::{resource_graph_report, RESOURCE_FEATURES};
+pub use schema::{feature_schema, feature_schema_hash, FEATURE_SCHEMA_VERSION};
+pub use scheme::{scheme_features, SCHEME_FEATURES};
+pub use schemepayload::{scheme_payload_report, scheme_risk_features, SCHEME_RISK_FEATURES};
+pub use score::{combine, feature_importances, set_importance_model, url_risk, UNCERTAINTY_FLOOR};
+pub use selftest::self_test;
+pub use signals::{
+    register_signal_provider, set_signal_mask, signal_mask, signal_providers, unregister_signal_provider, CustomSignal, SignalProvider,
+};
+pub use state::{State, FEATURE_CACHE_ENTRIES};
+pub use stream::{extract_stream, read_capped, score_line, stream_with, StreamStats, MAX_LINE_BYTES};
+pub use subdomain::{subdomain_features, SUBDOMAIN_FEATURES};
+pub use template::url_template;
+pub use time::{time_features, TIME_FEATURES};
+pub use timing::{set_timing_clock, timing_enabled, Clock};
+pub use translit::transliterate;
+pub use trees::{Aggregation, TreeEnsemble, TreeModel};
+pub use types::{
+    CertInfo, ContextConfig, DownloadEvent, Evidence, FormSnapshot, FromJson, HostSignals, PageSnapshot, Report, SchemaError, ToJson, SCHEMA_VERSION,
+};
+pub use typosquat::{typosquat_features, TYPOSQUAT_FEATURES};
+pub use upi::upi_request_report;
+pub use userdata::{USER_DATA_FORMAT, USER_DATA_VERSION};
+pub use vectors::{gen_test_vectors, MAX_TEST_VECTORS};
+pub use verdict::{Decision, Verdict};
+
+// ── Constants ─────────────────────────────────────────────────────────────────
+
+const BRANDS: &[&str] = &[
+    "google","facebook","amazon","apple","microsoft","paypal","netflix",
+    "instagram","twitter","linkedin","whatsapp","youtube","yahoo","ebay",
+    "dropbox","spotify","adobe","chase","wellsfargo","bankofamerica",
+    "citi","hsbc","barclays","halifax","natwest","santander","lloyds",
+    "steam","roblox","epic","coinbase","binance","metamask","opensea",
+    "paytm","phonepe","gpay","bhim","razorpay","hdfc","icici","sbi",
+    "axis","kotak","airtel","jio","vodafone","bsnl","flipkart","myntra",
+];
+
+const SUSPICIOUS_TLDS: &[&str] = &[
+    "xyz","tk","top","cf","ml","ga","gq","pw","cc","icu","club","online",
+    "site","website","space","live","click","link","info","biz","work",
+    "tech","store","shop",
+];
+
+const LEGIT_UPI_HANDLES: &[&str] = &[
+    "okaxis","okicici","oksbi","okhdfcbank","ybl","ibl","axl","apl","fbl",
+    "upi","paytm","waaxis","waxis","rajgovhdfcbank","barodampay","allbank",
+    "andb","aubank","cnrb","csbpay","dbs","dcb","federal","hdfcbank","idbi",
+    "idfc","indus","idfcbank","jio","kotak","lvb","mahb","nsdl","pnb",
+    "psb","rbl","sib","tjsb","uco","union","united","vijb","yapl","airtel",
+    "airtelpaymentsbank","postbank",
+];
+
+const SHORT_SERVICES: &[&str] = &[
+    "bit.ly","tinyurl.com","t.co","goo.gl","ow.ly","is.gd","buff.ly",
+    "adf.ly","tiny.cc","clck.ru","cutt.ly","rb.gy","short.io","v.gd",
+];
+
+const DANGEROUS_EXTS: &[&str] = &[
+    "exe","scr","bat","cmd","ps1","vbs","wsf","hta","jar","msi","msp",
+    "reg","dll","pif","com","cpl","inf","apk","ipa","dmg","pkg","deb","rpm",
+];
+
+const LOGIN_KW: &[&str] = &[
+    "login","signin","sign-in","account","verify","auth","authenticate","confirm","update",
+];
+const TRUST_KW: &[&str] = &["secure","safe","trust","bank","protected","official"];
+const PAY_KW:   &[&str] = &["pay","payment","wallet","upi","gpay","paytm","bhim","razorpay","phonepay"];
+const FREE_KW:  &[&str] = &["free","bonus","prize","winner","giveaway","reward","claim","gift","lucky","congratulations"];
+const FRAUD_KW: &[&str] = &["kyc","refund","tax","block","suspend","urgent","helpdesk","support","care","alert"];
+const FRAUD_PFX:&[&str] = &["refund","tax","prize","block","kyc","urgent","helpdesk","support","care"];
+
+// ── Math ──────────────────────────────────────────────────────────────────────
+
+/// Shannon entropy H = -Σ p(c) * log2(p(c))
+fn shannon_entropy(s: &str) -> f32 {
+    if s.is_empty() { return 0.0; }
+    let mut freq = [0u32; 256];
+    let bytes = s.as_bytes();
+    for &b in bytes { freq[b as usize] += 1; }
+    let n = bytes.len() as f32;
+    freq.iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| { let p = c as f32 / n; -p * p.log2() })
+        .sum()
+}
+
+pub(crate) fn char_ngram_entropy(s: &str, n: usize) -> f32 {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() < n { return 0.0; }
+    // ordered, so the float sum (and F19/F20) is the same on every run
+    let mut freqs = std::collections::BTreeMap::new();
+    for i in 0..=(chars.len() - n) {
+        let ng: String = chars[i..i+n].iter().collect();
+        *freqs.entry(ng).or_insert(0) += 1;
+    }
+    let total = freqs.values().sum::<i32>() as f32;
+    freqs.values().map(|&count| {
+        let p = count as f32 / total;
+        -p * p.log2()
+    }).sum()
+}
+
+/// Wagner-Fischer Levenshtein distance O(min(m,n)) space
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (a, b) = if a.len() < b.len() { (&b, &a) } else { (&a, &b) };
+    let m = a.len(); let n = b.len();
+    let mut prev: Vec<usize> = (0..=n).collect();
+    let mut curr = vec![0usize; n + 1];
+    for i in 1..=m {
+        curr[0] = i;
+        for j in 1..=n {
+            let cost = if a[i-1] == b[j-1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j-1] + 1).min(prev[j-1] + cost);
+        }
+        prev.clone_from(&curr);
+    }
+    prev[n]
+}
+
+pub(crate) fn max_consecutive_consonants(s: &str) -> usize {
+    let vowels = "aeiou";
+    let (mut max_run, mut cur) = (0usize, 0usize);
+    for c in s.to_lowercase().chars() {
+        if c.is_alphabetic() && !vowels.contains(c) {
+            cur += 1;
+            if cur > max_run { max_run = cur; }
+        } else {
+            cur = 0;
+        }
+    }
+    max_run
+}
+
+fn count_hex_encoded(url: &str) -> usize {
+    let bytes = url.as_bytes();
+    let mut count = 0usize;
+    let mut i = 0;
+    while i + 2 < bytes.len() {
+        if bytes[i] == b'%' && bytes[i+1].is_ascii_hexdigit() && bytes[i+2].is_ascii_hexdigit() {
+            count += 1;
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    count
+}
+
+/// One part of an IPv4 host as browsers read it: `0x` prefix for hex, a
+/// leading zero for octal, decimal otherwise.
+fn ipv4_part(part: &str) -> Option<u64> {
+    let (digits, radix) = match part.strip_prefix("0x").or_else(|| part.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None if part.len() > 1 && part.starts_with('0') => (&part[1..], 8),
+        None => (part, 10),
+    };
+    if part.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) { return None; }
+    if digits.is_empty() { return Some(0); }
+    u64::from_str_radix(digits, radix).ok()
+}
+
+/// Address of an IP host in any form a browser resolves: dotted quad, one to
+/// three parts with the last filling the remaining bytes (`3232235777`,
+/// `192.168.257`), octal or hex parts (`0300.0250.0.1`, `0xC0A80001`), and
+/// bracketed IPv6.
+fn ip_host(host: &str) -> Option<IpAddr> {
+    if let Some(v6) = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+        return v6.parse::<Ipv6Addr>().ok().map(IpAddr::V6);
+    }
+    let host = host.strip_suffix('.').unwrap_or(host);
+    let parts: Vec<u64> = host.split('.').map(ipv4_part).collect::<Option<_>>()?;
+    let (last, head) = parts.split_last()?;
+    if parts.len() > 4 || head.iter().any(|&n| n > 255) || *last >= 1 << (8 * (5 - parts.len())) { return None; }
+    let addr = head.iter().zip([24, 16, 8]).fold(*last, |a, (&n, shift)| a | n << shift);
+    Some(IpAddr::V4(Ipv4Addr::from(addr as u32)))
+}
+
+/// Detect an IP address, however encoded, in host string
+fn has_ip(host: &str) -> bool { ip_host(host).is_some() }
+
+// ── URL parser ────────────────────────────────────────────────────────────────
+
+/// A URL split the way the feature extractor sees it, as handed to
+/// [`SignalProvider`]s. `scheme`, `tld`, `reg_domain` and `subdomain` are
+/// lowercased; `host`, `path`, `query` and `fragment` keep the case they
+/// were written in.
+#[derive(Clone, Debug)]
+pub struct UrlParts {
+    pub scheme:  String,
+    pub host:    String,
+    pub path:    String,
+    pub query:   String,
+    pub fragment:String,
+    pub port:    Option<u16>,
+    pub tld:     String,
+    pub reg_domain: String,
+    pub subdomain:  String,
+}
+
+/// Split `url` as written, with no browser normalisation (see
+/// `ParseMode::Browser`). Only `scheme://` URLs split as `features.py`'s
+/// `urlparse` does; without `://` (`mailto:`, `javascript:`, `blob:`,
+/// `tel:`) everything before the first `/`, `?` or `#` is read as the host,
+/// where `urlparse` puts the body in `path`.
+fn parse_url(url: &str) -> UrlParts {
+    // scheme; searched in `url` itself, since lowercasing can change byte
+    // lengths ('İ' becomes "i̇") and the offset has to index the original
+    let (scheme, rest) = if let Some(pos) = url.find("://") {
+        (url[..pos].to_lowercase(), &url[pos+3..])
+    } else {
+        (String::new(), url)
+    };
+    // fragment
+    let (rest, fragment) = if let Some(pos) = rest.find('#') {
+        (&rest[..pos], rest[pos+1..].to_string())
+    } else { (rest, String::new()) };
+    // query
+    let (rest, query) = if let Some(pos) = rest.find('?') {
+        (&rest[..pos], rest[pos+1..].to_string())
+    } else { (rest, String::new()) };
+    // host+path
+    let (netloc, path) = if let Some(pos) = rest.find('/') {
+        (&rest[..pos], rest[pos..].to_string())
+    } else { (rest, String::new()) };
+    // strip auth (user:pass@host)
+    let netloc = if let Some(pos) = netloc.rfind('@') { &netloc[pos+1..] } else { netloc };
+    // strip port
+    let (host, port) = if let Some(pos) = netloc.rfind(':') {
+        let maybe_port = &netloc[pos+1..];
+        if maybe_port.chars().all(|c| c.is_ascii_digit()) {
+            (netloc[..pos].to_string(), maybe_port.parse::<u16>().ok())
+        } else {
+            (netloc.to_string(), None)
+        }
+    } else { (netloc.to_string(), None) };
+
+    let tld = host.rsplit('.').next().unwrap_or("").to_lowercase();
+    // an address has no registrable part to split off
+    let reg_domain = if has_ip(&host) { host.to_lowercase() } else { psl::registrable_domain(&host) };
+    let subdomain = host.to_lowercase().trim_end_matches('.')
+        .strip_suffix(reg_domain.as_str()).map_or("", |s| s.trim_end_matches('.')).to_string();
+
+    UrlParts {
+        scheme, host, path, query, fragment, port,
+        tld, reg_domain, subdomain,
+    }
+}
+
+/// Decode %XX escapes (and '+' as space when `plus` is set). Invalid escapes
+/// are kept literally; the result is lossily re-interpreted as UTF-8.
+fn percent_decode(s: &str, plus: bool) -> String {
+    String::from_utf8_lossy(&percent_decode_bytes(s, plus)).into_owned()
+}
+
+fn percent_decode_bytes(s: &str, plus: bool) -> Vec<u8> {
+    let b = s.as_bytes();
+    let mut out = Vec::with_capacity(b.len());
+    let mut i = 0;
+    while i < b.len() {
+        if b[i] == b'%' && i + 2 < b.len()
+            && b[i+1].is_ascii_hexdigit() && b[i+2].is_ascii_hexdigit() {
+            let hex = std::str::from_utf8(&b[i+1..i+3]).unwrap_or("00");
+            out.push(u8::from_str_radix(hex, 16).unwrap_or(0));
+            i += 3;
+        } else if plus && b[i] == b'+' {
+            out.push(b' ');
+            i += 1;
+        } else {
+            out.push(b[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Split a query string into decoded (key, value) pairs, keys lowercased.
+fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query.split('&').filter(|kv| !kv.is_empty()).map(|kv| {
+        let (k, v) = kv.split_once('=').unwrap_or((kv, ""));
+        (percent_decode(k, true).to_lowercase(), percent_decode(v, true))
+    }).collect()
+}
+
+/// Pull http(s)/www links out of free text, trimming trailing punctuation.
+fn find_urls(text: &str) -> Vec<String> {
+    let low = text.to_ascii_lowercase();
+    let mut out: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < low.len() {
+        let rest = &low[i..];
+        let start = ["https://", "http://", "www."].iter().filter_map(|m| rest.find(m)).min();
+        let Some(off) = start else { break };
+        let s = i + off;
+        let e = low[s..].find(|c: char| c.is_whitespace() || "\"'<>()[]{}".contains(c))
+            .map(|e| s + e).unwrap_or(low.len());
+        let url = text[s..e].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        if url.len() > 8 && !out.iter().any(|u| u == url) { out.push(url.to_string()); }
+        i = e.max(s + 1);
+    }
+    out
+}
+
+// ── Main exported function ────────────────────────────────────────────────────
+
+pub fn extract_features(url: &str) -> Vec<f32> { features_with_brands(url, &[]) }
+
+/// [`extract_features`] with `brands` counted next to the built-in list in
+/// the brand-similarity features (F21–F23); see [`Analyzer::url`].
+pub(crate) fn features_with_brands(url: &str, brands: &[String]) -> Vec<f32> {
+    let mut f = vec![0.0f32; MODEL_FEATURES];
+    let p   = parse_url(url);
+    let low = url.to_lowercase();
+    let host  = &p.host;
+    let path  = &p.path;
+    let query = &p.query;
+    let tld   = &p.tld;
+    let domain = &p.reg_domain;
+    let sub    = &p.subdomain;
+
+    // ── GROUP A: Lexical Structure (F0–F15) ────────────────────────────────────
+    f[0] = url.len() as f32;
+    f[1] = host.len() as f32;
+    f[2] = path.len() as f32;
+    f[3] = query.len() as f32;
+    f[4] = url.matches('.').count() as f32;
+    f[5] = url.matches('-').count() as f32;
+    f[6] = url.matches('_').count() as f32;
+    let no_proto = if let Some(pos) = url.find("://") { &url[pos+3..] } else { url };
+    f[7] = no_proto.matches('/').count() as f32;
+    f[8] = url.matches('@').count() as f32;
+    let digits = url.chars().filter(|c| c.is_ascii_digit()).count();
+    f[9]  = digits as f32;
+    f[10] = digits as f32 / url.len().max(1) as f32;
+    let scheme = scheme_features(url);
+    f[11] = scheme[0]; // scheme_https; the other scheme classes are F63–F71
+    f[12] = if has_ip(host) { 1.0 } else { 0.0 };
+    f[13] = if host.contains("xn--") { 1.0 } else { 0.0 };
+    f[14] = if sub.is_empty() { 0.0 } else { sub.split('.').count() as f32 };
+    f[15] = match p.port {
+        Some(pt) if pt != 80 && pt != 443 && pt != 8080 && pt != 8443 => 1.0,
+        _ => 0.0,
+    };
+
+    // ── GROUP B: Information Theory (F16–F20) ──────────────────────────────────
+    f[16] = shannon_entropy(url);
+    f[17] = shannon_entropy(host);
+    f[18] = shannon_entropy(path);
+    f[19] = char_ngram_entropy(host, 2);
+    f[20] = char_ngram_entropy(host, 3);
+
+    // ── GROUP C: Brand Similarity (F21–F23) ────────────────────────────────────
+    let min_dist = typosquat::min_brand_distance(domain, brands);
+    f[21] = if min_dist > 0 && min_dist <= 2 { 1.0 } else { 0.0 };
+    f[22] = (min_dist.min(10) as f32) / 10.0;
+    let brand_sub = brands::any_brand(brands, |b| sub.contains(b));
+    let brand_reg = domain.split('.').next().map(|d| brands::any_brand(brands, |b| d.contains(b))).unwrap_or(false);
+    f[23] = if brand_sub && !brand_reg { 1.0 } else { 0.0 };
+
+    // ── GROUP D: Keyword Signals (F24–F30) ─────────────────────────────────────
+    // on the fully decoded text, so `%256c%256f%2567%2569%256e` reads as login
+    let decoded = encoding::decode_fully(url).0.to_lowercase();
+    let decoded_host = encoding::decode_fully(host).0.to_lowercase();
+    f[24] = if LOGIN_KW.iter().any(|k| decoded.contains(k)) { 1.0 } else { 0.0 };
+    f[25] = if TRUST_KW.iter().chain(LOGIN_KW).any(|k| decoded_host.contains(k)) { 1.0 } else { 0.0 }; // roughly matches features.py trust_kw
+    f[26] = if PAY_KW.iter().any(|k| decoded.contains(k)) { 1.0 } else { 0.0 };
+    f[27] = if FREE_KW.iter().any(|k| decoded.contains(k)) { 1.0 } else { 0.0 };
+    f[28] = if FRAUD_KW.iter().any(|k| decoded.contains(k)) { 1.0 } else { 0.0 };
+
+    let all_kw_count = LOGIN_KW.iter().chain(TRUST_KW).chain(PAY_KW).chain(FREE_KW).chain(FRAUD_KW)
+        .filter(|k| decoded.contains(**k)).count();
+    f[29] = (all_kw_count as f32 / 6.0).min(1.0);
+    f[30] = if host.contains('-') { 1.0 } else { 0.0 };
+
+    // ── GROUP E: Obfuscation & Encoding (F31–F37) ──────────────────────────────
+    let double_ext = ["pdf","doc","docx","xls","jpg","jpeg","png","gif","mp4","zip"];
+    let danger_ext = ["exe","js","php","bat","ps1","vbs","cmd","scr","dll"];
+    let path_low = path.to_lowercase();
+    f[31] = if double_ext.iter().any(|de| danger_ext.iter().any(|xe|
+        path_low.contains(&format!(".{}.{}", de, xe)))) { 1.0 } else { 0.0 };
+    let pct = count_hex_encoded(url);
+    f[32] = pct as f32 / url.len().max(1) as f32;
+    f[33] = (pct as f32 / (url.len() as f32 / 3.0).max(1.0)).min(1.0);
+    f[34] = if query.is_empty() { 0.0 } else { query.matches('&').count() as f32 + 1.0 };
+    f[35] = if !p.fragment.is_empty() { 1.0 } else { 0.0 };
+    f[36] = if low.starts_with("data:") { 1.0 } else { 0.0 };
+    f[37] = if encoding::decode_fully(path).0.contains("..") || low.contains("%2e%2e") { 1.0 } else { 0.0 };
+
+    // ── GROUP F: Domain Quality (F38–F47) ──────────────────────────────────────
+    f[38] = if SUSPICIOUS_TLDS.contains(&tld.as_str()) { 1.0 } else { 0.0 };
+    f[39] = tld.len() as f32;
+    f[40] = if !sub.is_empty() { 1.0 } else { 0.0 };
+    f[41] = if host.chars().all(|c| c.is_ascii_digit() || c == '.') { 1.0 } else { 0.0 };
+    let unique: std::collections::HashSet<char> = url.chars().collect();
+    f[42] = unique.len() as f32 / url.len().max(1) as f32;
+    let vowels: usize = host.chars().filter(|c| "aeiou".contains(*c)).count();
+    let alpha: usize  = host.chars().filter(|c| c.is_alphabetic()).count();
+    f[43] = vowels as f32 / alpha.max(1) as f32;
+    f[44] = max_consecutive_consonants(host) as f32;
+    f[45] = if SHORT_SERVICES.contains(&domain.as_str()) { 1.0 } else { 0.0 };
+    f[46] = {
+        let mut has_b64 = false;
+        let mut run = 0usize;
+        for b in query.bytes() {
+            if b.is_ascii_alphanumeric() || b == b'+' || b == b'/' || b == b'=' {
+                run += 1;
+                if run >= 20 { has_b64 = true; break; }
+            } else { run = 0; }
+        }
+        if has_b64 { 1.0 } else { 0.0 }
+    };
+    f[47] = path.matches('/').count() as f32;
+
+    // ── GROUP G: UPI / Payment Specific (F48–F52) ──────────────────────────────
+    let upi_found = find_upi_vpa(url);
+    f[48] = if !upi_found.is_empty() { 1.0 } else { 0.0 };
+    f[49] = {
+        let mut sus = 0.0f32;
+        for (prefix, handle) in &upi_found {
+            if !LEGIT_UPI_HANDLES.contains(&handle.as_str()) {
+                sus = 1.0; break;
+            }
+            if FRAUD_PFX.iter().any(|fp| prefix.contains(fp)) {
+                sus = 1.0; break;
+            }
+        }
+        sus
+    };
+    f[50] = if low.contains("upi://pay") || low.contains("pa=") && low.contains("@") || low.contains("vpa=") { 1.0 } else { 0.0 };
+
+    // ── GROUP H: File & Extension Risk (F51–F55) ───────────────────────────────
+    let ext = extensions::extension_values(&p);
+    f[51] = ext[3]; // ext_executable; the rest of the class one-hot is F56–F62
+    let admin_paths = ["/wp-admin/", "/admin/", "/phpmyadmin/", "/cgi-bin/"];
+    f[52] = if admin_paths.iter().any(|p| low.contains(p)) { 1.0 } else { 0.0 };
+    let redirect_kws = ["redirect=http", "returnurl=http", "continue=http", "next=http", "goto=http", "url=http"];
+    f[53] = if redirect_kws.iter().any(|p| low.contains(p)) { 1.0 } else { 0.0 };
+    let max_rep = {
+        let mut max_count = 0;
+        let unique_host: std::collections::HashSet<char> = host.chars().collect();
+        for &ch in &unique_host {
+            let count = host.chars().filter(|&c| c == ch).count();
+            if count > max_count { max_count = count; }
+        }
+        max_count
+    };
+    f[54] = max_rep as f32 / host.len().max(1) as f32;
+    f[55] = {
+        let mut has_hex_token = false;
+        let mut run = 0;
+        for c in low.chars() {
+            if c.is_ascii_hexdigit() {
+                run += 1;
+                if run >= 32 { has_hex_token = true; break; }
+            } else { run = 0; }
+        }
+        if has_hex_token { 1.0 } else { 0.0 }
+    };
+
+    // ── GROUP I: Extension Class (F56–F62) ─────────────────────────────────────
+    f[56..59].copy_from_slice(&ext[..3]);
+    f[59..63].copy_from_slice(&ext[4..]);
+
+    // ── GROUP J: Scheme Class (F63–F71) ────────────────────────────────────────
+    f[63..72].copy_from_slice(&scheme[1..]);
+
+    // ── GROUP K: Scheme Payload Risk (F72–F76) ─────────────────────────────────
+    f[72..77].copy_from_slice(&scheme_risk_features(url));
+
+    mask::apply(&mut f);
+    f
+}
+
+/// The model features followed by [`TIME_FEATURES`] values for
+/// `local_ms` (see [`time_features`]). Statelessly, the domain counts as
+/// first seen now; `State::features_with_time` remembers real sightings.
+pub fn extract_features_with_time(url: &str, local_ms: f64) -> Vec<f32> {
+    let mut f = extract_features(url);
+    f.extend(time_features(local_ms, local_ms));
+    mask::apply(&mut f);
+    f
+}
+
+/// The model features followed by [`HOST_SIGNAL_FEATURES`] values from
+/// what the host looked up for `url` (see [`host_signal_features`]).
+pub fn extract_features_with_context(url: &str, signals: &HostSignals) -> Vec<f32> {
+    let mut f = extract_features(url);
+    f.extend(host_signal_features(url, signals));
+    mask::apply(&mut f);
+    f
+}
+
+/// The model features of `url` read in `mode`: as written, or as the
+/// address the browser navigates to (see [`browser_url`]).
+pub fn extract_features_as(url: &str, mode: ParseMode) -> Vec<f32> {
+    match mode {
+        ParseMode::Literal => extract_features(url),
+        ParseMode::Browser => extract_features(&browser_url(url)),
+    }
+}
+
+// ── UPI VPA parser ────────────────────────────────────────────────────────────
+
+/// Finds all UPI VPA patterns (prefix@handle) in a URL.
+/// Returns Vec<(prefix, handle)>.
+fn find_upi_vpa(text: &str) -> Vec<(String, String)> {
+    let mut results = Vec::new();
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'@' {
+            // scan backwards for prefix
+            let prefix_start = {
+                let mut s = i;
+                while s > 0 && (bytes[s-1].is_ascii_alphanumeric() ||
+                    bytes[s-1] == b'.' || bytes[s-1] == b'_' || bytes[s-1] == b'-') {
+                    s -= 1;
+                }
+                s
+            };
+            // scan forwards for handle
+            let handle_end = {
+                let mut e = i + 1;
+                while e < bytes.len() && bytes[e].is_ascii_alphabetic() {
+                    e += 1;
+                }
+                e
+            };
+            if prefix_start < i && handle_end > i + 1 {
+                // Ensure it's not a standard email address. Standard emails typically have a dot right after the handle (e.g., .com)
+                if handle_end < bytes.len() && bytes[handle_end] == b'.' {
+                    i = handle_end;
+                    continue;
+                }
+                let prefix = text[prefix_start..i].to_lowercase();
+                let handle = text[i+1..handle_end].to_lowercase();
+                if !prefix.is_empty() && handle.len() >= 2 {
+                    results.push((prefix, handle));
+                }
+            }
+            i = handle_end;
+        } else {
+            i += 1;
+        }
+    }
+    results
+}
+
+// ── DOM analysis exported functions ──────────────────────────────────────────
+
+/// Analyze a serialized form-action URL against the current page host.
+/// Returns risk score 0.0–1.0.
+pub fn analyze_form_action(form_action: &str, page_host: &str) -> f32 {
+    if form_action.is_empty() { return 0.0; }
+    let action_low = form_action.to_lowercase();
+    // Data URI form action — critical
+    if action_low.starts_with("data:") { return 1.0; }
+    // External domain form action
+    let action_parts = parse_url(form_action);
+    if !action_parts.host.is_empty() && !action_parts.host.contains(page_host)
+        && !page_host.contains(&action_parts.host) {
+        return 0.8;
+    }
+    0.0
+}
+
+/// Compute filename risk score for download interception.
+/// Returns 0.0–1.0 risk.
+pub fn score_filename(filename: &str) -> f32 { score_filename_with_brands(filename, &[]) }
+
+/// [`score_filename`] with `brands` counted next to the built-in list.
+pub(crate) fn score_filename_with_brands(filename: &str, brands: &[String]) -> f32 {
+    // Judge the name Windows will actually save, not the one the user sees.
+    let low = disposition::safe_filename(filename).to_lowercase();
+    let mut score = 0.0f32;
+    if disposition::has_bidi_override(filename) { score += 0.7; }
+    // Dangerous primary extension
+    let ext = low.rsplit('.').next().unwrap_or("");
+    if DANGEROUS_EXTS.contains(&ext) { score += 0.6; }
+    // Double extension
+    let parts: Vec<&str> = low.split('.').collect();
+    if parts.len() >= 3 {
+        let penult = parts[parts.len() - 2];
+        if DANGEROUS_EXTS.contains(&penult) { score += 0.4; }
+    }
+    // High filename entropy (gibberish / randomly named malware)
+    let entropy = shannon_entropy(filename);
+    if entropy > 4.5 { score += 0.2; }
+    // Brand + exe pattern
+    if DANGEROUS_EXTS.contains(&ext) && brands::any_brand(brands, |b| low.contains(b)) {
+        score += 0.3;
+    }
+    score.min(1.0)
+}
@@ -0,0 +1,231 @@
+// wasm-feature/crates/vigilant-core/src/mail.rs
+// Browser Vigilant — message-level verdicts for the Gmail / Outlook content scripts
+
+use crate::json::Json;
+use crate::mailauth::{auth_findings, auth_results_json, parse_authentication_results, parse_dmarc, parse_spf};
+use crate::psl::registrable_domain;
+use crate::score::{combine, url_risk};
+use crate::verdict::Verdict;
+use crate::{levenshtein, parse_url, BRANDS};
+
+/// Consumer mailbox providers — a brand never sends from these.
+pub(crate) const FREEMAIL: &[&str] = &[
+    "gmail.com","googlemail.com","outlook.com","hotmail.com","live.com","yahoo.com",
+    "aol.com","icloud.com","proton.me","protonmail.com","gmx.com","mail.ru",
+    "yandex.com","zoho.com","rediffmail.com",
+];
+
+/// Domain part of an address (`Name <a@b.com>` or bare `b.com`), lowercased.
+fn domain_of(addr: &str) -> String {
+    let addr = addr.trim();
+    let inner = match (addr.rfind('<'), addr.rfind('>')) {
+        (Some(s), Some(e)) if s < e => &addr[s + 1..e],
+        _ => addr,
+    };
+    inner.rsplit('@').next().unwrap_or("").trim().trim_end_matches('.').to_lowercase()
+}
+
+/// Domain of the first well-formed address written in `text`: a local part
+/// and a dotted domain of letter, digit and hyphen labels. `@` used as
+/// punctuation ("Support @ Team", "Bob (@bob)") is not an address.
+fn address_domain_in(text: &str) -> Option<String> {
+    text.split(|c: char| c.is_whitespace() || "<>()[]\"',;:".contains(c)).find_map(|tok| {
+        let (local, domain) = tok.rsplit_once('@')?;
+        let domain = domain.trim_end_matches('.').to_lowercase();
+        let labels_ok = domain.split('.').all(|l| !l.is_empty() && l.chars().all(|c| c.is_alphanumeric() || c == '-'));
+        (!local.is_empty() && !local.contains('@') && domain.contains('.') && labels_ok).then_some(domain)
+    })
+}
+
+fn brands_in(text: &str) -> Vec<&'static str> {
+    let low: String = text.to_lowercase().chars().filter(|c| c.is_alphanumeric()).collect();
+    // short brands ("sbi", "epic") collide with ordinary words in display names
+    BRANDS.iter().copied().filter(|b| b.len() >= 4 && low.contains(b)).collect()
+}
+
+struct Link {
+    href: String,
+    text: String,
+}
+
+fn links_of(msg: &Json) -> Vec<Link> {
+    msg.get("urls").and_then(Json::as_array).unwrap_or(&[]).iter().filter_map(|v| match v {
+        Json::Str(s) => Some(Link { href: s.clone(), text: String::new() }),
+        Json::Obj(_) => Some(Link { href: v.str_field("href").to_string(), text: v.str_field("text").to_string() }),
+        _ => None,
+    }).filter(|l| !l.href.is_empty()).collect()
+}
+
+fn score_link(link: &Link, sender_reg: &str) -> Json {
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut score = url_risk(&link.href);
+    let host = parse_url(&link.href).host.to_lowercase();
+    let reg = registrable_domain(&host);
+    let sender_label = sender_reg.split('.').next().unwrap_or("");
+    // Visible text that is itself a URL/domain but points elsewhere.
+    let text = link.text.trim().to_lowercase();
+    if !text.is_empty() && !text.contains(' ') && text.contains('.') {
+        let shown = parse_url(if text.contains("://") { text.clone() } else { format!("http://{}", text) }.as_str()).host;
+        if !shown.is_empty() && registrable_domain(&shown) != reg {
+            flags.push("text_href_mismatch");
+            score += 0.4;
+        }
+    }
+    if !sender_reg.is_empty() && reg != sender_reg && brands_in(&host).contains(&sender_label) {
+        flags.push("sender_brand_on_foreign_host");
+        score += 0.2;
+    }
+    Json::obj()
+        .with("url", link.href.as_str())
+        .with("host", host)
+        .with("score", score.min(1.0))
+        .with("flags", flags)
+}
+
+/// Score one message. `message_json`:
+/// `{ sender, display_name, reply_to?, urls: [href | {href, text}],
+///    authentication_results?, dmarc_record?, spf_record? }`
+/// (`sender_domain` may be passed instead of `sender`).
+pub fn mail_report(message_json: &str) -> Json {
+    let msg = match Json::parse(message_json) {
+        Ok(m) => m,
+        Err(e) => return Json::obj().with("error", e),
+    };
+    let sender = msg.str_field("sender");
+    let sender_domain = match msg.str_field("sender_domain") {
+        "" => domain_of(sender),
+        d => d.trim().to_lowercase(),
+    };
+    let sender_reg = registrable_domain(&sender_domain);
+    let display = msg.str_field("display_name");
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut score = 0.0f32;
+
+    // Display name claims a brand the sending domain doesn't belong to.
+    let claimed = brands_in(display);
+    let sender_label = sender_reg.split('.').next().unwrap_or("");
+    let brand_mismatch = claimed.iter().any(|b| sender_label != *b);
+    if brand_mismatch {
+        flags.push("display_name_brand_mismatch");
+        score += 0.35;
+        if FREEMAIL.contains(&sender_reg.as_str()) { flags.push("brand_from_freemail"); score += 0.25; }
+    }
+    if claimed.iter().any(|b| sender_label != *b && levenshtein(sender_label, b) <= 2) {
+        flags.push("sender_domain_lookalike");
+        score += 0.3;
+    }
+    // An address inside the display name that disagrees with the real sender.
+    if address_domain_in(display).is_some_and(|d| registrable_domain(&d) != sender_reg) {
+        flags.push("display_name_address_spoof");
+        score += 0.3;
+    }
+
+    let reply_to = msg.str_field("reply_to");
+    if !reply_to.is_empty() {
+        let reply_reg = registrable_domain(&domain_of(reply_to));
+        if !reply_reg.is_empty() && reply_reg != sender_reg {
+            flags.push("reply_to_divergence");
+            score += 0.2;
+            if FREEMAIL.contains(&reply_reg.as_str()) && !FREEMAIL.contains(&sender_reg.as_str()) {
+                flags.push("reply_to_freemail");
+                score += 0.15;
+            }
+        }
+    }
+
+    // Header authentication, when the content script can see raw headers.
+    let ar_header = msg.str_field("authentication_results");
+    let mut auth = Json::Null;
+    if !ar_header.is_empty() {
+        let ar = parse_authentication_results(ar_header);
+        for (flag, w) in auth_findings(&ar, &sender_domain) {
+            flags.push(flag);
+            score += w;
+        }
+        auth = auth_results_json(&ar);
+    }
+    let dmarc = msg.str_field("dmarc_record");
+    if !dmarc.is_empty() && !parse_dmarc(dmarc).valid {
+        flags.push("dmarc_record_invalid");
+    }
+    let spf = msg.str_field("spf_record");
+    if !spf.is_empty() {
+        let p = parse_spf(spf);
+        if p.valid && p.all == "+" { flags.push("spf_permissive"); score += 0.1; }
+    }
+
+    let links: Vec<Json> = links_of(&msg).iter().map(|l| score_link(l, &sender_reg)).collect();
+    let max_link = links.iter().filter_map(|l| l.get("score").and_then(Json::as_f64)).fold(0.0f64, f64::max) as f32;
+    let total = combine(score, max_link);
+
+    Json::obj()
+        .with("verdict", Verdict::from_score(total).as_str())
+        .with("score", total)
+        .with("sender_domain", sender_domain)
+        .with("brands_claimed", claimed)
+        .with("flags", flags)
+        .with("auth", auth)
+        .with("links", links)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(msg: &str) -> (Vec<String>, String) {
+        let r = mail_report(msg);
+        let flags = r.get("flags").and_then(Json::as_array).unwrap_or(&[]).iter().filter_map(Json::as_str).map(String::from).collect();
+        (flags, r.str_field("verdict").to_string())
+    }
+
+    #[test]
+    fn a_brand_sending_from_freemail_is_flagged() {
+        let (flags, _) = report(r#"{"sender":"paypal.support@gmail.com","display_name":"PayPal Service"}"#);
+        assert_eq!(flags, ["display_name_brand_mismatch", "brand_from_freemail"]);
+    }
+
+    #[test]
+    fn a_sender_domain_misspelling_the_claimed_brand_is_a_lookalike() {
+        let (flags, _) = report(r#"{"sender":"PayPal <service@paypa1.com>","display_name":"PayPal"}"#);
+        assert_eq!(flags, ["display_name_brand_mismatch", "sender_domain_lookalike"]);
+    }
+
+    #[test]
+    fn a_domain_merely_containing_the_brand_is_not_the_brand() {
+        for sender in ["service@notpaypal.com", "service@paypal-support.xyz"] {
+            let msg = Json::obj().with("sender", sender).with("display_name", "PayPal").to_string();
+            assert_eq!(report(&msg).0, ["display_name_brand_mismatch"], "{}", sender);
+        }
+        assert!(report(r#"{"sender":"service@paypal.com","display_name":"PayPal"}"#).0.is_empty());
+
+        let link = Link { href: "https://paypal.secure-login.tk/".into(), text: String::new() };
+        let flags = |sender_reg| score_link(&link, sender_reg).str_list("flags");
+        assert_eq!(flags("paypal.com"), ["sender_brand_on_foreign_host"]);
+        assert!(flags("notpaypal.com").is_empty());
+    }
+
+    #[test]
+    fn a_reply_to_on_another_domain_is_flagged() {
+        let (flags, _) = report(r#"{"sender":"billing@shop.example.com","display_name":"Billing","reply_to":"Billing <shop.billing@gmail.com>"}"#);
+        assert_eq!(flags, ["reply_to_divergence", "reply_to_freemail"]);
+    }
+
+    #[test]
+    fn an_address_in_the_display_name_must_parse_to_spoof() {
+        let (flags, _) = report(r#"{"sender":"x@mailer.example.net","display_name":"billing@acme-corp.com"}"#);
+        assert_eq!(flags, ["display_name_address_spoof"]);
+        for display in ["Support @ Team", "Bob (@bob)", "ops@localhost", "a@b..com"] {
+            let msg = Json::obj().with("sender", "x@mailer.example.net").with("display_name", display).to_string();
+            assert!(report(&msg).0.is_empty(), "{}", display);
+        }
+        let (flags, _) = report(r#"{"sender":"billing@acme-corp.com","display_name":"Billing (billing@acme-corp.com)"}"#);
+        assert!(flags.is_empty());
+    }
+
+    #[test]
+    fn a_clean_message_is_allowed() {
+        let (flags, verdict) = report(r#"{"sender":"noreply@github.com","display_name":"GitHub","urls":[{"href":"https://github.com/notifications","text":"github.com"}]}"#);
+        assert!(flags.is_empty());
+        assert_eq!(verdict, "allow");
+    }
+}
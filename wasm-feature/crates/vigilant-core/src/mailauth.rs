@@ -0,0 +1,267 @@
+// wasm-feature/crates/vigilant-core/src/mailauth.rs
+// Browser Vigilant — Authentication-Results (RFC 8601), DMARC and SPF record parsers
+
+use crate::json::Json;
+use crate::psl::registrable_domain;
+
+/// One `method=result` clause with its `ptype.property=value` pairs.
+pub struct AuthClause {
+    pub method: String,
+    pub result: String,
+    pub props:  Vec<(String, String)>,
+    /// Parenthesised comment text (DMARC puts `p=REJECT` here).
+    pub comment: String,
+}
+
+impl AuthClause {
+    pub fn prop(&self, key: &str) -> Option<&str> {
+        self.props.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+pub struct AuthResults {
+    pub authserv_id: String,
+    pub clauses: Vec<AuthClause>,
+}
+
+impl AuthResults {
+    fn first(&self, method: &str) -> Option<&AuthClause> {
+        self.clauses.iter().find(|c| c.method == method)
+    }
+
+    pub fn result(&self, method: &str) -> &str {
+        self.first(method).map(|c| c.result.as_str()).unwrap_or("none")
+    }
+
+    pub fn header_from(&self) -> String {
+        self.first("dmarc").and_then(|c| c.prop("header.from")).unwrap_or("").to_lowercase()
+    }
+}
+
+/// Split off parenthesised comments (CFWS), returning (text, comments).
+fn strip_comments(s: &str) -> (String, String) {
+    let (mut text, mut comment, mut depth) = (String::new(), String::new(), 0usize);
+    for c in s.chars() {
+        match c {
+            '(' => { depth += 1; if depth == 1 { comment.push(' '); continue; } }
+            ')' if depth > 0 => { depth -= 1; if depth == 0 { continue; } }
+            _ => {}
+        }
+        if depth > 0 { comment.push(c) } else { text.push(c) }
+    }
+    (text, comment.trim().to_string())
+}
+
+/// Split `s` at each `;` outside a parenthesised comment.
+fn split_clauses(s: &str) -> Vec<&str> {
+    let (mut out, mut start, mut depth) = (Vec::new(), 0, 0usize);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => { out.push(&s[start..i]); start = i + 1; }
+            _ => {}
+        }
+    }
+    out.push(&s[start..]);
+    out
+}
+
+const HEADER_NAME: &str = "Authentication-Results:";
+
+pub fn parse_authentication_results(header: &str) -> AuthResults {
+    let header = header.trim();
+    // header names are case-insensitive (RFC 5322)
+    let header = match header.get(..HEADER_NAME.len()) {
+        Some(name) if name.eq_ignore_ascii_case(HEADER_NAME) => &header[HEADER_NAME.len()..],
+        _ => header,
+    };
+    let mut parts = split_clauses(header).into_iter();
+    let authserv_id = strip_comments(parts.next().unwrap_or("")).0
+        .split_whitespace().next().unwrap_or("").to_lowercase();
+    let clauses = parts.filter_map(|raw| {
+        let (text, comment) = strip_comments(raw);
+        let mut toks = text.split_whitespace();
+        let (method, result) = toks.next()?.split_once('=')?;
+        let props = toks.filter_map(|t| t.split_once('='))
+            .map(|(k, v)| (k.to_lowercase(), v.trim_matches('"').to_string())).collect();
+        Some(AuthClause { method: method.to_lowercase(), result: result.to_lowercase(), props, comment })
+    }).collect();
+    AuthResults { authserv_id, clauses }
+}
+
+/// `tag=value` list shared by DMARC (`;`-separated).
+fn tags(record: &str) -> Vec<(String, String)> {
+    record.split(';').filter_map(|t| t.split_once('='))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string())).collect()
+}
+
+pub struct DmarcPolicy {
+    pub valid: bool,
+    pub policy: String,
+    pub subdomain_policy: String,
+    pub pct: u32,
+    pub strict_dkim: bool,
+    pub strict_spf: bool,
+}
+
+pub fn parse_dmarc(record: &str) -> DmarcPolicy {
+    let t = tags(record);
+    let tag = |k: &str| t.iter().find(|(tk, _)| tk == k).map(|(_, v)| v.to_lowercase());
+    let valid = tag("v").is_some_and(|v| v.eq_ignore_ascii_case("dmarc1")) && tag("p").is_some();
+    let policy = tag("p").unwrap_or_else(|| "none".into());
+    DmarcPolicy {
+        valid,
+        subdomain_policy: tag("sp").unwrap_or_else(|| policy.clone()),
+        policy,
+        pct: tag("pct").and_then(|p| p.parse().ok()).unwrap_or(100).min(100),
+        strict_dkim: tag("adkim").as_deref() == Some("s"),
+        strict_spf: tag("aspf").as_deref() == Some("s"),
+    }
+}
+
+pub struct SpfPolicy {
+    pub valid: bool,
+    /// Qualifier on the terminal `all`: `+`, `-`, `~`, `?`, or empty when absent.
+    pub all: String,
+    pub includes: Vec<String>,
+    pub mechanisms: usize,
+}
+
+pub fn parse_spf(record: &str) -> SpfPolicy {
+    let mut toks = record.split_whitespace();
+    let valid = toks.next().is_some_and(|v| v.eq_ignore_ascii_case("v=spf1"));
+    let (mut all, mut includes, mut mechanisms) = (String::new(), Vec::new(), 0usize);
+    for t in toks {
+        let t = t.to_lowercase();
+        let (q, mech) = match t.chars().next() {
+            Some(c @ ('+' | '-' | '~' | '?')) => (c.to_string(), t[1..].to_string()),
+            _ => ("+".to_string(), t.clone()),
+        };
+        if mech == "all" { all = q; continue; }
+        if let Some(inc) = mech.strip_prefix("include:") { includes.push(inc.to_string()); }
+        if !mech.starts_with("redirect=") && !mech.starts_with("exp=") { mechanisms += 1; }
+    }
+    SpfPolicy { valid, all, includes, mechanisms }
+}
+
+/// Authentication findings folded into the mail verdict: (flag, weight).
+pub fn auth_findings(ar: &AuthResults, sender_domain: &str) -> Vec<(&'static str, f32)> {
+    let mut out = Vec::new();
+    // only an explicit clause: `result` reads a missing one as "none" too
+    match ar.first("dmarc").map(|c| c.result.as_str()) {
+        Some("fail") => out.push(("dmarc_fail", 0.45)),
+        Some("none") => out.push(("dmarc_none", 0.05)),
+        _ => {}
+    }
+    match ar.result("spf") {
+        "fail" => out.push(("spf_fail", 0.25)),
+        "softfail" => out.push(("spf_softfail", 0.1)),
+        _ => {}
+    }
+    let dkim: Vec<&AuthClause> = ar.clauses.iter().filter(|c| c.method == "dkim").collect();
+    // `dkim=none` is an unsigned message, not a failed signature
+    if dkim.iter().all(|c| c.result != "pass") && dkim.iter().any(|c| matches!(c.result.as_str(), "fail" | "permerror")) {
+        out.push(("dkim_fail", 0.2));
+    }
+    // A passing signature from some unrelated domain proves nothing about the sender.
+    let from = match ar.header_from() { f if f.is_empty() => sender_domain.to_lowercase(), f => f };
+    if !from.is_empty() {
+        let from_reg = registrable_domain(&from);
+        let signer = |c: &&AuthClause| c.prop("header.d").or(c.prop("header.i"))
+            .map(|d| registrable_domain(d.trim_start_matches('@').rsplit('@').next().unwrap_or("")));
+        let passing: Vec<String> = dkim.iter().filter(|c| c.result == "pass").filter_map(signer).collect();
+        if !passing.is_empty() && !passing.contains(&from_reg) {
+            out.push(("dkim_unaligned", 0.15));
+        }
+    }
+    if ar.first("dmarc").is_some_and(|c| c.comment.to_lowercase().contains("p=none")) {
+        out.push(("dmarc_policy_none", 0.05));
+    }
+    out
+}
+
+pub fn auth_results_json(ar: &AuthResults) -> Json {
+    let clauses: Vec<Json> = ar.clauses.iter().map(|c| {
+        let props = Json::Obj(c.props.iter().map(|(k, v)| (k.clone(), Json::from(v))).collect());
+        Json::obj().with("method", &c.method).with("result", &c.result)
+            .with("props", props).with("comment", &c.comment)
+    }).collect();
+    Json::obj()
+        .with("authserv_id", &ar.authserv_id)
+        .with("spf", ar.result("spf"))
+        .with("dkim", ar.result("dkim"))
+        .with("dmarc", ar.result("dmarc"))
+        .with("header_from", ar.header_from())
+        .with("clauses", clauses)
+}
+
+pub fn dmarc_json(p: &DmarcPolicy) -> Json {
+    Json::obj()
+        .with("valid", p.valid)
+        .with("policy", &p.policy)
+        .with("subdomain_policy", &p.subdomain_policy)
+        .with("pct", p.pct)
+        .with("strict_dkim", p.strict_dkim)
+        .with("strict_spf", p.strict_spf)
+        .with("enforcing", p.valid && p.policy != "none" && p.pct > 0)
+}
+
+pub fn spf_json(p: &SpfPolicy) -> Json {
+    Json::obj()
+        .with("valid", p.valid)
+        .with("all", &p.all)
+        .with("includes", p.includes.clone())
+        .with("mechanisms", p.mechanisms)
+        .with("permissive", p.all == "+" || p.all == "?" || p.all.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(header: &str) -> Vec<&'static str> {
+        auth_findings(&parse_authentication_results(header), "example.com").into_iter().map(|(f, _)| f).collect()
+    }
+
+    #[test]
+    fn semicolons_inside_comments_do_not_split_clauses() {
+        let ar = parse_authentication_results(
+            "mx.example.net; dkim=pass (good signature; key 2048 bits) header.d=example.com; spf=fail (mailfrom; not permitted) smtp.mailfrom=example.com");
+        assert_eq!(ar.authserv_id, "mx.example.net");
+        assert_eq!(ar.clauses.len(), 2);
+        assert_eq!(ar.clauses[0].comment, "good signature; key 2048 bits");
+        assert_eq!(ar.clauses[0].prop("header.d"), Some("example.com"));
+        assert_eq!(ar.result("spf"), "fail");
+        assert_eq!(flags("mx.example.net; dmarc=pass (p=none; dis=none) header.from=example.com"), ["dmarc_policy_none"]);
+    }
+
+    #[test]
+    fn a_missing_dmarc_clause_is_not_dmarc_none() {
+        assert_eq!(flags("mx.example.net; spf=pass smtp.mailfrom=example.com"), Vec::<&str>::new());
+        assert_eq!(flags("mx.example.net; dmarc=none header.from=example.com"), ["dmarc_none"]);
+        assert_eq!(flags("mx.example.net; dmarc=fail header.from=example.com"), ["dmarc_fail"]);
+        assert_eq!(flags(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn unsigned_mail_is_not_a_dkim_failure() {
+        assert_eq!(flags("mx.example.net; dkim=none"), Vec::<&str>::new());
+        assert_eq!(flags("mx.example.net; dkim=neutral header.d=example.com"), Vec::<&str>::new());
+        assert_eq!(flags("mx.example.net; dkim=fail header.d=example.com"), ["dkim_fail"]);
+        assert_eq!(flags("mx.example.net; dkim=permerror header.d=example.com; dkim=none"), ["dkim_fail"]);
+        assert_eq!(flags("mx.example.net; dkim=fail header.d=other.net; dkim=pass header.d=example.com"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn the_header_name_matches_in_any_case() {
+        for h in ["Authentication-Results: mx.example.net; spf=fail", "authentication-results: mx.example.net; spf=fail", "AUTHENTICATION-RESULTS:mx.example.net; spf=fail"] {
+            let ar = parse_authentication_results(h);
+            assert_eq!(ar.authserv_id, "mx.example.net", "{h}");
+            assert_eq!(ar.result("spf"), "fail", "{h}");
+        }
+        // a short or non-ASCII start is left alone
+        assert_eq!(parse_authentication_results("mx; spf=pass").authserv_id, "mx");
+        assert_eq!(parse_authentication_results("Äuthentication-Results: mx; spf=pass").authserv_id, "äuthentication-results:");
+    }
+}
@@ -0,0 +1,185 @@
+// wasm-feature/crates/vigilant-core/src/malformed.rs
+// Browser Vigilant — URLs only a browser reads right. The URL standard is
+// forgiving for http(s): it drops tabs and newlines anywhere, strips
+// leading and trailing spaces, reads `\` as `/`, and takes any run of
+// slashes after `https:` — or none — as the start of the host. A check
+// that splits at `://` and the first `/` disagrees with the address bar
+// on `https://bank.example\@evil.tk/` (the browser goes to `bank.example`),
+// `https:/\evil.tk` and a scheme broken by a newline, and lures are built
+// around the disagreement. `normalize_url` reads the browser's form; the
+// model vector keeps reading the string as written, which is what
+// `features.py` trains on, and the features here mark which trick the raw
+// text used. `ParseMode::Browser` takes the model features from the
+// address the browser ends up at instead, `file:` paths typed as `C:\` or
+// `\\server\share` and default ports included.
+
+use crate::parse_url;
+
+/// Values returned by [`malformed_features`]: multiple_at,
+/// stripped_whitespace, backslash_separator, slash_confusion,
+/// host_confusion.
+pub const MALFORMED_FEATURES: usize = 5;
+
+/// How a URL string is read before features are taken from it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// The string as written, which is what `features.py` trains on: tabs,
+    /// newlines and `\` are kept, and the host is read after `://`.
+    #[default]
+    Literal,
+    /// The address the browser navigates to, as [`browser_url`] spells it:
+    /// ignored characters stripped, `\` read as `/`, default ports dropped
+    /// and Windows paths turned into `file:` URLs.
+    Browser,
+}
+
+impl ParseMode {
+    pub fn parse(name: &str) -> Option<ParseMode> {
+        match name { "literal" => Some(ParseMode::Literal), "browser" => Some(ParseMode::Browser), _ => None }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self { ParseMode::Literal => "literal", ParseMode::Browser => "browser" }
+    }
+}
+
+/// Ports a browser leaves out of the address for each lenient scheme.
+const DEFAULT_PORTS: &[(&str, u32)] = &[("http", 80), ("https", 443), ("ws", 80), ("wss", 443), ("ftp", 21)];
+
+/// Schemes whose URLs browsers parse leniently. `file:` is special too but
+/// has hosts of its own kind, and is left as written.
+const LENIENT_SCHEMES: &[&str] = &["http", "https", "ws", "wss", "ftp"];
+
+/// `url` without what browsers strip: leading and trailing controls and
+/// spaces, and tabs and newlines anywhere.
+fn strip_ignored(url: &str) -> String {
+    url.trim_matches(|c: char| c <= ' ').chars().filter(|c| !matches!(c, '\t' | '\n' | '\r')).collect()
+}
+
+/// Scheme and the text after its colon, for a lenient scheme only.
+fn lenient(url: &str) -> Option<(&str, &str)> {
+    url.split_once(':').filter(|(s, _)| LENIENT_SCHEMES.contains(&s.to_ascii_lowercase().as_str()))
+}
+
+/// `url` as a browser reads it, spelled the usual way: ignored characters
+/// stripped and, for http(s), ws(s) and ftp, exactly `//` after the
+/// scheme and `/` for every `\` before the query. Anything else is only
+/// stripped.
+pub(crate) fn browser_form(url: &str) -> String {
+    let s = strip_ignored(url);
+    let Some((scheme, rest)) = lenient(&s) else { return s };
+    let rest = rest.trim_start_matches(['/', '\\']);
+    let (authority, tail) = rest.split_at(rest.find(['/', '\\', '?', '#']).unwrap_or(rest.len()));
+    let (path, after) = tail.split_at(tail.find(['?', '#']).unwrap_or(tail.len()));
+    format!("{scheme}://{authority}{}{after}", path.replace('\\', "/"))
+}
+
+/// The address a browser navigates to for `url`, spelled out: the
+/// [`browser_form`], with scheme and host lowercased and a default or
+/// empty port dropped. A Windows path (`C:\Users\a.html`) and a UNC path
+/// (`\\server\share\a.html`) become the `file:` URLs Windows browsers
+/// open for them, and `file:` URLs take `/` for `\` as well.
+pub fn browser_url(url: &str) -> String {
+    let s = strip_ignored(url);
+    let drive = s.as_bytes().get(..3).is_some_and(|b| b[0].is_ascii_alphabetic() && b[1] == b':' && matches!(b[2], b'\\' | b'/'));
+    if drive { return format!("file:///{}", s.replace('\\', "/")); }
+    if let Some(unc) = s.strip_prefix("\\\\") { return format!("file://{}", unc.replace('\\', "/")); }
+    if s.get(..5).is_some_and(|p| p.eq_ignore_ascii_case("file:")) {
+        let (path, after) = s.split_at(s.find(['?', '#']).unwrap_or(s.len()));
+        return format!("file:{}{after}", path[5..].replace('\\', "/"));
+    }
+    let form = browser_form(&s);
+    let Some((scheme, rest)) = lenient(&form) else { return form };
+    let scheme = scheme.to_ascii_lowercase();
+    let rest = &rest[2..];
+    let (authority, tail) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+    let (userinfo, hostport) = match authority.rsplit_once('@') { Some((u, h)) => (format!("{u}@"), h), None => (String::new(), authority) };
+    // a colon inside an IPv6 literal is not a port separator
+    let (host, port) = match hostport.rsplit_once(':').filter(|(_, p)| !p.contains(']')) {
+        Some((h, p)) if p.bytes().all(|b| b.is_ascii_digit()) => (h, p),
+        _ => (hostport, ""),
+    };
+    let default = DEFAULT_PORTS.iter().find(|(s, _)| *s == scheme).map(|(_, p)| *p);
+    let port = match port.parse::<u32>() {
+        Ok(n) if Some(n) != default => format!(":{n}"),
+        _ => String::new(),
+    };
+    let slash = if tail.starts_with('/') { "" } else { "/" };
+    format!("{scheme}://{userinfo}{}{port}{slash}{tail}", host.to_lowercase())
+}
+
+/// Host a parser that splits at `://`, the first `/` and the last `@`
+/// reads in the raw text, lowercased.
+fn naive_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    let netloc = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = netloc.rsplit_once('@').map_or(netloc, |(_, h)| h);
+    let host = match host.rsplit_once(':') {
+        Some((h, port)) if port.bytes().all(|b| b.is_ascii_digit()) => h,
+        _ => host,
+    };
+    host.to_lowercase()
+}
+
+/// `[multiple_at, stripped_whitespace, backslash_separator,
+/// slash_confusion, host_confusion]`: more than one `@` before the host;
+/// tabs, newlines or edge spaces the browser drops; a `\` it reads as a
+/// path separator; anything but `//` after an http(s) scheme
+/// (`https:/\`, `https:evil.tk`, `https:///`); and whether the host a
+/// naive `://` split reads differs from the one the browser goes to.
+pub fn malformed_features(url: &str) -> [f32; MALFORMED_FEATURES] {
+    let clean = strip_ignored(url);
+    let rest = lenient(&clean).map(|(_, r)| r);
+    let before_query = |r: &str| r.split(['?', '#']).next().unwrap_or("").to_string();
+    let browser = browser_form(url);
+    let authority = browser.split_once("://").map_or("", |(_, r)| r.split(['/', '?', '#']).next().unwrap_or(""));
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    [
+        b(authority.matches('@').count() >= 2),
+        b(clean != url),
+        b(rest.is_some_and(|r| before_query(r).contains('\\'))),
+        b(rest.is_some_and(|r| !r.starts_with("//") || r[2..].starts_with(['/', '\\']))),
+        b(naive_host(url) != parse_url(&browser).host.to_lowercase()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract_features_as;
+
+    #[test]
+    fn literal_mode_reads_the_string_as_written() {
+        for url in ["https:/\\evil.tk/paypal", "https://bank.example\\@evil.tk/login", "http://a\tb.com/x"] {
+            assert_eq!(extract_features_as(url, ParseMode::Literal), crate::extract_features(url), "{url}");
+            assert_ne!(extract_features_as(url, ParseMode::Literal), extract_features_as(url, ParseMode::Browser), "{url}");
+        }
+        assert_eq!(parse_url("https://bank.example\\@evil.tk/login").host, "evil.tk");
+        assert_eq!(parse_url(&browser_form("https://bank.example\\@evil.tk/login")).host, "bank.example");
+    }
+
+    #[test]
+    fn browser_url_spells_the_navigated_address() {
+        assert_eq!(browser_url(" HTTPS:/\\Evil.TK:443\\a\tb?q"), "https://evil.tk/ab?q");
+        assert_eq!(browser_url("C:\\Users\\a.html"), "file:///C:/Users/a.html");
+        assert_eq!(browser_url("\\\\server\\share\\a.html"), "file://server/share/a.html");
+    }
+
+    #[test]
+    fn case_folding_that_grows_the_string_keeps_offsets() {
+        // 'İ' lowercases to two chars, three bytes against its two
+        let p = parse_url("İİ://x");
+        assert_eq!((p.scheme.as_str(), p.host.as_str()), ("i̇i̇", "x"));
+        for mode in [ParseMode::Literal, ParseMode::Browser] {
+            assert_eq!(extract_features_as("İİ://x/İ?q#f", mode).len(), crate::extract_features("https://x/").len());
+        }
+        assert_eq!(malformed_features("İİ://x"), malformed_features("ii://x"));
+    }
+
+    #[test]
+    fn features_mark_the_trick_used() {
+        assert_eq!(malformed_features("https://bank.example\\@evil.tk/"), [0.0, 0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(malformed_features("https://a@b@evil.tk/"), [1.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(malformed_features("https://example.com/"), [0.0; MALFORMED_FEATURES]);
+    }
+}
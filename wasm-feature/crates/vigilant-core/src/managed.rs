@@ -0,0 +1,98 @@
+// wasm-feature/crates/vigilant-core/src/managed.rs
+// Browser Vigilant — admin-managed policy (chrome.storage.managed)
+// Applied inside Context so no caller can skip it: forced thresholds, forced
+// download-category blocks, admin host rules and locked-down user overrides.
+
+use crate::json::Json;
+use crate::overrides::{match_keys, OverrideRule};
+use crate::policy::is_category;
+use crate::types::{err, FromJson, Fields, SchemaError};
+use crate::verdict::Verdict;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManagedPolicy {
+    pub schema_version:         u32,
+    /// Download categories (`download_context_report` names) that always block.
+    pub force_block_categories: Vec<String>,
+    /// (warn, block) score thresholds replacing the context's profile.
+    pub thresholds:             Option<(f32, f32)>,
+    /// Domains (and subdomains) where user overrides are ignored, e.g. banks.
+    pub locked_domains:         Vec<String>,
+    /// Ignore user overrides everywhere.
+    pub disable_user_overrides: bool,
+    /// Non-allow verdicts must be sent to the organisation's reporting endpoint.
+    pub require_reporting:      bool,
+    /// Host rules in [`OverrideRule`] form, stored canonical.
+    pub block_hosts:            Vec<String>,
+    pub allow_hosts:            Vec<String>,
+}
+
+fn norm(host: &str) -> String { host.trim().trim_end_matches('.').to_lowercase() }
+
+/// `host` equals `domain` or is one of its subdomains.
+fn within(host: &str, domain: &str) -> bool {
+    host == domain || host.strip_suffix(domain).is_some_and(|rest| rest.ends_with('.'))
+}
+
+impl FromJson for ManagedPolicy {
+    fn from_json(v: &Json, path: &str) -> Result<ManagedPolicy, SchemaError> {
+        let f = Fields::new(v, path)?;
+        let force_block_categories = f.list("force_block_categories", |x, p| {
+            let c = x.as_str().ok_or_else(|| err(p, "expected string"))?;
+            if is_category(c) { Ok(c.to_string()) } else { Err(err(p, &format!("unknown download category \"{}\"", c))) }
+        })?;
+        let thresholds = match (f.opt_num("warn_threshold")?, f.opt_num("block_threshold")?) {
+            (None, None) => None,
+            (w, b) => {
+                let (w, b) = (w.unwrap_or(0.4) as f32, b.unwrap_or(0.7) as f32);
+                if !(0.0..=1.0).contains(&w) || !(0.0..=1.0).contains(&b) || w > b {
+                    return Err(err(&f.at("warn_threshold"), "thresholds must satisfy 0 <= warn <= block <= 1"));
+                }
+                Some((w, b))
+            }
+        };
+        let hosts = |key: &str| f.strings(key).map(|v| v.iter().map(|h| norm(h)).filter(|h| !h.is_empty()).collect());
+        let rules = |key: &str| f.list(key, |x, p| {
+            let r = x.as_str().ok_or_else(|| err(p, "expected string"))?;
+            OverrideRule::parse(r).map(|r| r.key()).map_err(|e| err(p, &e.message))
+        });
+        Ok(ManagedPolicy {
+            schema_version: f.schema_version()?,
+            force_block_categories,
+            thresholds,
+            locked_domains: hosts("locked_domains")?,
+            disable_user_overrides: f.bool_or("disable_user_overrides", false)?,
+            require_reporting: f.bool_or("require_reporting", false)?,
+            block_hosts: rules("block_hosts")?,
+            allow_hosts: rules("allow_hosts")?,
+        })
+    }
+}
+
+impl ManagedPolicy {
+    /// Verdict for `score`, from the forced thresholds when set.
+    pub fn verdict(&self, score: f32, fallback: Verdict) -> Verdict {
+        match self.thresholds {
+            Some((w, b)) => if score >= b { Verdict::Block } else if score >= w { Verdict::Warn } else { Verdict::Allow },
+            None => fallback,
+        }
+    }
+
+    /// Whether users may override `rule`, judged by the host it names.
+    pub fn user_override_allowed(&self, rule: &str) -> bool {
+        let h = OverrideRule::parse(rule).map_or_else(|_| norm(rule), |r| r.host);
+        !self.disable_user_overrides && !self.locked_domains.iter().any(|d| within(&h, d))
+    }
+
+    /// Admin host rule for `path` at `host`; a block entry anywhere beats
+    /// an allow entry, however specific.
+    pub fn host_rule(&self, host: &str, path: &str) -> Option<Verdict> {
+        let keys: Vec<String> = match_keys(&norm(host), path).into_iter().flat_map(|(_, k)| k).collect();
+        if self.block_hosts.iter().any(|r| keys.contains(r)) { return Some(Verdict::Block); }
+        self.allow_hosts.iter().any(|r| keys.contains(r)).then_some(Verdict::Allow)
+    }
+
+    pub fn category_blocked(&self, category: &str) -> bool { self.force_block_categories.iter().any(|c| c == category) }
+
+    pub fn must_report(&self, verdict: Verdict) -> bool { self.require_reporting && verdict != Verdict::Allow }
+}
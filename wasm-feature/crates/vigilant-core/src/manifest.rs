@@ -0,0 +1,64 @@
+// wasm-feature/crates/vigilant-core/src/manifest.rs
+// Browser Vigilant — what data a release ships with, for support and audits.
+// `capabilities_report` lists each table's entry count and content version;
+// this adds where each list came from and when the build was made, so a
+// report can name the exact suffix snapshot or brand list behind a verdict.
+
+use crate::audit::ENGINE_VERSION;
+use crate::capabilities::tables;
+use crate::json::Json;
+use crate::model::FEATURE_VERSION;
+use crate::psl::PSL_SNAPSHOT;
+
+/// Where each embedded table's entries come from. Tables not listed are
+/// curated by hand from observed lures.
+pub(crate) const SOURCES: &[(&str, &str)] = &[
+    ("brands", "curated: most-impersonated brands in reported phishing"),
+    ("suspicious_tlds", "curated: TLDs over-represented in phishing feeds"),
+    ("short_services", "curated: public URL shorteners"),
+    ("public_suffixes", "publicsuffix.org: multi-label ICANN and private suffixes"),
+    ("suffix_wildcards", "publicsuffix.org: wildcard rules"),
+    ("suffix_exceptions", "publicsuffix.org: exception rules"),
+    ("freemail", "curated: free mail providers"),
+    ("identity_providers", "curated: OAuth identity provider endpoints"),
+    ("app_stores", "curated: official app store hosts"),
+    ("app_packages", "curated: Android package names of impersonated apps"),
+    ("kit_dirs", "curated: CMS directories phishing kits are dropped into"),
+    ("kit_files", "curated: file names of off-the-shelf phishing kits"),
+    ("popular_sites", "curated: popularity-ranked sites, most visited first"),
+    ("messaging_webhooks", "curated: chat webhook endpoints used for exfiltration"),
+    ("confusables", "unicode.org: confusables.txt (subset)"),
+    ("premium_prefixes", "curated: premium-rate numbering ranges"),
+    ("high_risk_prefixes", "curated: prefixes in revenue-share fraud reports"),
+    ("messages", "vigilant: explanation templates"),
+    ("severities", "vigilant: flag severities"),
+    ("signal_overlaps", "vigilant: overlapping signal groups"),
+];
+
+/// Source recorded for tables [`SOURCES`] doesn't name.
+const DEFAULT_SOURCE: &str = "curated";
+
+/// Seconds since the Unix epoch the build was made at, when the build set
+/// `SOURCE_DATE_EPOCH` (as reproducible builds do).
+fn built_at() -> Option<u64> { option_env!("SOURCE_DATE_EPOCH").and_then(|s| s.trim().parse().ok()) }
+
+/// Returns `{ engine_version, feature_version, built_at, psl_snapshot,
+/// tables: [{ name, entries, version, source }], total_entries }`:
+/// every embedded table with its entry count, content version (as in
+/// `capabilities_report`) and source. `built_at` is null unless the build
+/// set `SOURCE_DATE_EPOCH`.
+pub fn data_manifest() -> Json {
+    let tables = tables();
+    let total: usize = tables.iter().map(|t| t.entries).sum();
+    let entries: Vec<Json> = tables.iter().map(|t| {
+        let source = SOURCES.iter().find(|(n, _)| *n == t.name).map_or(DEFAULT_SOURCE, |(_, s)| *s);
+        Json::obj().with("name", t.name).with("entries", t.entries).with("version", t.digest()).with("source", source)
+    }).collect();
+    Json::obj()
+        .with("engine_version", ENGINE_VERSION)
+        .with("feature_version", FEATURE_VERSION)
+        .with("built_at", built_at())
+        .with("psl_snapshot", PSL_SNAPSHOT)
+        .with("tables", entries)
+        .with("total_entries", total)
+}
@@ -0,0 +1,46 @@
+// wasm-feature/crates/vigilant-core/src/mask.rs
+// Browser Vigilant — process-wide feature mask for ablation runs. Masked
+// features read as zero wherever a vector is built, so the heuristic score,
+// the composite analyzers and exported vectors all see the same ablated
+// input, and a field experiment measures what a feature group really adds.
+
+use std::sync::{PoisonError, RwLock};
+use crate::{MODEL_FEATURES, TIME_FEATURES};
+
+/// Bit `i` set zeroes feature `i` of the model-plus-time vector.
+static FEATURE_MASK: RwLock<u128> = RwLock::new(0);
+
+/// Highest maskable index plus one: the model features, then the time features.
+pub const MASKABLE_FEATURES: usize = MODEL_FEATURES + TIME_FEATURES;
+
+const _: () = assert!(MASKABLE_FEATURES <= 128);
+
+/// Zero `indices` from now on for every analyzer in the process, replacing
+/// the previous mask; an empty list turns masking off. Fails without
+/// changing anything if an index is out of range.
+pub fn set_feature_mask(indices: &[usize]) -> Result<(), String> {
+    let mut bits = 0u128;
+    for &i in indices {
+        if i >= MASKABLE_FEATURES { return Err(format!("feature index {} out of range (0–{})", i, MASKABLE_FEATURES - 1)); }
+        bits |= 1 << i;
+    }
+    *FEATURE_MASK.write().unwrap_or_else(PoisonError::into_inner) = bits;
+    Ok(())
+}
+
+/// Indices currently masked, ascending.
+pub fn feature_mask() -> Vec<usize> {
+    let bits = bits();
+    (0..MASKABLE_FEATURES).filter(|i| bits & (1 << i) != 0).collect()
+}
+
+pub(crate) fn bits() -> u128 { *FEATURE_MASK.read().unwrap_or_else(PoisonError::into_inner) }
+
+/// Zero the masked entries of `f`, a vector starting at feature 0.
+pub(crate) fn apply(f: &mut [f32]) {
+    let bits = bits();
+    if bits == 0 { return; }
+    for (i, x) in f.iter_mut().enumerate().take(MASKABLE_FEATURES) {
+        if bits & (1 << i) != 0 { *x = 0.0; }
+    }
+}
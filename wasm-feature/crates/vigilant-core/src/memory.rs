@@ -0,0 +1,44 @@
+// wasm-feature/crates/vigilant-core/src/memory.rs
+// Browser Vigilant — memory introspection for the debug panel and low-memory trims
+// Sizes are estimates: string bytes plus the slice/Vec headers that hold them.
+
+use std::mem::size_of;
+use crate::analyzer::Analyzer;
+use crate::capabilities::tables;
+use crate::json::Json;
+use crate::score;
+use crate::state::State;
+
+fn strs(list: &[&str]) -> usize { list.iter().map(|s| s.len() + size_of::<&str>()).sum() }
+
+fn table(name: &str, entries: usize, bytes: usize) -> (String, Json) {
+    (name.to_string(), Json::obj().with("entries", entries).with("bytes", bytes))
+}
+
+/// Built-in lists compiled into the module, by name.
+fn dictionaries() -> Vec<(String, Json)> {
+    tables().iter().map(|t| table(t.name, t.entries, strs(&t.items))).collect()
+}
+
+/// Returns `{ dictionaries: { <name>: { entries, bytes } }, dictionary_bytes,
+/// models: { url_weights: { entries, bytes } }, model_bytes, analyzer_bytes,
+/// caches: { feature_cache, memo, reputation, sessions, first_seen, templates }, cache_bytes, total_bytes }`.
+pub fn memory_report(analyzer: &Analyzer, state: &State) -> Json {
+    let dicts = dictionaries();
+    let bytes_of = |t: &[(String, Json)]| t.iter().filter_map(|(_, v)| v.get("bytes").and_then(Json::as_f64)).sum::<f64>() as usize;
+    let dictionary_bytes = bytes_of(&dicts);
+    let models = vec![table("url_weights", score::WEIGHTS.len(), std::mem::size_of_val(score::WEIGHTS))];
+    let model_bytes = bytes_of(&models);
+    let analyzer_bytes = analyzer.heap_bytes();
+    let cache_bytes = state.heap_bytes();
+
+    Json::obj()
+        .with("dictionaries", Json::Obj(dicts))
+        .with("dictionary_bytes", dictionary_bytes)
+        .with("models", Json::Obj(models))
+        .with("model_bytes", model_bytes)
+        .with("analyzer_bytes", analyzer_bytes)
+        .with("caches", state.memory())
+        .with("cache_bytes", cache_bytes)
+        .with("total_bytes", dictionary_bytes + model_bytes + analyzer_bytes + cache_bytes)
+}
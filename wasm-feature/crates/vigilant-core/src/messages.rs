@@ -0,0 +1,1320 @@
+// wasm-feature/crates/vigilant-core/src/messages.rs
+// Browser Vigilant — human-readable reasons. Every verdict, flag and finding
+// tag maps to a message key with named parameter slots, and each key has an
+// embedded template per locale, so the popup renders explanations in the
+// user's language from the report alone.
+
+use crate::json::Json;
+
+/// Locale every key has a template in, and the fallback for the rest.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Message keys and their parameter slots. A slot names a field of the
+/// report (or of its `details`), or of the finding for `finding.*` keys.
+pub(crate) const CATALOG: &[(&str, &[&str])] = &[
+    ("verdict.allow", &[]),
+    ("verdict.warn", &[]),
+    ("verdict.block", &[]),
+    ("flag.unknown", &["flag"]),
+    ("finding.exfiltration.messaging_webhook", &["service", "endpoint"]),
+    ("finding.evasion.domain_fronting", &["sni", "host"]),
+    // url
+    ("flag.custom_brand_impersonation", &[]),
+    ("flag.brand_path_mimicry", &[]),
+    ("flag.keyword_pack_match", &[]),
+    ("flag.keyword_stuffing", &[]),
+    ("flag.kit_fingerprint_path", &[]),
+    ("flag.deep_random_path", &[]),
+    ("flag.login_form_on_compromised_path", &[]),
+    ("flag.homoglyph_impersonation", &[]),
+    ("flag.brand_alias_impersonation", &[]),
+    ("flag.numeric_brand_lookalike", &[]),
+    ("flag.nested_encoding", &[]),
+    ("flag.encoded_traversal", &[]),
+    ("flag.encoded_keyword", &[]),
+    ("flag.idn_brand_risky_tld", &[]),
+    ("flag.alias_brand_risky_tld", &[]),
+    ("flag.brand_login_risky_tld", &[]),
+    ("flag.ip_host_login", &[]),
+    ("flag.upi_collect_unknown_handle", &[]),
+    ("flag.redirect_to_executable", &[]),
+    // context
+    ("flag.allowlisted", &[]),
+    ("flag.blocklisted", &[]),
+    ("flag.managed_allow", &[]),
+    ("flag.managed_block", &[]),
+    ("flag.threat_feed_match", &[]),
+    ("flag.bad_reputation", &[]),
+    ("flag.kit_path_on_known_site", &[]),
+    ("flag.unusual_site_template", &[]),
+    ("flag.personal_adjustment", &[]),
+    // page
+    ("flag.password_over_http", &[]),
+    ("flag.credentials_posted_offsite", &[]),
+    ("flag.password_in_query", &[]),
+    ("flag.autofill_blocked", &[]),
+    ("flag.lure_text", &[]),
+    ("flag.credential_page_weak_csp", &[]),
+    ("flag.messaging_webhook_exfil", &[]),
+    // resource graph
+    ("flag.credential_sink", &[]),
+    ("flag.messaging_webhook", &[]),
+    ("flag.form_off_script_origin", &[]),
+    // certificate
+    ("flag.self_signed", &[]),
+    ("flag.name_mismatch", &["host"]),
+    ("flag.fresh_certificate", &[]),
+    ("flag.recent_certificate", &[]),
+    ("flag.bulk_san_list", &[]),
+    ("flag.over_long_validity", &[]),
+    ("flag.brand_in_certificate_host", &["host"]),
+    // mail
+    ("flag.brand_from_freemail", &[]),
+    ("flag.display_name_address_spoof", &[]),
+    ("flag.display_name_brand_mismatch", &[]),
+    ("flag.dmarc_record_invalid", &[]),
+    ("flag.reply_to_divergence", &[]),
+    ("flag.reply_to_freemail", &[]),
+    ("flag.sender_brand_on_foreign_host", &[]),
+    ("flag.sender_domain_lookalike", &[]),
+    ("flag.spf_permissive", &[]),
+    ("flag.text_href_mismatch", &[]),
+    ("flag.dmarc_fail", &[]),
+    ("flag.dmarc_none", &[]),
+    ("flag.dmarc_policy_none", &[]),
+    ("flag.spf_fail", &[]),
+    ("flag.spf_softfail", &[]),
+    ("flag.dkim_fail", &[]),
+    ("flag.dkim_unaligned", &[]),
+    // calendar invites
+    ("flag.inline_binary_attachment", &[]),
+    ("flag.no_organizer", &[]),
+    ("flag.organizer_brand_mismatch", &[]),
+    ("flag.organizer_cn_address_spoof", &[]),
+    ("flag.organizer_domain_lookalike", &[]),
+    ("flag.organizer_sent_by_mismatch", &[]),
+    // oauth
+    ("flag.broad_scopes", &[]),
+    ("flag.broad_scopes_with_offline_access", &[]),
+    ("flag.device_code_flow", &[]),
+    ("flag.device_code_prefilled", &[]),
+    ("flag.forced_consent_offline", &[]),
+    ("flag.implicit_grant", &[]),
+    ("flag.insecure_authorize_endpoint", &[]),
+    ("flag.lookalike_idp", &[]),
+    ("flag.unknown_idp", &[]),
+    ("flag.redirect_chained_url", &[]),
+    ("flag.redirect_ip_host", &[]),
+    ("flag.redirect_missing", &[]),
+    ("flag.redirect_oob", &[]),
+    ("flag.redirect_same_site_as_idp", &[]),
+    ("flag.redirect_shortener", &[]),
+    ("flag.redirect_suspicious_tld", &[]),
+    ("flag.redirect_tunnel_host", &[]),
+    // downloads and filenames
+    ("flag.extension_content_mismatch", &[]),
+    ("flag.mime_mismatch", &[]),
+    ("flag.bidi_control", &[]),
+    ("flag.control_characters", &[]),
+    ("flag.extension_disguised", &[]),
+    ("flag.fallback_name_mismatch", &[]),
+    ("flag.path_in_filename", &[]),
+    ("flag.rtlo_override", &[]),
+    ("flag.trailing_dot_or_space", &[]),
+    // archives
+    ("flag.contains_executable", &[]),
+    ("flag.deeply_nested_archive", &[]),
+    ("flag.disk_image_container", &[]),
+    ("flag.disk_image_with_executable", &[]),
+    ("flag.encrypted_archive", &[]),
+    ("flag.encrypted_executable", &[]),
+    ("flag.encrypted_headers", &[]),
+    ("flag.password_in_filename", &[]),
+    ("flag.password_in_page_text", &[]),
+    ("flag.password_unlocks_archive", &[]),
+    // apk
+    ("flag.accessibility_with_overlay", &[]),
+    ("flag.banking_trojan_combo", &[]),
+    ("flag.sms_with_screen_control", &[]),
+    ("flag.otp_interception", &[]),
+    ("flag.sms_access", &[]),
+    ("flag.installs_packages", &[]),
+    ("flag.device_admin", &[]),
+    ("flag.contact_spread", &[]),
+    ("flag.package_claims_brand", &["package"]),
+    // office
+    ("flag.activex_controls", &[]),
+    ("flag.embedded_objects", &[]),
+    ("flag.external_ole_link", &[]),
+    ("flag.macro_content_type", &[]),
+    ("flag.protocol_handler_target", &[]),
+    ("flag.remote_template", &[]),
+    ("flag.vba_project", &[]),
+    // pdf
+    ("flag.all_streams_opaque", &[]),
+    ("flag.auto_run_on_open", &[]),
+    ("flag.embedded_file", &[]),
+    ("flag.javascript_action", &[]),
+    ("flag.launch_action", &[]),
+    ("flag.single_page_link_lure", &[]),
+    ("flag.submit_form", &[]),
+    // html and svg attachments
+    ("flag.auto_submit_form", &[]),
+    ("flag.credential_form", &[]),
+    ("flag.credential_form_remote_action", &[]),
+    ("flag.event_handler", &[]),
+    ("flag.html_smuggling", &[]),
+    ("flag.javascript_url", &[]),
+    ("flag.large_base64_blob", &[]),
+    ("flag.meta_refresh", &[]),
+    ("flag.obfuscated_script", &[]),
+    ("flag.script", &[]),
+    ("flag.script_redirect", &[]),
+    ("flag.svg_embedded_html", &[]),
+    ("flag.svg_script", &[]),
+    // clipboard
+    ("flag.clipboard_hijack", &[]),
+    ("flag.lookalike_payment_address", &[]),
+    // display
+    ("flag.confusable_domain", &["lookalike_of"]),
+    ("flag.mixed_script_label", &[]),
+    ("flag.registrable_domain_hidden", &["registrable_domain"]),
+    ("flag.visible_brand_lure", &["lookalike_of"]),
+    // intent
+    ("flag.intent_fallback_off_host", &["fallback_host"]),
+    ("flag.intent_fallback_risky", &[]),
+    ("flag.intent_fallback_script", &[]),
+    ("flag.intent_package_mismatch", &["package", "brand"]),
+    // deep_link
+    ("flag.enterprise_app_install", &[]),
+    ("flag.manifest_off_origin", &["manifest_host"]),
+    ("flag.sideload_from_untrusted_page", &[]),
+    ("flag.store_package_mismatch", &["package", "brand"]),
+    ("flag.store_redirect_without_gesture", &[]),
+    // endpoint
+    ("flag.endpoint_ip_host", &[]),
+    ("flag.endpoint_private_network", &["host"]),
+    ("flag.endpoint_raw_port", &["port"]),
+    ("flag.endpoint_suspicious_tld", &[]),
+    ("flag.endpoint_tunnel_host", &[]),
+    // host consistency
+    ("flag.sni_host_mismatch", &["sni", "target"]),
+    ("flag.sni_subdomain_mismatch", &["sni", "target"]),
+    ("flag.sni_ip_literal", &["sni"]),
+    ("flag.url_host_mismatch", &["url_host", "host_header"]),
+    // short url expansion
+    ("flag.redirect_chain_long", &["chain_length"]),
+    ("flag.shortener_chain", &["shortener_hops"]),
+    ("flag.redirect_loop", &[]),
+    // upi request
+    ("flag.upi_reverse_payment", &["payee"]),
+    ("flag.upi_pull_request", &["payee"]),
+    ("flag.upi_unknown_handle", &["handle"]),
+    ("flag.upi_scam_prefix", &["payee"]),
+    // data uri
+    ("flag.data_uri_document", &[]),
+    ("flag.data_uri_script", &[]),
+    ("flag.data_uri_form", &[]),
+    ("flag.data_uri_mime_mismatch", &["mime", "content_kind"]),
+    ("flag.data_uri_executable", &["content_kind"]),
+    ("flag.data_uri_malformed", &[]),
+    // javascript: and blob: payloads
+    ("flag.javascript_scheme", &[]),
+    ("flag.js_obfuscated", &[]),
+    ("flag.js_writes_page", &[]),
+    ("flag.js_credential_prompt", &[]),
+    ("flag.js_redirect", &[]),
+    ("flag.blob_scheme", &["origin"]),
+    ("flag.blob_opaque_origin", &[]),
+    // mailto and tel links
+    ("flag.mailto_lookalike_domain", &["domain", "brand"]),
+    ("flag.mailto_display_spoof", &["domain"]),
+    ("flag.mailto_brand_freemail", &["domain", "brand"]),
+    ("flag.contact_text_mismatch", &["shown"]),
+    ("flag.tel_premium_rate", &["number"]),
+    ("flag.tel_high_risk_prefix", &["number"]),
+    ("flag.tel_ussd", &["number"]),
+    ("flag.tel_international", &[]),
+];
+
+const EN: &[(&str, &str)] = &[
+    ("verdict.allow", "No signs of phishing or malware were found."),
+    ("verdict.warn", "This looks suspicious. Proceed with care."),
+    ("verdict.block", "This is very likely dangerous and was blocked."),
+    ("flag.unknown", "Suspicious signal: {flag}."),
+    ("finding.exfiltration.messaging_webhook", "Data is sent straight to a {service} bot ({endpoint}), a common way to steal passwords."),
+    ("finding.evasion.domain_fronting", "The connection names {sni} to the network but is really headed for {host}, a trick used to hide where traffic goes."),
+    ("flag.custom_brand_impersonation", "The address imitates a brand you asked to protect."),
+    ("flag.brand_path_mimicry", "The link copies a well-known site's page path on a different domain."),
+    ("flag.keyword_pack_match", "The address contains words from a loaded phishing keyword list."),
+    ("flag.keyword_stuffing", "The address stacks several sign-in, payment or prize words together."),
+    ("flag.kit_fingerprint_path", "The address uses file and folder names typical of phishing kits planted on hacked sites."),
+    ("flag.deep_random_path", "The page is buried under randomly named folders of a well-known site."),
+    ("flag.login_form_on_compromised_path", "A sign-in form sits on a hidden, planted-looking part of a well-known site."),
+    ("flag.homoglyph_impersonation", "The address spells a well-known brand with look-alike letters from another alphabet."),
+    ("flag.brand_alias_impersonation", "The address writes a well-known brand's name in another script or spelled out, on a site that isn't the brand's."),
+    ("flag.numeric_brand_lookalike", "The address spells a short bank or brand name with digits or look-alike letters, like 1cici for ICICI."),
+    ("flag.nested_encoding", "The address is percent-encoded several times over, a trick to get past filters."),
+    ("flag.encoded_traversal", "Once decoded, the address climbs out of its folder, hidden under layers of encoding."),
+    ("flag.encoded_keyword", "Once decoded, the address contains sign-in, payment or prize words that its encoding hides."),
+    ("flag.idn_brand_risky_tld", "An encoded look-alike address imitates a well-known brand on a domain ending often used for throwaway sites."),
+    ("flag.alias_brand_risky_tld", "The address writes a well-known brand's name in another script on a domain ending often used for throwaway sites."),
+    ("flag.brand_login_risky_tld", "A misspelled brand name, a sign-in page and a throwaway domain ending appear together, a classic phishing pattern."),
+    ("flag.ip_host_login", "A sign-in page is served from a bare numeric address instead of a named site."),
+    ("flag.upi_collect_unknown_handle", "This payment request collects money for a UPI address with an unknown handle or a scam prefix."),
+    ("flag.redirect_to_executable", "A redirect link sends you straight to a program download."),
+    ("flag.allowlisted", "You or your administrator marked this site as trusted."),
+    ("flag.blocklisted", "You or your administrator blocked this site."),
+    ("flag.managed_allow", "Your organisation's policy allows this site."),
+    ("flag.managed_block", "Your organisation's policy blocks this site."),
+    ("flag.threat_feed_match", "This site is on a threat-intelligence blocklist."),
+    ("flag.bad_reputation", "This site has repeatedly looked risky before."),
+    ("flag.kit_path_on_known_site", "This address uses a folder phishing kits plant on hacked sites, unlike the rest of this site."),
+    ("flag.unusual_site_template", "This address doesn't look like the pages this site normally serves."),
+    ("flag.personal_adjustment", "Your earlier allow and block decisions changed this score."),
+    ("flag.password_over_http", "A password form here is sent without encryption."),
+    ("flag.credentials_posted_offsite", "The login form sends your password to a different, risky site."),
+    ("flag.password_in_query", "The login form puts your password in the web address."),
+    ("flag.autofill_blocked", "Password autofill was turned off for this page."),
+    ("flag.lure_text", "The page text uses typical phishing phrases."),
+    ("flag.credential_page_weak_csp", "This login page has little or no script protection, unlike genuine sign-in pages."),
+    ("flag.messaging_webhook_exfil", "The page sends form data to a chat bot, a common way to steal passwords."),
+    ("flag.credential_sink", "The page sends data to a hosted form collector."),
+    ("flag.messaging_webhook", "The page contacts a chat-bot webhook."),
+    ("flag.form_off_script_origin", "A form posts to a site none of the page's code came from."),
+    ("flag.self_signed", "The site's certificate is self-signed."),
+    ("flag.name_mismatch", "The certificate does not cover {host}."),
+    ("flag.fresh_certificate", "The certificate was issued in the last two days."),
+    ("flag.recent_certificate", "The certificate was issued in the last two weeks."),
+    ("flag.bulk_san_list", "The certificate covers an unusually large number of names."),
+    ("flag.over_long_validity", "The certificate is valid for longer than public certificates may be."),
+    ("flag.brand_in_certificate_host", "The certificate for {host} names a brand it does not belong to."),
+    ("flag.brand_from_freemail", "A message claiming to be from a brand was sent from a free email account."),
+    ("flag.display_name_address_spoof", "The sender's display name shows a different email address."),
+    ("flag.display_name_brand_mismatch", "The sender's name claims a brand its address does not belong to."),
+    ("flag.dmarc_record_invalid", "The sender's domain has a broken DMARC record."),
+    ("flag.reply_to_divergence", "Replies would go to a different domain than the sender's."),
+    ("flag.reply_to_freemail", "Replies would go to a free email account."),
+    ("flag.sender_brand_on_foreign_host", "The sender's domain contains a brand name it does not belong to."),
+    ("flag.sender_domain_lookalike", "The sender's domain imitates a well-known one."),
+    ("flag.spf_permissive", "The sender's domain lets any server send mail in its name."),
+    ("flag.text_href_mismatch", "A link shows one address but leads to another."),
+    ("flag.dmarc_fail", "The message failed the sender domain's DMARC check."),
+    ("flag.dmarc_none", "The sender's domain does not publish a DMARC policy."),
+    ("flag.dmarc_policy_none", "The sender's domain does not enforce its DMARC policy."),
+    ("flag.spf_fail", "The message came from a server the sender's domain does not allow."),
+    ("flag.spf_softfail", "The message came from a server the sender's domain does not fully trust."),
+    ("flag.dkim_fail", "The message's signature is invalid."),
+    ("flag.dkim_unaligned", "The message is signed by an unrelated domain."),
+    ("flag.inline_binary_attachment", "The invitation carries an embedded file."),
+    ("flag.no_organizer", "The invitation has no organiser."),
+    ("flag.organizer_brand_mismatch", "The organiser claims a brand their address does not belong to."),
+    ("flag.organizer_cn_address_spoof", "The organiser's name shows a different email address."),
+    ("flag.organizer_domain_lookalike", "The organiser's domain imitates a well-known one."),
+    ("flag.organizer_sent_by_mismatch", "The invitation was sent on the organiser's behalf by another domain."),
+    ("flag.broad_scopes", "The app asks for broad access to your account."),
+    ("flag.broad_scopes_with_offline_access", "The app asks for broad access that keeps working after you leave."),
+    ("flag.device_code_flow", "This sign-in uses a device code, a flow often abused to take over accounts."),
+    ("flag.device_code_prefilled", "The device code was filled in for you; only enter codes shown on your own device."),
+    ("flag.forced_consent_offline", "The app forces a consent prompt for long-lived access."),
+    ("flag.implicit_grant", "The app uses an outdated sign-in flow that exposes tokens."),
+    ("flag.insecure_authorize_endpoint", "The sign-in page is not encrypted."),
+    ("flag.lookalike_idp", "The sign-in page imitates a well-known identity provider."),
+    ("flag.unknown_idp", "The sign-in provider is not one we recognise."),
+    ("flag.redirect_chained_url", "After sign-in you would be sent on through another link."),
+    ("flag.redirect_ip_host", "After sign-in you would be sent to a bare IP address."),
+    ("flag.redirect_missing", "The sign-in request does not say where it returns to."),
+    ("flag.redirect_oob", "The sign-in result is shown for copying instead of returned to the app."),
+    ("flag.redirect_same_site_as_idp", "The return address sits on the sign-in provider's own site."),
+    ("flag.redirect_shortener", "After sign-in you would be sent through a link shortener."),
+    ("flag.redirect_suspicious_tld", "After sign-in you would be sent to a domain with a high-abuse ending."),
+    ("flag.redirect_tunnel_host", "After sign-in you would be sent to a temporary tunnel host."),
+    ("flag.extension_content_mismatch", "The file's contents do not match its extension."),
+    ("flag.mime_mismatch", "The server describes the file as a different type than it is."),
+    ("flag.bidi_control", "The file name uses hidden text-direction characters."),
+    ("flag.control_characters", "The file name contains hidden control characters."),
+    ("flag.extension_disguised", "The file name hides its real extension."),
+    ("flag.fallback_name_mismatch", "The file offers two different names."),
+    ("flag.path_in_filename", "The file name contains a folder path."),
+    ("flag.rtlo_override", "The file name is reversed to disguise its extension."),
+    ("flag.trailing_dot_or_space", "The file name ends in a dot or space to disguise it."),
+    ("flag.contains_executable", "The archive contains a program."),
+    ("flag.deeply_nested_archive", "The archive is packed inside several other archives."),
+    ("flag.disk_image_container", "The download is a disk image."),
+    ("flag.disk_image_with_executable", "The disk image contains a program."),
+    ("flag.encrypted_archive", "The archive is password-protected, so its contents can't be checked."),
+    ("flag.encrypted_executable", "The password-protected archive contains a program."),
+    ("flag.encrypted_headers", "The archive hides even its file names."),
+    ("flag.password_in_filename", "The archive's password is given in its file name."),
+    ("flag.password_in_page_text", "The archive's password is given on the page."),
+    ("flag.password_unlocks_archive", "The password shown really opens the archive."),
+    ("flag.accessibility_with_overlay", "The app can read your screen and draw over other apps."),
+    ("flag.banking_trojan_combo", "The app requests the permissions banking trojans use to steal logins and codes."),
+    ("flag.sms_with_screen_control", "The app can read text messages and control the screen."),
+    ("flag.otp_interception", "The app can read one-time codes from messages and notifications."),
+    ("flag.sms_access", "The app can read text messages."),
+    ("flag.installs_packages", "The app can install other apps."),
+    ("flag.device_admin", "The app asks to become a device administrator."),
+    ("flag.contact_spread", "The app can text your contacts."),
+    ("flag.package_claims_brand", "The app {package} claims a brand it does not belong to."),
+    ("flag.activex_controls", "The document contains ActiveX controls."),
+    ("flag.embedded_objects", "The document contains embedded objects."),
+    ("flag.external_ole_link", "The document loads content from an external link."),
+    ("flag.macro_content_type", "The document is a macro-enabled type."),
+    ("flag.protocol_handler_target", "The document opens an external program through a link."),
+    ("flag.remote_template", "The document downloads a template from the internet when opened."),
+    ("flag.vba_project", "The document contains macros."),
+    ("flag.all_streams_opaque", "None of the PDF's contents could be read."),
+    ("flag.auto_run_on_open", "The PDF runs an action as soon as it is opened."),
+    ("flag.embedded_file", "The PDF carries an embedded file."),
+    ("flag.javascript_action", "The PDF contains JavaScript."),
+    ("flag.launch_action", "The PDF tries to start a program."),
+    ("flag.single_page_link_lure", "The PDF is a single page built around a link."),
+    ("flag.submit_form", "The PDF sends form data to a website."),
+    ("flag.auto_submit_form", "The attachment submits a form by itself."),
+    ("flag.credential_form", "The attachment contains a password form."),
+    ("flag.credential_form_remote_action", "The attachment's password form sends to a website."),
+    ("flag.event_handler", "The attachment runs code on events."),
+    ("flag.html_smuggling", "The attachment assembles a hidden file inside the browser."),
+    ("flag.javascript_url", "The attachment contains JavaScript links."),
+    ("flag.large_base64_blob", "The attachment carries a large encoded payload."),
+    ("flag.meta_refresh", "The attachment redirects you to a website."),
+    ("flag.obfuscated_script", "The attachment contains deliberately obscured code."),
+    ("flag.script", "The attachment contains scripts."),
+    ("flag.script_redirect", "The attachment uses code to send you to a website."),
+    ("flag.svg_embedded_html", "The image contains a hidden web page."),
+    ("flag.svg_script", "The image contains scripts."),
+    ("flag.clipboard_hijack", "The payment address you pasted is not the one you copied."),
+    ("flag.lookalike_payment_address", "The pasted address was made to look like the copied one at a glance."),
+    ("flag.confusable_domain", "The address is written to look like {lookalike_of}."),
+    ("flag.mixed_script_label", "The address mixes Latin letters with Greek or Cyrillic ones."),
+    ("flag.registrable_domain_hidden", "The address is too long to show its real site, {registrable_domain}."),
+    ("flag.visible_brand_lure", "The visible part of the address names {lookalike_of}, but the site is another one."),
+    ("flag.intent_fallback_off_host", "If the app is missing, the link sends you to {fallback_host} instead."),
+    ("flag.intent_fallback_risky", "The address the link falls back to looks unsafe."),
+    ("flag.intent_fallback_script", "The link's fallback runs code instead of opening a website."),
+    ("flag.intent_package_mismatch", "The link opens the app {package}, which is not from {brand}."),
+    ("flag.enterprise_app_install", "The link installs an app from outside the official app store."),
+    ("flag.manifest_off_origin", "The app would be installed from another site, {manifest_host}."),
+    ("flag.sideload_from_untrusted_page", "An untrusted page is trying to install an app on your device."),
+    ("flag.store_package_mismatch", "The store listing is for {package}, which is not from {brand}."),
+    ("flag.store_redirect_without_gesture", "A page opened an app store listing without you asking."),
+    ("flag.endpoint_ip_host", "The page talks to a server by its raw IP address."),
+    ("flag.endpoint_private_network", "A website is contacting a device on your local network, {host}."),
+    ("flag.endpoint_raw_port", "The page connects to a server on an unusual port, {port}."),
+    ("flag.endpoint_suspicious_tld", "The page sends data to a domain with a high-abuse ending."),
+    ("flag.endpoint_tunnel_host", "The page sends data through a temporary tunnelling service."),
+    ("flag.sni_host_mismatch", "The connection claims to go to {sni} but asks for {target}."),
+    ("flag.sni_subdomain_mismatch", "The connection was set up for {sni} but asks for {target} on the same site."),
+    ("flag.sni_ip_literal", "The connection names a raw IP address, {sni}, instead of a site."),
+    ("flag.url_host_mismatch", "The request for {url_host} is addressed to another site, {host_header}."),
+    ("flag.redirect_chain_long", "The short link bounces through {chain_length} redirects before reaching its page."),
+    ("flag.shortener_chain", "The link hides behind {shortener_hops} link shorteners in a row."),
+    ("flag.redirect_loop", "The link redirects in a circle and never reaches a page."),
+    ("flag.upi_reverse_payment", "This says you will receive money, but approving it sends money from your account to {payee}. You never need your UPI PIN to receive money."),
+    ("flag.upi_pull_request", "This is a collect request: {payee} is asking to take money from your account, not to send you any."),
+    ("flag.upi_unknown_handle", "The UPI address uses a handle, @{handle}, that no known bank or payment app issues."),
+    ("flag.upi_scam_prefix", "The UPI address {payee} is named like a refund, KYC or support desk; banks don't collect money through such addresses."),
+    ("flag.data_uri_document", "The link carries a whole web page inside itself instead of pointing to a website."),
+    ("flag.data_uri_script", "The link carries a script inside itself."),
+    ("flag.data_uri_form", "The page inside the link contains a form; whatever you type goes to whoever made it."),
+    ("flag.data_uri_mime_mismatch", "The link says it holds {mime} but actually holds {content_kind} content."),
+    ("flag.data_uri_executable", "The link carries a program ({content_kind}) inside itself."),
+    ("flag.data_uri_malformed", "The link's encoded content is damaged or deliberately garbled."),
+    ("flag.javascript_scheme", "The link is a script that runs inside the page you open it from."),
+    ("flag.js_obfuscated", "The script hides what it does behind encoding or eval."),
+    ("flag.js_writes_page", "The script replaces the page with content of its own."),
+    ("flag.js_credential_prompt", "The script asks for a password, code or card details."),
+    ("flag.js_redirect", "The script sends you, or what you type, to another address."),
+    ("flag.blob_scheme", "The link points to content that a page at {origin} assembled inside the browser."),
+    ("flag.blob_opaque_origin", "The link points to content assembled by a hidden or sandboxed page."),
+    ("flag.mailto_lookalike_domain", "The link writes to {domain}, which imitates {brand}."),
+    ("flag.mailto_display_spoof", "The address shown as the sender is not where the mail goes: {domain}."),
+    ("flag.mailto_brand_freemail", "The link writes to a free mail account on {domain} named after {brand}."),
+    ("flag.contact_text_mismatch", "The link shows {shown} but opens a different address or number."),
+    ("flag.tel_premium_rate", "The number {number} is a premium-rate line charged by the minute."),
+    ("flag.tel_high_risk_prefix", "The number {number} uses a prefix common in call-charge fraud."),
+    ("flag.tel_ussd", "Dialing {number} runs a phone service code, which can forward your calls."),
+    ("flag.tel_international", "The number is an international call."),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("verdict.allow", "No se encontraron indicios de phishing ni de malware."),
+    ("verdict.warn", "Esto parece sospechoso. Proceda con cuidado."),
+    ("verdict.block", "Esto es muy probablemente peligroso y se ha bloqueado."),
+    ("flag.unknown", "Señal sospechosa: {flag}."),
+    ("finding.exfiltration.messaging_webhook", "Los datos se envían directamente a un bot de {service} ({endpoint}), una forma habitual de robar contraseñas."),
+    ("finding.evasion.domain_fronting", "La conexión se presenta ante la red como {sni}, pero en realidad va a {host}, un truco para ocultar adónde va el tráfico."),
+    ("flag.custom_brand_impersonation", "La dirección imita una marca que pidió proteger."),
+    ("flag.brand_path_mimicry", "El enlace copia la ruta de una página conocida en otro dominio."),
+    ("flag.keyword_pack_match", "La dirección contiene palabras de una lista de phishing cargada."),
+    ("flag.keyword_stuffing", "La dirección acumula varias palabras de acceso, pago o premio."),
+    ("flag.kit_fingerprint_path", "La dirección usa nombres de archivos y carpetas típicos de los kits de phishing instalados en sitios hackeados."),
+    ("flag.deep_random_path", "La página está escondida bajo carpetas con nombres aleatorios de un sitio conocido."),
+    ("flag.login_form_on_compromised_path", "Hay un formulario de acceso en una parte oculta y aparentemente plantada de un sitio conocido."),
+    ("flag.homoglyph_impersonation", "La dirección escribe una marca conocida con letras parecidas de otro alfabeto."),
+    ("flag.brand_alias_impersonation", "La dirección escribe el nombre de una marca conocida en otra escritura o completo, en un sitio que no es de la marca."),
+    ("flag.numeric_brand_lookalike", "La dirección escribe el nombre corto de un banco o marca con números o letras parecidas, como 1cici por ICICI."),
+    ("flag.nested_encoding", "La dirección está codificada varias veces seguidas, un truco para evadir filtros."),
+    ("flag.encoded_traversal", "Una vez decodificada, la dirección sale de su carpeta, oculta bajo varias capas de codificación."),
+    ("flag.encoded_keyword", "Una vez decodificada, la dirección contiene palabras de acceso, pago o premio que su codificación oculta."),
+    ("flag.idn_brand_risky_tld", "Una dirección codificada con letras parecidas imita una marca conocida en una terminación de dominio usada a menudo para sitios desechables."),
+    ("flag.alias_brand_risky_tld", "La dirección escribe el nombre de una marca conocida en otra escritura con una terminación de dominio usada a menudo para sitios desechables."),
+    ("flag.brand_login_risky_tld", "Aparecen juntos un nombre de marca mal escrito, una página de inicio de sesión y una terminación de dominio desechable, un patrón clásico de phishing."),
+    ("flag.ip_host_login", "Una página de inicio de sesión se sirve desde una dirección numérica en lugar de un sitio con nombre."),
+    ("flag.upi_collect_unknown_handle", "Esta solicitud de pago cobra dinero para una dirección UPI con un identificador desconocido o un prefijo de estafa."),
+    ("flag.redirect_to_executable", "Un enlace de redirección le lleva directamente a la descarga de un programa."),
+    ("flag.allowlisted", "Usted o su administrador marcaron este sitio como de confianza."),
+    ("flag.blocklisted", "Usted o su administrador bloquearon este sitio."),
+    ("flag.managed_allow", "La política de su organización permite este sitio."),
+    ("flag.managed_block", "La política de su organización bloquea este sitio."),
+    ("flag.threat_feed_match", "Este sitio figura en una lista de amenazas."),
+    ("flag.bad_reputation", "Este sitio ya ha parecido peligroso en repetidas ocasiones."),
+    ("flag.kit_path_on_known_site", "Esta dirección usa una carpeta que los kits de phishing colocan en sitios hackeados, a diferencia del resto del sitio."),
+    ("flag.unusual_site_template", "Esta dirección no se parece a las páginas que este sitio suele servir."),
+    ("flag.personal_adjustment", "Sus decisiones anteriores de permitir y bloquear han cambiado esta puntuación."),
+    ("flag.password_over_http", "Un formulario de contraseña se envía sin cifrar."),
+    ("flag.credentials_posted_offsite", "El formulario de acceso envía su contraseña a otro sitio de riesgo."),
+    ("flag.password_in_query", "El formulario de acceso pone su contraseña en la dirección web."),
+    ("flag.autofill_blocked", "Se desactivó el autocompletado de contraseñas en esta página."),
+    ("flag.lure_text", "El texto de la página usa frases típicas de phishing."),
+    ("flag.credential_page_weak_csp", "Esta página de acceso apenas protege sus scripts, a diferencia de las páginas de acceso reales."),
+    ("flag.messaging_webhook_exfil", "La página envía los datos del formulario a un bot de chat, una forma habitual de robar contraseñas."),
+    ("flag.credential_sink", "La página envía datos a un servicio externo de recogida de formularios."),
+    ("flag.messaging_webhook", "La página contacta con un webhook de bot de chat."),
+    ("flag.form_off_script_origin", "Un formulario se envía a un sitio del que no procede nada del código de la página."),
+    ("flag.self_signed", "El certificado del sitio es autofirmado."),
+    ("flag.name_mismatch", "El certificado no cubre {host}."),
+    ("flag.fresh_certificate", "El certificado se emitió en los últimos dos días."),
+    ("flag.recent_certificate", "El certificado se emitió en las últimas dos semanas."),
+    ("flag.bulk_san_list", "El certificado cubre un número inusualmente grande de nombres."),
+    ("flag.over_long_validity", "El certificado es válido durante más tiempo del permitido a los certificados públicos."),
+    ("flag.brand_in_certificate_host", "El certificado de {host} nombra una marca a la que no pertenece."),
+    ("flag.brand_from_freemail", "Un mensaje que dice venir de una marca se envió desde una cuenta de correo gratuita."),
+    ("flag.display_name_address_spoof", "El nombre del remitente muestra otra dirección de correo."),
+    ("flag.display_name_brand_mismatch", "El nombre del remitente reclama una marca a la que su dirección no pertenece."),
+    ("flag.dmarc_record_invalid", "El dominio del remitente tiene un registro DMARC defectuoso."),
+    ("flag.reply_to_divergence", "Las respuestas irían a un dominio distinto del remitente."),
+    ("flag.reply_to_freemail", "Las respuestas irían a una cuenta de correo gratuita."),
+    ("flag.sender_brand_on_foreign_host", "El dominio del remitente contiene el nombre de una marca a la que no pertenece."),
+    ("flag.sender_domain_lookalike", "El dominio del remitente imita uno conocido."),
+    ("flag.spf_permissive", "El dominio del remitente permite que cualquier servidor envíe correo en su nombre."),
+    ("flag.text_href_mismatch", "Un enlace muestra una dirección pero lleva a otra."),
+    ("flag.dmarc_fail", "El mensaje no superó la comprobación DMARC del dominio del remitente."),
+    ("flag.dmarc_none", "El dominio del remitente no publica una política DMARC."),
+    ("flag.dmarc_policy_none", "El dominio del remitente no aplica su política DMARC."),
+    ("flag.spf_fail", "El mensaje procede de un servidor que el dominio del remitente no autoriza."),
+    ("flag.spf_softfail", "El mensaje procede de un servidor en el que el dominio del remitente no confía del todo."),
+    ("flag.dkim_fail", "La firma del mensaje no es válida."),
+    ("flag.dkim_unaligned", "El mensaje está firmado por un dominio ajeno."),
+    ("flag.inline_binary_attachment", "La invitación incluye un archivo incrustado."),
+    ("flag.no_organizer", "La invitación no tiene organizador."),
+    ("flag.organizer_brand_mismatch", "El organizador reclama una marca a la que su dirección no pertenece."),
+    ("flag.organizer_cn_address_spoof", "El nombre del organizador muestra otra dirección de correo."),
+    ("flag.organizer_domain_lookalike", "El dominio del organizador imita uno conocido."),
+    ("flag.organizer_sent_by_mismatch", "Otro dominio envió la invitación en nombre del organizador."),
+    ("flag.broad_scopes", "La aplicación pide un acceso amplio a su cuenta."),
+    ("flag.broad_scopes_with_offline_access", "La aplicación pide un acceso amplio que sigue activo cuando usted se va."),
+    ("flag.device_code_flow", "Este inicio de sesión usa un código de dispositivo, un método que se abusa para robar cuentas."),
+    ("flag.device_code_prefilled", "El código de dispositivo ya venía rellenado; introduzca solo códigos que vea en su propio dispositivo."),
+    ("flag.forced_consent_offline", "La aplicación fuerza una solicitud de consentimiento para un acceso prolongado."),
+    ("flag.implicit_grant", "La aplicación usa un método de acceso obsoleto que expone los tokens."),
+    ("flag.insecure_authorize_endpoint", "La página de inicio de sesión no está cifrada."),
+    ("flag.lookalike_idp", "La página de inicio de sesión imita a un proveedor de identidad conocido."),
+    ("flag.unknown_idp", "No reconocemos este proveedor de inicio de sesión."),
+    ("flag.redirect_chained_url", "Tras iniciar sesión, se le reenviaría a través de otro enlace."),
+    ("flag.redirect_ip_host", "Tras iniciar sesión, se le enviaría a una dirección IP sin nombre."),
+    ("flag.redirect_missing", "La solicitud de inicio de sesión no indica adónde vuelve."),
+    ("flag.redirect_oob", "El resultado del inicio de sesión se muestra para copiarlo en vez de devolverse a la aplicación."),
+    ("flag.redirect_same_site_as_idp", "La dirección de retorno está en el propio sitio del proveedor de inicio de sesión."),
+    ("flag.redirect_shortener", "Tras iniciar sesión, se le enviaría a través de un acortador de enlaces."),
+    ("flag.redirect_suspicious_tld", "Tras iniciar sesión, se le enviaría a un dominio con una terminación muy abusada."),
+    ("flag.redirect_tunnel_host", "Tras iniciar sesión, se le enviaría a un túnel temporal."),
+    ("flag.extension_content_mismatch", "El contenido del archivo no coincide con su extensión."),
+    ("flag.mime_mismatch", "El servidor describe el archivo como de otro tipo."),
+    ("flag.bidi_control", "El nombre del archivo usa caracteres ocultos de dirección del texto."),
+    ("flag.control_characters", "El nombre del archivo contiene caracteres de control ocultos."),
+    ("flag.extension_disguised", "El nombre del archivo oculta su extensión real."),
+    ("flag.fallback_name_mismatch", "El archivo ofrece dos nombres distintos."),
+    ("flag.path_in_filename", "El nombre del archivo contiene una ruta de carpetas."),
+    ("flag.rtlo_override", "El nombre del archivo está invertido para disimular su extensión."),
+    ("flag.trailing_dot_or_space", "El nombre del archivo termina en punto o espacio para disimularlo."),
+    ("flag.contains_executable", "El archivo comprimido contiene un programa."),
+    ("flag.deeply_nested_archive", "El archivo comprimido está dentro de varios otros."),
+    ("flag.disk_image_container", "La descarga es una imagen de disco."),
+    ("flag.disk_image_with_executable", "La imagen de disco contiene un programa."),
+    ("flag.encrypted_archive", "El archivo comprimido tiene contraseña, así que no se puede revisar su contenido."),
+    ("flag.encrypted_executable", "El archivo protegido con contraseña contiene un programa."),
+    ("flag.encrypted_headers", "El archivo comprimido oculta incluso los nombres de sus archivos."),
+    ("flag.password_in_filename", "La contraseña del archivo comprimido aparece en su nombre."),
+    ("flag.password_in_page_text", "La contraseña del archivo comprimido aparece en la página."),
+    ("flag.password_unlocks_archive", "La contraseña indicada abre realmente el archivo comprimido."),
+    ("flag.accessibility_with_overlay", "La aplicación puede leer su pantalla y superponerse a otras aplicaciones."),
+    ("flag.banking_trojan_combo", "La aplicación pide los permisos que usan los troyanos bancarios para robar accesos y códigos."),
+    ("flag.sms_with_screen_control", "La aplicación puede leer SMS y controlar la pantalla."),
+    ("flag.otp_interception", "La aplicación puede leer códigos de un solo uso de mensajes y notificaciones."),
+    ("flag.sms_access", "La aplicación puede leer SMS."),
+    ("flag.installs_packages", "La aplicación puede instalar otras aplicaciones."),
+    ("flag.device_admin", "La aplicación pide ser administradora del dispositivo."),
+    ("flag.contact_spread", "La aplicación puede enviar SMS a sus contactos."),
+    ("flag.package_claims_brand", "La aplicación {package} reclama una marca a la que no pertenece."),
+    ("flag.activex_controls", "El documento contiene controles ActiveX."),
+    ("flag.embedded_objects", "El documento contiene objetos incrustados."),
+    ("flag.external_ole_link", "El documento carga contenido desde un enlace externo."),
+    ("flag.macro_content_type", "El documento es de un tipo con macros."),
+    ("flag.protocol_handler_target", "El documento abre un programa externo mediante un enlace."),
+    ("flag.remote_template", "El documento descarga una plantilla de internet al abrirse."),
+    ("flag.vba_project", "El documento contiene macros."),
+    ("flag.all_streams_opaque", "No se pudo leer nada del contenido del PDF."),
+    ("flag.auto_run_on_open", "El PDF ejecuta una acción nada más abrirse."),
+    ("flag.embedded_file", "El PDF incluye un archivo incrustado."),
+    ("flag.javascript_action", "El PDF contiene JavaScript."),
+    ("flag.launch_action", "El PDF intenta iniciar un programa."),
+    ("flag.single_page_link_lure", "El PDF es una sola página construida en torno a un enlace."),
+    ("flag.submit_form", "El PDF envía datos de formulario a un sitio web."),
+    ("flag.auto_submit_form", "El adjunto envía un formulario por sí solo."),
+    ("flag.credential_form", "El adjunto contiene un formulario de contraseña."),
+    ("flag.credential_form_remote_action", "El formulario de contraseña del adjunto se envía a un sitio web."),
+    ("flag.event_handler", "El adjunto ejecuta código ante eventos."),
+    ("flag.html_smuggling", "El adjunto genera un archivo oculto dentro del navegador."),
+    ("flag.javascript_url", "El adjunto contiene enlaces JavaScript."),
+    ("flag.large_base64_blob", "El adjunto lleva una gran carga codificada."),
+    ("flag.meta_refresh", "El adjunto le redirige a un sitio web."),
+    ("flag.obfuscated_script", "El adjunto contiene código ofuscado a propósito."),
+    ("flag.script", "El adjunto contiene scripts."),
+    ("flag.script_redirect", "El adjunto usa código para enviarle a un sitio web."),
+    ("flag.svg_embedded_html", "La imagen contiene una página web oculta."),
+    ("flag.svg_script", "La imagen contiene scripts."),
+    ("flag.clipboard_hijack", "La dirección de pago que pegó no es la que copió."),
+    ("flag.lookalike_payment_address", "La dirección pegada se creó para parecerse a primera vista a la copiada."),
+    ("flag.confusable_domain", "La dirección está escrita para parecerse a {lookalike_of}."),
+    ("flag.mixed_script_label", "La dirección mezcla letras latinas con griegas o cirílicas."),
+    ("flag.registrable_domain_hidden", "La dirección es demasiado larga para mostrar su sitio real, {registrable_domain}."),
+    ("flag.visible_brand_lure", "La parte visible de la dirección nombra a {lookalike_of}, pero el sitio es otro."),
+    ("flag.intent_fallback_off_host", "Si falta la aplicación, el enlace lo lleva a {fallback_host}."),
+    ("flag.intent_fallback_risky", "La dirección alternativa del enlace parece insegura."),
+    ("flag.intent_fallback_script", "La alternativa del enlace ejecuta código en lugar de abrir un sitio web."),
+    ("flag.intent_package_mismatch", "El enlace abre la aplicación {package}, que no es de {brand}."),
+    ("flag.enterprise_app_install", "El enlace instala una aplicación desde fuera de la tienda oficial."),
+    ("flag.manifest_off_origin", "La aplicación se instalaría desde otro sitio, {manifest_host}."),
+    ("flag.sideload_from_untrusted_page", "Una página no confiable intenta instalar una aplicación en su dispositivo."),
+    ("flag.store_package_mismatch", "La ficha de la tienda es de {package}, que no es de {brand}."),
+    ("flag.store_redirect_without_gesture", "Una página abrió una ficha de la tienda de aplicaciones sin que usted lo pidiera."),
+    ("flag.endpoint_ip_host", "La página se comunica con un servidor por su dirección IP."),
+    ("flag.endpoint_private_network", "Un sitio web está contactando un dispositivo de su red local, {host}."),
+    ("flag.endpoint_raw_port", "La página se conecta a un servidor en un puerto poco habitual, {port}."),
+    ("flag.endpoint_suspicious_tld", "La página envía datos a un dominio con una terminación muy abusada."),
+    ("flag.endpoint_tunnel_host", "La página envía datos a través de un servicio de túnel temporal."),
+    ("flag.sni_host_mismatch", "La conexión dice ir a {sni}, pero solicita {target}."),
+    ("flag.sni_subdomain_mismatch", "La conexión se estableció para {sni}, pero solicita {target} en el mismo sitio."),
+    ("flag.sni_ip_literal", "La conexión indica una dirección IP, {sni}, en lugar de un sitio."),
+    ("flag.url_host_mismatch", "La solicitud de {url_host} va dirigida a otro sitio, {host_header}."),
+    ("flag.redirect_chain_long", "El enlace corto salta por {chain_length} redirecciones antes de llegar a su página."),
+    ("flag.shortener_chain", "El enlace se esconde tras {shortener_hops} acortadores de enlaces seguidos."),
+    ("flag.redirect_loop", "El enlace redirige en círculo y nunca llega a una página."),
+    ("flag.upi_reverse_payment", "Dice que vas a recibir dinero, pero si lo apruebas se envía dinero de tu cuenta a {payee}. Nunca necesitas tu PIN de UPI para recibir dinero."),
+    ("flag.upi_pull_request", "Es una solicitud de cobro: {payee} pide sacar dinero de tu cuenta, no enviarte nada."),
+    ("flag.upi_unknown_handle", "La dirección UPI usa un identificador, @{handle}, que no emite ningún banco ni aplicación de pago conocida."),
+    ("flag.upi_scam_prefix", "La dirección UPI {payee} se presenta como reembolsos, KYC o soporte; los bancos no cobran dinero con direcciones así."),
+    ("flag.data_uri_document", "El enlace lleva una página web completa dentro en lugar de apuntar a un sitio."),
+    ("flag.data_uri_script", "El enlace lleva un script dentro."),
+    ("flag.data_uri_form", "La página dentro del enlace contiene un formulario; lo que escribas va a quien lo creó."),
+    ("flag.data_uri_mime_mismatch", "El enlace dice contener {mime} pero en realidad contiene {content_kind}."),
+    ("flag.data_uri_executable", "El enlace lleva un programa ({content_kind}) dentro."),
+    ("flag.data_uri_malformed", "El contenido codificado del enlace está dañado o alterado a propósito."),
+    ("flag.javascript_scheme", "El enlace es un script que se ejecuta dentro de la página desde la que lo abres."),
+    ("flag.js_obfuscated", "El script oculta lo que hace tras codificación o eval."),
+    ("flag.js_writes_page", "El script sustituye la página por contenido propio."),
+    ("flag.js_credential_prompt", "El script pide una contraseña, un código o datos de tarjeta."),
+    ("flag.js_redirect", "El script te envía a ti, o lo que escribes, a otra dirección."),
+    ("flag.blob_scheme", "El enlace apunta a contenido que una página de {origin} montó dentro del navegador."),
+    ("flag.blob_opaque_origin", "El enlace apunta a contenido montado por una página oculta o aislada."),
+    ("flag.mailto_lookalike_domain", "El enlace escribe a {domain}, que imita a {brand}."),
+    ("flag.mailto_display_spoof", "La dirección que se muestra como remitente no es a donde va el correo: {domain}."),
+    ("flag.mailto_brand_freemail", "El enlace escribe a una cuenta de correo gratuita en {domain} con el nombre de {brand}."),
+    ("flag.contact_text_mismatch", "El enlace muestra {shown} pero abre otra dirección o número."),
+    ("flag.tel_premium_rate", "El número {number} es una línea de tarificación especial cobrada por minuto."),
+    ("flag.tel_high_risk_prefix", "El número {number} usa un prefijo habitual en el fraude de llamadas."),
+    ("flag.tel_ussd", "Marcar {number} ejecuta un código de servicio del teléfono, que puede desviar tus llamadas."),
+    ("flag.tel_international", "El número es una llamada internacional."),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("verdict.allow", "Aucun signe d'hameçonnage ni de logiciel malveillant n'a été trouvé."),
+    ("verdict.warn", "Ceci semble suspect. Soyez prudent."),
+    ("verdict.block", "Ceci est très probablement dangereux et a été bloqué."),
+    ("flag.unknown", "Signal suspect : {flag}."),
+    ("finding.exfiltration.messaging_webhook", "Les données sont envoyées directement à un bot {service} ({endpoint}), un moyen courant de voler des mots de passe."),
+    ("finding.evasion.domain_fronting", "La connexion se présente au réseau comme {sni} mais se dirige en réalité vers {host}, une ruse pour masquer la destination du trafic."),
+    ("flag.custom_brand_impersonation", "L'adresse imite une marque que vous avez demandé à protéger."),
+    ("flag.brand_path_mimicry", "Le lien reprend le chemin d'une page connue sur un autre domaine."),
+    ("flag.keyword_pack_match", "L'adresse contient des mots d'une liste d'hameçonnage chargée."),
+    ("flag.keyword_stuffing", "L'adresse accumule plusieurs mots de connexion, de paiement ou de gain."),
+    ("flag.kit_fingerprint_path", "L'adresse utilise des noms de fichiers et de dossiers typiques des kits d'hameçonnage déposés sur des sites piratés."),
+    ("flag.deep_random_path", "La page est enfouie sous des dossiers aux noms aléatoires d'un site connu."),
+    ("flag.login_form_on_compromised_path", "Un formulaire de connexion se trouve dans une partie cachée, visiblement plantée, d'un site connu."),
+    ("flag.homoglyph_impersonation", "L'adresse écrit une marque connue avec des lettres semblables d'un autre alphabet."),
+    ("flag.brand_alias_impersonation", "L'adresse écrit le nom d'une marque connue dans une autre écriture ou en toutes lettres, sur un site qui n'est pas le sien."),
+    ("flag.numeric_brand_lookalike", "L'adresse écrit le nom court d'une banque ou d'une marque avec des chiffres ou des lettres semblables, comme 1cici pour ICICI."),
+    ("flag.nested_encoding", "L'adresse est encodée plusieurs fois de suite, une astuce pour passer les filtres."),
+    ("flag.encoded_traversal", "Une fois décodée, l'adresse sort de son dossier, cachée sous plusieurs couches d'encodage."),
+    ("flag.encoded_keyword", "Une fois décodée, l'adresse contient des mots de connexion, de paiement ou de gain que son encodage cache."),
+    ("flag.idn_brand_risky_tld", "Une adresse encodée aux lettres semblables imite une marque connue sur une extension de domaine souvent utilisée pour des sites jetables."),
+    ("flag.alias_brand_risky_tld", "L'adresse écrit le nom d'une marque connue dans une autre écriture sur une extension de domaine souvent utilisée pour des sites jetables."),
+    ("flag.brand_login_risky_tld", "Un nom de marque mal orthographié, une page de connexion et une extension de domaine jetable apparaissent ensemble, un schéma d'hameçonnage classique."),
+    ("flag.ip_host_login", "Une page de connexion est servie depuis une adresse numérique au lieu d'un site nommé."),
+    ("flag.upi_collect_unknown_handle", "Cette demande de paiement encaisse de l'argent pour une adresse UPI au identifiant inconnu ou au préfixe d'arnaque."),
+    ("flag.redirect_to_executable", "Un lien de redirection vous envoie directement vers le téléchargement d'un programme."),
+    ("flag.allowlisted", "Vous ou votre administrateur avez marqué ce site comme fiable."),
+    ("flag.blocklisted", "Vous ou votre administrateur avez bloqué ce site."),
+    ("flag.managed_allow", "La politique de votre organisation autorise ce site."),
+    ("flag.managed_block", "La politique de votre organisation bloque ce site."),
+    ("flag.threat_feed_match", "Ce site figure sur une liste de menaces."),
+    ("flag.bad_reputation", "Ce site a déjà paru risqué à plusieurs reprises."),
+    ("flag.kit_path_on_known_site", "Cette adresse utilise un dossier que les kits d'hameçonnage déposent sur les sites piratés, contrairement au reste du site."),
+    ("flag.unusual_site_template", "Cette adresse ne ressemble pas aux pages que ce site sert d'habitude."),
+    ("flag.personal_adjustment", "Vos décisions précédentes d'autoriser ou de bloquer ont modifié ce score."),
+    ("flag.password_over_http", "Un formulaire de mot de passe est envoyé sans chiffrement."),
+    ("flag.credentials_posted_offsite", "Le formulaire de connexion envoie votre mot de passe à un autre site risqué."),
+    ("flag.password_in_query", "Le formulaire de connexion place votre mot de passe dans l'adresse web."),
+    ("flag.autofill_blocked", "Le remplissage automatique des mots de passe a été désactivé sur cette page."),
+    ("flag.lure_text", "Le texte de la page utilise des formules typiques d'hameçonnage."),
+    ("flag.credential_page_weak_csp", "Cette page de connexion protège peu ou pas ses scripts, contrairement aux vraies pages de connexion."),
+    ("flag.messaging_webhook_exfil", "La page envoie les données du formulaire à un bot de messagerie, un moyen courant de voler des mots de passe."),
+    ("flag.credential_sink", "La page envoie des données à un service externe de collecte de formulaires."),
+    ("flag.messaging_webhook", "La page contacte un webhook de bot de messagerie."),
+    ("flag.form_off_script_origin", "Un formulaire est envoyé à un site dont ne provient aucun code de la page."),
+    ("flag.self_signed", "Le certificat du site est auto-signé."),
+    ("flag.name_mismatch", "Le certificat ne couvre pas {host}."),
+    ("flag.fresh_certificate", "Le certificat a été émis au cours des deux derniers jours."),
+    ("flag.recent_certificate", "Le certificat a été émis au cours des deux dernières semaines."),
+    ("flag.bulk_san_list", "Le certificat couvre un nombre inhabituel de noms."),
+    ("flag.over_long_validity", "Le certificat est valide plus longtemps que ne le permettent les certificats publics."),
+    ("flag.brand_in_certificate_host", "Le certificat de {host} mentionne une marque à laquelle il n'appartient pas."),
+    ("flag.brand_from_freemail", "Un message prétendant venir d'une marque a été envoyé depuis une messagerie gratuite."),
+    ("flag.display_name_address_spoof", "Le nom de l'expéditeur affiche une autre adresse e-mail."),
+    ("flag.display_name_brand_mismatch", "Le nom de l'expéditeur revendique une marque à laquelle son adresse n'appartient pas."),
+    ("flag.dmarc_record_invalid", "Le domaine de l'expéditeur a un enregistrement DMARC invalide."),
+    ("flag.reply_to_divergence", "Les réponses iraient à un autre domaine que celui de l'expéditeur."),
+    ("flag.reply_to_freemail", "Les réponses iraient à une messagerie gratuite."),
+    ("flag.sender_brand_on_foreign_host", "Le domaine de l'expéditeur contient le nom d'une marque à laquelle il n'appartient pas."),
+    ("flag.sender_domain_lookalike", "Le domaine de l'expéditeur imite un domaine connu."),
+    ("flag.spf_permissive", "Le domaine de l'expéditeur permet à n'importe quel serveur d'envoyer des e-mails en son nom."),
+    ("flag.text_href_mismatch", "Un lien affiche une adresse mais mène à une autre."),
+    ("flag.dmarc_fail", "Le message a échoué au contrôle DMARC du domaine de l'expéditeur."),
+    ("flag.dmarc_none", "Le domaine de l'expéditeur ne publie pas de politique DMARC."),
+    ("flag.dmarc_policy_none", "Le domaine de l'expéditeur n'applique pas sa politique DMARC."),
+    ("flag.spf_fail", "Le message provient d'un serveur que le domaine de l'expéditeur n'autorise pas."),
+    ("flag.spf_softfail", "Le message provient d'un serveur auquel le domaine de l'expéditeur ne fait pas entièrement confiance."),
+    ("flag.dkim_fail", "La signature du message n'est pas valide."),
+    ("flag.dkim_unaligned", "Le message est signé par un domaine sans rapport."),
+    ("flag.inline_binary_attachment", "L'invitation contient un fichier intégré."),
+    ("flag.no_organizer", "L'invitation n'a pas d'organisateur."),
+    ("flag.organizer_brand_mismatch", "L'organisateur revendique une marque à laquelle son adresse n'appartient pas."),
+    ("flag.organizer_cn_address_spoof", "Le nom de l'organisateur affiche une autre adresse e-mail."),
+    ("flag.organizer_domain_lookalike", "Le domaine de l'organisateur imite un domaine connu."),
+    ("flag.organizer_sent_by_mismatch", "Un autre domaine a envoyé l'invitation au nom de l'organisateur."),
+    ("flag.broad_scopes", "L'application demande un accès étendu à votre compte."),
+    ("flag.broad_scopes_with_offline_access", "L'application demande un accès étendu qui persiste après votre départ."),
+    ("flag.device_code_flow", "Cette connexion utilise un code d'appareil, une méthode détournée pour prendre le contrôle de comptes."),
+    ("flag.device_code_prefilled", "Le code d'appareil a été prérempli ; ne saisissez que des codes affichés sur votre propre appareil."),
+    ("flag.forced_consent_offline", "L'application impose une demande de consentement pour un accès durable."),
+    ("flag.implicit_grant", "L'application utilise une méthode de connexion obsolète qui expose les jetons."),
+    ("flag.insecure_authorize_endpoint", "La page de connexion n'est pas chiffrée."),
+    ("flag.lookalike_idp", "La page de connexion imite un fournisseur d'identité connu."),
+    ("flag.unknown_idp", "Ce fournisseur de connexion nous est inconnu."),
+    ("flag.redirect_chained_url", "Après la connexion, vous seriez renvoyé vers un autre lien."),
+    ("flag.redirect_ip_host", "Après la connexion, vous seriez envoyé vers une simple adresse IP."),
+    ("flag.redirect_missing", "La demande de connexion n'indique pas où elle revient."),
+    ("flag.redirect_oob", "Le résultat de la connexion s'affiche pour être copié au lieu d'être renvoyé à l'application."),
+    ("flag.redirect_same_site_as_idp", "L'adresse de retour se trouve sur le site même du fournisseur de connexion."),
+    ("flag.redirect_shortener", "Après la connexion, vous passeriez par un raccourcisseur de liens."),
+    ("flag.redirect_suspicious_tld", "Après la connexion, vous seriez envoyé vers un domaine à extension très abusée."),
+    ("flag.redirect_tunnel_host", "Après la connexion, vous seriez envoyé vers un tunnel temporaire."),
+    ("flag.extension_content_mismatch", "Le contenu du fichier ne correspond pas à son extension."),
+    ("flag.mime_mismatch", "Le serveur décrit le fichier comme étant d'un autre type."),
+    ("flag.bidi_control", "Le nom du fichier utilise des caractères cachés de sens d'écriture."),
+    ("flag.control_characters", "Le nom du fichier contient des caractères de contrôle cachés."),
+    ("flag.extension_disguised", "Le nom du fichier cache sa véritable extension."),
+    ("flag.fallback_name_mismatch", "Le fichier propose deux noms différents."),
+    ("flag.path_in_filename", "Le nom du fichier contient un chemin de dossier."),
+    ("flag.rtlo_override", "Le nom du fichier est inversé pour masquer son extension."),
+    ("flag.trailing_dot_or_space", "Le nom du fichier se termine par un point ou une espace pour le déguiser."),
+    ("flag.contains_executable", "L'archive contient un programme."),
+    ("flag.deeply_nested_archive", "L'archive est emballée dans plusieurs autres archives."),
+    ("flag.disk_image_container", "Le téléchargement est une image disque."),
+    ("flag.disk_image_with_executable", "L'image disque contient un programme."),
+    ("flag.encrypted_archive", "L'archive est protégée par mot de passe, son contenu ne peut donc pas être vérifié."),
+    ("flag.encrypted_executable", "L'archive protégée par mot de passe contient un programme."),
+    ("flag.encrypted_headers", "L'archive cache jusqu'aux noms de ses fichiers."),
+    ("flag.password_in_filename", "Le mot de passe de l'archive figure dans son nom."),
+    ("flag.password_in_page_text", "Le mot de passe de l'archive figure sur la page."),
+    ("flag.password_unlocks_archive", "Le mot de passe indiqué ouvre bien l'archive."),
+    ("flag.accessibility_with_overlay", "L'application peut lire votre écran et s'afficher par-dessus d'autres applications."),
+    ("flag.banking_trojan_combo", "L'application demande les autorisations qu'utilisent les chevaux de Troie bancaires pour voler identifiants et codes."),
+    ("flag.sms_with_screen_control", "L'application peut lire les SMS et contrôler l'écran."),
+    ("flag.otp_interception", "L'application peut lire les codes à usage unique des messages et notifications."),
+    ("flag.sms_access", "L'application peut lire les SMS."),
+    ("flag.installs_packages", "L'application peut installer d'autres applications."),
+    ("flag.device_admin", "L'application demande à devenir administrateur de l'appareil."),
+    ("flag.contact_spread", "L'application peut envoyer des SMS à vos contacts."),
+    ("flag.package_claims_brand", "L'application {package} revendique une marque à laquelle elle n'appartient pas."),
+    ("flag.activex_controls", "Le document contient des contrôles ActiveX."),
+    ("flag.embedded_objects", "Le document contient des objets intégrés."),
+    ("flag.external_ole_link", "Le document charge du contenu depuis un lien externe."),
+    ("flag.macro_content_type", "Le document est d'un type prenant en charge les macros."),
+    ("flag.protocol_handler_target", "Le document ouvre un programme externe via un lien."),
+    ("flag.remote_template", "Le document télécharge un modèle depuis Internet à l'ouverture."),
+    ("flag.vba_project", "Le document contient des macros."),
+    ("flag.all_streams_opaque", "Aucun contenu du PDF n'a pu être lu."),
+    ("flag.auto_run_on_open", "Le PDF exécute une action dès son ouverture."),
+    ("flag.embedded_file", "Le PDF contient un fichier intégré."),
+    ("flag.javascript_action", "Le PDF contient du JavaScript."),
+    ("flag.launch_action", "Le PDF tente de lancer un programme."),
+    ("flag.single_page_link_lure", "Le PDF tient en une seule page construite autour d'un lien."),
+    ("flag.submit_form", "Le PDF envoie des données de formulaire à un site web."),
+    ("flag.auto_submit_form", "La pièce jointe envoie un formulaire d'elle-même."),
+    ("flag.credential_form", "La pièce jointe contient un formulaire de mot de passe."),
+    ("flag.credential_form_remote_action", "Le formulaire de mot de passe de la pièce jointe est envoyé à un site web."),
+    ("flag.event_handler", "La pièce jointe exécute du code lors d'événements."),
+    ("flag.html_smuggling", "La pièce jointe assemble un fichier caché dans le navigateur."),
+    ("flag.javascript_url", "La pièce jointe contient des liens JavaScript."),
+    ("flag.large_base64_blob", "La pièce jointe transporte une grosse charge encodée."),
+    ("flag.meta_refresh", "La pièce jointe vous redirige vers un site web."),
+    ("flag.obfuscated_script", "La pièce jointe contient du code volontairement obscurci."),
+    ("flag.script", "La pièce jointe contient des scripts."),
+    ("flag.script_redirect", "La pièce jointe utilise du code pour vous envoyer vers un site web."),
+    ("flag.svg_embedded_html", "L'image contient une page web cachée."),
+    ("flag.svg_script", "L'image contient des scripts."),
+    ("flag.clipboard_hijack", "L'adresse de paiement collée n'est pas celle que vous avez copiée."),
+    ("flag.lookalike_payment_address", "L'adresse collée a été conçue pour ressembler au premier coup d'œil à celle copiée."),
+    ("flag.confusable_domain", "L'adresse est écrite pour ressembler à {lookalike_of}."),
+    ("flag.mixed_script_label", "L'adresse mélange des lettres latines avec des lettres grecques ou cyrilliques."),
+    ("flag.registrable_domain_hidden", "L'adresse est trop longue pour afficher son vrai site, {registrable_domain}."),
+    ("flag.visible_brand_lure", "La partie visible de l'adresse nomme {lookalike_of}, mais le site en est un autre."),
+    ("flag.intent_fallback_off_host", "Si l'application est absente, le lien vous envoie plutôt vers {fallback_host}."),
+    ("flag.intent_fallback_risky", "L'adresse de secours du lien semble dangereuse."),
+    ("flag.intent_fallback_script", "Le lien de secours exécute du code au lieu d'ouvrir un site web."),
+    ("flag.intent_package_mismatch", "Le lien ouvre l'application {package}, qui n'est pas de {brand}."),
+    ("flag.enterprise_app_install", "Le lien installe une application hors de la boutique officielle."),
+    ("flag.manifest_off_origin", "L'application serait installée depuis un autre site, {manifest_host}."),
+    ("flag.sideload_from_untrusted_page", "Une page non fiable tente d'installer une application sur votre appareil."),
+    ("flag.store_package_mismatch", "La fiche de la boutique concerne {package}, qui n'est pas de {brand}."),
+    ("flag.store_redirect_without_gesture", "Une page a ouvert une fiche de boutique d'applications sans votre demande."),
+    ("flag.endpoint_ip_host", "La page communique avec un serveur par son adresse IP."),
+    ("flag.endpoint_private_network", "Un site web contacte un appareil de votre réseau local, {host}."),
+    ("flag.endpoint_raw_port", "La page se connecte à un serveur sur un port inhabituel, {port}."),
+    ("flag.endpoint_suspicious_tld", "La page envoie des données à un domaine dont l'extension est très abusée."),
+    ("flag.endpoint_tunnel_host", "La page envoie des données via un service de tunnel temporaire."),
+    ("flag.sni_host_mismatch", "La connexion prétend aller vers {sni} mais demande {target}."),
+    ("flag.sni_subdomain_mismatch", "La connexion a été établie pour {sni} mais demande {target} sur le même site."),
+    ("flag.sni_ip_literal", "La connexion indique une adresse IP, {sni}, au lieu d'un site."),
+    ("flag.url_host_mismatch", "La requête pour {url_host} est adressée à un autre site, {host_header}."),
+    ("flag.redirect_chain_long", "Le lien court rebondit sur {chain_length} redirections avant d'atteindre sa page."),
+    ("flag.shortener_chain", "Le lien se cache derrière {shortener_hops} raccourcisseurs de liens à la suite."),
+    ("flag.redirect_loop", "Le lien redirige en boucle et n'atteint jamais de page."),
+    ("flag.upi_reverse_payment", "Le message dit que vous allez recevoir de l'argent, mais l'approuver envoie de l'argent de votre compte à {payee}. Recevoir de l'argent ne demande jamais votre code PIN UPI."),
+    ("flag.upi_pull_request", "C'est une demande d'encaissement : {payee} demande à prélever de l'argent sur votre compte, pas à vous en envoyer."),
+    ("flag.upi_unknown_handle", "L'adresse UPI utilise un identifiant, @{handle}, qu'aucune banque ni application de paiement connue ne délivre."),
+    ("flag.upi_scam_prefix", "L'adresse UPI {payee} se présente comme un service de remboursement, de KYC ou d'assistance ; les banques n'encaissent pas d'argent par de telles adresses."),
+    ("flag.data_uri_document", "Le lien contient une page web entière au lieu de pointer vers un site."),
+    ("flag.data_uri_script", "Le lien contient un script."),
+    ("flag.data_uri_form", "La page contenue dans le lien comporte un formulaire ; ce que vous saisissez part chez son auteur."),
+    ("flag.data_uri_mime_mismatch", "Le lien prétend contenir {mime} mais contient en réalité {content_kind}."),
+    ("flag.data_uri_executable", "Le lien contient un programme ({content_kind})."),
+    ("flag.data_uri_malformed", "Le contenu encodé du lien est endommagé ou brouillé exprès."),
+    ("flag.javascript_scheme", "Le lien est un script qui s'exécute dans la page depuis laquelle vous l'ouvrez."),
+    ("flag.js_obfuscated", "Le script cache ce qu'il fait derrière un encodage ou eval."),
+    ("flag.js_writes_page", "Le script remplace la page par son propre contenu."),
+    ("flag.js_credential_prompt", "Le script demande un mot de passe, un code ou des données de carte."),
+    ("flag.js_redirect", "Le script vous envoie, vous ou ce que vous saisissez, vers une autre adresse."),
+    ("flag.blob_scheme", "Le lien pointe vers un contenu qu'une page de {origin} a assemblé dans le navigateur."),
+    ("flag.blob_opaque_origin", "Le lien pointe vers un contenu assemblé par une page cachée ou isolée."),
+    ("flag.mailto_lookalike_domain", "Le lien écrit à {domain}, qui imite {brand}."),
+    ("flag.mailto_display_spoof", "L'adresse affichée comme expéditeur n'est pas celle où part le courriel : {domain}."),
+    ("flag.mailto_brand_freemail", "Le lien écrit à un compte de messagerie gratuit sur {domain} au nom de {brand}."),
+    ("flag.contact_text_mismatch", "Le lien affiche {shown} mais ouvre une autre adresse ou un autre numéro."),
+    ("flag.tel_premium_rate", "Le numéro {number} est une ligne surtaxée facturée à la minute."),
+    ("flag.tel_high_risk_prefix", "Le numéro {number} utilise un préfixe courant dans la fraude aux appels."),
+    ("flag.tel_ussd", "Composer {number} lance un code de service du téléphone, qui peut renvoyer vos appels."),
+    ("flag.tel_international", "Le numéro est un appel international."),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("verdict.allow", "Es wurden keine Anzeichen von Phishing oder Schadsoftware gefunden."),
+    ("verdict.warn", "Das wirkt verdächtig. Seien Sie vorsichtig."),
+    ("verdict.block", "Das ist sehr wahrscheinlich gefährlich und wurde blockiert."),
+    ("flag.unknown", "Verdächtiges Signal: {flag}."),
+    ("finding.exfiltration.messaging_webhook", "Daten werden direkt an einen {service}-Bot gesendet ({endpoint}) – ein gängiger Weg, Passwörter zu stehlen."),
+    ("finding.evasion.domain_fronting", "Die Verbindung gibt sich im Netzwerk als {sni} aus, geht aber eigentlich zu {host} – ein Trick, um das Ziel des Datenverkehrs zu verbergen."),
+    ("flag.custom_brand_impersonation", "Die Adresse ahmt eine Marke nach, die Sie schützen wollten."),
+    ("flag.brand_path_mimicry", "Der Link kopiert den Seitenpfad einer bekannten Website auf einer fremden Domain."),
+    ("flag.keyword_pack_match", "Die Adresse enthält Wörter aus einer geladenen Phishing-Wortliste."),
+    ("flag.keyword_stuffing", "Die Adresse häuft mehrere Anmelde-, Zahlungs- oder Gewinnwörter an."),
+    ("flag.kit_fingerprint_path", "Die Adresse nutzt Datei- und Ordnernamen, die für auf gehackten Websites abgelegte Phishing-Kits typisch sind."),
+    ("flag.deep_random_path", "Die Seite liegt versteckt unter zufällig benannten Ordnern einer bekannten Website."),
+    ("flag.login_form_on_compromised_path", "Ein Anmeldeformular steht in einem versteckten, offenbar eingeschleusten Bereich einer bekannten Website."),
+    ("flag.homoglyph_impersonation", "Die Adresse schreibt eine bekannte Marke mit ähnlich aussehenden Buchstaben aus einem anderen Alphabet."),
+    ("flag.brand_alias_impersonation", "Die Adresse schreibt den Namen einer bekannten Marke in einer anderen Schrift oder ausgeschrieben, auf einer Website, die nicht der Marke gehört."),
+    ("flag.numeric_brand_lookalike", "Die Adresse schreibt den kurzen Namen einer Bank oder Marke mit Ziffern oder ähnlichen Buchstaben, etwa 1cici statt ICICI."),
+    ("flag.nested_encoding", "Die Adresse ist mehrfach hintereinander kodiert, ein Trick, um Filter zu umgehen."),
+    ("flag.encoded_traversal", "Dekodiert verlässt die Adresse ihren Ordner, versteckt unter mehreren Kodierungsschichten."),
+    ("flag.encoded_keyword", "Dekodiert enthält die Adresse Anmelde-, Zahlungs- oder Gewinnwörter, die ihre Kodierung versteckt."),
+    ("flag.idn_brand_risky_tld", "Eine kodierte Adresse mit ähnlich aussehenden Buchstaben ahmt eine bekannte Marke auf einer Domain-Endung nach, die oft für Wegwerf-Websites genutzt wird."),
+    ("flag.alias_brand_risky_tld", "Die Adresse schreibt den Namen einer bekannten Marke in einer anderen Schrift auf einer Domain-Endung, die oft für Wegwerf-Websites genutzt wird."),
+    ("flag.brand_login_risky_tld", "Ein falsch geschriebener Markenname, eine Anmeldeseite und eine Wegwerf-Domain-Endung treten zusammen auf, ein klassisches Phishing-Muster."),
+    ("flag.ip_host_login", "Eine Anmeldeseite wird von einer reinen Zahlenadresse statt von einer benannten Website ausgeliefert."),
+    ("flag.upi_collect_unknown_handle", "Diese Zahlungsanforderung zieht Geld für eine UPI-Adresse mit unbekanntem Handle oder Betrugspräfix ein."),
+    ("flag.redirect_to_executable", "Ein Weiterleitungslink führt Sie direkt zum Download eines Programms."),
+    ("flag.allowlisted", "Sie oder Ihr Administrator haben diese Website als vertrauenswürdig markiert."),
+    ("flag.blocklisted", "Sie oder Ihr Administrator haben diese Website blockiert."),
+    ("flag.managed_allow", "Die Richtlinie Ihrer Organisation erlaubt diese Website."),
+    ("flag.managed_block", "Die Richtlinie Ihrer Organisation blockiert diese Website."),
+    ("flag.threat_feed_match", "Diese Website steht auf einer Bedrohungsliste."),
+    ("flag.bad_reputation", "Diese Website wirkte schon mehrfach riskant."),
+    ("flag.kit_path_on_known_site", "Diese Adresse nutzt einen Ordner, den Phishing-Kits auf gehackten Websites ablegen – anders als der Rest dieser Website."),
+    ("flag.unusual_site_template", "Diese Adresse sieht nicht aus wie die Seiten, die diese Website sonst ausliefert."),
+    ("flag.personal_adjustment", "Ihre früheren Entscheidungen zum Zulassen und Blockieren haben diese Bewertung verändert."),
+    ("flag.password_over_http", "Ein Passwortformular wird hier unverschlüsselt gesendet."),
+    ("flag.credentials_posted_offsite", "Das Anmeldeformular sendet Ihr Passwort an eine andere, riskante Website."),
+    ("flag.password_in_query", "Das Anmeldeformular schreibt Ihr Passwort in die Webadresse."),
+    ("flag.autofill_blocked", "Das automatische Ausfüllen von Passwörtern wurde für diese Seite deaktiviert."),
+    ("flag.lure_text", "Der Seitentext verwendet typische Phishing-Formulierungen."),
+    ("flag.credential_page_weak_csp", "Diese Anmeldeseite schützt ihre Skripte kaum oder gar nicht, anders als echte Anmeldeseiten."),
+    ("flag.messaging_webhook_exfil", "Die Seite sendet Formulardaten an einen Chat-Bot – ein gängiger Weg, Passwörter zu stehlen."),
+    ("flag.credential_sink", "Die Seite sendet Daten an einen externen Formular-Sammeldienst."),
+    ("flag.messaging_webhook", "Die Seite kontaktiert einen Chat-Bot-Webhook."),
+    ("flag.form_off_script_origin", "Ein Formular wird an eine Website gesendet, von der kein Code der Seite stammt."),
+    ("flag.self_signed", "Das Zertifikat der Website ist selbstsigniert."),
+    ("flag.name_mismatch", "Das Zertifikat gilt nicht für {host}."),
+    ("flag.fresh_certificate", "Das Zertifikat wurde in den letzten zwei Tagen ausgestellt."),
+    ("flag.recent_certificate", "Das Zertifikat wurde in den letzten zwei Wochen ausgestellt."),
+    ("flag.bulk_san_list", "Das Zertifikat gilt für ungewöhnlich viele Namen."),
+    ("flag.over_long_validity", "Das Zertifikat ist länger gültig, als es öffentliche Zertifikate sein dürfen."),
+    ("flag.brand_in_certificate_host", "Das Zertifikat für {host} nennt eine Marke, zu der es nicht gehört."),
+    ("flag.brand_from_freemail", "Eine Nachricht, die angeblich von einer Marke stammt, kam von einem kostenlosen E-Mail-Konto."),
+    ("flag.display_name_address_spoof", "Der Anzeigename des Absenders zeigt eine andere E-Mail-Adresse."),
+    ("flag.display_name_brand_mismatch", "Der Name des Absenders beansprucht eine Marke, zu der seine Adresse nicht gehört."),
+    ("flag.dmarc_record_invalid", "Die Domain des Absenders hat einen fehlerhaften DMARC-Eintrag."),
+    ("flag.reply_to_divergence", "Antworten würden an eine andere Domain als die des Absenders gehen."),
+    ("flag.reply_to_freemail", "Antworten würden an ein kostenloses E-Mail-Konto gehen."),
+    ("flag.sender_brand_on_foreign_host", "Die Domain des Absenders enthält einen Markennamen, zu dem sie nicht gehört."),
+    ("flag.sender_domain_lookalike", "Die Domain des Absenders ahmt eine bekannte Domain nach."),
+    ("flag.spf_permissive", "Die Domain des Absenders erlaubt jedem Server, in ihrem Namen E-Mails zu senden."),
+    ("flag.text_href_mismatch", "Ein Link zeigt eine Adresse an, führt aber zu einer anderen."),
+    ("flag.dmarc_fail", "Die Nachricht hat die DMARC-Prüfung der Absenderdomain nicht bestanden."),
+    ("flag.dmarc_none", "Die Domain des Absenders veröffentlicht keine DMARC-Richtlinie."),
+    ("flag.dmarc_policy_none", "Die Domain des Absenders setzt ihre DMARC-Richtlinie nicht durch."),
+    ("flag.spf_fail", "Die Nachricht kam von einem Server, den die Absenderdomain nicht zulässt."),
+    ("flag.spf_softfail", "Die Nachricht kam von einem Server, dem die Absenderdomain nicht voll vertraut."),
+    ("flag.dkim_fail", "Die Signatur der Nachricht ist ungültig."),
+    ("flag.dkim_unaligned", "Die Nachricht ist von einer fremden Domain signiert."),
+    ("flag.inline_binary_attachment", "Die Einladung enthält eine eingebettete Datei."),
+    ("flag.no_organizer", "Die Einladung hat keinen Organisator."),
+    ("flag.organizer_brand_mismatch", "Der Organisator beansprucht eine Marke, zu der seine Adresse nicht gehört."),
+    ("flag.organizer_cn_address_spoof", "Der Name des Organisators zeigt eine andere E-Mail-Adresse."),
+    ("flag.organizer_domain_lookalike", "Die Domain des Organisators ahmt eine bekannte Domain nach."),
+    ("flag.organizer_sent_by_mismatch", "Eine andere Domain hat die Einladung im Namen des Organisators gesendet."),
+    ("flag.broad_scopes", "Die App verlangt weitreichenden Zugriff auf Ihr Konto."),
+    ("flag.broad_scopes_with_offline_access", "Die App verlangt weitreichenden Zugriff, der auch nach dem Abmelden bestehen bleibt."),
+    ("flag.device_code_flow", "Diese Anmeldung nutzt einen Gerätecode – ein Verfahren, das oft zur Kontoübernahme missbraucht wird."),
+    ("flag.device_code_prefilled", "Der Gerätecode war bereits ausgefüllt; geben Sie nur Codes ein, die Ihr eigenes Gerät anzeigt."),
+    ("flag.forced_consent_offline", "Die App erzwingt eine Zustimmung für dauerhaften Zugriff."),
+    ("flag.implicit_grant", "Die App nutzt ein veraltetes Anmeldeverfahren, das Tokens preisgibt."),
+    ("flag.insecure_authorize_endpoint", "Die Anmeldeseite ist nicht verschlüsselt."),
+    ("flag.lookalike_idp", "Die Anmeldeseite ahmt einen bekannten Identitätsanbieter nach."),
+    ("flag.unknown_idp", "Dieser Anmeldeanbieter ist uns nicht bekannt."),
+    ("flag.redirect_chained_url", "Nach der Anmeldung würden Sie über einen weiteren Link weitergeleitet."),
+    ("flag.redirect_ip_host", "Nach der Anmeldung würden Sie an eine bloße IP-Adresse weitergeleitet."),
+    ("flag.redirect_missing", "Die Anmeldeanfrage gibt nicht an, wohin sie zurückkehrt."),
+    ("flag.redirect_oob", "Das Anmeldeergebnis wird zum Kopieren angezeigt, statt an die App zurückzugehen."),
+    ("flag.redirect_same_site_as_idp", "Die Rücksprungadresse liegt auf der Website des Anmeldeanbieters selbst."),
+    ("flag.redirect_shortener", "Nach der Anmeldung würden Sie über einen Link-Kürzer weitergeleitet."),
+    ("flag.redirect_suspicious_tld", "Nach der Anmeldung würden Sie auf eine Domain mit häufig missbrauchter Endung weitergeleitet."),
+    ("flag.redirect_tunnel_host", "Nach der Anmeldung würden Sie auf einen temporären Tunnel weitergeleitet."),
+    ("flag.extension_content_mismatch", "Der Inhalt der Datei passt nicht zu ihrer Endung."),
+    ("flag.mime_mismatch", "Der Server gibt für die Datei einen anderen Typ an."),
+    ("flag.bidi_control", "Der Dateiname enthält versteckte Zeichen zur Schreibrichtung."),
+    ("flag.control_characters", "Der Dateiname enthält versteckte Steuerzeichen."),
+    ("flag.extension_disguised", "Der Dateiname verbirgt seine echte Endung."),
+    ("flag.fallback_name_mismatch", "Die Datei bietet zwei verschiedene Namen an."),
+    ("flag.path_in_filename", "Der Dateiname enthält einen Ordnerpfad."),
+    ("flag.rtlo_override", "Der Dateiname ist umgekehrt, um seine Endung zu tarnen."),
+    ("flag.trailing_dot_or_space", "Der Dateiname endet zur Tarnung auf einen Punkt oder ein Leerzeichen."),
+    ("flag.contains_executable", "Das Archiv enthält ein Programm."),
+    ("flag.deeply_nested_archive", "Das Archiv steckt in mehreren anderen Archiven."),
+    ("flag.disk_image_container", "Der Download ist ein Disk-Image."),
+    ("flag.disk_image_with_executable", "Das Disk-Image enthält ein Programm."),
+    ("flag.encrypted_archive", "Das Archiv ist passwortgeschützt, sein Inhalt kann daher nicht geprüft werden."),
+    ("flag.encrypted_executable", "Das passwortgeschützte Archiv enthält ein Programm."),
+    ("flag.encrypted_headers", "Das Archiv verbirgt sogar seine Dateinamen."),
+    ("flag.password_in_filename", "Das Passwort des Archivs steht in seinem Dateinamen."),
+    ("flag.password_in_page_text", "Das Passwort des Archivs steht auf der Seite."),
+    ("flag.password_unlocks_archive", "Das angegebene Passwort öffnet das Archiv tatsächlich."),
+    ("flag.accessibility_with_overlay", "Die App kann Ihren Bildschirm auslesen und sich über andere Apps legen."),
+    ("flag.banking_trojan_combo", "Die App verlangt die Berechtigungen, mit denen Banking-Trojaner Zugangsdaten und Codes stehlen."),
+    ("flag.sms_with_screen_control", "Die App kann SMS lesen und den Bildschirm steuern."),
+    ("flag.otp_interception", "Die App kann Einmalcodes aus Nachrichten und Benachrichtigungen lesen."),
+    ("flag.sms_access", "Die App kann SMS lesen."),
+    ("flag.installs_packages", "Die App kann andere Apps installieren."),
+    ("flag.device_admin", "Die App möchte Geräteadministrator werden."),
+    ("flag.contact_spread", "Die App kann Ihren Kontakten SMS senden."),
+    ("flag.package_claims_brand", "Die App {package} beansprucht eine Marke, zu der sie nicht gehört."),
+    ("flag.activex_controls", "Das Dokument enthält ActiveX-Steuerelemente."),
+    ("flag.embedded_objects", "Das Dokument enthält eingebettete Objekte."),
+    ("flag.external_ole_link", "Das Dokument lädt Inhalte über einen externen Link."),
+    ("flag.macro_content_type", "Das Dokument ist ein makrofähiger Dateityp."),
+    ("flag.protocol_handler_target", "Das Dokument öffnet über einen Link ein externes Programm."),
+    ("flag.remote_template", "Das Dokument lädt beim Öffnen eine Vorlage aus dem Internet."),
+    ("flag.vba_project", "Das Dokument enthält Makros."),
+    ("flag.all_streams_opaque", "Kein Inhalt des PDFs konnte gelesen werden."),
+    ("flag.auto_run_on_open", "Das PDF führt beim Öffnen sofort eine Aktion aus."),
+    ("flag.embedded_file", "Das PDF enthält eine eingebettete Datei."),
+    ("flag.javascript_action", "Das PDF enthält JavaScript."),
+    ("flag.launch_action", "Das PDF versucht, ein Programm zu starten."),
+    ("flag.single_page_link_lure", "Das PDF besteht aus einer einzigen Seite rund um einen Link."),
+    ("flag.submit_form", "Das PDF sendet Formulardaten an eine Website."),
+    ("flag.auto_submit_form", "Der Anhang sendet selbstständig ein Formular ab."),
+    ("flag.credential_form", "Der Anhang enthält ein Passwortformular."),
+    ("flag.credential_form_remote_action", "Das Passwortformular des Anhangs wird an eine Website gesendet."),
+    ("flag.event_handler", "Der Anhang führt bei Ereignissen Code aus."),
+    ("flag.html_smuggling", "Der Anhang setzt im Browser eine versteckte Datei zusammen."),
+    ("flag.javascript_url", "Der Anhang enthält JavaScript-Links."),
+    ("flag.large_base64_blob", "Der Anhang enthält eine große kodierte Nutzlast."),
+    ("flag.meta_refresh", "Der Anhang leitet Sie auf eine Website weiter."),
+    ("flag.obfuscated_script", "Der Anhang enthält absichtlich verschleierten Code."),
+    ("flag.script", "Der Anhang enthält Skripte."),
+    ("flag.script_redirect", "Der Anhang leitet Sie per Code auf eine Website weiter."),
+    ("flag.svg_embedded_html", "Das Bild enthält eine versteckte Webseite."),
+    ("flag.svg_script", "Das Bild enthält Skripte."),
+    ("flag.clipboard_hijack", "Die eingefügte Zahlungsadresse ist nicht die, die Sie kopiert haben."),
+    ("flag.lookalike_payment_address", "Die eingefügte Adresse wurde so erzeugt, dass sie auf den ersten Blick wie die kopierte aussieht."),
+    ("flag.confusable_domain", "Die Adresse ist so geschrieben, dass sie wie {lookalike_of} aussieht."),
+    ("flag.mixed_script_label", "Die Adresse mischt lateinische mit griechischen oder kyrillischen Buchstaben."),
+    ("flag.registrable_domain_hidden", "Die Adresse ist zu lang, um ihre echte Website {registrable_domain} anzuzeigen."),
+    ("flag.visible_brand_lure", "Der sichtbare Teil der Adresse nennt {lookalike_of}, die Website ist aber eine andere."),
+    ("flag.intent_fallback_off_host", "Fehlt die App, führt der Link Sie stattdessen zu {fallback_host}."),
+    ("flag.intent_fallback_risky", "Die Ausweichadresse des Links wirkt unsicher."),
+    ("flag.intent_fallback_script", "Die Ausweichadresse des Links führt Code aus, statt eine Website zu öffnen."),
+    ("flag.intent_package_mismatch", "Der Link öffnet die App {package}, die nicht von {brand} stammt."),
+    ("flag.enterprise_app_install", "Der Link installiert eine App außerhalb des offiziellen App-Stores."),
+    ("flag.manifest_off_origin", "Die App würde von einer anderen Website installiert, {manifest_host}."),
+    ("flag.sideload_from_untrusted_page", "Eine nicht vertrauenswürdige Seite versucht, eine App auf Ihrem Gerät zu installieren."),
+    ("flag.store_package_mismatch", "Der Store-Eintrag gehört zu {package}, das nicht von {brand} stammt."),
+    ("flag.store_redirect_without_gesture", "Eine Seite hat ohne Ihr Zutun einen App-Store-Eintrag geöffnet."),
+    ("flag.endpoint_ip_host", "Die Seite spricht einen Server über seine IP-Adresse an."),
+    ("flag.endpoint_private_network", "Eine Website kontaktiert ein Gerät in Ihrem lokalen Netzwerk, {host}."),
+    ("flag.endpoint_raw_port", "Die Seite verbindet sich mit einem Server auf einem ungewöhnlichen Port, {port}."),
+    ("flag.endpoint_suspicious_tld", "Die Seite sendet Daten an eine Domain mit einer häufig missbrauchten Endung."),
+    ("flag.endpoint_tunnel_host", "Die Seite sendet Daten über einen temporären Tunneldienst."),
+    ("flag.sni_host_mismatch", "Die Verbindung gibt {sni} als Ziel an, fragt aber {target} an."),
+    ("flag.sni_subdomain_mismatch", "Die Verbindung wurde für {sni} aufgebaut, fragt aber {target} auf derselben Website an."),
+    ("flag.sni_ip_literal", "Die Verbindung nennt eine IP-Adresse, {sni}, statt einer Website."),
+    ("flag.url_host_mismatch", "Die Anfrage für {url_host} ist an eine andere Website gerichtet, {host_header}."),
+    ("flag.redirect_chain_long", "Der Kurzlink springt über {chain_length} Weiterleitungen, bevor er seine Seite erreicht."),
+    ("flag.shortener_chain", "Der Link versteckt sich hinter {shortener_hops} Linkkürzern hintereinander."),
+    ("flag.redirect_loop", "Der Link leitet im Kreis weiter und erreicht nie eine Seite."),
+    ("flag.upi_reverse_payment", "Hier steht, dass Sie Geld erhalten, doch wenn Sie zustimmen, geht Geld von Ihrem Konto an {payee}. Um Geld zu erhalten, brauchen Sie nie Ihre UPI-PIN."),
+    ("flag.upi_pull_request", "Das ist eine Einzugsanforderung: {payee} will Geld von Ihrem Konto abbuchen, nicht Ihnen welches senden."),
+    ("flag.upi_unknown_handle", "Die UPI-Adresse nutzt ein Handle, @{handle}, das keine bekannte Bank oder Zahlungs-App vergibt."),
+    ("flag.upi_scam_prefix", "Die UPI-Adresse {payee} gibt sich als Erstattungs-, KYC- oder Support-Stelle aus; Banken ziehen über solche Adressen kein Geld ein."),
+    ("flag.data_uri_document", "Der Link enthält eine ganze Webseite, statt auf eine Website zu verweisen."),
+    ("flag.data_uri_script", "Der Link enthält ein Skript."),
+    ("flag.data_uri_form", "Die Seite im Link enthält ein Formular; was Sie eingeben, geht an dessen Urheber."),
+    ("flag.data_uri_mime_mismatch", "Der Link gibt an, {mime} zu enthalten, enthält aber {content_kind}."),
+    ("flag.data_uri_executable", "Der Link enthält ein Programm ({content_kind})."),
+    ("flag.data_uri_malformed", "Der kodierte Inhalt des Links ist beschädigt oder absichtlich verstümmelt."),
+    ("flag.javascript_scheme", "Der Link ist ein Skript, das in der Seite läuft, von der aus Sie ihn öffnen."),
+    ("flag.js_obfuscated", "Das Skript verbirgt hinter Kodierung oder eval, was es tut."),
+    ("flag.js_writes_page", "Das Skript ersetzt die Seite durch eigene Inhalte."),
+    ("flag.js_credential_prompt", "Das Skript fragt nach einem Passwort, einem Code oder Kartendaten."),
+    ("flag.js_redirect", "Das Skript schickt Sie oder Ihre Eingaben an eine andere Adresse."),
+    ("flag.blob_scheme", "Der Link zeigt auf Inhalte, die eine Seite auf {origin} im Browser zusammengesetzt hat."),
+    ("flag.blob_opaque_origin", "Der Link zeigt auf Inhalte, die eine verborgene oder abgeschottete Seite zusammengesetzt hat."),
+    ("flag.mailto_lookalike_domain", "Der Link schreibt an {domain}, das {brand} nachahmt."),
+    ("flag.mailto_display_spoof", "Die als Absender angezeigte Adresse ist nicht die, an die die Mail geht: {domain}."),
+    ("flag.mailto_brand_freemail", "Der Link schreibt an ein kostenloses Mailkonto auf {domain}, das nach {brand} benannt ist."),
+    ("flag.contact_text_mismatch", "Der Link zeigt {shown}, öffnet aber eine andere Adresse oder Nummer."),
+    ("flag.tel_premium_rate", "Die Nummer {number} ist eine minutenweise abgerechnete Mehrwertnummer."),
+    ("flag.tel_high_risk_prefix", "Die Nummer {number} nutzt eine Vorwahl, die bei Gebührenbetrug üblich ist."),
+    ("flag.tel_ussd", "Das Wählen von {number} führt einen Dienstcode des Telefons aus, der Ihre Anrufe umleiten kann."),
+    ("flag.tel_international", "Die Nummer ist ein Auslandsanruf."),
+];
+
+const PT: &[(&str, &str)] = &[
+    ("verdict.allow", "Não foram encontrados sinais de phishing nem de malware."),
+    ("verdict.warn", "Isto parece suspeito. Prossiga com cuidado."),
+    ("verdict.block", "Isto é muito provavelmente perigoso e foi bloqueado."),
+    ("flag.unknown", "Sinal suspeito: {flag}."),
+    ("finding.exfiltration.messaging_webhook", "Os dados são enviados diretamente para um bot do {service} ({endpoint}), uma forma comum de roubar senhas."),
+    ("finding.evasion.domain_fronting", "A conexão se apresenta à rede como {sni}, mas na verdade vai para {host}, um truque para esconder o destino do tráfego."),
+    ("flag.custom_brand_impersonation", "O endereço imita uma marca que você pediu para proteger."),
+    ("flag.brand_path_mimicry", "O link copia o caminho de página de um site conhecido em outro domínio."),
+    ("flag.keyword_pack_match", "O endereço contém palavras de uma lista de phishing carregada."),
+    ("flag.keyword_stuffing", "O endereço acumula várias palavras de início de sessão, pagamento ou prémio."),
+    ("flag.kit_fingerprint_path", "O endereço usa nomes de ficheiros e pastas típicos dos kits de phishing instalados em sites invadidos."),
+    ("flag.deep_random_path", "A página está escondida sob pastas com nomes aleatórios de um site conhecido."),
+    ("flag.login_form_on_compromised_path", "Há um formulário de início de sessão numa parte oculta, aparentemente plantada, de um site conhecido."),
+    ("flag.homoglyph_impersonation", "O endereço escreve uma marca conhecida com letras parecidas de outro alfabeto."),
+    ("flag.brand_alias_impersonation", "O endereço escreve o nome de uma marca conhecida noutra escrita ou por extenso, num site que não é da marca."),
+    ("flag.numeric_brand_lookalike", "O endereço escreve o nome curto de um banco ou marca com números ou letras parecidas, como 1cici em vez de ICICI."),
+    ("flag.nested_encoding", "O endereço está codificado várias vezes seguidas, um truque para escapar a filtros."),
+    ("flag.encoded_traversal", "Depois de descodificado, o endereço sai da sua pasta, escondido sob várias camadas de codificação."),
+    ("flag.encoded_keyword", "Depois de descodificado, o endereço contém palavras de início de sessão, pagamento ou prémio que a codificação esconde."),
+    ("flag.idn_brand_risky_tld", "Um endereço codificado com letras parecidas imita uma marca conhecida numa terminação de domínio muito usada para sites descartáveis."),
+    ("flag.alias_brand_risky_tld", "O endereço escreve o nome de uma marca conhecida noutra escrita numa terminação de domínio muito usada para sites descartáveis."),
+    ("flag.brand_login_risky_tld", "Um nome de marca mal escrito, uma página de login e uma terminação de domínio descartável aparecem juntos, um padrão clássico de phishing."),
+    ("flag.ip_host_login", "Uma página de login é servida a partir de um endereço numérico em vez de um site com nome."),
+    ("flag.upi_collect_unknown_handle", "Este pedido de pagamento cobra dinheiro para um endereço UPI com um identificador desconhecido ou um prefixo de burla."),
+    ("flag.redirect_to_executable", "Um link de redirecionamento leva você diretamente ao download de um programa."),
+    ("flag.allowlisted", "Você ou seu administrador marcaram este site como confiável."),
+    ("flag.blocklisted", "Você ou seu administrador bloquearam este site."),
+    ("flag.managed_allow", "A política da sua organização permite este site."),
+    ("flag.managed_block", "A política da sua organização bloqueia este site."),
+    ("flag.threat_feed_match", "Este site está numa lista de ameaças."),
+    ("flag.bad_reputation", "Este site já pareceu arriscado várias vezes."),
+    ("flag.kit_path_on_known_site", "Este endereço usa uma pasta que os kits de phishing instalam em sites invadidos, ao contrário do resto do site."),
+    ("flag.unusual_site_template", "Este endereço não se parece com as páginas que este site costuma servir."),
+    ("flag.personal_adjustment", "As suas decisões anteriores de permitir e bloquear alteraram esta pontuação."),
+    ("flag.password_over_http", "Um formulário de senha é enviado sem criptografia."),
+    ("flag.credentials_posted_offsite", "O formulário de login envia sua senha para outro site arriscado."),
+    ("flag.password_in_query", "O formulário de login coloca sua senha no endereço da página."),
+    ("flag.autofill_blocked", "O preenchimento automático de senhas foi desativado nesta página."),
+    ("flag.lure_text", "O texto da página usa frases típicas de phishing."),
+    ("flag.credential_page_weak_csp", "Esta página de login protege pouco ou nada os seus scripts, ao contrário das páginas de login reais."),
+    ("flag.messaging_webhook_exfil", "A página envia os dados do formulário para um bot de chat, uma forma comum de roubar senhas."),
+    ("flag.credential_sink", "A página envia dados para um serviço externo de coleta de formulários."),
+    ("flag.messaging_webhook", "A página contata um webhook de bot de chat."),
+    ("flag.form_off_script_origin", "Um formulário é enviado para um site de onde não veio nenhum código da página."),
+    ("flag.self_signed", "O certificado do site é autoassinado."),
+    ("flag.name_mismatch", "O certificado não cobre {host}."),
+    ("flag.fresh_certificate", "O certificado foi emitido nos últimos dois dias."),
+    ("flag.recent_certificate", "O certificado foi emitido nas últimas duas semanas."),
+    ("flag.bulk_san_list", "O certificado cobre um número invulgarmente grande de nomes."),
+    ("flag.over_long_validity", "O certificado é válido por mais tempo do que os certificados públicos podem ser."),
+    ("flag.brand_in_certificate_host", "O certificado de {host} menciona uma marca à qual não pertence."),
+    ("flag.brand_from_freemail", "Uma mensagem que diz vir de uma marca foi enviada de uma conta de e-mail gratuita."),
+    ("flag.display_name_address_spoof", "O nome do remetente mostra outro endereço de e-mail."),
+    ("flag.display_name_brand_mismatch", "O nome do remetente reivindica uma marca à qual o seu endereço não pertence."),
+    ("flag.dmarc_record_invalid", "O domínio do remetente tem um registro DMARC inválido."),
+    ("flag.reply_to_divergence", "As respostas iriam para um domínio diferente do remetente."),
+    ("flag.reply_to_freemail", "As respostas iriam para uma conta de e-mail gratuita."),
+    ("flag.sender_brand_on_foreign_host", "O domínio do remetente contém o nome de uma marca à qual não pertence."),
+    ("flag.sender_domain_lookalike", "O domínio do remetente imita um domínio conhecido."),
+    ("flag.spf_permissive", "O domínio do remetente permite que qualquer servidor envie e-mails em seu nome."),
+    ("flag.text_href_mismatch", "Um link mostra um endereço mas leva a outro."),
+    ("flag.dmarc_fail", "A mensagem falhou na verificação DMARC do domínio do remetente."),
+    ("flag.dmarc_none", "O domínio do remetente não publica uma política DMARC."),
+    ("flag.dmarc_policy_none", "O domínio do remetente não aplica a sua política DMARC."),
+    ("flag.spf_fail", "A mensagem veio de um servidor que o domínio do remetente não autoriza."),
+    ("flag.spf_softfail", "A mensagem veio de um servidor em que o domínio do remetente não confia totalmente."),
+    ("flag.dkim_fail", "A assinatura da mensagem é inválida."),
+    ("flag.dkim_unaligned", "A mensagem foi assinada por um domínio sem relação."),
+    ("flag.inline_binary_attachment", "O convite traz um arquivo incorporado."),
+    ("flag.no_organizer", "O convite não tem organizador."),
+    ("flag.organizer_brand_mismatch", "O organizador reivindica uma marca à qual o seu endereço não pertence."),
+    ("flag.organizer_cn_address_spoof", "O nome do organizador mostra outro endereço de e-mail."),
+    ("flag.organizer_domain_lookalike", "O domínio do organizador imita um domínio conhecido."),
+    ("flag.organizer_sent_by_mismatch", "Outro domínio enviou o convite em nome do organizador."),
+    ("flag.broad_scopes", "O aplicativo pede acesso amplo à sua conta."),
+    ("flag.broad_scopes_with_offline_access", "O aplicativo pede acesso amplo que continua ativo depois de você sair."),
+    ("flag.device_code_flow", "Este login usa um código de dispositivo, um método muito usado para roubar contas."),
+    ("flag.device_code_prefilled", "O código do dispositivo já veio preenchido; digite apenas códigos mostrados no seu próprio dispositivo."),
+    ("flag.forced_consent_offline", "O aplicativo força um pedido de consentimento para acesso prolongado."),
+    ("flag.implicit_grant", "O aplicativo usa um método de login obsoleto que expõe tokens."),
+    ("flag.insecure_authorize_endpoint", "A página de login não é criptografada."),
+    ("flag.lookalike_idp", "A página de login imita um provedor de identidade conhecido."),
+    ("flag.unknown_idp", "Não reconhecemos este provedor de login."),
+    ("flag.redirect_chained_url", "Depois do login, você seria reencaminhado por outro link."),
+    ("flag.redirect_ip_host", "Depois do login, você seria enviado para um endereço IP simples."),
+    ("flag.redirect_missing", "O pedido de login não indica para onde retorna."),
+    ("flag.redirect_oob", "O resultado do login é mostrado para ser copiado em vez de voltar ao aplicativo."),
+    ("flag.redirect_same_site_as_idp", "O endereço de retorno fica no próprio site do provedor de login."),
+    ("flag.redirect_shortener", "Depois do login, você passaria por um encurtador de links."),
+    ("flag.redirect_suspicious_tld", "Depois do login, você seria enviado para um domínio com terminação muito abusada."),
+    ("flag.redirect_tunnel_host", "Depois do login, você seria enviado para um túnel temporário."),
+    ("flag.extension_content_mismatch", "O conteúdo do arquivo não corresponde à sua extensão."),
+    ("flag.mime_mismatch", "O servidor descreve o arquivo como sendo de outro tipo."),
+    ("flag.bidi_control", "O nome do arquivo usa caracteres ocultos de direção do texto."),
+    ("flag.control_characters", "O nome do arquivo contém caracteres de controle ocultos."),
+    ("flag.extension_disguised", "O nome do arquivo esconde a sua extensão real."),
+    ("flag.fallback_name_mismatch", "O arquivo oferece dois nomes diferentes."),
+    ("flag.path_in_filename", "O nome do arquivo contém um caminho de pastas."),
+    ("flag.rtlo_override", "O nome do arquivo está invertido para disfarçar a extensão."),
+    ("flag.trailing_dot_or_space", "O nome do arquivo termina em ponto ou espaço para disfarçá-lo."),
+    ("flag.contains_executable", "O arquivo compactado contém um programa."),
+    ("flag.deeply_nested_archive", "O arquivo compactado está dentro de vários outros."),
+    ("flag.disk_image_container", "O download é uma imagem de disco."),
+    ("flag.disk_image_with_executable", "A imagem de disco contém um programa."),
+    ("flag.encrypted_archive", "O arquivo compactado tem senha, por isso o seu conteúdo não pode ser verificado."),
+    ("flag.encrypted_executable", "O arquivo protegido por senha contém um programa."),
+    ("flag.encrypted_headers", "O arquivo compactado esconde até os nomes dos seus arquivos."),
+    ("flag.password_in_filename", "A senha do arquivo compactado aparece no seu nome."),
+    ("flag.password_in_page_text", "A senha do arquivo compactado aparece na página."),
+    ("flag.password_unlocks_archive", "A senha indicada abre mesmo o arquivo compactado."),
+    ("flag.accessibility_with_overlay", "O aplicativo pode ler a sua tela e sobrepor-se a outros aplicativos."),
+    ("flag.banking_trojan_combo", "O aplicativo pede as permissões que os trojans bancários usam para roubar logins e códigos."),
+    ("flag.sms_with_screen_control", "O aplicativo pode ler SMS e controlar a tela."),
+    ("flag.otp_interception", "O aplicativo pode ler códigos de uso único de mensagens e notificações."),
+    ("flag.sms_access", "O aplicativo pode ler SMS."),
+    ("flag.installs_packages", "O aplicativo pode instalar outros aplicativos."),
+    ("flag.device_admin", "O aplicativo pede para ser administrador do dispositivo."),
+    ("flag.contact_spread", "O aplicativo pode enviar SMS aos seus contatos."),
+    ("flag.package_claims_brand", "O aplicativo {package} reivindica uma marca à qual não pertence."),
+    ("flag.activex_controls", "O documento contém controles ActiveX."),
+    ("flag.embedded_objects", "O documento contém objetos incorporados."),
+    ("flag.external_ole_link", "O documento carrega conteúdo de um link externo."),
+    ("flag.macro_content_type", "O documento é de um tipo com macros."),
+    ("flag.protocol_handler_target", "O documento abre um programa externo através de um link."),
+    ("flag.remote_template", "O documento baixa um modelo da internet ao ser aberto."),
+    ("flag.vba_project", "O documento contém macros."),
+    ("flag.all_streams_opaque", "Nenhum conteúdo do PDF pôde ser lido."),
+    ("flag.auto_run_on_open", "O PDF executa uma ação assim que é aberto."),
+    ("flag.embedded_file", "O PDF traz um arquivo incorporado."),
+    ("flag.javascript_action", "O PDF contém JavaScript."),
+    ("flag.launch_action", "O PDF tenta iniciar um programa."),
+    ("flag.single_page_link_lure", "O PDF é uma única página construída em torno de um link."),
+    ("flag.submit_form", "O PDF envia dados de formulário para um site."),
+    ("flag.auto_submit_form", "O anexo envia um formulário sozinho."),
+    ("flag.credential_form", "O anexo contém um formulário de senha."),
+    ("flag.credential_form_remote_action", "O formulário de senha do anexo é enviado para um site."),
+    ("flag.event_handler", "O anexo executa código em eventos."),
+    ("flag.html_smuggling", "O anexo monta um arquivo oculto dentro do navegador."),
+    ("flag.javascript_url", "O anexo contém links JavaScript."),
+    ("flag.large_base64_blob", "O anexo traz uma grande carga codificada."),
+    ("flag.meta_refresh", "O anexo redireciona você para um site."),
+    ("flag.obfuscated_script", "O anexo contém código deliberadamente ofuscado."),
+    ("flag.script", "O anexo contém scripts."),
+    ("flag.script_redirect", "O anexo usa código para enviar você a um site."),
+    ("flag.svg_embedded_html", "A imagem contém uma página web oculta."),
+    ("flag.svg_script", "A imagem contém scripts."),
+    ("flag.clipboard_hijack", "O endereço de pagamento que colou não é o que copiou."),
+    ("flag.lookalike_payment_address", "O endereço colado foi criado para parecer à primeira vista o que foi copiado."),
+    ("flag.confusable_domain", "O endereço foi escrito para parecer {lookalike_of}."),
+    ("flag.mixed_script_label", "O endereço mistura letras latinas com gregas ou cirílicas."),
+    ("flag.registrable_domain_hidden", "O endereço é demasiado longo para mostrar o seu site real, {registrable_domain}."),
+    ("flag.visible_brand_lure", "A parte visível do endereço indica {lookalike_of}, mas o site é outro."),
+    ("flag.intent_fallback_off_host", "Se o aplicativo não estiver instalado, o link leva você para {fallback_host}."),
+    ("flag.intent_fallback_risky", "O endereço alternativo do link parece inseguro."),
+    ("flag.intent_fallback_script", "A alternativa do link executa código em vez de abrir um site."),
+    ("flag.intent_package_mismatch", "O link abre o aplicativo {package}, que não é da {brand}."),
+    ("flag.enterprise_app_install", "O link instala um aplicativo de fora da loja oficial."),
+    ("flag.manifest_off_origin", "O aplicativo seria instalado a partir de outro site, {manifest_host}."),
+    ("flag.sideload_from_untrusted_page", "Uma página não confiável está tentando instalar um aplicativo no seu dispositivo."),
+    ("flag.store_package_mismatch", "A página da loja é de {package}, que não é da {brand}."),
+    ("flag.store_redirect_without_gesture", "Uma página abriu uma página da loja de aplicativos sem você pedir."),
+    ("flag.endpoint_ip_host", "A página se comunica com um servidor pelo endereço IP."),
+    ("flag.endpoint_private_network", "Um site está contactando um dispositivo da sua rede local, {host}."),
+    ("flag.endpoint_raw_port", "A página se conecta a um servidor numa porta incomum, {port}."),
+    ("flag.endpoint_suspicious_tld", "A página envia dados para um domínio com uma terminação muito abusada."),
+    ("flag.endpoint_tunnel_host", "A página envia dados através de um serviço de túnel temporário."),
+    ("flag.sni_host_mismatch", "A conexão diz ir para {sni}, mas pede {target}."),
+    ("flag.sni_subdomain_mismatch", "A conexão foi aberta para {sni}, mas pede {target} no mesmo site."),
+    ("flag.sni_ip_literal", "A conexão indica um endereço IP, {sni}, em vez de um site."),
+    ("flag.url_host_mismatch", "A solicitação para {url_host} é dirigida a outro site, {host_header}."),
+    ("flag.redirect_chain_long", "O link curto passa por {chain_length} redirecionamentos antes de chegar à página."),
+    ("flag.shortener_chain", "O link esconde-se atrás de {shortener_hops} encurtadores de links seguidos."),
+    ("flag.redirect_loop", "O link redireciona em círculo e nunca chega a uma página."),
+    ("flag.upi_reverse_payment", "Diz que vai receber dinheiro, mas aprovar envia dinheiro da sua conta para {payee}. Nunca precisa do seu PIN UPI para receber dinheiro."),
+    ("flag.upi_pull_request", "É um pedido de cobrança: {payee} está a pedir para tirar dinheiro da sua conta, não para lhe enviar."),
+    ("flag.upi_unknown_handle", "O endereço UPI usa um identificador, @{handle}, que nenhum banco ou aplicação de pagamento conhecida emite."),
+    ("flag.upi_scam_prefix", "O endereço UPI {payee} apresenta-se como reembolsos, KYC ou apoio; os bancos não cobram dinheiro através de endereços assim."),
+    ("flag.data_uri_document", "O link traz uma página web inteira dentro de si em vez de apontar para um site."),
+    ("flag.data_uri_script", "O link traz um script dentro de si."),
+    ("flag.data_uri_form", "A página dentro do link contém um formulário; o que escrever vai para quem o criou."),
+    ("flag.data_uri_mime_mismatch", "O link diz conter {mime} mas na verdade contém {content_kind}."),
+    ("flag.data_uri_executable", "O link traz um programa ({content_kind}) dentro de si."),
+    ("flag.data_uri_malformed", "O conteúdo codificado do link está danificado ou baralhado de propósito."),
+    ("flag.javascript_scheme", "O link é um script que corre dentro da página a partir da qual o abre."),
+    ("flag.js_obfuscated", "O script esconde o que faz atrás de codificação ou eval."),
+    ("flag.js_writes_page", "O script substitui a página por conteúdo próprio."),
+    ("flag.js_credential_prompt", "O script pede uma palavra-passe, um código ou dados do cartão."),
+    ("flag.js_redirect", "O script envia-o a si, ou o que escreve, para outro endereço."),
+    ("flag.blob_scheme", "O link aponta para conteúdo que uma página em {origin} montou dentro do navegador."),
+    ("flag.blob_opaque_origin", "O link aponta para conteúdo montado por uma página oculta ou isolada."),
+    ("flag.mailto_lookalike_domain", "O link escreve para {domain}, que imita {brand}."),
+    ("flag.mailto_display_spoof", "O endereço mostrado como remetente não é para onde vai o e-mail: {domain}."),
+    ("flag.mailto_brand_freemail", "O link escreve para uma conta de e-mail gratuita em {domain} com o nome de {brand}."),
+    ("flag.contact_text_mismatch", "O link mostra {shown} mas abre outro endereço ou número."),
+    ("flag.tel_premium_rate", "O número {number} é uma linha de tarifa majorada cobrada ao minuto."),
+    ("flag.tel_high_risk_prefix", "O número {number} usa um prefixo comum em fraudes de chamadas."),
+    ("flag.tel_ussd", "Marcar {number} executa um código de serviço do telefone, que pode desviar as suas chamadas."),
+    ("flag.tel_international", "O número é uma chamada internacional."),
+];
+
+/// Templates by locale; [`DEFAULT_LOCALE`] first.
+pub(crate) const TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[("en", EN), ("es", ES), ("fr", FR), ("de", DE), ("pt", PT)];
+
+/// Locale whose templates serve `locale` (`pt-BR` → `pt`), else [`DEFAULT_LOCALE`].
+fn resolve(locale: &str) -> &'static str {
+    let lang = locale.split(['-', '_']).next().unwrap_or("").to_ascii_lowercase();
+    TRANSLATIONS.iter().map(|(l, _)| *l).find(|l| *l == lang).unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Template for `key` in `locale`, falling back to English.
+pub(crate) fn template(locale: &str, key: &str) -> Option<&'static str> {
+    let lookup = |l: &str| TRANSLATIONS.iter().find(|(n, _)| *n == l)
+        .and_then(|(_, t)| t.iter().find(|(k, _)| *k == key)).map(|(_, v)| *v);
+    lookup(locale).or_else(|| lookup(DEFAULT_LOCALE))
+}
+
+/// `{name}` slots written in a template, in order.
+pub(crate) fn slots_of(template: &str) -> Vec<&str> {
+    template.split('{').skip(1).filter_map(|s| s.split_once('}').map(|(n, _)| n)).collect()
+}
+
+fn slots(key: &str) -> &'static [&'static str] {
+    CATALOG.iter().find(|(k, _)| *k == key).map_or(&[], |(_, s)| *s)
+}
+
+fn param(source: &Json, name: &str) -> String {
+    let v = source.get(name).or_else(|| source.get("details").and_then(|d| d.get(name)));
+    match v {
+        Some(Json::Str(s)) => s.clone(),
+        Some(Json::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// `{ <label>, key, params, text }` for `key`, its slots filled from `source`.
+fn message(locale: &str, label: (&str, &str), key: &str, source: &Json) -> Json {
+    let params: Vec<(String, Json)> = slots(key).iter().map(|s| (s.to_string(), Json::from(param(source, s)))).collect();
+    let mut text = template(locale, key).unwrap_or("").to_string();
+    for (name, value) in &params {
+        text = text.replace(&format!("{{{}}}", name), value.as_str().unwrap_or(""));
+    }
+    let head = if label.0.is_empty() { Json::obj() } else { Json::obj().with(label.0, label.1) };
+    head.with("key", key).with("params", Json::Obj(params)).with("text", text)
+}
+
+/// Human-readable reasons for any report (or its `Report` envelope). A flag
+/// the catalog doesn't know renders as `flag.unknown` with the flag name.
+/// Returns `{ locale, verdict: { key, params, text } | null, reasons: [{ flag,
+/// key, params, text }], findings: [{ tag, key, params, text }] }`; `locale`
+/// is the one actually used.
+pub fn render_explanation(report: &Json, locale: &str) -> Json {
+    let locale = resolve(locale);
+    let verdict = report.get("verdict").and_then(Json::as_str).map(|v| format!("verdict.{}", v))
+        .filter(|k| template(locale, k).is_some())
+        .map_or(Json::Null, |k| message(locale, ("", ""), &k, report));
+    let reasons: Vec<Json> = report.str_list("flags").iter().map(|f| {
+        let key = format!("flag.{}", f);
+        if template(locale, &key).is_some() {
+            message(locale, ("flag", f), &key, report)
+        } else {
+            message(locale, ("flag", f), "flag.unknown", &Json::obj().with("flag", f.as_str()))
+        }
+    }).collect();
+    let listed = report.get("findings").or_else(|| report.get("details").and_then(|d| d.get("findings")));
+    let findings: Vec<Json> = listed.and_then(Json::as_array).unwrap_or(&[]).iter().filter_map(|f| {
+        let tag = f.get("tag").and_then(Json::as_str)?;
+        let key = format!("finding.{}", tag);
+        template(locale, &key)?;
+        Some(message(locale, ("tag", tag), &key, f))
+    }).collect();
+    Json::obj()
+        .with("locale", locale)
+        .with("verdict", verdict)
+        .with("reasons", reasons)
+        .with("findings", findings)
+}
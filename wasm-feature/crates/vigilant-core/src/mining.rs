@@ -0,0 +1,150 @@
+// wasm-feature/crates/vigilant-core/src/mining.rs
+// Browser Vigilant — hard negatives collected in the field for the next
+// training run. A URL the scorer was confidently wrong about, as the user's
+// own allow/block decision or a disagreement between the rules and the
+// loaded model shows, is worth more to retraining than thousands of easy
+// ones. The store is bounded and exports labelled feature rows as JSONL.
+
+use std::collections::VecDeque;
+use std::mem::size_of;
+use crate::hash::Privacy;
+use crate::json::Json;
+use crate::types::{err, Fields, FromJson, SchemaError, ToJson};
+
+/// Entries kept before the oldest are overwritten.
+pub const HARD_NEGATIVE_CAPACITY: usize = 500;
+
+/// Gap between the rule score and the model score that counts as a disagreement.
+pub const MIN_DISAGREEMENT: f32 = 0.5;
+
+/// What exposed the misclassification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MiningSource {
+    /// The user allowed a URL the scorer blocked, or blocked one it allowed.
+    Feedback,
+    /// The rules and the loaded model gave opposite verdicts.
+    Disagreement,
+}
+
+impl MiningSource {
+    pub fn as_str(self) -> &'static str {
+        match self { MiningSource::Feedback => "feedback", MiningSource::Disagreement => "disagreement" }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HardNegative {
+    pub url_hash: String,
+    /// The URL itself; `None` in hash-only mode.
+    pub url:      Option<String>,
+    pub features: Vec<f32>,
+    /// 1 for phishing, 0 for benign.
+    pub label:    u8,
+    /// The score that was wrong.
+    pub score:    f32,
+    pub source:   MiningSource,
+}
+
+#[derive(Clone, Debug)]
+pub struct HardNegatives {
+    entries:  VecDeque<HardNegative>,
+    capacity: usize,
+    privacy:  Privacy,
+}
+
+impl Default for HardNegatives {
+    fn default() -> HardNegatives { HardNegatives::with_capacity(HARD_NEGATIVE_CAPACITY) }
+}
+
+impl HardNegatives {
+    pub fn with_capacity(capacity: usize) -> HardNegatives {
+        HardNegatives { entries: VecDeque::new(), capacity, privacy: Privacy::plain() }
+    }
+
+    /// Keep only salted hashes of URLs from now on. Entries recorded under
+    /// the old setting are dropped.
+    pub fn set_privacy(&mut self, privacy: Privacy) {
+        self.clear();
+        self.privacy = privacy;
+    }
+
+    /// Record `url` as labelled `phishing` (or not) after it scored `score`.
+    /// A URL already held is replaced, and user feedback is never replaced
+    /// by a disagreement.
+    pub fn record(&mut self, url: &str, features: &[f32], phishing: bool, score: f32, source: MiningSource) {
+        let url_hash = self.privacy.digest(url);
+        let url = (!self.privacy.is_hashed()).then(|| url.to_string());
+        self.insert(HardNegative { url_hash, url, features: features.to_vec(), label: phishing as u8, score, source });
+    }
+
+    /// [`HardNegatives::record`] for an entry already keyed under this store's privacy mode.
+    pub(crate) fn insert(&mut self, e: HardNegative) {
+        if self.capacity == 0 { return; }
+        if let Some(i) = self.entries.iter().position(|x| x.url_hash == e.url_hash) {
+            if e.source == MiningSource::Disagreement && self.entries[i].source == MiningSource::Feedback { return; }
+            self.entries.remove(i);
+        }
+        if self.entries.len() == self.capacity { self.entries.pop_front(); }
+        self.entries.push_back(e);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &HardNegative> { self.entries.iter() }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    pub fn clear(&mut self) { self.entries.clear(); }
+
+    pub fn heap_bytes(&self) -> usize {
+        self.entries.iter().map(|e| {
+            size_of::<HardNegative>() + e.url_hash.capacity() + e.url.as_ref().map_or(0, String::capacity) + e.features.capacity() * size_of::<f32>()
+        }).sum()
+    }
+
+    /// One `{ label, features, url_hash, url?, score, source }` row per
+    /// entry, oldest first, in the layout the training script reads. `url`
+    /// is left out in hash-only mode.
+    pub fn export_jsonl(&self) -> String {
+        let mut out = String::new();
+        for e in &self.entries {
+            out.push_str(&e.to_json().to_string());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl ToJson for HardNegative {
+    fn to_json(&self) -> Json {
+        let mut row = Json::obj()
+            .with("label", self.label as u32)
+            .with("features", self.features.clone())
+            .with("url_hash", self.url_hash.as_str());
+        if let Some(url) = &self.url { row = row.with("url", url.as_str()); }
+        row.with("score", self.score).with("source", self.source.as_str())
+    }
+}
+
+impl FromJson for HardNegative {
+    fn from_json(v: &Json, path: &str) -> Result<HardNegative, SchemaError> {
+        let f = Fields::new(v, path)?;
+        let features = f.list("features", |x, p| x.as_f64().map(|n| n as f32).filter(|n| n.is_finite()).ok_or_else(|| err(p, "expected number")))?;
+        let source = match f.opt_string("source")?.as_deref() {
+            None | Some("feedback") => MiningSource::Feedback,
+            Some("disagreement") => MiningSource::Disagreement,
+            Some(s) => return Err(err(&f.at("source"), &format!("unknown source \"{}\"", s))),
+        };
+        Ok(HardNegative {
+            url_hash: f.string("url_hash")?,
+            url: f.opt_string("url")?,
+            features,
+            label: match f.opt_count("label")? {
+                Some(n @ (0 | 1)) => n as u8,
+                _ => return Err(err(&f.at("label"), "expected 0 or 1")),
+            },
+            score: f.opt_num("score")?.unwrap_or(0.0) as f32,
+            source,
+        })
+    }
+}
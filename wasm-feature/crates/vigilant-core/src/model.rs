@@ -0,0 +1,237 @@
+// wasm-feature/crates/vigilant-core/src/model.rs
+// Browser Vigilant — the container a trained URL model ships in. The ONNX
+// payload alone says nothing about which feature layout it was trained on,
+// so a model built for an older vector would load fine and score garbage.
+// The container puts a metadata header in front of the payload, and loading
+// refuses any model whose feature version this build doesn't extract.
+//
+// Layout: `BVMD`, header length (u32 little-endian), header JSON, payload.
+
+use crate::hash::sha256_hex;
+use crate::json::Json;
+use crate::personal::{logit, sigmoid};
+use crate::schema::feature_schema_hash;
+use crate::types::{err, Fields, FromJson, SchemaError, ToJson};
+use crate::MODEL_FEATURES;
+
+/// Layout version of the [`MODEL_FEATURES`] vector; bumped whenever a
+/// feature moves or changes meaning.
+pub const FEATURE_VERSION: u32 = 9;
+
+/// Leading bytes of a model container.
+pub(crate) const MAGIC: &[u8; 4] = b"BVMD";
+
+/// Container layout this build reads.
+pub(crate) const CONTAINER_VERSION: u32 = 1;
+
+/// Largest header accepted; real ones are a few hundred bytes.
+const MAX_HEADER_BYTES: usize = 64 << 10;
+
+/// Platt scaling of the model's raw output: `sigmoid(slope · logit(p) + intercept)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Calibration {
+    pub slope:     f32,
+    pub intercept: f32,
+}
+
+impl Default for Calibration {
+    fn default() -> Calibration { Calibration { slope: 1.0, intercept: 0.0 } }
+}
+
+impl Calibration {
+    /// Calibrated probability for raw model output `p`.
+    pub fn apply(&self, p: f32) -> f32 {
+        if *self == Calibration::default() { return p.clamp(0.0, 1.0); }
+        sigmoid(self.slope * logit(p) + self.intercept)
+    }
+}
+
+/// Provenance and input contract of a model, from its container header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelInfo {
+    pub container_version: u32,
+    /// Payload format: "onnx" for the JS runtime (or [`crate::TreeModel`] when
+    /// it holds a tree ensemble), "trees" for [`crate::TreeModel`].
+    pub kind:              String,
+    /// ISO 8601 date the model was trained.
+    pub trained_at:        String,
+    /// Hash of the training set, as the training pipeline reports it.
+    pub dataset_hash:      String,
+    pub feature_version:   u32,
+    pub feature_count:     usize,
+    /// [`crate::feature_schema_hash`] the model was trained against; empty
+    /// when the header doesn't say.
+    pub feature_schema:    String,
+    pub calibration:       Calibration,
+    pub license:           String,
+    /// SHA-256 of the payload, computed at load.
+    pub payload_sha256:    String,
+    pub payload_bytes:     usize,
+}
+
+impl FromJson for ModelInfo {
+    fn from_json(v: &Json, path: &str) -> Result<ModelInfo, SchemaError> {
+        let f = Fields::new(v, path)?;
+        let container_version = match f.opt_count("container_version")? {
+            None => CONTAINER_VERSION,
+            Some(n) if n as u32 <= CONTAINER_VERSION && n > 0 => n as u32,
+            Some(n) => return Err(err(&f.at("container_version"), &format!("unsupported container {} (this build reads up to {})", n, CONTAINER_VERSION))),
+        };
+        let feature_version = f.opt_count("feature_version")?.ok_or_else(|| err(&f.at("feature_version"), "required number is missing"))?;
+        if feature_version != FEATURE_VERSION as usize {
+            return Err(err(&f.at("feature_version"), &format!("model expects feature version {}, this build extracts {}", feature_version, FEATURE_VERSION)));
+        }
+        let feature_count = f.opt_count("feature_count")?.unwrap_or(MODEL_FEATURES);
+        if feature_count != MODEL_FEATURES {
+            return Err(err(&f.at("feature_count"), &format!("model expects {} features, this build extracts {}", feature_count, MODEL_FEATURES)));
+        }
+        let feature_schema = f.opt_string("feature_schema")?.unwrap_or_default();
+        if !feature_schema.is_empty() && feature_schema != feature_schema_hash() {
+            return Err(err(&f.at("feature_schema"), &format!("model was trained on feature schema {}, this build extracts {}", feature_schema, feature_schema_hash())));
+        }
+        let calibration = match v.get("calibration").filter(|c| !matches!(c, Json::Null)) {
+            None => Calibration::default(),
+            Some(c) => {
+                let path = f.at("calibration");
+                let c = Fields::new(c, &path)?;
+                let d = Calibration::default();
+                Calibration {
+                    slope:     c.opt_num("slope")?.map_or(d.slope, |n| n as f32),
+                    intercept: c.opt_num("intercept")?.map_or(d.intercept, |n| n as f32),
+                }
+            }
+        };
+        Ok(ModelInfo {
+            container_version,
+            kind: f.opt_string("kind")?.unwrap_or_else(|| "onnx".to_string()),
+            trained_at: f.string("trained_at")?,
+            dataset_hash: f.string("dataset_hash")?,
+            feature_version: FEATURE_VERSION,
+            feature_count,
+            feature_schema,
+            calibration,
+            license: f.string("license")?,
+            payload_sha256: String::new(),
+            payload_bytes: 0,
+        })
+    }
+}
+
+impl ToJson for ModelInfo {
+    fn to_json(&self) -> Json {
+        Json::obj()
+            .with("container_version", self.container_version)
+            .with("kind", self.kind.as_str())
+            .with("trained_at", self.trained_at.as_str())
+            .with("dataset_hash", self.dataset_hash.as_str())
+            .with("feature_version", self.feature_version)
+            .with("feature_count", self.feature_count)
+            .with("feature_schema", self.feature_schema.as_str())
+            .with("calibration", Json::obj().with("slope", self.calibration.slope).with("intercept", self.calibration.intercept))
+            .with("license", self.license.as_str())
+            .with("payload_sha256", self.payload_sha256.as_str())
+            .with("payload_bytes", self.payload_bytes)
+    }
+}
+
+/// Split a model container into its header and payload. Fails on a
+/// malformed container and on a model trained for another feature layout.
+pub fn parse_model(blob: &[u8]) -> Result<(ModelInfo, &[u8]), SchemaError> {
+    if blob.len() < 8 || &blob[..4] != MAGIC { return Err(err("", "not a model container")); }
+    let header_len = u32::from_le_bytes([blob[4], blob[5], blob[6], blob[7]]) as usize;
+    if header_len > MAX_HEADER_BYTES || blob.len() < 8 + header_len { return Err(err("", "truncated model header")); }
+    let header = std::str::from_utf8(&blob[8..8 + header_len]).map_err(|_| err("", "model header is not UTF-8"))?;
+    let header = Json::parse(header).map_err(|e| err("", &e))?;
+    let mut info = ModelInfo::from_json(&header, "")?;
+    let payload = &blob[8 + header_len..];
+    if payload.is_empty() { return Err(err("", "model container has no payload")); }
+    info.payload_sha256 = sha256_hex(payload);
+    info.payload_bytes = payload.len();
+    Ok((info, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trees::TreeModel;
+
+    const HEADER: &str = r#""kind":"trees","trained_at":"2024-01-01","dataset_hash":"d","license":"MIT""#;
+
+    fn container(header: &str, payload: &[u8]) -> Vec<u8> {
+        [&MAGIC[..], &(header.len() as u32).to_le_bytes(), header.as_bytes(), payload].concat()
+    }
+
+    fn header(extra: &str) -> String { format!("{{{},{}}}", HEADER, extra) }
+
+    fn current() -> String { header(&format!(r#""feature_version":{}"#, FEATURE_VERSION)) }
+
+    /// A one-leaf ensemble whose leaf value's bytes are `value`.
+    fn leaf_payload(value: [u8; 4]) -> Vec<u8> {
+        let mut p = vec![0];
+        p.extend(0f32.to_le_bytes());
+        p.extend(1u32.to_le_bytes());
+        p.extend(1u32.to_le_bytes());
+        p.extend(u16::MAX.to_le_bytes());
+        p.extend(value);
+        p.extend([0; 8]);
+        p
+    }
+
+    fn path_of(blob: &[u8]) -> (String, String) {
+        let e = parse_model(blob).unwrap_err();
+        (e.path, e.message)
+    }
+
+    #[test]
+    fn a_current_container_splits_into_header_and_payload() {
+        let blob = container(&current(), b"xyz");
+        let (info, payload) = parse_model(&blob).unwrap();
+        assert_eq!((info.kind.as_str(), info.feature_count, info.payload_bytes), ("trees", MODEL_FEATURES, 3));
+        assert_eq!(info.payload_sha256, sha256_hex(b"xyz"));
+        assert_eq!(payload, b"xyz");
+    }
+
+    #[test]
+    fn another_feature_version_is_refused() {
+        let blob = container(&header(&format!(r#""feature_version":{}"#, FEATURE_VERSION - 1)), b"xyz");
+        let (path, message) = path_of(&blob);
+        assert_eq!(path, "feature_version");
+        assert!(message.contains(&format!("this build extracts {}", FEATURE_VERSION)), "{}", message);
+        let (path, message) = path_of(&container(&format!("{{{}}}", HEADER), b"xyz"));
+        assert_eq!((path.as_str(), message.as_str()), ("feature_version", "required number is missing"));
+    }
+
+    #[test]
+    fn another_feature_count_is_refused() {
+        let blob = container(&header(&format!(r#""feature_version":{},"feature_count":56"#, FEATURE_VERSION)), b"xyz");
+        assert_eq!(path_of(&blob).0, "feature_count");
+    }
+
+    #[test]
+    fn malformed_containers_are_refused() {
+        let blob = container(&current(), b"xyz");
+        assert_eq!(path_of(b"BVM").1, "not a model container");
+        assert_eq!(path_of(&[b"ONNX", &blob[4..]].concat()).1, "not a model container");
+        assert_eq!(path_of(&blob[..20]).1, "truncated model header");
+        assert_eq!(path_of(&container(&current(), b"")).1, "model container has no payload");
+        assert_eq!(path_of(&container("{\"kind\":", b"xyz")).0, "");
+    }
+
+    #[test]
+    fn non_numeric_header_values_are_refused() {
+        let blob = container(&header(r#""feature_version":"9""#), b"xyz");
+        assert_eq!(path_of(&blob), ("feature_version".to_string(), "expected number".to_string()));
+        let blob = container(&header(&format!(r#""feature_version":{},"calibration":{{"slope":"steep"}}"#, FEATURE_VERSION)), b"xyz");
+        assert_eq!(path_of(&blob), ("calibration.slope".to_string(), "expected number".to_string()));
+    }
+
+    #[test]
+    fn truncated_or_non_finite_weights_are_refused() {
+        let payload = leaf_payload(0.5f32.to_le_bytes());
+        assert!(TreeModel::parse(&container(&current(), &payload)).is_ok());
+        assert!(TreeModel::parse(&container(&current(), &payload[..payload.len() - 3])).is_err());
+        assert!(TreeModel::parse(&container(&current(), &payload[..6])).is_err());
+        assert!(TreeModel::parse(&container(&current(), &leaf_payload(f32::NAN.to_le_bytes()))).is_err());
+        assert!(TreeModel::parse(&container(&current(), &leaf_payload(f32::INFINITY.to_le_bytes()))).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+// wasm-feature/crates/vigilant-core/src/named.rs
+// Browser Vigilant — the model vector by name, with a line of text per
+// feature, so the popup can say why a URL was flagged instead of showing
+// a row of floats. Names are the ones `features.py` trains under.
+
+use crate::json::Json;
+use crate::score::WEIGHTS;
+use crate::{extract_features, MODEL_FEATURES};
+
+/// Name and reason for each model feature, as in `features.py`'s
+/// `FEATURE_NAMES`. A reason with `{v}` describes any value; one without
+/// applies only when the feature is set.
+const NAMED: [(&str, &str); MODEL_FEATURES] = [
+    ("url_length", "URL is {v} characters long"),
+    ("domain_length", "Host name is {v} characters long"),
+    ("path_length", "Path is {v} characters long"),
+    ("query_length", "Query string is {v} characters long"),
+    ("dot_count", "Dots in the URL: {v}"),
+    ("hyphen_count", "Hyphens in the URL: {v}"),
+    ("underscore_count", "Underscores in the URL: {v}"),
+    ("slash_count", "Slashes after the scheme: {v}"),
+    ("at_count", "@ signs, which can hide the real host: {v}"),
+    ("digit_count", "Digits in the URL: {v}"),
+    ("digit_ratio", "Digits make up {v} of the URL"),
+    ("scheme_https", "Connection uses HTTPS"),
+    ("ip_in_url", "Host is a bare IP address instead of a name"),
+    ("is_punycode", "Host uses punycode, which can disguise look-alike characters"),
+    ("subdomain_depth", "Subdomain levels in front of the registered domain: {v}"),
+    ("port_anomaly", "URL names an unusual port"),
+    ("url_entropy", "URL character entropy is {v} bits"),
+    ("domain_entropy", "Host character entropy is {v} bits"),
+    ("path_entropy", "Path character entropy is {v} bits"),
+    ("domain_bigram_entropy", "Host bigram entropy is {v} bits"),
+    ("domain_trigram_entropy", "Host trigram entropy is {v} bits"),
+    ("brand_spoof_flag", "Domain is one or two letters away from a well-known brand"),
+    ("brand_distance_norm", "Distance to the nearest brand name is {v} (0 is identical)"),
+    ("brand_in_subdomain_only", "A brand name appears in the subdomain but not in the registered domain"),
+    ("has_login_kw", "URL contains login words such as \"signin\" or \"verify\""),
+    ("has_trust_kw_in_domain", "Host contains trust words such as \"secure\" or \"bank\""),
+    ("has_payment_kw", "URL contains payment words"),
+    ("has_free_kw", "URL promises something free"),
+    ("has_fraud_kw", "URL contains words common in scams"),
+    ("keyword_density", "Suspicious keyword density is {v}"),
+    ("hyphen_in_domain", "Host contains a hyphen"),
+    ("double_extension", "Path has a double extension, like invoice.pdf.exe"),
+    ("pct_encoding_ratio", "Percent-encoding makes up {v} of the URL"),
+    ("heavy_encoding", "URL is heavily percent-encoded ({v})"),
+    ("query_param_count", "Query parameters: {v}"),
+    ("has_fragment", "URL has a #fragment"),
+    ("is_data_uri", "URL is a data: URI carrying its own content"),
+    ("path_traversal", "Path climbs directories with .."),
+    ("suspicious_tld", "Top-level domain is one abused often"),
+    ("tld_length", "Top-level domain is {v} characters long"),
+    ("has_subdomain", "Host has a subdomain"),
+    ("numeric_domain", "Host is all digits"),
+    ("url_compression_ratio", "Distinct characters per character is {v}"),
+    ("vowel_ratio", "Vowels make up {v} of the host's letters"),
+    ("max_consonant_run", "Longest run of consonants in the host is {v}"),
+    ("is_short_url", "Link goes through a URL shortener that hides the destination"),
+    ("base64_in_query", "Query carries a long base64 token"),
+    ("path_depth", "Path depth: {v}"),
+    ("upi_vpa_present", "URL carries a UPI payment address"),
+    ("suspicious_upi_vpa", "UPI address uses an unknown handle or a scam prefix"),
+    ("upi_collect_request", "URL is a UPI payment request"),
+    ("ext_executable", "Link points at an executable or installer package"),
+    ("admin_path", "Path is an admin or CGI directory"),
+    ("open_redirect", "Query redirects to another URL"),
+    ("max_char_repeat_ratio", "The most repeated host character makes up {v} of it"),
+    ("hex_token_in_url", "URL carries a long hex token"),
+    ("ext_document", "Link points at a document file"),
+    ("ext_media", "Link points at an image, audio or video file"),
+    ("ext_script", "Link points at a script file"),
+    ("ext_archive", "Link points at an archive or disk image"),
+    ("ext_none", "Path names no known file type"),
+    ("server_script", "Path names a server-side script, like login.php"),
+    ("server_script_odd_host", "Server-side script served from an IP address, an unusual port or an abused TLD"),
+    ("scheme_http", "Connection uses plain, unencrypted HTTP"),
+    ("scheme_ftp", "Link is an ftp: download"),
+    ("scheme_data", "Link is a data: URI carrying its own content"),
+    ("scheme_javascript", "Link runs script instead of opening a page"),
+    ("scheme_blob", "Link is a blob: URL built by a page's script"),
+    ("scheme_mailto", "Link opens an email draft"),
+    ("scheme_intent", "Link launches an Android app through an intent: URL"),
+    ("scheme_upi", "Link opens a UPI payment app"),
+    ("scheme_other", "Link has no scheme or an unusual one"),
+    ("js_obfuscation_calls", "Decoding or eval calls in the link's script: {v}"),
+    ("js_writes_page", "Link's script replaces the page with its own"),
+    ("js_credential_prompt", "Link's script asks for a password or other credential"),
+    ("js_navigates", "Link's script sends you, or what you type, elsewhere"),
+    ("blob_opaque_origin", "blob: link comes from a sandboxed or data: page"),
+];
+
+/// Name of model feature `i`.
+pub(crate) fn feature_name(i: usize) -> Option<&'static str> { NAMED.get(i).map(|(n, _)| *n) }
+
+/// Model feature `i` is a 0/1 flag rather than a measurement.
+pub(crate) fn is_flag(i: usize) -> bool { NAMED.get(i).is_some_and(|(_, r)| !r.contains("{v}")) }
+
+fn shown(v: f32) -> String {
+    if v.fract() == 0.0 { format!("{}", v) } else { format!("{:.2}", v) }
+}
+
+/// Name the entries of model vector `features`. Returns `{ <name>: { index,
+/// value, weight, contribution, reason } }` in vector order: `weight` is the
+/// heuristic score's weight for the feature (0 for unweighted ones),
+/// `contribution` what it adds to the score here, and `reason` null for a
+/// flag that isn't set. Entries past the model features are left out.
+pub fn name_features(features: &[f32]) -> Json {
+    let fields = features.iter().zip(NAMED).enumerate().map(|(i, (&v, (name, reason)))| {
+        let weight = WEIGHTS.iter().find(|(n, _)| *n == i).map_or(0.0, |(_, w)| *w);
+        let reason = if reason.contains("{v}") { Some(reason.replace("{v}", &shown(v))) } else { (v > 0.0).then(|| reason.to_string()) };
+        let entry = Json::obj()
+            .with("index", i)
+            .with("value", v)
+            .with("weight", weight)
+            .with("contribution", v.min(1.0) * weight)
+            .with("reason", reason);
+        (name.to_string(), entry)
+    });
+    Json::Obj(fields.collect())
+}
+
+/// [`extract_features`] of `url`, named (see [`name_features`]).
+pub fn extract_features_named(url: &str) -> Json { name_features(&extract_features(url)) }
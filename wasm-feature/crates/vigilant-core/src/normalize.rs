@@ -0,0 +1,201 @@
+// wasm-feature/crates/vigilant-core/src/normalize.rs
+// Browser Vigilant — one spelling per target. `HTTPS://Evil.com:443/a/./b`,
+// `https://evil.com/a/%62` and `https://evil.com/x/../a/b?utm_source=mail`
+// all load the same page, but string matching sees three URLs: a blocked
+// path slips its override, a learned template reads as new, a brand path
+// hides behind an escape. Rules, templates and the analyzer's path checks
+// therefore look at the normalized URL. The model vector keeps the raw one:
+// its obfuscation features (F32, F33, F37) measure exactly the disguise
+// that normalization removes, and features.py is trained on raw URLs.
+
+/// Query parameters that only tell a site where the click came from.
+pub(crate) const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "gclsrc", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "twclid", "ttclid", "igshid", "li_fat_id",
+    "mc_cid", "mc_eid", "mkt_tok", "_ga", "_gl", "_hsenc", "_hsmi", "oly_anon_id", "oly_enc_id", "vero_id", "vero_conv",
+];
+
+/// Parameter prefixes that mark a tracking parameter (`utm_source`, …).
+pub(crate) const TRACKING_PREFIXES: &[&str] = &["utm_"];
+
+use crate::malformed::browser_form;
+
+const DEFAULT_PORTS: &[(&str, u16)] = &[("http", 80), ("https", 443), ("ws", 80), ("wss", 443), ("ftp", 21)];
+
+fn is_scheme(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic()) && s.chars().all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c))
+}
+
+fn is_tracking(param: &str) -> bool {
+    let key = param.split('=').next().unwrap_or("").to_ascii_lowercase();
+    TRACKING_PARAMS.contains(&key.as_str()) || TRACKING_PREFIXES.iter().any(|p| key.starts_with(p))
+}
+
+/// Decode escapes of unreserved characters (`%7E` → `~`) and uppercase the
+/// hex of the rest (`%2f` → `%2F`). A hex digit that would complete an
+/// escape with a stray `%` before it (`%%34%31`) stays encoded, so the
+/// result doesn't decode further.
+fn decode_unreserved(s: &str) -> String {
+    let b = s.as_bytes();
+    let mut out = String::with_capacity(s.len());
+    let mut i = 0;
+    let hex = |c: &u8| (*c as char).to_digit(16);
+    while i < b.len() {
+        if let (b'%', Some(hi), Some(lo)) = (b[i], b.get(i + 1).and_then(hex), b.get(i + 2).and_then(hex)) {
+            let c = char::from((hi * 16 + lo) as u8);
+            let tail = out.as_bytes();
+            let opens_escape = tail.last() == Some(&b'%') || (tail.len() >= 2 && tail[tail.len() - 2] == b'%' && tail[tail.len() - 1].is_ascii_hexdigit());
+            if (c.is_ascii_alphanumeric() || "-._~".contains(c)) && !(c.is_ascii_hexdigit() && opens_escape) {
+                out.push(c);
+            } else {
+                out += &format!("%{:02X}", hi * 16 + lo);
+            }
+            i += 3;
+            continue;
+        }
+        let len = s[i..].chars().next().map_or(1, char::len_utf8);
+        out.push_str(&s[i..i + len]);
+        i += len;
+    }
+    out
+}
+
+/// `path` with `.` and `..` segments resolved (RFC 3986, 5.2.4); `..`
+/// never climbs above the root.
+fn remove_dot_segments(path: &str) -> String {
+    let absolute = path.starts_with('/');
+    let mut out: Vec<&str> = Vec::new();
+    let mut dir = false;
+    for seg in path.split('/').skip(usize::from(absolute)) {
+        dir = seg == "." || seg == "..";
+        match seg {
+            "." => {}
+            ".." => { out.pop(); }
+            s => out.push(s),
+        }
+    }
+    let mut p = if absolute { "/".to_string() } else { String::new() };
+    p += &out.join("/");
+    if dir && !out.is_empty() { p.push('/'); }
+    p
+}
+
+/// `path` as [`normalize_url`] spells it.
+pub(crate) fn normalize_path(path: &str) -> String { remove_dot_segments(&decode_unreserved(path)) }
+
+/// Canonical spelling of `url`: scheme and host lowercased, a default or
+/// empty port dropped, unreserved escapes decoded and the rest uppercased,
+/// `.`/`..` path segments resolved (an empty path becomes `/`), and the
+/// query stripped of tracking parameters and sorted. Userinfo and the
+/// fragment are kept as they are; an opaque URL (`data:`, `javascript:`)
+/// only has its scheme lowercased. The URL is first read as a browser
+/// reads it, so `https:/\evil.com\a` and `https://evil.com/a` agree.
+pub fn normalize_url(url: &str) -> String {
+    let url = browser_form(url);
+    let url = url.as_str();
+    let (scheme, rest) = match url.find("://") {
+        Some(at) if is_scheme(&url[..at]) => (Some(url[..at].to_ascii_lowercase()), &url[at + 3..]),
+        _ => {
+            if let Some((s, body)) = url.split_once(':') {
+                if is_scheme(s) && !s.contains('.') && !body.starts_with(|c: char| c.is_ascii_digit()) {
+                    return format!("{}:{body}", s.to_ascii_lowercase());
+                }
+            }
+            (None, url)
+        }
+    };
+    let (rest, fragment) = match rest.find('#') { Some(at) => (&rest[..at], Some(&rest[at..])), None => (rest, None) };
+    let (rest, query) = match rest.find('?') { Some(at) => (&rest[..at], Some(&rest[at + 1..])), None => (rest, None) };
+    let (authority, path) = match rest.find('/') { Some(at) => rest.split_at(at), None => (rest, "") };
+
+    let (userinfo, hostport) = match authority.rfind('@') { Some(at) => authority.split_at(at + 1), None => ("", authority) };
+    // a colon inside an IPv6 literal is not a port separator
+    let port_at = hostport.rfind(':').filter(|&at| !hostport[at..].contains(']'));
+    let (host, port) = match port_at {
+        Some(at) if hostport[at + 1..].bytes().all(|b| b.is_ascii_digit()) => (&hostport[..at], Some(&hostport[at + 1..])),
+        _ => (hostport, None),
+    };
+    let default = scheme.as_deref().and_then(|s| DEFAULT_PORTS.iter().find(|(n, _)| *n == s)).map(|(_, p)| *p);
+    let port = port.filter(|p| !p.is_empty()).map(|p| match p.parse::<u16>() {
+        Ok(n) if Some(n) == default => String::new(),
+        Ok(n) => format!(":{n}"),
+        Err(_) => format!(":{p}"),
+    }).unwrap_or_default();
+
+    let path = normalize_path(path);
+    let path = if path.is_empty() && scheme.is_some() { "/".to_string() } else { path };
+    let mut out = scheme.map_or(String::new(), |s| s + "://");
+    out += userinfo;
+    out += &host.to_lowercase();
+    out += &port;
+    out += &path;
+    if let Some(q) = query {
+        let mut params: Vec<String> = q.split('&').filter(|p| !p.is_empty()).map(decode_unreserved).filter(|p| !is_tracking(p)).collect();
+        params.sort();
+        if !params.is_empty() { out.push('?'); out += &params.join("&"); }
+    }
+    out += fragment.unwrap_or("");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spellings_of_one_page_agree() {
+        let want = "https://evil.com/a/b";
+        for url in ["HTTPS://Evil.com:443/a/./b", "https://evil.com/a/%62", "https://evil.com/x/../a/b?utm_source=mail", "https:/\\evil.com\\a\\b"] {
+            assert_eq!(normalize_url(url), want, "{}", url);
+        }
+    }
+
+    #[test]
+    fn malformed_escapes_are_kept_as_written() {
+        assert_eq!(normalize_url("HTTP://A.COM/%zz%2"), "http://a.com/%zz%2");
+        assert_eq!(normalize_url("http://a.com/%e2%82"), "http://a.com/%E2%82");
+        assert_eq!(normalize_url("http://a.com/%%41"), "http://a.com/%%41");
+        assert_eq!(normalize_url("http://a.com/%%34%31"), "http://a.com/%%341");
+        assert_eq!(normalize_url("http://a.com/%4%31x"), "http://a.com/%4%31x");
+    }
+
+    #[test]
+    fn invalid_ports_are_kept_and_empty_ones_dropped() {
+        assert_eq!(normalize_url("http://a.com:99999/"), "http://a.com:99999/");
+        assert_eq!(normalize_url("http://a.com:abc/"), "http://a.com:abc/");
+        assert_eq!(normalize_url("http://a.com:/x"), "http://a.com/x");
+        assert_eq!(normalize_url("http://a.com:0080/"), "http://a.com/");
+        assert_eq!(normalize_url("http://[::1]:80/"), "http://[::1]/");
+        assert_eq!(normalize_url("http://[::1"), "http://[::1/");
+    }
+
+    #[test]
+    fn dot_segments_never_climb_above_the_root() {
+        assert_eq!(normalize_url("http://a.com/../../etc"), "http://a.com/etc");
+        assert_eq!(normalize_url("http://a.com/a/.."), "http://a.com/");
+        assert_eq!(remove_dot_segments("a/../../b"), "b");
+        assert_eq!(remove_dot_segments("/a/b/."), "/a/b/");
+    }
+
+    #[test]
+    fn empty_and_tracking_only_queries_are_dropped() {
+        assert_eq!(normalize_url("http://a.com/?&&utm_x=1&"), "http://a.com/");
+        assert_eq!(normalize_url("http://a.com/?FBCLID=1&b=2&a"), "http://a.com/?a&b=2");
+    }
+
+    #[test]
+    fn input_without_an_authority_is_left_alone() {
+        assert_eq!(normalize_url(""), "");
+        assert_eq!(normalize_url("   "), "");
+        assert_eq!(normalize_url("MAILTO:X@Y"), "mailto:X@Y");
+        assert_eq!(normalize_url("localhost:8080/a"), "localhost:8080/a");
+        assert_eq!(normalize_url("http://"), "http:///");
+    }
+
+    #[test]
+    fn normalizing_twice_changes_nothing() {
+        for url in ["HTTP://A.COM/%zz%2", "http://a.com/%%41", "http://a.com/%%34%31", "http://a.com/%25%34%31", "http://a.com:abc/./x", "http://[::1", "javascript:alert(1)", "http://a.com/?b&a#F"] {
+            let once = normalize_url(url);
+            assert_eq!(normalize_url(&once), once, "{}", url);
+        }
+    }
+}
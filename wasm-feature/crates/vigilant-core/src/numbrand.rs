@@ -0,0 +1,86 @@
+// wasm-feature/crates/vigilant-core/src/numbrand.rs
+// Browser Vigilant — short brands spelled with digits. Indian bank lures
+// register `1cici-netbanking.in`, `5bi-kyc.top` and `hdfcc.co`. Because
+// the real names are three to five letters long, a single edit is already
+// a 20–33% distance, and every threshold that catches them (F21–F22,
+// `typosquat_features`) also catches `sky`, `abi` and `hdtv`. These
+// tokens are instead matched exactly against the spellings lures actually
+// use, and against the brand with digits stuck on (`sbi24`, `365icici`).
+
+use crate::parse_url;
+
+/// Values returned by [`numeric_brand_features`]: lookalike_token,
+/// digit_affixed_brand, in_registrable_label.
+pub const NUMERIC_BRAND_FEATURES: usize = 3;
+
+/// Short brands (as in `BRANDS`) and the spellings that pass for them:
+/// digits for letters (`1`/`i`, `5`/`s`, `0`/`o`, `4`/`a`), `l` for `i`,
+/// a doubled last letter. Only spellings no ordinary word shares are
+/// listed; matches are on whole tokens of the host.
+pub(crate) const SHORT_BRAND_LOOKALIKES: &[(&str, &[&str])] = &[
+    ("sbi",    &["5bi", "sb1", "5b1", "sbl", "5bl", "ssbi", "sbii"]),
+    ("icici",  &["1cici", "icic1", "1c1c1", "1cic1", "lcici", "iclci", "icicl", "icicii", "iccici"]),
+    ("hdfc",   &["hdfcc", "hdffc", "hbfc", "hdf0", "hdfcbnk"]),
+    ("axis",   &["ax1s", "axls", "4xis", "4x1s", "axiss"]),
+    ("kotak",  &["k0tak", "kot4k", "k0t4k", "kotakk"]),
+    ("bsnl",   &["b5nl", "bsn1", "bsnll"]),
+    ("citi",   &["c1ti", "cit1", "c1t1", "clti", "citii"]),
+    ("hsbc",   &["h5bc", "hsbcc", "hsdc"]),
+    ("bhim",   &["bh1m", "bhlm", "bhimm"]),
+    ("paytm",  &["p4ytm", "paytmm", "paytn"]),
+    ("jio",    &["j1o", "ji0", "j10"]),
+    ("airtel", &["a1rtel", "airte1", "4irtel", "airtell"]),
+];
+
+/// Words lures glue onto a brand token (`1cicibank`, `onlinesbl`); one is
+/// stripped from either end before matching.
+const AFFIX_WORDS: &[&str] = &["netbanking", "banking", "bank", "online", "login", "secure", "card", "cards", "kyc", "upi", "pay", "care", "support", "help"];
+
+/// Most digits stuck on a brand for the token to count as digit-affixed.
+const MAX_AFFIX_DIGITS: usize = 4;
+
+/// `token` without one [`AFFIX_WORDS`] prefix or suffix, when what's left
+/// is still a token.
+fn strip_word(token: &str) -> &str {
+    AFFIX_WORDS.iter().find_map(|w| token.strip_suffix(w).or_else(|| token.strip_prefix(w)).filter(|r| r.len() >= 3)).unwrap_or(token)
+}
+
+/// Brand `token` spells with a look-alike from [`SHORT_BRAND_LOOKALIKES`].
+fn lookalike(token: &str) -> Option<&'static str> {
+    SHORT_BRAND_LOOKALIKES.iter().find(|(_, spellings)| spellings.contains(&token)).map(|(brand, _)| *brand)
+}
+
+/// Brand `token` is with 1–[`MAX_AFFIX_DIGITS`] digits before or after it.
+fn digit_affixed(token: &str) -> Option<&'static str> {
+    let core = token.trim_matches(|c: char| c.is_ascii_digit());
+    let digits = token.len() - core.len();
+    if digits == 0 || digits > MAX_AFFIX_DIGITS || core.is_empty() { return None; }
+    SHORT_BRAND_LOOKALIKES.iter().map(|(brand, _)| *brand).find(|b| *b == core)
+}
+
+/// The first brand a token of `host` passes for, whether it was a
+/// look-alike spelling (rather than digits stuck on), and whether the
+/// token sits in `reg_label`.
+pub(crate) fn numeric_brand(host: &str, reg_label: &str) -> Option<(&'static str, bool, bool)> {
+    let host = host.trim_end_matches('.').to_lowercase();
+    let reg_label = reg_label.to_lowercase();
+    let in_reg = |t: &str| reg_label.split('-').any(|r| r == t);
+    host.split(['.', '-']).filter(|t| t.len() >= 3).find_map(|t| {
+        let core = strip_word(t);
+        lookalike(core).map(|b| (b, true, in_reg(t))).or_else(|| digit_affixed(core).map(|b| (b, false, in_reg(t))))
+    })
+}
+
+/// `[lookalike_token, digit_affixed_brand, in_registrable_label]`: a host
+/// token (less one glued-on word like `bank`) is a listed look-alike of a
+/// short brand; one is the brand with digits before or after it; and the
+/// matched token is in the registrable label rather than a subdomain.
+pub fn numeric_brand_features(url: &str) -> [f32; NUMERIC_BRAND_FEATURES] {
+    let p = parse_url(url);
+    let reg_label = p.reg_domain.split('.').next().unwrap_or("");
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    match numeric_brand(&p.host, reg_label) {
+        Some((_, spelled, in_reg)) => [b(spelled), b(!spelled), b(in_reg)],
+        None => [0.0; NUMERIC_BRAND_FEATURES],
+    }
+}
@@ -0,0 +1,241 @@
+// wasm-feature/crates/vigilant-core/src/oauth.rs
+// Browser Vigilant — OAuth / OpenID Connect consent-phishing analyzer
+
+use crate::json::Json;
+use crate::psl::registrable_domain;
+use crate::typosquat::misspells;
+use crate::{has_ip, ip_host, parse_url, query_pairs, SHORT_SERVICES, SUSPICIOUS_TLDS};
+
+/// Known identity providers: (name, registrable domain, authorize-host prefix).
+pub(crate) const IDPS: &[(&str, &str, &str)] = &[
+    ("microsoft", "microsoftonline.com", "login."),
+    ("microsoft", "live.com",            "login."),
+    ("microsoft", "microsoft.com",       ""),
+    ("google",    "google.com",          "accounts."),
+    ("apple",     "apple.com",           "appleid."),
+    ("github",    "github.com",          ""),
+    ("facebook",  "facebook.com",        ""),
+    ("okta",      "okta.com",            ""),
+    ("auth0",     "auth0.com",           ""),
+    ("salesforce","salesforce.com",      "login."),
+    ("slack",     "slack.com",           ""),
+    ("dropbox",   "dropbox.com",         ""),
+];
+
+/// Domains the identity providers serve their own APIs, content and
+/// tenants from; no host on them imitates an IdP.
+const IDP_SERVICE_DOMAINS: &[&str] = &[
+    "googleapis.com", "googleusercontent.com", "gstatic.com", "google.co.uk", "youtube.com", "gmail.com",
+    "msauth.net", "msftauth.net", "live.net", "office.com", "office365.com", "outlook.com", "sharepoint.com",
+    "azure.com", "windows.net", "icloud.com", "githubusercontent.com", "githubassets.com", "github.io",
+    "fbcdn.net", "facebook.net", "oktacdn.com", "oktapreview.com", "okta-emea.com", "slack-edge.com",
+    "dropboxusercontent.com", "dropboxstatic.com", "force.com", "salesforce-sites.com",
+];
+
+/// Words that put a host naming an IdP in a sign-in context.
+const CREDENTIAL_WORDS: &[&str] = &["login", "signin", "logon", "auth", "account", "verify", "sso", "password", "secure"];
+
+/// Verification pages for the device-authorization grant (RFC 8628).
+const DEVICE_PATHS: &[&str] = &["/devicelogin", "/device", "/oauth2/deviceauth", "/activate", "/login/device"];
+
+/// Scopes that grant mailbox, file-store or directory-wide access.
+const BROAD_SCOPES: &[&str] = &[
+    "mail.read","mail.readwrite","mail.send","mailboxsettings.readwrite",
+    "files.read.all","files.readwrite.all","sites.readwrite.all",
+    "directory.readwrite.all","user.readwrite.all","full_access_as_user",
+    "contacts.read","contacts.readwrite",
+    "https://mail.google.com/","https://www.googleapis.com/auth/gmail.modify",
+    "https://www.googleapis.com/auth/gmail.readonly","https://www.googleapis.com/auth/gmail.send",
+    "https://www.googleapis.com/auth/drive","https://www.googleapis.com/auth/contacts",
+    "repo","admin:org","delete_repo","write:org",
+];
+
+/// Free hosting / tunnelling services that show up as consent-phish redirect targets.
+pub(crate) const TUNNEL_HOSTS: &[&str] = &[
+    "ngrok.io","ngrok-free.app","trycloudflare.com","loca.lt","serveo.net",
+    "herokuapp.com","workers.dev","pages.dev","web.app","firebaseapp.com",
+    "netlify.app","vercel.app","glitch.me","repl.co","azurewebsites.net",
+];
+
+fn idp_of(host: &str) -> Option<&'static str> {
+    let reg = registrable_domain(host);
+    IDPS.iter().find(|(_, d, _)| reg == *d).map(|(n, _, _)| *n)
+}
+
+/// Host imitates an IdP without being one or one of its service domains:
+/// its registrable label misspells the IdP's, or it names the IdP as a
+/// label or hyphenated word next to a sign-in word (`microsoft-login.com`,
+/// `login.google.com.evil.tk`).
+fn lookalike_idp(host: &str) -> Option<&'static str> {
+    let service = |d: &&str| host == *d || host.strip_suffix(*d).is_some_and(|h| h.ends_with('.'));
+    if idp_of(host).is_some() || IDP_SERVICE_DOMAINS.iter().any(service) { return None; }
+    let reg = registrable_domain(host);
+    let label = reg.split('.').next().unwrap_or("");
+    let words: Vec<&str> = host.split(['.', '-']).collect();
+    let sign_in = words.iter().any(|w| CREDENTIAL_WORDS.iter().any(|c| w.contains(c)));
+    IDPS.iter().find_map(|(name, dom, _)| {
+        let idp_label = dom.split('.').next().unwrap_or("");
+        if idp_label.len() < 5 { return None; }
+        let named = words.iter().any(|w| w == name || *w == idp_label);
+        (misspells(label, idp_label) || (named && sign_in)).then_some(*name)
+    })
+}
+
+fn is_loopback(host: &str) -> bool {
+    host == "localhost" || host.ends_with(".localhost") || ip_host(host).is_some_and(|ip| ip.is_loopback())
+}
+
+/// Flags raised for a redirect_uri under web / native client conventions.
+fn redirect_flags(redirect: &str, auth_host: &str, flags: &mut Vec<&'static str>) -> String {
+    let low = redirect.to_lowercase();
+    if low.starts_with("urn:ietf:wg:oauth:2.0:oob") {
+        flags.push("redirect_oob");
+        return String::new();
+    }
+    // private-use schemes (com.example.app:/cb) are the native-client convention
+    if let Some((scheme, _)) = low.split_once(':') {
+        if scheme.contains('.') && !scheme.contains('/') && !low.contains("://") { return String::new(); }
+    }
+    let r = parse_url(redirect);
+    let host = r.host.to_lowercase();
+    match r.scheme.as_str() {
+        "https" => {}
+        "http" if is_loopback(&host) => {}
+        "http" => flags.push("redirect_insecure_http"),
+        "" => flags.push("redirect_unparseable"),
+        _ => flags.push("redirect_unusual_scheme"),
+    }
+    if host.is_empty() { return host; }
+    if has_ip(&host) && !is_loopback(&host) { flags.push("redirect_ip_host"); }
+    let reg = registrable_domain(&host);
+    if TUNNEL_HOSTS.contains(&reg.as_str()) { flags.push("redirect_tunnel_host"); }
+    if SHORT_SERVICES.contains(&reg.as_str()) { flags.push("redirect_shortener"); }
+    if SUSPICIOUS_TLDS.contains(&r.tld.as_str()) { flags.push("redirect_suspicious_tld"); }
+    if query_pairs(&r.query).iter().any(|(_, v)| v.starts_with("http://") || v.starts_with("https://")) {
+        flags.push("redirect_chained_url");
+    }
+    if reg == registrable_domain(auth_host) { flags.push("redirect_same_site_as_idp"); }
+    host
+}
+
+/// Analyze an OAuth 2.0 / OIDC authorization or device-verification URL.
+/// Returns `{ is_oauth, provider, lookalike_of, client_id, redirect_uri,
+/// redirect_host, response_type, scopes, broad_scopes, flags, risk }`.
+pub fn oauth_report(url: &str) -> Json {
+    let p = parse_url(url.trim());
+    let host = p.host.to_lowercase();
+    let path = p.path.to_lowercase();
+    let params = query_pairs(&p.query);
+    let param = |k: &str| params.iter().find(|(pk, _)| pk == k).map(|(_, v)| v.clone());
+
+    let client_id = param("client_id");
+    let response_type = param("response_type");
+    let redirect_uri = param("redirect_uri");
+    let is_device = DEVICE_PATHS.iter().any(|d| path == *d || path.starts_with(&format!("{}/", d)));
+    let is_authorize = path.contains("authorize") || path.contains("/oauth") || path.contains("/o/oauth2");
+    let is_oauth = (client_id.is_some() && (response_type.is_some() || is_authorize)) || is_device;
+
+    let provider = idp_of(&host);
+    let lookalike = lookalike_idp(&host);
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+
+    if !is_oauth {
+        return Json::obj()
+            .with("is_oauth", false)
+            .with("provider", provider)
+            .with("lookalike_of", lookalike)
+            .with("risk", if lookalike.is_some() { 0.6f32 } else { 0.0 });
+    }
+
+    if lookalike.is_some() {
+        flags.push("lookalike_idp");
+        risk += 0.6;
+    } else if provider.is_none() {
+        flags.push("unknown_idp");
+        risk += 0.1;
+    }
+    if p.scheme != "https" { flags.push("insecure_authorize_endpoint"); risk += 0.3; }
+
+    // Device-code lure: verification page with the attacker's code pre-filled.
+    if is_device {
+        flags.push("device_code_flow");
+        if params.iter().any(|(k, _)| k == "user_code" || k == "otc" || k == "code") {
+            flags.push("device_code_prefilled");
+            risk += 0.5;
+        }
+        risk += 0.1;
+    }
+
+    let redirect_host = redirect_uri.as_deref()
+        .map(|r| redirect_flags(r, &host, &mut flags))
+        .unwrap_or_default();
+    if redirect_uri.is_none() && !is_device { flags.push("redirect_missing"); }
+    for f in &flags {
+        risk += match *f {
+            "redirect_insecure_http" | "redirect_ip_host" | "redirect_tunnel_host" => 0.3,
+            "redirect_shortener" | "redirect_chained_url" => 0.25,
+            "redirect_suspicious_tld" | "redirect_unusual_scheme" => 0.2,
+            "redirect_oob" => 0.15,
+            _ => 0.0,
+        };
+    }
+
+    let scope_raw = param("scope").unwrap_or_default();
+    let scopes: Vec<String> = scope_raw.split([' ', ',']).filter(|s| !s.is_empty()).map(str::to_string).collect();
+    let broad: Vec<String> = scopes.iter()
+        .filter(|s| BROAD_SCOPES.contains(&s.to_lowercase().as_str()) || s.to_lowercase().ends_with(".all"))
+        .cloned().collect();
+    let offline = scopes.iter().any(|s| s.eq_ignore_ascii_case("offline_access"));
+    if !broad.is_empty() {
+        flags.push("broad_scopes");
+        risk += (0.1 * broad.len() as f32).min(0.3);
+        if offline { flags.push("broad_scopes_with_offline_access"); risk += 0.2; }
+    }
+    if param("prompt").is_some_and(|v| v.contains("consent")) && offline {
+        flags.push("forced_consent_offline");
+        risk += 0.1;
+    }
+    // Implicit grant hands tokens straight to the redirect target.
+    if response_type.as_deref().is_some_and(|rt| rt.split(' ').any(|t| t == "token"))
+        && !redirect_host.is_empty() && idp_of(&redirect_host).is_none() {
+        flags.push("implicit_grant");
+        risk += 0.1;
+    }
+
+    Json::obj()
+        .with("is_oauth", true)
+        .with("provider", provider)
+        .with("lookalike_of", lookalike)
+        .with("client_id", client_id)
+        .with("redirect_uri", redirect_uri)
+        .with("redirect_host", redirect_host)
+        .with("response_type", response_type)
+        .with("scopes", scopes)
+        .with("broad_scopes", broad)
+        .with("flags", flags)
+        .with("risk", risk.min(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lookalike_idp;
+
+    #[test]
+    fn idp_service_domains_and_words_are_not_lookalikes() {
+        for host in ["storage.googleapis.com", "lh3.googleusercontent.com", "black.com", "maple.com", "login.microsoftonline.com", "accounts.google.com"] {
+            assert_eq!(lookalike_idp(host), None, "{}", host);
+        }
+    }
+
+    #[test]
+    fn misspelt_or_borrowed_idp_names_are_lookalikes() {
+        for (host, idp) in [
+            ("slsck.com", "slack"), ("appel.com", "apple"), ("githb.com", "github"), ("micros0ftonline.com", "microsoft"),
+            ("microsoft-login.com", "microsoft"), ("login.google.com.evil.tk", "google"), ("accounts.google-verify.tk", "google"),
+        ] {
+            assert_eq!(lookalike_idp(host), Some(idp), "{}", host);
+        }
+        assert_eq!(lookalike_idp("google-fans.blog"), None);
+    }
+}
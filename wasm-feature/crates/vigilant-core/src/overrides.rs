@@ -0,0 +1,168 @@
+// wasm-feature/crates/vigilant-core/src/overrides.rs
+// Browser Vigilant — host patterns for user overrides and admin host rules.
+// A rule is a host (covering its subdomains), `*.` in front of a host for
+// its subdomains only, and optionally a path prefix scoping it to part of
+// the site: `*.corp.example.com/admin`. Rules are stored by their canonical
+// text and looked up by every key a URL could match, so hash-only mode,
+// which never keeps a rule in the clear, matches them the same way.
+
+use crate::json::Json;
+use crate::normalize::{normalize_path, normalize_url};
+use crate::parse_url;
+use crate::psl::public_suffix;
+use crate::types::{err, SchemaError};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverrideRule {
+    /// `*.host`: subdomains only, not the host itself.
+    pub wildcard: bool,
+    pub host:     String,
+    /// Path prefix, matched on segment boundaries; `None` covers the whole site.
+    pub path:     Option<String>,
+}
+
+impl OverrideRule {
+    /// Parse a rule; a pasted URL's scheme, userinfo, port, query and
+    /// trailing `/*` are dropped, and the path is spelled as
+    /// [`normalize_url`] spells the URLs it is matched against.
+    pub fn parse(rule: &str) -> Result<OverrideRule, SchemaError> {
+        let s = rule.trim();
+        let s = s.split_once("://").map_or(s, |(_, rest)| rest);
+        let s = s.split(['?', '#']).next().unwrap_or("");
+        let (host, path) = match s.find('/') { Some(i) => s.split_at(i), None => (s, "") };
+        let host = host.rsplit('@').next().unwrap_or("");
+        // as `parse_url` splits it, so `[::1]` keeps its colons
+        let host = match host.rsplit_once(':') { Some((h, port)) if port.bytes().all(|b| b.is_ascii_digit()) => h, _ => host };
+        let host = host.trim_end_matches('.').to_lowercase();
+        let (wildcard, host) = match host.strip_prefix("*.") { Some(h) => (true, h.to_string()), None => (false, host) };
+        if host.is_empty() { return Err(err("", "rule has no host")); }
+        if host.contains('*') { return Err(err("", "a wildcard may only replace the leftmost label, as in *.example.com")); }
+        if host.split('.').any(str::is_empty) || host.chars().any(char::is_whitespace) {
+            return Err(err("", &format!("\"{}\" is not a host name", host)));
+        }
+        if wildcard && public_suffix(&host) == host {
+            return Err(err("", &format!("*.{} would cover every site under a public suffix", host)));
+        }
+        let path = normalize_path(path.strip_suffix("/*").unwrap_or(path));
+        if path.contains('*') { return Err(err("", "path scopes are prefixes and take no wildcards")); }
+        // matched segment by segment, so empty ones would never match
+        let segs: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let path = (!segs.is_empty()).then(|| format!("/{}", segs.join("/")));
+        Ok(OverrideRule { wildcard, host, path })
+    }
+
+    /// Canonical text, the form rules are stored and exported under.
+    pub fn key(&self) -> String {
+        format!("{}{}{}", if self.wildcard { "*." } else { "" }, self.host, self.path.as_deref().unwrap_or(""))
+    }
+
+    /// Labels in the rule's host and segments in its path prefix; the
+    /// higher pair wins, host first.
+    pub fn specificity(&self) -> (usize, usize) {
+        (self.host.split('.').count(), self.path.as_deref().map_or(0, |p| p.split('/').filter(|s| !s.is_empty()).count()))
+    }
+}
+
+/// Rule keys that match `host` and `path`, grouped by specificity, most
+/// specific first: the host itself, then each parent as a plain and a
+/// wildcard rule, and at each host every path prefix, longest first.
+pub(crate) fn match_keys(host: &str, path: &str) -> Vec<((usize, usize), Vec<String>)> {
+    let host = host.trim_end_matches('.').to_lowercase();
+    let segs: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let prefixes: Vec<String> = (0..=segs.len()).rev()
+        .map(|n| segs[..n].iter().map(|s| format!("/{}", s)).collect())
+        .collect();
+    let mut groups = Vec::new();
+    let levels = std::iter::successors(Some(host.as_str()), |h| h.split_once('.').map(|(_, rest)| rest)).filter(|h| !h.is_empty());
+    for level in levels {
+        let labels = level.split('.').count();
+        let hosts = if level == host { vec![level.to_string()] } else { vec![level.to_string(), format!("*.{}", level)] };
+        for (depth, prefix) in prefixes.iter().enumerate().map(|(i, p)| (segs.len() - i, p)) {
+            groups.push(((labels, depth), hosts.iter().map(|h| format!("{}{}", h, prefix)).collect()));
+        }
+    }
+    groups
+}
+
+/// Check `rule` against `url` before deploying it. Returns `{ rule,
+/// wildcard, host, path, specificity: { labels, segments }, host_match,
+/// path_match, matches }`, where `rule` is the canonical form it is stored
+/// under. `url` is matched in its [`normalize_url`] form, as a context does.
+/// Fails on a malformed rule.
+pub fn test_rule(url: &str, rule: &str) -> Result<Json, SchemaError> {
+    let r = OverrideRule::parse(rule)?;
+    let p = parse_url(&normalize_url(url));
+    let site = OverrideRule { path: None, ..r.clone() }.key();
+    let host_match = match_keys(&p.host, "").iter().any(|(_, keys)| keys.contains(&site));
+    let segs = |s: &str| s.split('/').filter(|s| !s.is_empty()).map(str::to_string).collect::<Vec<_>>();
+    let path_match = r.path.as_deref().is_none_or(|rp| segs(&p.path).starts_with(&segs(rp)));
+    let key = r.key();
+    let (labels, segments) = r.specificity();
+    Ok(Json::obj()
+        .with("rule", key.as_str())
+        .with("wildcard", r.wildcard)
+        .with("host", r.host.as_str())
+        .with("path", r.path.as_deref())
+        .with("specificity", Json::obj().with("labels", labels).with("segments", segments))
+        .with("host_match", host_match)
+        .with("path_match", path_match)
+        .with("matches", host_match && path_match))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(rule: &str) -> Result<String, String> { OverrideRule::parse(rule).map(|r| r.key()).map_err(|e| e.message) }
+
+    fn matches(url: &str, rule: &str) -> bool { test_rule(url, rule).unwrap().get("matches").and_then(Json::as_bool) == Some(true) }
+
+    #[test]
+    fn pasted_urls_reduce_to_their_rule() {
+        assert_eq!(key("https://User@EXAMPLE.com.:8080/admin/*?x=1#top"), Ok("example.com/admin".to_string()));
+        assert_eq!(key("*.Corp.Example.com/"), Ok("*.corp.example.com".to_string()));
+        assert_eq!(key("[::1]:8080/x"), Ok("[::1]/x".to_string()));
+    }
+
+    #[test]
+    fn paths_are_spelled_as_matched_urls_are() {
+        assert_eq!(key("example.com//admin//"), Ok("example.com/admin".to_string()));
+        assert_eq!(key("example.com/%61dmin"), Ok("example.com/admin".to_string()));
+        assert_eq!(key("example.com/a/../b/./c"), Ok("example.com/b/c".to_string()));
+        assert_eq!(key("example.com/admin/.."), Ok("example.com".to_string()));
+        assert!(matches("https://example.com/admin/users", "example.com//%61dmin"));
+    }
+
+    #[test]
+    fn rules_without_a_host_are_refused() {
+        for rule in ["", "  ", "https://", "/admin", "user@", ":8080/x"] {
+            assert_eq!(key(rule), Err("rule has no host".to_string()), "{:?}", rule);
+        }
+    }
+
+    #[test]
+    fn misplaced_wildcards_are_refused() {
+        for rule in ["*", "*.", "foo.*.com", "*.*.example.com", "*example.com"] {
+            assert!(key(rule).is_err_and(|e| e.starts_with("a wildcard may only replace")), "{:?}", rule);
+        }
+        assert_eq!(key("example.com/a*b"), Err("path scopes are prefixes and take no wildcards".to_string()));
+        assert_eq!(key("*.co.uk"), Err("*.co.uk would cover every site under a public suffix".to_string()));
+        assert!(key("*.com").is_err());
+    }
+
+    #[test]
+    fn malformed_host_names_are_refused() {
+        for rule in ["a..b", ".example.com", "ex ample.com", "example.com\t/x"] {
+            assert!(key(rule).is_err_and(|e| e.ends_with("is not a host name")), "{:?}", rule);
+        }
+        assert!(test_rule("https://example.com/", "a..b").is_err());
+    }
+
+    #[test]
+    fn paths_match_on_segment_boundaries() {
+        assert!(matches("https://example.com/admin", "example.com/admin"));
+        assert!(!matches("https://example.com/administrator", "example.com/admin"));
+        assert!(matches("https://x.example.com/admin/a", "*.example.com/admin"));
+        assert!(!matches("https://example.com/admin", "*.example.com"));
+    }
+}
@@ -0,0 +1,245 @@
+// wasm-feature/crates/vigilant-core/src/packed.rs
+// Browser Vigilant — compact feature vectors for telemetry and the
+// extension's IndexedDB cache. A 77-float vector is 308 bytes as f32; most
+// entries are flags, ratios and small counts, so one byte each carries them
+// with no or little loss, and the whole vector fits in 80 bytes.
+//
+// Layout: precision tag (0 f32, 1 f16, 2 u8), feature version, value count,
+// then the values, little-endian. A vector packed under another
+// [`FEATURE_VERSION`] doesn't decode: its indices mean something else.
+//
+// u8 quantizes by what each feature holds: 0–1 values in 1/255 steps,
+// counts as themselves up to 255, lengths on a log scale (within 2.2%),
+// entropies in steps of 1/16 bit.
+
+use crate::mask::MASKABLE_FEATURES;
+use crate::model::FEATURE_VERSION;
+use crate::types::{err, SchemaError};
+
+/// How wide each packed value is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Precision {
+    /// Lossless.
+    F32,
+    /// Half floats: about three significant digits.
+    F16,
+    /// One byte per feature, quantized per feature; model-plus-time vectors only.
+    #[default]
+    U8,
+}
+
+impl Precision {
+    pub fn parse(name: &str) -> Option<Precision> {
+        match name { "f32" => Some(Precision::F32), "f16" => Some(Precision::F16), "u8" => Some(Precision::U8), _ => None }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self { Precision::F32 => "f32", Precision::F16 => "f16", Precision::U8 => "u8" }
+    }
+
+    fn tag(self) -> u8 {
+        match self { Precision::F32 => 0, Precision::F16 => 1, Precision::U8 => 2 }
+    }
+
+    fn from_tag(tag: u8) -> Option<Precision> {
+        match tag { 0 => Some(Precision::F32), 1 => Some(Precision::F16), 2 => Some(Precision::U8), _ => None }
+    }
+
+    fn width(self) -> usize {
+        match self { Precision::F32 => 4, Precision::F16 => 2, Precision::U8 => 1 }
+    }
+}
+
+/// What a feature holds, and so how it fits in a byte.
+#[derive(Clone, Copy)]
+enum Quant {
+    /// Flags and ratios in 0–1.
+    Unit,
+    /// Small whole numbers, saturating at 255.
+    Count,
+    /// Lengths, which run into the thousands: `16 · log2(1 + v)`.
+    Length,
+    /// Bits of entropy, up to 16.
+    Bits,
+}
+
+use Quant::{Bits, Count, Length, Unit};
+
+/// Per index of the model-plus-time vector (see `features.py` for names).
+const QUANT: [Quant; MASKABLE_FEATURES] = [
+    // lexical structure
+    Length, Count, Length, Length, Count, Count, Count, Count, Count, Length, Unit, Unit, Unit, Unit, Count, Unit,
+    // information theory
+    Bits, Bits, Bits, Bits, Bits,
+    // brand similarity, keywords
+    Unit, Unit, Unit, Unit, Unit, Unit, Unit, Unit, Unit, Unit,
+    // obfuscation
+    Unit, Unit, Unit, Count, Unit, Unit, Unit,
+    // domain quality
+    Unit, Count, Unit, Unit, Unit, Unit, Count, Unit, Unit, Count,
+    // UPI, file and extension risk
+    Unit, Unit, Unit, Unit, Unit, Unit, Unit, Unit,
+    // extension class, scheme class, scheme payload risk
+    Unit, Unit, Unit, Unit, Unit, Unit, Unit,
+    Unit, Unit, Unit, Unit, Unit, Unit, Unit, Unit, Unit,
+    Count, Unit, Unit, Unit, Unit,
+    // time: is_weekend, local_night_hours, first_seen_bucket
+    Unit, Unit, Count,
+];
+
+impl Quant {
+    fn as_str(self) -> &'static str {
+        match self { Unit => "ratio", Count => "count", Length => "length", Bits => "bits" }
+    }
+
+    fn encode(self, v: f32) -> u8 {
+        let v = if v.is_nan() { 0.0 } else { v.max(0.0) };
+        let q = match self {
+            Unit => v.min(1.0) * 255.0,
+            Count => v,
+            Length => (1.0 + v).log2() * 16.0,
+            Bits => v * 16.0,
+        };
+        q.round().min(255.0) as u8
+    }
+
+    fn decode(self, q: u8) -> f32 {
+        let q = f32::from(q);
+        match self {
+            Unit => q / 255.0,
+            Count => q,
+            Length => ((q / 16.0).exp2() - 1.0).round(),
+            Bits => q / 16.0,
+        }
+    }
+}
+
+/// What maskable feature `i` measures: "ratio" (0–1), "count", "length" or
+/// "bits" of entropy.
+pub(crate) fn value_type(i: usize) -> Option<&'static str> { QUANT.get(i).map(|q| q.as_str()) }
+
+/// IEEE 754 half from single, rounding to nearest even.
+fn f16_bits(v: f32) -> u16 {
+    let x = v.to_bits();
+    let sign = ((x >> 16) & 0x8000) as u16;
+    let exp = ((x >> 23) & 0xff) as i32;
+    let man = x & 0x7f_ffff;
+    if exp == 0xff { return sign | 0x7c00 | if man != 0 { 0x200 } else { 0 }; }
+    let e = exp - 127 + 15;
+    if e >= 0x1f { return sign | 0x7c00; }
+    if e <= 0 {
+        if e < -10 { return sign; }
+        let man = man | 0x80_0000;
+        let shift = (14 - e) as u32;
+        let half = 1 << (shift - 1);
+        let rest = man & ((1 << shift) - 1);
+        let mut h = man >> shift;
+        if rest > half || (rest == half && h & 1 == 1) { h += 1; }
+        return sign | h as u16;
+    }
+    let mut h = ((e as u32) << 10) | (man >> 13);
+    let rest = man & 0x1fff;
+    if rest > 0x1000 || (rest == 0x1000 && h & 1 == 1) { h += 1; }
+    sign | h as u16
+}
+
+fn f16_value(h: u16) -> f32 {
+    let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = i32::from((h >> 10) & 0x1f);
+    let man = f32::from(h & 0x3ff);
+    sign * match exp {
+        0 => man * (-24f32).exp2(),
+        0x1f if man == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + man / 1024.0) * ((exp - 15) as f32).exp2(),
+    }
+}
+
+/// Pack `features` (a model or model-plus-time vector for `U8`) at
+/// `precision`. Fails when `U8` gets a vector it has no layout for.
+pub fn pack_features(features: &[f32], precision: Precision) -> Result<Vec<u8>, SchemaError> {
+    if features.len() > u8::MAX as usize { return Err(err("", "at most 255 features pack")); }
+    if precision == Precision::U8 && features.len() > QUANT.len() {
+        return Err(err("", &format!("u8 packs at most {} features; use f16", QUANT.len())));
+    }
+    let mut out = Vec::with_capacity(3 + features.len() * precision.width());
+    out.extend([precision.tag(), FEATURE_VERSION as u8, features.len() as u8]);
+    for (i, &v) in features.iter().enumerate() {
+        match precision {
+            Precision::F32 => out.extend(v.to_le_bytes()),
+            Precision::F16 => out.extend(f16_bits(v).to_le_bytes()),
+            Precision::U8 => out.push(QUANT[i].encode(v)),
+        }
+    }
+    Ok(out)
+}
+
+/// Decode a vector from [`pack_features`]. Fails on a malformed buffer and
+/// on one packed under another feature version.
+pub fn unpack_features(bytes: &[u8]) -> Result<Vec<f32>, SchemaError> {
+    let [tag, version, count, body @ ..] = bytes else { return Err(err("", "packed vector is truncated")) };
+    let precision = Precision::from_tag(*tag).ok_or_else(|| err("", &format!("unknown precision tag {}", tag)))?;
+    if u32::from(*version) != FEATURE_VERSION {
+        return Err(err("", &format!("vector has feature version {}, this build extracts {}", version, FEATURE_VERSION)));
+    }
+    let count = *count as usize;
+    if body.len() != count * precision.width() { return Err(err("", "packed vector length doesn't match its count")); }
+    if precision == Precision::U8 && count > QUANT.len() { return Err(err("", "too many u8 features")); }
+    Ok(match precision {
+        Precision::F32 => body.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect(),
+        Precision::F16 => body.chunks_exact(2).map(|b| f16_value(u16::from_le_bytes([b[0], b[1]]))).collect(),
+        Precision::U8 => body.iter().zip(QUANT).map(|(&q, k)| k.decode(q)).collect(),
+    })
+}
+
+/// The model features of `url`, one byte each (see [`Precision::U8`]).
+pub fn extract_features_packed(url: &str) -> Vec<u8> {
+    pack_features(&crate::extract_features(url), Precision::U8).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(bytes: &[u8]) -> String { unpack_features(bytes).unwrap_err().message }
+
+    #[test]
+    fn every_precision_round_trips_a_url_vector() {
+        let f = crate::extract_features("http://secure-login.example.tk/verify.php?acct=1");
+        assert_eq!(unpack_features(&pack_features(&f, Precision::F32).unwrap()).unwrap(), f);
+        let half = unpack_features(&pack_features(&f, Precision::F16).unwrap()).unwrap();
+        assert!(half.iter().zip(&f).all(|(a, b)| (a - b).abs() <= b.abs() / 512.0), "{:?}", half);
+        assert_eq!(extract_features_packed("http://secure-login.example.tk/verify.php?acct=1").len(), 3 + f.len());
+    }
+
+    #[test]
+    fn a_short_buffer_is_refused() {
+        assert_eq!(message(&[]), "packed vector is truncated");
+        assert_eq!(message(&[2, FEATURE_VERSION as u8]), "packed vector is truncated");
+    }
+
+    #[test]
+    fn another_feature_version_is_refused() {
+        let mut bytes = pack_features(&[0.5, 1.0], Precision::F32).unwrap();
+        bytes[1] = FEATURE_VERSION as u8 - 1;
+        assert_eq!(message(&bytes), format!("vector has feature version {}, this build extracts {}", FEATURE_VERSION - 1, FEATURE_VERSION));
+    }
+
+    #[test]
+    fn a_body_that_disagrees_with_its_count_is_refused() {
+        let bytes = pack_features(&[0.5, 1.0], Precision::F16).unwrap();
+        assert_eq!(message(&bytes[..bytes.len() - 1]), "packed vector length doesn't match its count");
+        assert_eq!(message(&[&bytes[..], &[0]].concat()), "packed vector length doesn't match its count");
+        assert_eq!(message(&[0, FEATURE_VERSION as u8, 1, 0, 0]), "packed vector length doesn't match its count");
+    }
+
+    #[test]
+    fn unknown_tags_and_oversized_u8_vectors_are_refused() {
+        assert_eq!(message(&[3, FEATURE_VERSION as u8, 0]), "unknown precision tag 3");
+        let count = QUANT.len() + 1;
+        let bytes = [&[2, FEATURE_VERSION as u8, count as u8][..], &vec![0; count]].concat();
+        assert_eq!(message(&bytes), "too many u8 features");
+        assert!(pack_features(&vec![0.0; count], Precision::U8).is_err());
+        assert!(pack_features(&[0.0; 256], Precision::F32).is_err());
+    }
+}
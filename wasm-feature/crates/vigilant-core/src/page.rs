@@ -0,0 +1,92 @@
+// wasm-feature/crates/vigilant-core/src/page.rs
+// Browser Vigilant — whole-page verdict from a content-script snapshot
+
+use crate::csp::{csp_report, WEAK_CSP_RISK};
+use crate::exfil;
+use crate::json::Json;
+use crate::lang::{detect_lang, lure_words, INDIC};
+use crate::score::{combine, url_risk};
+use crate::translit::{romanize, ROMANIZED_LURES};
+use crate::types::PageSnapshot;
+use crate::verdict::Verdict;
+use crate::{analyze_form_action, parse_url, should_allow_autofill};
+
+/// Links scored per snapshot; the rest of a link farm adds nothing new.
+const MAX_LINKS: usize = 200;
+
+/// Page text scanned for lure phrases.
+const MAX_TEXT_CHARS: usize = 20_000;
+
+/// Lure phrases of the page's detected language found in its title and
+/// text. Indic text is also romanised, which catches loanwords and mixed
+/// spellings the native-script pack misses.
+fn lure_hits(p: &PageSnapshot, lang: &str) -> usize {
+    let text: String = p.title.chars().chain([' ']).chain(p.text.chars()).take(MAX_TEXT_CHARS).flat_map(char::to_lowercase).collect();
+    let native = lure_words(lang).iter().filter(|w| text.contains(*w)).count();
+    if !INDIC.contains(&lang) { return native; }
+    let latin = romanize(&text);
+    native.max(ROMANIZED_LURES.iter().filter(|w| latin.contains(*w)).count())
+}
+
+/// Score a content-script snapshot: the page URL, its forms, autofill, lure
+/// phrases in the text (in its detected language), chat-bot webhooks in form
+/// actions or inline scripts, and the worst outgoing link. Returns `{ url,
+/// url_risk, form_risk, autofill, lang, text_risk, worst_link, csp,
+/// findings, flags, score, verdict }`; any webhook finding is critical and
+/// blocks. `csp` is graded only on password pages whose policy was supplied.
+pub fn page_report(p: &PageSnapshot) -> Json {
+    let u = parse_url(&p.url);
+    let host = u.host.to_lowercase();
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = url_risk(&p.url);
+
+    let mut form_risk = 0.0f32;
+    for f in &p.forms {
+        let r = analyze_form_action(&f.action, &host);
+        form_risk = form_risk.max(r);
+        if f.has_password {
+            if u.scheme == "http" { flags.push("password_over_http"); form_risk = form_risk.max(0.6); }
+            if r >= 0.8 { flags.push("credentials_posted_offsite"); form_risk = form_risk.max(0.9); }
+            if f.method == "get" && !f.action.is_empty() { flags.push("password_in_query"); form_risk = form_risk.max(0.5); }
+        }
+    }
+    flags.dedup();
+    risk = risk.max(form_risk);
+
+    let autofill = p.saved_origin.as_deref().map(|o| should_allow_autofill(&p.url, o));
+    if autofill == Some(Verdict::Block) { flags.push("autofill_blocked"); risk = risk.max(0.7); }
+
+    let lang = detect_lang(if p.text.trim().is_empty() { &p.title } else { &p.text });
+    let hits = lure_hits(p, lang);
+    let text_risk = (0.15 * hits as f32).min(0.6);
+    if hits >= 2 { flags.push("lure_text"); }
+    risk = risk.max(text_risk);
+
+    // only graded where it matters: pages that take a password
+    let csp = p.csp.as_deref().filter(|_| p.forms.iter().any(|f| f.has_password)).map(|h| csp_report(h, false));
+    let csp_grade = csp.as_ref().map(|c| c.str_field("grade").to_string());
+    if matches!(csp_grade.as_deref(), Some("none" | "weak")) {
+        flags.push("credential_page_weak_csp");
+        risk = (risk + WEAK_CSP_RISK).min(1.0);
+    }
+
+    let findings = exfil::scan(p.forms.iter().map(|f| f.action.as_str()), p.scripts.iter().map(String::as_str));
+    if !findings.is_empty() { flags.push("messaging_webhook_exfil"); risk = 1.0; }
+
+    let worst_link = p.links.iter().take(MAX_LINKS).map(|l| url_risk(l)).fold(0.0f32, f32::max);
+    let total = combine(risk.min(1.0), worst_link);
+
+    Json::obj()
+        .with("url", p.url.as_str())
+        .with("url_risk", url_risk(&p.url))
+        .with("form_risk", form_risk)
+        .with("autofill", autofill.map(Verdict::as_str))
+        .with("lang", lang)
+        .with("text_risk", text_risk)
+        .with("worst_link", worst_link)
+        .with("csp", csp)
+        .with("findings", findings)
+        .with("flags", flags)
+        .with("score", total)
+        .with("verdict", Verdict::from_score(total).as_str())
+}
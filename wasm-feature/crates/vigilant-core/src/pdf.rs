@@ -0,0 +1,274 @@
+// wasm-feature/crates/vigilant-core/src/pdf.rs
+// Browser Vigilant — PDF action/link extractor for downloaded documents
+// Not a full PDF parser: it scans object bodies and inflated streams for
+// action dictionaries, which is enough for the one-big-button lure.
+
+use crate::inflate::inflate_zlib;
+use crate::json::Json;
+use crate::score::{combine, url_risk};
+use crate::verdict::Verdict;
+
+/// Per-stream and total decode caps (bytes).
+const STREAM_LIMIT: usize = 4 << 20;
+const TOTAL_LIMIT:  usize = 32 << 20;
+const MAX_URIS:     usize = 256;
+
+#[derive(Default)]
+struct PdfScan {
+    uris:        Vec<String>,
+    js:          usize,
+    launch:      usize,
+    open_action: usize,
+    auto_action: usize,
+    submit_form: usize,
+    embedded:    usize,
+    pages:       usize,
+    streams:     usize,
+    undecodable: usize,
+}
+
+/// Decode `#xx` escapes inside names so `/J#61vaScript` reads as `/JavaScript`.
+fn normalize_names(b: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(b.len());
+    let (mut i, mut in_name) = (0, false);
+    while i < b.len() {
+        let c = b[i];
+        if c == b'/' { in_name = true; } else if c.is_ascii_whitespace() || b"[]<>()/{}%".contains(&c) { in_name = false; }
+        if in_name && c == b'#' && i + 2 < b.len() && b[i+1].is_ascii_hexdigit() && b[i+2].is_ascii_hexdigit() {
+            let hex = std::str::from_utf8(&b[i+1..i+3]).unwrap_or("00");
+            out.push(u8::from_str_radix(hex, 16).unwrap_or(b'#'));
+            i += 3;
+            continue;
+        }
+        out.push(c);
+        i += 1;
+    }
+    out
+}
+
+fn find(hay: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    if from >= hay.len() { return None; }
+    hay[from..].windows(needle.len()).position(|w| w == needle).map(|p| p + from)
+}
+
+fn count_names(b: &[u8], name: &[u8]) -> usize {
+    let mut n = 0;
+    let mut i = 0;
+    while let Some(p) = find(b, name, i) {
+        // a name ends at a delimiter: /Page must not match /Pages
+        let next = b.get(p + name.len()).copied().unwrap_or(b' ');
+        if !next.is_ascii_alphanumeric() { n += 1; }
+        i = p + name.len();
+    }
+    n
+}
+
+/// Parse a PDF string token (literal `( … )` or hex `< … >`) at `i`.
+fn read_string(b: &[u8], mut i: usize) -> Option<String> {
+    while i < b.len() && b[i].is_ascii_whitespace() { i += 1; }
+    let mut out = Vec::new();
+    match b.get(i)? {
+        b'(' => {
+            let mut depth = 0usize;
+            i += 1;
+            while i < b.len() {
+                let c = b[i];
+                match c {
+                    b'\\' => {
+                        i += 1;
+                        match b.get(i) {
+                            Some(b'n') => out.push(b'\n'),
+                            Some(b'r') => out.push(b'\r'),
+                            Some(b't') => out.push(b'\t'),
+                            Some(d @ b'0'..=b'7') => {
+                                let mut v = (d - b'0') as u32;
+                                for _ in 0..2 {
+                                    match b.get(i + 1) {
+                                        Some(d @ b'0'..=b'7') => { v = v * 8 + (d - b'0') as u32; i += 1; }
+                                        _ => break,
+                                    }
+                                }
+                                out.push(v as u8);
+                            }
+                            Some(b'\r') | Some(b'\n') => {}
+                            Some(&o) => out.push(o),
+                            None => break,
+                        }
+                    }
+                    b'(' => { depth += 1; out.push(c); }
+                    b')' if depth == 0 => break,
+                    b')' => { depth -= 1; out.push(c); }
+                    _ => out.push(c),
+                }
+                i += 1;
+            }
+        }
+        b'<' => {
+            let end = find(b, b">", i)?;
+            let hex: Vec<u8> = b[i+1..end].iter().copied().filter(u8::is_ascii_hexdigit).collect();
+            for pair in hex.chunks(2) {
+                let s = std::str::from_utf8(pair).ok()?;
+                let v = u8::from_str_radix(s, 16).ok()?;
+                out.push(if pair.len() == 1 { v << 4 } else { v });
+            }
+        }
+        _ => return None,
+    }
+    // UTF-16BE strings carry a BOM
+    if out.starts_with(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = out[2..].chunks(2).filter(|c| c.len() == 2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+        return Some(String::from_utf16_lossy(&units));
+    }
+    Some(String::from_utf8_lossy(&out).into_owned())
+}
+
+fn scan_body(b: &[u8], s: &mut PdfScan) {
+    let b = normalize_names(b);
+    let mut i = 0;
+    while let Some(p) = find(&b, b"/URI", i) {
+        i = p + 4;
+        if b.get(i).is_some_and(u8::is_ascii_alphanumeric) { continue; } // /URIActions etc.
+        if let Some(u) = read_string(&b, i) {
+            let u = u.trim().to_string();
+            if !u.is_empty() && !s.uris.contains(&u) && s.uris.len() < MAX_URIS { s.uris.push(u); }
+        }
+    }
+    // an action is `/S /JavaScript /JS (…)`, names-tree entries carry only one of the two
+    s.js += count_names(&b, b"/JavaScript").max(count_names(&b, b"/JS"));
+    s.launch += count_names(&b, b"/Launch");
+    s.open_action += count_names(&b, b"/OpenAction");
+    s.auto_action += count_names(&b, b"/AA");
+    s.submit_form += count_names(&b, b"/SubmitForm");
+    s.embedded += count_names(&b, b"/EmbeddedFile");
+    s.pages += count_names(&b, b"/Type/Page") + count_names(&b, b"/Type /Page");
+}
+
+fn scan(bytes: &[u8]) -> PdfScan {
+    let mut s = PdfScan::default();
+    let mut budget = TOTAL_LIMIT;
+    // Everything outside stream bodies is object syntax; stream bodies are
+    // inflated when Flate-encoded (object streams hide annotations there).
+    let mut outside: Vec<u8> = Vec::new();
+    let (mut i, mut cursor) = (0, 0);
+    while let Some(p) = find(bytes, b"stream", cursor) {
+        cursor = p + 6;
+        if p >= 3 && &bytes[p-3..p] == b"end" { continue; }
+        let mut start = p + 6;
+        if bytes.get(start) == Some(&b'\r') { start += 1; }
+        if bytes.get(start) == Some(&b'\n') { start += 1; }
+        let end = find(bytes, b"endstream", start).unwrap_or(bytes.len());
+        outside.extend_from_slice(&bytes[i..p]);
+        s.streams += 1;
+        // the stream dictionary sits between the object header and `stream`
+        let dict_from = bytes[i..p].windows(3).rposition(|w| w == b"obj").map(|o| i + o).unwrap_or(p.saturating_sub(512).max(i));
+        let dict = normalize_names(&bytes[dict_from..p]);
+        if find(&dict, b"/FlateDecode", 0).is_some() && budget > 0 {
+            match inflate_zlib(&bytes[start..end], STREAM_LIMIT.min(budget)) {
+                Ok(dec) => { budget -= dec.len(); scan_body(&dec, &mut s); }
+                Err(_) => s.undecodable += 1,
+            }
+        } else if find(&dict, b"/Filter", 0).is_none() {
+            scan_body(&bytes[start..end], &mut s);
+        } else {
+            s.undecodable += 1;
+        }
+        i = (end + 9).min(bytes.len());
+        cursor = i;
+    }
+    if i < bytes.len() { outside.extend_from_slice(&bytes[i..]); }
+    scan_body(&outside, &mut s);
+    s
+}
+
+/// Scan raw PDF bytes. Returns `{ is_pdf, pages, uris: [{url, score}],
+/// js_actions, launch_actions, open_actions, auto_actions, submit_forms,
+/// embedded_files, streams, undecodable_streams, flags, score, verdict }`.
+pub fn pdf_report(bytes: &[u8]) -> Json {
+    let is_pdf = find(&bytes[..bytes.len().min(1024)], b"%PDF-", 0).is_some();
+    if !is_pdf {
+        return Json::obj().with("is_pdf", false).with("score", 0.0f32).with("verdict", Verdict::Allow.as_str());
+    }
+    let s = scan(bytes);
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+    if s.js > 0 { flags.push("javascript_action"); risk += 0.4; }
+    if s.launch > 0 { flags.push("launch_action"); risk += 0.5; }
+    if s.open_action > 0 && (s.js > 0 || s.launch > 0 || !s.uris.is_empty()) {
+        flags.push("auto_run_on_open");
+        risk += 0.2;
+    }
+    if s.submit_form > 0 { flags.push("submit_form"); risk += 0.2; }
+    if s.embedded > 0 { flags.push("embedded_file"); risk += 0.2; }
+    // The lure shape: one page whose only job is a link-button.
+    if s.pages <= 1 && !s.uris.is_empty() && s.uris.len() <= 3 { flags.push("single_page_link_lure"); risk += 0.15; }
+    if s.undecodable > 0 && s.streams > 0 && s.undecodable == s.streams { flags.push("all_streams_opaque"); risk += 0.1; }
+
+    let uris: Vec<Json> = s.uris.iter().map(|u| Json::obj().with("url", u.as_str()).with("score", url_risk(u))).collect();
+    let worst = s.uris.iter().map(|u| url_risk(u)).fold(0.0f32, f32::max);
+    let total = combine(risk.min(1.0), worst);
+
+    Json::obj()
+        .with("is_pdf", true)
+        .with("pages", s.pages)
+        .with("uris", uris)
+        .with("js_actions", s.js)
+        .with("launch_actions", s.launch)
+        .with("open_actions", s.open_action)
+        .with("auto_actions", s.auto_action)
+        .with("submit_forms", s.submit_form)
+        .with("embedded_files", s.embedded)
+        .with("streams", s.streams)
+        .with("undecodable_streams", s.undecodable)
+        .with("flags", flags)
+        .with("score", total)
+        .with("verdict", Verdict::from_score(total).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_link_and_escaped_javascript_action() {
+        let pdf = b"%PDF-1.7\n1 0 obj << /Type /Page /Annots [2 0 R] >> endobj\n\
+            2 0 obj << /A << /S /URI /URI (https://paypa1-login.tk/x) >> >> endobj\n\
+            3 0 obj << /OpenAction << /S /J#61vaScript /JS (app.alert\\(1\\)) >> >> endobj\n";
+        let r = pdf_report(pdf);
+        assert_eq!(r.get("is_pdf"), Some(&Json::Bool(true)));
+        assert_eq!(r.get("js_actions").and_then(Json::as_f64), Some(1.0));
+        let flags = r.str_list("flags");
+        assert!(flags.iter().any(|f| f == "javascript_action"), "{flags:?}");
+        assert!(flags.iter().any(|f| f == "auto_run_on_open"), "{flags:?}");
+    }
+
+    #[test]
+    fn reads_hex_and_utf16_strings() {
+        assert_eq!(read_string(b" <68747470733a2f2f612e746b>", 0).as_deref(), Some("https://a.tk"));
+        assert_eq!(read_string(b"(\xfe\xff\x00h\x00i)", 0).as_deref(), Some("hi"));
+        assert_eq!(read_string(b"<6", 0).as_deref(), None);
+    }
+
+    #[test]
+    fn unterminated_tokens_and_streams_do_not_panic() {
+        let cases: &[&[u8]] = &[
+            b"%PDF-1.4\n1 0 obj << /URI (unterminated",
+            b"%PDF-1.4\n1 0 obj << /URI <6874",
+            b"%PDF-1.4\n1 0 obj << /Filter /FlateDecode >> stream",
+            b"%PDF-1.4\n1 0 obj << /Filter /FlateDecode >> stream\r\n\x78\x9c\xff\xff",
+            b"%PDF-1.4\nendstream stream endstream",
+            b"%PDF-1.4\n/URI (\\",
+            b"%PDF-1.4\n/URI (\\1",
+        ];
+        for pdf in cases {
+            let r = pdf_report(pdf);
+            assert_eq!(r.get("is_pdf"), Some(&Json::Bool(true)));
+        }
+        for n in 0..cases[3].len() { let _ = pdf_report(&cases[3][..n]); }
+    }
+
+    #[test]
+    fn non_pdf_is_not_scanned() {
+        assert_eq!(pdf_report(b"PK\x03\x04").get("is_pdf"), Some(&Json::Bool(false)));
+        assert_eq!(pdf_report(b"").get("is_pdf"), Some(&Json::Bool(false)));
+    }
+}
@@ -0,0 +1,155 @@
+// wasm-feature/crates/vigilant-core/src/personal.rs
+// Browser Vigilant — per-user logistic layer over the global URL score.
+// Users in niche industries hit the same false positives every day (odd
+// TLDs, keyword-heavy intranet hosts) that the shipped weights will never
+// fix; a few allow/block decisions teach this layer to correct them. It
+// starts as the identity, learns slowly and can be reset at any time.
+
+use crate::json::Json;
+use crate::types::{err, Fields, SchemaError};
+
+/// Length of [`Personalizer::weights`]: bias, global score logit, https,
+/// login keyword, suspicious TLD, hyphenated host.
+pub const PERSONAL_FEATURES: usize = 6;
+
+/// Model-vector indices read as context features, after the bias and logit.
+const CONTEXT_FEATURES: [usize; PERSONAL_FEATURES - 2] = [11, 24, 38, 30];
+
+/// Weights the layer starts from and is pulled back toward: the global score unchanged.
+const PRIOR: [f32; PERSONAL_FEATURES] = [0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+
+/// Step size of the first updates; it decays with every decision learned.
+const MAX_LEARNING_RATE: f32 = 0.3;
+
+/// Cap on how far any weight may drift from the prior, so a run of
+/// mislabelled feedback can't switch the global score off.
+const MAX_DRIFT: f32 = 2.0;
+
+/// L2 pull toward [`PRIOR`] per update.
+const PRIOR_PULL: f32 = 0.01;
+
+pub(crate) fn sigmoid(x: f32) -> f32 { 1.0 / (1.0 + (-x).exp()) }
+
+pub(crate) fn logit(p: f32) -> f32 { let p = p.clamp(1e-4, 1.0 - 1e-4); (p / (1.0 - p)).ln() }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Personalizer {
+    weights: [f32; PERSONAL_FEATURES],
+    updates: u32,
+}
+
+impl Default for Personalizer {
+    fn default() -> Personalizer { Personalizer { weights: PRIOR, updates: 0 } }
+}
+
+impl Personalizer {
+    pub fn new() -> Personalizer { Personalizer::default() }
+
+    fn inputs(global: f32, features: &[f32]) -> [f32; PERSONAL_FEATURES] {
+        let mut x = [1.0, logit(global), 0.0, 0.0, 0.0, 0.0];
+        for (slot, &i) in x[2..].iter_mut().zip(&CONTEXT_FEATURES) {
+            let v = features.get(i).copied().unwrap_or(0.0);
+            // a NaN or infinite slot must not poison the weights
+            *slot = if v.is_nan() { 0.0 } else { v.clamp(0.0, 1.0) };
+        }
+        x
+    }
+
+    /// `global` (0–1) as this user's layer sees it, given the URL's model
+    /// features; untrained, that's `global` itself.
+    pub fn score(&self, global: f32, features: &[f32]) -> f32 {
+        if self.updates == 0 { return global; }
+        let x = Personalizer::inputs(global, features);
+        sigmoid(self.weights.iter().zip(x).map(|(w, x)| w * x).sum())
+    }
+
+    /// One gradient step toward `block` (the user blocked) or away from it
+    /// (the user allowed). Returns the score the same input now gets.
+    pub fn learn(&mut self, global: f32, features: &[f32], block: bool) -> f32 {
+        let x = Personalizer::inputs(global, features);
+        let p = sigmoid(self.weights.iter().zip(x).map(|(w, x)| w * x).sum());
+        let y = if block { 1.0 } else { 0.0 };
+        let rate = MAX_LEARNING_RATE / (1.0 + self.updates as f32 / 20.0).sqrt();
+        for ((w, x), prior) in self.weights.iter_mut().zip(x).zip(PRIOR) {
+            let step = rate * ((y - p) * x - PRIOR_PULL * (*w - prior));
+            *w = (*w + step).clamp(prior - MAX_DRIFT, prior + MAX_DRIFT);
+        }
+        self.updates = self.updates.saturating_add(1);
+        self.score(global, features)
+    }
+
+    /// Forget everything learned.
+    pub fn reset(&mut self) { *self = Personalizer::default(); }
+
+    pub fn weights(&self) -> &[f32; PERSONAL_FEATURES] { &self.weights }
+
+    /// Decisions learned since the last reset.
+    pub fn updates(&self) -> u32 { self.updates }
+
+    /// Returns `{ weights, updates }`.
+    pub fn export(&self) -> Json {
+        Json::obj().with("weights", self.weights.to_vec()).with("updates", self.updates)
+    }
+
+    /// Load a document from [`Personalizer::export`].
+    pub fn import(&mut self, v: &Json, path: &str) -> Result<(), SchemaError> {
+        let f = Fields::new(v, path)?;
+        let w = f.list("weights", |x, p| x.as_f64().map(|n| n as f32).filter(|n| n.is_finite()).ok_or_else(|| err(p, "expected number")))?;
+        let weights: [f32; PERSONAL_FEATURES] = w.try_into()
+            .map_err(|_| err(&f.at("weights"), &format!("expected {} weights", PERSONAL_FEATURES)))?;
+        let updates = f.opt_count("updates")?.unwrap_or(0).min(u32::MAX as usize) as u32;
+        for ((w, prior), i) in weights.iter().zip(PRIOR).zip(0..) {
+            if (w - prior).abs() > MAX_DRIFT + 1e-3 { return Err(err(&format!("{}[{}]", f.at("weights"), i), "weight out of range")); }
+        }
+        *self = Personalizer { weights, updates };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A model vector with the login keyword (F24) set.
+    fn login() -> Vec<f32> {
+        let mut f = vec![0.0; 40];
+        f[24] = 1.0;
+        f
+    }
+
+    #[test]
+    fn untrained_layer_is_the_identity() {
+        assert_eq!(Personalizer::new().score(0.37, &login()), 0.37);
+    }
+
+    #[test]
+    fn blocks_raise_and_allows_lower_the_score() {
+        let mut p = Personalizer::new();
+        let before = p.learn(0.4, &login(), true);
+        let after = p.learn(0.4, &login(), true);
+        assert!(before > 0.4 && after > before, "{} {}", before, after);
+
+        let mut p = Personalizer::new();
+        let before = p.learn(0.6, &login(), false);
+        let after = p.learn(0.6, &login(), false);
+        assert!(before < 0.6 && after < before, "{} {}", before, after);
+        // the login weight moved, https (F11) is unset and didn't
+        assert!(p.weights()[3] < 0.0 && p.weights()[2] == 0.0);
+    }
+
+    #[test]
+    fn extreme_inputs_keep_weights_finite_and_bounded() {
+        let mut p = Personalizer::new();
+        let odd = [vec![f32::MAX; 40], vec![f32::NEG_INFINITY; 40], vec![f32::NAN; 40], Vec::new()];
+        for i in 0..500 {
+            for (j, f) in odd.iter().enumerate() {
+                p.learn([0.0, 1.0, 0.5][j % 3], f, i % 2 == 0);
+            }
+        }
+        assert_eq!(p.updates(), 2000);
+        for (w, prior) in p.weights().iter().zip(PRIOR) {
+            assert!(w.is_finite() && (w - prior).abs() <= MAX_DRIFT, "{:?}", p.weights());
+        }
+        assert!(p.score(1.0, &odd[2]).is_finite());
+    }
+}
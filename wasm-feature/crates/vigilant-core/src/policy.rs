@@ -0,0 +1,167 @@
+// wasm-feature/crates/vigilant-core/src/policy.rs
+// Browser Vigilant — download context policy: which (file type, origin, gesture)
+// combinations are acceptable, as a first-match-wins table
+
+use crate::json::Json;
+use crate::verdict::Verdict;
+use crate::{has_ip, parse_url, SHORT_SERVICES, SUSPICIOUS_TLDS};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Category { Executable, Script, MobileApp, DiskImage, MacroDoc, Archive, Document, Other }
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Origin { AppStore, IpHost, Opaque, Risky, Insecure, Web }
+
+impl Category {
+    const ALL: [Category; 8] = [
+        Category::Executable, Category::Script, Category::MobileApp, Category::DiskImage,
+        Category::MacroDoc, Category::Archive, Category::Document, Category::Other,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Category::Executable => "executable",
+            Category::Script     => "script",
+            Category::MobileApp  => "mobile_app",
+            Category::DiskImage  => "disk_image",
+            Category::MacroDoc   => "macro_document",
+            Category::Archive    => "archive",
+            Category::Document   => "document",
+            Category::Other      => "other",
+        }
+    }
+    /// Types that run code once opened.
+    fn active(self) -> bool { !matches!(self, Category::Archive | Category::Document | Category::Other) }
+}
+
+/// Whether `name` is one of the categories `download_context_report` returns.
+pub(crate) fn is_category(name: &str) -> bool { Category::ALL.iter().any(|c| c.as_str() == name) }
+
+impl Origin {
+    fn as_str(self) -> &'static str {
+        match self {
+            Origin::AppStore => "app_store",
+            Origin::IpHost   => "ip_host",
+            Origin::Opaque   => "opaque",
+            Origin::Risky    => "risky_host",
+            Origin::Insecure => "insecure",
+            Origin::Web      => "web",
+        }
+    }
+}
+
+const EXT_CATEGORIES: &[(Category, &[&str])] = &[
+    (Category::Executable, &["exe","scr","msi","msp","dll","pif","com","cpl","sys","jar","appx","msix","pkg","deb","rpm","run","bin"]),
+    (Category::Script,     &["bat","cmd","ps1","psm1","vbs","vbe","js","jse","wsf","wsh","hta","reg","inf","lnk","sh","command","scf","url"]),
+    (Category::MobileApp,  &["apk","xapk","apks","aab","ipa"]),
+    (Category::DiskImage,  &["iso","img","vhd","vhdx","dmg","udf"]),
+    (Category::MacroDoc,   &["docm","xlsm","pptm","dotm","xltm","xlam","ppam","one","xll"]),
+    (Category::Archive,    &["zip","rar","7z","gz","tgz","tar","cab","arj","lzh","ace","bz2","xz"]),
+    (Category::Document,   &["pdf","doc","docx","xls","xlsx","ppt","pptx","odt","ods","txt","csv","rtf","png","jpg","jpeg","gif","webp","mp3","mp4","svg"]),
+];
+
+const MIME_CATEGORIES: &[(Category, &[&str])] = &[
+    (Category::Executable, &["application/x-msdownload","application/x-msdos-program","application/x-dosexec","application/vnd.microsoft.portable-executable","application/x-msi","application/x-ms-installer","application/java-archive"]),
+    (Category::Script,     &["application/hta","application/x-bat","application/x-sh","application/x-powershell","text/vbscript"]),
+    (Category::MobileApp,  &["application/vnd.android.package-archive"]),
+    (Category::DiskImage,  &["application/x-iso9660-image","application/x-apple-diskimage","application/x-raw-disk-image"]),
+    (Category::MacroDoc,   &["application/vnd.ms-word.document.macroenabled.12","application/vnd.ms-excel.sheet.macroenabled.12"]),
+    (Category::Archive,    &["application/zip","application/x-zip-compressed","application/x-rar-compressed","application/vnd.rar","application/x-7z-compressed","application/gzip"]),
+];
+
+/// MIME types a browser renders inline; an active file served as one of these
+/// was never meant to be seen by a content filter.
+const INLINE_MIMES: &[&str] = &["text/html","text/plain","text/css","application/pdf","image/","audio/","video/","application/json"];
+
+pub(crate) const APP_STORES: &[&str] = &[
+    "play.google.com","apps.apple.com","apps.microsoft.com","get.microsoft.com",
+    "f-droid.org","galaxy.store","appgallery.huawei.com",
+];
+
+struct Rule {
+    category: &'static [Category],
+    origin:   &'static [Origin],
+    gesture:  Option<bool>,
+    verdict:  Verdict,
+    reason:   &'static str,
+}
+
+const ANY_CAT: &[Category] = &[];
+const ANY_ORIGIN: &[Origin] = &[];
+const ACTIVE: &[Category] = &[Category::Executable, Category::Script, Category::MobileApp, Category::DiskImage, Category::MacroDoc];
+const HOSTILE: &[Origin] = &[Origin::IpHost, Origin::Opaque, Origin::Risky];
+
+/// First matching rule wins; empty slices are wildcards.
+const POLICY: &[Rule] = &[
+    Rule { category: &[Category::MobileApp], origin: &[Origin::AppStore], gesture: Some(true),  verdict: Verdict::Allow, reason: "store_install" },
+    Rule { category: ANY_CAT,                origin: &[Origin::AppStore], gesture: None,        verdict: Verdict::Warn,  reason: "store_without_gesture" },
+    Rule { category: ACTIVE,                 origin: HOSTILE,             gesture: None,        verdict: Verdict::Block, reason: "active_content_from_untrusted_origin" },
+    Rule { category: ACTIVE,                 origin: ANY_ORIGIN,          gesture: Some(false), verdict: Verdict::Block, reason: "drive_by_download" },
+    Rule { category: ACTIVE,                 origin: &[Origin::Insecure], gesture: None,        verdict: Verdict::Warn,  reason: "active_content_over_http" },
+    Rule { category: &[Category::Script],    origin: ANY_ORIGIN,          gesture: None,        verdict: Verdict::Warn,  reason: "script_download" },
+    Rule { category: &[Category::MobileApp], origin: ANY_ORIGIN,          gesture: None,        verdict: Verdict::Warn,  reason: "sideloaded_app" },
+    Rule { category: &[Category::DiskImage], origin: ANY_ORIGIN,          gesture: None,        verdict: Verdict::Warn,  reason: "disk_image" },
+    Rule { category: &[Category::MacroDoc],  origin: ANY_ORIGIN,          gesture: None,        verdict: Verdict::Warn,  reason: "macro_document" },
+    Rule { category: &[Category::Executable],origin: &[Origin::Web],      gesture: Some(true),  verdict: Verdict::Allow, reason: "user_initiated_installer" },
+    Rule { category: &[Category::Archive],   origin: HOSTILE,             gesture: None,        verdict: Verdict::Warn,  reason: "archive_from_untrusted_origin" },
+    Rule { category: &[Category::Archive],   origin: ANY_ORIGIN,          gesture: Some(false), verdict: Verdict::Warn,  reason: "archive_without_gesture" },
+    Rule { category: &[Category::Archive, Category::Document, Category::Other], origin: ANY_ORIGIN, gesture: None, verdict: Verdict::Allow, reason: "passive_content" },
+];
+
+fn lookup(table: &[(Category, &[&str])], key: &str) -> Option<Category> {
+    table.iter().find(|(_, keys)| keys.contains(&key)).map(|(c, _)| *c)
+}
+
+fn classify_origin(origin: &str) -> Origin {
+    let low = origin.trim().to_lowercase();
+    if low.is_empty() || low == "null" || low.starts_with("data:") || low.starts_with("blob:") || low.starts_with("file:") {
+        return Origin::Opaque;
+    }
+    let u = parse_url(&low);
+    let host = u.host.trim_end_matches('.');
+    if has_ip(host) { return Origin::IpHost; }
+    if u.scheme == "https" && APP_STORES.contains(&host) { return Origin::AppStore; }
+    if SUSPICIOUS_TLDS.contains(&u.tld.as_str()) || SHORT_SERVICES.contains(&host) { return Origin::Risky; }
+    if u.scheme == "http" { return Origin::Insecure; }
+    Origin::Web
+}
+
+fn escalate(v: Verdict) -> Verdict {
+    match v { Verdict::Allow => Verdict::Warn, _ => Verdict::Block }
+}
+
+/// Apply the download policy table. `mime` is the served Content-Type,
+/// `ext` the final extension, `initiating_origin` the page that started the
+/// download. Returns `{ category, origin_class, mime_mismatch, rule, verdict }`.
+pub fn download_context_report(mime: &str, ext: &str, initiating_origin: &str, user_gesture: bool) -> Json {
+    let mime = mime.split(';').next().unwrap_or("").trim().to_lowercase();
+    let ext = ext.trim().trim_start_matches('.').to_lowercase();
+    let by_ext = lookup(EXT_CATEGORIES, &ext);
+    let by_mime = lookup(MIME_CATEGORIES, &mime);
+    // an unknown extension takes the type the server admits to
+    let category = match (by_ext, by_mime) {
+        (Some(c), _) if c != Category::Document => c,
+        (_, Some(m)) if m.active() => m,
+        (Some(c), _) => c,
+        (None, Some(m)) => m,
+        (None, None) => Category::Other,
+    };
+    let origin = classify_origin(initiating_origin);
+    let inline = INLINE_MIMES.iter().any(|m| mime.starts_with(m));
+    let mime_mismatch = (category.active() && inline) || by_mime.is_some_and(|m| m.active() && by_ext == Some(Category::Document));
+
+    let rule = POLICY.iter().find(|r| {
+        (r.category.is_empty() || r.category.contains(&category))
+            && (r.origin.is_empty() || r.origin.contains(&origin))
+            && r.gesture.is_none_or(|g| g == user_gesture)
+    });
+    let (mut verdict, reason) = rule.map(|r| (r.verdict, r.reason)).unwrap_or((Verdict::Warn, "no_matching_rule"));
+    if mime_mismatch { verdict = escalate(verdict); }
+
+    Json::obj()
+        .with("category", category.as_str())
+        .with("origin_class", origin.as_str())
+        .with("mime_mismatch", mime_mismatch)
+        .with("rule", reason)
+        .with("verdict", verdict.as_str())
+}
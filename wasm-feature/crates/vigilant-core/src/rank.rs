@@ -0,0 +1,465 @@
+// wasm-feature/crates/vigilant-core/src/rank.rs
+// Browser Vigilant — severity ranking for report signals. Every flag, finding
+// tag and weighted URL feature gets one severity; signals that describe the
+// same evidence (a brand lookalike in the registrable domain and the same
+// brand in the subdomain) collapse into the strongest, so every UI shows the
+// same ordered list.
+
+use crate::compound::COMPOUNDS;
+use crate::json::Json;
+use crate::score::by_importance;
+use crate::MODEL_FEATURES;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info     = 0,
+    Low      = 1,
+    Medium   = 2,
+    High     = 3,
+    Critical = 4,
+}
+
+impl Severity {
+    pub fn parse(name: &str) -> Option<Severity> {
+        match name {
+            "info" => Some(Severity::Info), "low" => Some(Severity::Low), "medium" => Some(Severity::Medium),
+            "high" => Some(Severity::High), "critical" => Some(Severity::Critical), _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Info => "info", Severity::Low => "low", Severity::Medium => "medium",
+            Severity::High => "high", Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Severity of a signal missing from [`SEVERITIES`].
+const UNKNOWN_SEVERITY: Severity = Severity::Medium;
+
+/// Names for the features `score::WEIGHTS` weighs, as reported when a URL report's
+/// `features` are ranked.
+pub(crate) const URL_SIGNALS: &[(usize, &str)] = &[
+    (12, "ip_in_url"), (13, "is_punycode"), (15, "port_anomaly"), (21, "brand_spoof_flag"),
+    (23, "brand_in_subdomain_only"), (24, "has_login_kw"), (25, "has_trust_kw_in_domain"), (27, "has_free_kw"),
+    (28, "has_fraud_kw"), (31, "double_extension"), (33, "heavy_encoding"), (36, "is_data_uri"),
+    (37, "path_traversal"), (38, "suspicious_tld"), (45, "is_short_url"), (49, "suspicious_upi_vpa"),
+    (51, "ext_executable"), (53, "open_redirect"), (58, "ext_script"), (62, "server_script_odd_host"),
+];
+
+/// Severity per signal: flags, finding tags and [`URL_SIGNALS`].
+pub(crate) const SEVERITIES: &[(&str, Severity)] = &[
+    ("exfiltration.messaging_webhook", Severity::Critical),
+    ("evasion.domain_fronting", Severity::High),
+    // url features
+    ("ip_in_url", Severity::Medium),
+    ("is_punycode", Severity::Medium),
+    ("port_anomaly", Severity::Low),
+    ("brand_spoof_flag", Severity::High),
+    ("brand_in_subdomain_only", Severity::High),
+    ("has_login_kw", Severity::Low),
+    ("has_trust_kw_in_domain", Severity::Low),
+    ("has_free_kw", Severity::Low),
+    ("has_fraud_kw", Severity::Low),
+    ("double_extension", Severity::High),
+    ("heavy_encoding", Severity::Low),
+    ("is_data_uri", Severity::High),
+    ("path_traversal", Severity::Low),
+    ("suspicious_tld", Severity::Low),
+    ("is_short_url", Severity::Low),
+    ("suspicious_upi_vpa", Severity::High),
+    ("ext_executable", Severity::High),
+    ("open_redirect", Severity::Medium),
+    ("ext_script", Severity::Medium),
+    ("server_script_odd_host", Severity::Medium),
+    // url
+    ("custom_brand_impersonation", Severity::High),
+    ("brand_path_mimicry", Severity::High),
+    ("keyword_pack_match", Severity::Medium),
+    ("keyword_stuffing", Severity::Low),
+    ("kit_fingerprint_path", Severity::High),
+    ("deep_random_path", Severity::Medium),
+    ("login_form_on_compromised_path", Severity::High),
+    ("homoglyph_impersonation", Severity::High),
+    ("brand_alias_impersonation", Severity::High),
+    ("numeric_brand_lookalike", Severity::High),
+    ("nested_encoding", Severity::Medium),
+    ("encoded_traversal", Severity::Medium),
+    ("encoded_keyword", Severity::Low),
+    ("idn_brand_risky_tld", Severity::Critical),
+    ("alias_brand_risky_tld", Severity::Critical),
+    ("brand_login_risky_tld", Severity::Critical),
+    ("ip_host_login", Severity::High),
+    ("upi_collect_unknown_handle", Severity::Critical),
+    ("redirect_to_executable", Severity::High),
+    // context
+    ("allowlisted", Severity::Info),
+    ("blocklisted", Severity::Critical),
+    ("managed_allow", Severity::Info),
+    ("managed_block", Severity::Critical),
+    ("threat_feed_match", Severity::Critical),
+    ("bad_reputation", Severity::High),
+    ("kit_path_on_known_site", Severity::High),
+    ("unusual_site_template", Severity::Low),
+    ("personal_adjustment", Severity::Info),
+    // page
+    ("password_over_http", Severity::High),
+    ("credentials_posted_offsite", Severity::Critical),
+    ("password_in_query", Severity::Medium),
+    ("autofill_blocked", Severity::High),
+    ("lure_text", Severity::Low),
+    ("credential_page_weak_csp", Severity::Low),
+    ("messaging_webhook_exfil", Severity::Critical),
+    // resource graph
+    ("credential_sink", Severity::High),
+    ("messaging_webhook", Severity::Critical),
+    ("form_off_script_origin", Severity::Medium),
+    // certificate
+    ("self_signed", Severity::High),
+    ("name_mismatch", Severity::High),
+    ("fresh_certificate", Severity::Medium),
+    ("recent_certificate", Severity::Low),
+    ("bulk_san_list", Severity::Low),
+    ("over_long_validity", Severity::Low),
+    ("brand_in_certificate_host", Severity::High),
+    // mail
+    ("brand_from_freemail", Severity::High),
+    ("display_name_address_spoof", Severity::High),
+    ("display_name_brand_mismatch", Severity::Medium),
+    ("dmarc_record_invalid", Severity::Low),
+    ("reply_to_divergence", Severity::Medium),
+    ("reply_to_freemail", Severity::Medium),
+    ("sender_brand_on_foreign_host", Severity::High),
+    ("sender_domain_lookalike", Severity::High),
+    ("spf_permissive", Severity::Low),
+    ("text_href_mismatch", Severity::Medium),
+    ("dmarc_fail", Severity::High),
+    ("dmarc_none", Severity::Info),
+    ("dmarc_policy_none", Severity::Info),
+    ("spf_fail", Severity::Medium),
+    ("spf_softfail", Severity::Low),
+    ("dkim_fail", Severity::Medium),
+    ("dkim_unaligned", Severity::Low),
+    // calendar invites
+    ("inline_binary_attachment", Severity::High),
+    ("no_organizer", Severity::Low),
+    ("organizer_brand_mismatch", Severity::Medium),
+    ("organizer_cn_address_spoof", Severity::High),
+    ("organizer_domain_lookalike", Severity::High),
+    ("organizer_sent_by_mismatch", Severity::Medium),
+    // oauth
+    ("broad_scopes", Severity::Medium),
+    ("broad_scopes_with_offline_access", Severity::High),
+    ("device_code_flow", Severity::High),
+    ("device_code_prefilled", Severity::Critical),
+    ("forced_consent_offline", Severity::Medium),
+    ("implicit_grant", Severity::Low),
+    ("insecure_authorize_endpoint", Severity::High),
+    ("lookalike_idp", Severity::Critical),
+    ("unknown_idp", Severity::Low),
+    ("redirect_chained_url", Severity::Medium),
+    ("redirect_ip_host", Severity::High),
+    ("redirect_missing", Severity::Low),
+    ("redirect_oob", Severity::Medium),
+    ("redirect_same_site_as_idp", Severity::Medium),
+    ("redirect_shortener", Severity::Medium),
+    ("redirect_suspicious_tld", Severity::Medium),
+    ("redirect_tunnel_host", Severity::High),
+    // downloads and filenames
+    ("extension_content_mismatch", Severity::High),
+    ("mime_mismatch", Severity::Medium),
+    ("bidi_control", Severity::High),
+    ("control_characters", Severity::Medium),
+    ("extension_disguised", Severity::High),
+    ("fallback_name_mismatch", Severity::Medium),
+    ("path_in_filename", Severity::Medium),
+    ("rtlo_override", Severity::Critical),
+    ("trailing_dot_or_space", Severity::Medium),
+    // archives
+    ("contains_executable", Severity::Medium),
+    ("deeply_nested_archive", Severity::Medium),
+    ("disk_image_container", Severity::Low),
+    ("disk_image_with_executable", Severity::High),
+    ("encrypted_archive", Severity::Medium),
+    ("encrypted_executable", Severity::High),
+    ("encrypted_headers", Severity::Medium),
+    ("password_in_filename", Severity::High),
+    ("password_in_page_text", Severity::High),
+    ("password_unlocks_archive", Severity::Low),
+    // apk
+    ("accessibility_with_overlay", Severity::High),
+    ("banking_trojan_combo", Severity::Critical),
+    ("sms_with_screen_control", Severity::High),
+    ("otp_interception", Severity::Medium),
+    ("sms_access", Severity::Low),
+    ("installs_packages", Severity::Low),
+    ("device_admin", Severity::Medium),
+    ("contact_spread", Severity::Low),
+    ("package_claims_brand", Severity::Low),
+    // office
+    ("activex_controls", Severity::High),
+    ("embedded_objects", Severity::Medium),
+    ("external_ole_link", Severity::High),
+    ("macro_content_type", Severity::Medium),
+    ("protocol_handler_target", Severity::Critical),
+    ("remote_template", Severity::High),
+    ("vba_project", Severity::High),
+    // pdf
+    ("all_streams_opaque", Severity::Low),
+    ("auto_run_on_open", Severity::Medium),
+    ("embedded_file", Severity::Medium),
+    ("javascript_action", Severity::Medium),
+    ("launch_action", Severity::High),
+    ("single_page_link_lure", Severity::Medium),
+    ("submit_form", Severity::Medium),
+    // html and svg attachments
+    ("auto_submit_form", Severity::High),
+    ("credential_form", Severity::High),
+    ("credential_form_remote_action", Severity::Critical),
+    ("event_handler", Severity::Low),
+    ("html_smuggling", Severity::Critical),
+    ("javascript_url", Severity::Medium),
+    ("large_base64_blob", Severity::Medium),
+    ("meta_refresh", Severity::Medium),
+    ("obfuscated_script", Severity::High),
+    ("script", Severity::Low),
+    ("script_redirect", Severity::Medium),
+    ("svg_embedded_html", Severity::High),
+    ("svg_script", Severity::High),
+    // clipboard
+    ("clipboard_hijack", Severity::High),
+    ("lookalike_payment_address", Severity::High),
+    // display
+    ("confusable_domain", Severity::High),
+    ("mixed_script_label", Severity::Medium),
+    ("registrable_domain_hidden", Severity::Low),
+    ("visible_brand_lure", Severity::High),
+    // intent
+    ("intent_fallback_off_host", Severity::Medium),
+    ("intent_fallback_risky", Severity::High),
+    ("intent_fallback_script", Severity::High),
+    ("intent_package_mismatch", Severity::High),
+    // deep_link
+    ("enterprise_app_install", Severity::Medium),
+    ("manifest_off_origin", Severity::Low),
+    ("sideload_from_untrusted_page", Severity::High),
+    ("store_package_mismatch", Severity::High),
+    ("store_redirect_without_gesture", Severity::Medium),
+    // endpoint
+    ("endpoint_ip_host", Severity::Medium),
+    ("endpoint_private_network", Severity::High),
+    ("endpoint_raw_port", Severity::Low),
+    ("endpoint_suspicious_tld", Severity::Low),
+    ("endpoint_tunnel_host", Severity::Medium),
+    // host consistency
+    ("sni_host_mismatch", Severity::High),
+    ("sni_subdomain_mismatch", Severity::Low),
+    ("sni_ip_literal", Severity::Low),
+    ("url_host_mismatch", Severity::Medium),
+    // short url expansion
+    ("redirect_chain_long", Severity::Medium),
+    ("shortener_chain", Severity::Medium),
+    ("redirect_loop", Severity::Low),
+    // upi request
+    ("upi_reverse_payment", Severity::Critical),
+    ("upi_pull_request", Severity::Medium),
+    ("upi_unknown_handle", Severity::Medium),
+    ("upi_scam_prefix", Severity::High),
+    // data uri
+    ("data_uri_document", Severity::High),
+    ("data_uri_script", Severity::High),
+    ("data_uri_form", Severity::Medium),
+    ("data_uri_mime_mismatch", Severity::Medium),
+    ("data_uri_executable", Severity::Critical),
+    ("data_uri_malformed", Severity::Low),
+    // javascript: and blob: payloads
+    ("javascript_scheme", Severity::Medium),
+    ("js_obfuscated", Severity::High),
+    ("js_writes_page", Severity::Medium),
+    ("js_credential_prompt", Severity::High),
+    ("js_redirect", Severity::Medium),
+    ("blob_scheme", Severity::Low),
+    ("blob_opaque_origin", Severity::High),
+    // mailto and tel links
+    ("mailto_lookalike_domain", Severity::High),
+    ("mailto_display_spoof", Severity::High),
+    ("mailto_brand_freemail", Severity::High),
+    ("contact_text_mismatch", Severity::High),
+    ("tel_premium_rate", Severity::High),
+    ("tel_high_risk_prefix", Severity::High),
+    ("tel_ussd", Severity::Critical),
+    ("tel_international", Severity::Low),
+];
+
+/// Signals describing the same evidence, strongest first. When several of a
+/// group are present only the first stays; the rest are listed under its
+/// `subsumes`.
+pub(crate) const OVERLAPS: &[&[&str]] = &[
+    &["custom_brand_impersonation", "brand_path_mimicry", "brand_spoof_flag", "brand_in_subdomain_only"],
+    &["homoglyph_impersonation", "is_punycode"],
+    &["exfiltration.messaging_webhook", "messaging_webhook_exfil", "messaging_webhook"],
+    &["evasion.domain_fronting", "sni_host_mismatch"],
+    &["managed_block", "blocklisted", "threat_feed_match", "bad_reputation"],
+    &["managed_allow", "allowlisted"],
+    &["credential_form_remote_action", "credential_form"],
+    &["fresh_certificate", "recent_certificate"],
+    &["lookalike_idp", "unknown_idp"],
+    &["broad_scopes_with_offline_access", "broad_scopes"],
+    &["device_code_prefilled", "device_code_flow"],
+    &["dmarc_fail", "dmarc_none", "dmarc_policy_none"],
+    &["spf_fail", "spf_softfail"],
+    &["rtlo_override", "bidi_control", "control_characters"],
+    &["disk_image_with_executable", "disk_image_container"],
+    &["encrypted_executable", "encrypted_archive"],
+    &["banking_trojan_combo", "sms_with_screen_control", "accessibility_with_overlay", "sms_access"],
+    &["html_smuggling", "large_base64_blob"],
+    &["is_data_uri", "heavy_encoding"],
+    &["lookalike_payment_address", "clipboard_hijack"],
+    &["confusable_domain", "mixed_script_label"],
+    &["visible_brand_lure", "registrable_domain_hidden"],
+    &["sideload_from_untrusted_page", "enterprise_app_install"],
+];
+
+pub(crate) fn severity_of(id: &str) -> Severity {
+    SEVERITIES.iter().find(|(n, _)| *n == id).map_or(UNKNOWN_SEVERITY, |(_, s)| *s)
+}
+
+struct Signal {
+    id:       String,
+    kind:     &'static str,
+    severity: Severity,
+    extra:    Vec<(String, Json)>,
+    subsumes: Vec<String>,
+}
+
+/// Named URL features that fired, when `report` carries a model vector,
+/// in the order [`crate::feature_importances`] ranks them.
+fn feature_signals(report: &Json) -> Vec<&'static str> {
+    let Some(f) = report.get("features").and_then(Json::as_array).filter(|f| f.len() >= MODEL_FEATURES) else { return Vec::new() };
+    by_importance().into_iter()
+        .filter(|(i, _)| f[*i].as_f64().is_some_and(|v| v > 0.0))
+        .filter_map(|(i, _)| URL_SIGNALS.iter().find(|(n, _)| *n == i).map(|(_, s)| *s))
+        .collect()
+}
+
+/// Signals of `report` (or its `Report` envelope), severity-ranked and with
+/// overlapping ones merged. Findings keep their own `severity` and merge
+/// when tag, service and endpoint repeat. Returns `{ severity, signals: [{
+/// id, kind, severity, subsumes, service?, endpoint?, provider? }] }`, most
+/// severe first; `kind` is flag, finding, feature or custom (a registered
+/// provider's signal that added risk), and `severity` is the top one (or
+/// null when nothing fired). Features of equal severity come in
+/// `feature_importances` order.
+pub fn ranked_signals(report: &Json) -> Json {
+    let details = report.get("details");
+    let flags = match report.str_list("flags") { f if f.is_empty() => details.map_or(Vec::new(), |d| d.str_list("flags")), f => f };
+    let mut signals: Vec<Signal> = Vec::new();
+    let listed = report.get("findings").or_else(|| details.and_then(|d| d.get("findings")));
+    for f in listed.and_then(Json::as_array).unwrap_or(&[]) {
+        let Some(tag) = f.get("tag").and_then(Json::as_str) else { continue };
+        let extra: Vec<(String, Json)> = ["service", "endpoint"].iter()
+            .filter_map(|k| f.get(k).map(|v| (k.to_string(), v.clone())))
+            .collect();
+        if signals.iter().any(|s| s.id == tag && s.extra == extra) { continue; }
+        let severity = f.get("severity").and_then(Json::as_str).and_then(Severity::parse).unwrap_or_else(|| severity_of(tag));
+        signals.push(Signal { id: tag.to_string(), kind: "finding", severity, extra, subsumes: Vec::new() });
+    }
+    let features = feature_signals(details.unwrap_or(report));
+    let plain = flags.iter().map(|f| (f.as_str(), "flag")).chain(features.iter().map(|f| (*f, "feature")));
+    for (id, kind) in plain {
+        if signals.iter().any(|s| s.id == id) { continue; }
+        signals.push(Signal { id: id.to_string(), kind, severity: severity_of(id), extra: Vec::new(), subsumes: Vec::new() });
+    }
+    let custom = report.get("signals").or_else(|| details.and_then(|d| d.get("signals")));
+    for c in custom.and_then(Json::as_array).unwrap_or(&[]) {
+        if !c.get("contribution").and_then(Json::as_f64).is_some_and(|v| v > 0.0) { continue; }
+        let (id, provider) = (c.str_field("name"), c.str_field("provider"));
+        if id.is_empty() || signals.iter().any(|s| s.id == id) { continue; }
+        let severity = Severity::parse(c.str_field("severity")).unwrap_or(UNKNOWN_SEVERITY);
+        let extra = vec![("provider".to_string(), Json::from(provider))];
+        signals.push(Signal { id: id.to_string(), kind: "custom", severity, extra, subsumes: Vec::new() });
+    }
+
+    // a matched compound rule outranks its parts, so it merges them first
+    let rules: Vec<Vec<&str>> = COMPOUNDS.iter()
+        .filter(|(id, _)| signals.iter().any(|s| s.id == *id))
+        .map(|(id, parts)| std::iter::once(*id).chain(parts.iter().copied()).collect())
+        .collect();
+    for group in rules.iter().map(Vec::as_slice).chain(OVERLAPS.iter().copied()) {
+        let Some(keep) = group.iter().find(|g| signals.iter().any(|s| s.id == **g)) else { continue };
+        let merged: Vec<String> = group.iter().filter(|g| *g != keep && signals.iter().any(|s| s.id == **g)).map(|g| g.to_string()).collect();
+        signals.retain(|s| !merged.contains(&s.id));
+        if let Some(s) = signals.iter_mut().find(|s| s.id == *keep) { s.subsumes.extend(merged); }
+    }
+    // stable: equal severities keep report order
+    signals.sort_by_key(|s| std::cmp::Reverse(s.severity));
+
+    let top = signals.first().map(|s| s.severity);
+    let list: Vec<Json> = signals.into_iter().map(|s| {
+        let mut o = Json::obj().with("id", s.id).with("kind", s.kind).with("severity", s.severity.as_str()).with("subsumes", s.subsumes);
+        for (k, v) in s.extra { o = o.with(&k, v); }
+        o
+    }).collect();
+    Json::obj()
+        .with("severity", top.map(|s| s.as_str()))
+        .with("signals", list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranked(report: &str) -> Vec<(String, String, Vec<String>)> {
+        let r = ranked_signals(&Json::parse(report).unwrap());
+        r.get("signals").and_then(Json::as_array).unwrap().iter()
+            .map(|s| (s.str_field("id").to_string(), s.str_field("severity").to_string(), s.str_list("subsumes")))
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_signals_merge_into_the_strongest() {
+        let r = ranked(r#"{"flags":["port_anomaly","is_punycode","bad_reputation","homoglyph_impersonation","threat_feed_match"]}"#);
+        let ids: Vec<&str> = r.iter().map(|(id, _, _)| id.as_str()).collect();
+        assert_eq!(ids.last(), Some(&"port_anomaly"));
+        assert!(!ids.contains(&"is_punycode") && !ids.contains(&"bad_reputation"));
+        let subsumed = |id: &str| r.iter().find(|(i, _, _)| i == id).map(|(_, _, s)| s.clone());
+        assert_eq!(subsumed("homoglyph_impersonation"), Some(vec!["is_punycode".to_string()]));
+        assert_eq!(subsumed("threat_feed_match"), Some(vec!["bad_reputation".to_string()]));
+    }
+
+    #[test]
+    fn findings_keep_their_severity_and_merge_on_repeats() {
+        let r = ranked(r#"{"findings":[
+            {"tag":"exfiltration.telegram","severity":"low","service":"telegram"},
+            {"tag":"exfiltration.telegram","severity":"low","service":"telegram"},
+            {"tag":"exfiltration.telegram","severity":"low","service":"discord"}],
+            "flags":["tel_ussd"]}"#);
+        assert_eq!(r.iter().map(|(id, s, _)| (id.as_str(), s.as_str())).collect::<Vec<_>>(),
+            [("tel_ussd", "critical"), ("exfiltration.telegram", "low"), ("exfiltration.telegram", "low")]);
+    }
+
+    #[test]
+    fn custom_signals_count_only_when_they_added_risk() {
+        let r = ranked(r#"{"signals":[{"name":"corp_list","provider":"corp","severity":"high","contribution":0.3},
+            {"name":"quiet","provider":"corp","severity":"critical","contribution":0}]}"#);
+        assert_eq!(r, [("corp_list".to_string(), "high".to_string(), Vec::new())]);
+    }
+
+    #[test]
+    fn nothing_fired_has_no_severity() {
+        let r = ranked_signals(&Json::parse(r#"{"flags":[]}"#).unwrap());
+        assert_eq!(r.get("severity"), Some(&Json::Null));
+        assert_eq!(severity_of("never_heard_of_it"), UNKNOWN_SEVERITY);
+    }
+
+    #[test]
+    fn every_url_signal_has_one_severity() {
+        for (_, name) in URL_SIGNALS {
+            assert_eq!(SEVERITIES.iter().filter(|(n, _)| n == name).count(), 1, "{}", name);
+        }
+        for (i, (a, _)) in SEVERITIES.iter().enumerate() {
+            assert!(!SEVERITIES[i + 1..].iter().any(|(b, _)| a == b), "{} listed twice", a);
+        }
+    }
+}
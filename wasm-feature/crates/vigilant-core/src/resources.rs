@@ -0,0 +1,111 @@
+// wasm-feature/crates/vigilant-core/src/resources.rs
+// Browser Vigilant — subresource origin graph. What a page loads and where it
+// sends data says more about a kit than its markup: kits pull everything from
+// one or two origins, then post credentials to a form backend or chat bot
+// that none of their scripts came from.
+
+use crate::exfil::{finding, webhook_service};
+use crate::json::Json;
+use crate::parse_url;
+use crate::psl::registrable_domain;
+use crate::verdict::Verdict;
+
+/// Length of `resource_graph_report().features`: distinct_sites,
+/// third_party_ratio, credential_sink, messaging_webhook, form_off_script_origin.
+pub const RESOURCE_FEATURES: usize = 5;
+
+/// Resources looked at per page; the rest of an ad-heavy page adds nothing new.
+const MAX_RESOURCES: usize = 2000;
+
+/// Hosted form backends and request catchers kits post harvested fields to,
+/// as `host` or `host/path-prefix` (lowercase).
+pub(crate) const CREDENTIAL_SINKS: &[&str] = &[
+    "formspree.io", "getform.io", "formsubmit.co", "submit-form.com", "formcarry.com", "usebasin.com",
+    "formspark.io", "api.web3forms.com", "webhook.site", "requestcatcher.com", "pipedream.net", "hookbin.com",
+    "requestbin.com", "beeceptor.com", "script.google.com/macros/", "api.jsonbin.io", "ptsv3.com",
+];
+
+/// `url`'s host (and parents) and path match one of `patterns`.
+pub(crate) fn matches_endpoint(patterns: &[&str], url: &str) -> bool {
+    let p = parse_url(url);
+    let host = p.host.to_lowercase();
+    let path = p.path.to_lowercase();
+    patterns.iter().any(|pat| {
+        let (h, prefix) = match pat.find('/') { Some(i) => (&pat[..i], &pat[i..]), None => (*pat, "") };
+        (host == h || host.ends_with(&format!(".{}", h))) && path.starts_with(prefix)
+    })
+}
+
+struct Resource {
+    url:  String,
+    kind: String,
+    host: String,
+    site: String,
+}
+
+fn resources_of(v: &Json) -> Vec<Resource> {
+    let list = v.as_array().or_else(|| v.get("resources").and_then(Json::as_array)).unwrap_or(&[]);
+    list.iter().take(MAX_RESOURCES).filter_map(|r| {
+        let (url, kind) = match r {
+            Json::Str(u) => (u.as_str(), "other"),
+            _ => (r.str_field("url"), r.get("type").and_then(Json::as_str).unwrap_or("other")),
+        };
+        let host = parse_url(url).host.to_lowercase();
+        if host.is_empty() { return None; }
+        Some(Resource { url: url.to_string(), kind: kind.to_lowercase(), site: registrable_domain(&host), host })
+    }).collect()
+}
+
+fn sorted_unique(mut v: Vec<String>) -> Vec<String> { v.sort(); v.dedup(); v }
+
+/// Score the subresources a page loaded. `resources_json` is an array of
+/// URLs or `{ url, type }` (`type`: script, form, xhr, fetch, beacon,
+/// websocket, image, stylesheet, font, frame, …), e.g. from
+/// `performance.getEntriesByType("resource")` plus the page's form actions.
+/// Returns `{ page_site, resources, distinct_sites, third_party_sites,
+/// credential_sinks, messaging_webhooks, script_sites, form_sites, features,
+/// findings, flags, score, verdict }`; each webhook request is a critical finding.
+pub fn resource_graph_report(resources_json: &str, page_url: &str) -> Json {
+    let v = match Json::parse(resources_json) {
+        Ok(v) => v,
+        Err(e) => return Json::obj().with("error", e),
+    };
+    let page_site = registrable_domain(&parse_url(page_url).host.to_lowercase());
+    let res = resources_of(&v);
+    let sites = sorted_unique(res.iter().map(|r| r.site.clone()).collect());
+    let third_party = sites.iter().filter(|s| **s != page_site).count();
+    let sinks = sorted_unique(res.iter().filter(|r| matches_endpoint(CREDENTIAL_SINKS, &r.url)).map(|r| r.host.clone()).collect());
+    let findings: Vec<Json> = res.iter().filter_map(|r| webhook_service(&r.url).map(|s| finding("resource", s, &r.url))).collect();
+    let webhooks = sorted_unique(res.iter().filter(|r| webhook_service(&r.url).is_some()).map(|r| r.host.clone()).collect());
+    let script_sites = sorted_unique(res.iter().filter(|r| r.kind == "script").map(|r| r.site.clone()).collect());
+    let form_sites = sorted_unique(res.iter().filter(|r| r.kind == "form").map(|r| r.site.clone()).collect());
+    // a form posts to a site that served none of the page's code (analytics
+    // beacons do this all the time, forms on real sites don't)
+    let off_script = !script_sites.is_empty() && form_sites.iter().any(|s| *s != page_site && !script_sites.contains(s));
+
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut score = 0.0f32;
+    if !sinks.is_empty() { flags.push("credential_sink"); score += 0.5; }
+    if !webhooks.is_empty() { flags.push("messaging_webhook"); score += 1.0; }
+    if off_script { flags.push("form_off_script_origin"); score += 0.25; }
+    let score = score.min(1.0);
+
+    let b = |x: bool| if x { 1.0 } else { 0.0 };
+    let ratio = if sites.is_empty() { 0.0 } else { third_party as f32 / sites.len() as f32 };
+    let features = vec![sites.len() as f32, ratio, b(!sinks.is_empty()), b(!webhooks.is_empty()), b(off_script)];
+
+    Json::obj()
+        .with("page_site", page_site)
+        .with("resources", res.len())
+        .with("distinct_sites", sites.len())
+        .with("third_party_sites", third_party)
+        .with("credential_sinks", sinks)
+        .with("messaging_webhooks", webhooks)
+        .with("script_sites", script_sites)
+        .with("form_sites", form_sites)
+        .with("features", features)
+        .with("findings", findings)
+        .with("flags", flags)
+        .with("score", score)
+        .with("verdict", Verdict::from_score(score).as_str())
+}
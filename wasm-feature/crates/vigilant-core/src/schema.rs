@@ -0,0 +1,52 @@
+// wasm-feature/crates/vigilant-core/src/schema.rs
+// Browser Vigilant — the model vector's schema, for a handshake with the
+// training side. `FEATURE_VERSION` only moves when someone remembers to bump
+// it; a renamed or reordered feature in `features.py` otherwise trains a
+// model that loads fine and scores garbage. The schema hash covers the
+// version, every name and every type in order, `train.py` computes the same
+// hash from its own lists and writes it into the container, and loading
+// refuses a model whose hash differs.
+
+use crate::hash::sha256_hex;
+use crate::json::Json;
+use crate::model::FEATURE_VERSION;
+use crate::named::{feature_name, is_flag};
+use crate::packed::value_type;
+use crate::MODEL_FEATURES;
+
+/// Layout of the [`feature_schema`] report itself; bumped when a field or
+/// a type name changes meaning.
+pub const FEATURE_SCHEMA_VERSION: u32 = 1;
+
+/// Type of model feature `i`: "flag" (0 or 1), "ratio" (0–1), "count",
+/// "length" or "bits" of entropy.
+fn feature_type(i: usize) -> &'static str {
+    if is_flag(i) { "flag" } else { value_type(i).unwrap_or("ratio") }
+}
+
+/// Hash of the feature version and each feature's name and type, in order:
+/// the first 16 hex digits of the SHA-256 of `<version>` and a
+/// `<name>:<type>` line per feature, joined with newlines.
+pub fn feature_schema_hash() -> String {
+    let lines: Vec<String> = std::iter::once(FEATURE_VERSION.to_string())
+        .chain((0..MODEL_FEATURES).map(|i| format!("{}:{}", feature_name(i).unwrap_or(""), feature_type(i))))
+        .collect();
+    sha256_hex(lines.join("\n").as_bytes())[..16].to_string()
+}
+
+/// The [`crate::MODEL_FEATURES`] vector as callers should expect it.
+/// Returns `{ schema_version, feature_version, hash, features: [{ index,
+/// name, type }] }`, features in vector order.
+pub fn feature_schema() -> Json {
+    let features = (0..MODEL_FEATURES).map(|i| {
+        Json::obj()
+            .with("index", i)
+            .with("name", feature_name(i).unwrap_or(""))
+            .with("type", feature_type(i))
+    });
+    Json::obj()
+        .with("schema_version", FEATURE_SCHEMA_VERSION)
+        .with("feature_version", FEATURE_VERSION)
+        .with("hash", feature_schema_hash())
+        .with("features", Json::Arr(features.collect()))
+}
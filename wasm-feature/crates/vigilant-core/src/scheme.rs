@@ -0,0 +1,33 @@
+// wasm-feature/crates/vigilant-core/src/scheme.rs
+// Browser Vigilant — which kind of link a URL is. An https-or-not bit makes
+// `http:`, `data:` and an Android `intent:` link all look alike; lures use
+// the odd ones precisely because they skip the usual page checks, so the
+// model vector carries the whole one-hot (F11, then F63–F71). The scheme is
+// read from the raw text: the URL parser only knows `scheme://` forms.
+
+/// Values returned by [`scheme_features`]: one per [`SCHEME_CLASSES`]
+/// entry, then `other`.
+pub const SCHEME_FEATURES: usize = 10;
+
+/// Schemes with a feature of their own, in one-hot order.
+pub(crate) const SCHEME_CLASSES: &[&str] = &["https", "http", "ftp", "data", "javascript", "blob", "mailto", "intent", "upi"];
+
+/// Lowercased scheme of `url` (`blob` for `blob:https://…`), or "" when the
+/// text doesn't start with one.
+pub(crate) fn scheme_of(url: &str) -> String {
+    let url = url.trim_start();
+    let Some((scheme, _)) = url.split_once(':') else { return String::new() };
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if valid { scheme.to_ascii_lowercase() } else { String::new() }
+}
+
+/// `[https, http, ftp, data, javascript, blob, mailto, intent, upi, other]`,
+/// one-hot on the URL's scheme; text without a listed scheme (including a
+/// bare `example.com`) is `other`.
+pub fn scheme_features(url: &str) -> [f32; SCHEME_FEATURES] {
+    let scheme = scheme_of(url);
+    let mut f = [0.0f32; SCHEME_FEATURES];
+    f[SCHEME_CLASSES.iter().position(|s| *s == scheme).unwrap_or(SCHEME_CLASSES.len())] = 1.0;
+    f
+}
@@ -0,0 +1,211 @@
+// wasm-feature/crates/vigilant-core/src/schemepayload.rs
+// Browser Vigilant — links that are code. A `javascript:` URL runs in the
+// page it is opened from: kits hand them out as "bookmarklets", paste
+// them into chat as "fix this error", and wrap the script in `atob` and
+// escapes so the link reads as noise. A `blob:` URL points at content a
+// page assembled in memory, which is how HTML smuggling hands over its
+// file; one whose origin is `null` came out of a sandbox or a `data:`
+// page. The scheme features (`scheme_features`) only say which scheme it
+// is; this reads what the link would do.
+//
+// The scheme-risk values are F72–F76 of the model vector as well as a
+// group of their own. `urlparse` reads no script out of a `javascript:`
+// URL, so `features.py` carries a port of the decoding below; a change to
+// it, or to the lists, changes both and bumps `FEATURE_VERSION`.
+
+use crate::datauri::base64_decode;
+use crate::encoding::decode_fully;
+use crate::json::Json;
+use crate::malformed::browser_form;
+use crate::scheme::scheme_of;
+use crate::score::{combine, url_risk};
+use crate::verdict::Verdict;
+use crate::find_urls;
+
+/// Values returned by [`scheme_risk_features`]: obfuscation_calls,
+/// writes_page, credential_prompt, navigates, blob_opaque_origin.
+pub const SCHEME_RISK_FEATURES: usize = 5;
+
+/// Calls that decode or run hidden code, as they appear lowercased with
+/// spaces removed.
+pub(crate) const OBFUSCATION_CALLS: &[&str] = &["eval(", "atob(", "unescape(", "fromcharcode(", "decodeuricomponent(", "settimeout(", "setinterval(", "newfunction("];
+
+/// Writes that replace the page with the script's own.
+pub(crate) const PAGE_WRITES: &[&str] = &["document.write(", "document.writeln(", "innerhtml=", "outerhtml=", "document.open("];
+
+/// Navigations and submissions that send the user, or what they typed,
+/// somewhere else.
+pub(crate) const NAVIGATIONS: &[&str] = &["location=", "location.href=", "location.replace(", "location.assign(", "window.open(", ".submit()", "fetch(", "sendbeacon("];
+
+/// Words of a credential prompt, lowercased.
+pub(crate) const CREDENTIAL_WORDS: &[&str] = &[
+    "password", "passwd", "passcode", "type=\"password\"", "type='password'", "enter otp", "otp code", "one-time password", "cvv", "card number", "cardnumber", "username",
+    "sign in", "signin", "login", "verify your", "account number", "upi pin", "atm pin", "aadhaar", "seed phrase", "recovery phrase",
+];
+
+/// Rounds of `atob('…')` literals decoded inside decoded code.
+const MAX_DECODE_ROUNDS: usize = 3;
+
+/// Base64 string literals passed to `atob` in `code`, decoded.
+fn atob_literals(code: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = code;
+    while let Some(at) = rest.to_ascii_lowercase().find("atob(") {
+        rest = rest[at + 5..].trim_start();
+        let Some(quote) = rest.chars().next().filter(|c| matches!(c, '\'' | '"' | '`')) else { continue };
+        let Some(end) = rest[1..].find(quote) else { break };
+        if let Some(bytes) = base64_decode(&rest[1..=end]) { out.push(String::from_utf8_lossy(&bytes).into_owned()); }
+        rest = &rest[end + 1..];
+    }
+    out
+}
+
+/// What a `javascript:` or `blob:` URL carries.
+struct Payload {
+    scheme: String,
+    /// Percent-decoded script of a `javascript:` URL.
+    code: Option<String>,
+    /// Strings its `atob` literals decode to, outermost first.
+    decoded: Vec<String>,
+    /// Origin of a `blob:` URL, lowercased.
+    origin: Option<String>,
+}
+
+impl Payload {
+    fn of(url: &str) -> Payload {
+        // browsers drop tabs and newlines anywhere, `java\tscript:` included
+        let url = browser_form(url);
+        let scheme = scheme_of(&url);
+        let body = url.get(scheme.len() + 1..).unwrap_or("");
+        let mut p = Payload { scheme, code: None, decoded: Vec::new(), origin: None };
+        match p.scheme.as_str() {
+            "javascript" => {
+                let code = decode_fully(body).0;
+                let mut round = atob_literals(&code);
+                for _ in 0..MAX_DECODE_ROUNDS {
+                    if round.is_empty() { break; }
+                    let next: Vec<String> = round.iter().flat_map(|s| atob_literals(s)).collect();
+                    p.decoded.append(&mut round);
+                    round = next;
+                }
+                p.code = Some(code);
+            }
+            "blob" => {
+                // `blob:https://site.example/uuid`; the origin is the part before the id
+                let origin = body.rsplit_once('/').map_or(body, |(o, _)| o);
+                p.origin = Some(origin.to_lowercase());
+            }
+            _ => {}
+        }
+        p
+    }
+
+    /// Script and everything decoded from it; "" for anything but
+    /// `javascript:`.
+    fn joined(&self) -> String {
+        std::iter::once(self.code.as_deref().unwrap_or("")).chain(self.decoded.iter().map(String::as_str)).collect::<Vec<_>>().join("\n")
+    }
+
+    fn opaque_origin(&self) -> bool { self.origin.as_deref().is_some_and(|o| o == "null" || o.is_empty() || o.starts_with("data:")) }
+}
+
+fn found(hay: &str, list: &[&'static str]) -> Vec<&'static str> { list.iter().copied().filter(|n| hay.contains(n)).collect() }
+
+/// `[obfuscation_calls, writes_page, credential_prompt, navigates,
+/// blob_opaque_origin]`: distinct decoding or eval calls in a
+/// `javascript:` URL's script (with what its `atob` literals decode to),
+/// whether it writes a page of its own, asks for a credential, or sends
+/// the user elsewhere; and whether a `blob:` URL's origin is `null`. All 0
+/// for other schemes.
+pub fn scheme_risk_features(url: &str) -> [f32; SCHEME_RISK_FEATURES] {
+    let p = Payload::of(url);
+    let text = p.joined().to_lowercase();
+    let squashed = text.replace([' ', '\t'], "");
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    [
+        found(&squashed, OBFUSCATION_CALLS).len() as f32,
+        b(!found(&squashed, PAGE_WRITES).is_empty()),
+        b(!found(&text, CREDENTIAL_WORDS).is_empty()),
+        b(!found(&squashed, NAVIGATIONS).is_empty()),
+        b(p.opaque_origin()),
+    ]
+}
+
+/// Analyze what a `javascript:` or `blob:` URL would do. A script is
+/// percent-decoded, its `atob` literals decoded, and the lot searched for
+/// obfuscation, page writes, credential prompts, navigations and links;
+/// a blob's origin is scored as a URL. Returns `{ scheme, payload, code,
+/// decoded, obfuscation, page_writes, credential_words, navigations,
+/// links, origin, features, flags, score, verdict }`; `payload` is false,
+/// and only it, `scheme`, `flags`, `score` and `verdict` are set, for
+/// other schemes.
+pub fn scheme_payload_report(url: &str) -> Json {
+    let p = Payload::of(url);
+    if p.code.is_none() && p.origin.is_none() {
+        return Json::obj().with("scheme", p.scheme.as_str()).with("payload", false).with("flags", Vec::<&str>::new())
+            .with("score", 0.0f32).with("verdict", Verdict::from_score(0.0).as_str());
+    }
+    let joined = p.joined();
+    let text = joined.to_lowercase();
+    let squashed = text.replace([' ', '\t'], "");
+    let obfuscation = found(&squashed, OBFUSCATION_CALLS);
+    let writes = found(&squashed, PAGE_WRITES);
+    let credentials = found(&text, CREDENTIAL_WORDS);
+    let navigations = found(&squashed, NAVIGATIONS);
+    let mut links: Vec<String> = find_urls(&joined);
+    links.sort();
+    links.dedup();
+
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+    if p.code.is_some() {
+        flags.push("javascript_scheme");
+        risk += 0.2;
+        if !obfuscation.is_empty() { flags.push("js_obfuscated"); risk += 0.3; }
+        if !writes.is_empty() { flags.push("js_writes_page"); risk += 0.3; }
+        if !credentials.is_empty() { flags.push("js_credential_prompt"); risk += 0.4; }
+        if !navigations.is_empty() { flags.push("js_redirect"); risk += 0.2; }
+    }
+    let mut worst = links.iter().map(|u| url_risk(u)).fold(0.0f32, f32::max);
+    if let Some(origin) = p.origin.as_deref() {
+        if p.opaque_origin() { flags.push("blob_opaque_origin"); risk += 0.4; }
+        else { flags.push("blob_scheme"); risk += 0.1; worst = worst.max(url_risk(origin)); }
+    }
+    let score = combine(risk.min(1.0), worst);
+
+    Json::obj()
+        .with("scheme", p.scheme.as_str())
+        .with("payload", true)
+        .with("code", p.code.as_deref())
+        .with("decoded", p.decoded.clone())
+        .with("obfuscation", obfuscation)
+        .with("page_writes", writes)
+        .with("credential_words", credentials)
+        .with("navigations", navigations)
+        .with("links", links)
+        .with("origin", p.origin.as_deref())
+        .with("features", scheme_risk_features(url).to_vec())
+        .with("flags", flags)
+        .with("score", score)
+        .with("verdict", Verdict::from_score(score).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract_features;
+
+    #[test]
+    fn model_vector_carries_the_scheme_risk_group() {
+        for url in [
+            "javascript:eval(atob('ZG9jdW1lbnQud3JpdGUoIjxpbnB1dCB0eXBlPXBhc3N3b3JkPiIp'))",
+            "java\tscript:window.open(\"https://x.tk/login\")",
+            "blob:null/1234-5678",
+            "https://example.com/login",
+        ] {
+            assert_eq!(extract_features(url)[72..77], scheme_risk_features(url), "{url}");
+        }
+        assert_eq!(scheme_risk_features("javascript:eval(atob('ZG9jdW1lbnQud3JpdGUoIjxpbnB1dCB0eXBlPXBhc3N3b3JkPiIp'))"), [2.0, 1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(scheme_risk_features("https://example.com/login"), [0.0; SCHEME_RISK_FEATURES]);
+    }
+}
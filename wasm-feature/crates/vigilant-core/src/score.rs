@@ -0,0 +1,116 @@
+// wasm-feature/crates/vigilant-core/src/score.rs
+// Browser Vigilant — rule-weighted URL risk used by the composite analyzers
+// (mail, ICS, PDF, …) until an in-crate model is available.
+
+use std::sync::{PoisonError, RwLock};
+use crate::json::Json;
+use crate::named::feature_name;
+use crate::rank::URL_SIGNALS;
+use crate::trees::TreeModel;
+use crate::{extract_features, MODEL_FEATURES};
+
+/// (feature index, weight) for binary/ratio features that indicate risk.
+pub(crate) const WEIGHTS: &[(usize, f32)] = &[
+    (12, 0.35), // ip_in_url
+    (13, 0.25), // is_punycode
+    (15, 0.15), // port_anomaly
+    (21, 0.40), // brand_spoof_flag
+    (23, 0.35), // brand_in_subdomain_only
+    (24, 0.10), // has_login_kw
+    (25, 0.10), // has_trust_kw_in_domain
+    (27, 0.10), // has_free_kw
+    (28, 0.15), // has_fraud_kw
+    (31, 0.40), // double_extension
+    (33, 0.15), // heavy_encoding
+    (36, 0.50), // is_data_uri
+    (37, 0.15), // path_traversal
+    (38, 0.20), // suspicious_tld
+    (45, 0.15), // is_short_url
+    (49, 0.35), // suspicious_upi_vpa
+    (51, 0.35), // ext_executable
+    (53, 0.20), // open_redirect
+    (58, 0.25), // ext_script
+    (62, 0.20), // server_script_odd_host
+];
+
+/// Uncertainty every score carries for evidence the weights don't model.
+pub const UNCERTAINTY_FLOOR: f32 = 0.05;
+
+/// What each rule adds to the score of feature vector `f`.
+pub(crate) fn contributions(f: &[f32]) -> Vec<f32> {
+    let mut c: Vec<f32> = WEIGHTS.iter().map(|&(i, w)| f[i].min(1.0) * w).collect();
+    if f[11] == 0.0 && f[24] > 0.0 { c.push(0.10); } // login keywords over plain http
+    if f[1] > 40.0 { c.push(0.05); }
+    c
+}
+
+/// Heuristic risk in 0.0–1.0 from the model feature vector.
+pub fn url_risk(url: &str) -> f32 {
+    contributions(&extract_features(url)).iter().sum::<f32>().min(1.0)
+}
+
+/// Per-feature split counts of the tree model the host scores with, when
+/// it installed one.
+static SPLITS: RwLock<Option<Vec<usize>>> = RwLock::new(None);
+
+/// Rank features by `model`'s splits in [`feature_importances`] and
+/// `ranked_signals` instead of by [`WEIGHTS`]; `None`, or a model that
+/// never splits, goes back to the weights. Call whenever the loaded model
+/// changes.
+pub fn set_importance_model(model: Option<&TreeModel>) {
+    let splits = model.map(|m| { let mut s = m.split_counts(); s.truncate(MODEL_FEATURES); s }).filter(|s| s.iter().any(|&n| n > 0));
+    *SPLITS.write().unwrap_or_else(PoisonError::into_inner) = splits;
+}
+
+fn model_splits() -> Option<Vec<usize>> { SPLITS.read().unwrap_or_else(PoisonError::into_inner).clone() }
+
+/// `(feature index, weight)` heaviest first, ties in index order: every
+/// feature by split count when a tree model is installed, else the
+/// [`WEIGHTS`] entries.
+fn ranked(splits: Option<&[usize]>) -> Vec<(usize, f32)> {
+    let mut w = match splits {
+        Some(s) => s.iter().map(|&n| n as f32).enumerate().collect(),
+        None => WEIGHTS.to_vec(),
+    };
+    w.sort_by(|a, b| b.1.total_cmp(&a.1));
+    w
+}
+
+/// Feature indices with their weight or split count, heaviest first, from
+/// the same source [`feature_importances`] reports.
+pub(crate) fn by_importance() -> Vec<(usize, f32)> { ranked(model_splits().as_deref()) }
+
+/// Which URL features drive the score, heaviest first, for the options
+/// page: the installed tree model's splits (see [`set_importance_model`]),
+/// or the heuristic weights without one. `weight` is the split count or
+/// the rule weight, `importance` its share of the total; features a model
+/// never splits on are left out. Returns `{ model, kind, features: [{
+/// index, name, weight, importance }] }`, `kind` being "split_count" or
+/// "heuristic".
+pub fn feature_importances() -> Json {
+    let splits = model_splits();
+    let ranked = ranked(splits.as_deref());
+    let total: f32 = ranked.iter().map(|(_, w)| w).sum();
+    let features: Vec<Json> = ranked.into_iter().filter(|(_, w)| *w > 0.0).map(|(i, w)| {
+        let name = match splits { Some(_) => feature_name(i), None => URL_SIGNALS.iter().find(|(n, _)| *n == i).map(|(_, s)| *s) };
+        Json::obj().with("index", i).with("name", name).with("weight", w).with("importance", w / total)
+    }).collect();
+    let (model, kind) = if splits.is_some() { ("trees", "split_count") } else { ("url_weights", "heuristic") };
+    Json::obj().with("model", model).with("kind", kind).with("features", features)
+}
+
+/// Half-width of the band around the score built from `contributions`: the
+/// floor plus half of what the score would lose if its strongest signal were
+/// a false trigger. A score resting on one rule gets a wide band, one
+/// saturated by many a narrow one.
+pub(crate) fn uncertainty(contributions: &[f32]) -> f32 {
+    let sum: f32 = contributions.iter().sum();
+    let strongest = contributions.iter().copied().fold(0.0, f32::max);
+    UNCERTAINTY_FLOOR + 0.5 * (sum.min(1.0) - (sum - strongest).min(1.0))
+}
+
+/// Merge container-level signals with the worst contained link: the stronger
+/// one carries the verdict, the weaker one adds half its weight.
+pub fn combine(container: f32, worst_link: f32) -> f32 {
+    (container.max(worst_link) + 0.5 * container.min(worst_link)).min(1.0)
+}
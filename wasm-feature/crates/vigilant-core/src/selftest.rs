@@ -0,0 +1,220 @@
+// wasm-feature/crates/vigilant-core/src/selftest.rs
+// Browser Vigilant — start-up self-test: embedded table digests, golden
+// URLs through the default pipeline, and feature-length invariants. A
+// corrupted or half-applied update fails loudly here instead of quietly
+// misclassifying.
+
+use crate::analyzer::Analyzer;
+use crate::capabilities::{tables, MODEL_FEATURES};
+use crate::compound::COMPOUNDS;
+use crate::dga::MARKOV;
+use crate::hash::sha256_hex;
+use crate::json::Json;
+use crate::lexical;
+use crate::manifest;
+use crate::messages::{slots_of, template, CATALOG, DEFAULT_LOCALE, TRANSLATIONS};
+use crate::named::feature_name;
+use crate::packed::{pack_features, unpack_features, Precision};
+use crate::rank::{OVERLAPS, SEVERITIES, URL_SIGNALS};
+use crate::schema::feature_schema_hash;
+use crate::score::WEIGHTS;
+use crate::verdict::Verdict;
+use crate::{
+    brand_path_features, browser_url, char_class_features, contact_features, dga_features, encoding_features, entropy_features, extension_features, extract_features,
+    extract_features_with_time, feature_mask, homoglyph_features, host_signal_features, intent_features, keyword_features, malformed_features, numeric_brand_features, pronounce_features, resource_graph_report,
+    scheme_features, scheme_risk_features, subdomain_features, token_features, typosquat_features, EntropyOptions, HostSignals, ShortExpansion, BRAND_PATH_FEATURES, CHAR_CLASS_FEATURES, CONTACT_FEATURES, DGA_FEATURES, ENCODING_FEATURES, ENTROPY_FEATURES, EXPANSION_FEATURES,
+    EXTENSION_FEATURES, HOMOGLYPH_FEATURES, HOST_SIGNAL_FEATURES, INTENT_FEATURES, KEYWORD_FEATURES, MALFORMED_FEATURES, NUMERIC_BRAND_FEATURES, PRONOUNCE_FEATURES, RESOURCE_FEATURES, SCHEME_FEATURES, SCHEME_RISK_FEATURES, SUBDOMAIN_FEATURES,
+    TIME_FEATURES, TOKEN_FEATURES, TYPOSQUAT_FEATURES,
+};
+
+/// `Table::digest` of every embedded table. Editing a table means updating
+/// its entry here (`capabilities().dictionaries` prints the new value).
+const TABLE_DIGESTS: &[(&str, &str)] = &[
+    ("brands",             "2b24e5fe3c1cefff"),
+    ("brand_aliases",      "98652ced55ac1e74"),
+    ("compound_rules",     "d1532846a0588213"),
+    ("suspicious_tlds",    "8d310449e3f58303"),
+    ("short_services",     "ce701605bad4eb8d"),
+    ("dangerous_exts",     "ede11846f12611b2"),
+    ("data_uri_mimes",     "0ef13abb00f79af1"),
+    ("extension_classes",  "3bb74b71f155b3ac"),
+    ("server_script_exts", "240765825d37a14d"),
+    ("scheme_classes",     "ffe4b188f1cc11b2"),
+    ("premium_prefixes",   "54dc87d28cd48f09"),
+    ("high_risk_prefixes", "731388e75575e1ff"),
+    ("script_patterns",    "4de591229e4f67a4"),
+    ("tracking_params",    "23e9176386ff0465"),
+    ("keywords",           "3a70e943e82977c9"),
+    ("public_suffixes",    "7ea652db83b4d17b"),
+    ("suffix_wildcards",   "8636f10d0cc9e202"),
+    ("suffix_exceptions",  "dfbf3e07d8e99b5b"),
+    ("freemail",           "b2d10905435341a4"),
+    ("identity_providers", "c8b2671416abcf6d"),
+    ("app_stores",         "02cbbf6bab7adaaa"),
+    ("app_packages",       "ca283e7870fe03fa"),
+    ("language_profiles",  "4478b454b544c2b8"),
+    ("lure_words",         "88830d36d8bcdcc6"),
+    ("romanized_lures",    "2f4d1c101046a2cf"),
+    ("subdomain_words",    "fcb8e6038dac145c"),
+    ("digit_letters",      "3cd79b4ae29b4fe0"),
+    ("brand_paths",        "a52c3845b2e34042"),
+    ("kit_dirs",           "2689cb571586da76"),
+    ("kit_files",          "5098ae724b44c64a"),
+    ("popular_sites",      "3cb5e6dda0fee202"),
+    ("credential_sinks",   "9e057b743f99bbb6"),
+    ("messaging_webhooks", "332d2531f82a748a"),
+    ("severities",         "40a569ac28df9b71"),
+    ("signal_overlaps",    "3580e8dc2a882f23"),
+    ("confusables",        "2bba0d5f346fdba6"),
+    ("messages",           "cc05df1decd27389"),
+];
+
+/// `feature_schema_hash()` of this build. Renaming, reordering or retyping a
+/// model feature means updating it here and bumping `FEATURE_VERSION`.
+const FEATURE_SCHEMA_HASH: &str = "6c9855e3584aa0e3";
+
+/// Digest of the DGA Markov table, which `model/markov.py` regenerates.
+const MARKOV_DIGEST: &str = "6572c26f31a3b1da";
+
+/// URLs whose verdict under the default analyzer is pinned.
+const GOLDEN_URLS: &[(&str, Verdict)] = &[
+    ("https://www.google.com/", Verdict::Allow),
+    ("https://github.com/rust-lang/rust", Verdict::Allow),
+    ("https://paypa1-login.tk/signin", Verdict::Warn),
+    ("http://192.168.10.5/paypal/webscr/login.php?cmd=_login", Verdict::Block),
+    ("http://secure-paypal.com.account-update.tk/login/verify.php", Verdict::Block),
+];
+
+fn check(name: &str, ok: bool, detail: String) -> Json {
+    let c = Json::obj().with("name", name).with("ok", ok);
+    if ok { c } else { c.with("detail", detail) }
+}
+
+/// Returns `{ ok, failed, checks: [{ name, ok, detail? }] }`.
+pub fn self_test() -> Json {
+    let mut checks = Vec::new();
+
+    let tables = tables();
+    for t in &tables {
+        let want = TABLE_DIGESTS.iter().find(|(n, _)| *n == t.name).map(|(_, d)| *d);
+        let got = t.digest();
+        let ok = want == Some(got.as_str()) && t.entries > 0;
+        checks.push(check(&format!("table:{}", t.name), ok, format!("digest {} (expected {})", got, want.unwrap_or("none"))));
+    }
+    let missing: Vec<&str> = TABLE_DIGESTS.iter().map(|(n, _)| *n).filter(|n| !tables.iter().any(|t| t.name == *n)).collect();
+    checks.push(check("tables_registered", missing.is_empty(), format!("missing {}", missing.join(", "))));
+    let unsourced: Vec<&str> = manifest::SOURCES.iter().map(|(n, _)| *n).filter(|n| !tables.iter().any(|t| t.name == *n)).collect();
+    checks.push(check("manifest_sources", unsourced.is_empty(), format!("no table {}", unsourced.join(", "))));
+
+    let markov = sha256_hex(&MARKOV.concat())[..16].to_string();
+    checks.push(check("table:dga_markov", markov == MARKOV_DIGEST, format!("digest {} (expected {})", markov, MARKOV_DIGEST)));
+
+    let abc = sha256_hex(b"abc");
+    checks.push(check("sha256", abc == "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", abc));
+
+    // pinned verdicts only hold for the full feature set, not an ablation run
+    let analyzer = Analyzer::default();
+    let golden = if feature_mask().is_empty() { GOLDEN_URLS } else { &[] };
+    for (url, want) in golden {
+        let r = analyzer.url(url);
+        checks.push(check(&format!("golden:{}", url), r.verdict == *want,
+            format!("verdict {} at risk {:.2} (expected {})", r.verdict.as_str(), r.risk, want.as_str())));
+    }
+
+    let schema = feature_schema_hash();
+    checks.push(check("feature_schema", schema == FEATURE_SCHEMA_HASH, format!("hash {} (expected {})", schema, FEATURE_SCHEMA_HASH)));
+
+    let f = extract_features(GOLDEN_URLS[0].0);
+    checks.push(check("feature_length", f.len() == MODEL_FEATURES, format!("{} features", f.len())));
+    checks.push(check("features_finite", f.iter().all(|x| x.is_finite()), "non-finite feature".to_string()));
+    let round_trip = |p: Precision| pack_features(&f, p).and_then(|b| unpack_features(&b)).unwrap_or_default();
+    let lossless = round_trip(Precision::F32) == f;
+    let close = round_trip(Precision::U8).iter().zip(&f).filter(|(q, x)| (*q - *x).abs() > 0.05 * x.abs().max(1.0)).count();
+    checks.push(check("packed_round_trip", lossless && close == 0, format!("f32 lossless {}, {} u8 values off", lossless, close)));
+    let t = extract_features_with_time(GOLDEN_URLS[0].0, 0.0).len();
+    checks.push(check("time_feature_length", t == MODEL_FEATURES + TIME_FEATURES, format!("{} features", t)));
+    let b = brand_path_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("brand_path_feature_length", b == BRAND_PATH_FEATURES, format!("{} features", b)));
+    let k = keyword_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("keyword_feature_length", k == KEYWORD_FEATURES, format!("{} features", k)));
+    let c = char_class_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("char_class_feature_length", c == CHAR_CLASS_FEATURES, format!("{} features", c)));
+    let t = token_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("token_feature_length", t == TOKEN_FEATURES, format!("{} features", t)));
+    let e = entropy_features(GOLDEN_URLS[0].0, EntropyOptions::default());
+    checks.push(check("entropy_feature_length", e.len() == ENTROPY_FEATURES, format!("{} features", e.len())));
+    // in bits, the raw half is the model vector's F16–F20
+    let model = extract_features(GOLDEN_URLS[0].0);
+    let same = e[..5].iter().zip(&model[16..21]).all(|(a, b)| (a - b).abs() < 1e-5);
+    checks.push(check("entropy_matches_model", same, "F16–F20".to_string()));
+    let n = pronounce_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("pronounce_feature_length", n == PRONOUNCE_FEATURES, format!("{} features", n)));
+    let n = homoglyph_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("homoglyph_feature_length", n == HOMOGLYPH_FEATURES, format!("{} features", n)));
+    let n = numeric_brand_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("numeric_brand_feature_length", n == NUMERIC_BRAND_FEATURES, format!("{} features", n)));
+    let n = dga_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("dga_feature_length", n == DGA_FEATURES, format!("{} features", n)));
+    let n = subdomain_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("subdomain_feature_length", n == SUBDOMAIN_FEATURES, format!("{} features", n)));
+    let n = encoding_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("encoding_feature_length", n == ENCODING_FEATURES, format!("{} features", n)));
+    let n = malformed_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("malformed_feature_length", n == MALFORMED_FEATURES, format!("{} features", n)));
+    let b = browser_url(GOLDEN_URLS[0].0);
+    checks.push(check("browser_url_plain", b == GOLDEN_URLS[0].0, b));
+    let n = typosquat_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("typosquat_feature_length", n == TYPOSQUAT_FEATURES, format!("{} features", n)));
+    let e = extension_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("extension_feature_length", e == EXTENSION_FEATURES, format!("{} features", e)));
+    let s = scheme_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("scheme_feature_length", s == SCHEME_FEATURES, format!("{} features", s)));
+    let s = scheme_risk_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("scheme_risk_feature_length", s == SCHEME_RISK_FEATURES, format!("{} features", s)));
+    let c = contact_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("contact_feature_length", c == CONTACT_FEATURES, format!("{} features", c)));
+    let h = host_signal_features(GOLDEN_URLS[0].0, &HostSignals::default()).len();
+    checks.push(check("host_signal_feature_length", h == HOST_SIGNAL_FEATURES, format!("{} features", h)));
+    let i = intent_features(GOLDEN_URLS[0].0).len();
+    checks.push(check("intent_feature_length", i == INTENT_FEATURES, format!("{} features", i)));
+    let x = ShortExpansion::new(GOLDEN_URLS[0].0).features().len();
+    checks.push(check("expansion_feature_length", x == EXPANSION_FEATURES, format!("{} features", x)));
+    let cdf = lexical::LABEL_LENGTH_CDF;
+    let rising = cdf.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 <= w[1].1) && cdf.last().is_some_and(|l| l.1 == 1.0);
+    checks.push(check("label_length_cdf", rising, "not a rising CDF ending at 1".to_string()));
+    let r = resource_graph_report("[]", GOLDEN_URLS[0].0).get("features").and_then(Json::as_array).map_or(0, <[Json]>::len);
+    checks.push(check("resource_feature_length", r == RESOURCE_FEATURES, format!("{} features", r)));
+    // every template belongs to a catalog key and fills exactly its slots
+    let mut bad: Vec<String> = CATALOG.iter().filter(|(k, _)| template(DEFAULT_LOCALE, k).is_none()).map(|(k, _)| k.to_string()).collect();
+    for (locale, templates) in TRANSLATIONS {
+        for (key, text) in templates.iter() {
+            let mut want: Vec<&str> = CATALOG.iter().find(|(k, _)| k == key).map_or(vec!["?"], |(_, s)| s.to_vec());
+            let mut got = slots_of(text);
+            want.sort();
+            got.sort();
+            if want != got { bad.push(format!("{}:{}", locale, key)); }
+        }
+    }
+    checks.push(check("message_catalog", bad.is_empty(), format!("bad templates: {}", bad.join(", "))));
+    // every flag and tag the catalog explains is ranked, and nothing else is
+    let explained = |id: &str| CATALOG.iter().any(|(k, _)| k.strip_prefix("flag.").or_else(|| k.strip_prefix("finding.")) == Some(id));
+    let mut bad: Vec<String> = CATALOG.iter()
+        .filter_map(|(k, _)| k.strip_prefix("flag.").or_else(|| k.strip_prefix("finding.")))
+        .filter(|id| *id != "unknown" && !SEVERITIES.iter().any(|(n, _)| n == id))
+        .map(str::to_string)
+        .collect();
+    let known = |id: &str| explained(id) || URL_SIGNALS.iter().any(|(_, n)| *n == id);
+    bad.extend(SEVERITIES.iter().map(|(n, _)| *n).chain(OVERLAPS.concat()).filter(|n| !known(n)).map(str::to_string));
+    let feature = |n: &str| (0..MODEL_FEATURES).any(|i| feature_name(i) == Some(n));
+    bad.extend(COMPOUNDS.iter().flat_map(|(id, parts)| std::iter::once(id).chain(parts.iter())).filter(|n| !known(n) && !feature(n)).map(|n| n.to_string()));
+    if URL_SIGNALS.len() != WEIGHTS.len() || URL_SIGNALS.iter().zip(WEIGHTS).any(|((a, _), (b, _))| a != b) {
+        bad.push("url_signals".to_string());
+    }
+    bad.extend(URL_SIGNALS.iter().filter(|(i, n)| feature_name(*i) != Some(*n)).map(|(_, n)| format!("{} (misnamed)", n)));
+    checks.push(check("severity_table", bad.is_empty(), format!("unranked or unknown: {}", bad.join(", "))));
+    let bad: Vec<String> = WEIGHTS.iter().filter(|(i, _)| *i >= MODEL_FEATURES).map(|(i, _)| i.to_string()).collect();
+    checks.push(check("weight_indices", bad.is_empty(), format!("out of range: {}", bad.join(", "))));
+
+    let failed = checks.iter().filter(|c| c.get("ok").and_then(Json::as_bool) == Some(false)).count();
+    Json::obj().with("ok", failed == 0).with("failed", failed).with("checks", Json::Arr(checks))
+}
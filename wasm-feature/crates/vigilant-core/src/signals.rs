@@ -0,0 +1,144 @@
+// wasm-feature/crates/vigilant-core/src/signals.rs
+// Browser Vigilant — signals from downstream crates. A host embedding the
+// engine registers a `SignalProvider` for its own checks (an internal asset
+// inventory, a proprietary reputation score) and `Context::url` runs it next
+// to the built-in ones: the values travel in the report's `signals`, add
+// their weighted risk, show up in `ranked_signals` and the capabilities
+// report, and read as zero while masked, all without patching this crate.
+
+use std::sync::{Arc, PoisonError, RwLock};
+use crate::context::Context;
+use crate::json::Json;
+use crate::rank::Severity;
+use crate::UrlParts;
+
+/// A source of extra URL signals.
+pub trait SignalProvider: Send + Sync {
+    /// Unique, stable name: ASCII letters, digits and `_`.
+    fn name(&self) -> &str;
+
+    /// Values for `url` as parsed, under `ctx`; the same number every call.
+    fn extract(&self, url: &UrlParts, ctx: &Context) -> Vec<f32>;
+
+    /// Names of the values, in order; `<name>.<i>` when left empty.
+    fn feature_names(&self) -> Vec<String> { Vec::new() }
+
+    /// Risk each value adds per unit (values clamp to 0–1); none by default,
+    /// so a provider only reports until it is given weights.
+    fn weights(&self) -> Vec<f32> { Vec::new() }
+
+    /// Severity of a value that adds risk, for `ranked_signals`.
+    fn severity(&self) -> Severity { Severity::Medium }
+}
+
+/// One value a provider gave for a URL.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomSignal {
+    pub provider:     String,
+    pub name:         String,
+    pub value:        f32,
+    /// Risk it added: its weight times `value` clamped to 0–1.
+    pub contribution: f32,
+    pub severity:     Severity,
+}
+
+impl CustomSignal {
+    pub(crate) fn to_json(&self) -> Json {
+        Json::obj()
+            .with("provider", self.provider.as_str())
+            .with("name", self.name.as_str())
+            .with("value", self.value)
+            .with("contribution", self.contribution)
+            .with("severity", self.severity.as_str())
+    }
+}
+
+static PROVIDERS: RwLock<Vec<Arc<dyn SignalProvider>>> = RwLock::new(Vec::new());
+
+/// Provider names whose values read as zero.
+static MASKED: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+/// Add `provider` for every context in the process. Fails on a malformed
+/// name or one already registered.
+pub fn register_signal_provider(provider: Arc<dyn SignalProvider>) -> Result<(), String> {
+    let name = provider.name().to_string();
+    if !valid_name(&name) { return Err(format!("signal provider name {:?} must be ASCII letters, digits and _", name)); }
+    let mut list = PROVIDERS.write().unwrap_or_else(PoisonError::into_inner);
+    if list.iter().any(|p| p.name() == name) { return Err(format!("signal provider {} is already registered", name)); }
+    list.push(provider);
+    Ok(())
+}
+
+/// Remove the provider named `name`; false when there was none.
+pub fn unregister_signal_provider(name: &str) -> bool {
+    let mut list = PROVIDERS.write().unwrap_or_else(PoisonError::into_inner);
+    let before = list.len();
+    list.retain(|p| p.name() != name);
+    list.len() != before
+}
+
+/// Names of the registered providers, in registration order.
+pub fn signal_providers() -> Vec<String> {
+    PROVIDERS.read().unwrap_or_else(PoisonError::into_inner).iter().map(|p| p.name().to_string()).collect()
+}
+
+/// Zero the values of the providers in `names` from now on, replacing the
+/// previous signal mask; the counterpart of `set_feature_mask` for ablation
+/// runs. Names need not be registered yet.
+pub fn set_signal_mask(names: &[&str]) -> Result<(), String> {
+    if let Some(bad) = names.iter().find(|n| !valid_name(n)) { return Err(format!("{:?} is not a signal provider name", bad)); }
+    *MASKED.write().unwrap_or_else(PoisonError::into_inner) = names.iter().map(|n| n.to_string()).collect();
+    Ok(())
+}
+
+/// Provider names currently masked.
+pub fn signal_mask() -> Vec<String> { MASKED.read().unwrap_or_else(PoisonError::into_inner).clone() }
+
+fn names_of(p: &dyn SignalProvider, n: usize) -> Vec<String> {
+    let mut names = p.feature_names();
+    names.truncate(n);
+    let base = names.len();
+    names.extend((base..n).map(|i| format!("{}.{}", p.name(), i)));
+    names
+}
+
+/// Every registered provider's values for `url`.
+pub(crate) fn extract(url: &UrlParts, ctx: &Context) -> Vec<CustomSignal> {
+    let providers = PROVIDERS.read().unwrap_or_else(PoisonError::into_inner).clone();
+    if providers.is_empty() { return Vec::new(); }
+    let masked = signal_mask();
+    let mut out = Vec::new();
+    for p in providers {
+        let mut values = p.extract(url, ctx);
+        if masked.iter().any(|m| m == p.name()) { values.iter_mut().for_each(|v| *v = 0.0); }
+        let weights = p.weights();
+        let severity = p.severity();
+        for (i, (value, name)) in values.iter().zip(names_of(p.as_ref(), values.len())).enumerate() {
+            let value = if value.is_finite() { *value } else { 0.0 };
+            let contribution = weights.get(i).copied().unwrap_or(0.0).max(0.0) * value.clamp(0.0, 1.0);
+            out.push(CustomSignal { provider: p.name().to_string(), name, value, contribution, severity });
+        }
+    }
+    out
+}
+
+/// Registered providers for the capabilities report: `[{ name, features,
+/// weights, severity, masked }]`.
+pub(crate) fn providers_report() -> Json {
+    let masked = signal_mask();
+    let providers = PROVIDERS.read().unwrap_or_else(PoisonError::into_inner).clone();
+    Json::Arr(providers.iter().map(|p| {
+        let weights = p.weights();
+        let names = p.feature_names();
+        Json::obj()
+            .with("name", p.name())
+            .with("features", names)
+            .with("weights", weights)
+            .with("severity", p.severity().as_str())
+            .with("masked", masked.iter().any(|m| m == p.name()))
+    }).collect())
+}
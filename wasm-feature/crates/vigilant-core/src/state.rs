@@ -0,0 +1,480 @@
+// wasm-feature/crates/vigilant-core/src/state.rs
+// Browser Vigilant — mutable stores kept between calls: feature cache,
+// per-template scores, host reputation, per-tab risk sessions and domain
+// first-seen times. Everything
+// here can be dropped at any time; losing it costs recomputation (and makes
+// domains look new again), never a wrong verdict.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem::size_of;
+use crate::analyzer::Analyzer;
+use crate::api::{RiskSession, UrlReport, Visit};
+use crate::hash::Privacy;
+use crate::json::Json;
+use crate::mask;
+use crate::{parse_url, query_pairs};
+use crate::template::{abstract_segment, is_kit_path, url_template, SiteTemplates, MAX_SITES};
+use crate::time::time_features;
+use crate::timing::Stages;
+use crate::types::{err, Fields, SchemaError, SCHEMA_VERSION};
+use crate::verdict::Verdict;
+
+/// Default number of URLs whose reports are kept.
+pub const FEATURE_CACHE_ENTRIES: usize = 1024;
+
+/// Lookups a memoised template score serves before it is recomputed from
+/// the URL at hand.
+pub const MEMO_RECHECK_HITS: u32 = 64;
+
+/// Path segments a memo template keeps; deeper ones share a template.
+const MEMO_SEGMENTS: usize = 8;
+
+/// `origin/path?keys` of `url` with numbers, ids and file names abstracted
+/// and query values dropped (`https://api.example.com/v1/users/{n}?fields&page`).
+fn memo_template(url: &str) -> String {
+    let p = parse_url(url);
+    let port = p.port.map(|n| format!(":{}", n)).unwrap_or_default();
+    let segs: Vec<String> = p.path.split('/').filter(|s| !s.is_empty()).take(MEMO_SEGMENTS).map(abstract_segment).collect();
+    let mut keys: Vec<String> = query_pairs(&p.query).into_iter().map(|(k, _)| k).collect();
+    keys.sort();
+    keys.dedup();
+    format!("{}://{}{}/{}?{}", p.scheme, p.host.to_lowercase(), port, segs.join("/"), keys.join("&"))
+}
+
+fn str_bytes(s: &String) -> usize { s.capacity() + size_of::<String>() }
+
+fn report_bytes(r: &UrlReport) -> usize {
+    size_of::<UrlReport>() + r.features.capacity() * size_of::<f32>() + r.flags.capacity() * size_of::<&str>()
+}
+
+fn site_bytes(t: &SiteTemplates) -> usize {
+    size_of::<SiteTemplates>() + t.templates.keys().map(|k| str_bytes(k) + size_of::<u32>()).sum::<usize>()
+}
+
+/// Fixed identifier whose key lets an import check it was exported under
+/// the same privacy salt without the salt itself being stored.
+pub(crate) const KEY_CHECK: &str = "browser-vigilant";
+
+/// Memoised score: risk, lookups served since it was computed.
+#[derive(Clone, Copy, Debug)]
+struct Memo {
+    risk: f32,
+    hits: u32,
+}
+
+fn session_bytes(s: &RiskSession) -> usize {
+    size_of::<RiskSession>()
+        + s.signals().iter().map(|(k, _)| str_bytes(k) + size_of::<f32>()).sum::<usize>()
+        + s.visits().iter().map(|v| size_of::<Visit>() + v.domain.capacity()).sum::<usize>()
+}
+
+/// Drop least-recently-used entries until `bytes` fits in `budget`; returns bytes freed.
+fn evict<K: Clone + Eq + Hash, V>(map: &mut HashMap<K, (V, u64)>, size: impl Fn(&K, &V) -> usize, bytes: &mut usize, budget: usize) -> usize {
+    if *bytes <= budget { return 0; }
+    let mut order: Vec<(u64, K)> = map.iter().map(|(k, (_, t))| (*t, k.clone())).collect();
+    order.sort_by_key(|(t, _)| *t);
+    let mut freed = 0;
+    for (_, k) in order {
+        if *bytes <= budget { break; }
+        if let Some((v, _)) = map.remove(&k) {
+            let n = size(&k, &v);
+            *bytes -= n.min(*bytes);
+            freed += n;
+        }
+    }
+    freed
+}
+
+#[derive(Debug)]
+pub struct State {
+    features:   HashMap<String, (UrlReport, u64)>,
+    /// Origin and path template → the score its last checked URL got.
+    memo:       HashMap<String, (Memo, u64)>,
+    reputation: HashMap<String, (f32, u64)>,
+    sessions:   HashMap<u32, (RiskSession, u64)>,
+    /// Registrable domain → local ms it was first scored with a timestamp.
+    first_seen: HashMap<String, (f64, u64)>,
+    /// Registrable domain → URL templates learned for it (opt-in).
+    templates:  HashMap<String, (SiteTemplates, u64)>,
+    learn_templates: bool,
+    capacity:   usize,
+    /// Feature mask and analyzer brands the cached reports were scored under.
+    mask:       u128,
+    brands:     Vec<String>,
+    tick:       u64,
+    privacy:    Privacy,
+}
+
+impl Default for State {
+    fn default() -> State { State::with_feature_capacity(FEATURE_CACHE_ENTRIES) }
+}
+
+impl State {
+    pub fn new() -> State { State::default() }
+
+    pub fn with_feature_capacity(capacity: usize) -> State {
+        State {
+            features: HashMap::new(), memo: HashMap::new(), reputation: HashMap::new(), sessions: HashMap::new(), first_seen: HashMap::new(),
+            templates: HashMap::new(), learn_templates: false, capacity, mask: 0, brands: Vec::new(), tick: 0,
+            privacy: Privacy::plain(),
+        }
+    }
+
+    /// Switch identifier keying. Stored entries can't be re-keyed, so the
+    /// stores are emptied.
+    pub fn set_privacy(&mut self, privacy: Privacy) {
+        self.reset();
+        self.privacy = privacy;
+    }
+
+    pub fn privacy(&self) -> &Privacy { &self.privacy }
+
+    fn host_key(&self, host: &str) -> String { self.privacy.key(&host.trim_end_matches('.').to_lowercase()) }
+
+    fn touch(&mut self) -> u64 { self.tick += 1; self.tick }
+
+    /// A new ablation mask or brand list changes every vector and score.
+    fn drop_stale(&mut self, analyzer: &Analyzer) {
+        if mask::bits() != self.mask || analyzer.brands() != self.brands.as_slice() {
+            self.features.clear();
+            self.memo.clear();
+            self.mask = mask::bits();
+            self.brands = analyzer.brands().to_vec();
+        }
+    }
+
+    /// `analyzer.url(url)`, served from the feature cache when possible.
+    pub fn url(&mut self, analyzer: &Analyzer, url: &str) -> UrlReport {
+        let t = self.touch();
+        let mut stages = Stages::start();
+        let key = self.privacy.key(url);
+        self.drop_stale(analyzer);
+        if let Some((r, at)) = self.features.get_mut(&key) {
+            *at = t;
+            let mut r = r.clone();
+            stages.mark("cache");
+            r.timings = stages.into_spans();
+            return r;
+        }
+        if self.capacity == 0 { return analyzer.url(url); }
+        if self.features.len() >= self.capacity {
+            // evict the oldest quarter at once so inserts stay amortised O(1)
+            let mut ticks: Vec<u64> = self.features.values().map(|(_, t)| *t).collect();
+            let cut = self.capacity / 4;
+            ticks.select_nth_unstable(cut);
+            let oldest = ticks[cut];
+            self.features.retain(|_, (_, t)| *t > oldest);
+        }
+        let r = analyzer.url(url);
+        self.features.insert(key, (r.clone(), t));
+        r
+    }
+
+    /// Analyzer risk of `url`, memoised per origin and path template so the
+    /// thousandth `/api/items/{n}` request of a page costs a lookup. Query
+    /// keys are part of the template, values aren't. Each memo is recomputed
+    /// from the URL at hand every [`MEMO_RECHECK_HITS`] lookups, and a URL
+    /// with a new template is always scored in full.
+    pub fn memo_risk(&mut self, analyzer: &Analyzer, url: &str) -> f32 {
+        let t = self.touch();
+        let key = self.privacy.key(&memo_template(url));
+        self.drop_stale(analyzer);
+        if let Some((m, at)) = self.memo.get_mut(&key) {
+            if m.hits < MEMO_RECHECK_HITS {
+                m.hits += 1;
+                *at = t;
+                return m.risk;
+            }
+        }
+        let risk = self.url(analyzer, url).risk;
+        if self.capacity == 0 { return risk; }
+        if !self.memo.contains_key(&key) && self.memo.len() >= self.capacity {
+            let mut ticks: Vec<u64> = self.memo.values().map(|(_, t)| *t).collect();
+            let cut = self.capacity / 4;
+            ticks.select_nth_unstable(cut);
+            let oldest = ticks[cut];
+            self.memo.retain(|_, (_, t)| *t > oldest);
+        }
+        self.memo.insert(key, (Memo { risk, hits: 0 }, t));
+        risk
+    }
+
+    /// Remember an externally supplied reputation score (0 good – 1 bad) for a host.
+    pub fn set_reputation(&mut self, host: &str, score: f32) {
+        let t = self.touch();
+        let key = self.host_key(host);
+        self.reputation.insert(key, (score.clamp(0.0, 1.0), t));
+    }
+
+    pub fn reputation(&self, host: &str) -> Option<f32> {
+        self.reputation.get(&self.host_key(host)).map(|(s, _)| *s)
+    }
+
+    /// Cached features plus time features for `local_ms`; the first call
+    /// for a registrable domain records when it was first seen.
+    pub fn features_with_time(&mut self, analyzer: &Analyzer, url: &str, local_ms: f64) -> Vec<f32> {
+        let mut f = self.url(analyzer, url).features;
+        let t = self.touch();
+        let key = self.privacy.key(&parse_url(url).reg_domain);
+        let entry = self.first_seen.entry(key).or_insert((local_ms, t));
+        entry.1 = t;
+        f.extend(time_features(local_ms, entry.0));
+        mask::apply(&mut f);
+        f
+    }
+
+    /// Turn per-site URL template learning on or off; off forgets what was learned.
+    pub fn set_template_learning(&mut self, on: bool) {
+        self.learn_templates = on;
+        if !on { self.templates.clear(); self.templates.shrink_to_fit(); }
+    }
+
+    pub fn template_learning(&self) -> bool { self.learn_templates }
+
+    /// Learn `url`'s path template for its site and report whether it departs
+    /// from what the site normally serves: `kit_path_on_known_site` for CMS
+    /// directories, otherwise `unusual_site_template`. `None` while learning
+    /// is off or before the site has been visited often enough.
+    pub fn template_anomaly(&mut self, url: &str) -> Option<&'static str> {
+        if !self.learn_templates { return None; }
+        let p = parse_url(url);
+        if p.reg_domain.is_empty() { return None; }
+        let t = self.touch();
+        let site = self.privacy.key(&p.reg_domain.to_lowercase());
+        let template = url_template(&p.path);
+        let key = self.privacy.key(&template);
+        if !self.templates.contains_key(&site) && self.templates.len() >= MAX_SITES {
+            let oldest = self.templates.iter().min_by_key(|(_, (_, at))| *at).map(|(k, _)| k.clone());
+            if let Some(k) = oldest { self.templates.remove(&k); }
+        }
+        let entry = self.templates.entry(site).or_insert_with(|| (SiteTemplates::default(), t));
+        entry.1 = t;
+        let trusted = entry.0.trusted();
+        if entry.0.learn(key) > 0 || !trusted { return None; }
+        Some(if is_kit_path(&template) { "kit_path_on_known_site" } else { "unusual_site_template" })
+    }
+
+    pub fn session(&mut self, tab: u32) -> &mut RiskSession {
+        let t = self.touch();
+        let entry = self.sessions.entry(tab).or_insert_with(|| (RiskSession::new(), t));
+        entry.1 = t;
+        &mut entry.0
+    }
+
+    /// Score `url`, fold it (and any known host reputation) into the tab's
+    /// session and return the session verdict.
+    pub fn observe_url(&mut self, analyzer: &Analyzer, tab: u32, url: &str) -> Verdict {
+        self.observe(analyzer, tab, url, None)
+    }
+
+    /// [`State::observe_url`] for a navigation at `at_ms`, which also feeds
+    /// the session's navigation features.
+    pub fn observe_url_at(&mut self, analyzer: &Analyzer, tab: u32, url: &str, at_ms: f64) -> Verdict {
+        self.observe(analyzer, tab, url, Some(at_ms))
+    }
+
+    fn observe(&mut self, analyzer: &Analyzer, tab: u32, url: &str, at_ms: Option<f64>) -> Verdict {
+        let r = self.url(analyzer, url);
+        let host = parse_url(url).host;
+        let rep = self.reputation(&host);
+        let source = self.privacy.key(url);
+        let domain = self.host_key(&analyzer.registrable_domain(&host));
+        let s = self.session(tab);
+        s.record(&source, r.risk);
+        if let Some(rep) = rep { s.record("reputation", rep); }
+        if let Some(at) = at_ms { s.record_navigation(&domain, r.features.get(38).is_some_and(|&f| f > 0.0), at); }
+        s.verdict()
+    }
+
+    pub fn close_session(&mut self, tab: u32) -> bool { self.sessions.remove(&tab).is_some() }
+
+    fn sizes(&self) -> (usize, usize, usize, usize, usize, usize) {
+        let t = size_of::<u64>();
+        (
+            self.features.iter().map(|(k, (r, _))| str_bytes(k) + report_bytes(r) + t).sum(),
+            self.memo.keys().map(|k| str_bytes(k) + size_of::<Memo>() + t).sum(),
+            self.reputation.keys().map(|k| str_bytes(k) + size_of::<f32>() + t).sum(),
+            self.sessions.values().map(|(s, _)| size_of::<u32>() + session_bytes(s) + t).sum(),
+            self.first_seen.keys().map(|k| str_bytes(k) + size_of::<f64>() + t).sum(),
+            self.templates.iter().map(|(k, (v, _))| str_bytes(k) + site_bytes(v) + t).sum(),
+        )
+    }
+
+    pub fn heap_bytes(&self) -> usize { let (f, m, r, s, d, p) = self.sizes(); f + m + r + s + d + p }
+
+    /// Returns `{ feature_cache, memo, reputation, sessions, first_seen, templates }`, each `{ entries, bytes }`.
+    pub fn memory(&self) -> Json {
+        let (f, m, r, s, d, p) = self.sizes();
+        let store = |entries: usize, bytes: usize| Json::obj().with("entries", entries).with("bytes", bytes);
+        Json::obj()
+            .with("feature_cache", store(self.features.len(), f))
+            .with("memo", store(self.memo.len(), m))
+            .with("reputation", store(self.reputation.len(), r))
+            .with("sessions", store(self.sessions.len(), s))
+            .with("first_seen", store(self.first_seen.len(), d))
+            .with("templates", store(self.templates.len(), p))
+    }
+
+    /// Shrink the stores to roughly `target_bytes`, cheapest to rebuild
+    /// first: cached reports and template scores, then reputation, idle tab sessions, first-seen
+    /// times and finally learned site templates. Returns the bytes freed.
+    pub fn trim(&mut self, target_bytes: usize) -> usize {
+        let t = size_of::<u64>();
+        let mut bytes = self.heap_bytes();
+        let mut freed = evict(&mut self.features, |k, r| str_bytes(k) + report_bytes(r) + t, &mut bytes, target_bytes);
+        freed += evict(&mut self.memo, |k, _| str_bytes(k) + size_of::<Memo>() + t, &mut bytes, target_bytes);
+        freed += evict(&mut self.reputation, |k, _| str_bytes(k) + size_of::<f32>() + t, &mut bytes, target_bytes);
+        freed += evict(&mut self.sessions, |_, s| size_of::<u32>() + session_bytes(s) + t, &mut bytes, target_bytes);
+        freed += evict(&mut self.first_seen, |k, _| str_bytes(k) + size_of::<f64>() + t, &mut bytes, target_bytes);
+        freed += evict(&mut self.templates, |k, v| str_bytes(k) + site_bytes(v) + t, &mut bytes, target_bytes);
+        self.features.shrink_to_fit();
+        self.memo.shrink_to_fit();
+        self.reputation.shrink_to_fit();
+        self.sessions.shrink_to_fit();
+        self.first_seen.shrink_to_fit();
+        self.templates.shrink_to_fit();
+        freed
+    }
+
+    /// Drop cached reports and memoised scores, which go stale when the
+    /// analyzer is reconfigured.
+    pub fn clear_reports(&mut self) {
+        self.features.clear();
+        self.memo.clear();
+    }
+
+    /// Forget everything stored; the privacy mode is kept.
+    pub fn reset(&mut self) {
+        self.features.clear();
+        self.memo.clear();
+        self.reputation.clear();
+        self.sessions.clear();
+        self.first_seen.clear();
+        self.templates.clear();
+        self.features.shrink_to_fit();
+        self.memo.shrink_to_fit();
+        self.reputation.shrink_to_fit();
+        self.sessions.shrink_to_fit();
+        self.first_seen.shrink_to_fit();
+        self.templates.shrink_to_fit();
+        self.tick = 0;
+    }
+
+    // ── Persistence ──────────────────────────────────────────────────────────
+
+    /// The stores worth keeping across restarts, as `{ schema_version,
+    /// key_check, reputation: { key: score }, first_seen: { key: ms },
+    /// templates: { site: { visits, templates: { template: count } } } }`.
+    /// Cached reports and tab sessions are cheap to rebuild and left out.
+    /// Keys are exported exactly as stored, so hashed keys stay hashed.
+    pub fn export(&self) -> Json {
+        fn sorted<V>(m: &HashMap<String, V>, f: impl Fn(&V) -> Json) -> Json {
+            let mut v: Vec<(String, Json)> = m.iter().map(|(k, x)| (k.clone(), f(x))).collect();
+            v.sort_by(|a, b| a.0.cmp(&b.0));
+            Json::Obj(v)
+        }
+        Json::obj()
+            .with("schema_version", SCHEMA_VERSION)
+            .with("key_check", self.privacy.key(KEY_CHECK))
+            .with("reputation", sorted(&self.reputation, |(s, _)| Json::from(*s)))
+            .with("first_seen", sorted(&self.first_seen, |(ms, _)| Json::from(*ms)))
+            .with("templates", sorted(&self.templates, |(st, _)| {
+                Json::obj().with("visits", st.visits).with("templates", sorted(&st.templates, |n| Json::from(*n)))
+            }))
+    }
+
+    /// Merge a document from [`State::export`]; returns how many entries were
+    /// loaded. Fails without changes when it was written under another
+    /// privacy salt, since none of its keys would ever match. Templates load
+    /// only while learning is on.
+    pub fn import(&mut self, v: &Json, path: &str) -> Result<usize, SchemaError> {
+        let f = Fields::new(v, path)?;
+        f.schema_version()?;
+        if f.opt_string("key_check")?.is_some_and(|k| k != self.privacy.key(KEY_CHECK)) {
+            return Err(err(&f.at("key_check"), "state was exported under a different privacy mode or salt"));
+        }
+        let num = |x: &Json, p: &str| x.as_f64().ok_or_else(|| err(p, "expected number"));
+        let reputation = f.map("reputation", num)?;
+        let first_seen = f.map("first_seen", num)?;
+        let templates = f.map("templates", |x, p| {
+            let t = Fields::new(x, p)?;
+            let templates = t.map("templates", |x, p| num(x, p).map(|n| n.max(0.0) as u32))?;
+            Ok(SiteTemplates { visits: t.opt_count("visits")?.unwrap_or(0) as u32, templates: templates.into_iter().collect() })
+        })?;
+        let t = self.touch();
+        let n = reputation.len() + first_seen.len();
+        for (k, s) in reputation { self.reputation.insert(k, ((s as f32).clamp(0.0, 1.0), t)); }
+        for (k, ms) in first_seen {
+            let e = self.first_seen.entry(k).or_insert((ms, t));
+            e.0 = e.0.min(ms);
+        }
+        if !self.learn_templates { return Ok(n); }
+        let m = templates.len();
+        for (k, st) in templates.into_iter().take(MAX_SITES) { self.templates.insert(k, (st, t)); }
+        Ok(n + m.min(MAX_SITES))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(s: &State, store: &str) -> f64 {
+        s.memory().get(store).and_then(|m| m.get("entries")).and_then(Json::as_f64).unwrap_or(-1.0)
+    }
+
+    #[test]
+    fn reports_are_cached_up_to_capacity() {
+        let a = Analyzer::default();
+        let mut s = State::with_feature_capacity(4);
+        let first = s.url(&a, "https://example.com/a");
+        assert_eq!(s.url(&a, "https://example.com/a").features, first.features);
+        assert_eq!(entries(&s, "feature_cache"), 1.0);
+        for i in 0..10 { s.url(&a, &format!("https://example.com/{}", i)); }
+        assert!(entries(&s, "feature_cache") <= 4.0);
+
+        let mut off = State::with_feature_capacity(0);
+        off.url(&a, "https://example.com/a");
+        assert_eq!(entries(&off, "feature_cache"), 0.0);
+    }
+
+    #[test]
+    fn trim_to_zero_empties_every_store() {
+        let a = Analyzer::default();
+        let mut s = State::new();
+        s.url(&a, "https://example.com/a");
+        s.memo_risk(&a, "https://example.com/items/1");
+        s.set_reputation("evil.example", 0.9);
+        s.observe_url(&a, 7, "https://example.com/");
+        let before = s.heap_bytes();
+        assert!(before > 0);
+        assert_eq!(s.trim(0), before);
+        assert_eq!(s.heap_bytes(), 0);
+        assert_eq!(s.reputation("evil.example"), None);
+    }
+
+    #[test]
+    fn trim_drops_cached_reports_before_reputation() {
+        let a = Analyzer::default();
+        let mut s = State::new();
+        s.set_reputation("evil.example", 0.9);
+        for i in 0..20 { s.url(&a, &format!("https://example.com/{}", i)); }
+        let reputation = s.heap_bytes() - s.sizes().0;
+        s.trim(reputation);
+        assert_eq!(entries(&s, "feature_cache"), 0.0);
+        assert_eq!(s.reputation("evil.example"), Some(0.9));
+    }
+
+    #[test]
+    fn reset_forgets_everything_but_the_privacy_mode() {
+        let a = Analyzer::default();
+        let mut s = State::new();
+        s.set_privacy(Privacy::hashed(b"salt"));
+        s.url(&a, "https://example.com/a");
+        s.set_reputation("evil.example", 0.9);
+        s.reset();
+        assert_eq!(s.heap_bytes(), 0);
+        assert!(s.privacy().is_hashed());
+    }
+}
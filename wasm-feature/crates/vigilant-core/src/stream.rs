@@ -0,0 +1,146 @@
+// wasm-feature/crates/vigilant-core/src/stream.rs
+// Browser Vigilant — JSONL batch scoring for the CLI / server path
+// One line in, one line out: memory stays bounded by MAX_LINE_BYTES no matter
+// how many rows the dump has.
+
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use crate::analyzer::Analyzer;
+use crate::api::UrlReport;
+use crate::json::Json;
+
+/// Longest input line read; anything beyond is drained and reported as an error.
+pub const MAX_LINE_BYTES: usize = 64 << 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    /// Non-blank input lines seen.
+    pub lines:  u64,
+    pub scored: u64,
+    pub errors: u64,
+}
+
+/// Read one line into `buf` (without the newline), keeping at most `cap`
+/// bytes. Returns `None` at EOF, otherwise whether the line was cut short.
+pub fn read_capped<R: BufRead>(r: &mut R, buf: &mut Vec<u8>, cap: usize) -> io::Result<Option<bool>> {
+    buf.clear();
+    let (mut seen, mut cut) = (false, false);
+    loop {
+        let chunk = match r.fill_buf() {
+            Ok(c) => c,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if chunk.is_empty() { return Ok(seen.then_some(cut)); }
+        seen = true;
+        let (line, done) = match chunk.iter().position(|&b| b == b'\n') {
+            Some(i) => (&chunk[..i], Some(i + 1)),
+            None => (chunk, None),
+        };
+        let room = cap.saturating_sub(buf.len());
+        if line.len() > room { cut = true; }
+        buf.extend_from_slice(&line[..line.len().min(room)]);
+        let used = done.unwrap_or(chunk.len());
+        r.consume(used);
+        if done.is_some() { return Ok(Some(cut)); }
+    }
+}
+
+/// A line is a bare URL, a JSON string, or `{ "url": ..., "id"?: ... }`.
+fn parse_line(line: &str) -> Result<(String, Json), String> {
+    match line.as_bytes()[0] {
+        b'{' => {
+            let v = Json::parse(line)?;
+            match v.get("url").and_then(Json::as_str) {
+                Some(u) => Ok((u.to_string(), v.get("id").cloned().unwrap_or(Json::Null))),
+                None => Err("object has no string \"url\"".to_string()),
+            }
+        }
+        b'"' => Json::parse(line)?.as_str().map(|u| (u.to_string(), Json::Null)).ok_or_else(|| "expected string".to_string()),
+        _ => Ok((line.to_string(), Json::Null)),
+    }
+}
+
+/// Result line `n` for `url`, analyzed by `analyze`; a panicking analysis
+/// gives `{ line, error }` so one bad row doesn't end the stream.
+fn report_line(n: u64, url: String, id: Json, analyze: impl FnOnce(&str) -> UrlReport) -> Json {
+    let Ok(r) = panic::catch_unwind(AssertUnwindSafe(|| analyze(&url))) else {
+        return Json::obj().with("line", n).with("error", "analysis failed");
+    };
+    let mut o = Json::obj().with("line", n);
+    if id != Json::Null { o = o.with("id", id); }
+    o.with("url", url)
+        .with("features", r.features)
+        .with("risk", r.risk)
+        .with("verdict", r.verdict.as_str())
+        .with("uncertainty", r.uncertainty)
+        .with("decision", r.decision.as_str())
+        .with("flags", r.flags)
+}
+
+/// Score input line `n` (1-based) as read by [`read_capped`]; `None` for a
+/// blank line. Gives `{ line, id?, url, features, risk, verdict, uncertainty,
+/// decision, flags }`, or `{ line, error }` for a line that doesn't parse or
+/// whose analysis panicked.
+pub fn score_line(analyzer: &Analyzer, n: u64, raw: &[u8], cut: bool) -> Option<Json> {
+    let line = String::from_utf8_lossy(raw);
+    let line = line.trim();
+    if line.is_empty() { return None; }
+    let parsed = if cut { Err(format!("line exceeds {} bytes", MAX_LINE_BYTES)) } else { parse_line(line) };
+    Some(match parsed {
+        Ok((url, id)) => report_line(n, url, id, |u| analyzer.url(u)),
+        Err(e) => Json::obj().with("line", n).with("error", e),
+    })
+}
+
+/// Score every line of `reader` with `analyzer`, handing each result to
+/// `on_result` as soon as it is ready. Results are `{ line, id?, url,
+/// features, risk, verdict, uncertainty, decision, flags }` or
+/// `{ line, error }`; `line` is 1-based. Stops at the first I/O error from either side.
+pub fn stream_with<R: BufRead, F: FnMut(Json) -> io::Result<()>>(
+    mut reader: R,
+    analyzer: &Analyzer,
+    mut on_result: F,
+) -> io::Result<StreamStats> {
+    let mut stats = StreamStats::default();
+    let mut buf = Vec::with_capacity(1024);
+    let mut n = 0u64;
+    while let Some(cut) = read_capped(&mut reader, &mut buf, MAX_LINE_BYTES)? {
+        n += 1;
+        let Some(out) = score_line(analyzer, n, &buf, cut) else { continue };
+        stats.lines += 1;
+        if out.get("error").is_some() { stats.errors += 1 } else { stats.scored += 1 }
+        on_result(out)?;
+    }
+    Ok(stats)
+}
+
+/// JSONL in, JSONL out with the shared analyzer (see [`stream_with`]).
+pub fn extract_stream<R: BufRead, W: Write>(reader: R, mut writer: W) -> io::Result<StreamStats> {
+    let stats = stream_with(reader, Analyzer::shared(), |r| writeln!(writer, "{}", r))?;
+    writer.flush()?;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_panicking_analysis_leaves_an_error_row() {
+        let r = report_line(3, "https://x.example/".to_string(), Json::Null, |_| panic!("analysis bug"));
+        assert_eq!(r, Json::obj().with("line", 3u64).with("error", "analysis failed"));
+    }
+
+    #[test]
+    fn rows_after_a_hostile_url_are_still_scored() {
+        let input = "https://a.example/\nİİ://x\n{\"url\": 5}\nhttps://b.example/\n";
+        let mut out = Vec::new();
+        let stats = stream_with(input.as_bytes(), &Analyzer::default(), |r| { out.push(r); Ok(()) }).unwrap();
+        assert_eq!(stats, StreamStats { lines: 4, scored: 3, errors: 1 });
+        let lines: Vec<f64> = out.iter().filter_map(|r| r.get("line").and_then(Json::as_f64)).collect();
+        assert_eq!(lines, [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(out[1].str_field("url"), "İİ://x");
+        assert_eq!(out[3].str_field("url"), "https://b.example/");
+    }
+}
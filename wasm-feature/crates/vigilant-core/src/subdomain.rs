@@ -0,0 +1,99 @@
+// wasm-feature/crates/vigilant-core/src/subdomain.rs
+// Browser Vigilant — random subdomains. Kits on wildcard DNS mint a label
+// per victim (`kjq83ad.secure-login.tk`) so that a blocklisted host is
+// never seen twice. The host entropies (F16–F20) average that label with
+// the registered name and the suffix, and the DGA chain only reads the
+// registrable label, so the generated part disappears. This measures the
+// subdomain on its own: how much it varies, and how much of it spells
+// words an operator would have typed (`www`, `mail`, `login-secure`).
+
+use crate::{has_ip, parse_url, shannon_entropy, BRANDS, FRAUD_KW, FREE_KW, LOGIN_KW, PAY_KW, TRUST_KW};
+
+/// Values returned by [`subdomain_features`]: subdomain_entropy,
+/// dictionary_coverage, label_randomness, random_label.
+pub const SUBDOMAIN_FEATURES: usize = 4;
+
+/// Words that make up ordinary subdomains, lowercased. The keyword lists
+/// and brands count as words too; lures spell them out on purpose.
+pub(crate) const SUBDOMAIN_WORDS: &[&str] = &[
+    "www", "web", "mail", "email", "smtp", "imap", "pop", "mx", "webmail", "api", "app", "apps", "cdn", "static", "assets", "img",
+    "images", "media", "files", "download", "downloads", "docs", "help", "blog", "news", "shop", "store", "portal", "admin", "dev",
+    "test", "stage", "staging", "beta", "demo", "new", "old", "home", "my", "go", "m", "mobile", "cloud", "online", "service",
+    "services", "customer", "client", "user", "users", "member", "members", "auth", "sso", "id", "account", "accounts", "sign",
+    "signin", "connect", "access", "verify", "verification", "update", "security", "center", "centre", "info", "live", "net",
+    "office", "team", "community", "forum", "status", "video", "music", "search", "maps", "drive", "box", "remote", "vpn", "git",
+    "wiki", "jobs", "careers", "investor", "about", "contact", "en", "us", "uk", "in", "eu", "de", "fr", "es", "global", "intl",
+    "com", "org", "gov", "edu",
+];
+
+/// Randomness from which a label reads as generated.
+const RANDOM_LABEL: f32 = 0.7;
+
+/// Shortest label part that can read as random; too few characters carry
+/// no entropy worth weighing.
+const MIN_RANDOM_LABEL: usize = 5;
+
+/// Shortest dictionary word counted inside a label; shorter ones (`m`,
+/// `my`) only count as the whole label.
+const MIN_WORD: usize = 3;
+
+fn is_word(s: &str) -> bool {
+    SUBDOMAIN_WORDS.contains(&s) || BRANDS.contains(&s) || [LOGIN_KW, TRUST_KW, PAY_KW, FREE_KW, FRAUD_KW].iter().any(|l| l.contains(&s))
+}
+
+/// Characters of `label` covered by dictionary words (the best tiling,
+/// words not overlapping), and its letters and digits. A whole label that
+/// is a word counts whatever its length.
+fn covered(label: &str) -> (usize, usize) {
+    let chars: Vec<char> = label.chars().collect();
+    let total = chars.iter().filter(|c| c.is_ascii_alphanumeric()).count();
+    if is_word(label) { return (total, total); }
+    // best[i]: most characters of chars[..i] that words cover
+    let mut best = vec![0usize; chars.len() + 1];
+    for end in 1..=chars.len() {
+        best[end] = best[end - 1];
+        for start in 0..end.saturating_sub(MIN_WORD - 1) {
+            let w: String = chars[start..end].iter().collect();
+            if is_word(&w) { best[end] = best[end].max(best[start] + (end - start)); }
+        }
+    }
+    (best[chars.len()], total)
+}
+
+/// Normalized entropy of `part` (a label between hyphens) times the share
+/// of it no word covers: 0 for `www` or `login`, near 1 for `kjq83ad`,
+/// and 0 under [`MIN_RANDOM_LABEL`] characters.
+fn randomness(part: &str) -> f32 {
+    let n = part.chars().count();
+    if n < MIN_RANDOM_LABEL { return 0.0; }
+    let (words, total) = covered(part);
+    let uncovered = 1.0 - words as f32 / total.max(1) as f32;
+    (shannon_entropy(part) / (n as f32).log2()).min(1.0) * uncovered
+}
+
+/// `[subdomain_entropy, dictionary_coverage, label_randomness,
+/// random_label]` of the subdomain alone: Shannon entropy of its labels
+/// joined, the share of its letters and digits covered by ordinary words,
+/// the highest entropy-weighted randomness of any one label part, and
+/// whether one reads as generated. All 0 without a subdomain, for an
+/// IP host and for punycode labels.
+pub fn subdomain_features(url: &str) -> [f32; SUBDOMAIN_FEATURES] {
+    let p = parse_url(url);
+    if p.subdomain.is_empty() || has_ip(&p.host) { return [0.0; SUBDOMAIN_FEATURES]; }
+    let labels: Vec<&str> = p.subdomain.split('.').filter(|l| !l.is_empty()).collect();
+    if labels.iter().any(|l| l.starts_with("xn--")) { return [0.0; SUBDOMAIN_FEATURES]; }
+    let (mut words, mut total) = (0, 0);
+    let (mut highest, mut random) = (0.0f32, false);
+    for label in &labels {
+        let parts: Vec<(usize, usize)> = label.split('-').map(covered).collect();
+        words += parts.iter().map(|(w, _)| w).sum::<usize>();
+        total += parts.iter().map(|(_, t)| t).sum::<usize>();
+        for part in label.split('-') {
+            let r = randomness(part);
+            highest = highest.max(r);
+            random |= r >= RANDOM_LABEL;
+        }
+    }
+    let coverage = if total == 0 { 0.0 } else { words as f32 / total as f32 };
+    [shannon_entropy(&labels.concat()), coverage, highest, if random { 1.0 } else { 0.0 }]
+}
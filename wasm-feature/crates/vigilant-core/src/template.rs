@@ -0,0 +1,65 @@
+// wasm-feature/crates/vigilant-core/src/template.rs
+// Browser Vigilant — URL structure templates of the user's frequent sites
+// Learned locally (opt-in). A bank the user visits daily that suddenly serves
+// a page under /wp-content/ has most likely been compromised.
+
+use std::collections::HashMap;
+
+/// Sites remembered; the least recently visited is forgotten first.
+pub(crate) const MAX_SITES: usize = 256;
+
+/// Templates kept per site; once full, new ones are judged but not learned.
+pub(crate) const MAX_TEMPLATES: usize = 64;
+
+/// Visits before a site's templates are trusted enough to flag deviations.
+pub(crate) const MIN_VISITS: u32 = 20;
+
+/// Path segments making up a template.
+const TEMPLATE_SEGMENTS: usize = 2;
+
+/// Directories of off-the-shelf CMS / hosting stacks that phishing kits are
+/// dropped into on compromised sites.
+pub(crate) const KIT_DIRS: &[&str] = &["wp-content", "wp-admin", "wp-includes", "cgi-bin"];
+
+pub(crate) fn abstract_segment(seg: &str) -> String {
+    let seg = seg.to_lowercase();
+    if seg.chars().all(|c| c.is_ascii_digit()) { return "{n}".to_string(); }
+    let digits = seg.chars().filter(char::is_ascii_digit).count();
+    if seg.len() >= 16 && digits * 4 >= seg.len() { return "{id}".to_string(); }
+    match seg.rsplit_once('.') {
+        Some((_, ext)) if !ext.is_empty() && ext.len() <= 5 => format!("*.{}", ext),
+        _ => seg,
+    }
+}
+
+/// Structural template of a URL path: its first two segments with numbers,
+/// ids and file names abstracted (`/account/8812/summary` → `/account/{n}`).
+pub fn url_template(path: &str) -> String {
+    let segs: Vec<String> = path.split(['?', '#']).next().unwrap_or("")
+        .split('/').filter(|s| !s.is_empty()).take(TEMPLATE_SEGMENTS).map(abstract_segment).collect();
+    format!("/{}", segs.join("/"))
+}
+
+pub(crate) fn is_kit_path(template: &str) -> bool {
+    template.split('/').any(|s| KIT_DIRS.contains(&s))
+}
+
+/// What was learned about one site. Keys are template strings, or their
+/// hashes in hash-only privacy mode.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SiteTemplates {
+    pub(crate) visits:    u32,
+    pub(crate) templates: HashMap<String, u32>,
+}
+
+impl SiteTemplates {
+    /// Record a visit to `key`; returns how often it had been seen before.
+    pub(crate) fn learn(&mut self, key: String) -> u32 {
+        self.visits = self.visits.saturating_add(1);
+        let seen = self.templates.get(&key).copied().unwrap_or(0);
+        if seen > 0 || self.templates.len() < MAX_TEMPLATES { self.templates.insert(key, seen.saturating_add(1)); }
+        seen
+    }
+
+    pub(crate) fn trusted(&self) -> bool { self.visits >= MIN_VISITS }
+}
@@ -0,0 +1,37 @@
+// wasm-feature/crates/vigilant-core/src/time.rs
+// Browser Vigilant — features derived from a caller-supplied timestamp
+// Nothing here reads the system clock, so results stay reproducible.
+
+const MS_PER_HOUR: f64 = 3_600_000.0;
+const MS_PER_DAY:  f64 = 24.0 * MS_PER_HOUR;
+
+/// Values appended after the model features: is_weekend,
+/// local_night_hours, first_seen_bucket.
+pub const TIME_FEATURES: usize = 3;
+
+/// Upper bounds (ms) of the first-seen recency buckets 0–3; older is 4.
+const RECENCY_BOUNDS: [f64; 4] = [MS_PER_HOUR, MS_PER_DAY, 7.0 * MS_PER_DAY, 30.0 * MS_PER_DAY];
+
+/// Day of week for local epoch milliseconds, 0 = Sunday. 1970-01-01 was a Thursday.
+fn weekday(local_ms: f64) -> u32 { ((local_ms / MS_PER_DAY).floor() as i64 + 4).rem_euclid(7) as u32 }
+
+fn hour(local_ms: f64) -> u32 { (local_ms.rem_euclid(MS_PER_DAY) / MS_PER_HOUR) as u32 }
+
+pub fn is_weekend(local_ms: f64) -> bool { matches!(weekday(local_ms), 0 | 6) }
+
+/// 00:00–05:59 local time, when phishing campaigns see the least user scrutiny.
+pub fn is_night(local_ms: f64) -> bool { hour(local_ms) < 6 }
+
+/// 0 = first seen within the hour, 1 = today, 2 = this week, 3 = this month,
+/// 4 = older. A first sighting "in the future" (clock skew) counts as new.
+pub fn recency_bucket(age_ms: f64) -> u32 {
+    RECENCY_BOUNDS.iter().position(|&b| age_ms < b).unwrap_or(RECENCY_BOUNDS.len()) as u32
+}
+
+/// `[is_weekend, local_night_hours, first_seen_bucket]`. Timestamps are the
+/// caller's local wall clock as milliseconds since the Unix epoch (UTC
+/// milliseconds shifted by the time-zone offset).
+pub fn time_features(local_ms: f64, first_seen_ms: f64) -> [f32; TIME_FEATURES] {
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    [b(is_weekend(local_ms)), b(is_night(local_ms)), recency_bucket(local_ms - first_seen_ms) as f32]
+}
@@ -0,0 +1,60 @@
+// wasm-feature/crates/vigilant-core/src/timing.rs
+// Browser Vigilant — where a URL verdict spends its time. Hover scoring has
+// a latency budget on low-end devices, and each new subsystem spends part
+// of it; with a clock installed every URL report carries milliseconds per
+// stage, so a regression points at its stage instead of at the whole call.
+// The crate never reads a clock itself: the host passes one in (the wasm
+// build uses `performance.now`), and without one nothing is measured.
+
+use std::sync::{PoisonError, RwLock};
+use crate::json::Json;
+
+/// Milliseconds from an arbitrary origin.
+pub type Clock = fn() -> f64;
+
+static CLOCK: RwLock<Option<Clock>> = RwLock::new(None);
+
+/// Time the stages of every URL analysis in the process with `clock`;
+/// `None` turns timing off.
+pub fn set_timing_clock(clock: Option<Clock>) {
+    *CLOCK.write().unwrap_or_else(PoisonError::into_inner) = clock;
+}
+
+/// A timing clock is installed.
+pub fn timing_enabled() -> bool { CLOCK.read().unwrap_or_else(PoisonError::into_inner).is_some() }
+
+/// Stage durations of one call, in the order the stages ran.
+pub(crate) struct Stages {
+    clock: Option<Clock>,
+    last:  f64,
+    spans: Vec<(&'static str, f64)>,
+}
+
+impl Stages {
+    pub(crate) fn start() -> Stages {
+        let clock = *CLOCK.read().unwrap_or_else(PoisonError::into_inner);
+        Stages { clock, last: clock.map_or(0.0, |c| c()), spans: Vec::new() }
+    }
+
+    /// Close `stage`, which ran since the previous mark.
+    pub(crate) fn mark(&mut self, stage: &'static str) {
+        if let Some(c) = self.clock {
+            let now = c();
+            self.spans.push((stage, (now - self.last).max(0.0)));
+            self.last = now;
+        }
+    }
+
+    /// Restart from now without recording, after a call timed on its own.
+    pub(crate) fn skip(&mut self) {
+        if let Some(c) = self.clock { self.last = c(); }
+    }
+
+    pub(crate) fn into_spans(self) -> Vec<(&'static str, f64)> { self.spans }
+}
+
+/// `{ <stage>: ms, …, total }`.
+pub(crate) fn timings_json(spans: &[(&'static str, f64)]) -> Json {
+    let total: f64 = spans.iter().map(|(_, ms)| ms).sum();
+    spans.iter().fold(Json::obj(), |o, (stage, ms)| o.with(stage, *ms)).with("total", total)
+}
@@ -0,0 +1,174 @@
+// wasm-feature/crates/vigilant-core/src/translit.rs
+// Browser Vigilant — ISO 15919-style romanisation of Devanagari, Tamil and
+// Telugu, so Indic lure text can be matched against Latin keyword lists.
+
+/// Lure words as they come out of [`romanize`] (Hindi, Tamil, Telugu, and
+/// English loanwords written in Indic script).
+pub(crate) const ROMANIZED_LURES: &[&str] = &[
+    "khata", "satyapit", "turant", "band ho", "inam", "badhai", "kevaisi", "kyc", "pasavard", "blok", "apadet",
+    "login", "riphand", "kanakku", "caripar", "katavuccol", "utanatiyaka", "paricu", "putuppi", "ventane",
+    "bahumati", "dhrvikarinca",
+];
+
+#[derive(Clone, Copy)]
+enum Script { Devanagari, Tamil, Telugu }
+
+#[derive(Clone, Copy)]
+enum Glyph {
+    Consonant(&'static str),
+    Vowel(&'static str),
+    /// Dependent vowel sign replacing the inherent `a`.
+    Sign(&'static str),
+    /// Kills the inherent vowel.
+    Virama,
+    /// Anusvara, visarga and similar marks written after the syllable.
+    Mark(&'static str),
+    /// Nukta and zero-width joiners: no sound of their own.
+    Silent,
+}
+
+fn script(c: char) -> Option<Script> {
+    match c {
+        '\u{0900}'..='\u{097F}' => Some(Script::Devanagari),
+        '\u{0B80}'..='\u{0BFF}' => Some(Script::Tamil),
+        '\u{0C00}'..='\u{0C7F}' => Some(Script::Telugu),
+        _ => None,
+    }
+}
+
+fn devanagari(c: char) -> Option<Glyph> {
+    use Glyph::*;
+    Some(match c {
+        'अ' => Vowel("a"), 'आ' => Vowel("ā"), 'इ' => Vowel("i"), 'ई' => Vowel("ī"), 'उ' => Vowel("u"), 'ऊ' => Vowel("ū"),
+        'ऋ' => Vowel("r̥"), 'ए' => Vowel("e"), 'ऐ' => Vowel("ai"), 'ओ' => Vowel("o"), 'औ' => Vowel("au"), 'ऑ' => Vowel("ô"),
+        'ा' => Sign("ā"), 'ि' => Sign("i"), 'ी' => Sign("ī"), 'ु' => Sign("u"), 'ू' => Sign("ū"), 'ृ' => Sign("r̥"),
+        'े' => Sign("e"), 'ै' => Sign("ai"), 'ो' => Sign("o"), 'ौ' => Sign("au"), 'ॉ' => Sign("ô"),
+        'क' => Consonant("k"), 'ख' => Consonant("kh"), 'ग' => Consonant("g"), 'घ' => Consonant("gh"), 'ङ' => Consonant("ṅ"),
+        'च' => Consonant("c"), 'छ' => Consonant("ch"), 'ज' => Consonant("j"), 'झ' => Consonant("jh"), 'ञ' => Consonant("ñ"),
+        'ट' => Consonant("ṭ"), 'ठ' => Consonant("ṭh"), 'ड' => Consonant("ḍ"), 'ढ' => Consonant("ḍh"), 'ण' => Consonant("ṇ"),
+        'त' => Consonant("t"), 'थ' => Consonant("th"), 'द' => Consonant("d"), 'ध' => Consonant("dh"), 'न' => Consonant("n"),
+        'प' => Consonant("p"), 'फ' => Consonant("ph"), 'ब' => Consonant("b"), 'भ' => Consonant("bh"), 'म' => Consonant("m"),
+        'य' => Consonant("y"), 'र' => Consonant("r"), 'ल' => Consonant("l"), 'व' => Consonant("v"), 'श' => Consonant("ś"),
+        'ष' => Consonant("ṣ"), 'स' => Consonant("s"), 'ह' => Consonant("h"),
+        '\u{095C}' => Consonant("ṛ"), '\u{095D}' => Consonant("ṛh"), '\u{095E}' => Consonant("f"), '\u{095B}' => Consonant("z"),
+        '्' => Virama, 'ं' => Mark("ṁ"), 'ँ' => Mark("m̐"), 'ः' => Mark("ḥ"), '़' => Silent,
+        _ => return None,
+    })
+}
+
+fn tamil(c: char) -> Option<Glyph> {
+    use Glyph::*;
+    Some(match c {
+        'அ' => Vowel("a"), 'ஆ' => Vowel("ā"), 'இ' => Vowel("i"), 'ஈ' => Vowel("ī"), 'உ' => Vowel("u"), 'ஊ' => Vowel("ū"),
+        'எ' => Vowel("e"), 'ஏ' => Vowel("ē"), 'ஐ' => Vowel("ai"), 'ஒ' => Vowel("o"), 'ஓ' => Vowel("ō"), 'ஔ' => Vowel("au"),
+        'ா' => Sign("ā"), 'ி' => Sign("i"), 'ீ' => Sign("ī"), 'ு' => Sign("u"), 'ூ' => Sign("ū"),
+        'ெ' => Sign("e"), 'ே' => Sign("ē"), 'ை' => Sign("ai"), 'ொ' => Sign("o"), 'ோ' => Sign("ō"), 'ௌ' => Sign("au"),
+        'க' => Consonant("k"), 'ங' => Consonant("ṅ"), 'ச' => Consonant("c"), 'ஞ' => Consonant("ñ"), 'ட' => Consonant("ṭ"),
+        'ண' => Consonant("ṇ"), 'த' => Consonant("t"), 'ந' => Consonant("n"), 'ப' => Consonant("p"), 'ம' => Consonant("m"),
+        'ய' => Consonant("y"), 'ர' => Consonant("r"), 'ல' => Consonant("l"), 'வ' => Consonant("v"), 'ழ' => Consonant("ḻ"),
+        'ள' => Consonant("ḷ"), 'ற' => Consonant("ṟ"), 'ன' => Consonant("ṉ"), 'ஜ' => Consonant("j"), 'ஷ' => Consonant("ṣ"),
+        'ஸ' => Consonant("s"), 'ஹ' => Consonant("h"),
+        '்' => Virama, 'ஃ' => Mark("ḵ"),
+        _ => return None,
+    })
+}
+
+fn telugu(c: char) -> Option<Glyph> {
+    use Glyph::*;
+    Some(match c {
+        'అ' => Vowel("a"), 'ఆ' => Vowel("ā"), 'ఇ' => Vowel("i"), 'ఈ' => Vowel("ī"), 'ఉ' => Vowel("u"), 'ఊ' => Vowel("ū"),
+        'ఋ' => Vowel("r̥"), 'ఎ' => Vowel("e"), 'ఏ' => Vowel("ē"), 'ఐ' => Vowel("ai"), 'ఒ' => Vowel("o"), 'ఓ' => Vowel("ō"),
+        'ఔ' => Vowel("au"),
+        'ా' => Sign("ā"), 'ి' => Sign("i"), 'ీ' => Sign("ī"), 'ు' => Sign("u"), 'ూ' => Sign("ū"), 'ృ' => Sign("r̥"),
+        'ె' => Sign("e"), 'ే' => Sign("ē"), 'ై' => Sign("ai"), 'ొ' => Sign("o"), 'ో' => Sign("ō"), 'ౌ' => Sign("au"),
+        'క' => Consonant("k"), 'ఖ' => Consonant("kh"), 'గ' => Consonant("g"), 'ఘ' => Consonant("gh"), 'ఙ' => Consonant("ṅ"),
+        'చ' => Consonant("c"), 'ఛ' => Consonant("ch"), 'జ' => Consonant("j"), 'ఝ' => Consonant("jh"), 'ఞ' => Consonant("ñ"),
+        'ట' => Consonant("ṭ"), 'ఠ' => Consonant("ṭh"), 'డ' => Consonant("ḍ"), 'ఢ' => Consonant("ḍh"), 'ణ' => Consonant("ṇ"),
+        'త' => Consonant("t"), 'థ' => Consonant("th"), 'ద' => Consonant("d"), 'ధ' => Consonant("dh"), 'న' => Consonant("n"),
+        'ప' => Consonant("p"), 'ఫ' => Consonant("ph"), 'బ' => Consonant("b"), 'భ' => Consonant("bh"), 'మ' => Consonant("m"),
+        'య' => Consonant("y"), 'ర' => Consonant("r"), 'ఱ' => Consonant("ṟ"), 'ల' => Consonant("l"), 'ళ' => Consonant("ḷ"),
+        'వ' => Consonant("v"), 'శ' => Consonant("ś"), 'ష' => Consonant("ṣ"), 'స' => Consonant("s"), 'హ' => Consonant("h"),
+        '్' => Virama, 'ం' => Mark("ṁ"), 'ః' => Mark("ḥ"),
+        _ => return None,
+    })
+}
+
+fn glyph(c: char) -> Option<(Script, Glyph)> {
+    if matches!(c, '\u{200C}' | '\u{200D}') { return Some((Script::Devanagari, Glyph::Silent)); }
+    let s = script(c)?;
+    let g = match s { Script::Devanagari => devanagari(c), Script::Tamil => tamil(c), Script::Telugu => telugu(c) }?;
+    Some((s, g))
+}
+
+/// Romanise Devanagari, Tamil and Telugu runs of `text` in ISO 15919 style
+/// (`खाता` → `khātā`); everything else passes through. Hindi's word-final
+/// inherent vowel is dropped as it is in speech (`सत्यापित` → `satyāpit`).
+pub fn transliterate(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    // consonant still waiting to learn whether it carries the inherent vowel
+    let mut pending: Option<(Script, &str)> = None;
+    let flush = |out: &mut String, pending: &mut Option<(Script, &str)>, word_end: bool| {
+        if let Some((s, c)) = pending.take() {
+            out.push_str(c);
+            if !(word_end && matches!(s, Script::Devanagari)) { out.push('a'); }
+        }
+    };
+    for c in text.chars() {
+        match glyph(c) {
+            Some((s, Glyph::Consonant(lat))) => { flush(&mut out, &mut pending, false); pending = Some((s, lat)); }
+            Some((_, Glyph::Vowel(lat))) => { flush(&mut out, &mut pending, false); out.push_str(lat); }
+            Some((_, Glyph::Sign(lat))) => {
+                if let Some((_, p)) = pending.take() { out.push_str(p); }
+                out.push_str(lat);
+            }
+            Some((_, Glyph::Virama)) => { if let Some((_, p)) = pending.take() { out.push_str(p); } }
+            Some((_, Glyph::Mark(lat))) => { flush(&mut out, &mut pending, false); out.push_str(lat); }
+            Some((_, Glyph::Silent)) => {}
+            None => { flush(&mut out, &mut pending, true); out.push(c); }
+        }
+    }
+    flush(&mut out, &mut pending, true);
+    out
+}
+
+/// [`transliterate`] folded to plain ASCII letters, the way Indic words are
+/// usually typed in Latin script (`ā` → `a`, anusvara → `n`).
+pub(crate) fn romanize(text: &str) -> String {
+    transliterate(text).chars().filter_map(|c| match c {
+        'ā' => Some('a'), 'ī' => Some('i'), 'ū' => Some('u'), 'ē' => Some('e'), 'ō' | 'ô' => Some('o'),
+        'ṅ' | 'ñ' | 'ṇ' | 'ṉ' | 'ṁ' => Some('n'), 'ṭ' => Some('t'), 'ḍ' => Some('d'), 'ś' | 'ṣ' => Some('s'),
+        'ḥ' => Some('h'), 'ṛ' | 'ṟ' => Some('r'), 'ḻ' | 'ḷ' => Some('l'), 'ḵ' => Some('k'),
+        '\u{0300}'..='\u{036F}' => None,
+        c => Some(c),
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inherent_vowels_follow_each_script() {
+        assert_eq!(transliterate("खाता"), "khātā");
+        // Hindi drops the word-final `a`, Tamil and Telugu keep it
+        assert_eq!(transliterate("सत्यापित"), "satyāpit");
+        assert_eq!(transliterate("బహుమతి"), "bahumati");
+        assert_eq!(transliterate("கணக்கு"), "kaṇakku");
+    }
+
+    #[test]
+    fn romanized_lures_come_out_as_listed() {
+        for (native, lure) in [("तुरंत", "turant"), ("சரிபார்", "caripar"), ("ధృవీకరించండి", "dhrvikarinca")] {
+            let latin = romanize(native);
+            assert!(latin.starts_with(lure), "{} -> {}", native, latin);
+            assert!(ROMANIZED_LURES.contains(&lure));
+        }
+    }
+
+    #[test]
+    fn other_text_passes_through() {
+        assert_eq!(romanize("Hello खाता!"), "Hello khata!");
+        assert_eq!(transliterate("paypal.com ✓"), "paypal.com ✓");
+        assert!(ROMANIZED_LURES.iter().all(|l| l.bytes().all(|b| b.is_ascii_lowercase() || b == b' ')));
+    }
+}
@@ -0,0 +1,273 @@
+// wasm-feature/crates/vigilant-core/src/trees.rs
+// Browser Vigilant — the URL model evaluated in-crate. A model container of
+// kind "trees" carries a tree ensemble (the random forest `train.py` fits,
+// or a boosted one), scored here on the vector this build extracts, so there
+// is no second inference path in JS and no way for the two to disagree on
+// feature order.
+//
+// Payload, little-endian:
+//   aggregation u8 (0 sum of leaf logits, 1 mean of leaf probabilities),
+//   base f32 (added to the sum; unused by mean), tree count u32,
+//   then per tree: node count u32 and that many nodes of
+//   feature u16 (0xffff for a leaf), threshold f32 (a leaf's value), left u32, right u32.
+// Node 0 is the root; a split goes left when `x <= threshold` (sklearn's
+// rule) and both children come after it, so every walk ends at a leaf.
+
+use crate::model::{parse_model, ModelInfo};
+use crate::onnx::onnx_payload;
+use crate::personal::sigmoid;
+use crate::types::{err, SchemaError};
+use crate::{extract_features, MODEL_FEATURES};
+
+const LEAF: u16 = u16::MAX;
+
+/// Most nodes accepted across all trees.
+const MAX_NODES: usize = 4 << 20;
+
+const NODE_BYTES: usize = 14;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Node {
+    feature:   u16,
+    threshold: f32,
+    left:      u32,
+    right:     u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Gradient boosting: leaves hold logits, summed onto `base`.
+    Sum,
+    /// Random forest: leaves hold probabilities, averaged.
+    Mean,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeEnsemble {
+    aggregation: Aggregation,
+    base:        f32,
+    /// Each tree's nodes, root first.
+    trees:       Vec<Vec<Node>>,
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], SchemaError> {
+        if self.0.len() < N { return Err(err("", "tree payload is truncated")); }
+        let (head, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(head.try_into().unwrap_or([0; N]))
+    }
+
+    fn u32(&mut self) -> Result<u32, SchemaError> { self.take().map(u32::from_le_bytes) }
+
+    fn f32(&mut self) -> Result<f32, SchemaError> { self.take().map(f32::from_le_bytes) }
+}
+
+impl TreeEnsemble {
+    /// Parse a container payload. Fails on a malformed ensemble and on a
+    /// split over a feature past `feature_count`.
+    pub fn parse(payload: &[u8], feature_count: usize) -> Result<TreeEnsemble, SchemaError> {
+        let mut r = Reader(payload);
+        let aggregation = match r.take::<1>()?[0] {
+            0 => Aggregation::Sum,
+            1 => Aggregation::Mean,
+            n => return Err(err("", &format!("unknown tree aggregation {}", n))),
+        };
+        let base = r.f32()?;
+        let count = r.u32()? as usize;
+        if count == 0 { return Err(err("", "tree ensemble has no trees")); }
+        let mut trees = Vec::with_capacity(count.min(payload.len()));
+        let mut total = 0usize;
+        for t in 0..count {
+            let n = r.u32()? as usize;
+            total += n;
+            if n == 0 || total > MAX_NODES || r.0.len() < n * NODE_BYTES {
+                return Err(err("", &format!("tree {} is empty, truncated or too large", t)));
+            }
+            let mut nodes = Vec::with_capacity(n);
+            for i in 0..n {
+                let feature = u16::from_le_bytes(r.take()?);
+                let node = Node { feature, threshold: r.f32()?, left: r.u32()?, right: r.u32()? };
+                if !node.threshold.is_finite() { return Err(err("", &format!("tree {} node {} has a non-finite value", t, i))); }
+                if feature != LEAF {
+                    if feature as usize >= feature_count {
+                        return Err(err("", &format!("tree {} splits on feature {} of {}", t, feature, feature_count)));
+                    }
+                    let child = |c: u32| c as usize > i && (c as usize) < n;
+                    if !child(node.left) || !child(node.right) {
+                        return Err(err("", &format!("tree {} node {} points outside the tree", t, i)));
+                    }
+                }
+                nodes.push(node);
+            }
+            trees.push(nodes);
+        }
+        if !r.0.is_empty() { return Err(err("", "trailing bytes after the last tree")); }
+        Ok(TreeEnsemble { aggregation, base, trees })
+    }
+
+    pub fn aggregation(&self) -> Aggregation { self.aggregation }
+
+    pub fn trees(&self) -> usize { self.trees.len() }
+
+    pub fn nodes(&self) -> usize { self.trees.iter().map(Vec::len).sum() }
+
+    /// Splits on each of the first `feature_count` features, across all
+    /// trees. The payload carries no sample counts, so this is the only
+    /// importance it supports (gain would need the training impurities).
+    pub fn split_counts(&self, feature_count: usize) -> Vec<usize> {
+        let mut counts = vec![0; feature_count];
+        for n in self.trees.iter().flatten().filter(|n| n.feature != LEAF) {
+            if let Some(c) = counts.get_mut(n.feature as usize) { *c += 1; }
+        }
+        counts
+    }
+
+    fn leaf(nodes: &[Node], features: &[f32]) -> f32 {
+        let mut i = 0;
+        loop {
+            let n = nodes[i];
+            if n.feature == LEAF { return n.threshold; }
+            let x = features.get(n.feature as usize).copied().unwrap_or(0.0);
+            i = if x <= n.threshold { n.left } else { n.right } as usize;
+        }
+    }
+
+    /// Uncalibrated probability for feature vector `features`.
+    pub fn predict(&self, features: &[f32]) -> f32 {
+        let total: f32 = self.trees.iter().map(|t| TreeEnsemble::leaf(t, features)).sum();
+        match self.aggregation {
+            Aggregation::Sum => sigmoid(self.base + total),
+            Aggregation::Mean => (total / self.trees.len() as f32).clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// A loaded "trees" model: its container header and the ensemble.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeModel {
+    pub info:     ModelInfo,
+    pub ensemble: TreeEnsemble,
+}
+
+impl TreeModel {
+    /// Load a model container of kind "trees", or of kind "onnx" holding a
+    /// tree ensemble (see [`parse_model`] and [`crate::load_onnx_model`]).
+    pub fn parse(blob: &[u8]) -> Result<TreeModel, SchemaError> {
+        let (info, payload) = parse_model(blob)?;
+        match info.kind.as_str() {
+            "trees" => TreeModel::from_payload(info, payload),
+            "onnx" => { let payload = onnx_payload(payload, info.feature_count)?; TreeModel::from_payload(info, &payload) }
+            kind => Err(err("kind", &format!("a {} model can't be evaluated in-crate", kind))),
+        }
+    }
+
+    pub(crate) fn from_payload(info: ModelInfo, payload: &[u8]) -> Result<TreeModel, SchemaError> {
+        let ensemble = TreeEnsemble::parse(payload, info.feature_count)?;
+        Ok(TreeModel { info, ensemble })
+    }
+
+    /// Calibrated phishing probability for a [`MODEL_FEATURES`] vector.
+    pub fn score(&self, features: &[f32]) -> f32 {
+        self.info.calibration.apply(self.ensemble.predict(&features[..features.len().min(MODEL_FEATURES)]))
+    }
+
+    /// [`TreeEnsemble::split_counts`] over the model's features.
+    pub fn split_counts(&self) -> Vec<usize> { self.ensemble.split_counts(self.info.feature_count) }
+
+    /// [`TreeModel::score`] of `url`'s features.
+    pub fn score_url(&self, url: &str) -> f32 { self.score(&extract_features(url)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::Json;
+    use crate::model::Calibration;
+    use crate::{feature_importances, set_importance_model};
+
+    fn node(feature: u16, threshold: f32, left: u32, right: u32) -> Vec<u8> {
+        [&feature.to_le_bytes()[..], &threshold.to_le_bytes(), &left.to_le_bytes(), &right.to_le_bytes()].concat()
+    }
+
+    /// A mean forest of two trees: one splitting on 21 then 12, one on 21.
+    fn forest() -> Vec<u8> {
+        let mut p = vec![1];
+        p.extend(0f32.to_le_bytes());
+        p.extend(2u32.to_le_bytes());
+        p.extend(5u32.to_le_bytes());
+        for n in [node(21, 0.5, 1, 2), node(LEAF, 0.1, 0, 0), node(12, 0.5, 3, 4), node(LEAF, 0.6, 0, 0), node(LEAF, 0.9, 0, 0)] { p.extend(n); }
+        p.extend(3u32.to_le_bytes());
+        for n in [node(21, 0.5, 1, 2), node(LEAF, 0.2, 0, 0), node(LEAF, 0.8, 0, 0)] { p.extend(n); }
+        p
+    }
+
+    fn model() -> TreeModel {
+        let info = ModelInfo {
+            container_version: 1, kind: "trees".into(), trained_at: String::new(), dataset_hash: String::new(),
+            feature_version: crate::model::FEATURE_VERSION, feature_count: MODEL_FEATURES, feature_schema: String::new(),
+            calibration: Calibration::default(), license: String::new(), payload_sha256: String::new(), payload_bytes: 0,
+        };
+        TreeModel::from_payload(info, &forest()).unwrap()
+    }
+
+    fn features(f21: f32, f12: f32) -> Vec<f32> {
+        let mut f = vec![0.0; MODEL_FEATURES];
+        (f[21], f[12]) = (f21, f12);
+        f
+    }
+
+    #[test]
+    fn mean_averages_one_leaf_per_tree() {
+        let forest = TreeEnsemble::parse(&forest(), MODEL_FEATURES).unwrap();
+        assert_eq!((forest.aggregation(), forest.trees(), forest.nodes()), (Aggregation::Mean, 2, 8));
+        assert!((forest.predict(&features(0.0, 0.0)) - 0.15).abs() < 1e-6);
+        assert!((forest.predict(&features(1.0, 0.0)) - 0.7).abs() < 1e-6);
+        assert!((forest.predict(&features(1.0, 1.0)) - 0.85).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sum_adds_leaf_logits_to_the_base() {
+        let mut p = forest();
+        p[0] = 0;
+        p[1..5].copy_from_slice(&(-1f32).to_le_bytes());
+        let boosted = TreeEnsemble::parse(&p, MODEL_FEATURES).unwrap();
+        assert_eq!(boosted.aggregation(), Aggregation::Sum);
+        assert!((boosted.predict(&features(0.0, 0.0)) - sigmoid(-0.7)).abs() < 1e-6);
+        assert!((boosted.predict(&features(1.0, 1.0)) - sigmoid(0.7)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn a_value_equal_to_the_threshold_goes_left() {
+        let forest = TreeEnsemble::parse(&forest(), MODEL_FEATURES).unwrap();
+        assert_eq!(forest.predict(&features(0.5, 0.0)), forest.predict(&features(0.0, 0.0)));
+        assert_eq!(forest.predict(&features(1.0, 0.5)), forest.predict(&features(1.0, 0.0)));
+        assert!(forest.predict(&features(0.5 + f32::EPSILON, 0.0)) > forest.predict(&features(0.5, 0.0)));
+    }
+
+    #[test]
+    fn split_counts_cover_every_tree() {
+        let counts = model().split_counts();
+        assert_eq!(counts.len(), MODEL_FEATURES);
+        assert_eq!((counts[21], counts[12], counts.iter().sum::<usize>()), (2, 1, 3));
+    }
+
+    #[test]
+    fn importances_follow_the_installed_model() {
+        let heuristic = feature_importances();
+        assert_eq!(heuristic.str_field("kind"), "heuristic");
+
+        set_importance_model(Some(&model()));
+        let trees = feature_importances();
+        set_importance_model(None);
+        assert_eq!((trees.str_field("model"), trees.str_field("kind")), ("trees", "split_count"));
+        let features = trees.get("features").and_then(Json::as_array).unwrap();
+        let ranked: Vec<(Option<f64>, Option<&str>)> = features.iter().map(|f| (f.get("index").and_then(Json::as_f64), f.get("name").and_then(Json::as_str))).collect();
+        assert_eq!(ranked, [(Some(21.0), Some("brand_spoof_flag")), (Some(12.0), Some("ip_in_url"))]);
+        assert_eq!(features[0].get("importance").and_then(Json::as_f64).map(|v| (v * 3.0).round()), Some(2.0));
+
+        assert_eq!(feature_importances(), heuristic);
+    }
+}
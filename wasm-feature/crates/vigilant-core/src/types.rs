@@ -0,0 +1,463 @@
+// wasm-feature/crates/vigilant-core/src/types.rs
+// Browser Vigilant — typed, versioned inputs and outputs for the analyze APIs
+// Mapped to and from `Json` by hand; malformed input fails with the path of the
+// offending field instead of silently reading as empty.
+//
+// Not serde: vigilant-core has no dependencies, and the wasm, FFI, WASI and
+// server front ends all read these types from the same `Json` value, so
+// serde and `serde-wasm-bindgen` would be its first dependencies and add to
+// the wasm bundle for what `Fields` does in a page. `types.d.ts` is the
+// TypeScript side of the same shapes.
+
+use std::fmt;
+use crate::analyzer::{Caps, KeywordPack, Profile};
+use crate::json::Json;
+use crate::state::FEATURE_CACHE_ENTRIES;
+use crate::verdict::Verdict;
+
+/// Bumped whenever a field changes meaning or a required field is added.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaError {
+    pub path:    String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() { write!(f, "{}", self.message) } else { write!(f, "{}: {}", self.path, self.message) }
+    }
+}
+
+pub trait FromJson: Sized {
+    fn from_json(v: &Json, path: &str) -> Result<Self, SchemaError>;
+
+    /// Parse a JSON document into `Self`.
+    fn from_json_str(src: &str) -> Result<Self, SchemaError> {
+        let v = Json::parse(src).map_err(|e| SchemaError { path: String::new(), message: e })?;
+        Self::from_json(&v, "")
+    }
+}
+
+pub trait ToJson {
+    fn to_json(&self) -> Json;
+}
+
+// ── Field access with error paths ────────────────────────────────────────────
+
+pub(crate) struct Fields<'a> {
+    obj:  &'a Json,
+    path: &'a str,
+}
+
+impl<'a> Fields<'a> {
+    pub(crate) fn new(v: &'a Json, path: &'a str) -> Result<Fields<'a>, SchemaError> {
+        if !matches!(v, Json::Obj(_)) { return Err(err(path, "expected object")); }
+        Ok(Fields { obj: v, path })
+    }
+
+    pub(crate) fn at(&self, key: &str) -> String {
+        if self.path.is_empty() { key.to_string() } else { format!("{}.{}", self.path, key) }
+    }
+
+    fn present(&self, key: &str) -> Option<&'a Json> {
+        self.obj.get(key).filter(|v| !matches!(v, Json::Null))
+    }
+
+    pub(crate) fn string(&self, key: &str) -> Result<String, SchemaError> {
+        self.opt_string(key)?.ok_or_else(|| err(&self.at(key), "required string is missing"))
+    }
+
+    pub(crate) fn opt_string(&self, key: &str) -> Result<Option<String>, SchemaError> {
+        match self.present(key) {
+            None => Ok(None),
+            Some(v) => v.as_str().map(|s| Some(s.to_string())).ok_or_else(|| err(&self.at(key), "expected string")),
+        }
+    }
+
+    pub(crate) fn bool_or(&self, key: &str, default: bool) -> Result<bool, SchemaError> {
+        match self.present(key) {
+            None => Ok(default),
+            Some(v) => v.as_bool().ok_or_else(|| err(&self.at(key), "expected boolean")),
+        }
+    }
+
+    pub(crate) fn opt_num(&self, key: &str) -> Result<Option<f64>, SchemaError> {
+        match self.present(key) {
+            None => Ok(None),
+            Some(v) => v.as_f64().map(Some).ok_or_else(|| err(&self.at(key), "expected number")),
+        }
+    }
+
+    pub(crate) fn list<T, F: Fn(&Json, &str) -> Result<T, SchemaError>>(&self, key: &str, item: F) -> Result<Vec<T>, SchemaError> {
+        let Some(v) = self.present(key) else { return Ok(Vec::new()) };
+        let arr = v.as_array().ok_or_else(|| err(&self.at(key), "expected array"))?;
+        arr.iter().enumerate().map(|(i, x)| item(x, &format!("{}[{}]", self.at(key), i))).collect()
+    }
+
+    /// Object of `name: value` entries, in document order.
+    pub(crate) fn map<T, F: Fn(&Json, &str) -> Result<T, SchemaError>>(&self, key: &str, item: F) -> Result<Vec<(String, T)>, SchemaError> {
+        match self.present(key) {
+            None => Ok(Vec::new()),
+            Some(Json::Obj(entries)) => entries.iter().map(|(k, x)| Ok((k.clone(), item(x, &format!("{}.{}", self.at(key), k))?))).collect(),
+            Some(_) => Err(err(&self.at(key), "expected object")),
+        }
+    }
+
+    /// `None` when absent or null, else `item` of the value.
+    pub(crate) fn opt<T, F: Fn(&Json, &str) -> Result<T, SchemaError>>(&self, key: &str, item: F) -> Result<Option<T>, SchemaError> {
+        self.present(key).map(|v| item(v, &self.at(key))).transpose()
+    }
+
+    pub(crate) fn strings(&self, key: &str) -> Result<Vec<String>, SchemaError> {
+        self.list(key, |x, p| x.as_str().map(str::to_string).ok_or_else(|| err(p, "expected string")))
+    }
+
+    pub(crate) fn opt_count(&self, key: &str) -> Result<Option<usize>, SchemaError> {
+        match self.opt_num(key)? {
+            None => Ok(None),
+            Some(n) if n >= 0.0 && n.fract() == 0.0 => Ok(Some(n as usize)),
+            Some(_) => Err(err(&self.at(key), "expected a non-negative integer")),
+        }
+    }
+
+    /// Missing means "current"; anything newer than we understand is refused.
+    pub(crate) fn schema_version(&self) -> Result<u32, SchemaError> {
+        let v = self.opt_num("schema_version")?.unwrap_or(SCHEMA_VERSION as f64);
+        if v.fract() != 0.0 || v < 1.0 { return Err(err(&self.at("schema_version"), "expected a positive integer")); }
+        if v > SCHEMA_VERSION as f64 {
+            return Err(err(&self.at("schema_version"), &format!("unsupported version {} (this build reads up to {})", v, SCHEMA_VERSION)));
+        }
+        Ok(v as u32)
+    }
+}
+
+pub(crate) fn err(path: &str, message: &str) -> SchemaError {
+    SchemaError { path: path.to_string(), message: message.to_string() }
+}
+
+// ── Inputs ───────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FormSnapshot {
+    pub action:       String,
+    pub method:       String,
+    pub has_password: bool,
+}
+
+/// What the content script saw on a page.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PageSnapshot {
+    pub schema_version: u32,
+    pub url:            String,
+    pub title:          String,
+    pub text:           String,
+    pub forms:          Vec<FormSnapshot>,
+    pub links:          Vec<String>,
+    /// Origin the password manager would fill here, if any.
+    pub saved_origin:   Option<String>,
+    /// Inline `<script>` bodies and event-handler attributes.
+    pub scripts:        Vec<String>,
+    /// Enforced Content-Security-Policy (header or `<meta>`); `Some("")` when
+    /// the page had none, `None` when the host didn't look.
+    pub csp:            Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DownloadEvent {
+    pub schema_version:      u32,
+    pub filename:            String,
+    pub content_disposition: Option<String>,
+    pub mime:                String,
+    pub initiating_origin:   String,
+    pub user_gesture:        bool,
+}
+
+/// Leaf certificate summary as exposed by the browser's security info.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CertInfo {
+    pub schema_version: u32,
+    pub host:           String,
+    pub issuer:         String,
+    pub subject:        String,
+    /// Days since `notBefore`.
+    pub age_days:       f64,
+    /// `notAfter - notBefore` in days.
+    pub validity_days:  f64,
+    pub self_signed:    bool,
+    pub san:            Vec<String>,
+}
+
+/// What the host knows about a site from outside the module: WHOIS/RDAP,
+/// a ranking list, the certificate. Every field is optional; `None` is
+/// unknown, which is not the same as new or unranked.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HostSignals {
+    pub schema_version: u32,
+    /// Days since the registrable domain was registered.
+    pub domain_age_days: Option<f64>,
+    /// Position in a popularity list such as Tranco, 1 for the top site.
+    pub rank:            Option<f64>,
+    /// Days since the leaf certificate's `notBefore`.
+    pub cert_age_days:   Option<f64>,
+}
+
+/// Everything collected about one navigation. Only `url` is required; a
+/// `None` channel wasn't collected, which is not the same as clean.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Evidence {
+    pub schema_version: u32,
+    pub url:            String,
+    pub page:           Option<PageSnapshot>,
+    /// Response headers, `(name, value)` in the order received.
+    pub headers:        Option<Vec<(String, String)>>,
+    pub cert:           Option<CertInfo>,
+}
+
+impl FromJson for FormSnapshot {
+    fn from_json(v: &Json, path: &str) -> Result<FormSnapshot, SchemaError> {
+        let f = Fields::new(v, path)?;
+        Ok(FormSnapshot {
+            action:       f.opt_string("action")?.unwrap_or_default(),
+            method:       f.opt_string("method")?.unwrap_or_else(|| "get".to_string()).to_lowercase(),
+            has_password: f.bool_or("has_password", false)?,
+        })
+    }
+}
+
+impl FromJson for PageSnapshot {
+    fn from_json(v: &Json, path: &str) -> Result<PageSnapshot, SchemaError> {
+        let f = Fields::new(v, path)?;
+        Ok(PageSnapshot {
+            schema_version: f.schema_version()?,
+            url:            f.string("url")?,
+            title:          f.opt_string("title")?.unwrap_or_default(),
+            text:           f.opt_string("text")?.unwrap_or_default(),
+            forms:          f.list("forms", FormSnapshot::from_json)?,
+            links:          f.strings("links")?,
+            saved_origin:   f.opt_string("saved_origin")?,
+            scripts:        f.strings("scripts")?,
+            csp:            f.opt_string("csp")?,
+        })
+    }
+}
+
+impl FromJson for DownloadEvent {
+    fn from_json(v: &Json, path: &str) -> Result<DownloadEvent, SchemaError> {
+        let f = Fields::new(v, path)?;
+        let filename = f.opt_string("filename")?.unwrap_or_default();
+        let content_disposition = f.opt_string("content_disposition")?;
+        if filename.is_empty() && content_disposition.is_none() {
+            return Err(err(&f.at("filename"), "filename or content_disposition is required"));
+        }
+        Ok(DownloadEvent {
+            schema_version: f.schema_version()?,
+            filename,
+            content_disposition,
+            mime:              f.opt_string("mime")?.unwrap_or_default(),
+            initiating_origin: f.opt_string("initiating_origin")?.unwrap_or_default(),
+            user_gesture:      f.bool_or("user_gesture", false)?,
+        })
+    }
+}
+
+impl FromJson for CertInfo {
+    fn from_json(v: &Json, path: &str) -> Result<CertInfo, SchemaError> {
+        let f = Fields::new(v, path)?;
+        Ok(CertInfo {
+            schema_version: f.schema_version()?,
+            host:           f.string("host")?,
+            issuer:         f.opt_string("issuer")?.unwrap_or_default(),
+            subject:        f.opt_string("subject")?.unwrap_or_default(),
+            age_days:       f.opt_num("age_days")?.ok_or_else(|| err(&f.at("age_days"), "required number is missing"))?,
+            validity_days:  f.opt_num("validity_days")?.unwrap_or(0.0),
+            self_signed:    f.bool_or("self_signed", false)?,
+            san:            f.strings("san")?,
+        })
+    }
+}
+
+impl FromJson for HostSignals {
+    fn from_json(v: &Json, path: &str) -> Result<HostSignals, SchemaError> {
+        let f = Fields::new(v, path)?;
+        let non_negative = |key: &str| match f.opt_num(key)? {
+            Some(n) if n < 0.0 => Err(err(&f.at(key), "expected a non-negative number")),
+            n => Ok(n),
+        };
+        Ok(HostSignals {
+            schema_version:  f.schema_version()?,
+            domain_age_days: non_negative("domain_age_days")?,
+            rank:            non_negative("rank")?,
+            cert_age_days:   non_negative("cert_age_days")?,
+        })
+    }
+}
+
+impl FromJson for Evidence {
+    fn from_json(v: &Json, path: &str) -> Result<Evidence, SchemaError> {
+        let f = Fields::new(v, path)?;
+        let url = f.string("url")?;
+        // the snapshot is of this navigation's page unless it says otherwise
+        let page = f.opt("page", |x, p| match x.get("url") {
+            None => PageSnapshot::from_json(&x.clone().with("url", url.as_str()), p),
+            Some(_) => PageSnapshot::from_json(x, p),
+        })?;
+        let headers = f.opt("headers", |x, p| match x {
+            Json::Obj(entries) => entries.iter()
+                .map(|(k, h)| h.as_str().map(|s| (k.clone(), s.to_string())).ok_or_else(|| err(&format!("{}.{}", p, k), "expected string")))
+                .collect(),
+            _ => Err(err(p, "expected object")),
+        })?;
+        Ok(Evidence {
+            schema_version: f.schema_version()?,
+            url,
+            page,
+            headers,
+            cert: f.opt("cert", CertInfo::from_json)?,
+        })
+    }
+}
+
+/// Settings for one isolated context (e.g. a managed work profile).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextConfig {
+    pub schema_version:        u32,
+    pub profile:               Profile,
+    pub brands:                Vec<String>,
+    pub keywords:              Vec<String>,
+    pub keyword_packs:         Vec<KeywordPack>,
+    pub psl:                   bool,
+    pub caps:                  Caps,
+    pub feature_cache_entries: usize,
+    /// Hosts (and their subdomains) always allowed / always blocked.
+    pub allow:                 Vec<String>,
+    pub block:                 Vec<String>,
+    /// Enables hash-only privacy mode: identifiers are kept as
+    /// `sha256(salt ‖ id)` only.
+    pub privacy_salt:          Option<String>,
+    /// Learn the URL templates of frequent sites and flag departures from them.
+    pub learn_templates:       bool,
+}
+
+impl Default for ContextConfig {
+    fn default() -> ContextConfig {
+        ContextConfig {
+            schema_version: SCHEMA_VERSION, profile: Profile::default(), brands: Vec::new(), keywords: Vec::new(),
+            keyword_packs: Vec::new(), psl: true, caps: Caps::default(), feature_cache_entries: FEATURE_CACHE_ENTRIES,
+            allow: Vec::new(), block: Vec::new(), privacy_salt: None, learn_templates: false,
+        }
+    }
+}
+
+impl FromJson for ContextConfig {
+    fn from_json(v: &Json, path: &str) -> Result<ContextConfig, SchemaError> {
+        let f = Fields::new(v, path)?;
+        let d = ContextConfig::default();
+        let profile = match f.opt_string("profile")? {
+            None => d.profile,
+            Some(p) => Profile::parse(&p).ok_or_else(|| err(&f.at("profile"), &format!("unknown profile \"{}\"", p)))?,
+        };
+        let keyword_packs = f.list("keyword_packs", |x, p| {
+            let name = x.as_str().ok_or_else(|| err(p, "expected string"))?;
+            KeywordPack::parse(name).ok_or_else(|| err(p, &format!("unknown keyword pack \"{}\"", name)))
+        })?;
+        Ok(ContextConfig {
+            schema_version: f.schema_version()?,
+            profile,
+            brands: f.strings("brands")?,
+            keywords: f.strings("keywords")?,
+            keyword_packs,
+            psl: f.bool_or("psl", d.psl)?,
+            caps: Caps {
+                max_input_bytes: f.opt_count("max_input_bytes")?.unwrap_or(d.caps.max_input_bytes),
+                max_text_bytes:  f.opt_count("max_text_bytes")?.unwrap_or(d.caps.max_text_bytes),
+            },
+            feature_cache_entries: f.opt_count("feature_cache_entries")?.unwrap_or(d.feature_cache_entries),
+            allow: f.strings("allow")?,
+            block: f.strings("block")?,
+            privacy_salt: match f.opt_string("privacy_salt")? {
+                Some(s) if s.is_empty() => return Err(err(&f.at("privacy_salt"), "salt must not be empty")),
+                s => s,
+            },
+            learn_templates: f.bool_or("learn_templates", d.learn_templates)?,
+        })
+    }
+}
+
+// ── Output ───────────────────────────────────────────────────────────────────
+
+/// Uniform envelope for the typed entry points: the common fields up front,
+/// analyzer-specific findings under `details`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub schema_version: u32,
+    pub kind:           String,
+    pub score:          f32,
+    pub verdict:        Verdict,
+    pub flags:          Vec<String>,
+    pub details:        Json,
+}
+
+impl Report {
+    pub fn new(kind: &str, score: f32, verdict: Verdict, flags: Vec<String>, details: Json) -> Report {
+        Report { schema_version: SCHEMA_VERSION, kind: kind.to_string(), score, verdict, flags, details }
+    }
+
+    /// Wrap one of the analyzers' JSON reports.
+    pub fn from_report(kind: &str, report: Json) -> Report {
+        let score = report.get("score").and_then(Json::as_f64).unwrap_or(0.0) as f32;
+        let verdict = Verdict::parse(report.str_field("verdict")).unwrap_or_else(|| Verdict::from_score(score));
+        let flags = report.str_list("flags");
+        Report::new(kind, score, verdict, flags, report)
+    }
+}
+
+impl ToJson for Report {
+    fn to_json(&self) -> Json {
+        Json::obj()
+            .with("schema_version", self.schema_version)
+            .with("kind", self.kind.as_str())
+            .with("score", self.score)
+            .with("verdict", self.verdict.as_str())
+            .with("flags", self.flags.clone())
+            .with("details", self.details.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure<T: FromJson + fmt::Debug>(src: &str) -> (String, String) {
+        let e = T::from_json_str(src).unwrap_err();
+        (e.path, e.message)
+    }
+
+    #[test]
+    fn reads_a_page_snapshot() {
+        let p = PageSnapshot::from_json_str(r#"{"url":"https://a.example/","forms":[{"method":"POST","has_password":true}],"csp":null}"#).unwrap();
+        assert_eq!((p.schema_version, p.url.as_str(), p.csp), (SCHEMA_VERSION, "https://a.example/", None));
+        assert_eq!(p.forms, [FormSnapshot { action: String::new(), method: "post".into(), has_password: true }]);
+    }
+
+    #[test]
+    fn malformed_input_names_the_field() {
+        assert_eq!(failure::<PageSnapshot>("[]"), (String::new(), "expected object".into()));
+        assert_eq!(failure::<PageSnapshot>("{}"), ("url".into(), "required string is missing".into()));
+        assert_eq!(failure::<PageSnapshot>(r#"{"url":1}"#), ("url".into(), "expected string".into()));
+        assert_eq!(failure::<PageSnapshot>(r#"{"url":"u","forms":[{},{"has_password":"yes"}]}"#), ("forms[1].has_password".into(), "expected boolean".into()));
+        assert_eq!(failure::<PageSnapshot>(r#"{"url":"u","links":"x"}"#), ("links".into(), "expected array".into()));
+        assert_eq!(failure::<DownloadEvent>("{}"), ("filename".into(), "filename or content_disposition is required".into()));
+        assert_eq!(failure::<CertInfo>(r#"{"host":"a.example"}"#), ("age_days".into(), "required number is missing".into()));
+        assert!(!failure::<PageSnapshot>("{").1.is_empty());
+    }
+
+    #[test]
+    fn schema_versions_past_this_build_are_refused() {
+        let (path, message) = failure::<PageSnapshot>(r#"{"url":"u","schema_version":2}"#);
+        assert_eq!(path, "schema_version");
+        assert!(message.starts_with("unsupported version 2"), "{}", message);
+        assert_eq!(failure::<CertInfo>(r#"{"host":"h","age_days":1,"schema_version":0.5}"#).1, "expected a positive integer");
+    }
+}
@@ -0,0 +1,147 @@
+// wasm-feature/crates/vigilant-core/src/typosquat.rs
+// Browser Vigilant — typosquat distances. Plain Levenshtein counts a swap of
+// two neighbouring letters (`goolge`) as two edits, the same as an unrelated
+// label, and a fat-fingered key next to the intended one (`gpogle`) the same
+// as any other letter. Typosquatters register exactly those slips, so the
+// brand distance in F21–F22 is Damerau (optimal string alignment), and
+// `typosquat_features` adds a QWERTY-weighted distance and the two typo
+// shapes on their own.
+
+use crate::{brands, parse_url};
+
+/// Values returned by [`typosquat_features`]: damerau_distance_norm,
+/// keyboard_distance_norm, transposition_typo, fat_finger_typo.
+pub const TYPOSQUAT_FEATURES: usize = 4;
+
+/// Cost of substituting a key for one next to it on a QWERTY keyboard.
+pub(crate) const ADJACENT_KEY_COST: f32 = 0.5;
+
+/// Shortest brand the typo shapes are checked against; three-letter brands
+/// are a single slip away from too many real names.
+const MIN_TYPO_BRAND: usize = 5;
+
+/// Labels shorter than this count as imitated one typing slip away, longer
+/// ones two edits away; "black" is a word, not a typo of "slack".
+pub(crate) const SHORT_LABEL: usize = 7;
+
+const QWERTY: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Row and column of `c` on a QWERTY layout.
+fn key_pos(c: char) -> Option<(usize, usize)> {
+    QWERTY.iter().enumerate().find_map(|(r, row)| row.find(c.to_ascii_lowercase()).map(|col| (r, col)))
+}
+
+/// `a` and `b` are neighbouring keys: side by side, or touching across a
+/// row, where each row sits half a key right of the one above.
+fn adjacent_keys(a: char, b: char) -> bool {
+    let (Some((ra, ca)), Some((rb, cb))) = (key_pos(a), key_pos(b)) else { return false };
+    match (ra as isize - rb as isize, ca as isize - cb as isize) {
+        (0, d) => d.abs() == 1,
+        (1, d) => d == 0 || d == -1,
+        (-1, d) => d == 0 || d == 1,
+        _ => false,
+    }
+}
+
+/// Optimal string alignment distance with `sub` as the substitution cost:
+/// insertions, deletions and swaps of two adjacent characters cost 1.
+fn osa(a: &str, b: &str, sub: impl Fn(char, char) -> f32) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (m, n) = (a.len(), b.len());
+    let mut d = vec![vec![0f32; n + 1]; m + 1];
+    for (i, row) in d.iter_mut().enumerate() { row[0] = i as f32; }
+    for (j, v) in d[0].iter_mut().enumerate() { *v = j as f32; }
+    for i in 1..=m {
+        for j in 1..=n {
+            let cost = if a[i-1] == b[j-1] { 0.0 } else { sub(a[i-1], b[j-1]) };
+            let mut v = (d[i-1][j] + 1.0).min(d[i][j-1] + 1.0).min(d[i-1][j-1] + cost);
+            if i > 1 && j > 1 && a[i-1] == b[j-2] && a[i-2] == b[j-1] { v = v.min(d[i-2][j-2] + 1.0); }
+            d[i][j] = v;
+        }
+    }
+    d[m][n]
+}
+
+/// Damerau (optimal string alignment) distance.
+pub(crate) fn damerau(a: &str, b: &str) -> usize { osa(a, b, |_, _| 1.0) as usize }
+
+/// Damerau distance where a substitution by a neighbouring key costs
+/// [`ADJACENT_KEY_COST`].
+pub(crate) fn keyboard_distance(a: &str, b: &str) -> f32 {
+    osa(a, b, |x, y| if adjacent_keys(x, y) { ADJACENT_KEY_COST } else { 1.0 })
+}
+
+/// `label` is `brand` with exactly two neighbouring letters swapped.
+pub(crate) fn is_transposition(label: &str, brand: &str) -> bool {
+    let (l, b): (Vec<char>, Vec<char>) = (label.chars().collect(), brand.chars().collect());
+    if l.len() != b.len() { return false; }
+    let diff: Vec<usize> = (0..l.len()).filter(|&i| l[i] != b[i]).collect();
+    matches!(diff[..], [i, j] if j == i + 1 && l[i] == b[j] && l[j] == b[i])
+}
+
+/// `label` is a misspelling of `target`: within two edits of a long label;
+/// for a short one a neighbouring key, two swapped letters, or one letter
+/// dropped or doubled.
+pub(crate) fn misspells(label: &str, target: &str) -> bool {
+    let (l, t) = (label.chars().count(), target.chars().count());
+    if l.min(t) >= SHORT_LABEL { return damerau(label, target) <= 2; }
+    if l == t { keyboard_distance(label, target) <= ADJACENT_KEY_COST || is_transposition(label, target) } else { damerau(label, target) == 1 }
+}
+
+/// `label` is `brand` with one or two letters replaced by a neighbouring key.
+fn is_fat_finger(label: &str, brand: &str) -> bool {
+    let (l, b): (Vec<char>, Vec<char>) = (label.chars().collect(), brand.chars().collect());
+    if l.len() != b.len() { return false; }
+    let diff: Vec<usize> = (0..l.len()).filter(|&i| l[i] != b[i]).collect();
+    (1..=2).contains(&diff.len()) && diff.iter().all(|&i| adjacent_keys(l[i], b[i]))
+}
+
+/// Smallest Damerau distance from the first label of `domain` to a built-in
+/// brand or one of `extra`; 99 when there are none.
+pub(crate) fn min_brand_distance(domain: &str, extra: &[String]) -> usize {
+    let core = domain.split('.').next().unwrap_or("").to_lowercase();
+    brands::min_over_brands(extra, |b| damerau(&core, b)).unwrap_or(99)
+}
+
+/// `[damerau_distance_norm, keyboard_distance_norm, transposition_typo,
+/// fat_finger_typo]` for the registrable label: the distances to the
+/// nearest brand capped at 10 and scaled to 0–1, and whether the label is a
+/// brand with two neighbouring letters swapped or with one or two keys
+/// struck next to the intended ones.
+pub fn typosquat_features(url: &str) -> [f32; TYPOSQUAT_FEATURES] {
+    let p = parse_url(url);
+    let label = p.reg_domain.split('.').next().unwrap_or("").to_lowercase();
+    let dl = min_brand_distance(&label, &[]);
+    let kb = brands::fold_brands(&[], f32::MAX, |m, b| m.min(keyboard_distance(&label, b)));
+    let typo = |shape: fn(&str, &str) -> bool| brands::any_brand(&[], |b| b.len() >= MIN_TYPO_BRAND && shape(&label, b));
+    let b = |v: bool| if v { 1.0 } else { 0.0 };
+    [dl.min(10) as f32 / 10.0, kb.min(10.0) / 10.0, b(typo(is_transposition)), b(typo(is_fat_finger))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transposition_is_one_edit() {
+        assert_eq!(damerau("goolge", "google"), 1);
+        assert_eq!(typosquat_features("https://goolge.com/"), [0.1, 0.1, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn neighbouring_key_costs_half_an_edit() {
+        assert_eq!(keyboard_distance("gpogle", "google"), ADJACENT_KEY_COST);
+        assert_eq!(typosquat_features("https://gpogle.com/"), [0.1, 0.05, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn the_brand_itself_is_no_typo() {
+        assert_eq!(typosquat_features("https://www.google.com/search"), [0.0; TYPOSQUAT_FEATURES]);
+    }
+
+    #[test]
+    fn an_unrelated_word_is_far_from_every_brand() {
+        assert_eq!(typosquat_features("https://weatherstation.org/"), [0.9, 0.8, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,108 @@
+// wasm-feature/crates/vigilant-core/src/upi.rs
+// Browser Vigilant — UPI collect requests. A pay intent (`upi://pay?pa=…`)
+// moves money out when the user enters their PIN, and so does a collect
+// or mandate request: the payee pulls, the user approves. The classic
+// reverse-payment scam frames either as money coming in — "accept your
+// refund", "receive ₹5000 cashback" — and counts on the user not knowing
+// that receiving never needs a PIN. The model only sees that a URL carries
+// a VPA (F48–F50); this reads the request itself, from a link or a QR
+// payload, next to the text around it.
+
+use crate::json::Json;
+use crate::scheme::scheme_of;
+use crate::verdict::Verdict;
+use crate::{parse_url, query_pairs, FRAUD_PFX, LEGIT_UPI_HANDLES};
+
+/// UPI actions under which the payee takes the money.
+pub(crate) const PULL_ACTIONS: &[&str] = &["collect", "mandate"];
+
+/// Phrases that make a request read as money coming in, lowercased;
+/// English, Hinglish and Hindi, as Indian lures mix them.
+pub(crate) const RECEIVE_FRAMING: &[&str] = &[
+    "receive money", "receive payment", "receive your", "money received", "accept payment", "accept money", "accept your",
+    "refund", "cashback", "cash back", "reward", "prize", "lottery", "you won", "you have won", "credited to your", "claim your",
+    "paise milenge", "paisa milega", "paise aa", "rashi prapt", "रिफंड", "कैशबैक", "इनाम", "प्राप्त करें", "पैसे मिलेंगे",
+];
+
+/// A UPI request: the action and its decoded parameters.
+struct Request {
+    /// `pay`, `collect`, `mandate`, … as the link names it.
+    action: String,
+    params: Vec<(String, String)>,
+}
+
+impl Request {
+    fn param(&self, key: &str) -> Option<String> {
+        self.params.iter().find(|(k, _)| k == key).map(|(_, v)| v.trim().to_string()).filter(|v| !v.is_empty())
+    }
+}
+
+/// The request in `payload`: a `upi://` URI, an Android `intent://` link
+/// with `scheme=upi`, or a web payment link that carries a `pa`.
+fn parse_request(payload: &str) -> Option<Request> {
+    let payload = payload.trim();
+    let scheme = scheme_of(payload);
+    let split = |rest: &str| {
+        let rest = rest.trim_start_matches('/');
+        let rest = rest.split('#').next().unwrap_or("");
+        let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+        Request { action: action.trim_end_matches('/').to_lowercase(), params: query_pairs(query) }
+    };
+    match scheme.as_str() {
+        "upi" => Some(split(&payload["upi:".len()..])),
+        "intent" if payload.to_lowercase().contains(";scheme=upi;") => Some(split(&payload["intent:".len()..])),
+        _ => {
+            let p = parse_url(payload);
+            let params = query_pairs(&p.query);
+            if !params.iter().any(|(k, v)| k == "pa" && v.contains('@')) { return None; }
+            let path = p.path.to_lowercase();
+            let action = PULL_ACTIONS.iter().find(|a| path.contains(*a)).map_or("pay", |a| *a);
+            Some(Request { action: action.to_string(), params })
+        }
+    }
+}
+
+/// Analyze a UPI payment request from a link or a scanned QR payload, with
+/// `context_text` the message or page it came with ("" when there is
+/// none). A collect or mandate request is a pull; any request whose note,
+/// payee name or context frames it as money coming in is a reverse payment.
+/// Returns `{ upi, kind, action, payee, payee_name, amount, currency, note,
+/// handle, handle_known, framing, flags, score, verdict }`; `kind` is
+/// "pay" or "pull". Only `upi`, `flags`, `score` and `verdict` are set
+/// when `payload` isn't a UPI request.
+pub fn upi_request_report(payload: &str, context_text: &str) -> Json {
+    let Some(req) = parse_request(payload) else {
+        return Json::obj().with("upi", false).with("flags", Vec::<&str>::new()).with("score", 0.0f32).with("verdict", Verdict::from_score(0.0).as_str());
+    };
+    let payee = req.param("pa").map(|p| p.to_lowercase());
+    let (prefix, handle) = payee.as_deref().and_then(|p| p.split_once('@')).map_or((None, None), |(a, h)| (Some(a.to_string()), Some(h.to_string())));
+    let pull = PULL_ACTIONS.contains(&req.action.as_str());
+    let (note, name) = (req.param("tn"), req.param("pn"));
+    let text = [note.as_deref().unwrap_or(""), name.as_deref().unwrap_or(""), context_text].join("\n").to_lowercase();
+    let framing = RECEIVE_FRAMING.iter().find(|f| text.contains(*f)).copied();
+
+    let mut flags: Vec<&'static str> = Vec::new();
+    let mut risk = 0.0f32;
+    if pull { flags.push("upi_pull_request"); risk += 0.4; }
+    if framing.is_some() { flags.push("upi_reverse_payment"); risk += 0.6; }
+    let known = handle.as_deref().map(|h| LEGIT_UPI_HANDLES.contains(&h));
+    if known == Some(false) { flags.push("upi_unknown_handle"); risk += 0.2; }
+    if prefix.as_deref().is_some_and(|p| FRAUD_PFX.iter().any(|f| p.contains(f))) { flags.push("upi_scam_prefix"); risk += 0.3; }
+
+    let risk = risk.min(1.0);
+    Json::obj()
+        .with("upi", true)
+        .with("kind", if pull { "pull" } else { "pay" })
+        .with("action", req.action.as_str())
+        .with("payee", payee)
+        .with("payee_name", name)
+        .with("amount", req.param("am"))
+        .with("currency", req.param("cu"))
+        .with("note", note)
+        .with("handle", handle)
+        .with("handle_known", known)
+        .with("framing", framing)
+        .with("flags", flags)
+        .with("score", risk)
+        .with("verdict", Verdict::from_score(risk).as_str())
+}
@@ -0,0 +1,129 @@
+// wasm-feature/crates/vigilant-core/src/userdata.rs
+// Browser Vigilant — the user-data file: what a user set up and taught the
+// engine, in one document they can carry to another machine or attach to a
+// support request. Unlike `export_state` it leaves out what is rebuilt or
+// re-downloaded anyway (feeds, reputation, learned templates).
+//
+// Format: one JSON object,
+//   { format: "browser-vigilant/user-data", format_version, min_reader_version,
+//     engine, key_check, profile, protected_brands: [name],
+//     overrides: { rule: verdict }, personal: { weights, updates },
+//     feedback: [{ label, features, url_hash, url?, score }], checksum }
+// Readers ignore keys they don't know and refuse a file whose
+// `min_reader_version` is above theirs, so a newer writer adds fields
+// without breaking older readers and bumps `min_reader_version` only when
+// a field changes meaning. `checksum` is the SHA-256 of the document
+// without it, as this module serializes it; it catches truncated and
+// hand-edited files rather than proving who wrote them.
+
+use crate::hash::sha256_hex;
+use crate::json::Json;
+use crate::types::{err, Fields, SchemaError};
+
+pub const USER_DATA_FORMAT: &str = "browser-vigilant/user-data";
+
+/// Version this build writes and the highest `min_reader_version` it reads.
+pub const USER_DATA_VERSION: u32 = 1;
+
+/// Largest file accepted.
+const MAX_USER_DATA_BYTES: usize = 4 << 20;
+
+fn body_digest(doc: &[(String, Json)]) -> String {
+    let body: Vec<(String, Json)> = doc.iter().filter(|(k, _)| k != "checksum").cloned().collect();
+    sha256_hex(Json::Obj(body).to_string().as_bytes())
+}
+
+/// Stamp `doc` (the fields after the header) with the format header and
+/// checksum and serialize it.
+pub(crate) fn seal(doc: &Json) -> Vec<u8> {
+    let mut fields = vec![
+        ("format".to_string(), Json::from(USER_DATA_FORMAT)),
+        ("format_version".to_string(), Json::from(USER_DATA_VERSION)),
+        ("min_reader_version".to_string(), Json::from(1u32)),
+    ];
+    fields.extend(doc.as_object().unwrap_or(&[]).iter().cloned());
+    let checksum = body_digest(&fields);
+    Json::Obj(fields).with("checksum", checksum).to_string().into_bytes()
+}
+
+/// Parse and verify a file from [`seal`].
+pub(crate) fn open(bytes: &[u8]) -> Result<Json, SchemaError> {
+    if bytes.len() > MAX_USER_DATA_BYTES { return Err(err("", "user-data file is too large")); }
+    let text = std::str::from_utf8(bytes).map_err(|_| err("", "user-data file is not UTF-8"))?;
+    let doc = Json::parse(text.trim_start_matches('\u{feff}')).map_err(|e| err("", &e))?;
+    let f = Fields::new(&doc, "")?;
+    if f.opt_string("format")?.as_deref() != Some(USER_DATA_FORMAT) { return Err(err("format", "not a user-data file")); }
+    let min_reader = f.opt_count("min_reader_version")?.unwrap_or(1);
+    if min_reader > USER_DATA_VERSION as usize {
+        return Err(err("min_reader_version", &format!("file needs reader version {} (this build reads up to {})", min_reader, USER_DATA_VERSION)));
+    }
+    let checksum = f.string("checksum")?;
+    let fields = doc.as_object().unwrap_or(&[]);
+    if body_digest(fields) != checksum { return Err(err("checksum", "file was modified or is incomplete")); }
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Context, Verdict};
+
+    fn profile(ctx: &Context) -> String {
+        open(&ctx.export_user_data()).unwrap().str_field("profile").to_string()
+    }
+
+    fn path_of(bytes: &[u8]) -> (String, String) {
+        let mut ctx = Context::default();
+        let e = ctx.import_user_data(bytes).unwrap_err();
+        assert_eq!(profile(&ctx), "balanced", "a refused file changed the profile");
+        (e.path, e.message)
+    }
+
+    #[test]
+    fn an_exported_file_imports_into_a_fresh_context() {
+        let mut ctx = Context::default();
+        ctx.import_user_data(&seal(&Json::obj().with("profile", "strict"))).unwrap();
+        assert!(ctx.set_override("example.com", Verdict::Allow));
+        let mut other = Context::default();
+        assert_eq!(other.import_user_data(&ctx.export_user_data()).unwrap(), 2);
+        assert_eq!(profile(&other), "strict");
+    }
+
+    #[test]
+    fn a_truncated_or_edited_file_is_refused() {
+        let bytes = seal(&Json::obj().with("profile", "strict"));
+        assert_eq!(path_of(&bytes[..bytes.len() / 2]).0, "");
+        let text = String::from_utf8(bytes).unwrap().replace("strict", "permissive");
+        assert_eq!(path_of(text.as_bytes()), ("checksum".to_string(), "file was modified or is incomplete".to_string()));
+        assert_eq!(path_of(br#"{"format":"browser-vigilant/user-data"}"#).0, "checksum");
+        assert_eq!(path_of(br#"{"overrides":{}}"#).0, "format");
+        assert_eq!(path_of(&[0xff, 0xfe]).1, "user-data file is not UTF-8");
+    }
+
+    #[test]
+    fn a_file_for_a_newer_reader_is_refused() {
+        let text = String::from_utf8(seal(&Json::obj())).unwrap();
+        let newer = text.replace(r#""min_reader_version":1"#, r#""min_reader_version":2"#);
+        assert_eq!(path_of(newer.as_bytes()).0, "min_reader_version");
+        let typed = text.replace(r#""min_reader_version":1"#, r#""min_reader_version":"1""#);
+        assert_eq!(path_of(typed.as_bytes()), ("min_reader_version".to_string(), "expected number".to_string()));
+    }
+
+    #[test]
+    fn a_wrong_field_type_is_refused_with_its_path() {
+        let cases = [
+            (Json::obj().with("profile", 1u32), "profile"),
+            (Json::obj().with("profile", "paranoid"), "profile"),
+            (Json::obj().with("key_check", "someone-else"), "key_check"),
+            (Json::obj().with("protected_brands", Json::from(vec![Json::from(1u32)])), "protected_brands[0]"),
+            (Json::obj().with("overrides", Json::obj().with("example.com", "maybe")), "overrides.example.com"),
+            (Json::obj().with("feedback", Json::from(vec![Json::obj().with("url_hash", "h").with("label", 2u32)])), "feedback[0].label"),
+            (Json::obj().with("personal", "weights"), "personal"),
+        ];
+        for (doc, path) in cases {
+            // a valid profile next to the bad field must not be applied either
+            let doc = if doc.get("profile").is_some() { doc } else { doc.with("profile", "strict") };
+            assert_eq!(path_of(&seal(&doc)).0, path);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+// wasm-feature/crates/vigilant-core/src/vectors.rs
+// Browser Vigilant — canonical test vectors. The extension's JS wrapper and
+// the Kotlin port each re-implement the glue around this crate: argument
+// marshalling, the Float32Array hand-off, verdict strings. Hand-picked URLs
+// in their test suites drift from what the Rust side actually does, so the
+// crate generates the cases itself: URLs assembled from a seed to reach
+// every branch of the parser (schemes, userinfo, IP encodings, ports, PSL
+// suffixes, query and fragment edge cases), each with the parts, features
+// and verdict this build computes for it. Same seed, same build, same file.
+
+use crate::analyzer::Analyzer;
+use crate::audit::ENGINE_VERSION;
+use crate::json::Json;
+use crate::model::FEATURE_VERSION;
+use crate::normalize::normalize_url;
+use crate::schema::feature_schema_hash;
+use crate::{parse_url, psl, BRANDS, DANGEROUS_EXTS, LEGIT_UPI_HANDLES, LOGIN_KW, SHORT_SERVICES, SUSPICIOUS_TLDS};
+
+/// Most vectors one call generates.
+pub const MAX_TEST_VECTORS: usize = 10_000;
+
+/// SplitMix64: tiny, seedable and identical on every platform, which is all
+/// a vector generator needs.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize { (self.next() % n.max(1) as u64) as usize }
+
+    fn pick<'a>(&mut self, list: &[&'a str]) -> &'a str { list[self.below(list.len())] }
+
+    /// Lowercase letters, digits when `digits` is set.
+    fn word(&mut self, min: usize, max: usize, digits: bool) -> String {
+        let alphabet: &[u8] = if digits { b"abcdefghijklmnopqrstuvwxyz0123456789" } else { b"abcdefghijklmnopqrstuvwxyz" };
+        let len = min + self.below(max - min + 1);
+        (0..len).map(|_| alphabet[self.below(alphabet.len())] as char).collect()
+    }
+}
+
+/// Option `i` of a choice with `n` options: the `i`th URL to make the
+/// choice takes option `i` while there are options left, so the first few
+/// vectors of any seed cover every branch, and a seeded draw after that.
+fn choose(i: usize, n: usize, rng: &mut Rng) -> usize { if i < n { i } else { rng.below(n) } }
+
+const SCHEMES: &[&str] = &["https", "http", "upper", "ftp", "none", "intent", "blob", "data", "javascript"];
+const USERINFO: &[&str] = &["none", "user", "user_pass", "brand", "double_at"];
+const HOSTS: &[&str] = &[
+    "word", "brand", "brand_keyword", "transposed_brand", "digits", "ipv4", "ipv4_integer", "ipv4_hex", "ipv6", "punycode", "shortener", "mixed_case",
+];
+const SUBDOMAINS: &[&str] = &["none", "www", "labels", "brand"];
+const SUFFIXES: &[&str] = &["com", "suspicious", "multi_label", "wildcard", "trailing_dot"];
+const PORTS: &[&str] = &["none", "default", "custom", "empty", "overflow", "non_digit"];
+const PATHS: &[&str] = &["none", "root", "segments", "login_script", "dangerous_file", "encoded", "double_slash", "brand_login"];
+const QUERIES: &[&str] = &["none", "empty", "params", "redirect", "upi", "encoded"];
+const FRAGMENTS: &[&str] = &["none", "empty", "anchor", "route", "at_sign"];
+
+/// Host for the `kind` option of [`HOSTS`]; name hosts get a subdomain and
+/// a suffix, addresses and shorteners stand alone.
+fn host(kind: &str, sub: &str, suffix: &str, rng: &mut Rng) -> String {
+    let label = match kind {
+        "ipv4" => return format!("{}.{}.{}.{}", rng.below(256), rng.below(256), rng.below(256), rng.below(256)),
+        "ipv4_integer" => return (rng.next() as u32).to_string(),
+        "ipv4_hex" => return format!("0x{:08x}", rng.next() as u32),
+        "ipv6" => return format!("[2001:db8::{:x}]", rng.below(0x10000)),
+        "shortener" => return rng.pick(SHORT_SERVICES).to_string(),
+        "brand" => rng.pick(BRANDS).to_string(),
+        "brand_keyword" => format!("{}-{}", rng.pick(BRANDS), rng.pick(LOGIN_KW)),
+        "transposed_brand" => {
+            let mut b: Vec<char> = rng.pick(BRANDS).chars().collect();
+            let at = rng.below(b.len() - 1);
+            b.swap(at, at + 1);
+            b.into_iter().collect()
+        }
+        "digits" => rng.word(4, 12, true),
+        "punycode" => format!("xn--{}", rng.word(4, 10, true)),
+        "mixed_case" => rng.word(4, 10, false).chars().enumerate().map(|(i, c)| if i % 2 == 0 { c.to_ascii_uppercase() } else { c }).collect(),
+        _ => rng.word(3, 10, false),
+    };
+    let sub = match sub {
+        "www" => "www.".to_string(),
+        "labels" => (0..1 + rng.below(3)).map(|_| rng.word(2, 8, false) + ".").collect(),
+        "brand" => format!("{}.com.", rng.pick(BRANDS)),
+        _ => String::new(),
+    };
+    let suffix = match suffix {
+        "suspicious" => rng.pick(SUSPICIOUS_TLDS).to_string(),
+        "multi_label" => rng.pick(psl::MULTI_LABEL_SUFFIXES).to_string(),
+        "wildcard" => format!("{}.{}", rng.word(2, 6, false), rng.pick(psl::WILDCARD_SUFFIXES)),
+        "trailing_dot" => "com.".to_string(),
+        _ => "com".to_string(),
+    };
+    format!("{sub}{label}.{suffix}")
+}
+
+/// The `i`th URL, the `*hierarchical`th with an authority, and the option
+/// it took for each choice, as `choice:option`.
+fn build(i: usize, hierarchical: &mut usize, rng: &mut Rng) -> (String, Vec<String>) {
+    let mut cases = Vec::new();
+    let mut take = |name: &str, i: usize, options: &[&'static str], rng: &mut Rng| {
+        let o = options[choose(i, options.len(), rng)];
+        cases.push(format!("{name}:{o}"));
+        o
+    };
+    let scheme = take("scheme", i, SCHEMES, rng);
+    // opaque schemes have no authority for the other choices to fill
+    match scheme {
+        "data" => { let url = format!("data:text/html;base64,{}", rng.word(8, 24, true)); return (url, cases); }
+        "javascript" => { let url = format!("javascript:void({})", rng.below(10)); return (url, cases); }
+        _ => {}
+    }
+    let i = *hierarchical;
+    *hierarchical += 1;
+    let userinfo = take("userinfo", i, USERINFO, rng);
+    let kind = take("host", i, HOSTS, rng);
+    let sub = take("subdomain", i, SUBDOMAINS, rng);
+    let suffix = take("suffix", i, SUFFIXES, rng);
+    let port = take("port", i, PORTS, rng);
+    let path = take("path", i, PATHS, rng);
+    let query = take("query", i, QUERIES, rng);
+    let fragment = take("fragment", i, FRAGMENTS, rng);
+
+    let mut url = match scheme {
+        "upper" => "HTTPS://".to_string(),
+        "none" => String::new(),
+        "blob" => "blob:https://".to_string(),
+        s => format!("{s}://"),
+    };
+    url += &match userinfo {
+        "user" => format!("{}@", rng.word(3, 8, false)),
+        "user_pass" => format!("{}:{}@", rng.word(3, 8, false), rng.word(4, 10, true)),
+        "brand" => format!("{}.com@", rng.pick(BRANDS)),
+        "double_at" => format!("{}@{}@", rng.word(3, 6, false), rng.word(3, 6, false)),
+        _ => String::new(),
+    };
+    url += &host(kind, sub, suffix, rng);
+    url += &match port {
+        "default" => ":443".to_string(),
+        "custom" => format!(":{}", 1024 + rng.below(64_000)),
+        "empty" => ":".to_string(),
+        "overflow" => format!(":{}", 65_536 + rng.below(30_000)),
+        "non_digit" => format!(":{}a", rng.below(100)),
+        _ => String::new(),
+    };
+    url += &match path {
+        "root" => "/".to_string(),
+        "segments" => (0..1 + rng.below(4)).map(|_| format!("/{}", rng.word(2, 10, true))).collect(),
+        "login_script" => format!("/{}.php", rng.pick(LOGIN_KW)),
+        "dangerous_file" => format!("/{}.{}", rng.word(3, 10, false), rng.pick(DANGEROUS_EXTS)),
+        "encoded" => format!("/%2e%2e/{}%2F{}", rng.word(2, 6, false), rng.word(2, 6, false)),
+        "double_slash" => format!("//{}", rng.word(2, 8, false)),
+        "brand_login" => format!("/{}/{}", rng.pick(BRANDS), rng.pick(LOGIN_KW)),
+        _ => String::new(),
+    };
+    url += &match query {
+        "empty" => "?".to_string(),
+        "params" => format!("?{}={}&{}={}", rng.word(1, 6, false), rng.below(1000), rng.word(1, 6, false), rng.word(1, 8, true)),
+        "redirect" => format!("?redirect=https://{}.com/{}", rng.pick(BRANDS), rng.pick(LOGIN_KW)),
+        "upi" => format!("?pa={}@{}&am={}", rng.word(3, 10, false), rng.pick(LEGIT_UPI_HANDLES), rng.below(100_000)),
+        "encoded" => format!("?q=%3C{}%3E+{}", rng.word(2, 6, false), rng.word(2, 6, false)),
+        _ => String::new(),
+    };
+    url += &match fragment {
+        "empty" => "#".to_string(),
+        "anchor" => format!("#{}", rng.word(2, 10, false)),
+        "route" => format!("#/{}?next={}", rng.word(2, 8, false), rng.word(2, 8, false)),
+        "at_sign" => format!("#{}@{}.com", rng.word(2, 6, false), rng.pick(BRANDS)),
+        _ => String::new(),
+    };
+    (url, cases)
+}
+
+/// `count` (at most [`MAX_TEST_VECTORS`]) URLs generated from `seed`, each
+/// with what this build computes for it under the shared analyzer. The
+/// first vectors of any seed take every option of every choice once.
+/// Returns `{ seed, count, engine_version, feature_version,
+/// feature_schema_hash, vectors: [{ url, cases, normalized, parts: {
+/// scheme, host, path, query, fragment, port, tld, reg_domain, subdomain },
+/// features, risk, verdict, uncertainty, decision, flags }] }`. Runtime
+/// brands and a feature mask change the expected values, so generate on a
+/// fresh module.
+pub fn gen_test_vectors(seed: u64, count: usize) -> Json {
+    let mut rng = Rng(seed);
+    let analyzer = Analyzer::shared();
+    let mut hierarchical = 0;
+    let vectors: Vec<Json> = (0..count.min(MAX_TEST_VECTORS)).map(|i| {
+        let (url, cases) = build(i, &mut hierarchical, &mut rng);
+        let p = parse_url(&url);
+        let r = analyzer.url(&url);
+        Json::obj()
+            .with("url", url.as_str())
+            .with("cases", cases)
+            .with("normalized", normalize_url(&url))
+            .with("parts", Json::obj()
+                .with("scheme", p.scheme)
+                .with("host", p.host)
+                .with("path", p.path)
+                .with("query", p.query)
+                .with("fragment", p.fragment)
+                .with("port", p.port.map(u32::from))
+                .with("tld", p.tld)
+                .with("reg_domain", p.reg_domain)
+                .with("subdomain", p.subdomain))
+            .with("features", r.features)
+            .with("risk", r.risk)
+            .with("verdict", r.verdict.as_str())
+            .with("uncertainty", r.uncertainty)
+            .with("decision", r.decision.as_str())
+            .with("flags", r.flags)
+    }).collect();
+    Json::obj()
+        .with("seed", seed)
+        .with("count", vectors.len())
+        .with("engine_version", ENGINE_VERSION)
+        .with("feature_version", FEATURE_VERSION)
+        .with("feature_schema_hash", feature_schema_hash())
+        .with("vectors", vectors)
+}
@@ -0,0 +1,83 @@
+// wasm-feature/crates/vigilant-core/src/verdict.rs
+// Browser Vigilant — shared three-way verdict returned by the gating APIs
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    Allow = 0,
+    Warn  = 1,
+    Block = 2,
+}
+
+impl Verdict {
+    /// Score thresholds shared by the composite analyzers.
+    pub fn from_score(score: f32) -> Verdict {
+        if score >= 0.7 { Verdict::Block } else if score >= 0.4 { Verdict::Warn } else { Verdict::Allow }
+    }
+
+    pub fn parse(name: &str) -> Option<Verdict> {
+        match name { "allow" => Some(Verdict::Allow), "warn" => Some(Verdict::Warn), "block" => Some(Verdict::Block), _ => None }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self { Verdict::Allow => "allow", Verdict::Warn => "warn", Verdict::Block => "block" }
+    }
+}
+
+/// Whether an on-device verdict can stand or the URL needs the slower online
+/// lookup: `Uncertain` is the "needs cloud check" band.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision {
+    Safe,
+    Unsafe,
+    Uncertain,
+}
+
+impl Decision {
+    /// Decide from the band `risk ± uncertainty`: `Safe` when even its top
+    /// is allowed, `Unsafe` when even its bottom isn't, `Uncertain` when it
+    /// straddles the allow line.
+    pub fn from_band(risk: f32, uncertainty: f32, verdict: impl Fn(f32) -> Verdict) -> Decision {
+        if verdict((risk + uncertainty).min(1.0)) == Verdict::Allow { Decision::Safe }
+        else if verdict((risk - uncertainty).max(0.0)) != Verdict::Allow { Decision::Unsafe }
+        else { Decision::Uncertain }
+    }
+
+    pub fn parse(name: &str) -> Option<Decision> {
+        match name { "safe" => Some(Decision::Safe), "unsafe" => Some(Decision::Unsafe), "uncertain" => Some(Decision::Uncertain), _ => None }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self { Decision::Safe => "safe", Decision::Unsafe => "unsafe", Decision::Uncertain => "uncertain" }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn band_straddling_the_allow_line_is_uncertain() {
+        assert_eq!(Decision::from_band(0.2, 0.1, Verdict::from_score), Decision::Safe);
+        assert_eq!(Decision::from_band(0.35, 0.1, Verdict::from_score), Decision::Uncertain);
+        assert_eq!(Decision::from_band(0.55, 0.1, Verdict::from_score), Decision::Unsafe);
+        assert_eq!(Decision::from_band(0.6, 0.3, Verdict::from_score), Decision::Uncertain);
+    }
+
+    #[test]
+    fn zero_uncertainty_follows_the_verdict() {
+        for (risk, want) in [(0.0, Decision::Safe), (0.399, Decision::Safe), (0.4, Decision::Unsafe), (1.0, Decision::Unsafe)] {
+            assert_eq!(Decision::from_band(risk, 0.0, Verdict::from_score), want, "{}", risk);
+        }
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for d in [Decision::Safe, Decision::Unsafe, Decision::Uncertain] {
+            assert_eq!(Decision::parse(d.as_str()), Some(d));
+        }
+        for v in [Verdict::Allow, Verdict::Warn, Verdict::Block] {
+            assert_eq!(Verdict::parse(v.as_str()), Some(v));
+        }
+        assert_eq!(Decision::parse("Safe"), None);
+    }
+}
@@ -0,0 +1,201 @@
+// wasm-feature/crates/vigilant-core/src/zip.rs
+// Browser Vigilant — zip central-directory reader (entries + stored/deflate data)
+
+use crate::inflate::inflate;
+
+const EOCD_SIG:  u32 = 0x0605_4b50;
+const CEN_SIG:   u32 = 0x0201_4b50;
+const LOCAL_SIG: u32 = 0x0403_4b50;
+const MAX_ENTRIES: usize = 65_535;
+
+pub struct ZipEntry {
+    pub name:            String,
+    pub method:          u16,
+    pub flags:           u16,
+    pub mod_time:        u16,
+    pub crc32:           u32,
+    pub compressed_size: u32,
+    pub local_offset:    u32,
+}
+
+impl ZipEntry {
+    pub fn encrypted(&self) -> bool { self.flags & 1 != 0 }
+}
+
+fn u16_at(b: &[u8], o: usize) -> Option<u16> {
+    b.get(o..o.checked_add(2)?).map(|s| u16::from_le_bytes([s[0], s[1]]))
+}
+
+fn u32_at(b: &[u8], o: usize) -> Option<u32> {
+    b.get(o..o.checked_add(4)?).map(|s| u32::from_le_bytes([s[0], s[1], s[2], s[3]]))
+}
+
+pub fn is_zip(b: &[u8]) -> bool {
+    u32_at(b, 0) == Some(LOCAL_SIG) || find_eocd(b).is_some()
+}
+
+/// The EOCD record lives in the last 64 KiB + 22 bytes (comment length is u16).
+fn find_eocd(b: &[u8]) -> Option<usize> {
+    if b.len() < 22 { return None; }
+    let lo = b.len().saturating_sub(22 + 65_535);
+    (lo..=b.len() - 22).rev().find(|&o| u32_at(b, o) == Some(EOCD_SIG))
+}
+
+/// Central-directory listing. Falls back to walking local headers when the
+/// buffer is only a prefix of the archive (the tail with the EOCD is missing).
+pub fn entries(b: &[u8]) -> Vec<ZipEntry> {
+    match find_eocd(b) {
+        Some(eocd) => central_entries(b, eocd),
+        None => local_entries(b),
+    }
+}
+
+fn central_entries(b: &[u8], eocd: usize) -> Vec<ZipEntry> {
+    let mut out = Vec::new();
+    let count = u16_at(b, eocd + 10).unwrap_or(0) as usize;
+    let mut o = u32_at(b, eocd + 16).unwrap_or(0) as usize;
+    for _ in 0..count.min(MAX_ENTRIES) {
+        if u32_at(b, o) != Some(CEN_SIG) { break; }
+        let (Some(flags), Some(method), Some(time), Some(crc), Some(csize), Some(nlen), Some(xlen), Some(clen), Some(local)) = (
+            u16_at(b, o + 8), u16_at(b, o + 10), u16_at(b, o + 12), u32_at(b, o + 16), u32_at(b, o + 20),
+            u16_at(b, o + 28), u16_at(b, o + 30), u16_at(b, o + 32), u32_at(b, o + 42),
+        ) else { break };
+        let name = match b.get(o + 46..o + 46 + nlen as usize) {
+            Some(n) => String::from_utf8_lossy(n).into_owned(),
+            None => break,
+        };
+        out.push(ZipEntry { name, method, flags, mod_time: time, crc32: crc, compressed_size: csize, local_offset: local });
+        // offsets come from the archive; one that wraps ends the listing
+        let Some(next) = o.checked_add(46 + nlen as usize + xlen as usize + clen as usize) else { break };
+        o = next;
+    }
+    out
+}
+
+fn local_entries(b: &[u8]) -> Vec<ZipEntry> {
+    let mut out = Vec::new();
+    let mut o = 0usize;
+    while out.len() < MAX_ENTRIES && u32_at(b, o) == Some(LOCAL_SIG) {
+        let (Some(flags), Some(method), Some(time), Some(crc), Some(csize), Some(nlen), Some(xlen)) = (
+            u16_at(b, o + 6), u16_at(b, o + 8), u16_at(b, o + 10), u32_at(b, o + 14), u32_at(b, o + 18),
+            u16_at(b, o + 26), u16_at(b, o + 28),
+        ) else { break };
+        let name = match b.get(o + 30..o + 30 + nlen as usize) {
+            Some(n) => String::from_utf8_lossy(n).into_owned(),
+            None => break,
+        };
+        out.push(ZipEntry { name, method, flags, mod_time: time, crc32: crc, compressed_size: csize, local_offset: o as u32 });
+        // sizes live in a trailing data descriptor when bit 3 is set; we can't skip blindly
+        if flags & 0x08 != 0 && csize == 0 { break; }
+        let Some(next) = (o + 30 + nlen as usize + xlen as usize).checked_add(csize as usize) else { break };
+        o = next;
+    }
+    out
+}
+
+/// Decompressed entry data, capped at `limit` bytes. None for encrypted
+/// entries, unsupported methods or truncated buffers.
+pub fn read(b: &[u8], e: &ZipEntry, limit: usize) -> Option<Vec<u8>> {
+    if e.encrypted() { return None; }
+    let data = raw_data(b, e)?;
+    match e.method {
+        0 => Some(data[..data.len().min(limit)].to_vec()),
+        8 => inflate(data, limit).ok(),
+        _ => None,
+    }
+}
+
+/// The entry's stored bytes as they sit in the archive (still compressed or encrypted).
+pub fn raw_data<'a>(b: &'a [u8], e: &ZipEntry) -> Option<&'a [u8]> {
+    let o = e.local_offset as usize;
+    if u32_at(b, o) != Some(LOCAL_SIG) { return None; }
+    let start = o + 30 + u16_at(b, o + 26)? as usize + u16_at(b, o + 28)? as usize;
+    b.get(start..start.checked_add(e.compressed_size as usize)?)
+}
+
+/// Convenience: read an entry by exact name.
+pub fn read_named(b: &[u8], list: &[ZipEntry], name: &str, limit: usize) -> Option<Vec<u8>> {
+    list.iter().find(|e| e.name == name).and_then(|e| read(b, e, limit))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A zip of stored (uncompressed) entries with a central directory.
+    pub(crate) fn stored(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let (mut out, mut cen) = (Vec::new(), Vec::new());
+        for (name, data) in files {
+            let local = out.len() as u32;
+            out.extend(LOCAL_SIG.to_le_bytes());
+            out.extend([20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            out.extend((data.len() as u32).to_le_bytes());
+            out.extend((data.len() as u32).to_le_bytes());
+            out.extend((name.len() as u16).to_le_bytes());
+            out.extend([0, 0]);
+            out.extend(name.as_bytes());
+            out.extend(*data);
+            cen.extend(CEN_SIG.to_le_bytes());
+            cen.extend([20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            cen.extend((data.len() as u32).to_le_bytes());
+            cen.extend((data.len() as u32).to_le_bytes());
+            cen.extend((name.len() as u16).to_le_bytes());
+            cen.extend([0; 12]);
+            cen.extend(local.to_le_bytes());
+            cen.extend(name.as_bytes());
+        }
+        let cen_at = out.len() as u32;
+        out.extend(&cen);
+        out.extend(EOCD_SIG.to_le_bytes());
+        out.extend([0, 0, 0, 0]);
+        out.extend((files.len() as u16).to_le_bytes());
+        out.extend((files.len() as u16).to_le_bytes());
+        out.extend((cen.len() as u32).to_le_bytes());
+        out.extend(cen_at.to_le_bytes());
+        out.extend([0, 0]);
+        out
+    }
+
+    #[test]
+    fn reads_stored_entry_through_central_directory() {
+        let z = stored(&[("a.txt", b"hello"), ("b.txt", b"world")]);
+        let list = entries(&z);
+        assert_eq!(list.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(), ["a.txt", "b.txt"]);
+        assert_eq!(read_named(&z, &list, "b.txt", 1 << 10).as_deref(), Some(&b"world"[..]));
+    }
+
+    #[test]
+    fn walks_local_headers_of_a_prefix() {
+        let z = stored(&[("a.txt", b"hello")]);
+        let prefix = &z[..30 + 5 + 5];
+        let list = entries(prefix);
+        assert_eq!(list.len(), 1);
+        assert_eq!(read(prefix, &list[0], 16).as_deref(), Some(&b"hello"[..]));
+    }
+
+    #[test]
+    fn central_directory_offset_past_the_end_lists_nothing() {
+        let mut z = stored(&[("a.txt", b"hello")]);
+        let n = z.len();
+        z[n - 6..n - 2].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(entries(&z).is_empty());
+    }
+
+    #[test]
+    fn oversized_entry_reads_as_none() {
+        let mut z = stored(&[("a.txt", b"hello")]);
+        z[18..22].copy_from_slice(&u32::MAX.to_le_bytes());
+        let e = ZipEntry { name: "a.txt".into(), method: 0, flags: 0, mod_time: 0, crc32: 0, compressed_size: u32::MAX, local_offset: 0 };
+        assert!(read(&z, &e, 16).is_none());
+        let _ = local_entries(&z);
+    }
+
+    #[test]
+    fn wrapping_entry_sizes_end_the_walk() {
+        let mut z = stored(&[("a.txt", b"hello")]);
+        let cen = z.len() - 22 - 51;
+        // extra and comment lengths that push the next offset past the end
+        z[cen + 30..cen + 34].copy_from_slice(&[0xff; 4]);
+        assert_eq!(entries(&z).len(), 1);
+    }
+}
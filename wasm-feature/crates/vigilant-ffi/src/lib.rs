@@ -0,0 +1,165 @@
+// wasm-feature/crates/vigilant-ffi/src/lib.rs
+// Browser Vigilant — C ABI over vigilant-core for native hosts (mail gateways,
+// desktop agents). Reports come back as heap JSON strings the caller frees
+// with `vigilant_string_free`. A panic in the core never unwinds into the
+// host: the call returns 0, NaN, block or a null string instead.
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice};
+use vigilant_core as vc;
+use vigilant_core::json::Json;
+
+/// Length of the vector written by `vigilant_extract_features`.
+pub const VIGILANT_FEATURE_COUNT: usize = vc::MODEL_FEATURES;
+
+/// Borrow a C string; null or invalid UTF-8 reads as "".
+unsafe fn str_arg<'a>(p: *const c_char) -> &'a str {
+    if p.is_null() { return ""; }
+    CStr::from_ptr(p).to_str().unwrap_or("")
+}
+
+unsafe fn bytes_arg<'a>(p: *const u8, len: usize) -> &'a [u8] {
+    if p.is_null() || len == 0 { return &[]; }
+    slice::from_raw_parts(p, len)
+}
+
+/// `f()`, or `failed` when it panics; unwinding out of `extern "C"` aborts the host.
+fn guard<T>(failed: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(failed)
+}
+
+/// The report `f` builds as a heap string, null when it panics.
+fn json_out(f: impl FnOnce() -> Json) -> *mut c_char {
+    // serialised JSON never contains NUL; fall back to null just in case
+    guard(ptr::null_mut(), || CString::new(f().to_string()).map(CString::into_raw).unwrap_or(ptr::null_mut()))
+}
+
+/// Write up to `len` features for `url` into `out`; returns how many the
+/// model expects (`VIGILANT_FEATURE_COUNT`), or 0 when extraction panicked.
+///
+/// # Safety
+/// `url` is null or a NUL-terminated string; `out` is null or points to `len` writable floats.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_extract_features(url: *const c_char, out: *mut f32, len: usize) -> usize {
+    let url = str_arg(url);
+    let Some(f) = guard(None, || Some(vc::extract_features(url))) else { return 0 };
+    if !out.is_null() {
+        let n = len.min(f.len());
+        ptr::copy_nonoverlapping(f.as_ptr(), out, n);
+    }
+    f.len()
+}
+
+/// Heuristic URL risk, 0.0–1.0; NaN when scoring panicked.
+///
+/// # Safety
+/// `url` is null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_url_risk(url: *const c_char) -> f32 {
+    let url = str_arg(url);
+    guard(f32::NAN, || vc::url_risk(url))
+}
+
+/// 0 allow, 1 warn, 2 block; 2 when the check panicked.
+///
+/// # Safety
+/// Both arguments are null or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_should_allow_autofill(url: *const c_char, saved_origin: *const c_char) -> u8 {
+    let (url, saved_origin) = (str_arg(url), str_arg(saved_origin));
+    guard(2, || vc::should_allow_autofill(url, saved_origin) as u8)
+}
+
+/// Download report JSON (see `download_report`).
+///
+/// # Safety
+/// `filename` is null or a NUL-terminated string; `bytes` is null or points to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_analyze_download(filename: *const c_char, bytes: *const u8, len: usize) -> *mut c_char {
+    json_out(|| vc::download_report(str_arg(filename), bytes_arg(bytes, len)))
+}
+
+/// Mail report JSON (see `mail_report`); `message_json` uses the same shape as the JS API.
+///
+/// # Safety
+/// `message_json` is null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_analyze_mail(message_json: *const c_char) -> *mut c_char {
+    json_out(|| vc::mail_report(str_arg(message_json)))
+}
+
+/// OAuth consent-link report JSON (see `oauth_report`).
+///
+/// # Safety
+/// `url` is null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_analyze_oauth_url(url: *const c_char) -> *mut c_char {
+    json_out(|| vc::oauth_report(str_arg(url)))
+}
+
+/// Android `intent:` link report JSON (see `intent_report`).
+///
+/// # Safety
+/// `uri` is null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_analyze_intent_uri(uri: *const c_char) -> *mut c_char {
+    json_out(|| vc::intent_report(str_arg(uri)))
+}
+
+/// UPI payment request report JSON (see `upi_request_report`) for a link or
+/// QR payload; pass null or "" for `context_text` when there is none.
+///
+/// # Safety
+/// Each argument is null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_analyze_upi_request(payload: *const c_char, context_text: *const c_char) -> *mut c_char {
+    json_out(|| vc::upi_request_report(str_arg(payload), str_arg(context_text)))
+}
+
+/// `data:` URI payload report JSON (see `data_uri_report`).
+///
+/// # Safety
+/// `uri` is null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_analyze_data_uri(uri: *const c_char) -> *mut c_char {
+    json_out(|| vc::data_uri_report(str_arg(uri)))
+}
+
+/// `javascript:` / `blob:` payload report JSON (see `scheme_payload_report`).
+///
+/// # Safety
+/// `url` is null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_analyze_scheme_payload(url: *const c_char) -> *mut c_char {
+    json_out(|| vc::scheme_payload_report(str_arg(url)))
+}
+
+/// `mailto:` / `tel:` link report JSON (see `contact_link_report`); pass
+/// null or "" for `link_text` when the shown text is unknown.
+///
+/// # Safety
+/// Both arguments are null or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_analyze_contact_link(href: *const c_char, link_text: *const c_char) -> *mut c_char {
+    json_out(|| vc::contact_link_report(str_arg(href), str_arg(link_text)))
+}
+
+/// Host/SNI/Host-header consistency report JSON (see `check_host_consistency`);
+/// pass null or "" for a value the gateway didn't see.
+///
+/// # Safety
+/// Each argument is null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_check_host_consistency(url_host: *const c_char, sni: *const c_char, host_header: *const c_char) -> *mut c_char {
+    json_out(|| vc::check_host_consistency(str_arg(url_host), str_arg(sni), str_arg(host_header)))
+}
+
+/// Release a string returned by any `vigilant_analyze_*` or `vigilant_check_*` call.
+///
+/// # Safety
+/// `s` is null or a pointer previously returned by this library, freed at most once.
+#[no_mangle]
+pub unsafe extern "C" fn vigilant_string_free(s: *mut c_char) {
+    if !s.is_null() { drop(CString::from_raw(s)); }
+}
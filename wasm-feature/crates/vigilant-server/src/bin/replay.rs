@@ -0,0 +1,60 @@
+// wasm-feature/crates/vigilant-server/src/bin/replay.rs
+// Browser Vigilant — `vigilant-replay [--profile P] [--changed-only] [--fail-on-lowered]`
+// Re-scores archived detection bundles (JSONL on stdin) with this build and
+// writes one result per bundle to stdout; the verdict-change summary goes to
+// stderr as JSON. With `--fail-on-lowered` the exit code is 1 when any
+// incident now gets a weaker verdict than it did, so a release pipeline can
+// gate on it:
+//
+//   vigilant-replay --changed-only --fail-on-lowered < incidents.jsonl > replay.jsonl
+
+use std::io::{self, BufWriter};
+use std::process::ExitCode;
+use vigilant_core as vc;
+use vigilant_core::ToJson;
+use vigilant_server::replay::replay;
+
+struct Options {
+    profile:         vc::Profile,
+    changed_only:    bool,
+    fail_on_lowered: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut o = Options { profile: vc::Profile::default(), changed_only: false, fail_on_lowered: false };
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--profile" => {
+                let p = args.next().ok_or_else(|| format!("{} needs a value", flag))?;
+                o.profile = vc::Profile::parse(&p).ok_or_else(|| format!("--profile: unknown profile {:?}", p))?;
+            }
+            "--changed-only" => o.changed_only = true,
+            "--fail-on-lowered" => o.fail_on_lowered = true,
+            _ => return Err(format!("unknown argument {:?}", flag)),
+        }
+    }
+    Ok(o)
+}
+
+fn main() -> ExitCode {
+    let o = match parse_args(std::env::args().skip(1)) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("vigilant-replay: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let analyzer = vc::Analyzer::builder().profile(o.profile).build();
+    let out = BufWriter::new(io::stdout().lock());
+    match replay(io::stdin().lock(), &analyzer, out, o.changed_only) {
+        Ok(s) => {
+            eprintln!("{}", s.to_json());
+            if o.fail_on_lowered && s.lowered > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+        }
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("vigilant-replay: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// wasm-feature/crates/vigilant-server/src/bin/stream.rs
+// Browser Vigilant — `vigilant-stream [--workers N] [--in-flight N] [--profile P]`
+// Scores URL events from a message queue on a worker pool. Speaks JSONL on
+// stdin/stdout (the same line format as `extract_stream`) so it sits between
+// the queue's own CLI tools rather than linking a client library:
+//
+//   kcat -C -b kafka:9092 -t proxy-urls -u | vigilant-stream | kcat -P -b kafka:9092 -t verdicts
+//   nsq_tail --topic=proxy-urls --lookupd-http-address=nsqlookupd:4161 | vigilant-stream | to_nsq --topic=verdicts
+//
+// Backpressure: at most `--in-flight` events are read but not yet written.
+// When the sink stalls, reading stops, the pipe fills and the consumer stops
+// fetching. Verdicts come out in input order, so a consumer committing
+// offsets after each output line never skips an unscored event. A line
+// whose scoring panics is written as `{ line, error }` in its place.
+// Shutdown: when the consumer exits (stdin closes) every event already read
+// is scored and written before the process exits 0; counts go to stderr.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use vigilant_core as vc;
+use vigilant_core::json::Json;
+
+struct Options {
+    workers:   usize,
+    in_flight: usize,
+    profile:   vc::Profile,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let workers = thread::available_parallelism().map_or(2, |n| n.get());
+    let mut o = Options { workers, in_flight: 1024, profile: vc::Profile::default() };
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", flag));
+        match flag.as_str() {
+            "--workers" => o.workers = value()?.parse().map_err(|_| "--workers: expected a number".to_string())?,
+            "--in-flight" => o.in_flight = value()?.parse().map_err(|_| "--in-flight: expected a number".to_string())?,
+            "--profile" => {
+                let p = value()?;
+                o.profile = vc::Profile::parse(&p).ok_or_else(|| format!("--profile: unknown profile {:?}", p))?;
+            }
+            _ => return Err(format!("unknown argument {:?}", flag)),
+        }
+    }
+    o.workers = o.workers.max(1);
+    o.in_flight = o.in_flight.max(o.workers);
+    Ok(o)
+}
+
+/// An input line and its 0-based position.
+type Job = (u64, Vec<u8>, bool);
+
+/// Scores one input line: `vc::score_line`, with the 1-based line number.
+type Score = fn(&vc::Analyzer, u64, &[u8], bool) -> Option<Json>;
+
+/// `score` on line `seq`; a panic gives `{ line, error }` so the line's
+/// slot is still filled and the writer doesn't wait on it forever.
+fn score_guarded(score: Score, analyzer: &vc::Analyzer, seq: u64, line: &[u8], cut: bool) -> Option<Json> {
+    panic::catch_unwind(AssertUnwindSafe(|| score(analyzer, seq + 1, line, cut)))
+        .unwrap_or_else(|_| Some(Json::obj().with("line", seq + 1).with("error", "analysis failed")))
+}
+
+fn worker(analyzer: Arc<vc::Analyzer>, jobs: Arc<Mutex<Receiver<Job>>>, results: mpsc::Sender<(u64, Option<Json>)>, score: Score) {
+    loop {
+        let next = jobs.lock().map_err(|_| ()).and_then(|rx| rx.recv().map_err(|_| ()));
+        let Ok((seq, line, cut)) = next else { return };
+        if results.send((seq, score_guarded(score, &analyzer, seq, &line, cut))).is_err() { return; }
+    }
+}
+
+/// Write results in input order, returning one credit per line written.
+/// Dropping `credits` on failure is what stops the reader.
+fn writer<W: Write>(results: Receiver<(u64, Option<Json>)>, credits: SyncSender<()>, out: W) -> io::Result<vc::StreamStats> {
+    let mut out = BufWriter::new(out);
+    let mut stats = vc::StreamStats::default();
+    let mut pending = BTreeMap::new();
+    let mut next = 0u64;
+    while let Ok((seq, r)) = results.recv() {
+        pending.insert(seq, r);
+        while let Some(r) = pending.remove(&next) {
+            if let Some(r) = r {
+                stats.lines += 1;
+                if r.get("error").is_some() { stats.errors += 1 } else { stats.scored += 1 }
+                writeln!(out, "{}", r)?;
+            }
+            next += 1;
+            let _ = credits.send(());
+        }
+        if pending.is_empty() { out.flush()?; }
+    }
+    out.flush()?;
+    Ok(stats)
+}
+
+fn run<R: BufRead, W: Write + Send>(o: &Options, mut input: R, output: W, score: Score) -> io::Result<vc::StreamStats> {
+    let analyzer = Arc::new(vc::Analyzer::builder().profile(o.profile).build());
+    let (job_tx, job_rx) = mpsc::channel::<Job>();
+    let job_rx = Arc::new(Mutex::new(job_rx));
+    let (res_tx, res_rx) = mpsc::channel();
+    let (credit_tx, credit_rx) = mpsc::sync_channel(o.in_flight);
+    for _ in 0..o.in_flight { let _ = credit_tx.send(()); }
+
+    let workers: Vec<_> = (0..o.workers)
+        .map(|_| {
+            let (analyzer, jobs, results) = (Arc::clone(&analyzer), Arc::clone(&job_rx), res_tx.clone());
+            thread::spawn(move || worker(analyzer, jobs, results, score))
+        })
+        .collect();
+    drop(res_tx);
+    thread::scope(|s| {
+        let writer = s.spawn(move || writer(res_rx, credit_tx, output));
+
+        let mut read_err = None;
+        let mut seq = 0u64;
+        // a closed credit channel means the writer gave up
+        while credit_rx.recv().is_ok() {
+            let mut buf = Vec::new();
+            match vc::read_capped(&mut input, &mut buf, vc::MAX_LINE_BYTES) {
+                Ok(Some(cut)) => {
+                    if job_tx.send((seq, buf, cut)).is_err() { break; }
+                    seq += 1;
+                }
+                Ok(None) => break,
+                Err(e) => { read_err = Some(e); break; }
+            }
+        }
+        drop(job_tx);
+        for w in workers { let _ = w.join(); }
+        let stats = writer.join().map_err(|_| io::Error::other("writer panicked"))??;
+        match read_err {
+            Some(e) => Err(e),
+            None => Ok(stats),
+        }
+    })
+}
+
+fn main() -> ExitCode {
+    let o = match parse_args(std::env::args().skip(1)) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("vigilant-stream: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&o, io::stdin().lock(), io::stdout(), vc::score_line) {
+        Ok(s) => {
+            eprintln!("vigilant-stream: {} lines, {} scored, {} errors", s.lines, s.scored, s.errors);
+            ExitCode::SUCCESS
+        }
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("vigilant-stream: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn score_or_panic(analyzer: &vc::Analyzer, n: u64, line: &[u8], cut: bool) -> Option<Json> {
+        if line == b"boom" { panic!("analysis bug on line {}", n) }
+        vc::score_line(analyzer, n, line, cut)
+    }
+
+    #[test]
+    fn a_panicking_line_keeps_its_slot_and_the_rest_are_written() {
+        let mut input: Vec<String> = (0..21).map(|i| format!("https://site{}.example/", i)).collect();
+        input[4] = "boom".to_string();
+        input[9] = "İİ://x".to_string();
+        let input = input.join("\n");
+        let o = Options { workers: 2, in_flight: 4, profile: vc::Profile::default() };
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut out = Vec::new();
+            let stats = run(&o, input.as_bytes(), &mut out, score_or_panic);
+            let _ = tx.send((stats.map_err(|e| e.to_string()), out));
+        });
+        let (stats, out) = rx.recv_timeout(Duration::from_secs(30)).expect("stream hung on a panicking line");
+        assert_eq!(stats, Ok(vc::StreamStats { lines: 21, scored: 20, errors: 1 }));
+        let rows: Vec<Json> = String::from_utf8(out).unwrap().lines().map(|l| Json::parse(l).unwrap()).collect();
+        let lines: Vec<f64> = rows.iter().filter_map(|r| r.get("line").and_then(Json::as_f64)).collect();
+        assert_eq!(lines, (1..=21).map(f64::from).collect::<Vec<_>>());
+        assert_eq!(rows[4].str_field("error"), "analysis failed");
+        assert_eq!(rows[9].str_field("url"), "İİ://x");
+    }
+}
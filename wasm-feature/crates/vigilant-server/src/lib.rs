@@ -0,0 +1,329 @@
+// wasm-feature/crates/vigilant-server/src/lib.rs
+// Browser Vigilant — the core analyzer over a small HTTP/JSON API for teams
+// that can't embed the library. Same engine, same tables, so verdicts match
+// the extension exactly. std-only: one acceptor hands connections to a fixed
+// worker pool through a bounded queue; a full queue answers 503 instead of
+// piling up. gRPC isn't offered — there is no protobuf runtime in the
+// workspace — and every call maps onto the JSON routes one to one.
+//
+//   GET  /healthz           { ok, failed }            (503 if the self-test fails)
+//   GET  /v1/capabilities   capabilities report
+//   GET  /v1/data-manifest  embedded tables with entry counts and sources
+//   POST /v1/url            { url }                    -> UrlReport
+//   POST /v1/urls           { urls: [..] }             -> { results: [UrlReport | { url, error }] }
+//   POST /v1/oauth          { url }                    -> oauth report
+//   POST /v1/page           PageSnapshot               -> Report
+//   POST /v1/cert           CertInfo                   -> Report
+//   POST /v1/composite      Evidence                   -> Report
+//   POST /v1/mail           message JSON               -> mail report
+//   POST /v1/host-consistency { url_host?, sni?, host_header? } -> host consistency report
+//
+// A request whose analysis panics gets 500 and the worker carries on; in a
+// batch only the failing URL's slot holds `{ url, error }`.
+
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use vigilant_core as vc;
+use vigilant_core::json::Json;
+use vigilant_core::{FromJson, ToJson};
+
+pub mod replay;
+
+/// Largest request body accepted; bigger ones get 413.
+pub const MAX_BODY_BYTES: usize = 4 << 20;
+/// Most URLs one `/v1/urls` call may carry.
+pub const MAX_BATCH: usize = 1000;
+/// Batches at least this long are split across threads.
+const PARALLEL_BATCH: usize = 64;
+const MAX_HEADER_BYTES: usize = 16 << 10;
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    pub addr:    String,
+    /// Threads serving connections.
+    pub workers: usize,
+    /// Accepted connections allowed to wait for a worker.
+    pub queue:   usize,
+    pub profile: vc::Profile,
+}
+
+impl Default for ServerConfig {
+    fn default() -> ServerConfig {
+        ServerConfig { addr: "127.0.0.1:8787".to_string(), workers: 4, queue: 64, profile: vc::Profile::default() }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body:   Json,
+}
+
+impl Response {
+    fn ok(body: Json) -> Response { Response { status: 200, body } }
+
+    fn error(status: u16, message: impl Into<String>) -> Response {
+        Response { status, body: Json::obj().with("error", message.into()) }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK", 400 => "Bad Request", 404 => "Not Found", 405 => "Method Not Allowed",
+        413 => "Payload Too Large", 500 => "Internal Server Error", 503 => "Service Unavailable", _ => "Error",
+    }
+}
+
+fn url_arg(body: &str) -> Result<String, Response> {
+    let v = Json::parse(body).map_err(|e| Response::error(400, e))?;
+    v.get("url").and_then(Json::as_str).map(str::to_string).ok_or_else(|| Response::error(400, "url: expected a string"))
+}
+
+/// Stand-in for the report of a URL whose analysis panicked.
+fn failed(url: &str) -> Json { Json::obj().with("url", url).with("error", "analysis failed") }
+
+/// Scores one URL of a batch: the analyzer's report outside the tests.
+type Score<'a> = &'a (dyn Fn(&str) -> Json + Sync);
+
+/// `score(url)`, or [`failed`] when it panics.
+fn score_one(score: Score, url: &str) -> Json {
+    panic::catch_unwind(AssertUnwindSafe(|| score(url))).unwrap_or_else(|_| failed(url))
+}
+
+/// One result per URL, in order, so `results[i]` always answers `urls[i]`.
+fn score_batch(analyzer: &vc::Analyzer, urls: &[String]) -> Vec<Json> {
+    score_batch_with(urls, &|u| analyzer.url(u).to_json())
+}
+
+fn score_batch_with(urls: &[String], score: Score) -> Vec<Json> {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    if urls.len() < PARALLEL_BATCH || threads < 2 {
+        return urls.iter().map(|u| score_one(score, u)).collect();
+    }
+    let chunk = urls.len().div_ceil(threads);
+    thread::scope(|s| {
+        let parts: Vec<_> = urls.chunks(chunk)
+            .map(|part| (part, s.spawn(move || part.iter().map(|u| score_one(score, u)).collect::<Vec<_>>())))
+            .collect();
+        parts.into_iter().flat_map(|(part, h)| h.join().unwrap_or_else(|_| part.iter().map(|u| failed(u)).collect())).collect()
+    })
+}
+
+fn batch(analyzer: &vc::Analyzer, body: &str) -> Result<Response, Response> {
+    let v = Json::parse(body).map_err(|e| Response::error(400, e))?;
+    let list = v.get("urls").and_then(Json::as_array).ok_or_else(|| Response::error(400, "urls: expected an array"))?;
+    if list.len() > MAX_BATCH { return Err(Response::error(413, format!("urls: at most {} per call", MAX_BATCH))); }
+    let urls = list.iter().enumerate()
+        .map(|(i, u)| u.as_str().map(str::to_string).ok_or_else(|| Response::error(400, format!("urls[{}]: expected a string", i))))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Response::ok(Json::obj().with("results", Json::Arr(score_batch(analyzer, &urls)))))
+}
+
+/// Optional string field `key`; absent or null reads as "".
+fn opt_str(v: &Json, key: &str) -> Result<String, Response> {
+    match v.get(key) {
+        None | Some(Json::Null) => Ok(String::new()),
+        Some(Json::Str(s)) => Ok(s.clone()),
+        Some(_) => Err(Response::error(400, format!("{}: expected a string", key))),
+    }
+}
+
+fn host_consistency(body: &str) -> Result<Response, Response> {
+    let v = Json::parse(body).map_err(|e| Response::error(400, e))?;
+    let (url_host, sni, host_header) = (opt_str(&v, "url_host")?, opt_str(&v, "sni")?, opt_str(&v, "host_header")?);
+    Ok(Response::ok(vc::check_host_consistency(&url_host, &sni, &host_header)))
+}
+
+fn parsed<T: FromJson>(body: &str) -> Result<T, Response> {
+    T::from_json_str(body).map_err(|e| Response::error(400, e.to_string()))
+}
+
+/// `respond()`, or 500 when it panics, so a request the analyzer can't
+/// handle costs one response rather than a pool worker.
+fn guarded(respond: impl FnOnce() -> Response) -> Response {
+    panic::catch_unwind(AssertUnwindSafe(respond)).unwrap_or_else(|_| Response::error(500, "analysis failed"))
+}
+
+/// Route one request. Transport-free, so other front ends can reuse it.
+/// Answers 500 when the analysis panics.
+pub fn handle(analyzer: &vc::Analyzer, method: &str, path: &str, body: &str) -> Response {
+    guarded(|| route(analyzer, method, path, body))
+}
+
+fn route(analyzer: &vc::Analyzer, method: &str, path: &str, body: &str) -> Response {
+    let path = path.split('?').next().unwrap_or("");
+    let result = match (method, path) {
+        ("GET", "/healthz") => {
+            let t = vc::self_test();
+            let ok = t.get("ok").and_then(Json::as_bool).unwrap_or(false);
+            let body = Json::obj().with("ok", ok).with("failed", t.get("failed").cloned().unwrap_or(Json::Num(0.0)));
+            Ok(Response { status: if ok { 200 } else { 503 }, body })
+        }
+        ("GET", "/v1/capabilities") => Ok(Response::ok(vc::capabilities_report(analyzer))),
+        ("GET", "/v1/data-manifest") => Ok(Response::ok(vc::data_manifest())),
+        ("POST", "/v1/url") => url_arg(body).map(|u| Response::ok(analyzer.url(&u).to_json())),
+        ("POST", "/v1/urls") => batch(analyzer, body),
+        ("POST", "/v1/oauth") => url_arg(body).map(|u| Response::ok(vc::oauth_report(&u))),
+        ("POST", "/v1/page") => parsed::<vc::PageSnapshot>(body).map(|p| Response::ok(analyzer.page(&p).to_json())),
+        ("POST", "/v1/cert") => parsed::<vc::CertInfo>(body).map(|c| Response::ok(analyzer.cert(&c).to_json())),
+        ("POST", "/v1/composite") => parsed::<vc::Evidence>(body).map(|e| Response::ok(analyzer.composite(&e).to_json())),
+        ("POST", "/v1/mail") => Ok(Response::ok(analyzer.mail(body))),
+        ("POST", "/v1/host-consistency") => host_consistency(body),
+        (_, "/healthz" | "/v1/capabilities" | "/v1/data-manifest" | "/v1/url" | "/v1/urls" | "/v1/oauth" | "/v1/page" | "/v1/cert" | "/v1/composite" | "/v1/mail"
+            | "/v1/host-consistency") => {
+            Err(Response::error(405, format!("{} not allowed on {}", method, path)))
+        }
+        _ => Err(Response::error(404, format!("no route for {}", path))),
+    };
+    result.unwrap_or_else(|e| e)
+}
+
+struct Request {
+    method: String,
+    path:   String,
+    body:   String,
+}
+
+fn find(hay: &[u8], needle: &[u8]) -> Option<usize> { hay.windows(needle.len()).position(|w| w == needle) }
+
+/// Read one HTTP/1.1 request; `Err` carries the response to send instead.
+fn read_request(stream: &mut TcpStream) -> Result<Request, Response> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(i) = find(&buf, b"\r\n\r\n") { break i; }
+        if buf.len() > MAX_HEADER_BYTES { return Err(Response::error(413, "headers too large")); }
+        let n = stream.read(&mut chunk).map_err(|e| Response::error(400, e.to_string()))?;
+        if n == 0 { return Err(Response::error(400, "connection closed mid-request")); }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+    let head = String::from_utf8_lossy(&buf[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut start = lines.next().unwrap_or("").split(' ');
+    let (method, path) = (start.next().unwrap_or("").to_string(), start.next().unwrap_or("").to_string());
+    if method.is_empty() || !path.starts_with('/') { return Err(Response::error(400, "malformed request line")); }
+    let length = lines
+        .filter_map(|l| l.split_once(':'))
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case("content-length"))
+        .map(|(_, v)| v.trim().parse::<usize>().map_err(|_| Response::error(400, "bad content-length")))
+        .transpose()?
+        .unwrap_or(0);
+    if length > MAX_BODY_BYTES { return Err(Response::error(413, format!("body over {} bytes", MAX_BODY_BYTES))); }
+    let mut body = buf.split_off(head_end + 4);
+    body.truncate(length);
+    if body.len() < length {
+        let mut rest = vec![0u8; length - body.len()];
+        stream.read_exact(&mut rest).map_err(|e| Response::error(400, e.to_string()))?;
+        body.extend_from_slice(&rest);
+    }
+    let body = String::from_utf8(body).map_err(|_| Response::error(400, "body is not UTF-8"))?;
+    Ok(Request { method, path, body })
+}
+
+fn write_response(stream: &mut TcpStream, r: &Response) -> io::Result<()> {
+    let body = r.body.to_string();
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        r.status, reason(r.status), body.len(), body)?;
+    stream.flush()
+}
+
+/// Bound every read and write on `stream` by [`IO_TIMEOUT`], so a client
+/// that stops reading can't hold a worker or the accept loop.
+fn set_timeouts(stream: &TcpStream) {
+    let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+}
+
+fn serve_connection(analyzer: &vc::Analyzer, mut stream: TcpStream) {
+    set_timeouts(&stream);
+    let response = match read_request(&mut stream) {
+        Ok(req) => handle(analyzer, &req.method, &req.path, &req.body),
+        Err(r) => r,
+    };
+    let _ = write_response(&mut stream, &response);
+}
+
+fn worker(analyzer: Arc<vc::Analyzer>, queue: Arc<Mutex<Receiver<TcpStream>>>) {
+    loop {
+        // hold the lock only while waiting, not while serving
+        let next = queue.lock().map_err(|_| ()).and_then(|rx| rx.recv().map_err(|_| ()));
+        match next {
+            // a panic here drops the connection, never the worker
+            Ok(stream) => { let _ = panic::catch_unwind(AssertUnwindSafe(|| serve_connection(&analyzer, stream))); }
+            Err(()) => return,
+        }
+    }
+}
+
+/// Bind `config.addr` and serve until the listener fails.
+pub fn serve(config: &ServerConfig) -> io::Result<()> {
+    let listener = TcpListener::bind(&config.addr)?;
+    let analyzer = Arc::new(vc::Analyzer::builder().profile(config.profile).build());
+    let (tx, rx) = mpsc::sync_channel::<TcpStream>(config.queue.max(1));
+    let rx = Arc::new(Mutex::new(rx));
+    for _ in 0..config.workers.max(1) {
+        let (analyzer, rx) = (Arc::clone(&analyzer), Arc::clone(&rx));
+        thread::spawn(move || worker(analyzer, rx));
+    }
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        match tx.try_send(stream) {
+            Ok(()) => {}
+            Err(TrySendError::Full(mut s)) => {
+                set_timeouts(&s);
+                let _ = write_response(&mut s, &Response::error(503, "server busy, retry"));
+            }
+            Err(TrySendError::Disconnected(_)) => return Err(io::Error::other("worker pool exited")),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_results_answer_urls_in_order() {
+        let analyzer = vc::Analyzer::default();
+        let urls: Vec<String> = (0..PARALLEL_BATCH * 2 + 3).map(|i| format!("https://site.example/{}", "a".repeat(i))).collect();
+        let results = score_batch(&analyzer, &urls);
+        assert_eq!(results.len(), urls.len());
+        for (u, r) in urls.iter().zip(&results) { assert_eq!(*r, analyzer.url(u).to_json(), "{}", u); }
+    }
+
+    fn score_or_panic(analyzer: &vc::Analyzer) -> impl Fn(&str) -> Json + Sync + '_ {
+        move |u| if u.ends_with("/boom") { panic!("analysis bug on {}", u) } else { analyzer.url(u).to_json() }
+    }
+
+    #[test]
+    fn a_panicking_url_keeps_its_slot_in_the_batch() {
+        let analyzer = vc::Analyzer::default();
+        for len in [3, PARALLEL_BATCH * 2 + 3] {
+            let mut urls: Vec<String> = (0..len).map(|i| format!("https://site.example/{}", i)).collect();
+            urls[1] = "https://site.example/boom".to_string();
+            let results = score_batch_with(&urls, &score_or_panic(&analyzer));
+            assert_eq!(results.len(), len);
+            assert_eq!(results[1], failed(&urls[1]));
+            for (u, r) in urls.iter().zip(&results).filter(|(u, _)| !u.ends_with("/boom")) { assert_eq!(*r, analyzer.url(u).to_json(), "{}", u); }
+        }
+    }
+
+    #[test]
+    fn a_panicking_request_answers_500() {
+        let r = guarded(|| panic!("analysis bug"));
+        assert_eq!((r.status, r.body.str_field("error")), (500, "analysis failed"));
+        let analyzer = vc::Analyzer::default();
+        let r = handle(&analyzer, "POST", "/v1/url", r#"{"url": "İİ://x"}"#);
+        assert_eq!((r.status, r.body), (200, analyzer.url("İİ://x").to_json()));
+        let r = handle(&analyzer, "POST", "/v1/urls", r#"{"urls": ["İİ://x", "https://site.example/"]}"#);
+        assert_eq!(r.status, 200);
+        assert!(r.body.get("results").and_then(Json::as_array).is_some_and(|a| a.iter().all(|x| x.get("error").is_none())));
+    }
+}
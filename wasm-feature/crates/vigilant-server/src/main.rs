@@ -0,0 +1,42 @@
+// wasm-feature/crates/vigilant-server/src/main.rs
+// Browser Vigilant — `vigilant-server [--addr A] [--workers N] [--queue N] [--profile P]`
+
+use std::process::ExitCode;
+use vigilant_core::Profile;
+use vigilant_server::{serve, ServerConfig};
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<ServerConfig, String> {
+    let mut config = ServerConfig::default();
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", flag));
+        match flag.as_str() {
+            "--addr" => config.addr = value()?,
+            "--workers" => config.workers = value()?.parse().map_err(|_| "--workers: expected a number".to_string())?,
+            "--queue" => config.queue = value()?.parse().map_err(|_| "--queue: expected a number".to_string())?,
+            "--profile" => {
+                let p = value()?;
+                config.profile = Profile::parse(&p).ok_or_else(|| format!("--profile: unknown profile {:?}", p))?;
+            }
+            _ => return Err(format!("unknown argument {:?}", flag)),
+        }
+    }
+    Ok(config)
+}
+
+fn main() -> ExitCode {
+    let config = match parse_args(std::env::args().skip(1)) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("vigilant-server: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    eprintln!("vigilant-server: listening on {} ({} workers, {} profile)", config.addr, config.workers, config.profile.as_str());
+    match serve(&config) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("vigilant-server: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
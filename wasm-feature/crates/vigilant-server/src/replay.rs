@@ -0,0 +1,174 @@
+// wasm-feature/crates/vigilant-server/src/replay.rs
+// Browser Vigilant — re-scoring the incident archive. Each archived bundle
+// holds what the extension saw when it raised a detection (the URL, the page
+// snapshot, the response headers) and the verdict it gave then. Replaying
+// the archive through the current engine before a release shows which past
+// incidents a candidate would now miss, which it would block harder, and
+// which flags came or went on the way.
+//
+// Input is JSONL, one bundle per line:
+//   { id?, url, snapshot?: PageSnapshot, headers?: { name: value },
+//     recorded?: { verdict, score?, flags? } | "verdict" }
+// A `Content-Security-Policy` header fills `snapshot.csp` when the snapshot
+// didn't capture one; a bundle with headers but no such header had none.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use vigilant_core as vc;
+use vigilant_core::json::Json;
+use vigilant_core::{FromJson, ToJson};
+
+/// Longest bundle line read; snapshots carry page text, so well above
+/// [`vc::MAX_LINE_BYTES`].
+pub const MAX_BUNDLE_BYTES: usize = crate::MAX_BODY_BYTES;
+
+/// Verdict-change counts over one replay.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    /// Non-blank input lines seen.
+    pub bundles:    u64,
+    pub errors:     u64,
+    /// Bundles with no recorded verdict to compare against.
+    pub unrecorded: u64,
+    pub unchanged:  u64,
+    /// Verdict now stricter than recorded.
+    pub raised:     u64,
+    /// Verdict now weaker than recorded: an incident the candidate would miss.
+    pub lowered:    u64,
+    /// `(recorded, current)` → bundles, changed pairs only.
+    pub transitions: BTreeMap<(vc::Verdict, vc::Verdict), u64>,
+}
+
+impl ReplayStats {
+    fn count(&mut self, result: &Json) {
+        self.bundles += 1;
+        if result.get("error").is_some() { self.errors += 1; return; }
+        let current = vc::Verdict::parse(result.str_field("verdict"));
+        match (result.get("recorded").and_then(|r| vc::Verdict::parse(r.str_field("verdict"))), current) {
+            (Some(a), Some(b)) if a == b => self.unchanged += 1,
+            (Some(a), Some(b)) => {
+                if b > a { self.raised += 1 } else { self.lowered += 1 }
+                *self.transitions.entry((a, b)).or_default() += 1;
+            }
+            _ => self.unrecorded += 1,
+        }
+    }
+}
+
+impl ToJson for ReplayStats {
+    fn to_json(&self) -> Json {
+        let transitions: Vec<Json> = self.transitions.iter()
+            .map(|((a, b), n)| Json::obj().with("from", a.as_str()).with("to", b.as_str()).with("count", *n))
+            .collect();
+        Json::obj()
+            .with("bundles", self.bundles)
+            .with("errors", self.errors)
+            .with("unrecorded", self.unrecorded)
+            .with("unchanged", self.unchanged)
+            .with("changed", self.raised + self.lowered)
+            .with("raised", self.raised)
+            .with("lowered", self.lowered)
+            .with("transitions", transitions)
+    }
+}
+
+fn header<'a>(headers: &'a Json, name: &str) -> Option<&'a str> {
+    headers.as_object()?.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).and_then(|(_, v)| v.as_str())
+}
+
+/// The recorded outcome as `{ verdict, score?, flags? }`.
+fn recorded(bundle: &Json) -> Result<Option<Json>, String> {
+    match bundle.get("recorded") {
+        None | Some(Json::Null) => Ok(None),
+        Some(Json::Str(v)) => Ok(Some(Json::obj().with("verdict", v.as_str()))),
+        Some(r @ Json::Obj(_)) => Ok(Some(r.clone())),
+        Some(_) => Err("recorded: expected a verdict or an object".to_string()),
+    }
+}
+
+/// Run bundle `bundle` through the URL and page analyzers. Returns `{ id?,
+/// url, recorded?, verdict, score, flags, url_report, page_report?, diff? }`
+/// where `verdict` and `score` are the stricter of the two reports and
+/// `diff` (from [`vc::diff_reports`]) is present when a recorded outcome
+/// exists; fails on a malformed bundle.
+pub fn replay_bundle(analyzer: &vc::Analyzer, bundle: &Json) -> Result<Json, String> {
+    let url = bundle.get("url").and_then(Json::as_str).ok_or("url: expected a string")?;
+    let recorded = recorded(bundle)?;
+    let url_report = analyzer.url(url);
+    let mut verdict = url_report.verdict;
+    let mut score = url_report.risk;
+    let mut flags: Vec<String> = url_report.flags.iter().map(|f| f.to_string()).collect();
+    let mut out = Json::obj();
+    if let Some(id) = bundle.get("id") { out = out.with("id", id.clone()); }
+    out = out.with("url", url).with("recorded", recorded.clone());
+
+    let page = match bundle.get("snapshot").filter(|s| !matches!(s, Json::Null)) {
+        None => None,
+        Some(s) => {
+            let mut s = s.clone();
+            if s.get("url").is_none() { s = s.with("url", url); }
+            let mut snapshot = vc::PageSnapshot::from_json(&s, "snapshot").map_err(|e| e.to_string())?;
+            if let Some(headers) = bundle.get("headers").filter(|h| snapshot.csp.is_none() && h.as_object().is_some()) {
+                snapshot.csp = Some(header(headers, "content-security-policy").unwrap_or("").to_string());
+            }
+            let report = analyzer.page(&snapshot);
+            verdict = verdict.max(report.verdict);
+            score = score.max(report.score);
+            flags.extend(report.flags.iter().filter(|f| !flags.contains(f)).cloned().collect::<Vec<_>>());
+            Some(report.to_json())
+        }
+    };
+    let current = Json::obj().with("verdict", verdict.as_str()).with("score", score).with("flags", flags.clone());
+    let diff = recorded.map(|r| vc::diff_reports(&r, &current));
+    Ok(out
+        .with("verdict", verdict.as_str())
+        .with("score", score)
+        .with("flags", flags)
+        .with("url_report", url_report.to_json())
+        .with("page_report", page)
+        .with("diff", diff))
+}
+
+/// Replay every bundle of `reader`, handing each result (or `{ line, error
+/// }`) to `on_result` with its 1-based `line`. Stops at the first I/O error
+/// from either side.
+pub fn replay_with<R: BufRead, F: FnMut(Json) -> io::Result<()>>(
+    mut reader: R,
+    analyzer: &vc::Analyzer,
+    mut on_result: F,
+) -> io::Result<ReplayStats> {
+    let mut stats = ReplayStats::default();
+    let mut buf = Vec::with_capacity(4096);
+    let mut n = 0u64;
+    while let Some(cut) = vc::read_capped(&mut reader, &mut buf, MAX_BUNDLE_BYTES)? {
+        n += 1;
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim();
+        if line.is_empty() { continue; }
+        let result = if cut { Err(format!("line exceeds {} bytes", MAX_BUNDLE_BYTES)) } else { Json::parse(line) }
+            .and_then(|b| replay_bundle(analyzer, &b));
+        let out = match result {
+            Ok(r) => {
+                let mut fields = vec![("line".to_string(), Json::from(n))];
+                fields.extend(r.as_object().unwrap_or(&[]).iter().cloned());
+                Json::Obj(fields)
+            }
+            Err(e) => Json::obj().with("line", n).with("error", e),
+        };
+        stats.count(&out);
+        on_result(out)?;
+    }
+    Ok(stats)
+}
+
+/// JSONL in, JSONL out (see [`replay_with`]); with `changed_only`, bundles
+/// whose verdict held are counted but not written.
+pub fn replay<R: BufRead, W: Write>(reader: R, analyzer: &vc::Analyzer, mut writer: W, changed_only: bool) -> io::Result<ReplayStats> {
+    let stats = replay_with(reader, analyzer, |r| {
+        let held = r.get("diff").and_then(|d| d.get("verdict")).and_then(|v| v.get("changed")) == Some(&Json::Bool(false));
+        if changed_only && held { return Ok(()); }
+        writeln!(writer, "{}", r)
+    })?;
+    writer.flush()?;
+    Ok(stats)
+}
@@ -0,0 +1,10 @@
+// wasm-feature/crates/vigilant-store/src/lib.rs
+// Browser Vigilant — persistent stores for native / CLI / server builds, in
+// place of round-tripping `State::export` JSON through the host. The SQLite
+// backend is behind the `sqlite` feature.
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteStore, StoreError, SCHEMA_VERSION};
@@ -0,0 +1,298 @@
+// wasm-feature/crates/vigilant-store/src/sqlite.rs
+// Browser Vigilant — SQLite store for reputation, first-seen, site templates
+// and the audit log. Keys are whatever `State` keys by, so a hash-only state
+// writes only digests; `meta.key_check` pins the privacy salt the rows were
+// written under. The audit log isn't part of `State`: it is only appended
+// here and read back with `audit_since`.
+
+use std::fmt;
+use std::path::Path;
+use rusqlite::{params, Connection, OptionalExtension};
+use vigilant_core::json::Json;
+use vigilant_core::{AuditEntry, AuditLog, SchemaError, State, Verdict};
+
+/// `PRAGMA user_version` after every migration has run.
+pub const SCHEMA_VERSION: i64 = MIGRATIONS.len() as i64;
+
+/// Migration `i` takes the file from version `i` to `i + 1`. Append only.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+     CREATE TABLE reputation (key TEXT PRIMARY KEY, score REAL NOT NULL, updated_ms REAL NOT NULL);
+     CREATE TABLE first_seen (key TEXT PRIMARY KEY, first_ms REAL NOT NULL);
+     CREATE TABLE audit (
+         id            INTEGER PRIMARY KEY,
+         timestamp_ms  REAL NOT NULL,
+         url_hash      TEXT NOT NULL,
+         verdict       TEXT NOT NULL,
+         score         REAL NOT NULL,
+         model_version TEXT NOT NULL
+     );
+     CREATE INDEX audit_time ON audit (timestamp_ms);",
+    "CREATE TABLE templates (site TEXT PRIMARY KEY, doc TEXT NOT NULL, updated_ms REAL NOT NULL);",
+];
+
+#[derive(Debug)]
+pub enum StoreError {
+    Sqlite(rusqlite::Error),
+    Schema(SchemaError),
+    /// The file was written under a different privacy mode or salt.
+    KeyMismatch,
+    /// The file comes from a newer build than this one.
+    TooNew(i64),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::Sqlite(e) => write!(f, "sqlite: {}", e),
+            StoreError::Schema(e) => write!(f, "{}", e),
+            StoreError::KeyMismatch => write!(f, "store was written under a different privacy mode or salt"),
+            StoreError::TooNew(v) => write!(f, "store schema {} is newer than supported {}", v, SCHEMA_VERSION),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(e: rusqlite::Error) -> StoreError { StoreError::Sqlite(e) }
+}
+
+impl From<SchemaError> for StoreError {
+    fn from(e: SchemaError) -> StoreError { StoreError::Schema(e) }
+}
+
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+fn rows(conn: &Connection, sql: &str) -> Result<Vec<(String, Json)>, StoreError> {
+    let mut stmt = conn.prepare(sql)?;
+    let out = stmt.query_map([], |r| Ok((r.get::<_, String>(0)?, Json::Num(r.get::<_, f64>(1)?))))?;
+    Ok(out.collect::<Result<_, _>>()?)
+}
+
+impl SqliteStore {
+    /// Open (creating if needed) and migrate the store at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<SqliteStore, StoreError> {
+        SqliteStore::with_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<SqliteStore, StoreError> { SqliteStore::with_connection(Connection::open_in_memory()?) }
+
+    fn with_connection(mut conn: Connection) -> Result<SqliteStore, StoreError> {
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        conn.pragma_update(None, "auto_vacuum", "INCREMENTAL")?;
+        let version: i64 = conn.pragma_query_value(None, "user_version", |r| r.get(0))?;
+        if version > SCHEMA_VERSION { return Err(StoreError::TooNew(version)); }
+        for (i, sql) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            let tx = conn.transaction()?;
+            tx.execute_batch(sql)?;
+            tx.pragma_update(None, "user_version", i as i64 + 1)?;
+            tx.commit()?;
+        }
+        Ok(SqliteStore { conn })
+    }
+
+    /// `PRAGMA user_version` of the open file.
+    pub fn schema_version(&self) -> Result<i64, StoreError> {
+        Ok(self.conn.pragma_query_value(None, "user_version", |r| r.get(0))?)
+    }
+
+    fn key_check(&self) -> Result<Option<String>, StoreError> {
+        Ok(self.conn.query_row("SELECT value FROM meta WHERE key = 'key_check'", [], |r| r.get(0)).optional()?)
+    }
+
+    /// Write reputation, first-seen and template entries from `state`,
+    /// stamping them `now_ms` for [`SqliteStore::compact`]. Returns rows written.
+    pub fn save_state(&mut self, state: &State, now_ms: f64) -> Result<usize, StoreError> {
+        let doc = state.export();
+        let check = doc.str_field("key_check").to_string();
+        if self.key_check()?.is_some_and(|k| k != check) { return Err(StoreError::KeyMismatch); }
+        let entries = |name: &str| doc.get(name).and_then(Json::as_object).unwrap_or(&[]).to_vec();
+        let tx = self.conn.transaction()?;
+        tx.execute("INSERT OR REPLACE INTO meta (key, value) VALUES ('key_check', ?1)", params![check])?;
+        let mut n = 0;
+        for (k, v) in entries("reputation") {
+            n += tx.execute("INSERT OR REPLACE INTO reputation (key, score, updated_ms) VALUES (?1, ?2, ?3)",
+                params![k, v.as_f64().unwrap_or(0.0), now_ms])?;
+        }
+        for (k, v) in entries("first_seen") {
+            n += tx.execute(
+                "INSERT INTO first_seen (key, first_ms) VALUES (?1, ?2)
+                 ON CONFLICT (key) DO UPDATE SET first_ms = min(first_ms, excluded.first_ms)",
+                params![k, v.as_f64().unwrap_or(0.0)])?;
+        }
+        for (site, t) in entries("templates") {
+            n += tx.execute("INSERT OR REPLACE INTO templates (site, doc, updated_ms) VALUES (?1, ?2, ?3)",
+                params![site, t.to_string(), now_ms])?;
+        }
+        tx.commit()?;
+        Ok(n)
+    }
+
+    /// Merge the stored reputation, first-seen and template rows into `state`
+    /// (see `State::import`); templates load only while the state is learning
+    /// them. Audit rows stay in the file, for [`SqliteStore::audit_since`].
+    pub fn load_state(&self, state: &mut State) -> Result<usize, StoreError> {
+        let mut stmt = self.conn.prepare("SELECT site, doc FROM templates")?;
+        let templates = stmt
+            .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|(site, doc)| Json::parse(&doc).map(|d| (site, d)).map_err(|e| SchemaError { path: "templates".to_string(), message: e }))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut doc = Json::obj()
+            .with("schema_version", vigilant_core::SCHEMA_VERSION)
+            .with("reputation", Json::Obj(rows(&self.conn, "SELECT key, score FROM reputation")?))
+            .with("first_seen", Json::Obj(rows(&self.conn, "SELECT key, first_ms FROM first_seen")?))
+            .with("templates", Json::Obj(templates));
+        if let Some(k) = self.key_check()? { doc = doc.with("key_check", k); }
+        Ok(state.import(&doc, "sqlite")?)
+    }
+
+    /// Append entries of `log` newer than the newest stored one, so saving
+    /// the same ring buffer repeatedly never duplicates rows.
+    pub fn append_audit(&mut self, log: &AuditLog) -> Result<usize, StoreError> {
+        let newest: Option<f64> = self.conn.query_row("SELECT max(timestamp_ms) FROM audit", [], |r| r.get(0))?;
+        let tx = self.conn.transaction()?;
+        let mut n = 0;
+        for e in log.entries().filter(|e| newest.is_none_or(|t| e.timestamp_ms > t)) {
+            n += tx.execute(
+                "INSERT INTO audit (timestamp_ms, url_hash, verdict, score, model_version) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![e.timestamp_ms, e.url_hash, e.verdict.as_str(), e.score as f64, e.model_version])?;
+        }
+        tx.commit()?;
+        Ok(n)
+    }
+
+    /// Audit entries at or after `since_ms`, oldest first.
+    pub fn audit_since(&self, since_ms: f64) -> Result<Vec<AuditEntry>, StoreError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT timestamp_ms, url_hash, verdict, score, model_version FROM audit WHERE timestamp_ms >= ?1 ORDER BY id")?;
+        let out = stmt.query_map(params![since_ms], |r| {
+            Ok(AuditEntry {
+                timestamp_ms:  r.get(0)?,
+                url_hash:      r.get(1)?,
+                verdict:       Verdict::parse(&r.get::<_, String>(2)?).unwrap_or(Verdict::Allow),
+                score:         r.get::<_, f64>(3)? as f32,
+                model_version: r.get(4)?,
+            })
+        })?;
+        Ok(out.collect::<Result<_, _>>()?)
+    }
+
+    /// Drop reputation and template rows not saved since `stale_before_ms`,
+    /// keep the newest `keep_audit` audit rows, then return freed pages to
+    /// the file system. Returns rows deleted.
+    pub fn compact(&mut self, stale_before_ms: f64, keep_audit: usize) -> Result<usize, StoreError> {
+        let tx = self.conn.transaction()?;
+        let mut n = tx.execute("DELETE FROM reputation WHERE updated_ms < ?1", params![stale_before_ms])?;
+        n += tx.execute("DELETE FROM templates WHERE updated_ms < ?1", params![stale_before_ms])?;
+        n += tx.execute(
+            "DELETE FROM audit WHERE id NOT IN (SELECT id FROM audit ORDER BY id DESC LIMIT ?1)",
+            params![keep_audit as i64])?;
+        tx.commit()?;
+        self.conn.execute_batch("PRAGMA incremental_vacuum; PRAGMA wal_checkpoint(TRUNCATE);")?;
+        Ok(n)
+    }
+
+    /// Forget everything, e.g. after switching privacy salt.
+    pub fn clear(&mut self) -> Result<(), StoreError> {
+        self.conn.execute_batch(
+            "DELETE FROM meta; DELETE FROM reputation; DELETE FROM first_seen; DELETE FROM templates; DELETE FROM audit;")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vigilant_core::Privacy;
+
+    fn state_with(host: &str, score: f32) -> State {
+        let mut s = State::new();
+        s.set_reputation(host, score);
+        s
+    }
+
+    #[test]
+    fn a_new_file_runs_every_migration() {
+        let store = SqliteStore::in_memory().unwrap();
+        assert_eq!(store.schema_version().unwrap(), SCHEMA_VERSION);
+        let tables: i64 = store.conn.query_row(
+            "SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name IN ('meta', 'reputation', 'first_seen', 'audit', 'templates')",
+            [], |r| r.get(0)).unwrap();
+        assert_eq!(tables, 5);
+    }
+
+    #[test]
+    fn an_older_file_migrates_forward() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATIONS[0]).unwrap();
+        conn.pragma_update(None, "user_version", 1i64).unwrap();
+        let store = SqliteStore::with_connection(conn).unwrap();
+        assert_eq!(store.schema_version().unwrap(), SCHEMA_VERSION);
+        let templates: i64 = store.conn.query_row("SELECT count(*) FROM templates", [], |r| r.get(0)).unwrap();
+        assert_eq!(templates, 0);
+    }
+
+    #[test]
+    fn a_newer_file_is_refused() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1).unwrap();
+        match SqliteStore::with_connection(conn) {
+            Err(StoreError::TooNew(v)) => assert_eq!(v, SCHEMA_VERSION + 1),
+            other => panic!("expected TooNew, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn state_under_another_salt_is_refused() {
+        let mut store = SqliteStore::in_memory().unwrap();
+        assert_eq!(store.save_state(&state_with("a.example", 0.5), 1000.0).unwrap(), 1);
+        let mut hashed = State::new();
+        hashed.set_privacy(Privacy::hashed(b"salt"));
+        hashed.set_reputation("b.example", 0.9);
+        assert!(matches!(store.save_state(&hashed, 2000.0), Err(StoreError::KeyMismatch)));
+        assert!(store.load_state(&mut hashed).is_err());
+        let mut plain = State::new();
+        assert_eq!(store.load_state(&mut plain).unwrap(), 1);
+        assert_eq!(plain.reputation("a.example"), Some(0.5));
+    }
+
+    #[test]
+    fn saving_the_same_audit_log_twice_adds_no_rows() {
+        let mut store = SqliteStore::in_memory().unwrap();
+        let mut log = AuditLog::default();
+        log.record(1000.0, "https://a.example/", Verdict::Allow, 0.1);
+        log.record(2000.0, "https://b.example/", Verdict::Block, 0.9);
+        assert_eq!(store.append_audit(&log).unwrap(), 2);
+        assert_eq!(store.append_audit(&log).unwrap(), 0);
+        log.record(3000.0, "https://c.example/", Verdict::Warn, 0.5);
+        assert_eq!(store.append_audit(&log).unwrap(), 1);
+        let rows = store.audit_since(0.0).unwrap();
+        assert_eq!(rows.iter().map(|e| e.timestamp_ms).collect::<Vec<_>>(), [1000.0, 2000.0, 3000.0]);
+        assert_eq!(rows[1].verdict, Verdict::Block);
+        assert_eq!(store.audit_since(2500.0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn compact_drops_stale_rows_and_old_audit_entries() {
+        let mut store = SqliteStore::in_memory().unwrap();
+        store.save_state(&state_with("old.example", 0.2), 1000.0).unwrap();
+        store.save_state(&state_with("new.example", 0.8), 5000.0).unwrap();
+        let mut log = AuditLog::default();
+        for t in [1000.0, 2000.0, 3000.0] { log.record(t, "https://a.example/", Verdict::Allow, 0.1); }
+        store.append_audit(&log).unwrap();
+        assert_eq!(store.compact(2000.0, 1).unwrap(), 3);
+        let mut state = State::new();
+        store.load_state(&mut state).unwrap();
+        assert_eq!(state.reputation("old.example"), None);
+        assert_eq!(state.reputation("new.example"), Some(0.8));
+        let audit = store.audit_since(0.0).unwrap();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].timestamp_ms, 3000.0);
+        assert_eq!(store.compact(2000.0, 1).unwrap(), 0);
+    }
+}
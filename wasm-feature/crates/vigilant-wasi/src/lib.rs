@@ -0,0 +1,131 @@
+// wasm-feature/crates/vigilant-wasi/src/lib.rs
+// Browser Vigilant — wasm32-wasip1 build for server-side sandboxed scoring
+// (Wasmtime behind URL-rewriting mail gateways). Exports follow the
+// component-model canonical ABI for the `scorer` world in wit/vigilant.wit,
+// lowered by hand so no bindings generator is needed:
+//
+//   cargo build -p vigilant-wasi --target wasm32-wasip1 --release
+//   wasm-tools component embed wit target/wasm32-wasip1/release/vigilant_wasi.wasm -o core.wasm
+//   wasm-tools component new core.wasm --adapt wasi_snapshot_preview1.reactor.wasm -o vigilant.component.wasm
+//
+// On wasm32 `usize` and pointers are 32-bit, so the `#[repr(C)]` return
+// areas below match the canonical layouts exactly. Native builds only exist
+// so the workspace compiles everywhere.
+
+use std::alloc::{alloc, dealloc, realloc, Layout};
+use std::ptr;
+use vigilant_core as vc;
+use vigilant_core::{FromJson, ToJson};
+
+/// `string` as the canonical ABI lays it out: pointer then byte length.
+#[repr(C)]
+pub struct WitStr {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl WitStr {
+    fn owned(s: String) -> WitStr {
+        let mut b = s.into_bytes().into_boxed_slice();
+        let w = WitStr { ptr: b.as_mut_ptr(), len: b.len() };
+        std::mem::forget(b);
+        w
+    }
+
+    /// Static strings are lent, never freed.
+    fn lent(s: &'static str) -> WitStr { WitStr { ptr: s.as_ptr() as *mut u8, len: s.len() } }
+
+    /// # Safety
+    /// `self` came from [`WitStr::owned`] and hasn't been freed.
+    unsafe fn free(self) {
+        if self.len > 0 { drop(Box::from_raw(ptr::slice_from_raw_parts_mut(self.ptr, self.len))); }
+    }
+}
+
+/// `url-score`: f32 at 0, verdict enum at 4, `list<string>` at 8.
+#[repr(C)]
+pub struct UrlScore {
+    risk:      f32,
+    verdict:   u8,
+    flags_ptr: *mut WitStr,
+    flags_len: usize,
+}
+
+/// `result<string, string>`: discriminant (0 ok, 1 err) at 0, payload at 4.
+#[repr(C)]
+pub struct StrResult {
+    tag:   u8,
+    value: WitStr,
+}
+
+/// Take ownership of a string argument the host wrote via `cabi_realloc`.
+///
+/// # Safety
+/// `ptr`/`len` describe a live allocation from `cabi_realloc` with align 1.
+unsafe fn take_string(ptr: *mut u8, len: usize) -> String {
+    if len == 0 { return String::new(); }
+    let bytes = Vec::from_raw_parts(ptr, len, len);
+    String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// Allocator the host uses to pass arguments into linear memory.
+///
+/// # Safety
+/// `old` is null or a block from this function of `old_size` bytes at `align`.
+#[no_mangle]
+pub unsafe extern "C" fn cabi_realloc(old: *mut u8, old_size: usize, align: usize, new_size: usize) -> *mut u8 {
+    if new_size == 0 {
+        if !old.is_null() && old_size > 0 { dealloc(old, Layout::from_size_align_unchecked(old_size, align)); }
+        return align as *mut u8;
+    }
+    let p = if old.is_null() || old_size == 0 {
+        alloc(Layout::from_size_align_unchecked(new_size, align))
+    } else {
+        realloc(old, Layout::from_size_align_unchecked(old_size, align), new_size)
+    };
+    if p.is_null() { std::process::abort(); }
+    p
+}
+
+/// `score-url: func(url: string) -> url-score`.
+///
+/// # Safety
+/// Called by the component runtime with a string lowered through `cabi_realloc`.
+#[export_name = "score-url"]
+pub unsafe extern "C" fn score_url(url_ptr: *mut u8, url_len: usize) -> *mut UrlScore {
+    let url = take_string(url_ptr, url_len);
+    let r = vc::Analyzer::shared().url(&url);
+    let mut flags: Box<[WitStr]> = r.flags.iter().map(|f| WitStr::lent(f)).collect();
+    let (flags_ptr, flags_len) = (flags.as_mut_ptr(), flags.len());
+    std::mem::forget(flags);
+    Box::into_raw(Box::new(UrlScore { risk: r.risk, verdict: r.verdict as u8, flags_ptr, flags_len }))
+}
+
+/// # Safety
+/// `ret` is the pointer the matching `score-url` call returned.
+#[export_name = "cabi_post_score-url"]
+pub unsafe extern "C" fn score_url_post(ret: *mut UrlScore) {
+    let r = Box::from_raw(ret);
+    if r.flags_len > 0 { drop(Box::from_raw(ptr::slice_from_raw_parts_mut(r.flags_ptr, r.flags_len))); }
+}
+
+/// `analyze-page: func(snapshot: string) -> result<string, string>`.
+///
+/// # Safety
+/// Called by the component runtime with a string lowered through `cabi_realloc`.
+#[export_name = "analyze-page"]
+pub unsafe extern "C" fn analyze_page(snapshot_ptr: *mut u8, snapshot_len: usize) -> *mut StrResult {
+    let src = take_string(snapshot_ptr, snapshot_len);
+    let out = match vc::PageSnapshot::from_json_str(&src) {
+        Ok(p) => StrResult { tag: 0, value: WitStr::owned(vc::Analyzer::shared().page(&p).to_json().to_string()) },
+        Err(e) => StrResult { tag: 1, value: WitStr::owned(e.to_string()) },
+    };
+    Box::into_raw(Box::new(out))
+}
+
+/// # Safety
+/// `ret` is the pointer the matching `analyze-page` call returned.
+#[export_name = "cabi_post_analyze-page"]
+pub unsafe extern "C" fn analyze_page_post(ret: *mut StrResult) {
+    Box::from_raw(ret).value.free();
+}
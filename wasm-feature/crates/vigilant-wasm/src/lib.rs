@@ -0,0 +1,1624 @@
+// wasm-feature/crates/vigilant-wasm/src/lib.rs
+// Browser Vigilant — wasm-bindgen layer over vigilant-core
+// Only JS-facing glue lives here; every decision is made in the core crate.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use vigilant_core as vc;
+use vigilant_core::json::Json;
+use vigilant_core::ToJson;
+
+// ── JS bridge ─────────────────────────────────────────────────────────────────
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = JSON, js_name = parse)]
+    fn js_json_parse(s: &str) -> JsValue;
+    #[wasm_bindgen(js_namespace = JSON, js_name = stringify)]
+    fn js_json_stringify(v: &JsValue) -> JsValue;
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn js_date_now() -> f64;
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn js_performance_now() -> f64;
+
+    /// Any JS function; called with `this` undefined.
+    #[wasm_bindgen(js_name = Function)]
+    pub type JsFunction;
+    #[wasm_bindgen(method, catch, js_name = call)]
+    fn call1(this: &JsFunction, ctx: &JsValue, arg: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = Promise)]
+    type JsPromise;
+    #[wasm_bindgen(constructor, js_class = "Promise")]
+    fn new(executor: &mut dyn FnMut(JsFunction, JsFunction)) -> JsPromise;
+    #[wasm_bindgen(static_method_of = JsPromise, js_class = "Promise", js_name = resolve)]
+    fn resolved(v: &JsValue) -> JsPromise;
+    #[wasm_bindgen(method, js_name = then)]
+    fn then2(this: &JsPromise, ok: &JsValue, err: &JsValue) -> JsPromise;
+}
+
+// Report interfaces referenced by the `unchecked_return_type`s below.
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = include_str!("types.d.ts");
+
+/// The default context's analyzer, which the context-less exports use.
+fn analyzer() -> vc::Analyzer {
+    with_contexts(|c| c.default_mut().analyzer().clone())
+}
+
+thread_local! {
+    /// Every tenant's caches, reputation, overrides and tab sessions; the WASM
+    /// instance is single-threaded.
+    static CONTEXTS: RefCell<vc::Contexts> = RefCell::new(vc::Contexts::new());
+    /// Header of the model last accepted by `load_model`.
+    static MODEL: RefCell<Option<vc::ModelInfo>> = const { RefCell::new(None) };
+    /// The loaded model when it is a tree ensemble `score_url` evaluates here.
+    static TREES: RefCell<Option<vc::TreeModel>> = const { RefCell::new(None) };
+    /// The host's domain-age lookup, set by `set_domain_age_provider`.
+    static DOMAIN_AGE_PROVIDER: RefCell<Option<Rc<JsFunction>>> = const { RefCell::new(None) };
+}
+
+fn with_contexts<T>(f: impl FnOnce(&mut vc::Contexts) -> T) -> T {
+    CONTEXTS.with(|c| f(&mut c.borrow_mut()))
+}
+
+/// Wall clock for audit entries; the core crate never reads one itself.
+fn now_ms() -> f64 {
+    if cfg!(target_arch = "wasm32") { js_date_now() } else { 0.0 }
+}
+
+/// High-resolution clock for stage timings.
+fn performance_now() -> f64 {
+    if cfg!(target_arch = "wasm32") { js_performance_now() } else { 0.0 }
+}
+
+/// Fold a timed tab navigation into `ctx` and log the resulting session verdict.
+fn observe_logged(ctx: &mut vc::Context, tab_id: u32, url: &str) -> Verdict {
+    let at = now_ms();
+    let v = ctx.observe_url_at(tab_id, url, at);
+    let score = ctx.state_mut().session(tab_id).score();
+    ctx.audit_mut().record(at, url, v, score);
+    v.into()
+}
+
+/// State of the default context, which the context-less exports use.
+fn with_state<T>(f: impl FnOnce(&mut vc::State) -> T) -> T {
+    with_contexts(|c| f(c.default_mut().state_mut()))
+}
+
+fn with_context<T>(handle: u32, f: impl FnOnce(&mut vc::Context) -> T) -> Result<T, JsError> {
+    with_contexts(|c| c.get_mut(handle).map(f)).ok_or_else(|| JsError::new(&format!("unknown context {}", handle)))
+}
+
+/// Hand a report to JS as a plain object.
+fn to_js(v: &Json) -> JsValue {
+    js_json_parse(&v.to_string())
+}
+
+/// Three-way verdict as seen from JS (`0` allow, `1` warn, `2` block).
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    Allow = 0,
+    Warn  = 1,
+    Block = 2,
+}
+
+impl From<Verdict> for vc::Verdict {
+    fn from(v: Verdict) -> vc::Verdict {
+        match v {
+            Verdict::Allow => vc::Verdict::Allow,
+            Verdict::Warn  => vc::Verdict::Warn,
+            Verdict::Block => vc::Verdict::Block,
+        }
+    }
+}
+
+impl From<vc::Verdict> for Verdict {
+    fn from(v: vc::Verdict) -> Verdict {
+        match v {
+            vc::Verdict::Allow => Verdict::Allow,
+            vc::Verdict::Warn  => Verdict::Warn,
+            vc::Verdict::Block => Verdict::Block,
+        }
+    }
+}
+
+// ── URL model ─────────────────────────────────────────────────────────────────
+
+#[wasm_bindgen]
+pub fn extract_features(url: &str) -> Vec<f32> {
+    with_contexts(|c| c.default_mut().analyzer_report(url).features)
+}
+
+/// `extract_features` keyed by feature name, each with its value, weight,
+/// contribution to the score and a short reason for the popup.
+#[wasm_bindgen(unchecked_return_type = "NamedFeatures")]
+pub fn extract_features_named(url: &str) -> JsValue {
+    let f = with_contexts(|c| c.default_mut().analyzer_report(url).features);
+    to_js(&vc::name_features(&f))
+}
+
+/// The 56 version-1 features the shipped `model.onnx` was trained on;
+/// `extract_features` has since redefined some of those slots.
+#[wasm_bindgen]
+pub fn extract_legacy_features(url: &str) -> Vec<f32> {
+    vc::extract_legacy_features(url)
+}
+
+/// Feature version of `extract_legacy_features`, to check a model against.
+#[wasm_bindgen]
+pub fn legacy_feature_version() -> u32 {
+    vc::LEGACY_FEATURE_VERSION
+}
+
+/// Ordered names and types of the `extract_features` vector with the
+/// feature version and schema hash, to check a model against before use.
+#[wasm_bindgen(unchecked_return_type = "FeatureSchema")]
+pub fn feature_schema() -> JsValue {
+    to_js(&vc::feature_schema())
+}
+
+/// `extract_features` with the URL read in `mode`: "literal" (as written,
+/// which is what models are trained on) or "browser" (the address the
+/// browser navigates to, with tabs, backslashes and default ports resolved).
+#[wasm_bindgen]
+pub fn extract_features_as(url: &str, mode: &str) -> Result<Vec<f32>, JsError> {
+    let m = vc::ParseMode::parse(mode).ok_or_else(|| JsError::new(&format!("unknown parse mode {:?}", mode)))?;
+    Ok(vc::extract_features_as(url, m))
+}
+
+/// The address the browser navigates to for `url`: tabs and newlines
+/// dropped, `\` read as `/`, default ports left out, Windows and UNC paths
+/// as `file:` URLs.
+#[wasm_bindgen]
+pub fn browser_url(url: &str) -> String {
+    vc::browser_url(url)
+}
+
+/// `extract_features` one byte per feature, for the IndexedDB cache and
+/// telemetry; `unpack_features` reads it back.
+#[wasm_bindgen]
+pub fn extract_features_packed(url: &str) -> Vec<u8> {
+    let f = with_contexts(|c| c.default_mut().analyzer_report(url).features);
+    vc::pack_features(&f, vc::Precision::U8).unwrap_or_default()
+}
+
+/// Pack a feature vector at `precision` ("f32", "f16" or "u8"; "u8" takes
+/// the model-plus-time vector only).
+#[wasm_bindgen]
+pub fn pack_features(features: &[f32], precision: &str) -> Result<Vec<u8>, JsError> {
+    let p = vc::Precision::parse(precision).ok_or_else(|| JsError::new(&format!("unknown precision {:?}", precision)))?;
+    vc::pack_features(features, p).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Decode a packed vector; fails on one packed by a build with another
+/// feature layout, which a cache should treat as a miss.
+#[wasm_bindgen]
+pub fn unpack_features(bytes: &[u8]) -> Result<Vec<f32>, JsError> {
+    vc::unpack_features(bytes).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// `extract_features` followed by `[is_weekend, local_night_hours,
+/// first_seen_bucket]`. `epoch_ms` is local wall-clock time, e.g.
+/// `Date.now() - new Date().getTimezoneOffset() * 60000`; the engine never
+/// reads the clock itself. Domains' first sightings are kept in state.
+#[wasm_bindgen]
+pub fn extract_features_with_time(url: &str, epoch_ms: f64) -> Vec<f32> {
+    with_contexts(|c| c.default_mut().features_with_time(url, epoch_ms))
+}
+
+/// Zero the features at `indices_json` (an array of indices into the
+/// `extract_features_with_time` vector) everywhere from now on, for ablation
+/// experiments; `[]` restores the full feature set.
+#[wasm_bindgen]
+pub fn set_feature_mask(indices_json: &str) -> Result<(), JsError> {
+    let v = Json::parse(indices_json).map_err(|e| JsError::new(&e))?;
+    let list = v.as_array().ok_or_else(|| JsError::new("expected an array of feature indices"))?;
+    let indices = list.iter()
+        .map(|x| x.as_f64().filter(|n| n.fract() == 0.0 && *n >= 0.0).map(|n| n as usize))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| JsError::new("feature indices must be non-negative integers"))?;
+    vc::set_feature_mask(&indices).map_err(|e| JsError::new(&e))
+}
+
+#[wasm_bindgen]
+pub fn feature_mask() -> Vec<usize> {
+    vc::feature_mask()
+}
+
+/// Add a `timings` breakdown (milliseconds per stage: parse, features,
+/// score, homoglyph, brand_alias, brand_path, keywords, rules, and for
+/// contexts signals and context) to every URL report from now on, or stop.
+#[wasm_bindgen]
+pub fn set_timing(enabled: bool) {
+    vc::set_timing_clock(enabled.then_some(performance_now as vc::Clock));
+}
+
+/// Treat `brands` (corporate portals, regional banks) as brands on top of
+/// the built-in list in the default context, replacing its previous list:
+/// the brand-similarity features and download name checks of the
+/// context-less exports protect them too. `[]` leaves only the built-in
+/// brands; other contexts keep their own.
+#[wasm_bindgen]
+pub fn set_brands(brands: Vec<String>) -> Result<(), JsError> {
+    let brands: Vec<&str> = brands.iter().map(String::as_str).collect();
+    with_contexts(|c| c.default_mut().set_brands(&brands)).map_err(|e| JsError::new(&e))
+}
+
+/// Add `brands` to the default context's list (see `set_brands`).
+#[wasm_bindgen]
+pub fn add_brands(brands: Vec<String>) -> Result<(), JsError> {
+    let brands: Vec<&str> = brands.iter().map(String::as_str).collect();
+    with_contexts(|c| c.default_mut().add_brands(&brands)).map_err(|e| JsError::new(&e))
+}
+
+/// Analyze a serialized form-action URL against the current page host.
+/// Returns risk score 0.0–1.0.
+#[wasm_bindgen]
+pub fn analyze_form_action(form_action: &str, page_host: &str) -> f32 {
+    vc::analyze_form_action(form_action, page_host)
+}
+
+/// Compute filename risk score for download interception.
+/// Returns 0.0–1.0 risk.
+#[wasm_bindgen]
+pub fn score_filename(filename: &str) -> f32 {
+    vc::score_filename(filename)
+}
+
+/// Decide whether credentials saved for `saved_origin` may be filled into `url`.
+#[wasm_bindgen]
+pub fn should_allow_autofill(url: &str, saved_origin: &str) -> Verdict {
+    vc::should_allow_autofill(url, saved_origin).into()
+}
+
+/// Analyze an OAuth 2.0 / OIDC authorization or device-verification URL.
+/// Returns `{ is_oauth, provider, lookalike_of, client_id, redirect_uri,
+/// redirect_host, response_type, scopes, broad_scopes, flags, risk }`.
+#[wasm_bindgen(unchecked_return_type = "OAuthReport")]
+pub fn analyze_oauth_url(url: &str) -> JsValue {
+    to_js(&vc::oauth_report(url))
+}
+
+/// Compare the payment address (UPI VPA or crypto) the user copied with what
+/// got pasted; a swap is a clipboard hijack. Returns `{ asset, copied, pasted,
+/// pasted_asset, diff: { prefix, copied, pasted, suffix } | null, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "PaymentTargetReport")]
+pub fn compare_payment_targets(copied_text: &str, pasted_text: &str) -> JsValue {
+    to_js(&vc::compare_payment_targets(copied_text, pasted_text))
+}
+
+/// Analyze an Android `intent:` link: its target, package and browser
+/// fallback. Returns `{ intent, target, scheme, package, action, component,
+/// fallback_url, fallback_host, brand, extras, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "IntentReport")]
+pub fn intent_report(uri: &str) -> JsValue {
+    to_js(&vc::intent_report(uri))
+}
+
+/// Analyze a UPI payment request from a link or a scanned QR payload,
+/// with the message or page text it came with: pull requests and requests
+/// framed as money coming in. Returns `{ upi, kind, action, payee,
+/// payee_name, amount, currency, note, handle, handle_known, framing,
+/// flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "UpiRequestReport")]
+pub fn analyze_upi_request(payload: &str, context_text: &str) -> JsValue {
+    to_js(&vc::upi_request_report(payload, context_text))
+}
+
+/// Analyze a `data:` URI: its media type, the decoded payload, and the
+/// page, script, form or program it carries. Returns `{ data_uri, mime,
+/// params, base64, decoded, truncated, payload_bytes, entropy,
+/// content_kind, mime_mismatch, script, forms, content, flags, score,
+/// verdict }`.
+#[wasm_bindgen(unchecked_return_type = "DataUriReport")]
+pub fn analyze_data_uri(uri: &str) -> JsValue {
+    to_js(&vc::data_uri_report(uri))
+}
+
+/// Analyze what a `javascript:` or `blob:` URL would do: the decoded
+/// script's obfuscation, page writes, credential prompts and navigations,
+/// or the blob's origin. Returns `{ scheme, payload, code, decoded,
+/// obfuscation, page_writes, credential_words, navigations, links, origin,
+/// features, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "SchemePayloadReport")]
+pub fn analyze_scheme_payload(url: &str) -> JsValue {
+    to_js(&vc::scheme_payload_report(url))
+}
+
+/// Analyze a `mailto:` or `tel:` link with the text the page shows for it
+/// ("" when unknown): look-alike and freemail recipient domains, spoofed
+/// display addresses, premium-rate, fraud-prone and USSD numbers. Returns
+/// `{ kind, recipients, domain, brand, number, shown, features, flags,
+/// score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "ContactLinkReport")]
+pub fn analyze_contact_link(href: &str, link_text: &str) -> JsValue {
+    to_js(&vc::contact_link_report(href, link_text))
+}
+
+// ── Mail ──────────────────────────────────────────────────────────────────────
+
+/// Score one message. `message_json`:
+/// `{ sender, display_name, reply_to?, urls: [href | {href, text}],
+///    authentication_results?, dmarc_record?, spf_record? }`
+/// (`sender_domain` may be passed instead of `sender`).
+#[wasm_bindgen(unchecked_return_type = "MailReport | ErrorReport")]
+pub fn analyze_mail_message(message_json: &str) -> JsValue {
+    to_js(&analyzer().mail(message_json))
+}
+
+#[wasm_bindgen(unchecked_return_type = "AuthResultsReport")]
+pub fn parse_auth_results(header: &str) -> JsValue {
+    to_js(&vc::auth_results_json(&vc::parse_authentication_results(header)))
+}
+
+#[wasm_bindgen(unchecked_return_type = "DmarcReport")]
+pub fn parse_dmarc_record(record: &str) -> JsValue {
+    to_js(&vc::dmarc_json(&vc::parse_dmarc(record)))
+}
+
+#[wasm_bindgen(unchecked_return_type = "SpfReport")]
+pub fn parse_spf_record(record: &str) -> JsValue {
+    to_js(&vc::spf_json(&vc::parse_spf(record)))
+}
+
+/// Scan an ICS invite. Returns `{ events, organizer, organizer_domain, links,
+/// flags, score, verdict }`; each link carries its own `score`.
+#[wasm_bindgen(unchecked_return_type = "IcsReport")]
+pub fn analyze_ics(ics_text: &str) -> JsValue {
+    to_js(&analyzer().ics(ics_text))
+}
+
+// ── Attachments and downloads ─────────────────────────────────────────────────
+
+/// Scan raw PDF bytes. Returns `{ is_pdf, pages, uris: [{url, score}],
+/// js_actions, launch_actions, open_actions, auto_actions, submit_forms,
+/// embedded_files, streams, undecodable_streams, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "PdfReport")]
+pub fn scan_pdf(bytes: &[u8]) -> JsValue {
+    to_js(&analyzer().pdf(bytes))
+}
+
+/// Scan an OOXML container. Returns `{ is_ooxml, macros, macro_content_type,
+/// remote_templates, external_ole, external_links, activex, embedded_objects,
+/// flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "OfficeReport")]
+pub fn scan_office_doc(bytes: &[u8]) -> JsValue {
+    to_js(&analyzer().office(bytes))
+}
+
+/// Scan an `.svg` / `.html` attachment body. Returns `{ kind, scripts,
+/// event_handlers, meta_refresh, smuggling, auto_submit, password_fields,
+/// form_actions, links, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "HtmlAttachmentReport")]
+pub fn scan_html_attachment(bytes: &[u8]) -> JsValue {
+    to_js(&analyzer().html_attachment(bytes))
+}
+
+/// Inspect an APK (or a prefix of one that still contains the manifest).
+/// Returns `{ is_apk, package, permissions, service_permissions, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "ApkReport")]
+pub fn inspect_apk(bytes_prefix: &[u8]) -> JsValue {
+    to_js(&analyzer().apk(bytes_prefix))
+}
+
+/// Scan an archive download. `page_text` is the visible text of the page or
+/// message it came from, where droppers usually print the password.
+/// Returns `{ kind, encrypted, encrypted_entries, max_depth, risky_entries,
+/// password_hint, password_source, password_verified, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "ArchiveReport")]
+pub fn analyze_archive(filename: &str, bytes: &[u8], page_text: &str) -> JsValue {
+    to_js(&analyzer().archive(filename, bytes, page_text))
+}
+
+/// Decode a Content-Disposition header (or a bare filename) and score the
+/// result. Returns `{ filename, safe_name, displayed_as, ext, displayed_ext,
+/// bidi_override, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "FilenameReport")]
+pub fn analyze_content_disposition(header: &str) -> JsValue {
+    to_js(&vc::filename_report(header))
+}
+
+/// Analyze a completed (or partially buffered) download.
+/// Returns `{ filename_score, kind, ext_mismatch, content, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "DownloadReport")]
+pub fn analyze_download(filename: &str, bytes: &[u8]) -> JsValue {
+    to_js(&analyzer().download(filename, bytes))
+}
+
+/// Apply the download policy table. `mime` is the served Content-Type,
+/// `ext` the final extension, `initiating_origin` the page that started the
+/// download. Returns `{ category, origin_class, mime_mismatch, rule, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "DownloadContextReport")]
+pub fn evaluate_download_context(mime: &str, ext: &str, initiating_origin: &str, user_gesture: bool) -> JsValue {
+    to_js(&vc::download_context_report(mime, ext, initiating_origin, user_gesture))
+}
+
+/// Score `url` for phishing planted on a hacked popular site; `login_form`
+/// is whether the page shows a password form. On a popular site with a
+/// planted-looking path the host's signals are mostly set aside.
+#[wasm_bindgen(unchecked_return_type = "CompromisedSiteReport")]
+pub fn compromised_site_report(url: &str, login_form: bool) -> JsValue {
+    to_js(&vc::compromised_site_report(url, login_form))
+}
+
+/// Popularity rank of `host`'s site: 1 for the most visited, 0.5 at the end
+/// of the embedded list, 0 when unlisted.
+#[wasm_bindgen]
+pub fn popularity_rank(host: &str) -> f32 {
+    vc::popularity_rank(host)
+}
+
+/// Score an XHR, fetch, WebSocket or beacon target (`kind`) requested by the
+/// page at `initiator_origin`, with weights tuned for requests rather than
+/// navigations. Returns `{ kind, host, site, port, third_party, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "EndpointReport")]
+pub fn score_endpoint(url: &str, initiator_origin: &str, kind: &str) -> JsValue {
+    to_js(&vc::score_endpoint(url, initiator_origin, kind))
+}
+
+/// Analyze a `market://`, `itms-apps://` or `itms-services://` link the page
+/// at `initiating_origin` opened. Returns `{ kind, store, package,
+/// manifest_url, manifest_host, rule, brand, origin_class, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "DeepLinkReport")]
+pub fn analyze_deep_link(link: &str, initiating_origin: &str, user_gesture: bool) -> JsValue {
+    to_js(&vc::deep_link_report(link, initiating_origin, user_gesture))
+}
+
+/// URLs hidden in the query and fragment values of `url` (open-redirect
+/// payloads, `?u=https%3A%2F%2F…`), decoded and unpacked through nested
+/// redirects; with `analyze`, each is scored and `max_risk` is the worst.
+/// Returns `{ url, embedded: [{ url, source, param, depth, host, reg_domain,
+/// off_site, risk, verdict }], max_risk, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "EmbeddedUrls")]
+pub fn extract_embedded_urls(url: &str, analyze: bool) -> JsValue {
+    to_js(&vc::extract_embedded_urls(url, analyze))
+}
+
+// ── Short links ───────────────────────────────────────────────────────────────
+
+/// Text of a thrown JS value: its `message`, or the value itself.
+fn js_error_text(e: &JsValue) -> String {
+    let json = Json::parse(&js_json_stringify(e).as_string().unwrap_or_default()).unwrap_or(Json::Null);
+    e.as_string().or_else(|| json.get("message").and_then(Json::as_str).map(str::to_string)).unwrap_or_else(|| "fetch failed".to_string())
+}
+
+/// Feed one `{ status, location }` answer to the expansion.
+fn respond(x: &mut vc::ShortExpansion, answer: &JsValue) {
+    let a = Json::parse(&js_json_stringify(answer).as_string().unwrap_or_default()).unwrap_or(Json::Null);
+    let status = a.get("status").and_then(Json::as_f64).unwrap_or(0.0) as u16;
+    x.respond(status, a.get("location").and_then(Json::as_str));
+}
+
+/// Fetch the next URL of `x` through `fetcher` and carry on from its
+/// answer, or hand the report to `resolve` once the chain has ended. Each
+/// hop allocates two one-shot callbacks and only one of them runs; the
+/// other is freed with the page, which [`vc::MAX_REDIRECT_HOPS`] bounds.
+fn expand_step(mut x: vc::ShortExpansion, fetcher: Rc<JsFunction>, resolve: Rc<JsFunction>) {
+    let Some(next) = x.next_url().map(str::to_string) else {
+        let _ = resolve.call1(&JsValue::UNDEFINED, &to_js(&x.report(&analyzer())));
+        return;
+    };
+    let answer = match fetcher.call1(&JsValue::UNDEFINED, &JsValue::from_str(&next)) {
+        Ok(a) => a,
+        Err(e) => {
+            x.fail(&js_error_text(&e));
+            return expand_step(x, fetcher, resolve);
+        }
+    };
+    let (failed, f, r) = (x.clone(), fetcher.clone(), resolve.clone());
+    let ok = Closure::once_into_js(move |a: JsValue| {
+        respond(&mut x, &a);
+        expand_step(x, fetcher, resolve);
+    });
+    let err = Closure::once_into_js(move |e: JsValue| {
+        let mut x = failed;
+        x.fail(&js_error_text(&e));
+        expand_step(x, f, r);
+    });
+    JsPromise::resolved(&answer).then2(&ok, &err);
+}
+
+/// Follow the redirect chain from `url`. `fetcher(url)` is the host's: it
+/// requests `url` without following redirects and returns (or resolves to)
+/// `{ status, location }`; a non-3xx status or no `location` ends the
+/// chain, and a throw or rejection ends it with `error` set. Resolves to
+/// `{ url, final_url, done, chain: [{ url, status, host, shortener }],
+/// chain_length, cross_domain_hops, shortener_hops, looped, truncated,
+/// error, features, final, flags, score, verdict }`; never rejects.
+#[wasm_bindgen(unchecked_return_type = "Promise<ShortExpansionReport>")]
+pub fn expand_short_url(url: &str, #[wasm_bindgen(unchecked_param_type = "(url: string) => RedirectAnswer | Promise<RedirectAnswer>")] fetcher: JsFunction) -> JsValue {
+    let mut settle = None;
+    let promise = JsPromise::new(&mut |resolve, _reject| settle = Some(resolve));
+    if let Some(resolve) = settle {
+        expand_step(vc::ShortExpansion::new(url), Rc::new(fetcher), Rc::new(resolve));
+    }
+    promise.into()
+}
+
+// ── Host signals ──────────────────────────────────────────────────────────────
+
+/// Register the host's domain-age lookup, or remove it with `null`.
+/// `provider(domain)` gets a registrable domain and returns (or resolves
+/// to) its age in days from WHOIS or RDAP, or null when unknown.
+#[wasm_bindgen]
+pub fn set_domain_age_provider(
+    #[wasm_bindgen(unchecked_param_type = "((domain: string) => number | null | Promise<number | null>) | null")] provider: Option<JsFunction>,
+) {
+    DOMAIN_AGE_PROVIDER.with(|p| *p.borrow_mut() = provider.map(Rc::new));
+}
+
+fn context_features(url: &str, signals: &vc::HostSignals) -> JsValue {
+    JsValue::from(vc::extract_features_with_context(url, signals))
+}
+
+/// Resolves to the model features of `url` followed by the
+/// host-signal features of `context` (`{ domain_age_days?, rank?,
+/// cert_age_days? }`, or null). Without `domain_age_days` the domain-age
+/// provider is asked; a throw, rejection or non-number leaves the age
+/// unknown. Throws on a malformed `context`; the promise never rejects.
+#[wasm_bindgen(unchecked_return_type = "Promise<Float32Array>")]
+pub fn extract_features_with_context(url: &str, #[wasm_bindgen(unchecked_param_type = "HostSignals | null")] context: JsValue) -> Result<JsValue, JsError> {
+    let signals: vc::HostSignals = if context.is_null() || context.is_undefined() { vc::HostSignals::default() } else { from_js(&context)? };
+    let domain = vc::domain_age_key(url);
+    let provider = DOMAIN_AGE_PROVIDER.with(|p| p.borrow().clone()).filter(|_| signals.domain_age_days.is_none() && !domain.is_empty());
+    let Some(provider) = provider else {
+        return Ok(JsPromise::resolved(&context_features(url, &signals)).into());
+    };
+    let answer = provider.call1(&JsValue::UNDEFINED, &JsValue::from_str(&domain)).unwrap_or(JsValue::NULL);
+    let (url, failed_url) = (url.to_string(), url.to_string());
+    let (mut aged, unaged) = (signals.clone(), signals);
+    let ok = Closure::once_into_js(move |age: JsValue| {
+        aged.domain_age_days = age.as_f64().filter(|d| d.is_finite() && *d >= 0.0);
+        context_features(&url, &aged)
+    });
+    let err = Closure::once_into_js(move |_: JsValue| context_features(&failed_url, &unaged));
+    Ok(JsPromise::resolved(&answer).then2(&ok, &err).into())
+}
+
+/// `[domain_age_known, domain_age_log_days, young_domain, popularity,
+/// cert_age_known, cert_age_log_days, fresh_certificate]` from `context`
+/// alone, without asking the provider.
+#[wasm_bindgen]
+pub fn host_signal_features(url: &str, #[wasm_bindgen(unchecked_param_type = "HostSignals")] context: JsValue) -> Result<Vec<f32>, JsError> {
+    let signals: vc::HostSignals = from_js(&context)?;
+    Ok(vc::host_signal_features(url, &signals).to_vec())
+}
+
+// ── Introspection ─────────────────────────────────────────────────────────────
+
+/// Bytes of linear memory this instance has grown to. WASM memory never
+/// shrinks, so this is also the high-water mark.
+fn linear_memory_bytes() -> Option<usize> {
+    #[cfg(target_arch = "wasm32")]
+    { Some(core::arch::wasm32::memory_size::<0>() * 65536) }
+    #[cfg(not(target_arch = "wasm32"))]
+    { None }
+}
+
+/// Sizes of the built-in dictionaries, model weights and analyzer
+/// configuration, plus the WASM linear memory high-water mark.
+/// Returns `{ dictionaries, dictionary_bytes, models, model_bytes,
+/// analyzer_bytes, caches, cache_bytes, total_bytes, linear_memory_bytes }`.
+#[wasm_bindgen(unchecked_return_type = "MemoryStats")]
+pub fn memory_stats() -> JsValue {
+    let report = with_contexts(|c| {
+        let contexts: Vec<(String, Json)> =
+            c.handles().into_iter().filter_map(|h| c.get(h).map(|ctx| (h.to_string(), ctx.memory()))).collect();
+        let ctx = c.default_mut();
+        vc::memory_report(ctx.analyzer(), ctx.state()).with("contexts", Json::Obj(contexts))
+    });
+    to_js(&report.with("linear_memory_bytes", linear_memory_bytes()))
+}
+
+/// Which analyzers, dictionaries (entry counts and content versions) and
+/// models this build carries, and how the default analyzer is configured.
+#[wasm_bindgen(unchecked_return_type = "Capabilities")]
+pub fn capabilities() -> JsValue {
+    to_js(&vc::capabilities_report(&analyzer()))
+}
+
+/// Every embedded table this release ships with: entry count, content
+/// version and source, with the suffix-list snapshot date and build time.
+#[wasm_bindgen(unchecked_return_type = "DataManifest")]
+pub fn data_manifest() -> JsValue {
+    to_js(&vc::data_manifest())
+}
+
+/// Check a model container and return its payload for the ONNX runtime. A
+/// "trees" model, or an "onnx" one holding a tree ensemble, is kept here for
+/// `score_url` and `predict` as well. Fails, keeping the previous model, on a
+/// malformed container or a model trained for another feature version.
+#[wasm_bindgen]
+pub fn load_model(blob: &[u8]) -> Result<Vec<u8>, JsError> {
+    let (info, payload) = vc::parse_model(blob).map_err(|e| JsError::new(&e.to_string()))?;
+    let trees = match info.kind.as_str() {
+        "trees" => Some(vc::TreeModel::parse(blob).map_err(|e| JsError::new(&e.to_string()))?),
+        "onnx" => vc::TreeModel::parse(blob).ok(),
+        _ => None,
+    };
+    MODEL.with(|m| *m.borrow_mut() = Some(info));
+    vc::set_importance_model(trees.as_ref());
+    TREES.with(|t| *t.borrow_mut() = trees);
+    Ok(payload.to_vec())
+}
+
+/// Phishing probability of `url` from the loaded tree model, evaluated on
+/// the same vector `extract_features` returns; the heuristic risk until a
+/// "trees" model is loaded.
+#[wasm_bindgen]
+pub fn score_url(url: &str) -> f32 {
+    let r = with_contexts(|c| c.default_mut().analyzer_report(url));
+    TREES.with(|t| t.borrow().as_ref().map_or(r.risk, |m| m.score(&r.features)))
+}
+
+/// Load a retrained tree-ensemble classifier exported to ONNX, as a bare
+/// file or in a container of kind "onnx", replacing the loaded model. Fails,
+/// keeping the previous model, on anything but a binary ensemble over this
+/// build's feature vector.
+#[wasm_bindgen]
+pub fn load_onnx_model(bytes: &[u8]) -> Result<(), JsError> {
+    let model = vc::load_onnx_model(bytes).map_err(|e| JsError::new(&e.to_string()))?;
+    MODEL.with(|m| *m.borrow_mut() = Some(model.info.clone()));
+    vc::set_importance_model(Some(&model));
+    TREES.with(|t| *t.borrow_mut() = Some(model));
+    Ok(())
+}
+
+/// Phishing probability of `url` from the model loaded in-crate, from
+/// extraction to inference. Unlike `score_url` it fails while no such model
+/// is loaded.
+#[wasm_bindgen]
+pub fn predict(url: &str) -> Result<f32, JsError> {
+    let features = with_contexts(|c| c.default_mut().analyzer_report(url)).features;
+    TREES.with(|t| t.borrow().as_ref().map(|m| m.score(&features))).ok_or_else(|| JsError::new("no in-crate model is loaded"))
+}
+
+/// Training date, dataset hash, feature version, calibration and license of
+/// the loaded model, or null before `load_model` succeeds.
+#[wasm_bindgen(unchecked_return_type = "ModelInfo | null")]
+pub fn model_info() -> JsValue {
+    MODEL.with(|m| m.borrow().as_ref().map_or(JsValue::NULL, |i| to_js(&i.to_json())))
+}
+
+/// Raw model output mapped through the loaded model's calibration.
+#[wasm_bindgen]
+pub fn calibrate_score(raw: f32) -> f32 {
+    MODEL.with(|m| m.borrow().as_ref().map_or(vc::Calibration::default(), |i| i.calibration).apply(raw))
+}
+
+/// Which URL features drive the score, heaviest first: the loaded tree
+/// model's split counts, or the heuristic weights before one is loaded.
+#[wasm_bindgen(unchecked_return_type = "FeatureImportances")]
+pub fn feature_importances() -> JsValue {
+    to_js(&vc::feature_importances())
+}
+
+/// Verify embedded table digests, golden URL verdicts and feature-length
+/// invariants; call at extension start-up and refuse to score if `ok` is false.
+#[wasm_bindgen(unchecked_return_type = "SelfTestReport")]
+pub fn self_test() -> JsValue {
+    to_js(&vc::self_test())
+}
+
+/// `count` deterministic URLs from `seed` covering every URL-parser branch,
+/// each with its parts, features and verdict, for the wrapper's binding tests.
+#[wasm_bindgen(unchecked_return_type = "TestVectors")]
+pub fn gen_test_vectors(seed: u32, count: usize) -> JsValue {
+    to_js(&vc::gen_test_vectors(u64::from(seed), count))
+}
+
+/// Shrink caches, reputation and idle tab sessions towards `target_bytes`
+/// (call on memory pressure), shared out evenly across contexts. Returns the
+/// bytes freed.
+#[wasm_bindgen]
+pub fn trim_caches(target_bytes: usize) -> usize {
+    with_contexts(|c| {
+        let share = target_bytes / c.handles().len().max(1);
+        c.iter_mut().map(|ctx| ctx.state_mut().trim(share)).sum()
+    })
+}
+
+/// Drop every context's cached and per-tab state without reloading the
+/// module. Context configuration, overrides and feeds are kept.
+#[wasm_bindgen]
+pub fn reset_state() {
+    with_contexts(|c| c.iter_mut().for_each(|ctx| ctx.state_mut().reset()))
+}
+
+// ── Tab sessions and reputation ───────────────────────────────────────────────
+
+/// Fold a navigation into the tab's risk session; returns the session verdict.
+#[wasm_bindgen]
+pub fn observe_tab_url(tab_id: u32, url: &str) -> Verdict {
+    with_contexts(|c| observe_logged(c.default_mut(), tab_id, url))
+}
+
+/// Verdict for a request or navigation URL, memoised per origin and path
+/// template: repeat calls for `/api/items/{n}` cost a lookup.
+#[wasm_bindgen]
+pub fn memo_verdict(url: &str) -> Verdict {
+    with_contexts(|c| c.default_mut().memo_verdict(url)).into()
+}
+
+#[wasm_bindgen]
+pub fn tab_verdict(tab_id: u32) -> Verdict {
+    with_state(|s| s.session(tab_id).verdict()).into()
+}
+
+/// Navigation rate, suspicious-TLD share and new-domain run of the tab over
+/// the last five minutes; `redirect_storm` once they tripped the session.
+#[wasm_bindgen(unchecked_return_type = "NavigationFeatures")]
+pub fn tab_navigation_features(tab_id: u32) -> JsValue {
+    with_state(|s| to_js(&s.session(tab_id).navigation_report(now_ms())))
+}
+
+#[wasm_bindgen]
+pub fn close_tab(tab_id: u32) -> bool {
+    with_state(|s| s.close_session(tab_id))
+}
+
+/// Store a reputation score (0 good – 1 bad) for `host` from a feed or lookup.
+#[wasm_bindgen]
+pub fn set_host_reputation(host: &str, score: f32) {
+    with_state(|s| s.set_reputation(host, score))
+}
+
+#[wasm_bindgen]
+pub fn host_reputation(host: &str) -> Option<f32> {
+    with_state(|s| s.reputation(host))
+}
+
+// ── Typed snapshots ───────────────────────────────────────────────────────────
+
+/// Read a JS object into one of the core's typed inputs; a malformed field
+/// comes back as an `Error` naming its path (e.g. `forms[2].has_password`).
+fn from_js<T: vc::FromJson>(v: &JsValue) -> Result<T, JsError> {
+    let src = js_json_stringify(v).as_string().unwrap_or_default();
+    T::from_json_str(&src).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// `[brand_path_mimicry, path_depth, deep_link_off_brand]`: a brand's real
+/// path (e.g. PayPal's `/webapps/mpp/`) served from someone else's domain,
+/// and how deep the link lands.
+#[wasm_bindgen]
+pub fn brand_path_features(url: &str) -> Vec<f32> {
+    vc::brand_path_features(url).to_vec()
+}
+
+/// `[label_keyword_max, pay_free_in_host, login_trust_in_host,
+/// keyword_proximity, keyword_density]`: how many lure keywords a URL stacks
+/// and how closely, which a single genuine `/login` doesn't.
+#[wasm_bindgen]
+pub fn keyword_features(url: &str) -> Vec<f32> {
+    vc::keyword_features(url).to_vec()
+}
+
+/// `[class_transitions, letter_digit_alternations, digit_embedded_brand,
+/// transition_ratio]`: letter/digit flips in the host, as in `faceb00k`.
+#[wasm_bindgen]
+pub fn char_class_features(url: &str) -> Vec<f32> {
+    vc::char_class_features(url).to_vec()
+}
+
+/// `[query_value_entropy_max, max_token_length]`: one random query value
+/// or long unbroken token, which whole-URL entropy dilutes.
+#[wasm_bindgen]
+pub fn token_features(url: &str) -> Vec<f32> {
+    vc::token_features(url).to_vec()
+}
+
+/// Longest consonant run, longest digit run and vowel ratio of the first
+/// path segment, then of the longest query value.
+#[wasm_bindgen]
+pub fn pronounce_features(url: &str) -> Vec<f32> {
+    vc::pronounce_features(url).to_vec()
+}
+
+/// `[homoglyph_impersonation, confusable_share, mixed_script,
+/// undecodable_punycode]` of the host as the address bar decodes it.
+#[wasm_bindgen]
+pub fn homoglyph_features(url: &str) -> Vec<f32> {
+    vc::homoglyph_features(url).to_vec()
+}
+
+/// `[lookalike_token, digit_affixed_brand, in_registrable_label]`: a short
+/// brand spelled with digits or look-alike letters (`1cici`, `5bi`), or
+/// with digits stuck on (`sbi24`), matched exactly.
+#[wasm_bindgen]
+pub fn numeric_brand_features(url: &str) -> Vec<f32> {
+    vc::numeric_brand_features(url).to_vec()
+}
+
+/// `[markov_bits_per_char, digit_interleave_ratio, pronounceable_share,
+/// dga_like]` of the registrable label: how generated its name reads.
+#[wasm_bindgen]
+pub fn dga_features(url: &str) -> Vec<f32> {
+    vc::dga_features(url).to_vec()
+}
+
+/// `[subdomain_entropy, dictionary_coverage, label_randomness,
+/// random_label]` of the subdomain alone: a per-victim generated label
+/// (`kjq83ad.secure-login.tk`) that the host entropies average away.
+#[wasm_bindgen]
+pub fn subdomain_features(url: &str) -> Vec<f32> {
+    vc::subdomain_features(url).to_vec()
+}
+
+/// `[encoding_depth, hidden_traversal, hidden_keyword]`: how many times
+/// the URL is percent-encoded, and what only the fully decoded form shows.
+#[wasm_bindgen]
+pub fn encoding_features(url: &str) -> Vec<f32> {
+    vc::encoding_features(url).to_vec()
+}
+
+/// `[multiple_at, stripped_whitespace, backslash_separator,
+/// slash_confusion, host_confusion]`: which malformed spellings the URL
+/// uses that browsers accept, and whether a naive parser reads another host.
+#[wasm_bindgen]
+pub fn malformed_features(url: &str) -> Vec<f32> {
+    vc::malformed_features(url).to_vec()
+}
+
+/// `[damerau_distance_norm, keyboard_distance_norm, transposition_typo,
+/// fat_finger_typo]` of the registrable label against the brand list.
+#[wasm_bindgen]
+pub fn typosquat_features(url: &str) -> Vec<f32> {
+    vc::typosquat_features(url).to_vec()
+}
+
+/// `[url, host, path, host_bigram, host_trigram]` entropy in log `base`
+/// (default 2, as in the model vector), then the same five normalized to
+/// 0–1 by the maximum for their length.
+#[wasm_bindgen]
+pub fn entropy_features(url: &str, base: Option<f32>) -> Result<Vec<f32>, JsError> {
+    let opts = match base {
+        None => vc::EntropyOptions::default(),
+        Some(b) => vc::EntropyOptions::with_base(b).ok_or_else(|| JsError::new("entropy base must be a finite number above 1"))?,
+    };
+    Ok(vc::entropy_features(url, opts).to_vec())
+}
+
+/// `[ext_document, ext_media, ext_script, ext_executable, ext_archive,
+/// ext_none, server_script, server_script_odd_host]`: the path's file type,
+/// one-hot, and `.php`-style pages on an IP, odd port or throwaway TLD.
+#[wasm_bindgen]
+pub fn extension_features(url: &str) -> Vec<f32> {
+    vc::extension_features(url).to_vec()
+}
+
+/// `[https, http, ftp, data, javascript, blob, mailto, intent, upi, other]`,
+/// one-hot on the link's scheme.
+#[wasm_bindgen]
+pub fn scheme_features(url: &str) -> Vec<f32> {
+    vc::scheme_features(url).to_vec()
+}
+
+/// `[obfuscation_calls, writes_page, credential_prompt, navigates,
+/// blob_opaque_origin]`: what a `javascript:` or `blob:` URL would do; all
+/// 0 for other schemes.
+#[wasm_bindgen]
+pub fn scheme_risk_features(url: &str) -> Vec<f32> {
+    vc::scheme_risk_features(url).to_vec()
+}
+
+/// `[mailto_lookalike_domain, mailto_display_spoof, mailto_brand_freemail,
+/// tel_premium_rate, tel_international, tel_high_risk_prefix, tel_ussd]`
+/// for a `mailto:` or `tel:` link; all 0 for other schemes.
+#[wasm_bindgen]
+pub fn contact_features(url: &str) -> Vec<f32> {
+    vc::contact_features(url).to_vec()
+}
+
+/// `[has_fallback, fallback_off_host, fallback_unsafe_scheme, fallback_risk,
+/// package_brand_mismatch]` for an `intent:` link; zeros for other URLs.
+#[wasm_bindgen]
+pub fn intent_features(uri: &str) -> Vec<f32> {
+    vc::intent_features(uri).to_vec()
+}
+
+/// Canonical spelling of `url`: lowercased scheme and host, no default
+/// port, unreserved escapes decoded, dot segments resolved and the query
+/// sorted without tracking parameters. Overrides and templates match on it.
+#[wasm_bindgen]
+pub fn normalize_url(url: &str) -> String {
+    vc::normalize_url(url)
+}
+
+/// Percentile (0–1) of the registrable label's length among legitimate
+/// sites' labels, a normalised stand-in for the raw length features.
+#[wasm_bindgen]
+pub fn label_length_percentile(url: &str) -> f32 {
+    vc::label_length_percentile(url)
+}
+
+/// ISO 639-1 code of the dominant language of `text` ("und" if unsure).
+#[wasm_bindgen]
+pub fn detect_lang(text: &str) -> String {
+    vc::detect_lang(text).to_string()
+}
+
+/// ISO 15919-style romanisation of Devanagari, Tamil and Telugu in `text`.
+#[wasm_bindgen]
+pub fn transliterate(text: &str) -> String {
+    vc::transliterate(text)
+}
+
+/// Grade a Content-Security-Policy header value. Returns `{ present,
+/// report_only, directives, issues, strength, grade }`.
+#[wasm_bindgen(unchecked_return_type = "CspReport")]
+pub fn grade_csp(header: &str, report_only: bool) -> JsValue {
+    to_js(&vc::csp_report(header, report_only))
+}
+
+/// Score the subresources `page_url` loaded. `resources_json` is an array of
+/// URLs or `{ url, type }` (script, form, xhr, fetch, beacon, image, …).
+/// Returns `{ page_site, resources, distinct_sites, third_party_sites,
+/// credential_sinks, messaging_webhooks, script_sites, form_sites, features,
+/// findings, flags, score, verdict }`.
+#[wasm_bindgen(unchecked_return_type = "ResourceGraphReport | ErrorReport")]
+pub fn analyze_resource_graph(resources_json: &str, page_url: &str) -> JsValue {
+    to_js(&analyzer().resource_graph(resources_json, page_url))
+}
+
+/// Score a content-script page snapshot `{ schema_version?, url, title?, text?,
+/// forms?: [{action, method, has_password}], links?, saved_origin?, scripts?,
+/// csp? }`.
+/// Returns `{ schema_version, kind, score, verdict, flags, details }`.
+#[wasm_bindgen(unchecked_return_type = "PageReport")]
+pub fn analyze_page(
+    #[wasm_bindgen(unchecked_param_type = "PageSnapshot")] snapshot: JsValue,
+) -> Result<JsValue, JsError> {
+    let p: vc::PageSnapshot = from_js(&snapshot)?;
+    Ok(to_js(&analyzer().page(&p).to_json()))
+}
+
+/// Score a download event `{ schema_version?, filename, content_disposition?,
+/// mime?, initiating_origin?, user_gesture? }` plus any buffered `bytes`.
+#[wasm_bindgen(unchecked_return_type = "DownloadEventReport")]
+pub fn analyze_download_event(
+    #[wasm_bindgen(unchecked_param_type = "DownloadEvent")] event: JsValue,
+    bytes: &[u8],
+) -> Result<JsValue, JsError> {
+    let e: vc::DownloadEvent = from_js(&event)?;
+    Ok(to_js(&analyzer().download_event(&e, bytes).to_json()))
+}
+
+/// Score a leaf certificate `{ schema_version?, host, issuer?, subject?,
+/// age_days, validity_days?, self_signed?, san? }`.
+#[wasm_bindgen(unchecked_return_type = "CertReport")]
+pub fn analyze_cert(#[wasm_bindgen(unchecked_param_type = "CertInfo")] cert: JsValue) -> Result<JsValue, JsError> {
+    let c: vc::CertInfo = from_js(&cert)?;
+    Ok(to_js(&analyzer().cert(&c).to_json()))
+}
+
+/// Score a navigation from whatever was collected: `{ schema_version?, url,
+/// page?, headers?: { name: value }, cert? }`. Channels left out are
+/// reported as missing rather than scored as clean.
+#[wasm_bindgen(unchecked_return_type = "CompositeReport")]
+pub fn analyze_composite(#[wasm_bindgen(unchecked_param_type = "Evidence")] evidence: JsValue) -> Result<JsValue, JsError> {
+    let e: vc::Evidence = from_js(&evidence)?;
+    Ok(to_js(&analyzer().composite(&e).to_json()))
+}
+
+/// Could `url`, as the address bar renders it, pass for another site? Checks
+/// confusable and mixed-script labels and, for an omnibox `max_chars` wide
+/// (0 for a typical phone), whether the real domain is cut off.
+#[wasm_bindgen(unchecked_return_type = "DisplaySpoofReport")]
+pub fn display_spoof_risk(url: &str, max_chars: usize) -> JsValue {
+    to_js(&analyzer().display_spoof(url, max_chars).to_json())
+}
+
+// ── Contexts ──────────────────────────────────────────────────────────────────
+
+/// Create an isolated context (e.g. a managed work profile) from
+/// `{ profile?, brands?, keywords?, keyword_packs?, psl?, max_input_bytes?,
+/// max_text_bytes?, feature_cache_entries?, allow?, block? }`. Returns its handle.
+#[wasm_bindgen]
+pub fn create_context(#[wasm_bindgen(unchecked_param_type = "ContextConfig")] config: JsValue) -> Result<u32, JsError> {
+    let c: vc::ContextConfig = from_js(&config)?;
+    Ok(with_contexts(|all| all.create(vc::Context::from_config(&c))))
+}
+
+/// Drop a context and all of its state. Handle `0` is the default context,
+/// which is reset instead.
+#[wasm_bindgen]
+pub fn destroy_context(handle: u32) -> bool {
+    with_contexts(|c| c.destroy(handle))
+}
+
+/// URL verdict under a context's configuration, feeds, reputation and overrides.
+#[wasm_bindgen(unchecked_return_type = "UrlReport")]
+pub fn context_analyze_url(handle: u32, url: &str) -> Result<JsValue, JsError> {
+    with_context(handle, |ctx| {
+        let r = ctx.url(url);
+        ctx.audit_mut().record(now_ms(), url, r.verdict, r.risk);
+        let report_required = ctx.must_report(r.verdict);
+        to_js(&r.to_json().with("report_required", report_required))
+    })
+}
+
+/// Install the admin policy from `chrome.storage.managed`:
+/// `{ force_block_categories?, warn_threshold?, block_threshold?,
+/// locked_domains?, disable_user_overrides?, require_reporting?,
+/// block_hosts?, allow_hosts? }`.
+#[wasm_bindgen]
+pub fn context_set_policy(
+    handle: u32,
+    #[wasm_bindgen(unchecked_param_type = "ManagedPolicy")] policy: JsValue,
+) -> Result<(), JsError> {
+    let p: vc::ManagedPolicy = from_js(&policy)?;
+    with_context(handle, |ctx| ctx.set_policy(p))
+}
+
+/// Preview a managed policy on the default context before installing it:
+/// how the verdicts of `sample_urls_jsonl` (a JSON URL string or `{ url }`
+/// per line) would move from the installed policy to `policy`. Nothing is
+/// learned or audited.
+#[wasm_bindgen(unchecked_return_type = "PolicySimulation")]
+pub fn simulate_policy(
+    #[wasm_bindgen(unchecked_param_type = "ManagedPolicy")] policy: JsValue,
+    sample_urls_jsonl: &str,
+) -> Result<JsValue, JsError> {
+    context_simulate_policy(vc::DEFAULT_CONTEXT, policy, sample_urls_jsonl)
+}
+
+#[wasm_bindgen(unchecked_return_type = "PolicySimulation")]
+pub fn context_simulate_policy(
+    handle: u32,
+    #[wasm_bindgen(unchecked_param_type = "ManagedPolicy")] policy: JsValue,
+    sample_urls_jsonl: &str,
+) -> Result<JsValue, JsError> {
+    let p: vc::ManagedPolicy = from_js(&policy)?;
+    with_context(handle, |ctx| to_js(&ctx.simulate_policy(&p, sample_urls_jsonl)))
+}
+
+/// Pin `rule` — a host, `*.host` for its subdomains only, either with a
+/// path prefix — to `verdict`. Returns false for a malformed rule or when
+/// the managed policy locks overrides for its host.
+#[wasm_bindgen]
+pub fn context_set_override(handle: u32, rule: &str, verdict: Verdict) -> Result<bool, JsError> {
+    with_context(handle, |ctx| ctx.set_override(rule, verdict.into()))
+}
+
+/// `evaluate_download_context` with the context's managed category blocks.
+#[wasm_bindgen(unchecked_return_type = "DownloadContextReport")]
+pub fn context_evaluate_download(
+    handle: u32,
+    mime: &str,
+    ext: &str,
+    initiating_origin: &str,
+    user_gesture: bool,
+) -> Result<JsValue, JsError> {
+    with_context(handle, |ctx| {
+        let r = ctx.download_context(mime, ext, initiating_origin, user_gesture);
+        let report_required = vc::Verdict::parse(r.str_field("verdict")).is_some_and(|v| ctx.must_report(v));
+        to_js(&r.with("report_required", report_required))
+    })
+}
+
+/// `analyze_deep_link` for a link tab `tab_id` opened: the context's managed
+/// category blocks apply to installs and the score joins the tab's session.
+#[wasm_bindgen(unchecked_return_type = "DeepLinkReport")]
+pub fn context_analyze_deep_link(
+    handle: u32,
+    tab_id: u32,
+    link: &str,
+    initiating_origin: &str,
+    user_gesture: bool,
+) -> Result<JsValue, JsError> {
+    with_context(handle, |ctx| {
+        let r = ctx.deep_link(tab_id, link, initiating_origin, user_gesture);
+        let report_required = vc::Verdict::parse(r.str_field("verdict")).is_some_and(|v| ctx.must_report(v));
+        to_js(&r.with("report_required", report_required))
+    })
+}
+
+#[wasm_bindgen]
+pub fn context_clear_override(handle: u32, rule: &str) -> Result<bool, JsError> {
+    with_context(handle, |ctx| ctx.clear_override(rule))
+}
+
+/// Check an override or admin host rule against `url` before deploying it;
+/// fails on a malformed rule.
+#[wasm_bindgen(unchecked_return_type = "RuleTest")]
+pub fn test_rule(url: &str, rule: &str) -> Result<JsValue, JsError> {
+    vc::test_rule(url, rule).map(|r| to_js(&r)).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Hash-only privacy mode: with a salt, URLs and hosts are only ever stored
+/// as salted SHA-256; `null` returns to plain keys. Either way the context's
+/// overrides, feeds, cached state and audit log are cleared.
+#[wasm_bindgen]
+pub fn context_set_privacy(handle: u32, salt: Option<String>) -> Result<(), JsError> {
+    if salt.as_deref() == Some("") { return Err(JsError::new("salt must not be empty")); }
+    let privacy = salt.map_or_else(vc::Privacy::plain, |s| vc::Privacy::hashed(s.as_bytes()));
+    with_context(handle, |ctx| ctx.set_privacy(privacy))
+}
+
+/// Replace the brands a context protects beyond the built-in list (see `set_brands`).
+#[wasm_bindgen]
+pub fn context_set_brands(handle: u32, brands: Vec<String>) -> Result<(), JsError> {
+    let brands: Vec<&str> = brands.iter().map(String::as_str).collect();
+    with_context(handle, |ctx| ctx.set_brands(&brands))?.map_err(|e| JsError::new(&e))
+}
+
+/// Add `brands` to the ones a context protects (see `set_brands`).
+#[wasm_bindgen]
+pub fn context_add_brands(handle: u32, brands: Vec<String>) -> Result<(), JsError> {
+    let brands: Vec<&str> = brands.iter().map(String::as_str).collect();
+    with_context(handle, |ctx| ctx.add_brands(&brands))?.map_err(|e| JsError::new(&e))
+}
+
+/// Replace feed `name` of a context with `hosts`; returns how many were loaded.
+#[wasm_bindgen]
+pub fn context_load_feed(handle: u32, name: &str, hosts: Vec<String>) -> Result<usize, JsError> {
+    with_context(handle, |ctx| ctx.load_feed(name, hosts))
+}
+
+#[wasm_bindgen]
+pub fn context_set_host_reputation(handle: u32, host: &str, score: f32) -> Result<(), JsError> {
+    with_context(handle, |ctx| ctx.state_mut().set_reputation(host, score))
+}
+
+#[wasm_bindgen]
+pub fn context_observe_tab_url(handle: u32, tab_id: u32, url: &str) -> Result<Verdict, JsError> {
+    with_context(handle, |ctx| observe_logged(ctx, tab_id, url))
+}
+
+#[wasm_bindgen]
+pub fn context_memo_verdict(handle: u32, url: &str) -> Result<Verdict, JsError> {
+    with_context(handle, |ctx| ctx.memo_verdict(url).into())
+}
+
+#[wasm_bindgen(unchecked_return_type = "NavigationFeatures")]
+pub fn context_tab_navigation_features(handle: u32, tab_id: u32) -> Result<JsValue, JsError> {
+    with_context(handle, |ctx| to_js(&ctx.state_mut().session(tab_id).navigation_report(now_ms())))
+}
+
+// ── Audit log ─────────────────────────────────────────────────────────────────
+
+fn export_format(format: &str) -> Result<vc::AuditFormat, JsError> {
+    vc::AuditFormat::parse(format).ok_or_else(|| JsError::new(&format!("unknown audit format \"{}\" (use jsonl or csv)", format)))
+}
+
+/// Recorded verdicts of the default context, oldest first, as `"jsonl"` or
+/// `"csv"`. Each row is `{ timestamp_ms, url_hash, verdict, score, model_version }`.
+#[wasm_bindgen]
+pub fn export_audit(format: &str) -> Result<String, JsError> {
+    context_export_audit(vc::DEFAULT_CONTEXT, format)
+}
+
+#[wasm_bindgen]
+pub fn context_export_audit(handle: u32, format: &str) -> Result<String, JsError> {
+    let f = export_format(format)?;
+    with_context(handle, |ctx| ctx.audit().export(f))
+}
+
+#[wasm_bindgen]
+pub fn context_clear_audit(handle: u32) -> Result<(), JsError> {
+    with_context(handle, |ctx| ctx.audit_mut().clear())
+}
+
+/// Version string stamped on this context's audit entries from now on
+/// (defaults to the engine version).
+#[wasm_bindgen]
+pub fn context_set_model_version(handle: u32, version: &str) -> Result<(), JsError> {
+    with_context(handle, |ctx| ctx.audit_mut().set_model_version(version))
+}
+
+// ── Personalization ──────────────────────────────────────────────────────────
+
+/// Teach the default context's personal layer the user's allow/block
+/// decision on `url`; returns the URL's new score (undefined for `Warn`).
+/// Persisted with `export_state`.
+#[wasm_bindgen]
+pub fn personal_feedback(url: &str, verdict: Verdict) -> Option<f32> {
+    with_contexts(|c| c.default_mut().personal_feedback(url, verdict.into()))
+}
+
+#[wasm_bindgen]
+pub fn context_personal_feedback(handle: u32, url: &str, verdict: Verdict) -> Result<Option<f32>, JsError> {
+    with_context(handle, |ctx| ctx.personal_feedback(url, verdict.into()))
+}
+
+/// Compare the ONNX model's `model_score` for `url` with the default
+/// context's verdict; true when they disagree enough that the URL was kept
+/// as a hard negative.
+#[wasm_bindgen]
+pub fn model_disagreement(url: &str, model_score: f32) -> bool {
+    with_contexts(|c| c.default_mut().model_disagreement(url, model_score))
+}
+
+#[wasm_bindgen]
+pub fn context_model_disagreement(handle: u32, url: &str, model_score: f32) -> Result<bool, JsError> {
+    with_context(handle, |ctx| ctx.model_disagreement(url, model_score))
+}
+
+/// Hard negatives mined from feedback and model disagreement, oldest first,
+/// as training JSONL: `{ label, features, url_hash, url?, score, source }`
+/// per line, without `url` in hash-only mode.
+#[wasm_bindgen]
+pub fn export_hard_negatives() -> String {
+    with_contexts(|c| c.default_mut().hard_negatives().export_jsonl())
+}
+
+#[wasm_bindgen]
+pub fn context_export_hard_negatives(handle: u32) -> Result<String, JsError> {
+    with_context(handle, |ctx| ctx.hard_negatives().export_jsonl())
+}
+
+/// Drop the mined hard negatives, e.g. once they've been uploaded.
+#[wasm_bindgen]
+pub fn context_clear_hard_negatives(handle: u32) -> Result<(), JsError> {
+    with_context(handle, |ctx| ctx.hard_negatives_mut().clear())
+}
+
+/// `{ weights, updates }` of the personal layer.
+#[wasm_bindgen(unchecked_return_type = "Personalization")]
+pub fn context_personalization(handle: u32) -> Result<JsValue, JsError> {
+    with_context(handle, |ctx| to_js(&ctx.personal().export()))
+}
+
+#[wasm_bindgen]
+pub fn reset_personalization() {
+    with_contexts(|c| c.default_mut().reset_personalization())
+}
+
+#[wasm_bindgen]
+pub fn context_reset_personalization(handle: u32) -> Result<(), JsError> {
+    with_context(handle, |ctx| ctx.reset_personalization())
+}
+
+// ── Template learning & state persistence ────────────────────────────────────
+
+/// Learn the URL templates of frequently visited sites and flag navigations
+/// that depart from them (`unusual_site_template`, `kit_path_on_known_site`).
+/// Turning it off forgets what was learned.
+#[wasm_bindgen]
+pub fn context_set_template_learning(handle: u32, on: bool) -> Result<(), JsError> {
+    with_context(handle, |ctx| ctx.state_mut().set_template_learning(on))
+}
+
+/// Overrides, feeds, reputation, first-seen times, learned templates and the
+/// personal layer of the default context as one JSON document for `chrome.storage`.
+#[wasm_bindgen]
+pub fn export_state() -> Result<String, JsError> {
+    context_export_state(vc::DEFAULT_CONTEXT)
+}
+
+#[wasm_bindgen]
+pub fn context_export_state(handle: u32) -> Result<String, JsError> {
+    with_context(handle, |ctx| ctx.export_state().to_string())
+}
+
+/// Merge a document from `export_state`; returns how many entries were loaded.
+#[wasm_bindgen]
+pub fn import_state(state: &str) -> Result<usize, JsError> {
+    context_import_state(vc::DEFAULT_CONTEXT, state)
+}
+
+#[wasm_bindgen]
+pub fn context_import_state(handle: u32, state: &str) -> Result<usize, JsError> {
+    let v = Json::parse(state).map_err(|e| JsError::new(&e))?;
+    with_context(handle, |ctx| ctx.import_state(&v))?.map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// The default context's profile, protected brands, overrides, personal
+/// layer and feedback as a versioned user-data file, for moving to another
+/// machine or attaching to a support request.
+#[wasm_bindgen]
+pub fn export_user_data() -> Vec<u8> {
+    with_contexts(|c| c.default_mut().export_user_data())
+}
+
+#[wasm_bindgen]
+pub fn context_export_user_data(handle: u32) -> Result<Vec<u8>, JsError> {
+    with_context(handle, |ctx| ctx.export_user_data())
+}
+
+/// Load a file from `export_user_data`; returns how many entries were loaded.
+/// Fails without changes on a damaged file or one from another privacy salt.
+#[wasm_bindgen]
+pub fn import_user_data(bytes: &[u8]) -> Result<usize, JsError> {
+    context_import_user_data(vc::DEFAULT_CONTEXT, bytes)
+}
+
+#[wasm_bindgen]
+pub fn context_import_user_data(handle: u32, bytes: &[u8]) -> Result<usize, JsError> {
+    with_context(handle, |ctx| ctx.import_user_data(bytes))?.map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// `export_state` split into JSON strings of at most `max_chunk` bytes each,
+/// one per storage item (chrome.storage caps item size). Each chunk imports
+/// on its own with `import_state_chunk`.
+#[wasm_bindgen]
+pub fn export_state_chunks(max_chunk: usize) -> Result<Vec<String>, JsError> {
+    context_export_state_chunks(vc::DEFAULT_CONTEXT, max_chunk)
+}
+
+#[wasm_bindgen]
+pub fn context_export_state_chunks(handle: u32, max_chunk: usize) -> Result<Vec<String>, JsError> {
+    with_context(handle, |ctx| ctx.export_state_chunks(max_chunk))?
+        .map(|chunks| chunks.iter().map(Json::to_string).collect())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Merge one chunk from `export_state_chunks`, in any order; returns how
+/// many entries it loaded.
+#[wasm_bindgen]
+pub fn import_state_chunk(chunk: &str) -> Result<usize, JsError> {
+    context_import_state_chunk(vc::DEFAULT_CONTEXT, chunk)
+}
+
+#[wasm_bindgen]
+pub fn context_import_state_chunk(handle: u32, chunk: &str) -> Result<usize, JsError> {
+    let v = Json::parse(chunk).map_err(|e| JsError::new(&e))?;
+    with_context(handle, |ctx| ctx.import_state_chunk(&v))?.map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// `blocklist_state` (an `export_state` document) as declarativeNetRequest
+/// rules for `chrome.declarativeNetRequest.updateDynamicRules`, at most
+/// `max_rules` of them (0 for Chrome's dynamic-rule quota). User allows
+/// outrank user blocks, which outrank feed hosts; feed hosts drop first.
+#[wasm_bindgen(unchecked_return_type = "DnrRuleSet")]
+pub fn to_dnr_rules(blocklist_state: &str, max_rules: usize) -> Result<JsValue, JsError> {
+    let v = Json::parse(blocklist_state).map_err(|e| JsError::new(&e))?;
+    vc::dnr_rules(&v, max_rules).map(|r| to_js(&r)).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// The context's own overrides and feeds as declarativeNetRequest rules.
+#[wasm_bindgen(unchecked_return_type = "DnrRuleSet")]
+pub fn context_dnr_rules(handle: u32, max_rules: usize) -> Result<JsValue, JsError> {
+    with_context(handle, |ctx| ctx.dnr_rules(max_rules))?.map(|r| to_js(&r)).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Human-readable reasons for any report in `locale` (`"pt-BR"`, `"de"`, …;
+/// unknown locales fall back to English), so the popup needn't carry its own
+/// strings for every flag.
+#[wasm_bindgen(unchecked_return_type = "Explanation")]
+pub fn render_explanation(report: JsValue, locale: &str) -> Result<JsValue, JsError> {
+    let src = js_json_stringify(&report).as_string().unwrap_or_default();
+    let v = Json::parse(&src).map_err(|e| JsError::new(&e))?;
+    Ok(to_js(&vc::render_explanation(&v, locale)))
+}
+
+/// Signals of any report ranked by severity, overlapping ones merged into
+/// the strongest, so every surface lists them in the same order.
+#[wasm_bindgen(unchecked_return_type = "RankedSignals")]
+pub fn rank_signals(report: JsValue) -> Result<JsValue, JsError> {
+    let src = js_json_stringify(&report).as_string().unwrap_or_default();
+    let v = Json::parse(&src).map_err(|e| JsError::new(&e))?;
+    Ok(to_js(&vc::ranked_signals(&v)))
+}
+
+/// Field-level diff of two reports of the same input, e.g. from two engine
+/// versions over the golden corpus: score delta, verdict change, flags and
+/// findings added or removed, changed features and every other differing field.
+#[wasm_bindgen(unchecked_return_type = "ReportDiff")]
+pub fn diff_reports(a: JsValue, b: JsValue) -> Result<JsValue, JsError> {
+    let parse = |r: &JsValue| Json::parse(&js_json_stringify(r).as_string().unwrap_or_default()).map_err(|e| JsError::new(&e));
+    Ok(to_js(&vc::diff_reports(&parse(&a)?, &parse(&b)?)))
+}
+
+/// `types.d.ts` is written by hand; these keep it from falling behind the
+/// reports. Every key a core report emits must be declared on the
+/// interface its export promises, nested objects included.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const LIB_RS: &str = include_str!("lib.rs");
+    const TS_TYPES: &str = include_str!("types.d.ts");
+
+    /// `(name, type)` of an object type's members.
+    type Members = Vec<(String, String)>;
+
+    /// Interfaces (what they extend, and their members) and type aliases of
+    /// a declaration file.
+    struct Ts {
+        interfaces: HashMap<String, (Vec<String>, Members)>,
+        aliases:    HashMap<String, String>,
+    }
+
+    fn strip_comments(src: &str) -> String {
+        let mut out = String::new();
+        let mut rest = src;
+        while let Some(i) = rest.find("/*") {
+            out.push_str(&rest[..i]);
+            rest = rest[i..].find("*/").map_or("", |e| &rest[i + e + 2..]);
+        }
+        out.push_str(rest);
+        out.lines().map(|l| if l.trim_start().starts_with("//") { "" } else { l }).collect::<Vec<_>>().join("\n")
+    }
+
+    /// `text` cut at each depth-0 `sep`.
+    fn split_top(text: &str, seps: &[char]) -> Vec<String> {
+        let (mut parts, mut cur, mut depth) = (Vec::new(), String::new(), 0i32);
+        for c in text.chars() {
+            match c {
+                '{' | '(' | '<' | '[' => depth += 1,
+                '}' | ')' | '>' | ']' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 && seps.contains(&c) { parts.push(std::mem::take(&mut cur)); } else { cur.push(c); }
+        }
+        parts.push(cur);
+        parts.into_iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect()
+    }
+
+    /// `(name, type)` of each member of an object type's body.
+    fn members(body: &str) -> Members {
+        split_top(body, &[';', '\n', ',']).into_iter().filter_map(|m| {
+            let (name, ty) = split_top(&m, &[':']).split_first().map(|(n, t)| (n.clone(), t.join(":")))?;
+            Some((name.trim_end_matches('?').trim_matches('"').to_string(), ty))
+        }).collect()
+    }
+
+    /// Text from the `{` at `open` to its matching `}`, exclusive.
+    fn braced(src: &str, open: usize) -> &str {
+        let mut depth = 0;
+        for (i, c) in src[open..].char_indices() {
+            match c { '{' => depth += 1, '}' => { depth -= 1; if depth == 0 { return &src[open + 1..open + i]; } } _ => {} }
+        }
+        &src[open + 1..]
+    }
+
+    impl Ts {
+        fn parse(src: &str) -> Ts {
+            let src = strip_comments(src);
+            let mut ts = Ts { interfaces: HashMap::new(), aliases: HashMap::new() };
+            for (i, _) in src.match_indices("export interface ") {
+                let head = &src[i + 17..];
+                let open = head.find('{').unwrap_or(head.len());
+                let (name, extends) = head[..open].split_once(" extends ").unwrap_or((&head[..open], ""));
+                let extends = extends.split(',').map(|e| e.trim().to_string()).filter(|e| !e.is_empty()).collect();
+                ts.interfaces.insert(name.trim().to_string(), (extends, members(braced(head, open))));
+            }
+            for (i, _) in src.match_indices("export type ") {
+                let head = &src[i + 12..];
+                let Some((name, rest)) = head.split_once('=') else { continue };
+                let ty = split_top(rest, &[';']).into_iter().next().unwrap_or_default();
+                ts.aliases.insert(name.trim().to_string(), ty);
+            }
+            ts
+        }
+
+        fn declares(&self, name: &str) -> bool { self.interfaces.contains_key(name) || self.aliases.contains_key(name) }
+
+        /// Declared fields of object type `ty` (unions and intersections
+        /// merged), with the value type of an index signature or `Record`;
+        /// `None` when `ty` is no object type this file describes.
+        fn shape(&self, ty: &str) -> Option<(HashMap<String, String>, Option<String>)> {
+            let mut fields = HashMap::new();
+            let mut any = None;
+            let mut found = false;
+            for part in split_top(ty, &['|', '&']) {
+                let part = part.trim_start_matches('(').trim_end_matches(')').trim();
+                if part.ends_with("[]") { continue; }
+                if let Some(body) = part.strip_prefix('{') {
+                    fields.extend(members(body.trim_end_matches('}')));
+                    found = true;
+                } else if let Some(args) = part.strip_prefix("Record<").and_then(|a| a.strip_suffix('>')) {
+                    any = split_top(args, &[',']).get(1).cloned();
+                    found = true;
+                } else if let Some((extends, own)) = self.interfaces.get(part) {
+                    for e in extends {
+                        if let Some((f, a)) = self.shape(e) { fields.extend(f); any = any.or(a); }
+                    }
+                    fields.extend(own.iter().cloned());
+                    found = true;
+                } else if let Some((f, a)) = self.aliases.get(part).and_then(|t| self.shape(t)) {
+                    fields.extend(f);
+                    any = any.or(a);
+                    found = true;
+                }
+            }
+            let any = any.or_else(|| fields.iter().find(|(k, _)| k.starts_with('[')).map(|(_, t)| t.clone()));
+            found.then_some((fields, any))
+        }
+
+        /// Element type of the array alternatives of `ty`.
+        fn element(&self, ty: &str) -> Option<String> {
+            split_top(ty, &['|']).into_iter().find_map(|p| {
+                let p = p.trim_start_matches('(').trim_end_matches(')').to_string();
+                p.strip_suffix("[]").map(str::to_string)
+                    .or_else(|| self.aliases.get(&p).and_then(|t| self.element(t)))
+            })
+        }
+
+        /// Paths of keys in `v` that `ty` doesn't declare.
+        fn undeclared(&self, v: &Json, ty: &str, path: &str, out: &mut Vec<String>) {
+            match v {
+                Json::Arr(items) => if let Some(e) = self.element(ty) {
+                    for (i, x) in items.iter().enumerate() { self.undeclared(x, &e, &format!("{}[{}]", path, i), out); }
+                },
+                Json::Obj(entries) => if let Some((fields, any)) = self.shape(ty) {
+                    for (k, x) in entries {
+                        let at = format!("{}.{}", path, k);
+                        match fields.get(k).or(any.as_ref()) {
+                            Some(t) => self.undeclared(x, t, &at, out),
+                            None => out.push(at),
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn every_return_type_is_declared() {
+        let ts = Ts::parse(TS_TYPES);
+        let missing: Vec<&str> = LIB_RS.split("unchecked_return_type = \"").skip(1)
+            .filter_map(|s| s.split('"').next())
+            .flat_map(|t| t.split('|'))
+            .map(|t| t.trim().trim_start_matches("Promise<").trim_end_matches('>'))
+            .filter(|t| !matches!(*t, "null" | "Float32Array") && !ts.declares(t))
+            .collect();
+        assert!(missing.is_empty(), "types.d.ts lacks {:?}", missing);
+    }
+
+    #[test]
+    fn report_fields_are_declared() {
+        let ts = Ts::parse(TS_TYPES);
+        let a = analyzer();
+        let url = a.url("http://paypal.com.secure-login.tk/verify?next=https://evil.tk/").to_json();
+        let cert = vc::CertInfo { host: "paypal-verify.tk".into(), age_days: 0.5, self_signed: true, ..Default::default() };
+        let page = vc::PageSnapshot {
+            url: "http://paypal.com.secure-login.tk/verify".into(), title: "PayPal".into(), text: "Enter your password".into(),
+            forms: vec![vc::FormSnapshot { action: "https://evil.tk/post".into(), method: "post".into(), has_password: true }],
+            links: vec!["https://evil.tk/".into()], scripts: vec!["eval(atob('YQ=='))".into()], csp: Some(String::new()), ..Default::default()
+        };
+        let download = vc::DownloadEvent { filename: "invoice.pdf.exe".into(), mime: "application/x-msdownload".into(), initiating_origin: "http://evil.tk".into(), ..Default::default() };
+        let samples: Vec<(&str, Json)> = vec![
+            ("UrlReport", url.clone()),
+            ("NamedFeatures", vc::name_features(&vc::extract_features("http://paypal.com.secure-login.tk/"))),
+            ("FeatureSchema", vc::feature_schema()),
+            ("OAuthReport", vc::oauth_report("https://login.micros0ftonline.com/common/oauth2/v2.0/authorize?client_id=x&response_type=code&redirect_uri=http://evil.tk/cb&scope=mail.read")),
+            ("PaymentTargetReport", vc::compare_payment_targets("pay alice@okhdfcbank", "pay bob@ybl")),
+            ("IntentReport", vc::intent_report("intent://scan/#Intent;scheme=zxing;package=com.evil;S.browser_fallback_url=http%3A%2F%2Fevil.tk;end")),
+            ("UpiRequestReport", vc::upi_request_report("upi://pay?pa=scam@ybl&pn=PayPal&am=5000", "refund")),
+            ("DataUriReport", vc::data_uri_report("data:text/html;base64,PHNjcmlwdD5ldmFsKGF0b2IoJ2EnKSk8L3NjcmlwdD4=")),
+            ("SchemePayloadReport", vc::scheme_payload_report("javascript:eval(atob('YWxlcnQoMSk='))")),
+            ("ContactLinkReport", vc::contact_link_report("mailto:support@paypa1-help.com", "support@paypal.com")),
+            ("AuthResultsReport", vc::auth_results_json(&vc::parse_authentication_results("mx.example; spf=fail smtp.mailfrom=evil.tk; dkim=none; dmarc=fail header.from=paypal.com"))),
+            ("DmarcReport", vc::dmarc_json(&vc::parse_dmarc("v=DMARC1; p=none; rua=mailto:d@example.com"))),
+            ("SpfReport", vc::spf_json(&vc::parse_spf("v=spf1 include:_spf.example.com +all"))),
+            ("MailReport | ErrorReport", a.mail(r#"{"from":"PayPal <x@evil.tk>","subject":"Verify now","body_html":"<a href='http://evil.tk'>paypal.com</a>"}"#)),
+            ("IcsReport", a.ics("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:Verify\r\nDESCRIPTION:http://evil.tk/login\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n")),
+            ("PdfReport", a.pdf(b"%PDF-1.4\n1 0 obj << /A << /S /URI /URI (http://evil.tk/login) >> /OpenAction << /S /JavaScript /JS (app.alert(1)) >> >>")),
+            ("OfficeReport", a.office(b"PK\x03\x04")),
+            ("HtmlAttachmentReport", a.html_attachment(b"<form action='http://evil.tk'><input type=password></form><script>eval(atob('YQ=='))</script>")),
+            ("ApkReport", a.apk(b"PK\x03\x04")),
+            ("ArchiveReport", a.archive("invoice.zip", b"PK\x03\x04", "password 1234")),
+            ("FilenameReport", vc::filename_report("attachment; filename=\"invoice.pdf.exe\"")),
+            ("DownloadReport", a.download("invoice.pdf.exe", b"MZ")),
+            ("DownloadContextReport", vc::download_context_report("application/x-msdownload", "exe", "http://evil.tk", false)),
+            ("CompromisedSiteReport", vc::compromised_site_report("https://example.com/wp-content/uploads/login.php", true)),
+            ("EndpointReport", vc::score_endpoint("https://discord.com/api/webhooks/1/x", "https://bank.example", "fetch")),
+            ("DeepLinkReport", vc::deep_link_report("tg://resolve?domain=support", "http://evil.tk", false)),
+            ("EmbeddedUrls", vc::extract_embedded_urls("https://t.example/r?u=https%3A%2F%2Fevil.tk%2Flogin", true)),
+            ("Capabilities", vc::capabilities_report(&a)),
+            ("DataManifest", vc::data_manifest()),
+            ("FeatureImportances", vc::feature_importances()),
+            ("SelfTestReport", vc::self_test()),
+            ("TestVectors", vc::gen_test_vectors(1, 20)),
+            ("CspReport", vc::csp_report("script-src 'unsafe-inline' *", false)),
+            ("ResourceGraphReport | ErrorReport", a.resource_graph(r#"[{"url":"https://evil.tk/x.js","type":"script"}]"#, "https://bank.example/")),
+            ("PageReport", a.page(&page).to_json()),
+            ("DownloadEventReport", a.download_event(&download, b"MZ").to_json()),
+            ("CertReport", a.cert(&cert).to_json()),
+            ("DisplaySpoofReport", a.display_spoof("https://xn--pypal-4ve.com/", 40).to_json()),
+            ("Explanation", vc::render_explanation(&url, "en")),
+            ("RankedSignals", vc::ranked_signals(&url)),
+            ("ReportDiff", vc::diff_reports(&url, &a.url("https://example.com/").to_json())),
+        ];
+        let mut missing = Vec::new();
+        for (ty, report) in &samples { ts.undeclared(report, ty, ty, &mut missing); }
+        assert!(missing.is_empty(), "fields missing from types.d.ts: {:?}", missing);
+    }
+}
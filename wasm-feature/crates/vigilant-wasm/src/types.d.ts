@@ -0,0 +1,956 @@
+// wasm-feature/crates/vigilant-wasm/src/types.d.ts
+// Browser Vigilant — TypeScript shapes of every report the WASM exports return
+// Appended verbatim to the generated wasm_feature.d.ts. Keep in step with the
+// `Json::obj()` builders in vigilant-core; field order follows the core report.
+// The tests at the end of lib.rs fail when a report emits a key not declared here.
+
+export type VerdictName = "allow" | "warn" | "block";
+
+/** "uncertain" is the band worth an online lookup before trusting `verdict`. */
+export type DecisionName = "safe" | "unsafe" | "uncertain";
+
+/** Fields every scored report carries. */
+export interface Scored {
+  flags: string[];
+  score: number;
+  verdict: VerdictName;
+}
+
+// ── Typed snapshots (inputs) ─────────────────────────────────────────────────
+
+export interface FormSnapshot {
+  action?: string;
+  /** Defaults to `"get"`. */
+  method?: string;
+  has_password?: boolean;
+}
+
+export interface PageSnapshot {
+  /** Omit for the current version; newer versions are rejected. */
+  schema_version?: number;
+  url: string;
+  title?: string;
+  text?: string;
+  forms?: FormSnapshot[];
+  links?: string[];
+  /** Origin the password manager would fill here, if any. */
+  saved_origin?: string | null;
+  /** Inline `<script>` bodies and event-handler attributes. */
+  scripts?: string[];
+  /** Enforced Content-Security-Policy; "" when the page had none. */
+  csp?: string | null;
+}
+
+/** At least one of `filename` / `content_disposition` is required. */
+export interface DownloadEvent {
+  schema_version?: number;
+  filename?: string;
+  content_disposition?: string | null;
+  mime?: string;
+  initiating_origin?: string;
+  user_gesture?: boolean;
+}
+
+/** What the host looked up about a site; every field is optional. */
+export interface HostSignals {
+  schema_version?: number;
+  /** Days since the registrable domain was registered (WHOIS / RDAP). */
+  domain_age_days?: number | null;
+  /** Position in a popularity list such as Tranco, 1 for the top site. */
+  rank?: number | null;
+  /** Days since the leaf certificate's `notBefore`. */
+  cert_age_days?: number | null;
+}
+
+export interface CertInfo {
+  schema_version?: number;
+  host: string;
+  issuer?: string;
+  subject?: string;
+  /** Days since `notBefore`. */
+  age_days: number;
+  /** `notAfter - notBefore` in days. */
+  validity_days?: number;
+  self_signed?: boolean;
+  san?: string[];
+}
+
+/** Everything collected about one navigation; omit a channel that wasn't collected. */
+export interface Evidence {
+  schema_version?: number;
+  url: string;
+  /** `url` defaults to the navigation's. */
+  page?: Omit<PageSnapshot, "url"> & { url?: string } | null;
+  /** Response headers, `name: value`. */
+  headers?: Record<string, string> | null;
+  cert?: CertInfo | null;
+}
+
+export type ProfileName = "strict" | "balanced" | "permissive";
+export type KeywordPackName = "login" | "trust" | "payments" | "giveaway" | "fraud";
+
+/** Argument of `create_context`. */
+export interface ContextConfig {
+  schema_version?: number;
+  profile?: ProfileName;
+  brands?: string[];
+  keywords?: string[];
+  keyword_packs?: KeywordPackName[];
+  psl?: boolean;
+  max_input_bytes?: number;
+  max_text_bytes?: number;
+  feature_cache_entries?: number;
+  /** Hosts (and their subdomains) always allowed / always blocked. */
+  allow?: string[];
+  block?: string[];
+  /** Enables hash-only privacy mode with this salt. */
+  privacy_salt?: string;
+  /** Learn frequent sites' URL templates and flag departures from them. */
+  learn_templates?: boolean;
+}
+
+/** Argument of `context_set_policy` and `simulate_policy` (from `chrome.storage.managed`). */
+export interface ManagedPolicy {
+  schema_version?: number;
+  force_block_categories?: DownloadCategory[];
+  warn_threshold?: number;
+  block_threshold?: number;
+  /** Domains (and subdomains) where user overrides are ignored. */
+  locked_domains?: string[];
+  disable_user_overrides?: boolean;
+  require_reporting?: boolean;
+  /** Host rules: `host`, `*.host` or either with a path prefix. */
+  block_hosts?: string[];
+  allow_hosts?: string[];
+}
+
+export interface VerdictCounts {
+  allow: number;
+  warn: number;
+  block: number;
+}
+
+/** Returned by `simulate_policy`: installed policy (`current`) against the proposed one. */
+export interface PolicySimulation {
+  urls: number;
+  /** Lines past the simulation's limit, not scored. */
+  skipped: number;
+  errors: { line: number; error: string }[];
+  current: VerdictCounts;
+  proposed: VerdictCounts;
+  changed: number;
+  raised: number;
+  lowered: number;
+  transitions: { from: VerdictName; to: VerdictName; count: number }[];
+  /** The first changed URLs. */
+  examples: { url: string; risk: number; from: VerdictName; to: VerdictName }[];
+}
+
+export interface MailLinkInput {
+  href: string;
+  text?: string;
+}
+
+/** Serialise with `JSON.stringify` before passing to `analyze_mail_message`. */
+export interface MailMessage {
+  sender?: string;
+  sender_domain?: string;
+  display_name?: string;
+  reply_to?: string;
+  urls?: (string | MailLinkInput)[];
+  authentication_results?: string;
+  dmarc_record?: string;
+  spf_record?: string;
+}
+
+// ── URL and mail reports ─────────────────────────────────────────────────────
+
+export interface UrlReport {
+  features: number[];
+  risk: number;
+  verdict: VerdictName;
+  /** Half-width of the band `risk` may be off by. */
+  uncertainty: number;
+  decision: DecisionName;
+  flags: string[];
+  /** Values from signal providers the host registered; absent when none are. */
+  signals?: CustomSignal[];
+  /** Milliseconds per stage in the order they ran, plus `total`; present while `set_timing(true)` is on. A cached report has a single `cache` stage. */
+  timings?: Record<string, number>;
+  /** The managed policy wants this verdict reported. */
+  report_required: boolean;
+}
+
+export interface CustomSignal {
+  provider: string;
+  name: string;
+  value: number;
+  /** Risk the value added. */
+  contribution: number;
+  severity: Severity;
+}
+
+export interface OAuthReport {
+  is_oauth: boolean;
+  provider: string | null;
+  lookalike_of: string | null;
+  client_id?: string | null;
+  redirect_uri?: string | null;
+  redirect_host?: string | null;
+  response_type?: string | null;
+  scopes?: string[];
+  broad_scopes?: string[];
+  flags?: string[];
+  risk: number;
+}
+
+export type PaymentAsset = "upi" | "bitcoin" | "ethereum" | "litecoin" | "tron";
+
+export interface PaymentTargetReport extends Scored {
+  /** Asset of the copied target; null when nothing payable was copied. */
+  asset: PaymentAsset | null;
+  copied: string | null;
+  pasted: string | null;
+  pasted_asset: PaymentAsset | null;
+  /** The two addresses split into shared prefix, differing middles and shared suffix. */
+  diff: { prefix: string; copied: string; pasted: string; suffix: string } | null;
+}
+
+export interface UpiRequestReport extends Scored {
+  /** False when the payload isn't a UPI request; the other fields are then absent. */
+  upi: boolean;
+  /** "pull" for collect and mandate requests, where the payee takes the money. */
+  kind?: "pay" | "pull";
+  action?: string;
+  /** `pa`, the VPA the money goes to. */
+  payee?: string | null;
+  payee_name?: string | null;
+  amount?: string | null;
+  currency?: string | null;
+  /** `tn`, the transaction note. */
+  note?: string | null;
+  handle?: string | null;
+  handle_known?: boolean | null;
+  /** Phrase that frames the request as money coming in. */
+  framing?: string | null;
+}
+
+export interface DataUriReport extends Scored {
+  /** False for anything but a `data:` URI; the other fields are then absent. */
+  data_uri: boolean;
+  /** Declared media type, lowercased; "text/plain" when omitted. */
+  mime?: string;
+  params?: Record<string, string>;
+  base64?: boolean;
+  /** False when a base64 payload doesn't decode. */
+  decoded?: boolean;
+  /** The payload is longer than the inspected prefix. */
+  truncated?: boolean;
+  payload_bytes?: number;
+  /** Shannon entropy of the decoded payload, in bits per byte. */
+  entropy?: number;
+  /** What the payload's bytes are: "html", "svg", "pdf", "pe", "zip", … or "unknown". */
+  content_kind?: string;
+  mime_mismatch?: boolean;
+  /** The declared type is a script type. */
+  script?: boolean;
+  forms?: number;
+  /** Markup payloads, scanned as an attachment. */
+  content?: HtmlAttachmentReport | null;
+}
+
+export interface SchemePayloadReport extends Scored {
+  /** Lowercased scheme, "" when the text has none. */
+  scheme: string;
+  /** False for anything but `javascript:` and `blob:`; the fields below are then absent. */
+  payload: boolean;
+  /** Percent-decoded script of a `javascript:` URL. */
+  code?: string | null;
+  /** What the script's `atob` literals decode to. */
+  decoded?: string[];
+  obfuscation?: string[];
+  page_writes?: string[];
+  credential_words?: string[];
+  navigations?: string[];
+  links?: string[];
+  /** Origin of a `blob:` URL; "null" for one made by an opaque origin. */
+  origin?: string | null;
+  /** `scheme_risk_features` of the URL. */
+  features?: number[];
+}
+
+export interface ContactLinkReport extends Scored {
+  /** "mailto" or "tel"; null for other links, whose other fields are then absent. */
+  kind: "mailto" | "tel" | null;
+  recipients?: { address: string; display: string | null }[];
+  /** Recipient domain, the look-alike or freemail one when flagged. */
+  domain?: string | null;
+  /** Brand the recipient domain or name imitates. */
+  brand?: string | null;
+  /** Number a `tel:` link dials: digits, a leading `+`, `*` and `#`. */
+  number?: string | null;
+  /** Link text as passed in; null when empty. */
+  shown?: string | null;
+  /** `contact_features` of the link. */
+  features?: number[];
+}
+
+export interface IntentReport extends Scored {
+  /** False for anything but an `intent:` link; the other fields are then absent. */
+  intent: boolean;
+  /** Host and path after `intent://`. */
+  target?: string;
+  scheme?: string | null;
+  package?: string | null;
+  action?: string | null;
+  component?: string | null;
+  /** `S.browser_fallback_url`, loaded when the app isn't installed. */
+  fallback_url?: string | null;
+  fallback_host?: string;
+  /** Brand the package impersonates. */
+  brand?: string | null;
+  extras?: { key: string; value: string }[];
+}
+
+export interface CspReport {
+  present: boolean;
+  report_only: boolean;
+  directives: string[];
+  issues: string[];
+  strength: number;
+  grade: "none" | "weak" | "moderate" | "strong";
+}
+
+export interface Personalization {
+  /** bias, global score logit, https, login keyword, suspicious TLD, hyphenated host */
+  weights: number[];
+  /** Decisions learned since the last reset. */
+  updates: number;
+}
+
+export type Severity = "info" | "low" | "medium" | "high" | "critical";
+
+export interface RankedSignal {
+  /** Flag name, finding tag, URL feature name or custom signal name. */
+  id: string;
+  kind: "flag" | "finding" | "feature" | "custom";
+  severity: Severity;
+  /** Weaker signals for the same evidence folded into this one. */
+  subsumes: string[];
+  service?: string;
+  endpoint?: string;
+  /** Signal provider of a custom signal. */
+  provider?: string;
+}
+
+export interface ModelInfo {
+  container_version: number;
+  /** Payload format: "onnx" (run in-crate when it is a tree ensemble), or "trees" for `score_url`. */
+  kind: string;
+  trained_at: string;
+  dataset_hash: string;
+  feature_version: number;
+  feature_count: number;
+  /** Feature schema hash the model was trained against; empty when its header doesn't say. */
+  feature_schema: string;
+  /** Platt scaling: sigmoid(slope · logit(p) + intercept). */
+  calibration: { slope: number; intercept: number };
+  license: string;
+  payload_sha256: string;
+  payload_bytes: number;
+}
+
+export interface FeatureImportance {
+  /** Index in the model feature vector. */
+  index: number;
+  name: string | null;
+  /** Rule weight, or the tree model's split count. */
+  weight: number;
+  /** Share of all weights or splits, 0–1. */
+  importance: number;
+}
+
+export interface FeatureImportances {
+  /** "trees" once a tree model is loaded, else "url_weights". */
+  model: string;
+  /** "split_count" or "heuristic". */
+  kind: string;
+  /** Heaviest first. */
+  features: FeatureImportance[];
+}
+
+export interface RankedSignals {
+  /** Highest severity present, or null when nothing fired. */
+  severity: Severity | null;
+  /** Most severe first. */
+  signals: RankedSignal[];
+}
+
+export interface NamedFeature {
+  index: number;
+  value: number;
+  /** Weight in the heuristic score; 0 for unweighted features. */
+  weight: number;
+  /** What the feature adds to the score for this URL. */
+  contribution: number;
+  /** Null for a flag that isn't set. */
+  reason: string | null;
+}
+
+/** Model features keyed by name, e.g. `brand_spoof_flag`, `url_entropy`. */
+export type NamedFeatures = Record<string, NamedFeature>;
+
+export type FeatureType = "flag" | "ratio" | "count" | "length" | "bits";
+
+export interface FeatureSchema {
+  /** Layout of this report. */
+  schema_version: number;
+  feature_version: number;
+  /** Hash of the version and every name and type in order; `features.py` computes the same. */
+  hash: string;
+  features: { index: number; name: string; type: FeatureType }[];
+}
+
+export interface ReportDiff {
+  /** No section below lists a difference. */
+  identical: boolean;
+  /** `delta` is b − a. */
+  score: { a: number | null; b: number | null; delta: number | null };
+  verdict: { a: VerdictName | null; b: VerdictName | null; changed: boolean };
+  /** `added`: in b but not in a. */
+  flags: { added: string[]; removed: string[] };
+  findings: { added: Finding[]; removed: Finding[] };
+  features: { index: number; name: string | null; a: number | null; b: number | null; delta: number | null }[];
+  /** Every other leaf that differs, by dotted path. */
+  fields: { path: string; a: unknown; b: unknown }[];
+}
+
+export interface Message {
+  key: string;
+  /** Slot values the text was rendered with, by slot name. */
+  params: Record<string, string>;
+  text: string;
+}
+
+export interface Explanation {
+  /** Locale actually used. */
+  locale: string;
+  verdict: Message | null;
+  reasons: (Message & { flag: string })[];
+  findings: (Message & { tag: string })[];
+}
+
+export interface DnrRule {
+  id: number;
+  priority: number;
+  action: { type: "allow" | "block" };
+  condition: { requestDomains: string[]; resourceTypes: string[] };
+}
+
+export interface DnrRuleSet {
+  rules: DnrRule[];
+  hosts: number;
+  emitted: number;
+  dropped: number;
+  invalid: number;
+}
+
+export interface ResourceGraphReport extends Scored {
+  page_site: string;
+  resources: number;
+  distinct_sites: number;
+  third_party_sites: number;
+  credential_sinks: string[];
+  messaging_webhooks: string[];
+  script_sites: string[];
+  form_sites: string[];
+  /** distinct_sites, third_party_ratio, credential_sink, messaging_webhook, form_off_script_origin */
+  features: number[];
+  findings: Finding[];
+}
+
+export interface AuthClause {
+  method: string;
+  result: string;
+  props: Record<string, string>;
+  comment: string;
+}
+
+export interface AuthResultsReport {
+  authserv_id: string;
+  /** `"none"` when the method is absent. */
+  spf: string;
+  dkim: string;
+  dmarc: string;
+  header_from: string;
+  clauses: AuthClause[];
+}
+
+export interface DmarcReport {
+  valid: boolean;
+  policy: string;
+  subdomain_policy: string;
+  pct: number;
+  strict_dkim: boolean;
+  strict_spf: boolean;
+  enforcing: boolean;
+}
+
+export interface SpfReport {
+  valid: boolean;
+  all: string;
+  includes: string[];
+  mechanisms: number;
+  permissive: boolean;
+}
+
+export interface MailLink {
+  url: string;
+  host: string;
+  score: number;
+  flags: string[];
+}
+
+export interface MailReport extends Scored {
+  sender_domain: string;
+  brands_claimed: string[];
+  auth: AuthResultsReport | null;
+  links: MailLink[];
+}
+
+/** A critical chat-bot webhook hit (Telegram bot API, Discord/Slack webhooks). */
+export interface Finding {
+  tag: "exfiltration.messaging_webhook";
+  severity: "critical";
+  source: "form_action" | "resource" | "inline_script";
+  service: "telegram" | "discord" | "slack";
+  endpoint: string;
+}
+
+export interface ErrorReport {
+  error: string;
+}
+
+export interface ScoredUrl {
+  url: string;
+  score: number;
+}
+
+export interface IcsReport extends Scored {
+  events: number;
+  organizer: string | null;
+  organizer_domain: string;
+  links: ScoredUrl[];
+}
+
+// ── Attachments and downloads ────────────────────────────────────────────────
+
+/** Returned instead of the full report when the bytes aren't of that type. */
+export interface NotThisKind {
+  score: number;
+  verdict: VerdictName;
+}
+
+export interface PdfScan extends Scored {
+  is_pdf: true;
+  pages: number;
+  uris: ScoredUrl[];
+  js_actions: number;
+  launch_actions: number;
+  open_actions: number;
+  auto_actions: number;
+  submit_forms: number;
+  embedded_files: number;
+  streams: number;
+  undecodable_streams: number;
+}
+export type PdfReport = PdfScan | (NotThisKind & { is_pdf: false });
+
+export interface OfficeScan extends Scored {
+  is_ooxml: true;
+  macros: boolean;
+  macro_content_type: boolean;
+  remote_templates: string[];
+  external_ole: string[];
+  external_links: string[];
+  activex: boolean;
+  embedded_objects: number;
+}
+export type OfficeReport = OfficeScan | (NotThisKind & { is_ooxml: false });
+
+export interface HtmlAttachmentReport extends Scored {
+  kind: "svg" | "html" | "text";
+  scripts: number;
+  event_handlers: number;
+  meta_refresh: string | null;
+  smuggling: boolean;
+  smuggling_apis: string[];
+  auto_submit: boolean;
+  password_fields: number;
+  form_actions: string[];
+  links: string[];
+  findings: Finding[];
+}
+
+export interface ApkScan extends Scored {
+  is_apk: true;
+  package: string;
+  permissions: string[];
+  service_permissions: string[];
+  brands_claimed: string[];
+}
+export type ApkReport = ApkScan | (NotThisKind & { is_apk: boolean; package: null });
+
+export interface ArchiveReport extends Scored {
+  kind: "zip" | "iso" | "rar" | "unknown";
+  encrypted: boolean;
+  encrypted_entries: string[];
+  max_depth: number;
+  risky_entries: string[];
+  password_hint: string | null;
+  password_source: "filename" | "page_text" | null;
+  password_verified: boolean;
+}
+
+export interface FilenameReport extends Scored {
+  filename: string;
+  safe_name: string;
+  displayed_as: string;
+  ext: string;
+  displayed_ext: string;
+  bidi_override: boolean;
+}
+
+export type ContentKind =
+  | "pdf" | "pe" | "elf" | "macho" | "ole2" | "rar" | "7z" | "gzip" | "zip"
+  | "iso" | "disk_image" | "svg" | "html" | "unknown";
+
+export interface DownloadReport extends Scored {
+  filename_score: number;
+  kind: ContentKind;
+  ext_mismatch: boolean;
+  content: PdfReport | OfficeReport | ApkReport | ArchiveReport | HtmlAttachmentReport | null;
+}
+
+export type DownloadCategory =
+  | "executable" | "script" | "mobile_app" | "disk_image" | "macro_document"
+  | "archive" | "document" | "other";
+export type OriginClass = "app_store" | "ip_host" | "opaque" | "risky_host" | "insecure" | "web";
+
+export interface DownloadContextReport {
+  category: DownloadCategory;
+  origin_class: OriginClass;
+  mime_mismatch: boolean;
+  rule: string;
+  verdict: VerdictName;
+  /** Only from `context_evaluate_download`. */
+  report_required?: boolean;
+}
+
+export type EndpointKind = "fetch" | "xhr" | "websocket" | "beacon";
+
+export interface EndpointReport extends Scored {
+  /** Unknown kinds score as "fetch". */
+  kind: EndpointKind;
+  host: string;
+  site: string;
+  port: number | null;
+  /** Endpoint on another site than the initiating page. */
+  third_party: boolean;
+}
+
+export interface CompromisedSiteReport extends Scored {
+  /** "compromised_site" when a popular site's path looks planted. */
+  mode: "compromised_site" | "standard";
+  site: string;
+  /** 0 for sites outside the embedded popularity list. */
+  popularity: number;
+}
+
+export interface RuleTest {
+  /** Canonical form the rule is stored under. */
+  rule: string;
+  wildcard: boolean;
+  host: string;
+  path: string | null;
+  specificity: { labels: number; segments: number };
+  host_match: boolean;
+  path_match: boolean;
+  matches: boolean;
+}
+
+export interface DeepLinkReport extends Scored {
+  kind: "store" | "enterprise_manifest" | null;
+  store: "google_play" | "app_store" | null;
+  /** Play package name or App Store id of the listing. */
+  package: string | null;
+  /** `itms-services` install manifest and its host. */
+  manifest_url: string | null;
+  manifest_host: string | null;
+  /** Download-policy rule an enterprise install fell under. */
+  rule: string | null;
+  /** Brand a Play listing's package impersonates. */
+  brand: string | null;
+  origin_class: OriginClass;
+  /** Only from `context_analyze_deep_link`. */
+  report_required?: boolean;
+}
+
+export interface EmbeddedUrl {
+  url: string;
+  source: "query" | "fragment";
+  /** Parameter key; "" for a whole fragment. */
+  param: string;
+  /** 1 for a URL in the outer one, 2 for one inside that, … */
+  depth: number;
+  host: string;
+  reg_domain: string;
+  /** Leaves the registrable domain of the URL it sits in. */
+  off_site: boolean;
+  /** Null unless `analyze` was set. */
+  risk: number | null;
+  verdict: VerdictName | null;
+}
+
+export interface EmbeddedUrls {
+  url: string;
+  embedded: EmbeddedUrl[];
+  max_risk: number | null;
+  verdict: VerdictName | null;
+}
+
+/** What an `expand_short_url` fetcher reports for one URL. */
+export interface RedirectAnswer {
+  status: number;
+  /** `Location` header of a 3xx answer. */
+  location?: string | null;
+}
+
+export interface RedirectHop {
+  url: string;
+  /** Null for a URL that wasn't answered. */
+  status: number | null;
+  host: string;
+  /** Host is a known link shortener. */
+  shortener: boolean;
+}
+
+export interface ShortExpansionReport extends Scored {
+  url: string;
+  final_url: string;
+  done: boolean;
+  chain: RedirectHop[];
+  /** Redirects followed. */
+  chain_length: number;
+  /** Redirects that changed registrable domain. */
+  cross_domain_hops: number;
+  /** Redirects that left a known shortener. */
+  shortener_hops: number;
+  looped: boolean;
+  /** Stopped at the hop limit. */
+  truncated: boolean;
+  /** Why the fetcher failed, when it did. */
+  error: string | null;
+  /** `[chain_length, cross_domain_hops, shortener_hops, ended_early]`. */
+  features: number[];
+  /** Report on the final URL. */
+  final: UrlReport;
+}
+
+// ── Introspection ────────────────────────────────────────────────────────────
+
+export interface TableSize {
+  entries: number;
+  bytes: number;
+}
+
+export interface ContextMemory {
+  /** Hash-only privacy mode is on. */
+  hashed: boolean;
+  overrides: number;
+  feeds: Record<string, number>;
+  audit: TableSize;
+  hard_negatives: TableSize;
+  state: { feature_cache: TableSize; memo: TableSize; reputation: TableSize; sessions: TableSize; first_seen: TableSize; templates: TableSize };
+}
+
+export interface MemoryStats {
+  dictionaries: Record<string, TableSize>;
+  dictionary_bytes: number;
+  models: Record<string, TableSize>;
+  model_bytes: number;
+  analyzer_bytes: number;
+  caches: { feature_cache: TableSize; memo: TableSize; reputation: TableSize; sessions: TableSize; first_seen: TableSize; templates: TableSize };
+  cache_bytes: number;
+  total_bytes: number;
+  /** Per context handle. */
+  contexts: Record<string, ContextMemory>;
+  /** Null outside a wasm32 build. */
+  linear_memory_bytes: number | null;
+}
+
+export interface NavigationFeatures {
+  navigations_per_minute: number;
+  /** Share of the window's navigations to suspicious TLDs, 0–1. */
+  suspicious_tld_share: number;
+  /** Latest navigations in a row to domains new to the tab. */
+  new_domain_streak: number;
+  redirect_storm: boolean;
+}
+
+export interface Capabilities {
+  engine_version: string;
+  schema_version: number;
+  /** Feature layout a loaded model must have been trained on. */
+  feature_version: number;
+  /** `FeatureSchema.hash` of this build. */
+  feature_schema: string;
+  analyzers: string[];
+  /** Lengths of the model, time, brand-path, keyword, character-class, resource-graph and tab-session feature vectors. */
+  features: { model: number; time: number; brand_path: number; keyword: number; char_class: number; token: number; entropy: number; pronounce: number; homoglyph: number; typosquat: number; numeric_brand: number; dga: number; subdomain: number; encoding: number; malformed: number; extension: number; scheme: number; scheme_risk: number; contact: number; host_signal: number; intent: number; expansion: number; resource_graph: number; session: number };
+  /** Languages with lure phrase packs (ISO 639-1). */
+  languages: string[];
+  /** Locales `render_explanation` has templates for; others fall back to "en". */
+  locales: string[];
+  /** Feature indices `set_feature_mask` currently zeroes. */
+  feature_mask: number[];
+  /** Signal providers registered by the embedding host. */
+  signals: { name: string; features: string[]; weights: number[]; severity: Severity; masked: boolean }[];
+  /** Providers whose values currently read as zero. */
+  signal_mask: string[];
+  /** Brands the default context protects beyond the built-in list (`set_brands` / `add_brands`). */
+  runtime_brands: string[];
+  /** URL reports carry `timings`. */
+  timing: boolean;
+  /** `version` is a content digest; it changes whenever the table does. */
+  dictionaries: Record<string, { entries: number; version: string }>;
+  models: Record<string, { kind: string; entries: number; loaded: boolean }>;
+  analyzer: { profile: ProfileName; custom_brands: number; custom_keywords: number; psl: boolean };
+}
+
+export interface DataManifest {
+  engine_version: string;
+  feature_version: number;
+  /** Build time in seconds since the epoch, from `SOURCE_DATE_EPOCH`; null when the build didn't set it. */
+  built_at: number | null;
+  /** Date the public suffix subset was last checked against publicsuffix.org. */
+  psl_snapshot: string;
+  /** `version` is the same content digest as in `Capabilities.dictionaries`. */
+  tables: { name: string; entries: number; version: string; source: string }[];
+  total_entries: number;
+}
+
+export interface SelfTestReport {
+  ok: boolean;
+  failed: number;
+  checks: { name: string; ok: boolean; detail?: string }[];
+}
+
+/** One generated URL and what this build computes for it. */
+export interface TestVector {
+  url: string;
+  /** Option taken for each generator choice, as `choice:option`. */
+  cases: string[];
+  /** `normalize_url(url)`. */
+  normalized: string;
+  parts: {
+    scheme: string;
+    host: string;
+    path: string;
+    query: string;
+    fragment: string;
+    port: number | null;
+    tld: string;
+    reg_domain: string;
+    subdomain: string;
+  };
+  features: number[];
+  risk: number;
+  verdict: VerdictName;
+  uncertainty: number;
+  decision: DecisionName;
+  flags: string[];
+}
+
+/** Returned by `gen_test_vectors`; the same seed on the same build gives the same file. */
+export interface TestVectors {
+  seed: number;
+  count: number;
+  engine_version: string;
+  feature_version: number;
+  feature_schema_hash: string;
+  vectors: TestVector[];
+}
+
+// ── Typed snapshot reports ───────────────────────────────────────────────────
+
+export interface PageDetails extends Scored {
+  url: string;
+  url_risk: number;
+  form_risk: number;
+  autofill: VerdictName | null;
+  /** ISO 639-1 code the lure phrases were chosen for, or "und". */
+  lang: string;
+  text_risk: number;
+  worst_link: number;
+  /** Graded only on password pages whose policy was supplied. */
+  csp: CspReport | null;
+  findings: Finding[];
+}
+
+export interface DownloadEventDetails extends Scored {
+  filename: string;
+  name: FilenameReport;
+  context: DownloadContextReport;
+  content: DownloadReport | null;
+}
+
+export interface CertDetails extends Scored {
+  host: string;
+  issuer: string;
+  free_dv_issuer: boolean;
+  brands_claimed: string[];
+}
+
+export type EvidenceChannel = "url" | "page" | "cert" | "headers";
+
+export interface CompositeDetails extends Scored {
+  url: string;
+  /** Risk per channel; `null` when it wasn't collected. */
+  channels: Record<EvidenceChannel, number | null>;
+  missing: EvidenceChannel[];
+  /** Share of the channel weight that was collected, 0–1. */
+  completeness: number;
+  page_report: PageDetails | null;
+  cert_report: CertDetails | null;
+  headers: { attachment: FilenameReport | null; refresh: string | null } | null;
+  /** Widens as `completeness` drops. */
+  uncertainty: number;
+  decision: DecisionName;
+}
+
+/** Envelope returned by `analyze_page`, `analyze_download_event`, `analyze_cert`, `analyze_composite` and `display_spoof_risk`. */
+export interface Report<K extends string, D> extends Scored {
+  schema_version: number;
+  kind: K;
+  details: D;
+}
+export type PageReport = Report<"page", PageDetails>;
+export type DownloadEventReport = Report<"download", DownloadEventDetails>;
+export type CertReport = Report<"cert", CertDetails>;
+export type CompositeReport = Report<"composite", CompositeDetails>;
+
+export interface DisplaySpoofDetails extends Scored {
+  host: string;
+  /** The host with punycode labels decoded, as the address bar shows it. */
+  display_host: string;
+  /** `display_host` with confusable letters folded to ASCII. */
+  skeleton: string;
+  registrable_domain: string;
+  lookalike_of: string | null;
+  max_chars: number;
+  /** The first `max_chars` characters of `display_host`. */
+  visible: string;
+}
+
+export type DisplaySpoofReport = Report<"display_spoof", DisplaySpoofDetails>;
@@ -89,6 +89,10 @@ window.loadWasmFeatureExtractor = async () => {
                     return extractFeaturesFallback(url);
                 }
             },
+            // the vector model.onnx was trained on; the fallback above has
+            // neither, since its guesses match no model
+            extract_legacy_features: (url) => Array.from(wasmModule.extract_legacy_features(url)),
+            legacy_feature_version: wasmModule.legacy_feature_version(),
             analyze_form_action: wasmModule.analyze_form_action,
             score_filename: wasmModule.score_filename
         };